@@ -428,7 +428,7 @@ mod tests {
         assert!(matches!(
             res.unwrap_err(),
             StdError::VerificationErr {
-                source: VerificationError::InvalidPubkeyFormat,
+                source: VerificationError::InvalidPubkeyFormat { .. },
                 ..
             }
         ))
@@ -717,7 +717,7 @@ mod tests {
         assert!(matches!(
             res.unwrap_err(),
             StdError::VerificationErr {
-                source: VerificationError::InvalidPubkeyFormat,
+                source: VerificationError::InvalidPubkeyFormat { .. },
                 ..
             }
         ))
@@ -783,7 +783,7 @@ mod tests {
         assert!(matches!(
             res.unwrap_err(),
             StdError::VerificationErr {
-                source: VerificationError::InvalidPubkeyFormat,
+                source: VerificationError::InvalidPubkeyFormat { .. },
                 ..
             }
         ))
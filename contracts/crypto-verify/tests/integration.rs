@@ -483,7 +483,7 @@ fn tendermint_signature_verify_errors() {
     let res = query(&mut deps, mock_env(), verify_msg);
     assert_eq!(
         res.unwrap_err(),
-        "Verification error: Invalid public key format"
+        "Verification error: Invalid public key format (expected 32 bytes, got 0 bytes)"
     )
 }
 
@@ -1,6 +1,6 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Api, DenomMetadata, Deps, DepsMut, Empty, Env, MessageInfo,
-    PageRequest, QueryResponse, Response, StdError, StdResult, WasmMsg,
+    entry_point, to_json_binary, Api, Binary, DenomMetadata, Deps, DepsMut, Empty, Env, Event,
+    MessageInfo, PageRequest, QueryResponse, Response, StdError, StdResult, WasmMsg,
 };
 
 use crate::errors::ContractError;
@@ -32,11 +32,15 @@ pub fn execute(
         } => execute_argon2(mem_cost, time_cost),
         CpuLoop {} => execute_cpu_loop(),
         StorageLoop {} => execute_storage_loop(deps),
+        StorageReads { count } => execute_storage_reads(deps, count),
         MemoryLoop {} => execute_memory_loop(),
         MessageLoop {} => execute_message_loop(env),
+        ManyEvents { count } => execute_many_events(count),
+        Payload { size } => execute_payload(size),
         AllocateLargeMemory { pages } => execute_allocate_large_memory(pages),
         Panic {} => execute_panic(),
         Unreachable {} => execute_unreachable(),
+        Recurse { depth } => execute_recurse(depth),
         MirrorEnv {} => execute_mirror_env(env),
         Debug {} => execute_debug(deps.api),
         Noop {} => execute_noop(),
@@ -83,6 +87,16 @@ fn execute_storage_loop(deps: DepsMut) -> Result<Response, ContractError> {
     }
 }
 
+fn execute_storage_reads(deps: DepsMut, count: u32) -> Result<Response, ContractError> {
+    deps.storage.set(b"test.key", b"x");
+    for _ in 0..count {
+        deps.storage
+            .get(b"test.key")
+            .ok_or_else(|| StdError::not_found("test.key"))?;
+    }
+    Ok(Response::new())
+}
+
 fn execute_memory_loop() -> Result<Response, ContractError> {
     let mut data = vec![1usize];
     loop {
@@ -100,6 +114,15 @@ fn execute_message_loop(env: Env) -> Result<Response, ContractError> {
     Ok(resp)
 }
 
+fn execute_many_events(count: u32) -> Result<Response, ContractError> {
+    let events = (0..count).map(|i| Event::new("cyberpunk").add_attribute("index", i.to_string()));
+    Ok(Response::new().add_events(events))
+}
+
+fn execute_payload(size: u32) -> Result<Response, ContractError> {
+    Ok(Response::new().set_data(payload(size)))
+}
+
 #[allow(unused_variables)]
 fn execute_allocate_large_memory(pages: u32) -> Result<Response, ContractError> {
     // We create memory pages explicitly since Rust's default allocator seems to be clever enough
@@ -147,6 +170,22 @@ fn execute_unreachable() -> Result<Response, ContractError> {
     Err(StdError::generic_err("Unsupported architecture").into())
 }
 
+fn execute_recurse(depth: u32) -> Result<Response, ContractError> {
+    let result = recurse(depth);
+    Ok(Response::new().add_attribute("result", result.to_string()))
+}
+
+/// Calls itself `depth` times. `black_box` keeps the compiler from turning
+/// the recursion into a loop.
+#[inline(never)]
+fn recurse(depth: u32) -> u32 {
+    if depth == 0 {
+        0
+    } else {
+        core::hint::black_box(recurse(depth - 1)) + 1
+    }
+}
+
 fn execute_mirror_env(env: Env) -> Result<Response, ContractError> {
     Ok(Response::new().set_data(to_json_binary(&env)?))
 }
@@ -189,6 +228,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
         MirrorEnv {} => to_json_binary(&query_mirror_env(env)),
         Denoms {} => to_json_binary(&query_denoms(deps)?),
         Denom { denom } => to_json_binary(&query_denom(deps, denom)?),
+        Payload { size } => to_json_binary(&Binary::new(payload(size))),
     }
 }
 
@@ -223,6 +263,10 @@ fn query_denom(deps: Deps, denom: String) -> StdResult<DenomMetadata> {
     deps.querier.query_denom_metadata(denom)
 }
 
+fn payload(size: u32) -> Vec<u8> {
+    vec![b'a'; size as usize]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,10 +13,18 @@ pub enum ExecuteMsg {
     CpuLoop {},
     /// Infinite loop making storage calls (to test when their limit hits)
     StorageLoop {},
+    /// Writes a small value to storage and reads it back the given number of times
+    /// (to test per call allocation limits)
+    StorageReads { count: u32 },
     /// Infinite loop reading and writing memory
     MemoryLoop {},
     /// Infinite loop sending message to itself
     MessageLoop {},
+    /// Returns a response with the given number of events with one attribute each
+    /// (to test response limits)
+    ManyEvents { count: u32 },
+    /// Returns a response with `size` bytes of data (to test result limits)
+    Payload { size: u32 },
     /// Allocate large amounts of memory without consuming much gas
     AllocateLargeMemory { pages: u32 },
     /// Trigger a panic to ensure framework handles gracefully
@@ -26,6 +34,8 @@ pub enum ExecuteMsg {
     /// From <https://doc.rust-lang.org/beta/core/arch/wasm32/fn.unreachable.html>:
     /// "Generates the unreachable instruction, which causes an unconditional trap."
     Unreachable {},
+    /// Recurses the given number of times (to test call depth limits)
+    Recurse { depth: u32 },
     /// Returns the env for testing
     MirrorEnv {},
     /// Does a bit of work and calls debug
@@ -48,4 +58,8 @@ pub enum QueryMsg {
     /// Queries `DenomMetadata` from the bank module and returns the result
     #[returns(cosmwasm_std::DenomMetadata)]
     Denom { denom: String },
+
+    /// Returns `size` bytes of data (to test result limits)
+    #[returns(cosmwasm_std::Binary)]
+    Payload { size: u32 },
 }
@@ -17,9 +17,14 @@
 //!      });
 //! 4. Anywhere you see query(&deps, ...) you must replace it with query(&mut deps, ...)
 
-use cosmwasm_std::{from_json, Empty, Env, Response};
+use cosmwasm_std::{from_json, to_json_vec, Binary, ContractResult, Empty, Env, Response};
 use cosmwasm_vm::testing::{
     execute, instantiate, mock_env, mock_info, mock_instance, mock_instance_with_gas_limit, query,
+    MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_vm::{
+    call_execute, call_execute_raw, call_query, call_query_raw, Instance, ResponseLimits,
+    ResultLimits, VmError,
 };
 use std::io::Write;
 use std::time::SystemTime;
@@ -56,6 +61,280 @@ fn execute_argon2() {
     assert!(gas_used < expected * 120 / 100, "Gas used: {gas_used}");
 }
 
+#[test]
+fn storage_reads_count_allocations() {
+    let mut deps = mock_instance_with_gas_limit(WASM, 100_000_000_000);
+
+    let _res: Response =
+        instantiate(&mut deps, mock_env(), mock_info("admin", &[]), Empty {}).unwrap();
+
+    let msg = ExecuteMsg::StorageReads { count: 5000 };
+    let _res: Response = execute(&mut deps, mock_env(), mock_info("caller", &[]), msg).unwrap();
+    // env, info and msg plus one allocation for every value read
+    assert_eq!(deps.create_gas_report().allocations, 5003);
+
+    // with a low limit, the call fails
+    deps.set_allocation_limit(1000);
+    let msg = to_json_vec(&ExecuteMsg::StorageReads { count: 5000 }).unwrap();
+    let err =
+        call_execute::<_, _, _, Empty>(&mut deps, &mock_env(), &mock_info("caller", &[]), &msg)
+            .unwrap_err();
+    match err {
+        VmError::RuntimeErr { msg, .. } => {
+            assert!(msg.contains("Maximum number of allocations per call exceeded. Limit: 1000"))
+        }
+        err => panic!("Unexpected error: {err:?}"),
+    }
+
+    // small calls still work
+    let msg = ExecuteMsg::StorageReads { count: 10 };
+    let _res: Response = execute(&mut deps, mock_env(), mock_info("caller", &[]), msg).unwrap();
+    assert_eq!(deps.create_gas_report().allocations, 13);
+}
+
+#[test]
+fn memory_growth_is_charged_when_enabled() {
+    const PAGES: u32 = 50;
+    const GAS_PER_PAGE: u64 = 10_000_000;
+
+    let mut deps = mock_instance_with_gas_limit(WASM, 100_000_000_000);
+    let _res: Response =
+        instantiate(&mut deps, mock_env(), mock_info("admin", &[]), Empty {}).unwrap();
+
+    // disabled by default
+    let before = deps.create_gas_report();
+    let msg = ExecuteMsg::AllocateLargeMemory { pages: PAGES };
+    let _res: Response = execute(&mut deps, mock_env(), mock_info("caller", &[]), msg).unwrap();
+    let after = deps.create_gas_report();
+    assert!(after.pages_grown - before.pages_grown >= PAGES as u64);
+    assert_eq!(after.gas_for_memory, 0);
+
+    deps.set_memory_grow_gas_per_page(Some(GAS_PER_PAGE));
+    let before = deps.create_gas_report();
+    let msg = ExecuteMsg::AllocateLargeMemory { pages: PAGES };
+    let _res: Response = execute(&mut deps, mock_env(), mock_info("caller", &[]), msg).unwrap();
+    let after = deps.create_gas_report();
+    let pages_grown = after.pages_grown - before.pages_grown;
+    assert!(pages_grown >= PAGES as u64);
+    assert_eq!(
+        after.gas_for_memory - before.gas_for_memory,
+        pages_grown * GAS_PER_PAGE
+    );
+}
+
+#[test]
+fn response_limits_are_enforced() {
+    let mut deps = mock_instance(WASM, &[]);
+    let _res: Response =
+        instantiate(&mut deps, mock_env(), mock_info("admin", &[]), Empty {}).unwrap();
+
+    // the defaults are generous
+    let msg = ExecuteMsg::ManyEvents { count: 100 };
+    let res: Response = execute(&mut deps, mock_env(), mock_info("caller", &[]), msg).unwrap();
+    assert_eq!(res.events.len(), 100);
+
+    deps.set_response_limits(ResponseLimits {
+        max_events: 50,
+        ..ResponseLimits::default()
+    });
+    let msg = to_json_vec(&ExecuteMsg::ManyEvents { count: 100 }).unwrap();
+    let err =
+        call_execute::<_, _, _, Empty>(&mut deps, &mock_env(), &mock_info("caller", &[]), &msg)
+            .unwrap_err();
+    match err {
+        VmError::ResultLimitExceeded {
+            limit, actual, max, ..
+        } => {
+            assert_eq!(limit, "max_events_per_response");
+            assert_eq!(actual, 100);
+            assert_eq!(max, 50);
+        }
+        err => panic!("Unexpected error: {err:?}"),
+    }
+
+    // responses within the limit still work
+    let msg = ExecuteMsg::ManyEvents { count: 50 };
+    let res: Response = execute(&mut deps, mock_env(), mock_info("caller", &[]), msg).unwrap();
+    assert_eq!(res.events.len(), 50);
+}
+
+#[test]
+fn result_limits_are_enforced() {
+    let mut deps = mock_instance(WASM, &[]);
+    let _res: Response =
+        instantiate(&mut deps, mock_env(), mock_info("admin", &[]), Empty {}).unwrap();
+
+    deps.set_result_limits(ResultLimits {
+        execute: 1000,
+        query: 2000,
+        ..ResultLimits::default()
+    });
+
+    // (entry point, payload size, expected limit or None if the call succeeds)
+    let cases = [
+        ("execute", 500, None),
+        ("execute", 1000, Some(1000)),
+        ("query", 1000, None),
+        ("query", 2000, Some(2000)),
+    ];
+    for (entry_point, size, expected_limit) in cases {
+        let result = match entry_point {
+            "execute" => {
+                let msg = to_json_vec(&ExecuteMsg::Payload { size }).unwrap();
+                call_execute::<_, _, _, Empty>(
+                    &mut deps,
+                    &mock_env(),
+                    &mock_info("caller", &[]),
+                    &msg,
+                )
+                .map(|res| assert_eq!(res.unwrap().data.unwrap().len(), size as usize))
+            }
+            "query" => {
+                let msg = to_json_vec(&QueryMsg::Payload { size }).unwrap();
+                call_query(&mut deps, &mock_env(), &msg).map(|res| {
+                    let data: Binary = from_json(res.unwrap()).unwrap();
+                    assert_eq!(data.len(), size as usize);
+                })
+            }
+            _ => unreachable!(),
+        };
+        match (result, expected_limit) {
+            (Ok(()), None) => {}
+            (
+                Err(VmError::DeserializationLimitExceeded {
+                    length, max_length, ..
+                }),
+                Some(expected_limit),
+            ) => {
+                assert_eq!(max_length, expected_limit, "{entry_point} {size}");
+                assert!(length > max_length, "{entry_point} {size}");
+            }
+            (result, _) => panic!("Unexpected result for {entry_point} {size}: {result:?}"),
+        }
+    }
+}
+
+#[test]
+fn raw_result_limits_are_enforced() {
+    let mut deps = mock_instance(WASM, &[]);
+    let _res: Response =
+        instantiate(&mut deps, mock_env(), mock_info("admin", &[]), Empty {}).unwrap();
+    let env = to_json_vec(&mock_env()).unwrap();
+    let info = to_json_vec(&mock_info("caller", &[])).unwrap();
+
+    // Larger than the default result limit of 256 KiB of the typed calls
+    let large = 300 * 1024;
+
+    // The typed calls reject the large payload with the default limits ...
+    let msg = to_json_vec(&ExecuteMsg::Payload { size: large }).unwrap();
+    let err =
+        call_execute::<_, _, _, Empty>(&mut deps, &mock_env(), &mock_info("caller", &[]), &msg)
+            .unwrap_err();
+    match err {
+        VmError::DeserializationLimitExceeded { max_length, .. } => {
+            assert_eq!(max_length, 256 * 1024)
+        }
+        err => panic!("Unexpected error: {err:?}"),
+    }
+
+    // ... but the raw calls are only limited by the read limit unless raw result limits are set
+    fn run(
+        deps: &mut Instance<MockApi, MockStorage, MockQuerier>,
+        env: &[u8],
+        info: &[u8],
+        entry_point: &str,
+        size: u32,
+    ) -> Result<(), VmError> {
+        match entry_point {
+            "execute" => {
+                let msg = to_json_vec(&ExecuteMsg::Payload { size }).unwrap();
+                call_execute_raw(deps, env, info, &msg).map(|data| {
+                    let result: ContractResult<Response> = from_json(data).unwrap();
+                    assert_eq!(result.unwrap().data.unwrap().len(), size as usize);
+                })
+            }
+            "query" => {
+                let msg = to_json_vec(&QueryMsg::Payload { size }).unwrap();
+                call_query_raw(deps, env, &msg).map(|data| {
+                    let result: ContractResult<Binary> = from_json(data).unwrap();
+                    let payload: Binary = from_json(result.unwrap()).unwrap();
+                    assert_eq!(payload.len(), size as usize);
+                })
+            }
+            _ => unreachable!(),
+        }
+    }
+    for entry_point in ["execute", "query"] {
+        for size in [500, large] {
+            run(&mut deps, &env, &info, entry_point, size).unwrap();
+        }
+    }
+
+    deps.set_raw_result_limits(Some(ResultLimits {
+        execute: 1000,
+        query: 2000,
+        ..ResultLimits::default()
+    }));
+
+    // (entry point, payload size, expected limit or None if the call succeeds)
+    let cases = [
+        ("execute", 500, None),
+        ("execute", 1000, Some(1000)),
+        ("execute", large, Some(1000)),
+        ("query", 1000, None),
+        ("query", 2000, Some(2000)),
+        ("query", large, Some(2000)),
+    ];
+    for (entry_point, size, expected_limit) in cases {
+        match (
+            run(&mut deps, &env, &info, entry_point, size),
+            expected_limit,
+        ) {
+            (Ok(()), None) => {}
+            (
+                Err(VmError::DeserializationLimitExceeded {
+                    length, max_length, ..
+                }),
+                Some(expected_limit),
+            ) => {
+                assert_eq!(max_length, expected_limit, "{entry_point} {size}");
+                assert!(length > max_length, "{entry_point} {size}");
+            }
+            (result, _) => panic!("Unexpected result for {entry_point} {size}: {result:?}"),
+        }
+    }
+}
+
+#[test]
+fn call_depth_limit_is_enforced() {
+    let mut deps = mock_instance(WASM, &[]);
+    let _res: Response =
+        instantiate(&mut deps, mock_env(), mock_info("admin", &[]), Empty {}).unwrap();
+
+    // Leaves room for the entry point and the functions calling `recurse`
+    deps.set_max_call_depth(Some(1_000));
+
+    let msg = ExecuteMsg::Recurse { depth: 500 };
+    let res: Response = execute(&mut deps, mock_env(), mock_info("caller", &[]), msg).unwrap();
+    assert_eq!(res.attributes[0].value, "500");
+
+    let msg = to_json_vec(&ExecuteMsg::Recurse { depth: 1_000 }).unwrap();
+    let err =
+        call_execute::<_, _, _, Empty>(&mut deps, &mock_env(), &mock_info("caller", &[]), &msg)
+            .unwrap_err();
+    match err {
+        VmError::RuntimeErr { msg, .. } => {
+            assert!(msg.contains("Maximum Wasm call depth exceeded"), "{msg}")
+        }
+        err => panic!("Unexpected error: {err:?}"),
+    }
+
+    // the instance is usable again after the error
+    let msg = ExecuteMsg::Recurse { depth: 500 };
+    let res: Response = execute(&mut deps, mock_env(), mock_info("caller", &[]), msg).unwrap();
+    assert_eq!(res.attributes[0].value, "500");
+}
+
 // Test with
 // cargo integration-test debug_works -- --nocapture
 #[test]
@@ -4,8 +4,8 @@ use cosmwasm_std::{
 };
 
 use crate::msg::{
-    CountResponse, ExecuteMsg, InstantiateMsg, ListResponse, MigrateMsg, QueryMsg, ReducerResponse,
-    SumResponse,
+    CountResponse, ExecuteMsg, InstantiateMsg, ListResponse, MigrateMsg, PageResponse, QueryMsg,
+    ReducerResponse, SumResponse,
 };
 use crate::state::Item;
 
@@ -97,6 +97,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
         QueryMsg::Reducer {} => to_json_binary(&query_reducer(deps)?),
         QueryMsg::List {} => to_json_binary(&query_list(deps)),
         QueryMsg::OpenIterators { count } => to_json_binary(&query_open_iterators(deps, count)),
+        QueryMsg::Page { start_after, limit } => {
+            to_json_binary(&query_page(deps, start_after, limit)?)
+        }
     }
 }
 
@@ -175,6 +178,29 @@ fn query_open_iterators(deps: Deps, count: u32) -> Empty {
     Empty::default()
 }
 
+fn query_page(deps: Deps, start_after: Option<u32>, limit: u32) -> StdResult<PageResponse> {
+    // the smallest key greater than `start_after`
+    let start = start_after.map(|key| [key.to_be_bytes().as_slice(), &[0]].concat());
+    let items = deps
+        .storage
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit as usize)
+        .map(|(key, value)| {
+            let key = u32::from_be_bytes(key.try_into().unwrap());
+            Ok((key, from_json::<Item>(&value)?.value))
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    let next_start_after = if items.len() == limit as usize {
+        items.last().map(|(key, _)| *key)
+    } else {
+        None
+    };
+    Ok(PageResponse {
+        items,
+        next_start_after,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -347,6 +373,32 @@ mod tests {
         assert_eq!(ids.late, vec![0x20, 0x21, 0x22, 0x23, 0x24]);
     }
 
+    #[test]
+    fn query_page_works() {
+        let (mut deps, info) = create_contract();
+        for value in [40, 15, 85] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                info.clone(),
+                ExecuteMsg::Enqueue { value },
+            )
+            .unwrap();
+        }
+
+        let page = query_page(deps.as_ref(), None, 2).unwrap();
+        assert_eq!(page.items, vec![(0, 40), (1, 15)]);
+        assert_eq!(page.next_start_after, Some(1));
+
+        let page = query_page(deps.as_ref(), Some(1), 2).unwrap();
+        assert_eq!(page.items, vec![(2, 85)]);
+        assert_eq!(page.next_start_after, None);
+
+        let page = query_page(deps.as_ref(), Some(u32::MAX), 2).unwrap();
+        assert_eq!(page.items, vec![]);
+        assert_eq!(page.next_start_after, None);
+    }
+
     #[test]
     fn query_open_iterators() {
         let (deps, _info) = create_contract();
@@ -32,6 +32,13 @@ pub enum QueryMsg {
     /// Returns and `Empty` response.
     #[returns(cosmwasm_std::Empty)]
     OpenIterators { count: u32 },
+    /// Lists at most `limit` items, starting after the item with key `start_after`.
+    /// Used to test paginated queries over large data sets.
+    #[returns(PageResponse)]
+    Page {
+        start_after: Option<u32>,
+        limit: u32,
+    },
 }
 
 #[cw_serde]
@@ -60,3 +67,11 @@ pub struct ListResponse {
     /// List all IDs starting from 0x20
     pub late: Vec<u32>,
 }
+
+#[cw_serde]
+pub struct PageResponse {
+    /// Pairs of (key, value) for every item in the page
+    pub items: Vec<(u32, i32)>,
+    /// The `start_after` value for the next page. `None` if this is the last page.
+    pub next_start_after: Option<u32>,
+}
@@ -17,18 +17,20 @@
 //!      });
 //! 4. Anywhere you see query(&deps, ...) you must replace it with query(&mut deps, ...)
 
-use cosmwasm_std::{from_json, MessageInfo, Response};
+use std::collections::BTreeSet;
+
+use cosmwasm_std::{from_json, to_json_vec, MessageInfo, Response};
 use cosmwasm_vm::{
     testing::{
         execute, instantiate, migrate, mock_env, mock_info, mock_instance_with_gas_limit, query,
-        MockApi, MockQuerier, MockStorage,
+        query_all_pages, MockApi, MockQuerier, MockStorage,
     },
     Instance,
 };
 
 use queue::msg::{
-    CountResponse, ExecuteMsg, InstantiateMsg, ListResponse, MigrateMsg, QueryMsg, ReducerResponse,
-    SumResponse,
+    CountResponse, ExecuteMsg, InstantiateMsg, ListResponse, MigrateMsg, PageResponse, QueryMsg,
+    ReducerResponse, SumResponse,
 };
 use queue::state::Item;
 
@@ -229,6 +231,58 @@ fn query_list() {
     assert_eq!(ids.late, vec![0x20, 0x21, 0x22, 0x23, 0x24]);
 }
 
+#[test]
+fn query_pages() {
+    const ITEMS: u32 = 1000;
+    const PAGE_SIZE: u32 = 100;
+
+    let mut deps = mock_instance_with_gas_limit(WASM, 100_000_000_000);
+    let _: Response = instantiate(
+        &mut deps,
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {},
+    )
+    .unwrap();
+    deps.with_storage(|storage| {
+        storage.extend((0..ITEMS).map(|key| {
+            let item = Item {
+                value: key as i32 * 2,
+            };
+            (key.to_be_bytes(), to_json_vec(&item).unwrap())
+        }));
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(get_count(&mut deps), ITEMS);
+
+    let mut pages = 0;
+    let items = query_all_pages(
+        &mut deps,
+        mock_env(),
+        |start_after| QueryMsg::Page {
+            start_after,
+            limit: PAGE_SIZE,
+        },
+        |res: PageResponse| {
+            pages += 1;
+            assert!(res.items.len() <= PAGE_SIZE as usize);
+            (res.items, res.next_start_after)
+        },
+    )
+    .unwrap();
+
+    // the last page is empty since the contract cannot know there are no more items
+    assert_eq!(pages, ITEMS / PAGE_SIZE + 1);
+    assert_eq!(items.len(), ITEMS as usize);
+    let keys: BTreeSet<u32> = items.iter().map(|(key, _)| *key).collect();
+    assert_eq!(keys.len(), items.len(), "Found duplicate keys");
+    for (expected_key, (key, value)) in (0..ITEMS).zip(items) {
+        assert_eq!(key, expected_key);
+        assert_eq!(value, key as i32 * 2);
+    }
+}
+
 #[test]
 fn query_open_iterators() {
     let (mut deps, _info) = create_contract();
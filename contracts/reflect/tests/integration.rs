@@ -17,22 +17,27 @@
 //!      });
 //! 4. Anywhere you see query(&deps, ...) you must replace it with query(&mut deps, ...)
 
+use std::cell::RefCell;
+use std::rc::Rc;
+
 use cosmwasm_std::{
-    coin, coins, from_json, BankMsg, BankQuery, Binary, Coin, ContractResult, Event, QueryRequest,
-    Reply, Response, StakingMsg, SubMsg, SubMsgResponse, SubMsgResult, SupplyResponse,
-    SystemResult,
+    coin, coins, from_json, storage_keys::to_length_prefixed, to_json_binary, Addr, AnyMsg,
+    AuthzMsg, BalanceResponse, BankMsg, BankQuery, Binary, Coin, ContractResult, Event,
+    QueryRequest, Reply, Response, StakingMsg, SubMsg, SubMsgResponse, SubMsgResult,
+    SupplyResponse, SystemResult, WasmQuery,
 };
 use cosmwasm_vm::{
     testing::{
-        execute, instantiate, mock_env, mock_info, mock_instance, mock_instance_options, query,
-        reply, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+        authz_msgs, execute, instantiate, mock_env, mock_info, mock_instance,
+        mock_instance_options, query, reply, MockApi, MockQuerier, MockStorage, QueryRouter,
+        RoutingQuerier, MOCK_CONTRACT_ADDR,
     },
-    Backend, Instance,
+    Backend, Instance, Storage,
 };
 
 use reflect::msg::{
     CapitalizedResponse, ChainResponse, CustomMsg, ExecuteMsg, InstantiateMsg, OwnerResponse,
-    QueryMsg, SpecialQuery,
+    QueryMsg, RawResponse, SpecialQuery,
 };
 use reflect::testing::custom_query_execute;
 
@@ -121,6 +126,37 @@ fn reflect() {
     assert_eq!(payload, res.messages);
 }
 
+#[test]
+fn reflect_authz() {
+    let mut deps = mock_instance(WASM, &[]);
+
+    let msg = InstantiateMsg {};
+    let info = mock_info("creator", &[]);
+    let _res: Response<CustomMsg> = instantiate(&mut deps, mock_env(), info, msg).unwrap();
+
+    let exec = AuthzMsg::MsgExec {
+        grantee: MOCK_CONTRACT_ADDR.to_string(),
+        msgs: vec![AnyMsg {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: Binary::new(vec![1, 2, 3]),
+        }],
+    };
+    let payload = vec![
+        BankMsg::Send {
+            to_address: String::from("friend"),
+            amount: coins(1, "token"),
+        }
+        .into(),
+        exec.clone().into(),
+    ];
+    let msg = ExecuteMsg::ReflectMsg { msgs: payload };
+    let info = mock_info("creator", &[]);
+    let res: Response<CustomMsg> = execute(&mut deps, mock_env(), info, msg).unwrap();
+
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(authz_msgs(&res), [exec]);
+}
+
 #[test]
 fn reflect_requires_owner() {
     let mut deps = mock_instance(WASM, &[]);
@@ -231,6 +267,58 @@ fn dispatch_custom_query() {
     assert_eq!(value.text, "DEMO ONE");
 }
 
+#[test]
+fn chain_query_into_other_contract() {
+    let router = QueryRouter::new();
+    let routed_backend = |balances: &[(&str, &[Coin])]| Backend {
+        api: MockApi::default(),
+        storage: MockStorage::default(),
+        querier: RoutingQuerier::new(
+            &router,
+            MockQuerier::new(balances)
+                .with_custom_handler(|query| SystemResult::Ok(custom_query_execute(query))),
+        ),
+    };
+    let (instance_options, memory_limit) = mock_instance_options();
+
+    // only the querier of contract B knows the balances
+    let backend_b = routed_backend(&[("ryan_reynolds", &[coin(5, "ATOM"), coin(10, "OSMO")])]);
+    let contract_b = Instance::from_code(WASM, backend_b, instance_options, memory_limit).unwrap();
+    let mut env_b = mock_env();
+    env_b.contract.address = Addr::unchecked("contract_b");
+    // the router does not keep contract B alive, so we hold on to it for the whole test
+    let contract_b = Rc::new(RefCell::new(contract_b));
+    router.register("contract_b", &contract_b, env_b);
+
+    let backend_a = routed_backend(&[]);
+    let mut contract_a =
+        Instance::from_code(WASM, backend_a, instance_options, memory_limit).unwrap();
+
+    // contract A queries contract B, which queries the bank module
+    let inner = QueryMsg::Chain {
+        request: QueryRequest::Bank(BankQuery::Balance {
+            address: "ryan_reynolds".to_string(),
+            denom: "OSMO".to_string(),
+        }),
+    };
+    let res = query(
+        &mut contract_a,
+        mock_env(),
+        QueryMsg::Chain {
+            request: QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: "contract_b".to_string(),
+                msg: to_json_binary(&inner).unwrap(),
+            }),
+        },
+    )
+    .unwrap();
+
+    let res: ChainResponse = from_json(res).unwrap();
+    let res: ChainResponse = from_json(res.data).unwrap();
+    let res: BalanceResponse = from_json(res.data).unwrap();
+    assert_eq!(res.amount, coin(10, "OSMO"));
+}
+
 #[test]
 fn reflect_subcall() {
     let mut deps = mock_instance(WASM, &[]);
@@ -302,3 +390,123 @@ fn reply_and_query() {
     }
     assert_eq!(result.events, events);
 }
+
+#[test]
+fn raw_query_own_storage() {
+    let (instance_options, memory_limit) = mock_instance_options();
+    let mut deps = Instance::from_code(
+        WASM,
+        mock_dependencies_with_custom_querier(&[]),
+        instance_options,
+        memory_limit,
+    )
+    .unwrap();
+    let _res: Response<CustomMsg> = instantiate(
+        &mut deps,
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {},
+    )
+    .unwrap();
+
+    // let the querier answer raw queries of the contract like the chain would
+    let own_address = mock_env().contract.address.to_string();
+    let config_key = to_length_prefixed(b"config");
+    let config = deps
+        .with_storage(|storage| Ok(storage.get(&config_key).0.unwrap().unwrap()))
+        .unwrap();
+    let expected_address = own_address.clone();
+    let expected_key = config_key.clone();
+    deps.with_querier(|querier| {
+        querier.update_wasm(move |query| match query {
+            WasmQuery::Raw { contract_addr, key } if *contract_addr == expected_address => {
+                let value = if key.as_slice() == expected_key.as_slice() {
+                    config.clone()
+                } else {
+                    vec![]
+                };
+                SystemResult::Ok(ContractResult::Ok(value.into()))
+            }
+            _ => panic!("Unexpected query: {query:?}"),
+        });
+        Ok(())
+    })
+    .unwrap();
+    let query_count = |deps: &mut Instance<_, _, MockQuerier<SpecialQuery>>| {
+        deps.with_querier(|querier| Ok(querier.query_count()))
+            .unwrap()
+    };
+
+    let raw_queries = |deps: &mut Instance<_, _, MockQuerier<SpecialQuery>>| {
+        [config_key.as_slice(), b"unknown".as_slice()].map(|key| {
+            let msg = QueryMsg::Raw {
+                contract: own_address.clone(),
+                key: Binary::from(key),
+            };
+            let res = query(deps, mock_env(), msg).unwrap();
+            from_json::<RawResponse>(res).unwrap().data
+        })
+    };
+
+    // via the querier
+    let via_querier = raw_queries(&mut deps);
+    assert_eq!(query_count(&mut deps), 2);
+    assert!(!via_querier[0].is_empty());
+    assert!(via_querier[1].is_empty());
+
+    // from storage
+    deps.set_contract_address(Some(own_address.clone()));
+    let from_storage = raw_queries(&mut deps);
+    assert_eq!(query_count(&mut deps), 2);
+    assert_eq!(from_storage, via_querier);
+}
+
+#[test]
+fn chain_raw_query_own_storage() {
+    let (instance_options, memory_limit) = mock_instance_options();
+    let mut deps = Instance::from_code(
+        WASM,
+        mock_dependencies_with_custom_querier(&[]),
+        instance_options,
+        memory_limit,
+    )
+    .unwrap();
+    let _res: Response<CustomMsg> = instantiate(
+        &mut deps,
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {},
+    )
+    .unwrap();
+    let own_address = mock_env().contract.address.to_string();
+    deps.set_contract_address(Some(own_address.clone()));
+
+    let config_key = to_length_prefixed(b"config");
+    let config = deps
+        .with_storage(|storage| Ok(storage.get(&config_key).0.unwrap().unwrap()))
+        .unwrap();
+
+    // the contract forwards the raw query to the chain, which is answered from its own storage
+    let msg = QueryMsg::Chain {
+        request: QueryRequest::Wasm(WasmQuery::Raw {
+            contract_addr: own_address,
+            key: config_key.into(),
+        }),
+    };
+    let res = query(&mut deps, mock_env(), msg).unwrap();
+    let res: ChainResponse = from_json(res).unwrap();
+    assert_eq!(res.data.as_slice(), config);
+
+    // other queries still go to the querier
+    let msg = QueryMsg::Chain {
+        request: QueryRequest::Bank(BankQuery::Balance {
+            address: "someone".to_string(),
+            denom: "ATOM".to_string(),
+        }),
+    };
+    query(&mut deps, mock_env(), msg).unwrap();
+    let query_count = deps
+        .with_querier(|querier| Ok(querier.query_count()))
+        .unwrap();
+    assert_eq!(query_count, 1);
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
@@ -10,11 +10,13 @@ use clap::{Arg, ArgAction, Command};
 use colored::Colorize;
 
 use cosmwasm_std::from_json;
-use cosmwasm_vm::internals::{check_wasm, compile, make_compiling_engine, LogOutput, Logger};
-use cosmwasm_vm::{capabilities_from_csv, WasmLimits};
+use cosmwasm_vm::internals::{
+    check_wasm, compile, entry_point_warnings, make_compiling_engine, LogOutput, Logger,
+};
+use cosmwasm_vm::{capabilities_from_csv, required_capabilities, WasmLimits};
 
 const DEFAULT_AVAILABLE_CAPABILITIES: &str =
-    "iterator,staking,stargate,cosmwasm_1_1,cosmwasm_1_2,cosmwasm_1_3,cosmwasm_1_4,cosmwasm_2_0,cosmwasm_2_1,cosmwasm_2_2";
+    "iterator,staking,stargate,cosmwasm_1_1,cosmwasm_1_2,cosmwasm_1_3,cosmwasm_1_4,cosmwasm_2_0,cosmwasm_2_1,cosmwasm_2_2,cosmwasm_2_3";
 
 pub fn main() {
     let matches = Command::new("Contract checking")
@@ -87,7 +89,13 @@ If this is not provided, the default values are used.")
                 &wasm_limits,
             );
             match &result {
-                Ok(_) => println!("{}: {}", p, "pass".green()),
+                Ok(minimal_capabilities) => {
+                    println!("{}: {}", p, "pass".green());
+                    println!(
+                        "    Minimal capabilities: {}",
+                        format_capabilities(minimal_capabilities)
+                    );
+                }
                 Err(e) => {
                     println!("{}: {}", p, "failure".red());
                     println!("{e}");
@@ -127,12 +135,25 @@ fn read_wasm_limits(input: &str) -> anyhow::Result<WasmLimits> {
         })
 }
 
+fn format_capabilities(capabilities: &BTreeSet<String>) -> String {
+    if capabilities.is_empty() {
+        "none".to_string()
+    } else {
+        capabilities
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Checks the contract and returns the minimal set of capabilities it needs
 fn check_contract(
     path: &str,
     available_capabilities: &HashSet<String>,
     verbose: bool,
     wasm_limits: &WasmLimits,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<BTreeSet<String>> {
     let mut file = File::open(path)?;
 
     // Read wasm
@@ -155,6 +176,12 @@ fn check_contract(
     };
     // Check wasm
     check_wasm(&wasm, available_capabilities, wasm_limits, logs)?;
+    if !verbose {
+        // In verbose mode, the warnings are already part of the logs
+        for warning in entry_point_warnings(&wasm)? {
+            eprintln!("{prefix}{}: {warning}", "Warning".yellow());
+        }
+    }
 
     // Compile module
     let start = Instant::now();
@@ -167,5 +194,5 @@ fn check_contract(
         eprintln!("Compile time: {:?}", duration);
     }
 
-    Ok(())
+    Ok(required_capabilities(&wasm)?.into_iter().collect())
 }
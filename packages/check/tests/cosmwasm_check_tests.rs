@@ -17,6 +17,21 @@ fn valid_contract_check() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn contract_check_prints_minimal_capabilities() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("cosmwasm-check")?;
+
+    cmd.arg("../vm/testdata/ibc_reflect.wasm");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("pass"))
+        .stdout(predicate::str::contains(
+            "Minimal capabilities: iterator, stargate",
+        ));
+
+    Ok(())
+}
+
 #[test]
 fn contract_check_verbose() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("cosmwasm-check")?;
@@ -135,3 +150,40 @@ fn wasm_limits_file_check() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn wasm_limits_accepts_response_limits() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("cosmwasm-check")?;
+
+    // response limits are only enforced at runtime, so they don't affect static checks
+    let limits = r#"{"max_events_per_response":1,"max_attributes_per_event":1,"max_submessages_per_response":1,"max_response_data_bytes":1}"#;
+
+    cmd.arg("--wasm-limits")
+        .arg(limits)
+        .arg("../vm/testdata/hackatom.wasm");
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn contract_check_floats() -> Result<(), Box<dyn std::error::Error>> {
+    // floats are allowed by default, but logged in verbose mode
+    let mut cmd = Command::cargo_bin("cosmwasm-check")?;
+    cmd.arg("../vm/testdata/floaty_2.0.wasm").arg("--verbose");
+    cmd.assert().success().stderr(predicate::str::contains(
+        "Warning: Floating-point operations in functions",
+    ));
+
+    let mut cmd = Command::cargo_bin("cosmwasm-check")?;
+    let mut limits = WasmLimits::default();
+    limits.forbid_floats = Some(true);
+    cmd.arg("--wasm-limits")
+        .arg(to_json_string(&limits).unwrap())
+        .arg("../vm/testdata/floaty_2.0.wasm");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "Wasm contract uses floating-point operations",
+    ));
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ use core::fmt::{Debug, Display, Formatter, Result};
 
 /// This wraps an actual backtrace to achieve two things:
 /// - being able to fill this with a stub implementation in `no_std` environments
-/// - being able to use this in conjunction with [`thiserror::Error`]
+/// - being able to store this in error types that implement `Debug` and `Display`
 pub struct BT(Box<dyn Printable + Sync + Send>);
 
 impl BT {
@@ -0,0 +1,15 @@
+mod backtrace;
+mod recover_pubkey_error;
+mod std_error;
+mod verification_error;
+
+pub(crate) use backtrace::{impl_from_err, BT};
+pub use recover_pubkey_error::RecoverPubkeyError;
+pub use std_error::{
+    CheckedFromRatioError, CheckedMultiplyFractionError, CheckedMultiplyRatioError,
+    ConversionOverflowError, DivideByZeroError, DivisionError, OverflowError, OverflowOperation,
+    RoundDownOverflowError, RoundUpOverflowError, StdError, StdResult,
+};
+pub use verification_error::{
+    AggregationError, InputLength, PairingEqualityError, VerificationError,
+};
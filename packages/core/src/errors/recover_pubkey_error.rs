@@ -0,0 +1,110 @@
+use core::fmt::{self, Debug, Display};
+
+use super::verification_error::{format_length, InputLength};
+use super::BT;
+
+#[derive(Debug)]
+pub enum RecoverPubkeyError {
+    /// `length` is set if the hash is known to have the wrong length
+    InvalidHashFormat {
+        length: Option<InputLength>,
+    },
+    /// `length` is set if the signature is known to have the wrong length
+    InvalidSignatureFormat {
+        length: Option<InputLength>,
+    },
+    InvalidRecoveryParam,
+    UnknownErr {
+        error_code: u32,
+        backtrace: BT,
+    },
+}
+
+impl RecoverPubkeyError {
+    pub fn unknown_err(error_code: u32) -> Self {
+        RecoverPubkeyError::UnknownErr {
+            error_code,
+
+            backtrace: BT::capture(),
+        }
+    }
+}
+
+impl Display for RecoverPubkeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecoverPubkeyError::InvalidHashFormat { length } => {
+                write!(f, "Invalid hash format{}", format_length(length))
+            }
+            RecoverPubkeyError::InvalidSignatureFormat { length } => {
+                write!(f, "Invalid signature format{}", format_length(length))
+            }
+            RecoverPubkeyError::InvalidRecoveryParam => {
+                f.write_str("Invalid recovery parameter. Supported values: 0 and 1.")
+            }
+            RecoverPubkeyError::UnknownErr { error_code, .. } => {
+                write!(f, "Unknown error: {error_code}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RecoverPubkeyError {}
+
+impl PartialEq<RecoverPubkeyError> for RecoverPubkeyError {
+    fn eq(&self, rhs: &RecoverPubkeyError) -> bool {
+        match self {
+            RecoverPubkeyError::InvalidHashFormat { length } => {
+                matches!(rhs, RecoverPubkeyError::InvalidHashFormat { length: rhs_length } if rhs_length == length)
+            }
+            RecoverPubkeyError::InvalidSignatureFormat { length } => {
+                matches!(rhs, RecoverPubkeyError::InvalidSignatureFormat { length: rhs_length } if rhs_length == length)
+            }
+            RecoverPubkeyError::InvalidRecoveryParam => {
+                matches!(rhs, RecoverPubkeyError::InvalidRecoveryParam)
+            }
+            RecoverPubkeyError::UnknownErr { error_code, .. } => {
+                if let RecoverPubkeyError::UnknownErr {
+                    error_code: rhs_error_code,
+                    ..
+                } = rhs
+                {
+                    error_code == rhs_error_code
+                } else {
+                    false
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn format_errors_display_input_length() {
+        let length = Some(InputLength {
+            expected: 32,
+            actual: 33,
+        });
+        assert_eq!(
+            RecoverPubkeyError::InvalidHashFormat { length }.to_string(),
+            "Invalid hash format (expected 32 bytes, got 33 bytes)"
+        );
+        let length = Some(InputLength {
+            expected: 64,
+            actual: 65,
+        });
+        assert_eq!(
+            RecoverPubkeyError::InvalidSignatureFormat { length }.to_string(),
+            "Invalid signature format (expected 64 bytes, got 65 bytes)"
+        );
+        assert_eq!(
+            RecoverPubkeyError::InvalidSignatureFormat { length: None }.to_string(),
+            "Invalid signature format"
+        );
+    }
+}
@@ -20,67 +20,125 @@ use crate::errors::{RecoverPubkeyError, VerificationError};
 /// Checklist for adding a new error:
 /// - Add enum case
 /// - Add creator function in std_error_helpers.rs
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug)]
 pub enum StdError {
-    #[error("Verification error: {source}")]
     VerificationErr {
         source: VerificationError,
         backtrace: BT,
     },
-    #[error("Recover pubkey error: {source}")]
     RecoverPubkeyErr {
         source: RecoverPubkeyError,
         backtrace: BT,
     },
     /// Whenever there is no specific error type available
-    #[error("Generic error: {msg}")]
-    GenericErr { msg: String, backtrace: BT },
-    #[error("Invalid Base64 string: {msg}")]
-    InvalidBase64 { msg: String, backtrace: BT },
-    #[error("Invalid data size: expected={expected} actual={actual}")]
+    GenericErr {
+        msg: String,
+        backtrace: BT,
+    },
+    InvalidBase64 {
+        msg: String,
+        backtrace: BT,
+    },
     InvalidDataSize {
         expected: u64,
         actual: u64,
         backtrace: BT,
     },
-    #[error("Invalid hex string: {msg}")]
-    InvalidHex { msg: String, backtrace: BT },
+    InvalidHex {
+        msg: String,
+        backtrace: BT,
+    },
     /// Whenever UTF-8 bytes cannot be decoded into a unicode string, e.g. in String::from_utf8 or str::from_utf8.
-    #[error("Cannot decode UTF8 bytes into string: {msg}")]
-    InvalidUtf8 { msg: String, backtrace: BT },
-    #[error("{kind} not found")]
-    NotFound { kind: String, backtrace: BT },
-    #[error("Error parsing into type {target_type}: {msg}")]
+    InvalidUtf8 {
+        msg: String,
+        backtrace: BT,
+    },
+    NotFound {
+        kind: String,
+        backtrace: BT,
+    },
+    /// Whenever the requested functionality is not (yet) supported
+    NotImplemented {
+        feature: String,
+        backtrace: BT,
+    },
+    /// Whenever the sender is not allowed to perform the requested action
+    Unauthorized {
+        description: String,
+        backtrace: BT,
+    },
     ParseErr {
         /// the target type that was attempted
         target_type: String,
         msg: String,
         backtrace: BT,
     },
-    #[error("Error serializing type {source_type}: {msg}")]
     SerializeErr {
         /// the source type that was attempted
         source_type: String,
         msg: String,
         backtrace: BT,
     },
-    #[error("Overflow: {source}")]
     Overflow {
         source: OverflowError,
         backtrace: BT,
     },
-    #[error("Divide by zero: {source}")]
     DivideByZero {
         source: DivideByZeroError,
         backtrace: BT,
     },
-    #[error("Conversion error: ")]
     ConversionOverflow {
         source: ConversionOverflowError,
         backtrace: BT,
     },
 }
 
+impl fmt::Display for StdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StdError::VerificationErr { source, .. } => write!(f, "Verification error: {source}"),
+            StdError::RecoverPubkeyErr { source, .. } => {
+                write!(f, "Recover pubkey error: {source}")
+            }
+            StdError::GenericErr { msg, .. } => write!(f, "Generic error: {msg}"),
+            StdError::InvalidBase64 { msg, .. } => write!(f, "Invalid Base64 string: {msg}"),
+            StdError::InvalidDataSize {
+                expected, actual, ..
+            } => write!(f, "Invalid data size: expected={expected} actual={actual}"),
+            StdError::InvalidHex { msg, .. } => write!(f, "Invalid hex string: {msg}"),
+            StdError::InvalidUtf8 { msg, .. } => {
+                write!(f, "Cannot decode UTF8 bytes into string: {msg}")
+            }
+            StdError::NotFound { kind, .. } => write!(f, "{kind} not found"),
+            StdError::NotImplemented { feature, .. } => write!(f, "Not implemented: {feature}"),
+            StdError::Unauthorized { description, .. } => write!(f, "Unauthorized: {description}"),
+            StdError::ParseErr {
+                target_type, msg, ..
+            } => write!(f, "Error parsing into type {target_type}: {msg}"),
+            StdError::SerializeErr {
+                source_type, msg, ..
+            } => write!(f, "Error serializing type {source_type}: {msg}"),
+            StdError::Overflow { source, .. } => write!(f, "Overflow: {source}"),
+            StdError::DivideByZero { source, .. } => write!(f, "Divide by zero: {source}"),
+            StdError::ConversionOverflow { .. } => f.write_str("Conversion error: "),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StdError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StdError::VerificationErr { source, .. } => Some(source),
+            StdError::RecoverPubkeyErr { source, .. } => Some(source),
+            StdError::Overflow { source, .. } => Some(source),
+            StdError::DivideByZero { source, .. } => Some(source),
+            StdError::ConversionOverflow { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
 impl_from_err!(
     ConversionOverflowError,
     StdError,
@@ -146,6 +204,20 @@ impl StdError {
         }
     }
 
+    pub fn not_implemented(feature: impl Into<String>) -> Self {
+        StdError::NotImplemented {
+            feature: feature.into(),
+            backtrace: BT::capture(),
+        }
+    }
+
+    pub fn unauthorized(description: impl Into<String>) -> Self {
+        StdError::Unauthorized {
+            description: description.into(),
+            backtrace: BT::capture(),
+        }
+    }
+
     pub fn parse_err(target: impl Into<String>, msg: impl ToString) -> Self {
         StdError::ParseErr {
             target_type: target.into(),
@@ -175,6 +247,16 @@ impl StdError {
             backtrace: BT::capture(),
         }
     }
+
+    /// Returns `true` if this is a [`StdError::NotImplemented`] error
+    pub fn is_not_implemented(&self) -> bool {
+        matches!(self, StdError::NotImplemented { .. })
+    }
+
+    /// Returns `true` if this is a [`StdError::Unauthorized`] error
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, StdError::Unauthorized { .. })
+    }
 }
 
 impl PartialEq<StdError> for StdError {
@@ -279,6 +361,34 @@ impl PartialEq<StdError> for StdError {
                     false
                 }
             }
+            StdError::NotImplemented {
+                feature,
+                backtrace: _,
+            } => {
+                if let StdError::NotImplemented {
+                    feature: rhs_feature,
+                    backtrace: _,
+                } = rhs
+                {
+                    feature == rhs_feature
+                } else {
+                    false
+                }
+            }
+            StdError::Unauthorized {
+                description,
+                backtrace: _,
+            } => {
+                if let StdError::Unauthorized {
+                    description: rhs_description,
+                    backtrace: _,
+                } = rhs
+                {
+                    description == rhs_description
+                } else {
+                    false
+                }
+            }
             StdError::ParseErr {
                 target_type,
                 msg,
@@ -393,6 +503,16 @@ impl From<DivideByZeroError> for StdError {
     }
 }
 
+impl From<CheckedMultiplyFractionError> for StdError {
+    fn from(source: CheckedMultiplyFractionError) -> Self {
+        match source {
+            CheckedMultiplyFractionError::DivideByZero(err) => err.into(),
+            CheckedMultiplyFractionError::ConversionOverflow(err) => err.into(),
+            CheckedMultiplyFractionError::Overflow(err) => err.into(),
+        }
+    }
+}
+
 /// The return type for init, execute and query. Since the error type cannot be serialized to JSON,
 /// this is only available within the contract and its unit tests.
 ///
@@ -416,8 +536,7 @@ impl fmt::Display for OverflowOperation {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-#[error("Cannot {operation} with given operands")]
+#[derive(Debug, PartialEq, Eq)]
 pub struct OverflowError {
     pub operation: OverflowOperation,
 }
@@ -428,14 +547,22 @@ impl OverflowError {
     }
 }
 
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cannot {} with given operands", self.operation)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OverflowError {}
+
 /// The error returned by [`TryFrom`] conversions that overflow, for example
 /// when converting from [`Uint256`] to [`Uint128`].
 ///
 /// [`TryFrom`]: core::convert::TryFrom
 /// [`Uint256`]: crate::Uint256
 /// [`Uint128`]: crate::Uint128
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-#[error("Error converting {source_type} to {target_type}")]
+#[derive(Debug, PartialEq, Eq)]
 pub struct ConversionOverflowError {
     pub source_type: &'static str,
     pub target_type: &'static str,
@@ -450,8 +577,20 @@ impl ConversionOverflowError {
     }
 }
 
-#[derive(Debug, Default, PartialEq, Eq, thiserror::Error)]
-#[error("Cannot divide by zero")]
+impl fmt::Display for ConversionOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Error converting {} to {}",
+            self.source_type, self.target_type
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConversionOverflowError {}
+
+#[derive(Debug, Default, PartialEq, Eq)]
 pub struct DivideByZeroError;
 
 impl DivideByZeroError {
@@ -460,90 +599,143 @@ impl DivideByZeroError {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+impl fmt::Display for DivideByZeroError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Cannot divide by zero")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DivideByZeroError {}
+
+#[derive(Debug, PartialEq, Eq)]
 pub enum DivisionError {
-    #[error("Divide by zero")]
     DivideByZero,
-
-    #[error("Overflow in division")]
     Overflow,
 }
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+impl fmt::Display for DivisionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DivisionError::DivideByZero => f.write_str("Divide by zero"),
+            DivisionError::Overflow => f.write_str("Overflow in division"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DivisionError {}
+
+#[derive(Debug, PartialEq, Eq)]
 pub enum CheckedMultiplyFractionError {
-    #[error("{_0}")]
-    DivideByZero(#[from] DivideByZeroError),
+    DivideByZero(DivideByZeroError),
+    ConversionOverflow(ConversionOverflowError),
+    Overflow(OverflowError),
+}
 
-    #[error("{_0}")]
-    ConversionOverflow(#[from] ConversionOverflowError),
+impl fmt::Display for CheckedMultiplyFractionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckedMultiplyFractionError::DivideByZero(err) => fmt::Display::fmt(err, f),
+            CheckedMultiplyFractionError::ConversionOverflow(err) => fmt::Display::fmt(err, f),
+            CheckedMultiplyFractionError::Overflow(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
 
-    #[error("{_0}")]
-    Overflow(#[from] OverflowError),
+#[cfg(feature = "std")]
+impl std::error::Error for CheckedMultiplyFractionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CheckedMultiplyFractionError::DivideByZero(err) => Some(err),
+            CheckedMultiplyFractionError::ConversionOverflow(err) => Some(err),
+            CheckedMultiplyFractionError::Overflow(err) => Some(err),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-pub enum CheckedMultiplyRatioError {
-    #[error("Denominator must not be zero")]
-    DivideByZero,
+impl From<DivideByZeroError> for CheckedMultiplyFractionError {
+    fn from(source: DivideByZeroError) -> Self {
+        Self::DivideByZero(source)
+    }
+}
 
-    #[error("Multiplication overflow")]
-    Overflow,
+impl From<ConversionOverflowError> for CheckedMultiplyFractionError {
+    fn from(source: ConversionOverflowError) -> Self {
+        Self::ConversionOverflow(source)
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-pub enum CheckedFromRatioError {
-    #[error("Denominator must not be zero")]
-    DivideByZero,
+impl From<OverflowError> for CheckedMultiplyFractionError {
+    fn from(source: OverflowError) -> Self {
+        Self::Overflow(source)
+    }
+}
 
-    #[error("Overflow")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum CheckedMultiplyRatioError {
+    DivideByZero,
     Overflow,
 }
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-#[error("Round up operation failed because of overflow")]
-pub struct RoundUpOverflowError;
+impl fmt::Display for CheckedMultiplyRatioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckedMultiplyRatioError::DivideByZero => f.write_str("Denominator must not be zero"),
+            CheckedMultiplyRatioError::Overflow => f.write_str("Multiplication overflow"),
+        }
+    }
+}
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-#[error("Round down operation failed because of overflow")]
-pub struct RoundDownOverflowError;
+#[cfg(feature = "std")]
+impl std::error::Error for CheckedMultiplyRatioError {}
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-pub enum CoinsError {
-    #[error("Duplicate denom")]
-    DuplicateDenom,
+#[derive(Debug, PartialEq, Eq)]
+pub enum CheckedFromRatioError {
+    DivideByZero,
+    Overflow,
 }
 
-impl From<CoinsError> for StdError {
-    fn from(value: CoinsError) -> Self {
-        Self::generic_err(format!("Creating Coins: {value}"))
+impl fmt::Display for CheckedFromRatioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckedFromRatioError::DivideByZero => f.write_str("Denominator must not be zero"),
+            CheckedFromRatioError::Overflow => f.write_str("Overflow"),
+        }
     }
 }
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-pub enum CoinFromStrError {
-    #[error("Missing denominator")]
-    MissingDenom,
-    #[error("Missing amount or non-digit characters in amount")]
-    MissingAmount,
-    #[error("Invalid amount: {_0}")]
-    InvalidAmount(core::num::ParseIntError),
-}
+#[cfg(feature = "std")]
+impl std::error::Error for CheckedFromRatioError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RoundUpOverflowError;
 
-impl From<core::num::ParseIntError> for CoinFromStrError {
-    fn from(value: core::num::ParseIntError) -> Self {
-        Self::InvalidAmount(value)
+impl fmt::Display for RoundUpOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Round up operation failed because of overflow")
     }
 }
 
-impl From<CoinFromStrError> for StdError {
-    fn from(value: CoinFromStrError) -> Self {
-        Self::generic_err(format!("Parsing Coin: {value}"))
+#[cfg(feature = "std")]
+impl std::error::Error for RoundUpOverflowError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RoundDownOverflowError;
+
+impl fmt::Display for RoundDownOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Round down operation failed because of overflow")
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for RoundDownOverflowError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec::Vec;
     use core::str;
 
     // constructors
@@ -663,6 +855,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn not_implemented_works() {
+        let error = StdError::not_implemented("ibc");
+        match &error {
+            StdError::NotImplemented { feature, .. } => assert_eq!(feature, "ibc"),
+            _ => panic!("expect different error"),
+        }
+        assert_eq!(error.to_string(), "Not implemented: ibc");
+        assert!(error.is_not_implemented());
+        assert!(!error.is_unauthorized());
+        assert_eq!(error, StdError::not_implemented(String::from("ibc")));
+        assert_ne!(error, StdError::not_implemented("sudo"));
+    }
+
+    #[test]
+    fn unauthorized_works() {
+        let error = StdError::unauthorized("only the admin can update the config");
+        match &error {
+            StdError::Unauthorized { description, .. } => {
+                assert_eq!(description, "only the admin can update the config")
+            }
+            _ => panic!("expect different error"),
+        }
+        assert_eq!(
+            error.to_string(),
+            "Unauthorized: only the admin can update the config"
+        );
+        assert!(error.is_unauthorized());
+        assert!(!error.is_not_implemented());
+        assert!(!StdError::generic_err("unauthorized").is_unauthorized());
+        assert_ne!(error, StdError::unauthorized("not the owner"));
+    }
+
     #[test]
     fn parse_err_works() {
         let error = StdError::parse_err("Book", "Missing field: title");
@@ -0,0 +1,267 @@
+use alloc::string::String;
+use core::fmt::{self, Debug, Display};
+
+use super::BT;
+
+#[derive(Debug, PartialEq)]
+pub enum AggregationError {
+    Empty,
+    NotMultiple,
+}
+
+impl Display for AggregationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregationError::Empty => f.write_str("List of points is empty"),
+            AggregationError::NotMultiple => f.write_str("List is not an expected multiple"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AggregationError {}
+
+#[derive(Debug, PartialEq)]
+pub enum PairingEqualityError {
+    NotMultipleG1,
+    NotMultipleG2,
+    UnequalPointAmount,
+}
+
+impl Display for PairingEqualityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PairingEqualityError::NotMultipleG1 => f.write_str("List is not a multiple of 48"),
+            PairingEqualityError::NotMultipleG2 => f.write_str("List is not a multiple of 96"),
+            PairingEqualityError::UnequalPointAmount => {
+                f.write_str("Not the same amount of points passed")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PairingEqualityError {}
+
+/// The expected and actual length of a malformed input in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputLength {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl Display for InputLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} bytes, got {} bytes",
+            self.expected, self.actual
+        )
+    }
+}
+
+pub(super) fn format_length(length: &Option<InputLength>) -> String {
+    length.map(|l| format!(" ({l})")).unwrap_or_default()
+}
+
+#[derive(Debug)]
+pub enum VerificationError {
+    Aggregation {
+        source: AggregationError,
+    },
+    BatchErr,
+    GenericErr,
+    /// `length` is set if the hash is known to have the wrong length
+    InvalidHashFormat {
+        length: Option<InputLength>,
+    },
+    /// `length` is set if the signature is known to have the wrong length
+    InvalidSignatureFormat {
+        length: Option<InputLength>,
+    },
+    /// `length` is set if the public key is known to have the wrong length for its encoding
+    InvalidPubkeyFormat {
+        length: Option<InputLength>,
+    },
+    InvalidRecoveryParam,
+    InvalidPoint,
+    UnknownHashFunction,
+    PairingEquality {
+        source: PairingEqualityError,
+    },
+    UnknownErr {
+        error_code: u32,
+        backtrace: BT,
+    },
+}
+
+impl VerificationError {
+    pub fn unknown_err(error_code: u32) -> Self {
+        VerificationError::UnknownErr {
+            error_code,
+
+            backtrace: BT::capture(),
+        }
+    }
+}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationError::Aggregation { source } => write!(f, "Aggregation error: {source}"),
+            VerificationError::BatchErr => f.write_str("Batch error"),
+            VerificationError::GenericErr => f.write_str("Generic error"),
+            VerificationError::InvalidHashFormat { length } => {
+                write!(f, "Invalid hash format{}", format_length(length))
+            }
+            VerificationError::InvalidSignatureFormat { length } => {
+                write!(f, "Invalid signature format{}", format_length(length))
+            }
+            VerificationError::InvalidPubkeyFormat { length } => {
+                write!(f, "Invalid public key format{}", format_length(length))
+            }
+            VerificationError::InvalidRecoveryParam => {
+                f.write_str("Invalid recovery parameter. Supported values: 0 and 1.")
+            }
+            VerificationError::InvalidPoint => f.write_str("Invalid point"),
+            VerificationError::UnknownHashFunction => f.write_str("Unknown hash function"),
+            VerificationError::PairingEquality { source } => {
+                write!(f, "Aggregation pairing equality error: {source}")
+            }
+            VerificationError::UnknownErr { error_code, .. } => {
+                write!(f, "Unknown error: {error_code}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerificationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            VerificationError::Aggregation { source } => Some(source),
+            VerificationError::PairingEquality { source } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq<VerificationError> for VerificationError {
+    fn eq(&self, rhs: &VerificationError) -> bool {
+        match self {
+            VerificationError::Aggregation { source: lhs_source } => {
+                matches!(rhs, VerificationError::Aggregation { source: rhs_source } if rhs_source == lhs_source)
+            }
+            VerificationError::PairingEquality { source: lhs_source } => {
+                matches!(rhs, VerificationError::PairingEquality { source: rhs_source } if rhs_source == lhs_source)
+            }
+            VerificationError::BatchErr => matches!(rhs, VerificationError::BatchErr),
+            VerificationError::GenericErr => matches!(rhs, VerificationError::GenericErr),
+            VerificationError::InvalidHashFormat { length } => {
+                matches!(rhs, VerificationError::InvalidHashFormat { length: rhs_length } if rhs_length == length)
+            }
+            VerificationError::InvalidPubkeyFormat { length } => {
+                matches!(rhs, VerificationError::InvalidPubkeyFormat { length: rhs_length } if rhs_length == length)
+            }
+            VerificationError::InvalidSignatureFormat { length } => {
+                matches!(rhs, VerificationError::InvalidSignatureFormat { length: rhs_length } if rhs_length == length)
+            }
+            VerificationError::InvalidRecoveryParam => {
+                matches!(rhs, VerificationError::InvalidRecoveryParam)
+            }
+            VerificationError::InvalidPoint => matches!(rhs, VerificationError::InvalidPoint),
+            VerificationError::UnknownHashFunction => {
+                matches!(rhs, VerificationError::UnknownHashFunction)
+            }
+            VerificationError::UnknownErr { error_code, .. } => {
+                if let VerificationError::UnknownErr {
+                    error_code: rhs_error_code,
+                    ..
+                } = rhs
+                {
+                    error_code == rhs_error_code
+                } else {
+                    false
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    // constructors
+    #[test]
+    fn unknown_err_works() {
+        let error = VerificationError::unknown_err(123);
+        match error {
+            VerificationError::UnknownErr { error_code, .. } => assert_eq!(error_code, 123),
+            _ => panic!("wrong error type!"),
+        }
+    }
+
+    #[test]
+    fn format_errors_display_input_length() {
+        let length = Some(InputLength {
+            expected: 32,
+            actual: 31,
+        });
+        assert_eq!(
+            VerificationError::InvalidHashFormat { length }.to_string(),
+            "Invalid hash format (expected 32 bytes, got 31 bytes)"
+        );
+        assert_eq!(
+            VerificationError::InvalidHashFormat { length: None }.to_string(),
+            "Invalid hash format"
+        );
+
+        let length = Some(InputLength {
+            expected: 64,
+            actual: 0,
+        });
+        assert_eq!(
+            VerificationError::InvalidSignatureFormat { length }.to_string(),
+            "Invalid signature format (expected 64 bytes, got 0 bytes)"
+        );
+        assert_eq!(
+            VerificationError::InvalidSignatureFormat { length: None }.to_string(),
+            "Invalid signature format"
+        );
+
+        let length = Some(InputLength {
+            expected: 33,
+            actual: 34,
+        });
+        assert_eq!(
+            VerificationError::InvalidPubkeyFormat { length }.to_string(),
+            "Invalid public key format (expected 33 bytes, got 34 bytes)"
+        );
+        assert_eq!(
+            VerificationError::InvalidPubkeyFormat { length: None }.to_string(),
+            "Invalid public key format"
+        );
+    }
+
+    #[test]
+    fn format_errors_compare_input_length() {
+        let length = Some(InputLength {
+            expected: 32,
+            actual: 31,
+        });
+        assert_eq!(
+            VerificationError::InvalidHashFormat { length },
+            VerificationError::InvalidHashFormat { length }
+        );
+        assert_ne!(
+            VerificationError::InvalidHashFormat { length },
+            VerificationError::InvalidHashFormat { length: None }
+        );
+        assert_ne!(
+            VerificationError::InvalidHashFormat { length },
+            VerificationError::InvalidSignatureFormat { length }
+        );
+    }
+}
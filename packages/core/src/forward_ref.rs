@@ -31,6 +31,7 @@ macro_rules! __internal__forward_ref_partial_eq {
 /// based on "T op U" where T and U are expected to be `Copy`able
 ///
 /// Copied from `libcore`
+#[cfg(feature = "math")]
 macro_rules! forward_ref_binop {
     (impl $imp:ident, $method:ident for $t:ty, $u:ty) => {
         impl<'a> $imp<$u> for &'a $t {
@@ -69,6 +70,7 @@ macro_rules! forward_ref_binop {
 /// where U is expected to be `Copy`able
 ///
 /// Copied from `libcore`
+#[cfg(feature = "math")]
 macro_rules! forward_ref_op_assign {
     (impl $imp:ident, $method:ident for $t:ty, $u:ty) => {
         impl $imp<&$u> for $t {
@@ -81,4 +83,5 @@ macro_rules! forward_ref_op_assign {
     };
 }
 
+#[cfg(feature = "math")]
 pub(crate) use {forward_ref_binop, forward_ref_op_assign};
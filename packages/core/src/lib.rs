@@ -1,8 +1,29 @@
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[macro_use]
+extern crate alloc;
+
+#[doc(hidden)]
+pub mod __internal;
 mod crypto;
+mod errors;
+mod forward_ref;
+#[cfg(feature = "math")]
+mod math;
 
 #[doc(hidden)]
 pub use self::crypto::{
     BLS12_381_G1_GENERATOR, BLS12_381_G1_POINT_LEN, BLS12_381_G2_GENERATOR, BLS12_381_G2_POINT_LEN,
 };
+pub use self::errors::{
+    AggregationError, CheckedFromRatioError, CheckedMultiplyFractionError,
+    CheckedMultiplyRatioError, ConversionOverflowError, DivideByZeroError, DivisionError,
+    InputLength, OverflowError, OverflowOperation, PairingEqualityError, RecoverPubkeyError,
+    RoundDownOverflowError, RoundUpOverflowError, StdError, StdResult, VerificationError,
+};
+#[cfg(feature = "math")]
+pub use self::math::{
+    Decimal, Decimal256, Decimal256RangeExceeded, DecimalRangeExceeded, Fraction, Int128, Int256,
+    Int512, Int64, Isqrt, SignedDecimal, SignedDecimal256, SignedDecimal256RangeExceeded,
+    SignedDecimalRangeExceeded, Uint128, Uint256, Uint512, Uint64,
+};
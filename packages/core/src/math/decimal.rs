@@ -3,6 +3,7 @@ use core::cmp::Ordering;
 use core::fmt::{self, Write};
 use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign};
 use core::str::FromStr;
+#[cfg(feature = "serde")]
 use serde::{de, ser, Deserialize, Deserializer, Serialize};
 
 use crate::errors::{
@@ -10,8 +11,9 @@ use crate::errors::{
     OverflowOperation, RoundUpOverflowError, StdError,
 };
 use crate::forward_ref::{forward_ref_binop, forward_ref_op_assign};
-use crate::{Decimal256, SignedDecimal, SignedDecimal256, __internal::forward_ref_partial_eq};
+use crate::{__internal::forward_ref_partial_eq, Decimal256, SignedDecimal, SignedDecimal256};
 
+use super::scientific::parse_scientific;
 use super::Fraction;
 use super::Isqrt;
 use super::{Uint128, Uint256};
@@ -19,15 +21,24 @@ use super::{Uint128, Uint256};
 /// A fixed-point decimal value with 18 fractional digits, i.e. Decimal(1_000_000_000_000_000_000) == 1.0
 ///
 /// The greatest possible value that can be represented is 340282366920938463463.374607431768211455 (which is (2^128 - 1) / 10^18)
-#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct Decimal(#[schemars(with = "String")] Uint128);
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Decimal(#[cfg_attr(feature = "schemars", schemars(with = "String"))] Uint128);
 
 forward_ref_partial_eq!(Decimal, Decimal);
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-#[error("Decimal range exceeded")]
+#[derive(Debug, PartialEq, Eq)]
 pub struct DecimalRangeExceeded;
 
+impl fmt::Display for DecimalRangeExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Decimal range exceeded")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecimalRangeExceeded {}
+
 impl Decimal {
     const DECIMAL_FRACTIONAL: Uint128 = Uint128::new(1_000_000_000_000_000_000u128); // 1*10**18
     const DECIMAL_FRACTIONAL_SQUARED: Uint128 =
@@ -71,7 +82,7 @@ impl Decimal {
     ///
     /// ```
     /// # use std::str::FromStr;
-    /// # use cosmwasm_std::Decimal;
+    /// # use cosmwasm_core::Decimal;
     /// const HALF: Decimal = Decimal::percent(50);
     ///
     /// assert_eq!(HALF, Decimal::from_str("0.5").unwrap());
@@ -88,7 +99,7 @@ impl Decimal {
     ///
     /// ```
     /// # use std::str::FromStr;
-    /// # use cosmwasm_std::Decimal;
+    /// # use cosmwasm_core::Decimal;
     /// const HALF: Decimal = Decimal::permille(500);
     ///
     /// assert_eq!(HALF, Decimal::from_str("0.5").unwrap());
@@ -105,7 +116,7 @@ impl Decimal {
     ///
     /// ```
     /// # use std::str::FromStr;
-    /// # use cosmwasm_std::Decimal;
+    /// # use cosmwasm_core::Decimal;
     /// const TWO_BPS: Decimal = Decimal::bps(2);
     /// const HALF: Decimal = Decimal::bps(5000);
     ///
@@ -129,7 +140,7 @@ impl Decimal {
     /// ## Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{Decimal, Uint128};
+    /// # use cosmwasm_core::{Decimal, Uint128};
     /// let a = Decimal::from_atomics(Uint128::new(1234), 3).unwrap();
     /// assert_eq!(a.to_string(), "1.234");
     ///
@@ -170,7 +181,66 @@ impl Decimal {
         })
     }
 
-    /// Returns the ratio (numerator / denominator) as a Decimal
+    /// Creates a Decimal from a string in decimal or scientific notation,
+    /// e.g. "1.5", "1.5e-3", "15E-4" or "+0.15e-2".
+    ///
+    /// The input consists of an optional `+` sign, a whole part, an optional fractional
+    /// part and an optional exponent introduced by `e` or `E`.
+    ///
+    /// Unlike [`FromStr`], this accepts values with more than
+    /// [`DECIMAL_PLACES`](Self::DECIMAL_PLACES) fractional digits as long as the
+    /// additional digits are zero. Values that cannot be represented exactly result in an error.
+    /// Use [`Decimal::from_scientific_truncated`] to round towards zero instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::Decimal;
+    /// let a = Decimal::from_scientific("1.5e-3").unwrap();
+    /// assert_eq!(a.to_string(), "0.0015");
+    ///
+    /// let a = Decimal::from_scientific("1e18").unwrap();
+    /// assert_eq!(a.to_string(), "1000000000000000000");
+    ///
+    /// // smaller than the smallest positive value
+    /// assert!(Decimal::from_scientific("1e-19").is_err());
+    /// ```
+    pub fn from_scientific(input: &str) -> Result<Self, StdError> {
+        Self::parse_scientific(input, false)
+    }
+
+    /// Like [`Decimal::from_scientific`] but rounds towards zero if the value
+    /// has more than [`DECIMAL_PLACES`](Self::DECIMAL_PLACES) fractional digits.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::Decimal;
+    /// let a = Decimal::from_scientific_truncated("1.9e-18").unwrap();
+    /// assert_eq!(a, Decimal::raw(1));
+    ///
+    /// let a = Decimal::from_scientific_truncated("1e-19").unwrap();
+    /// assert_eq!(a, Decimal::zero());
+    /// ```
+    pub fn from_scientific_truncated(input: &str) -> Result<Self, StdError> {
+        Self::parse_scientific(input, true)
+    }
+
+    fn parse_scientific(input: &str, truncate: bool) -> Result<Self, StdError> {
+        let atomics = parse_scientific(input, Self::DECIMAL_PLACES, false, truncate)
+            .map_err(|err| err.into_std_error(DecimalRangeExceeded, Self::DECIMAL_PLACES))?;
+        // parse_scientific limits the number of digits but not the exact range of the type
+        atomics
+            .digits
+            .parse::<Uint128>()
+            .map(Self)
+            .map_err(|_| StdError::generic_err(DecimalRangeExceeded.to_string()))
+    }
+
+    /// Returns the ratio (numerator / denominator) as a Decimal.
+    ///
+    /// The result is rounded down to the nearest representable value.
+    /// See [`Decimal::from_ratio_ceil`] for rounding up.
     pub fn from_ratio(numerator: impl Into<Uint128>, denominator: impl Into<Uint128>) -> Self {
         match Decimal::checked_from_ratio(numerator, denominator) {
             Ok(value) => value,
@@ -181,7 +251,10 @@ impl Decimal {
         }
     }
 
-    /// Returns the ratio (numerator / denominator) as a Decimal
+    /// Returns the ratio (numerator / denominator) as a Decimal.
+    ///
+    /// The result is rounded down to the nearest representable value.
+    /// See [`Decimal::checked_from_ratio_ceil`] for rounding up.
     pub fn checked_from_ratio(
         numerator: impl Into<Uint128>,
         denominator: impl Into<Uint128>,
@@ -200,6 +273,77 @@ impl Decimal {
         }
     }
 
+    /// Returns the ratio (numerator / denominator) as a Decimal, rounded down.
+    ///
+    /// This is the same as [`Decimal::from_ratio`] but makes the rounding direction explicit.
+    pub fn from_ratio_floor(
+        numerator: impl Into<Uint128>,
+        denominator: impl Into<Uint128>,
+    ) -> Self {
+        Self::from_ratio(numerator, denominator)
+    }
+
+    /// Returns the ratio (numerator / denominator) as a Decimal, rounded up.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::Decimal;
+    /// assert_eq!(Decimal::from_ratio_floor(2u128, 3u128).to_string(), "0.666666666666666666");
+    /// assert_eq!(Decimal::from_ratio_ceil(2u128, 3u128).to_string(), "0.666666666666666667");
+    /// ```
+    pub fn from_ratio_ceil(numerator: impl Into<Uint128>, denominator: impl Into<Uint128>) -> Self {
+        match Self::checked_from_ratio_ceil(numerator, denominator) {
+            Ok(value) => value,
+            Err(CheckedFromRatioError::DivideByZero) => {
+                panic!("Denominator must not be zero")
+            }
+            Err(CheckedFromRatioError::Overflow) => panic!("Multiplication overflow"),
+        }
+    }
+
+    /// Returns the ratio (numerator / denominator) as a Decimal, rounded up.
+    pub fn checked_from_ratio_ceil(
+        numerator: impl Into<Uint128>,
+        denominator: impl Into<Uint128>,
+    ) -> Result<Self, CheckedFromRatioError> {
+        let numerator: Uint128 = numerator.into();
+        let denominator: Uint128 = denominator.into();
+        if denominator.is_zero() {
+            return Err(CheckedFromRatioError::DivideByZero);
+        }
+
+        let dividend = numerator.full_mul(Self::DECIMAL_FRACTIONAL);
+        let divisor = Uint256::from(denominator);
+        let mut atomics = dividend / divisor;
+        if !(dividend % divisor).is_zero() {
+            atomics += Uint256::one();
+        }
+        atomics
+            .try_into()
+            .map(Self)
+            .map_err(|_| CheckedFromRatioError::Overflow)
+    }
+
+    /// Returns the exact value as a fraction `(numerator, denominator)` in lowest terms.
+    ///
+    /// Passing the result to [`Decimal::from_ratio`] returns the original value.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::{Decimal, Uint128};
+    /// # use core::str::FromStr;
+    /// let value = Decimal::from_str("0.75").unwrap();
+    /// assert_eq!(value.to_fraction(), (Uint128::from(3u32), Uint128::from(4u32)));
+    /// assert_eq!(Decimal::from_ratio(3u128, 4u128), value);
+    /// ```
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn to_fraction(&self) -> (Uint128, Uint128) {
+        let gcd = self.0.gcd(Self::DECIMAL_FRACTIONAL);
+        (self.0 / gcd, Self::DECIMAL_FRACTIONAL / gcd)
+    }
+
     #[must_use]
     pub const fn is_zero(&self) -> bool {
         self.0.is_zero()
@@ -211,7 +355,7 @@ impl Decimal {
     /// ## Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{Decimal, Uint128};
+    /// # use cosmwasm_core::{Decimal, Uint128};
     /// # use core::str::FromStr;
     /// // Value with whole and fractional part
     /// let a = Decimal::from_str("1.234").unwrap();
@@ -415,7 +559,7 @@ impl Decimal {
     ///
     /// ```
     /// use core::str::FromStr;
-    /// use cosmwasm_std::{Decimal, Uint128};
+    /// use cosmwasm_core::{Decimal, Uint128};
     ///
     /// let d = Decimal::from_str("12.345").unwrap();
     /// assert_eq!(d.to_uint_floor(), Uint128::new(12));
@@ -438,7 +582,7 @@ impl Decimal {
     ///
     /// ```
     /// use core::str::FromStr;
-    /// use cosmwasm_std::{Decimal, Uint128};
+    /// use cosmwasm_core::{Decimal, Uint128};
     ///
     /// let d = Decimal::from_str("12.345").unwrap();
     /// assert_eq!(d.to_uint_ceil(), Uint128::new(13));
@@ -739,6 +883,7 @@ where
 }
 
 /// Serializes as a decimal string
+#[cfg(feature = "serde")]
 impl Serialize for Decimal {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -749,6 +894,7 @@ impl Serialize for Decimal {
 }
 
 /// Deserializes as a base64 string
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Decimal {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -758,8 +904,10 @@ impl<'de> Deserialize<'de> for Decimal {
     }
 }
 
+#[cfg(feature = "serde")]
 struct DecimalVisitor;
 
+#[cfg(feature = "serde")]
 impl de::Visitor<'_> for DecimalVisitor {
     type Value = Decimal;
 
@@ -781,6 +929,7 @@ impl de::Visitor<'_> for DecimalVisitor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::{prop_assert_eq, property_test};
 
     use alloc::vec::Vec;
 
@@ -1033,6 +1182,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decimal_from_ratio_floor_and_ceil_work() {
+        // exact values are not rounded
+        assert_eq!(
+            Decimal::from_ratio_floor(3u128, 4u128),
+            Decimal::percent(75)
+        );
+        assert_eq!(Decimal::from_ratio_ceil(3u128, 4u128), Decimal::percent(75));
+        assert_eq!(Decimal::from_ratio_ceil(0u128, 4u128), Decimal::zero());
+
+        // 1/3
+        assert_eq!(
+            Decimal::from_ratio_floor(1u128, 3u128),
+            Decimal::from_atomics(333_333_333_333_333_333u128, 18).unwrap()
+        );
+        assert_eq!(
+            Decimal::from_ratio_ceil(1u128, 3u128),
+            Decimal::from_atomics(333_333_333_333_333_334u128, 18).unwrap()
+        );
+
+        // smallest positive value
+        assert_eq!(Decimal::from_ratio_floor(1u128, u128::MAX), Decimal::zero());
+        assert_eq!(
+            Decimal::from_ratio_ceil(1u128, u128::MAX),
+            Decimal::from_atomics(1u128, 18).unwrap()
+        );
+
+        assert_eq!(
+            Decimal::checked_from_ratio_ceil(1u128, 0u128),
+            Err(CheckedFromRatioError::DivideByZero)
+        );
+        assert_eq!(
+            Decimal::checked_from_ratio_ceil(Decimal::MAX.atomics(), 1u128),
+            Err(CheckedFromRatioError::Overflow)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Denominator must not be zero")]
+    fn decimal_from_ratio_ceil_panics_for_zero_denominator() {
+        let _ = Decimal::from_ratio_ceil(1u128, 0u128);
+    }
+
+    #[test]
+    fn decimal_to_fraction_works() {
+        let fraction = |value: &str| Decimal::from_str(value).unwrap().to_fraction();
+        assert_eq!(fraction("0"), (Uint128::zero(), Uint128::one()));
+        assert_eq!(fraction("1"), (Uint128::one(), Uint128::one()));
+        assert_eq!(fraction("0.5"), (Uint128::one(), Uint128::from(2u128)));
+        assert_eq!(
+            fraction("0.75"),
+            (Uint128::from(3u128), Uint128::from(4u128))
+        );
+        assert_eq!(
+            fraction("2.5"),
+            (Uint128::from(5u128), Uint128::from(2u128))
+        );
+        assert_eq!(fraction("42"), (Uint128::from(42u128), Uint128::one()));
+        assert_eq!(
+            fraction("0.000000000000000001"),
+            (Uint128::one(), Uint128::from(1_000_000_000_000_000_000u128))
+        );
+        assert_eq!(
+            fraction("0.333333333333333333"),
+            (
+                Uint128::from(333_333_333_333_333_333u128),
+                Uint128::from(1_000_000_000_000_000_000u128)
+            )
+        );
+    }
+
+    #[property_test]
+    fn decimal_to_fraction_roundtrips_and_is_reduced(atomics: u128) {
+        let value = Decimal::new(Uint128::from(atomics));
+        let (numerator, denominator) = value.to_fraction();
+        prop_assert_eq!(Decimal::from_ratio(numerator, denominator), value);
+        prop_assert_eq!(numerator.gcd(denominator), Uint128::one());
+    }
+
     #[test]
     fn decimal_implements_fraction() {
         let fraction = Decimal::from_str("1234.567").unwrap();
@@ -1177,6 +1405,134 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decimal_from_scientific_works() {
+        assert_eq!(Decimal::from_scientific("1").unwrap(), Decimal::one());
+        assert_eq!(Decimal::from_scientific("+1.5").unwrap(), dec("1.5"));
+        assert_eq!(Decimal::from_scientific("1.5e-3").unwrap(), dec("0.0015"));
+        assert_eq!(Decimal::from_scientific("15E-4").unwrap(), dec("0.0015"));
+        assert_eq!(Decimal::from_scientific("1.5e+3").unwrap(), dec("1500"));
+        assert_eq!(
+            Decimal::from_scientific("1e18").unwrap(),
+            dec("1000000000000000000")
+        );
+        assert_eq!(Decimal::from_scientific("1e-18").unwrap(), Decimal::raw(1));
+        assert_eq!(Decimal::from_scientific("0e-100").unwrap(), Decimal::zero());
+        assert_eq!(
+            Decimal::from_scientific("00.0e100").unwrap(),
+            Decimal::zero()
+        );
+        // more than 18 fractional digits are fine as long as they are zero
+        assert_eq!(
+            Decimal::from_scientific("1.0000000000000000000000").unwrap(),
+            Decimal::one()
+        );
+        assert_eq!(
+            Decimal::from_scientific("340282366920938463463374607431768211455e-18").unwrap(),
+            Decimal::MAX
+        );
+    }
+
+    #[test]
+    fn decimal_from_scientific_errors() {
+        let err = |input| match Decimal::from_scientific(input).unwrap_err() {
+            StdError::GenericErr { msg, .. } => msg,
+            e => panic!("Unexpected error: {e:?}"),
+        };
+
+        assert_eq!(err(""), "Error parsing whole");
+        assert_eq!(err("+"), "Error parsing whole");
+        assert_eq!(err(".5"), "Error parsing whole");
+        assert_eq!(err("e5"), "Error parsing whole");
+        assert_eq!(err("1."), "Error parsing fractional");
+        assert_eq!(err("1.2.3"), "Error parsing fractional");
+        assert_eq!(err("1e"), "Error parsing exponent");
+        assert_eq!(err("1e1.5"), "Error parsing exponent");
+        assert_eq!(err("1e+-5"), "Error parsing exponent");
+        assert_eq!(err("1e99999999999"), "Exponent out of range");
+        assert_eq!(err("-1"), "Negative values are not supported by this type");
+
+        assert_eq!(err("1e21"), "Decimal range exceeded");
+        assert_eq!(err("1e100"), "Decimal range exceeded");
+        assert_eq!(
+            err("340282366920938463463374607431768211456e-18"),
+            "Decimal range exceeded"
+        );
+
+        assert_eq!(
+            err("1e-19"),
+            "Value is too small to be represented with 18 fractional digits"
+        );
+        assert_eq!(
+            err("123e-40"),
+            "Value is too small to be represented with 18 fractional digits"
+        );
+        assert_eq!(
+            err("1.5e-18"),
+            "Cannot represent more than 18 fractional digits"
+        );
+    }
+
+    #[test]
+    fn decimal_from_scientific_truncated_works() {
+        assert_eq!(
+            Decimal::from_scientific_truncated("1e-19").unwrap(),
+            Decimal::zero()
+        );
+        assert_eq!(
+            Decimal::from_scientific_truncated("1.9e-18").unwrap(),
+            Decimal::raw(1)
+        );
+        assert_eq!(
+            Decimal::from_scientific_truncated("1.23456789012345678999").unwrap(),
+            dec("1.234567890123456789")
+        );
+        assert_eq!(
+            Decimal::from_scientific_truncated("1.5e-3").unwrap(),
+            dec("0.0015")
+        );
+        // range is still checked
+        assert!(Decimal::from_scientific_truncated("1e21").is_err());
+    }
+
+    #[test]
+    fn decimal_from_scientific_does_not_change_from_str() {
+        assert!(Decimal::from_str("1e3").is_err());
+    }
+
+    #[property_test]
+    fn decimal_from_scientific_roundtrips_display(atomics: u128) {
+        let value = Decimal::raw(atomics);
+        prop_assert_eq!(Decimal::from_scientific(&value.to_string()), Ok(value));
+        prop_assert_eq!(Decimal::from_scientific(&format!("{value}e0")), Ok(value));
+        prop_assert_eq!(
+            Decimal::from_scientific(&format!("{atomics}e-18")),
+            Ok(value)
+        );
+    }
+
+    #[property_test]
+    fn decimal_from_scientific_matches_reference(mantissa: u64, exp: i8) {
+        let exp = i32::from(exp % 40);
+        let input = format!("{mantissa}e{exp}");
+        // the value in atomics is mantissa * 10^shift
+        let shift = exp + 18;
+        let (strict, truncated) = if shift >= 0 {
+            let atomics = Uint256::from(mantissa) * Uint256::from(10u8).pow(shift as u32);
+            let value = Uint128::try_from(atomics).ok().map(Decimal::new);
+            (value, value)
+        } else {
+            let divisor = Uint128::from(10u8).pow(shift.unsigned_abs());
+            let mantissa = Uint128::from(mantissa);
+            let value = Some(Decimal::new(mantissa / divisor));
+            let exact = (mantissa % divisor).is_zero();
+            (if exact { value } else { None }, value)
+        };
+
+        prop_assert_eq!(Decimal::from_scientific(&input).ok(), strict);
+        prop_assert_eq!(Decimal::from_scientific_truncated(&input).ok(), truncated);
+    }
+
     #[test]
     fn decimal_atomics_works() {
         let zero = Decimal::zero();
@@ -3,6 +3,7 @@ use core::cmp::Ordering;
 use core::fmt::{self, Write};
 use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign};
 use core::str::FromStr;
+#[cfg(feature = "serde")]
 use serde::{de, ser, Deserialize, Deserializer, Serialize};
 
 use crate::errors::{
@@ -11,9 +12,10 @@ use crate::errors::{
 };
 use crate::forward_ref::{forward_ref_binop, forward_ref_op_assign};
 use crate::{
-    Decimal, SignedDecimal, SignedDecimal256, Uint512, __internal::forward_ref_partial_eq,
+    __internal::forward_ref_partial_eq, Decimal, SignedDecimal, SignedDecimal256, Uint512,
 };
 
+use super::scientific::parse_scientific;
 use super::Fraction;
 use super::Isqrt;
 use super::Uint256;
@@ -23,15 +25,24 @@ use super::Uint256;
 /// The greatest possible value that can be represented is
 /// 115792089237316195423570985008687907853269984665640564039457.584007913129639935
 /// (which is (2^256 - 1) / 10^18)
-#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct Decimal256(#[schemars(with = "String")] Uint256);
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Decimal256(#[cfg_attr(feature = "schemars", schemars(with = "String"))] Uint256);
 
 forward_ref_partial_eq!(Decimal256, Decimal256);
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-#[error("Decimal256 range exceeded")]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Decimal256RangeExceeded;
 
+impl fmt::Display for Decimal256RangeExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Decimal256 range exceeded")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Decimal256RangeExceeded {}
+
 impl Decimal256 {
     const DECIMAL_FRACTIONAL: Uint256 = // 1*10**18
         Uint256::from_u128(1_000_000_000_000_000_000);
@@ -76,7 +87,7 @@ impl Decimal256 {
     ///
     /// ```
     /// # use std::str::FromStr;
-    /// # use cosmwasm_std::Decimal256;
+    /// # use cosmwasm_core::Decimal256;
     /// const HALF: Decimal256 = Decimal256::percent(50);
     ///
     /// assert_eq!(HALF, Decimal256::from_str("0.5").unwrap());
@@ -93,7 +104,7 @@ impl Decimal256 {
     ///
     /// ```
     /// # use std::str::FromStr;
-    /// # use cosmwasm_std::Decimal256;
+    /// # use cosmwasm_core::Decimal256;
     /// const HALF: Decimal256 = Decimal256::permille(500);
     ///
     /// assert_eq!(HALF, Decimal256::from_str("0.5").unwrap());
@@ -110,7 +121,7 @@ impl Decimal256 {
     ///
     /// ```
     /// # use std::str::FromStr;
-    /// # use cosmwasm_std::Decimal256;
+    /// # use cosmwasm_core::Decimal256;
     /// const TWO_BPS: Decimal256 = Decimal256::bps(2);
     /// const HALF: Decimal256 = Decimal256::bps(5000);
     ///
@@ -134,7 +145,7 @@ impl Decimal256 {
     /// ## Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{Decimal256, Uint256};
+    /// # use cosmwasm_core::{Decimal256, Uint256};
     /// let a = Decimal256::from_atomics(1234u64, 3).unwrap();
     /// assert_eq!(a.to_string(), "1.234");
     ///
@@ -181,7 +192,66 @@ impl Decimal256 {
         })
     }
 
-    /// Returns the ratio (numerator / denominator) as a Decimal256
+    /// Creates a Decimal256 from a string in decimal or scientific notation,
+    /// e.g. "1.5", "1.5e-3", "15E-4" or "+0.15e-2".
+    ///
+    /// The input consists of an optional `+` sign, a whole part, an optional fractional
+    /// part and an optional exponent introduced by `e` or `E`.
+    ///
+    /// Unlike [`FromStr`], this accepts values with more than
+    /// [`DECIMAL_PLACES`](Self::DECIMAL_PLACES) fractional digits as long as the
+    /// additional digits are zero. Values that cannot be represented exactly result in an error.
+    /// Use [`Decimal256::from_scientific_truncated`] to round towards zero instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::Decimal256;
+    /// let a = Decimal256::from_scientific("1.5e-3").unwrap();
+    /// assert_eq!(a.to_string(), "0.0015");
+    ///
+    /// let a = Decimal256::from_scientific("1e18").unwrap();
+    /// assert_eq!(a.to_string(), "1000000000000000000");
+    ///
+    /// // smaller than the smallest positive value
+    /// assert!(Decimal256::from_scientific("1e-19").is_err());
+    /// ```
+    pub fn from_scientific(input: &str) -> Result<Self, StdError> {
+        Self::parse_scientific(input, false)
+    }
+
+    /// Like [`Decimal256::from_scientific`] but rounds towards zero if the value
+    /// has more than [`DECIMAL_PLACES`](Self::DECIMAL_PLACES) fractional digits.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::Decimal256;
+    /// let a = Decimal256::from_scientific_truncated("1.9e-18").unwrap();
+    /// assert_eq!(a, Decimal256::raw(1));
+    ///
+    /// let a = Decimal256::from_scientific_truncated("1e-19").unwrap();
+    /// assert_eq!(a, Decimal256::zero());
+    /// ```
+    pub fn from_scientific_truncated(input: &str) -> Result<Self, StdError> {
+        Self::parse_scientific(input, true)
+    }
+
+    fn parse_scientific(input: &str, truncate: bool) -> Result<Self, StdError> {
+        let atomics = parse_scientific(input, Self::DECIMAL_PLACES, false, truncate)
+            .map_err(|err| err.into_std_error(Decimal256RangeExceeded, Self::DECIMAL_PLACES))?;
+        // parse_scientific limits the number of digits but not the exact range of the type
+        atomics
+            .digits
+            .parse::<Uint256>()
+            .map(Self)
+            .map_err(|_| StdError::generic_err(Decimal256RangeExceeded.to_string()))
+    }
+
+    /// Returns the ratio (numerator / denominator) as a Decimal256.
+    ///
+    /// The result is rounded down to the nearest representable value.
+    /// See [`Decimal256::from_ratio_ceil`] for rounding up.
     pub fn from_ratio(numerator: impl Into<Uint256>, denominator: impl Into<Uint256>) -> Self {
         match Decimal256::checked_from_ratio(numerator, denominator) {
             Ok(value) => value,
@@ -192,7 +262,10 @@ impl Decimal256 {
         }
     }
 
-    /// Returns the ratio (numerator / denominator) as a Decimal256
+    /// Returns the ratio (numerator / denominator) as a Decimal256.
+    ///
+    /// The result is rounded down to the nearest representable value.
+    /// See [`Decimal256::checked_from_ratio_ceil`] for rounding up.
     pub fn checked_from_ratio(
         numerator: impl Into<Uint256>,
         denominator: impl Into<Uint256>,
@@ -211,6 +284,77 @@ impl Decimal256 {
         }
     }
 
+    /// Returns the ratio (numerator / denominator) as a Decimal256, rounded down.
+    ///
+    /// This is the same as [`Decimal256::from_ratio`] but makes the rounding direction explicit.
+    pub fn from_ratio_floor(
+        numerator: impl Into<Uint256>,
+        denominator: impl Into<Uint256>,
+    ) -> Self {
+        Self::from_ratio(numerator, denominator)
+    }
+
+    /// Returns the ratio (numerator / denominator) as a Decimal256, rounded up.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::Decimal256;
+    /// assert_eq!(Decimal256::from_ratio_floor(2u128, 3u128).to_string(), "0.666666666666666666");
+    /// assert_eq!(Decimal256::from_ratio_ceil(2u128, 3u128).to_string(), "0.666666666666666667");
+    /// ```
+    pub fn from_ratio_ceil(numerator: impl Into<Uint256>, denominator: impl Into<Uint256>) -> Self {
+        match Self::checked_from_ratio_ceil(numerator, denominator) {
+            Ok(value) => value,
+            Err(CheckedFromRatioError::DivideByZero) => {
+                panic!("Denominator must not be zero")
+            }
+            Err(CheckedFromRatioError::Overflow) => panic!("Multiplication overflow"),
+        }
+    }
+
+    /// Returns the ratio (numerator / denominator) as a Decimal256, rounded up.
+    pub fn checked_from_ratio_ceil(
+        numerator: impl Into<Uint256>,
+        denominator: impl Into<Uint256>,
+    ) -> Result<Self, CheckedFromRatioError> {
+        let numerator: Uint256 = numerator.into();
+        let denominator: Uint256 = denominator.into();
+        if denominator.is_zero() {
+            return Err(CheckedFromRatioError::DivideByZero);
+        }
+
+        let dividend = numerator.full_mul(Self::DECIMAL_FRACTIONAL);
+        let divisor = Uint512::from(denominator);
+        let mut atomics = dividend / divisor;
+        if !(dividend % divisor).is_zero() {
+            atomics += Uint512::one();
+        }
+        atomics
+            .try_into()
+            .map(Self)
+            .map_err(|_| CheckedFromRatioError::Overflow)
+    }
+
+    /// Returns the exact value as a fraction `(numerator, denominator)` in lowest terms.
+    ///
+    /// Passing the result to [`Decimal256::from_ratio`] returns the original value.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::{Decimal256, Uint256};
+    /// # use core::str::FromStr;
+    /// let value = Decimal256::from_str("0.75").unwrap();
+    /// assert_eq!(value.to_fraction(), (Uint256::from(3u32), Uint256::from(4u32)));
+    /// assert_eq!(Decimal256::from_ratio(3u128, 4u128), value);
+    /// ```
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn to_fraction(&self) -> (Uint256, Uint256) {
+        let gcd = self.0.gcd(Self::DECIMAL_FRACTIONAL);
+        (self.0 / gcd, Self::DECIMAL_FRACTIONAL / gcd)
+    }
+
     #[must_use]
     pub const fn is_zero(&self) -> bool {
         self.0.is_zero()
@@ -222,7 +366,7 @@ impl Decimal256 {
     /// ## Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{Decimal256, Uint256};
+    /// # use cosmwasm_core::{Decimal256, Uint256};
     /// # use core::str::FromStr;
     /// // Value with whole and fractional part
     /// let a = Decimal256::from_str("1.234").unwrap();
@@ -430,7 +574,7 @@ impl Decimal256 {
     ///
     /// ```
     /// use core::str::FromStr;
-    /// use cosmwasm_std::{Decimal256, Uint256};
+    /// use cosmwasm_core::{Decimal256, Uint256};
     ///
     /// let d = Decimal256::from_str("12.345").unwrap();
     /// assert_eq!(d.to_uint_floor(), Uint256::from(12u64));
@@ -453,7 +597,7 @@ impl Decimal256 {
     ///
     /// ```
     /// use core::str::FromStr;
-    /// use cosmwasm_std::{Decimal256, Uint256};
+    /// use cosmwasm_core::{Decimal256, Uint256};
     ///
     /// let d = Decimal256::from_str("12.345").unwrap();
     /// assert_eq!(d.to_uint_ceil(), Uint256::from(13u64));
@@ -741,6 +885,7 @@ where
 }
 
 /// Serializes as a decimal string
+#[cfg(feature = "serde")]
 impl Serialize for Decimal256 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -751,6 +896,7 @@ impl Serialize for Decimal256 {
 }
 
 /// Deserializes as a base64 string
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Decimal256 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -760,8 +906,10 @@ impl<'de> Deserialize<'de> for Decimal256 {
     }
 }
 
+#[cfg(feature = "serde")]
 struct Decimal256Visitor;
 
+#[cfg(feature = "serde")]
 impl de::Visitor<'_> for Decimal256Visitor {
     type Value = Decimal256;
 
@@ -1007,6 +1155,94 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decimal256_from_ratio_floor_and_ceil_work() {
+        // exact values are not rounded
+        assert_eq!(
+            Decimal256::from_ratio_floor(3u128, 4u128),
+            Decimal256::percent(75)
+        );
+        assert_eq!(
+            Decimal256::from_ratio_ceil(3u128, 4u128),
+            Decimal256::percent(75)
+        );
+        assert_eq!(
+            Decimal256::from_ratio_ceil(0u128, 4u128),
+            Decimal256::zero()
+        );
+
+        // 1/3
+        assert_eq!(
+            Decimal256::from_ratio_floor(1u128, 3u128),
+            Decimal256::from_atomics(333_333_333_333_333_333u128, 18).unwrap()
+        );
+        assert_eq!(
+            Decimal256::from_ratio_ceil(1u128, 3u128),
+            Decimal256::from_atomics(333_333_333_333_333_334u128, 18).unwrap()
+        );
+
+        // smallest positive value
+        assert_eq!(
+            Decimal256::from_ratio_floor(1u128, u128::MAX),
+            Decimal256::zero()
+        );
+        assert_eq!(
+            Decimal256::from_ratio_ceil(1u128, u128::MAX),
+            Decimal256::from_atomics(1u128, 18).unwrap()
+        );
+
+        assert_eq!(
+            Decimal256::checked_from_ratio_ceil(1u128, 0u128),
+            Err(CheckedFromRatioError::DivideByZero)
+        );
+        assert_eq!(
+            Decimal256::checked_from_ratio_ceil(Decimal256::MAX.atomics(), 1u128),
+            Err(CheckedFromRatioError::Overflow)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Denominator must not be zero")]
+    fn decimal256_from_ratio_ceil_panics_for_zero_denominator() {
+        let _ = Decimal256::from_ratio_ceil(1u128, 0u128);
+    }
+
+    #[test]
+    fn decimal256_to_fraction_works() {
+        let fraction = |value: &str| Decimal256::from_str(value).unwrap().to_fraction();
+        assert_eq!(fraction("0"), (Uint256::zero(), Uint256::one()));
+        assert_eq!(fraction("1"), (Uint256::one(), Uint256::one()));
+        assert_eq!(fraction("0.5"), (Uint256::one(), Uint256::from(2u128)));
+        assert_eq!(
+            fraction("0.75"),
+            (Uint256::from(3u128), Uint256::from(4u128))
+        );
+        assert_eq!(
+            fraction("2.5"),
+            (Uint256::from(5u128), Uint256::from(2u128))
+        );
+        assert_eq!(fraction("42"), (Uint256::from(42u128), Uint256::one()));
+        assert_eq!(
+            fraction("0.000000000000000001"),
+            (Uint256::one(), Uint256::from(1_000_000_000_000_000_000u128))
+        );
+        assert_eq!(
+            fraction("0.333333333333333333"),
+            (
+                Uint256::from(333_333_333_333_333_333u128),
+                Uint256::from(1_000_000_000_000_000_000u128)
+            )
+        );
+    }
+
+    #[property_test]
+    fn decimal256_to_fraction_roundtrips_and_is_reduced(atomics: u128) {
+        let value = Decimal256::new(Uint256::from(atomics));
+        let (numerator, denominator) = value.to_fraction();
+        prop_assert_eq!(Decimal256::from_ratio(numerator, denominator), value);
+        prop_assert_eq!(numerator.gcd(denominator), Uint256::one());
+    }
+
     #[test]
     fn decimal256_implements_fraction() {
         let fraction = Decimal256::from_str("1234.567").unwrap();
@@ -1213,6 +1449,81 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decimal256_from_scientific_works() {
+        assert_eq!(Decimal256::from_scientific("1").unwrap(), Decimal256::one());
+        assert_eq!(Decimal256::from_scientific("+1.5").unwrap(), dec("1.5"));
+        assert_eq!(
+            Decimal256::from_scientific("1.5e-3").unwrap(),
+            dec("0.0015")
+        );
+        assert_eq!(Decimal256::from_scientific("15E-4").unwrap(), dec("0.0015"));
+        assert_eq!(
+            Decimal256::from_scientific("1e18").unwrap(),
+            dec("1000000000000000000")
+        );
+        assert_eq!(
+            Decimal256::from_scientific("1e59").unwrap(),
+            dec("100000000000000000000000000000000000000000000000000000000000")
+        );
+        assert_eq!(
+            Decimal256::from_scientific("1e-18").unwrap(),
+            Decimal256::raw(1)
+        );
+        assert_eq!(
+            Decimal256::from_scientific("0e-100").unwrap(),
+            Decimal256::zero()
+        );
+        assert_eq!(
+            Decimal256::from_scientific(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935e-18"
+            )
+            .unwrap(),
+            Decimal256::MAX
+        );
+    }
+
+    #[test]
+    fn decimal256_from_scientific_errors() {
+        let err = |input| match Decimal256::from_scientific(input).unwrap_err() {
+            StdError::GenericErr { msg, .. } => msg,
+            e => panic!("Unexpected error: {e:?}"),
+        };
+
+        assert_eq!(err(""), "Error parsing whole");
+        assert_eq!(err("1."), "Error parsing fractional");
+        assert_eq!(err("1e"), "Error parsing exponent");
+        assert_eq!(err("-1"), "Negative values are not supported by this type");
+
+        assert_eq!(err("1e60"), "Decimal256 range exceeded");
+        assert_eq!(
+            err("115792089237316195423570985008687907853269984665640564039457584007913129639936e-18"),
+            "Decimal256 range exceeded"
+        );
+
+        assert_eq!(
+            err("1e-19"),
+            "Value is too small to be represented with 18 fractional digits"
+        );
+        assert_eq!(
+            err("1.5e-18"),
+            "Cannot represent more than 18 fractional digits"
+        );
+    }
+
+    #[test]
+    fn decimal256_from_scientific_truncated_works() {
+        assert_eq!(
+            Decimal256::from_scientific_truncated("1e-19").unwrap(),
+            Decimal256::zero()
+        );
+        assert_eq!(
+            Decimal256::from_scientific_truncated("1.9e-18").unwrap(),
+            Decimal256::raw(1)
+        );
+        assert!(Decimal256::from_scientific_truncated("1e60").is_err());
+    }
+
     #[test]
     fn decimal256_atomics_works() {
         let zero = Decimal256::zero();
@@ -0,0 +1,259 @@
+//! Division of unsigned integers stored as little endian `u64` limbs.
+//!
+//! This is a fast path for [`Uint256`](crate::Uint256) and [`Uint512`](crate::Uint512)
+//! for divisors of at most half their width (e.g. a 256 bit value divided by a 128 bit value),
+//! which are very common in fixed-point math. Wider divisors are left to the general
+//! implementation of the underlying type.
+
+/// The maximum number of limbs supported (512 bits)
+const MAX_LIMBS: usize = 8;
+
+/// Divides `dividend` by `divisor`, returning the quotient and the remainder.
+///
+/// Returns `None` if the divisor is zero or uses more than half of the limbs.
+/// In this case the caller must use a general purpose division instead.
+pub(crate) fn div_rem_narrow<const N: usize>(
+    dividend: &[u64; N],
+    divisor: &[u64; N],
+) -> Option<([u64; N], [u64; N])> {
+    debug_assert!(N <= MAX_LIMBS);
+
+    let n = significant_limbs(divisor);
+    if n == 0 || n > N / 2 {
+        return None;
+    }
+    let m = significant_limbs(dividend);
+
+    let mut quotient = [0u64; N];
+    let mut remainder = [0u64; N];
+    if m < n {
+        return Some((quotient, *dividend));
+    }
+
+    if m <= 2 {
+        // Both fit into a u128
+        let a = to_u128(dividend[0], dividend[1]);
+        let b = to_u128(divisor[0], divisor[1]);
+        (quotient[0], quotient[1]) = from_u128(a / b);
+        (remainder[0], remainder[1]) = from_u128(a % b);
+    } else if n == 1 {
+        let d = u128::from(divisor[0]);
+        let mut rem = 0u128;
+        for i in (0..m).rev() {
+            let current = (rem << 64) | u128::from(dividend[i]);
+            quotient[i] = (current / d) as u64;
+            rem = current % d;
+        }
+        remainder[0] = rem as u64;
+    } else {
+        knuth_d(&dividend[..m], &divisor[..n], &mut quotient, &mut remainder);
+    }
+    Some((quotient, remainder))
+}
+
+/// Algorithm D from Knuth, The Art of Computer Programming, Vol. 2, Section 4.3.1,
+/// following the presentation in Hacker's Delight, Section 9-2, with 64 bit digits.
+///
+/// Requires `u.len() >= v.len() >= 2` and the most significant limb of `v` to be non-zero.
+fn knuth_d(u: &[u64], v: &[u64], quotient: &mut [u64], remainder: &mut [u64]) {
+    let m = u.len();
+    let n = v.len();
+
+    // Normalize such that the highest bit of the divisor is set
+    let shift = v[n - 1].leading_zeros();
+    let mut vn = [0u64; MAX_LIMBS];
+    let mut un = [0u64; MAX_LIMBS + 1];
+    shl(v, shift, &mut vn[..n]);
+    un[m] = shl(u, shift, &mut un[..m]);
+
+    let divisor_high = u128::from(vn[n - 1]);
+    let divisor_next = u128::from(vn[n - 2]);
+    for j in (0..=m - n).rev() {
+        // Estimate the quotient digit from the top two digits of the current dividend
+        let numerator = to_u128(un[j + n - 1], un[j + n]);
+        let mut qhat = numerator / divisor_high;
+        let mut rhat = numerator % divisor_high;
+        while qhat > u128::from(u64::MAX)
+            || qhat * divisor_next > ((rhat << 64) | u128::from(un[j + n - 2]))
+        {
+            qhat -= 1;
+            rhat += divisor_high;
+            if rhat > u128::from(u64::MAX) {
+                break;
+            }
+        }
+
+        // Multiply and subtract
+        let mut carry = 0u128;
+        for i in 0..n {
+            let product = qhat * u128::from(vn[i]) + carry;
+            let (difference, borrow) = un[i + j].overflowing_sub(product as u64);
+            un[i + j] = difference;
+            carry = (product >> 64) + u128::from(borrow);
+        }
+        let (difference, negative) = un[j + n].overflowing_sub(carry as u64);
+        un[j + n] = difference;
+
+        // The estimate was one too large in rare cases, so add back
+        if negative {
+            qhat -= 1;
+            let mut carry = 0u128;
+            for i in 0..n {
+                let sum = u128::from(un[i + j]) + u128::from(vn[i]) + carry;
+                un[i + j] = sum as u64;
+                carry = sum >> 64;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u64);
+        }
+        quotient[j] = qhat as u64;
+    }
+
+    // Unnormalize the remainder
+    for i in 0..n {
+        remainder[i] = if shift == 0 {
+            un[i]
+        } else {
+            (un[i] >> shift) | (un[i + 1] << (64 - shift))
+        };
+    }
+}
+
+/// Shifts `input` left by `shift` (< 64) bits into `output` and returns the bits shifted out.
+fn shl(input: &[u64], shift: u32, output: &mut [u64]) -> u64 {
+    if shift == 0 {
+        output.copy_from_slice(input);
+        return 0;
+    }
+    let mut carry = 0;
+    for (out, limb) in output.iter_mut().zip(input) {
+        *out = (limb << shift) | carry;
+        carry = limb >> (64 - shift);
+    }
+    carry
+}
+
+fn significant_limbs(limbs: &[u64]) -> usize {
+    limbs
+        .iter()
+        .rposition(|&limb| limb != 0)
+        .map_or(0, |i| i + 1)
+}
+
+#[inline]
+fn to_u128(low: u64, high: u64) -> u128 {
+    (u128::from(high) << 64) | u128::from(low)
+}
+
+#[inline]
+fn from_u128(value: u128) -> (u64, u64) {
+    (value as u64, (value >> 64) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bnum::types::{U256, U512};
+    use proptest::{prop_assert_eq, prop_assume, property_test};
+
+    fn check_u256(dividend: [u64; 4], divisor: [u64; 4]) {
+        let (q, r) = div_rem_narrow(&dividend, &divisor).unwrap();
+        let a = U256::from_digits(dividend);
+        let b = U256::from_digits(divisor);
+        assert_eq!(U256::from_digits(q), a / b, "{a} / {b}");
+        assert_eq!(U256::from_digits(r), a % b, "{a} % {b}");
+    }
+
+    fn check_u512(dividend: [u64; 8], divisor: [u64; 8]) {
+        let (q, r) = div_rem_narrow(&dividend, &divisor).unwrap();
+        let a = U512::from_digits(dividend);
+        let b = U512::from_digits(divisor);
+        assert_eq!(U512::from_digits(q), a / b, "{a} / {b}");
+        assert_eq!(U512::from_digits(r), a % b, "{a} % {b}");
+    }
+
+    #[test]
+    fn div_rem_narrow_rejects_zero_and_wide_divisors() {
+        assert_eq!(div_rem_narrow(&[1, 2, 3, 4], &[0, 0, 0, 0]), None);
+        assert_eq!(div_rem_narrow(&[1, 2, 3, 4], &[0, 0, 1, 0]), None);
+        assert_eq!(div_rem_narrow(&[1, 2, 3, 4], &[0, 0, 0, 1]), None);
+        assert_eq!(div_rem_narrow(&[1; 8], &[0, 0, 0, 0, 1, 0, 0, 0]), None);
+
+        assert!(div_rem_narrow(&[1, 2, 3, 4], &[0, 1, 0, 0]).is_some());
+        assert!(div_rem_narrow(&[1; 8], &[0, 0, 0, 1, 0, 0, 0, 0]).is_some());
+    }
+
+    #[test]
+    fn div_rem_narrow_works_for_boundaries() {
+        const MAX: u64 = u64::MAX;
+        let limbs = [0, 1, 2, 1 << 63, MAX - 1, MAX];
+
+        for &a0 in &limbs {
+            for &a1 in &limbs {
+                for &a3 in &limbs {
+                    for &b0 in &limbs {
+                        for &b1 in &limbs {
+                            if b0 == 0 && b1 == 0 {
+                                continue;
+                            }
+                            check_u256([a0, a1, a1, a3], [b0, b1, 0, 0]);
+                            check_u512(
+                                [a0, a1, a3, a1, a0, a3, a1, a3],
+                                [b0, b1, b1, b0, 0, 0, 0, 0],
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        // the quotient estimate needs to be corrected
+        check_u256([0, 0, 0, 1 << 63], [1, 1 << 63, 0, 0]);
+        check_u256([MAX, MAX, MAX, MAX - 1], [MAX, MAX, 0, 0]);
+        check_u512(
+            [0, 0, 0, 0, 0, 0, 0, 1 << 63],
+            [1, 0, 0, 1 << 63, 0, 0, 0, 0],
+        );
+        check_u512(
+            [0, 0, MAX, MAX, MAX, MAX, MAX, 0x7fff_ffff_ffff_ffff],
+            [1, 0, 0, 1 << 63, 0, 0, 0, 0],
+        );
+    }
+
+    #[property_test]
+    fn div_rem_narrow_matches_u256(dividend: [u64; 4], divisor: [u64; 2], zeros: u8) {
+        // vary the width of both operands
+        let mut dividend = dividend;
+        let zeros = usize::from(zeros % 4);
+        for limb in dividend.iter_mut().rev().take(zeros) {
+            *limb = 0;
+        }
+        let divisor = [divisor[0], divisor[1] >> (zeros * 16), 0, 0];
+        prop_assume!(divisor != [0; 4]);
+
+        let (q, r) = div_rem_narrow(&dividend, &divisor).unwrap();
+        let a = U256::from_digits(dividend);
+        let b = U256::from_digits(divisor);
+        prop_assert_eq!(U256::from_digits(q), a / b);
+        prop_assert_eq!(U256::from_digits(r), a % b);
+    }
+
+    #[property_test]
+    fn div_rem_narrow_matches_u512(dividend: [u64; 8], divisor: [u64; 4], zeros: u8) {
+        let mut dividend = dividend;
+        let zeros = usize::from(zeros % 8);
+        for limb in dividend.iter_mut().rev().take(zeros) {
+            *limb = 0;
+        }
+        let mut divisor = [divisor[0], divisor[1], divisor[2], divisor[3], 0, 0, 0, 0];
+        for limb in divisor[..4].iter_mut().rev().take(zeros / 2) {
+            *limb = 0;
+        }
+        prop_assume!(divisor != [0; 8]);
+
+        let (q, r) = div_rem_narrow(&dividend, &divisor).unwrap();
+        let a = U512::from_digits(dividend);
+        let b = U512::from_digits(divisor);
+        prop_assert_eq!(U512::from_digits(q), a / b);
+        prop_assert_eq!(U512::from_digits(r), a % b);
+    }
+}
@@ -9,14 +9,15 @@ use core::str::FromStr;
 use crate::errors::{DivideByZeroError, DivisionError, OverflowError, OverflowOperation, StdError};
 use crate::forward_ref::{forward_ref_binop, forward_ref_op_assign};
 use crate::{
-    CheckedMultiplyRatioError, Int256, Int512, Int64, Uint128, Uint256, Uint512, Uint64,
-    __internal::forward_ref_partial_eq,
+    __internal::forward_ref_partial_eq, CheckedMultiplyRatioError, Int256, Int512, Int64, Uint128,
+    Uint256, Uint512, Uint64,
 };
 
 use super::conversion::{
     forward_try_from, from_and_to_bytes, primitive_to_wrapped_int, try_from_int_to_int,
     wrapped_int_to_primitive,
 };
+#[cfg(feature = "serde")]
 use super::impl_int_serde;
 use super::num_consts::NumConsts;
 
@@ -29,13 +30,15 @@ use super::num_consts::NumConsts;
 /// Use `from` to create instances of this and `i128` to get the value out:
 ///
 /// ```
-/// # use cosmwasm_std::Int128;
+/// # use cosmwasm_core::Int128;
 /// let a = Int128::from(258i128);
 /// assert_eq!(a.i128(), 258);
 /// ```
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct Int128(#[schemars(with = "String")] pub(crate) i128);
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Int128(#[cfg_attr(feature = "schemars", schemars(with = "String"))] pub(crate) i128);
 
+#[cfg(feature = "serde")]
 impl_int_serde!(Int128);
 forward_ref_partial_eq!(Int128, Int128);
 
@@ -114,7 +117,7 @@ impl Int128 {
     /// # Examples
     ///
     /// ```
-    /// use cosmwasm_std::Int128;
+    /// use cosmwasm_core::Int128;
     ///
     /// let a = Int128::MAX;
     /// let result = a.full_mul(2i32);
@@ -9,8 +9,8 @@ use core::str::FromStr;
 use crate::errors::{DivideByZeroError, DivisionError, OverflowError, OverflowOperation, StdError};
 use crate::forward_ref::{forward_ref_binop, forward_ref_op_assign};
 use crate::{
-    CheckedMultiplyRatioError, Int128, Int512, Int64, Uint128, Uint256, Uint512, Uint64,
-    __internal::forward_ref_partial_eq,
+    __internal::forward_ref_partial_eq, CheckedMultiplyRatioError, Int128, Int512, Int64, Uint128,
+    Uint256, Uint512, Uint64,
 };
 
 /// Used internally - we don't want to leak this type since we might change
@@ -20,6 +20,7 @@ use bnum::types::{I256, U256};
 use super::conversion::{
     grow_be_int, primitive_to_wrapped_int, try_from_int_to_int, try_from_uint_to_int,
 };
+#[cfg(feature = "serde")]
 use super::impl_int_serde;
 use super::num_consts::NumConsts;
 
@@ -33,7 +34,7 @@ use super::num_consts::NumConsts;
 /// endian bytes:
 ///
 /// ```
-/// # use cosmwasm_std::Int256;
+/// # use cosmwasm_core::Int256;
 /// let a = Int256::from(258u128);
 /// let b = Int256::new([
 ///     0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8,
@@ -43,9 +44,11 @@ use super::num_consts::NumConsts;
 /// ]);
 /// assert_eq!(a, b);
 /// ```
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct Int256(#[schemars(with = "String")] pub(crate) I256);
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Int256(#[cfg_attr(feature = "schemars", schemars(with = "String"))] pub(crate) I256);
 
+#[cfg(feature = "serde")]
 impl_int_serde!(Int256);
 forward_ref_partial_eq!(Int256, Int256);
 
@@ -188,7 +191,7 @@ impl Int256 {
     /// # Examples
     ///
     /// ```
-    /// use cosmwasm_std::Int256;
+    /// use cosmwasm_core::Int256;
     ///
     /// let a = Int256::MAX;
     /// let result = a.full_mul(2i32);
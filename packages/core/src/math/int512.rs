@@ -9,7 +9,7 @@ use core::str::FromStr;
 use crate::errors::{DivideByZeroError, DivisionError, OverflowError, OverflowOperation, StdError};
 use crate::forward_ref::{forward_ref_binop, forward_ref_op_assign};
 use crate::{
-    Int128, Int256, Int64, Uint128, Uint256, Uint512, Uint64, __internal::forward_ref_partial_eq,
+    __internal::forward_ref_partial_eq, Int128, Int256, Int64, Uint128, Uint256, Uint512, Uint64,
 };
 
 /// Used internally - we don't want to leak this type since we might change
@@ -17,6 +17,7 @@ use crate::{
 use bnum::types::{I512, U512};
 
 use super::conversion::{grow_be_int, primitive_to_wrapped_int, try_from_uint_to_int};
+#[cfg(feature = "serde")]
 use super::impl_int_serde;
 use super::num_consts::NumConsts;
 
@@ -30,7 +31,7 @@ use super::num_consts::NumConsts;
 /// endian bytes:
 ///
 /// ```
-/// # use cosmwasm_std::Int512;
+/// # use cosmwasm_core::Int512;
 /// let a = Int512::from(258u128);
 /// let b = Int512::new([
 ///     0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8,
@@ -44,9 +45,11 @@ use super::num_consts::NumConsts;
 /// ]);
 /// assert_eq!(a, b);
 /// ```
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct Int512(#[schemars(with = "String")] pub(crate) I512);
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Int512(#[cfg_attr(feature = "schemars", schemars(with = "String"))] pub(crate) I512);
 
+#[cfg(feature = "serde")]
 impl_int_serde!(Int512);
 forward_ref_partial_eq!(Int512, Int512);
 
@@ -9,14 +9,15 @@ use core::str::FromStr;
 use crate::errors::{DivideByZeroError, DivisionError, OverflowError, OverflowOperation, StdError};
 use crate::forward_ref::{forward_ref_binop, forward_ref_op_assign};
 use crate::{
-    CheckedMultiplyRatioError, Int128, Int256, Int512, Uint128, Uint256, Uint512, Uint64,
-    __internal::forward_ref_partial_eq,
+    __internal::forward_ref_partial_eq, CheckedMultiplyRatioError, Int128, Int256, Int512, Uint128,
+    Uint256, Uint512, Uint64,
 };
 
 use super::conversion::{
     forward_try_from, from_and_to_bytes, primitive_to_wrapped_int, try_from_int_to_int,
     wrapped_int_to_primitive,
 };
+#[cfg(feature = "serde")]
 use super::impl_int_serde;
 use super::num_consts::NumConsts;
 
@@ -29,13 +30,15 @@ use super::num_consts::NumConsts;
 /// Use `from` to create instances of this and `i64` to get the value out:
 ///
 /// ```
-/// # use cosmwasm_std::Int64;
+/// # use cosmwasm_core::Int64;
 /// let a = Int64::from(258i64);
 /// assert_eq!(a.i64(), 258);
 /// ```
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct Int64(#[schemars(with = "String")] pub(crate) i64);
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Int64(#[cfg_attr(feature = "schemars", schemars(with = "String"))] pub(crate) i64);
 
+#[cfg(feature = "serde")]
 impl_int_serde!(Int64);
 forward_ref_partial_eq!(Int64, Int64);
 
@@ -114,7 +117,7 @@ impl Int64 {
     /// # Examples
     ///
     /// ```
-    /// use cosmwasm_std::Int64;
+    /// use cosmwasm_core::Int64;
     ///
     /// let a = Int64::MAX;
     /// let result = a.full_mul(2i32);
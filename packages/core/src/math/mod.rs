@@ -1,6 +1,7 @@
 mod conversion;
 mod decimal;
 mod decimal256;
+mod division;
 mod fraction;
 mod int128;
 mod int256;
@@ -8,6 +9,7 @@ mod int512;
 mod int64;
 mod isqrt;
 mod num_consts;
+mod scientific;
 mod signed_decimal;
 mod signed_decimal_256;
 mod uint128;
@@ -30,6 +32,7 @@ pub use uint256::Uint256;
 pub use uint512::Uint512;
 pub use uint64::Uint64;
 
+#[cfg(feature = "serde")]
 macro_rules! impl_int_serde {
     ($ty:ty) => {
         impl ::serde::Serialize for $ty {
@@ -52,18 +55,18 @@ macro_rules! impl_int_serde {
             /// Serialize to JSON:
             ///
             /// ```
-            /// # use cosmwasm_std::{to_json_vec, Uint64};
+            /// # use cosmwasm_core::Uint64;
             /// let value = Uint64::new(17);
-            /// let serialized = to_json_vec(&value).unwrap();
+            /// let serialized = serde_json::to_vec(&value).unwrap();
             /// assert_eq!(serialized, b"\"17\"");
             /// ```
             ///
             /// Serialize to MessagePack:
             ///
             /// ```
-            /// # use cosmwasm_std::{to_msgpack_vec, Uint64};
+            /// # use cosmwasm_core::Uint64;
             /// let value = Uint64::new(17);
-            /// let serialized = to_msgpack_vec(&value).unwrap();
+            /// let serialized = rmp_serde::to_vec_named(&value).unwrap();
             /// assert_eq!(serialized, [0b10100000 ^ 2, b'1', b'7']); // string of lengths 2 with value "17"
             /// ```
             fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -110,6 +113,7 @@ macro_rules! impl_int_serde {
         }
     };
 }
+#[cfg(feature = "serde")]
 use impl_int_serde;
 
 #[cfg(test)]
@@ -0,0 +1,285 @@
+//! Parsing of decimal strings in scientific notation (e.g. "1.234e-5"),
+//! shared between all decimal types.
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use crate::errors::StdError;
+
+/// Any number with more digits than this exceeds the range of all decimal types.
+/// Used to reject huge exponents before allocating the digits string.
+const MAX_ATOMICS_DIGITS: usize = 80;
+
+/// The result of [`parse_scientific`]
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ScientificAtomics {
+    /// True if and only if the value is negative. Always false for zero.
+    pub negative: bool,
+    /// The absolute value in atomic units as a string of decimal digits
+    pub digits: String,
+}
+
+impl ScientificAtomics {
+    /// Returns the atomics as a signed integer string, e.g. "-123"
+    pub fn to_signed_string(&self) -> String {
+        if self.negative {
+            format!("-{}", self.digits)
+        } else {
+            self.digits.clone()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ScientificError {
+    /// The input is not a valid number in scientific notation
+    Invalid(&'static str),
+    /// The value does not fit into the decimal type
+    RangeExceeded,
+    /// The value has more fractional digits than the decimal type supports
+    TooManyFractionalDigits,
+    /// The absolute value is larger than zero but smaller than the smallest positive value
+    /// of the decimal type
+    TooSmall,
+}
+
+impl ScientificError {
+    /// Converts into a [`StdError`], using the given range exceeded error
+    /// of the decimal type for [`ScientificError::RangeExceeded`].
+    pub fn into_std_error(
+        self,
+        range_exceeded: impl fmt::Display,
+        decimal_places: u32,
+    ) -> StdError {
+        match self {
+            ScientificError::Invalid(msg) => StdError::generic_err(msg),
+            ScientificError::RangeExceeded => StdError::generic_err(range_exceeded.to_string()),
+            ScientificError::TooManyFractionalDigits => StdError::generic_err(format!(
+                "Cannot represent more than {decimal_places} fractional digits"
+            )),
+            ScientificError::TooSmall => StdError::generic_err(format!(
+                "Value is too small to be represented with {decimal_places} fractional digits"
+            )),
+        }
+    }
+}
+
+/// Parses a decimal string with an optional exponent into atomic units with
+/// the given number of decimal places.
+///
+/// Accepted inputs consist of an optional sign (`+` or `-`), a whole part, an optional
+/// fractional part separated by `.` and an optional exponent introduced by `e` or `E`,
+/// e.g. "1", "+1.5", "-0.001", "1.234e-5", "12E+3".
+///
+/// Digits that do not fit into the decimal places are an error unless `truncate` is true,
+/// in which case the value is rounded towards zero.
+pub(crate) fn parse_scientific(
+    input: &str,
+    decimal_places: u32,
+    signed: bool,
+    truncate: bool,
+) -> Result<ScientificAtomics, ScientificError> {
+    let (negative, unsigned) = if let Some(rest) = input.strip_prefix('-') {
+        (true, rest)
+    } else if let Some(rest) = input.strip_prefix('+') {
+        (false, rest)
+    } else {
+        (false, input)
+    };
+    if negative && !signed {
+        return Err(ScientificError::Invalid(
+            "Negative values are not supported by this type",
+        ));
+    }
+
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => {
+            // `i32::from_str` would accept a second sign, so we check digits ourselves
+            let exponent_digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            if exponent_digits.is_empty() || !exponent_digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ScientificError::Invalid("Error parsing exponent"));
+            }
+            let exponent = exponent
+                .parse::<i32>()
+                .map_err(|_| ScientificError::Invalid("Exponent out of range"))?;
+            (mantissa, exponent)
+        }
+        None => (unsigned, 0),
+    };
+
+    let (whole, fractional) = match mantissa.split_once('.') {
+        Some((_, "")) => return Err(ScientificError::Invalid("Error parsing fractional")),
+        Some((whole, fractional)) => (whole, fractional),
+        None => (mantissa, ""),
+    };
+    if whole.is_empty() || !whole.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ScientificError::Invalid("Error parsing whole"));
+    }
+    if !fractional.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ScientificError::Invalid("Error parsing fractional"));
+    }
+
+    let mut digits = String::with_capacity(whole.len() + fractional.len());
+    digits.push_str(whole.trim_start_matches('0'));
+    digits.push_str(fractional);
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(ScientificAtomics {
+            negative: false,
+            digits: "0".to_string(),
+        });
+    }
+
+    // The value is `digits * 10^shift` atomic units.
+    // This cannot overflow since all summands are in the i32/u32 range.
+    let shift = i64::from(exponent) + i64::from(decimal_places) - fractional.len() as i64;
+    let atomics = if shift >= 0 {
+        if digits.len() as i64 + shift > MAX_ATOMICS_DIGITS as i64 {
+            return Err(ScientificError::RangeExceeded);
+        }
+        let mut atomics = String::with_capacity(digits.len() + shift as usize);
+        atomics.push_str(digits);
+        atomics.push_str(&"0".repeat(shift as usize));
+        atomics
+    } else {
+        let dropped_count = shift.unsigned_abs().min(digits.len() as u64) as usize;
+        let (kept, dropped) = digits.split_at(digits.len() - dropped_count);
+        if !truncate && dropped.bytes().any(|b| b != b'0') {
+            return Err(if kept.is_empty() {
+                ScientificError::TooSmall
+            } else {
+                ScientificError::TooManyFractionalDigits
+            });
+        }
+        if kept.is_empty() {
+            return Ok(ScientificAtomics {
+                negative: false,
+                digits: "0".to_string(),
+            });
+        }
+        kept.to_string()
+    };
+
+    Ok(ScientificAtomics {
+        negative,
+        digits: atomics,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<ScientificAtomics, ScientificError> {
+        parse_scientific(input, 18, true, false)
+    }
+
+    fn atomics(negative: bool, digits: &str) -> Result<ScientificAtomics, ScientificError> {
+        Ok(ScientificAtomics {
+            negative,
+            digits: digits.to_string(),
+        })
+    }
+
+    #[test]
+    fn parse_scientific_works() {
+        assert_eq!(parse("1"), atomics(false, "1000000000000000000"));
+        assert_eq!(parse("+1"), atomics(false, "1000000000000000000"));
+        assert_eq!(parse("-1"), atomics(true, "1000000000000000000"));
+        assert_eq!(parse("1.5"), atomics(false, "1500000000000000000"));
+        assert_eq!(parse("1.234e-5"), atomics(false, "12340000000000"));
+        assert_eq!(parse("1.234E-5"), atomics(false, "12340000000000"));
+        assert_eq!(parse("12e+3"), atomics(false, "12000000000000000000000"));
+        assert_eq!(parse("0012.50e1"), atomics(false, "125000000000000000000"));
+        assert_eq!(
+            parse("1e18"),
+            atomics(false, "1000000000000000000000000000000000000")
+        );
+        assert_eq!(parse("1e-18"), atomics(false, "1"));
+        assert_eq!(parse("10e-19"), atomics(false, "1"));
+        assert_eq!(
+            parse("1.000000000000000000000e0"),
+            atomics(false, "1000000000000000000")
+        );
+    }
+
+    #[test]
+    fn parse_scientific_normalizes_zero() {
+        assert_eq!(parse("0"), atomics(false, "0"));
+        assert_eq!(parse("-0"), atomics(false, "0"));
+        assert_eq!(parse("-0.000e5"), atomics(false, "0"));
+        assert_eq!(parse("0e-1000"), atomics(false, "0"));
+        assert_eq!(parse("0e1000"), atomics(false, "0"));
+        assert_eq!(
+            parse_scientific("-1e-19", 18, true, true),
+            atomics(false, "0")
+        );
+    }
+
+    #[test]
+    fn parse_scientific_handles_precision() {
+        assert_eq!(parse("1e-19"), Err(ScientificError::TooSmall));
+        assert_eq!(parse("0.9e-18"), Err(ScientificError::TooSmall));
+        assert_eq!(parse("1e-2147483648"), Err(ScientificError::TooSmall));
+        assert_eq!(
+            parse("1.0000000000000000001"),
+            Err(ScientificError::TooManyFractionalDigits)
+        );
+        assert_eq!(
+            parse("1.5e-18"),
+            Err(ScientificError::TooManyFractionalDigits)
+        );
+
+        // truncating
+        let parse_truncated = |input| parse_scientific(input, 18, true, true);
+        assert_eq!(parse_truncated("1e-19"), atomics(false, "0"));
+        assert_eq!(parse_truncated("1.5e-18"), atomics(false, "1"));
+        assert_eq!(parse_truncated("-1.9e-18"), atomics(true, "1"));
+        assert_eq!(
+            parse_truncated("1.0000000000000000001"),
+            atomics(false, "1000000000000000000")
+        );
+    }
+
+    #[test]
+    fn parse_scientific_handles_range() {
+        assert_eq!(parse("1e2147483647"), Err(ScientificError::RangeExceeded));
+        assert_eq!(
+            parse("1e61"),
+            atomics(false, &("1".to_string() + &"0".repeat(79)))
+        );
+        assert_eq!(parse("1e62"), Err(ScientificError::RangeExceeded));
+        assert_eq!(
+            parse("1e2147483648"),
+            Err(ScientificError::Invalid("Exponent out of range"))
+        );
+    }
+
+    #[test]
+    fn parse_scientific_rejects_invalid_input() {
+        for input in [
+            "", "+", "-", ".", "e", "e5", ".5", "1.", "1..5", "1.5.", "1.5.0", "1e", "1e+", "1e-",
+            "1e+-5", "1e5.0", "1e5e5", "--1", "+-1", "1 ", " 1", "1,5", "0x10", "1_000", "abc",
+            "1.e5", "inf", "NaN",
+        ] {
+            assert!(
+                matches!(parse(input), Err(ScientificError::Invalid(_))),
+                "Input {input:?} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_scientific_rejects_negative_for_unsigned() {
+        assert_eq!(
+            parse_scientific("-1", 18, false, false),
+            Err(ScientificError::Invalid(
+                "Negative values are not supported by this type"
+            ))
+        );
+        assert_eq!(
+            parse_scientific("+1", 18, false, false),
+            atomics(false, "1000000000000000000")
+        );
+    }
+}
@@ -5,6 +5,7 @@ use core::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
 use core::str::FromStr;
+#[cfg(feature = "serde")]
 use serde::{de, ser, Deserialize, Deserializer, Serialize};
 
 use crate::errors::{
@@ -12,8 +13,9 @@ use crate::errors::{
     OverflowOperation, RoundDownOverflowError, RoundUpOverflowError, StdError,
 };
 use crate::forward_ref::{forward_ref_binop, forward_ref_op_assign};
-use crate::{Decimal, Decimal256, Int256, SignedDecimal256, __internal::forward_ref_partial_eq};
+use crate::{__internal::forward_ref_partial_eq, Decimal, Decimal256, Int256, SignedDecimal256};
 
+use super::scientific::parse_scientific;
 use super::Fraction;
 use super::Int128;
 
@@ -21,15 +23,24 @@ use super::Int128;
 ///
 /// The greatest possible value that can be represented is 170141183460469231731.687303715884105727 (which is (2^127 - 1) / 10^18)
 /// and the smallest is -170141183460469231731.687303715884105728 (which is -2^127 / 10^18).
-#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct SignedDecimal(#[schemars(with = "String")] Int128);
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SignedDecimal(#[cfg_attr(feature = "schemars", schemars(with = "String"))] Int128);
 
 forward_ref_partial_eq!(SignedDecimal, SignedDecimal);
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-#[error("SignedDecimal range exceeded")]
+#[derive(Debug, PartialEq, Eq)]
 pub struct SignedDecimalRangeExceeded;
 
+impl fmt::Display for SignedDecimalRangeExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SignedDecimal range exceeded")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignedDecimalRangeExceeded {}
+
 impl SignedDecimal {
     const DECIMAL_FRACTIONAL: Int128 = Int128::new(1_000_000_000_000_000_000i128); // 1*10**18
     const DECIMAL_FRACTIONAL_SQUARED: Int128 =
@@ -44,7 +55,7 @@ impl SignedDecimal {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal;
+    /// # use cosmwasm_core::SignedDecimal;
     /// assert_eq!(SignedDecimal::MAX.to_string(), "170141183460469231731.687303715884105727");
     /// ```
     pub const MAX: Self = Self(Int128::MAX);
@@ -54,7 +65,7 @@ impl SignedDecimal {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal;
+    /// # use cosmwasm_core::SignedDecimal;
     /// assert_eq!(SignedDecimal::MIN.to_string(), "-170141183460469231731.687303715884105728");
     /// ```
     pub const MIN: Self = Self(Int128::MIN);
@@ -65,7 +76,7 @@ impl SignedDecimal {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{SignedDecimal, Int128};
+    /// # use cosmwasm_core::{SignedDecimal, Int128};
     /// assert_eq!(SignedDecimal::new(Int128::one()).to_string(), "0.000000000000000001");
     /// ```
     pub const fn new(value: Int128) -> Self {
@@ -78,7 +89,7 @@ impl SignedDecimal {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal;
+    /// # use cosmwasm_core::SignedDecimal;
     /// assert_eq!(SignedDecimal::raw(1234i128).to_string(), "0.000000000000001234");
     /// ```
     pub const fn raw(value: i128) -> Self {
@@ -129,7 +140,7 @@ impl SignedDecimal {
     /// ## Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{SignedDecimal, Int128};
+    /// # use cosmwasm_core::{SignedDecimal, Int128};
     /// let a = SignedDecimal::from_atomics(Int128::new(1234), 3).unwrap();
     /// assert_eq!(a.to_string(), "1.234");
     ///
@@ -173,12 +184,68 @@ impl SignedDecimal {
         })
     }
 
+    /// Creates a SignedDecimal from a string in decimal or scientific notation,
+    /// e.g. "1.5", "1.5e-3", "15E-4" or "+0.15e-2".
+    ///
+    /// The input consists of an optional sign (`+` or `-`), a whole part, an optional fractional
+    /// part and an optional exponent introduced by `e` or `E`.
+    ///
+    /// Unlike [`FromStr`], this accepts values with more than
+    /// [`DECIMAL_PLACES`](Self::DECIMAL_PLACES) fractional digits as long as the
+    /// additional digits are zero. Values that cannot be represented exactly result in an error.
+    /// Use [`SignedDecimal::from_scientific_truncated`] to round towards zero instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::SignedDecimal;
+    /// let a = SignedDecimal::from_scientific("-1.5e-3").unwrap();
+    /// assert_eq!(a.to_string(), "-0.0015");
+    ///
+    /// let a = SignedDecimal::from_scientific("1e18").unwrap();
+    /// assert_eq!(a.to_string(), "1000000000000000000");
+    ///
+    /// // smaller than the smallest positive value
+    /// assert!(SignedDecimal::from_scientific("1e-19").is_err());
+    /// ```
+    pub fn from_scientific(input: &str) -> Result<Self, StdError> {
+        Self::parse_scientific(input, false)
+    }
+
+    /// Like [`SignedDecimal::from_scientific`] but rounds towards zero if the value
+    /// has more than [`DECIMAL_PLACES`](Self::DECIMAL_PLACES) fractional digits.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::SignedDecimal;
+    /// let a = SignedDecimal::from_scientific_truncated("1.9e-18").unwrap();
+    /// assert_eq!(a, SignedDecimal::raw(1));
+    ///
+    /// let a = SignedDecimal::from_scientific_truncated("1e-19").unwrap();
+    /// assert_eq!(a, SignedDecimal::zero());
+    /// ```
+    pub fn from_scientific_truncated(input: &str) -> Result<Self, StdError> {
+        Self::parse_scientific(input, true)
+    }
+
+    fn parse_scientific(input: &str, truncate: bool) -> Result<Self, StdError> {
+        let atomics = parse_scientific(input, Self::DECIMAL_PLACES, true, truncate)
+            .map_err(|err| err.into_std_error(SignedDecimalRangeExceeded, Self::DECIMAL_PLACES))?;
+        // parse_scientific limits the number of digits but not the exact range of the type
+        atomics
+            .to_signed_string()
+            .parse::<Int128>()
+            .map(Self)
+            .map_err(|_| StdError::generic_err(SignedDecimalRangeExceeded.to_string()))
+    }
+
     /// Returns the ratio (numerator / denominator) as a SignedDecimal
     ///
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal;
+    /// # use cosmwasm_core::SignedDecimal;
     /// assert_eq!(
     ///     SignedDecimal::from_ratio(1, 3).to_string(),
     ///     "0.333333333333333333"
@@ -199,7 +266,7 @@ impl SignedDecimal {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{SignedDecimal, CheckedFromRatioError};
+    /// # use cosmwasm_core::{SignedDecimal, CheckedFromRatioError};
     /// assert_eq!(
     ///     SignedDecimal::checked_from_ratio(1, 3).unwrap().to_string(),
     ///     "0.333333333333333333"
@@ -245,7 +312,7 @@ impl SignedDecimal {
     /// ## Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{SignedDecimal, Int128};
+    /// # use cosmwasm_core::{SignedDecimal, Int128};
     /// # use core::str::FromStr;
     /// // Value with whole and fractional part
     /// let a = SignedDecimal::from_str("1.234").unwrap();
@@ -278,7 +345,7 @@ impl SignedDecimal {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal;
+    /// # use cosmwasm_core::SignedDecimal;
     /// # use core::str::FromStr;
     /// assert!(SignedDecimal::from_str("0.6").unwrap().trunc().is_zero());
     /// assert_eq!(SignedDecimal::from_str("-5.8").unwrap().trunc().to_string(), "-5");
@@ -293,7 +360,7 @@ impl SignedDecimal {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal;
+    /// # use cosmwasm_core::SignedDecimal;
     /// # use core::str::FromStr;
     /// assert!(SignedDecimal::from_str("0.6").unwrap().floor().is_zero());
     /// assert_eq!(SignedDecimal::from_str("-5.2").unwrap().floor().to_string(), "-6");
@@ -328,7 +395,7 @@ impl SignedDecimal {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal;
+    /// # use cosmwasm_core::SignedDecimal;
     /// # use core::str::FromStr;
     /// assert_eq!(SignedDecimal::from_str("0.2").unwrap().ceil(), SignedDecimal::one());
     /// assert_eq!(SignedDecimal::from_str("-5.8").unwrap().ceil().to_string(), "-5");
@@ -482,7 +549,7 @@ impl SignedDecimal {
     ///
     /// ```
     /// use core::str::FromStr;
-    /// use cosmwasm_std::{SignedDecimal, Int128};
+    /// use cosmwasm_core::{SignedDecimal, Int128};
     ///
     /// let d = SignedDecimal::from_str("12.345").unwrap();
     /// assert_eq!(d.to_int_floor(), Int128::new(12));
@@ -514,7 +581,7 @@ impl SignedDecimal {
     ///
     /// ```
     /// use core::str::FromStr;
-    /// use cosmwasm_std::{SignedDecimal, Int128};
+    /// use cosmwasm_core::{SignedDecimal, Int128};
     ///
     /// let d = SignedDecimal::from_str("12.345").unwrap();
     /// assert_eq!(d.to_int_trunc(), Int128::new(12));
@@ -537,7 +604,7 @@ impl SignedDecimal {
     ///
     /// ```
     /// use core::str::FromStr;
-    /// use cosmwasm_std::{SignedDecimal, Int128};
+    /// use cosmwasm_core::{SignedDecimal, Int128};
     ///
     /// let d = SignedDecimal::from_str("12.345").unwrap();
     /// assert_eq!(d.to_int_ceil(), Int128::new(13));
@@ -864,6 +931,7 @@ where
 }
 
 /// Serializes as a decimal string
+#[cfg(feature = "serde")]
 impl Serialize for SignedDecimal {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -874,6 +942,7 @@ impl Serialize for SignedDecimal {
 }
 
 /// Deserializes as a base64 string
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for SignedDecimal {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -883,8 +952,10 @@ impl<'de> Deserialize<'de> for SignedDecimal {
     }
 }
 
+#[cfg(feature = "serde")]
 struct SignedDecimalVisitor;
 
+#[cfg(feature = "serde")]
 impl de::Visitor<'_> for SignedDecimalVisitor {
     type Value = SignedDecimal;
 
@@ -906,6 +977,7 @@ impl de::Visitor<'_> for SignedDecimalVisitor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::{prop_assert_eq, property_test};
 
     use alloc::vec::Vec;
 
@@ -1469,6 +1541,139 @@ mod tests {
         assert_eq!(SignedDecimal::try_from(max).unwrap(), SignedDecimal::MAX);
     }
 
+    #[test]
+    fn signed_decimal_from_scientific_works() {
+        assert_eq!(
+            SignedDecimal::from_scientific("1").unwrap(),
+            SignedDecimal::one()
+        );
+        assert_eq!(SignedDecimal::from_scientific("+1.5").unwrap(), dec("1.5"));
+        assert_eq!(
+            SignedDecimal::from_scientific("-1.5e-3").unwrap(),
+            dec("-0.0015")
+        );
+        assert_eq!(
+            SignedDecimal::from_scientific("-15E-4").unwrap(),
+            dec("-0.0015")
+        );
+        assert_eq!(
+            SignedDecimal::from_scientific("1e18").unwrap(),
+            dec("1000000000000000000")
+        );
+        assert_eq!(
+            SignedDecimal::from_scientific("-1e-18").unwrap(),
+            SignedDecimal::raw(-1)
+        );
+        assert_eq!(
+            SignedDecimal::from_scientific("-0e-100").unwrap(),
+            SignedDecimal::zero()
+        );
+        assert_eq!(
+            SignedDecimal::from_scientific("170141183460469231731687303715884105727e-18").unwrap(),
+            SignedDecimal::MAX
+        );
+        assert_eq!(
+            SignedDecimal::from_scientific("-170141183460469231731687303715884105728e-18").unwrap(),
+            SignedDecimal::MIN
+        );
+    }
+
+    #[test]
+    fn signed_decimal_from_scientific_errors() {
+        let err = |input| SignedDecimal::from_scientific(input).unwrap_err();
+
+        assert_eq!(err(""), StdError::generic_err("Error parsing whole"));
+        assert_eq!(err("-"), StdError::generic_err("Error parsing whole"));
+        assert_eq!(err("--1"), StdError::generic_err("Error parsing whole"));
+        assert_eq!(err("+-1"), StdError::generic_err("Error parsing whole"));
+        assert_eq!(
+            err("-1."),
+            StdError::generic_err("Error parsing fractional")
+        );
+        assert_eq!(err("-1e"), StdError::generic_err("Error parsing exponent"));
+
+        let range_exceeded = StdError::generic_err("SignedDecimal range exceeded");
+        assert_eq!(err("1e21"), range_exceeded);
+        assert_eq!(err("-1e21"), range_exceeded);
+        assert_eq!(
+            err("170141183460469231731687303715884105728e-18"),
+            range_exceeded
+        );
+        assert_eq!(
+            err("-170141183460469231731687303715884105729e-18"),
+            range_exceeded
+        );
+
+        assert_eq!(
+            err("-1e-19"),
+            StdError::generic_err("Value is too small to be represented with 18 fractional digits")
+        );
+        assert_eq!(
+            err("-1.5e-18"),
+            StdError::generic_err("Cannot represent more than 18 fractional digits")
+        );
+    }
+
+    #[test]
+    fn signed_decimal_from_scientific_truncated_works() {
+        assert_eq!(
+            SignedDecimal::from_scientific_truncated("-1e-19").unwrap(),
+            SignedDecimal::zero()
+        );
+        assert_eq!(
+            SignedDecimal::from_scientific_truncated("1.9e-18").unwrap(),
+            SignedDecimal::raw(1)
+        );
+        // rounds towards zero
+        assert_eq!(
+            SignedDecimal::from_scientific_truncated("-1.9e-18").unwrap(),
+            SignedDecimal::raw(-1)
+        );
+        assert!(SignedDecimal::from_scientific_truncated("-1e21").is_err());
+    }
+
+    #[property_test]
+    fn signed_decimal_from_scientific_roundtrips_display(atomics: i128) {
+        let value = SignedDecimal::raw(atomics);
+        prop_assert_eq!(
+            SignedDecimal::from_scientific(&value.to_string()),
+            Ok(value)
+        );
+        prop_assert_eq!(
+            SignedDecimal::from_scientific(&format!("{value}e0")),
+            Ok(value)
+        );
+        prop_assert_eq!(
+            SignedDecimal::from_scientific(&format!("{atomics}e-18")),
+            Ok(value)
+        );
+    }
+
+    #[property_test]
+    fn signed_decimal_from_scientific_matches_reference(mantissa: i64, exp: i8) {
+        let exp = i32::from(exp % 40);
+        let input = format!("{mantissa}e{exp}");
+        // the value in atomics is mantissa * 10^shift
+        let shift = exp + 18;
+        let (strict, truncated) = if shift >= 0 {
+            let atomics = Int256::from(mantissa) * Int256::from(10u8).pow(shift as u32);
+            let value = Int128::try_from(atomics).ok().map(SignedDecimal::new);
+            (value, value)
+        } else {
+            let divisor = Int128::from(10u8).pow(shift.unsigned_abs());
+            let mantissa = Int128::from(mantissa);
+            let value = Some(SignedDecimal::new(mantissa / divisor));
+            let exact = (mantissa % divisor).is_zero();
+            (if exact { value } else { None }, value)
+        };
+
+        prop_assert_eq!(SignedDecimal::from_scientific(&input).ok(), strict);
+        prop_assert_eq!(
+            SignedDecimal::from_scientific_truncated(&input).ok(),
+            truncated
+        );
+    }
+
     #[test]
     fn signed_decimal_atomics_works() {
         let zero = SignedDecimal::zero();
@@ -5,6 +5,7 @@ use core::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
 use core::str::FromStr;
+#[cfg(feature = "serde")]
 use serde::{de, ser, Deserialize, Deserializer, Serialize};
 
 use crate::errors::{
@@ -12,8 +13,9 @@ use crate::errors::{
     OverflowOperation, RoundDownOverflowError, RoundUpOverflowError, StdError,
 };
 use crate::forward_ref::{forward_ref_binop, forward_ref_op_assign};
-use crate::{Decimal, Decimal256, Int512, SignedDecimal, __internal::forward_ref_partial_eq};
+use crate::{__internal::forward_ref_partial_eq, Decimal, Decimal256, Int512, SignedDecimal};
 
+use super::scientific::parse_scientific;
 use super::Fraction;
 use super::Int256;
 
@@ -26,15 +28,24 @@ use super::Int256;
 /// and the smallest is
 /// -57896044618658097711785492504343953926634992332820282019728.792003956564819968
 /// (which is -2^255 / 10^18).
-#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct SignedDecimal256(#[schemars(with = "String")] Int256);
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SignedDecimal256(#[cfg_attr(feature = "schemars", schemars(with = "String"))] Int256);
 
 forward_ref_partial_eq!(SignedDecimal256, SignedDecimal256);
 
-#[derive(Debug, PartialEq, Eq, thiserror::Error)]
-#[error("SignedDecimal256 range exceeded")]
+#[derive(Debug, PartialEq, Eq)]
 pub struct SignedDecimal256RangeExceeded;
 
+impl fmt::Display for SignedDecimal256RangeExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SignedDecimal256 range exceeded")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignedDecimal256RangeExceeded {}
+
 impl SignedDecimal256 {
     const DECIMAL_FRACTIONAL: Int256 = // 1*10**18
         Int256::from_i128(1_000_000_000_000_000_000);
@@ -50,7 +61,7 @@ impl SignedDecimal256 {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal256;
+    /// # use cosmwasm_core::SignedDecimal256;
     /// assert_eq!(
     ///     SignedDecimal256::MAX.to_string(),
     ///     "57896044618658097711785492504343953926634992332820282019728.792003956564819967"
@@ -63,7 +74,7 @@ impl SignedDecimal256 {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal256;
+    /// # use cosmwasm_core::SignedDecimal256;
     /// assert_eq!(
     ///     SignedDecimal256::MIN.to_string(),
     ///     "-57896044618658097711785492504343953926634992332820282019728.792003956564819968"
@@ -77,7 +88,7 @@ impl SignedDecimal256 {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{SignedDecimal256, Int256};
+    /// # use cosmwasm_core::{SignedDecimal256, Int256};
     /// assert_eq!(SignedDecimal256::new(Int256::one()).to_string(), "0.000000000000000001");
     /// ```
     pub const fn new(value: Int256) -> Self {
@@ -90,7 +101,7 @@ impl SignedDecimal256 {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal256;
+    /// # use cosmwasm_core::SignedDecimal256;
     /// assert_eq!(SignedDecimal256::raw(1234i128).to_string(), "0.000000000000001234");
     /// ```
     pub const fn raw(value: i128) -> Self {
@@ -142,7 +153,7 @@ impl SignedDecimal256 {
     /// ## Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{SignedDecimal256, Int256};
+    /// # use cosmwasm_core::{SignedDecimal256, Int256};
     /// let a = SignedDecimal256::from_atomics(Int256::from(1234), 3).unwrap();
     /// assert_eq!(a.to_string(), "1.234");
     ///
@@ -186,12 +197,70 @@ impl SignedDecimal256 {
         })
     }
 
+    /// Creates a SignedDecimal256 from a string in decimal or scientific notation,
+    /// e.g. "1.5", "1.5e-3", "15E-4" or "+0.15e-2".
+    ///
+    /// The input consists of an optional sign (`+` or `-`), a whole part, an optional fractional
+    /// part and an optional exponent introduced by `e` or `E`.
+    ///
+    /// Unlike [`FromStr`], this accepts values with more than
+    /// [`DECIMAL_PLACES`](Self::DECIMAL_PLACES) fractional digits as long as the
+    /// additional digits are zero. Values that cannot be represented exactly result in an error.
+    /// Use [`SignedDecimal256::from_scientific_truncated`] to round towards zero instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::SignedDecimal256;
+    /// let a = SignedDecimal256::from_scientific("-1.5e-3").unwrap();
+    /// assert_eq!(a.to_string(), "-0.0015");
+    ///
+    /// let a = SignedDecimal256::from_scientific("1e18").unwrap();
+    /// assert_eq!(a.to_string(), "1000000000000000000");
+    ///
+    /// // smaller than the smallest positive value
+    /// assert!(SignedDecimal256::from_scientific("1e-19").is_err());
+    /// ```
+    pub fn from_scientific(input: &str) -> Result<Self, StdError> {
+        Self::parse_scientific(input, false)
+    }
+
+    /// Like [`SignedDecimal256::from_scientific`] but rounds towards zero if the value
+    /// has more than [`DECIMAL_PLACES`](Self::DECIMAL_PLACES) fractional digits.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_core::SignedDecimal256;
+    /// let a = SignedDecimal256::from_scientific_truncated("1.9e-18").unwrap();
+    /// assert_eq!(a, SignedDecimal256::raw(1));
+    ///
+    /// let a = SignedDecimal256::from_scientific_truncated("1e-19").unwrap();
+    /// assert_eq!(a, SignedDecimal256::zero());
+    /// ```
+    pub fn from_scientific_truncated(input: &str) -> Result<Self, StdError> {
+        Self::parse_scientific(input, true)
+    }
+
+    fn parse_scientific(input: &str, truncate: bool) -> Result<Self, StdError> {
+        let atomics =
+            parse_scientific(input, Self::DECIMAL_PLACES, true, truncate).map_err(|err| {
+                err.into_std_error(SignedDecimal256RangeExceeded, Self::DECIMAL_PLACES)
+            })?;
+        // parse_scientific limits the number of digits but not the exact range of the type
+        atomics
+            .to_signed_string()
+            .parse::<Int256>()
+            .map(Self)
+            .map_err(|_| StdError::generic_err(SignedDecimal256RangeExceeded.to_string()))
+    }
+
     /// Returns the ratio (numerator / denominator) as a SignedDecimal256
     ///
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal256;
+    /// # use cosmwasm_core::SignedDecimal256;
     /// assert_eq!(
     ///     SignedDecimal256::from_ratio(1, 3).to_string(),
     ///     "0.333333333333333333"
@@ -212,7 +281,7 @@ impl SignedDecimal256 {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{SignedDecimal256, CheckedFromRatioError};
+    /// # use cosmwasm_core::{SignedDecimal256, CheckedFromRatioError};
     /// assert_eq!(
     ///     SignedDecimal256::checked_from_ratio(1, 3).unwrap().to_string(),
     ///     "0.333333333333333333"
@@ -258,7 +327,7 @@ impl SignedDecimal256 {
     /// ## Examples
     ///
     /// ```
-    /// # use cosmwasm_std::{SignedDecimal256, Int256};
+    /// # use cosmwasm_core::{SignedDecimal256, Int256};
     /// # use core::str::FromStr;
     /// // Value with whole and fractional part
     /// let a = SignedDecimal256::from_str("1.234").unwrap();
@@ -291,7 +360,7 @@ impl SignedDecimal256 {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal256;
+    /// # use cosmwasm_core::SignedDecimal256;
     /// # use core::str::FromStr;
     /// assert!(SignedDecimal256::from_str("0.6").unwrap().trunc().is_zero());
     /// assert_eq!(SignedDecimal256::from_str("-5.8").unwrap().trunc().to_string(), "-5");
@@ -306,7 +375,7 @@ impl SignedDecimal256 {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal256;
+    /// # use cosmwasm_core::SignedDecimal256;
     /// # use core::str::FromStr;
     /// assert!(SignedDecimal256::from_str("0.6").unwrap().floor().is_zero());
     /// assert_eq!(SignedDecimal256::from_str("-5.2").unwrap().floor().to_string(), "-6");
@@ -341,7 +410,7 @@ impl SignedDecimal256 {
     /// # Examples
     ///
     /// ```
-    /// # use cosmwasm_std::SignedDecimal256;
+    /// # use cosmwasm_core::SignedDecimal256;
     /// # use core::str::FromStr;
     /// assert_eq!(SignedDecimal256::from_str("0.2").unwrap().ceil(), SignedDecimal256::one());
     /// assert_eq!(SignedDecimal256::from_str("-5.8").unwrap().ceil().to_string(), "-5");
@@ -495,7 +564,7 @@ impl SignedDecimal256 {
     ///
     /// ```
     /// use core::str::FromStr;
-    /// use cosmwasm_std::{SignedDecimal256, Int256};
+    /// use cosmwasm_core::{SignedDecimal256, Int256};
     ///
     /// let d = SignedDecimal256::from_str("12.345").unwrap();
     /// assert_eq!(d.to_int_floor(), Int256::from(12));
@@ -527,7 +596,7 @@ impl SignedDecimal256 {
     ///
     /// ```
     /// use core::str::FromStr;
-    /// use cosmwasm_std::{SignedDecimal256, Int256};
+    /// use cosmwasm_core::{SignedDecimal256, Int256};
     ///
     /// let d = SignedDecimal256::from_str("12.345").unwrap();
     /// assert_eq!(d.to_int_trunc(), Int256::from(12));
@@ -550,7 +619,7 @@ impl SignedDecimal256 {
     ///
     /// ```
     /// use core::str::FromStr;
-    /// use cosmwasm_std::{SignedDecimal256, Int256};
+    /// use cosmwasm_core::{SignedDecimal256, Int256};
     ///
     /// let d = SignedDecimal256::from_str("12.345").unwrap();
     /// assert_eq!(d.to_int_ceil(), Int256::from(13));
@@ -865,6 +934,7 @@ where
 }
 
 /// Serializes as a decimal string
+#[cfg(feature = "serde")]
 impl Serialize for SignedDecimal256 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -875,6 +945,7 @@ impl Serialize for SignedDecimal256 {
 }
 
 /// Deserializes as a base64 string
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for SignedDecimal256 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -884,8 +955,10 @@ impl<'de> Deserialize<'de> for SignedDecimal256 {
     }
 }
 
+#[cfg(feature = "serde")]
 struct SignedDecimal256Visitor;
 
+#[cfg(feature = "serde")]
 impl de::Visitor<'_> for SignedDecimal256Visitor {
     type Value = SignedDecimal256;
 
@@ -1482,6 +1555,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn signed_decimal_256_from_scientific_works() {
+        assert_eq!(
+            SignedDecimal256::from_scientific("+1.5").unwrap(),
+            dec("1.5")
+        );
+        assert_eq!(
+            SignedDecimal256::from_scientific("-1.5e-3").unwrap(),
+            dec("-0.0015")
+        );
+        assert_eq!(
+            SignedDecimal256::from_scientific("-1e58").unwrap(),
+            dec("-10000000000000000000000000000000000000000000000000000000000")
+        );
+        assert_eq!(
+            SignedDecimal256::from_scientific("-1e-18").unwrap(),
+            SignedDecimal256::raw(-1)
+        );
+        assert_eq!(
+            SignedDecimal256::from_scientific("-0.0").unwrap(),
+            SignedDecimal256::zero()
+        );
+        assert_eq!(
+            SignedDecimal256::from_scientific(
+                "-57896044618658097711785492504343953926634992332820282019728792003956564819968e-18"
+            )
+            .unwrap(),
+            SignedDecimal256::MIN
+        );
+    }
+
+    #[test]
+    fn signed_decimal_256_from_scientific_errors() {
+        let err = |input| SignedDecimal256::from_scientific(input).unwrap_err();
+
+        assert_eq!(err("-"), StdError::generic_err("Error parsing whole"));
+        assert_eq!(
+            err("-1."),
+            StdError::generic_err("Error parsing fractional")
+        );
+        assert_eq!(err("-1E"), StdError::generic_err("Error parsing exponent"));
+
+        let range_exceeded = StdError::generic_err("SignedDecimal256 range exceeded");
+        assert_eq!(err("1e59"), range_exceeded);
+        assert_eq!(err("-1e59"), range_exceeded);
+        assert_eq!(
+            err("-57896044618658097711785492504343953926634992332820282019728792003956564819969e-18"),
+            range_exceeded
+        );
+
+        assert_eq!(
+            err("-1e-19"),
+            StdError::generic_err("Value is too small to be represented with 18 fractional digits")
+        );
+        assert_eq!(
+            err("1.5e-18"),
+            StdError::generic_err("Cannot represent more than 18 fractional digits")
+        );
+    }
+
+    #[test]
+    fn signed_decimal_256_from_scientific_truncated_works() {
+        assert_eq!(
+            SignedDecimal256::from_scientific_truncated("-1e-19").unwrap(),
+            SignedDecimal256::zero()
+        );
+        assert_eq!(
+            SignedDecimal256::from_scientific_truncated("-1.9e-18").unwrap(),
+            SignedDecimal256::raw(-1)
+        );
+    }
+
     #[test]
     fn signed_decimal_256_atomics_works() {
         let zero = SignedDecimal256::zero();
@@ -19,6 +19,7 @@ use crate::{
 use super::conversion::{
     forward_try_from, from_and_to_bytes, primitive_to_wrapped_int, wrapped_int_to_primitive,
 };
+#[cfg(feature = "serde")]
 use super::impl_int_serde;
 use super::num_consts::NumConsts;
 
@@ -31,7 +32,7 @@ use super::num_consts::NumConsts;
 /// Use `from` to create instances of this and `u128` to get the value out:
 ///
 /// ```
-/// # use cosmwasm_std::Uint128;
+/// # use cosmwasm_core::Uint128;
 /// let a = Uint128::from(123u128);
 /// assert_eq!(a.u128(), 123);
 ///
@@ -41,9 +42,11 @@ use super::num_consts::NumConsts;
 /// let c = Uint128::from(70u32);
 /// assert_eq!(c.u128(), 70);
 /// ```
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct Uint128(#[schemars(with = "String")] pub(crate) u128);
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Uint128(#[cfg_attr(feature = "schemars", schemars(with = "String"))] pub(crate) u128);
 
+#[cfg(feature = "serde")]
 impl_int_serde!(Uint128);
 forward_ref_partial_eq!(Uint128, Uint128);
 
@@ -145,7 +148,7 @@ impl Uint128 {
     /// # Examples
     ///
     /// ```
-    /// use cosmwasm_std::Uint128;
+    /// use cosmwasm_core::Uint128;
     ///
     /// let a = Uint128::MAX;
     /// let result = a.full_mul(2u32);
@@ -297,6 +300,33 @@ impl Uint128 {
             self.0 - other.0
         })
     }
+
+    /// Returns the greatest common divisor of `self` and `other`.
+    /// The greatest common divisor of 0 and 0 is defined to be 0.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        while !b.is_zero() {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    /// Returns the least common multiple of `self` and `other`,
+    /// which is 0 if one of them is 0.
+    pub fn checked_lcm(self, other: Self) -> Result<Self, OverflowError> {
+        if self.is_zero() || other.is_zero() {
+            return Ok(Self::zero());
+        }
+        (self / self.gcd(other)).checked_mul(other)
+    }
+
+    /// Same operation as `checked_lcm` except panicking on overflow.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn lcm(self, other: Self) -> Self {
+        self.checked_lcm(other)
+            .expect("attempt to multiply with overflow")
+    }
 }
 
 impl NumConsts for Uint128 {
@@ -1230,6 +1260,40 @@ mod tests {
         assert_eq!(b.abs_diff(a), expected);
     }
 
+    #[test]
+    fn uint128_gcd_works() {
+        let gcd = |a: u32, b: u32| Uint128::from(a).gcd(Uint128::from(b));
+        assert_eq!(gcd(0, 0), Uint128::zero());
+        assert_eq!(gcd(0, 7), Uint128::from(7u32));
+        assert_eq!(gcd(7, 0), Uint128::from(7u32));
+        assert_eq!(gcd(1, 1), Uint128::one());
+        assert_eq!(gcd(12, 18), Uint128::from(6u32));
+        assert_eq!(gcd(18, 12), Uint128::from(6u32));
+        assert_eq!(gcd(17, 5), Uint128::one());
+        assert_eq!(Uint128::MAX.gcd(Uint128::MAX), Uint128::MAX);
+        assert_eq!(Uint128::MAX.gcd(Uint128::one()), Uint128::one());
+    }
+
+    #[test]
+    fn uint128_lcm_works() {
+        let lcm = |a: u32, b: u32| Uint128::from(a).lcm(Uint128::from(b));
+        assert_eq!(lcm(0, 0), Uint128::zero());
+        assert_eq!(lcm(0, 7), Uint128::zero());
+        assert_eq!(lcm(4, 6), Uint128::from(12u32));
+        assert_eq!(lcm(6, 4), Uint128::from(12u32));
+        assert_eq!(lcm(17, 5), Uint128::from(85u32));
+        assert_eq!(Uint128::MAX.lcm(Uint128::MAX), Uint128::MAX);
+
+        let err = Uint128::MAX.checked_lcm(Uint128::from(2u32)).unwrap_err();
+        assert_eq!(err.operation, OverflowOperation::Mul);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn uint128_lcm_panics_on_overflow() {
+        let _ = Uint128::MAX.lcm(Uint128::from(2u32));
+    }
+
     #[test]
     fn uint128_partial_eq() {
         let test_cases = [(1, 1, true), (42, 42, true), (42, 24, false), (0, 0, true)]
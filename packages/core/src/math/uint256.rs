@@ -21,6 +21,8 @@ use crate::{
 use bnum::types::U256;
 
 use super::conversion::{forward_try_from, primitive_to_wrapped_int, try_from_int_to_uint};
+use super::division::div_rem_narrow;
+#[cfg(feature = "serde")]
 use super::impl_int_serde;
 use super::num_consts::NumConsts;
 
@@ -34,7 +36,7 @@ use super::num_consts::NumConsts;
 /// endian bytes:
 ///
 /// ```
-/// # use cosmwasm_std::Uint256;
+/// # use cosmwasm_core::Uint256;
 /// let a = Uint256::from(258u128);
 /// let b = Uint256::new([
 ///     0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8,
@@ -44,9 +46,11 @@ use super::num_consts::NumConsts;
 /// ]);
 /// assert_eq!(a, b);
 /// ```
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct Uint256(#[schemars(with = "String")] pub(crate) U256);
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Uint256(#[cfg_attr(feature = "schemars", schemars(with = "String"))] pub(crate) U256);
 
+#[cfg(feature = "serde")]
 impl_int_serde!(Uint256);
 forward_ref_partial_eq!(Uint256, Uint256);
 
@@ -226,7 +230,7 @@ impl Uint256 {
     /// # Examples
     ///
     /// ```
-    /// use cosmwasm_std::Uint256;
+    /// use cosmwasm_core::Uint256;
     ///
     /// let a = Uint256::MAX;
     /// let result = a.full_mul(2u32);
@@ -271,6 +275,9 @@ impl Uint256 {
     }
 
     pub fn checked_div(self, other: Self) -> Result<Self, DivideByZeroError> {
+        if let Some((quotient, _)) = self.div_rem_narrow(other) {
+            return Ok(quotient);
+        }
         self.0
             .checked_div(other.0)
             .map(Self)
@@ -282,12 +289,29 @@ impl Uint256 {
     }
 
     pub fn checked_rem(self, other: Self) -> Result<Self, DivideByZeroError> {
+        if let Some((_, remainder)) = self.div_rem_narrow(other) {
+            return Ok(remainder);
+        }
         self.0
             .checked_rem(other.0)
             .map(Self)
             .ok_or(DivideByZeroError)
     }
 
+    /// Calculates quotient and remainder using a faster algorithm than the general
+    /// purpose division for divisors of at most half the bit size.
+    ///
+    /// Returns `None` if the divisor is zero or too large for the fast path.
+    #[inline]
+    fn div_rem_narrow(self, other: Self) -> Option<(Self, Self)> {
+        div_rem_narrow(self.0.digits(), other.0.digits()).map(|(quotient, remainder)| {
+            (
+                Self(U256::from_digits(quotient)),
+                Self(U256::from_digits(remainder)),
+            )
+        })
+    }
+
     pub fn checked_shr(self, other: u32) -> Result<Self, OverflowError> {
         if other >= 256 {
             return Err(OverflowError::new(OverflowOperation::Shr));
@@ -374,6 +398,33 @@ impl Uint256 {
     pub const fn abs_diff(self, other: Self) -> Self {
         Self(self.0.abs_diff(other.0))
     }
+
+    /// Returns the greatest common divisor of `self` and `other`.
+    /// The greatest common divisor of 0 and 0 is defined to be 0.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        while !b.is_zero() {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    /// Returns the least common multiple of `self` and `other`,
+    /// which is 0 if one of them is 0.
+    pub fn checked_lcm(self, other: Self) -> Result<Self, OverflowError> {
+        if self.is_zero() || other.is_zero() {
+            return Ok(Self::zero());
+        }
+        (self / self.gcd(other)).checked_mul(other)
+    }
+
+    /// Same operation as `checked_lcm` except panicking on overflow.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn lcm(self, other: Self) -> Self {
+        self.checked_lcm(other)
+            .expect("attempt to multiply with overflow")
+    }
 }
 
 impl NumConsts for Uint256 {
@@ -477,6 +528,9 @@ impl Div<Uint256> for Uint256 {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self::Output {
+        if let Some((quotient, _)) = self.div_rem_narrow(rhs) {
+            return quotient;
+        }
         Self(
             self.0
                 .checked_div(rhs.0)
@@ -501,6 +555,9 @@ impl Rem for Uint256 {
     /// This operation will panic if `rhs` is zero.
     #[inline]
     fn rem(self, rhs: Self) -> Self {
+        if let Some((_, remainder)) = self.div_rem_narrow(rhs) {
+            return remainder;
+        }
         Self(self.0.rem(rhs.0))
     }
 }
@@ -1730,6 +1787,40 @@ mod tests {
         assert_eq!(b.abs_diff(a), expected);
     }
 
+    #[test]
+    fn uint256_gcd_works() {
+        let gcd = |a: u32, b: u32| Uint256::from(a).gcd(Uint256::from(b));
+        assert_eq!(gcd(0, 0), Uint256::zero());
+        assert_eq!(gcd(0, 7), Uint256::from(7u32));
+        assert_eq!(gcd(7, 0), Uint256::from(7u32));
+        assert_eq!(gcd(1, 1), Uint256::one());
+        assert_eq!(gcd(12, 18), Uint256::from(6u32));
+        assert_eq!(gcd(18, 12), Uint256::from(6u32));
+        assert_eq!(gcd(17, 5), Uint256::one());
+        assert_eq!(Uint256::MAX.gcd(Uint256::MAX), Uint256::MAX);
+        assert_eq!(Uint256::MAX.gcd(Uint256::one()), Uint256::one());
+    }
+
+    #[test]
+    fn uint256_lcm_works() {
+        let lcm = |a: u32, b: u32| Uint256::from(a).lcm(Uint256::from(b));
+        assert_eq!(lcm(0, 0), Uint256::zero());
+        assert_eq!(lcm(0, 7), Uint256::zero());
+        assert_eq!(lcm(4, 6), Uint256::from(12u32));
+        assert_eq!(lcm(6, 4), Uint256::from(12u32));
+        assert_eq!(lcm(17, 5), Uint256::from(85u32));
+        assert_eq!(Uint256::MAX.lcm(Uint256::MAX), Uint256::MAX);
+
+        let err = Uint256::MAX.checked_lcm(Uint256::from(2u32)).unwrap_err();
+        assert_eq!(err.operation, OverflowOperation::Mul);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn uint256_lcm_panics_on_overflow() {
+        let _ = Uint256::MAX.lcm(Uint256::from(2u32));
+    }
+
     #[test]
     fn uint256_partial_eq() {
         let test_cases = [(1, 1, true), (42, 42, true), (42, 24, false), (0, 0, true)]
@@ -11,7 +11,7 @@ use crate::errors::{
 };
 use crate::forward_ref::{forward_ref_binop, forward_ref_op_assign};
 use crate::{
-    Int128, Int256, Int512, Int64, Uint128, Uint256, Uint64, __internal::forward_ref_partial_eq,
+    __internal::forward_ref_partial_eq, Int128, Int256, Int512, Int64, Uint128, Uint256, Uint64,
 };
 
 /// Used internally - we don't want to leak this type since we might change
@@ -19,6 +19,8 @@ use crate::{
 use bnum::types::U512;
 
 use super::conversion::{forward_try_from, primitive_to_wrapped_int, try_from_int_to_uint};
+use super::division::div_rem_narrow;
+#[cfg(feature = "serde")]
 use super::impl_int_serde;
 use super::num_consts::NumConsts;
 
@@ -32,7 +34,7 @@ use super::num_consts::NumConsts;
 /// endian bytes:
 ///
 /// ```
-/// # use cosmwasm_std::Uint512;
+/// # use cosmwasm_core::Uint512;
 /// let a = Uint512::from(258u128);
 /// let b = Uint512::new([
 ///     0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8,
@@ -46,9 +48,11 @@ use super::num_consts::NumConsts;
 /// ]);
 /// assert_eq!(a, b);
 /// ```
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct Uint512(#[schemars(with = "String")] pub(crate) U512);
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Uint512(#[cfg_attr(feature = "schemars", schemars(with = "String"))] pub(crate) U512);
 
+#[cfg(feature = "serde")]
 impl_int_serde!(Uint512);
 forward_ref_partial_eq!(Uint512, Uint512);
 
@@ -237,6 +241,9 @@ impl Uint512 {
     }
 
     pub fn checked_div(self, other: Self) -> Result<Self, DivideByZeroError> {
+        if let Some((quotient, _)) = self.div_rem_narrow(other) {
+            return Ok(quotient);
+        }
         self.0
             .checked_div(other.0)
             .map(Self)
@@ -248,12 +255,29 @@ impl Uint512 {
     }
 
     pub fn checked_rem(self, other: Self) -> Result<Self, DivideByZeroError> {
+        if let Some((_, remainder)) = self.div_rem_narrow(other) {
+            return Ok(remainder);
+        }
         self.0
             .checked_rem(other.0)
             .map(Self)
             .ok_or(DivideByZeroError)
     }
 
+    /// Calculates quotient and remainder using a faster algorithm than the general
+    /// purpose division for divisors of at most half the bit size.
+    ///
+    /// Returns `None` if the divisor is zero or too large for the fast path.
+    #[inline]
+    fn div_rem_narrow(self, other: Self) -> Option<(Self, Self)> {
+        div_rem_narrow(self.0.digits(), other.0.digits()).map(|(quotient, remainder)| {
+            (
+                Self(U512::from_digits(quotient)),
+                Self(U512::from_digits(remainder)),
+            )
+        })
+    }
+
     pub fn checked_shr(self, other: u32) -> Result<Self, OverflowError> {
         self.0
             .checked_shr(other)
@@ -339,6 +363,33 @@ impl Uint512 {
     pub const fn abs_diff(self, other: Self) -> Self {
         Self(self.0.abs_diff(other.0))
     }
+
+    /// Returns the greatest common divisor of `self` and `other`.
+    /// The greatest common divisor of 0 and 0 is defined to be 0.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        while !b.is_zero() {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    /// Returns the least common multiple of `self` and `other`,
+    /// which is 0 if one of them is 0.
+    pub fn checked_lcm(self, other: Self) -> Result<Self, OverflowError> {
+        if self.is_zero() || other.is_zero() {
+            return Ok(Self::zero());
+        }
+        (self / self.gcd(other)).checked_mul(other)
+    }
+
+    /// Same operation as `checked_lcm` except panicking on overflow.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn lcm(self, other: Self) -> Self {
+        self.checked_lcm(other)
+            .expect("attempt to multiply with overflow")
+    }
 }
 
 impl NumConsts for Uint512 {
@@ -460,6 +511,9 @@ impl Div<Uint512> for Uint512 {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self::Output {
+        if let Some((quotient, _)) = self.div_rem_narrow(rhs) {
+            return quotient;
+        }
         Self(self.0.checked_div(rhs.0).unwrap())
     }
 }
@@ -468,7 +522,7 @@ impl<'a> Div<&'a Uint512> for Uint512 {
     type Output = Self;
 
     fn div(self, rhs: &'a Uint512) -> Self::Output {
-        Self(self.0.checked_div(rhs.0).unwrap())
+        self / *rhs
     }
 }
 
@@ -480,6 +534,9 @@ impl Rem for Uint512 {
     /// This operation will panic if `rhs` is zero.
     #[inline]
     fn rem(self, rhs: Self) -> Self {
+        if let Some((_, remainder)) = self.div_rem_narrow(rhs) {
+            return remainder;
+        }
         Self(self.0.rem(rhs.0))
     }
 }
@@ -567,13 +624,13 @@ impl<'a> AddAssign<&'a Uint512> for Uint512 {
 
 impl DivAssign<Uint512> for Uint512 {
     fn div_assign(&mut self, rhs: Self) {
-        self.0 = self.0.checked_div(rhs.0).unwrap();
+        *self = *self / rhs;
     }
 }
 
 impl<'a> DivAssign<&'a Uint512> for Uint512 {
     fn div_assign(&mut self, rhs: &'a Uint512) {
-        self.0 = self.0.checked_div(rhs.0).unwrap();
+        *self = *self / rhs;
     }
 }
 
@@ -1383,6 +1440,40 @@ mod tests {
         assert_eq!(b.abs_diff(a), expected);
     }
 
+    #[test]
+    fn uint512_gcd_works() {
+        let gcd = |a: u32, b: u32| Uint512::from(a).gcd(Uint512::from(b));
+        assert_eq!(gcd(0, 0), Uint512::zero());
+        assert_eq!(gcd(0, 7), Uint512::from(7u32));
+        assert_eq!(gcd(7, 0), Uint512::from(7u32));
+        assert_eq!(gcd(1, 1), Uint512::one());
+        assert_eq!(gcd(12, 18), Uint512::from(6u32));
+        assert_eq!(gcd(18, 12), Uint512::from(6u32));
+        assert_eq!(gcd(17, 5), Uint512::one());
+        assert_eq!(Uint512::MAX.gcd(Uint512::MAX), Uint512::MAX);
+        assert_eq!(Uint512::MAX.gcd(Uint512::one()), Uint512::one());
+    }
+
+    #[test]
+    fn uint512_lcm_works() {
+        let lcm = |a: u32, b: u32| Uint512::from(a).lcm(Uint512::from(b));
+        assert_eq!(lcm(0, 0), Uint512::zero());
+        assert_eq!(lcm(0, 7), Uint512::zero());
+        assert_eq!(lcm(4, 6), Uint512::from(12u32));
+        assert_eq!(lcm(6, 4), Uint512::from(12u32));
+        assert_eq!(lcm(17, 5), Uint512::from(85u32));
+        assert_eq!(Uint512::MAX.lcm(Uint512::MAX), Uint512::MAX);
+
+        let err = Uint512::MAX.checked_lcm(Uint512::from(2u32)).unwrap_err();
+        assert_eq!(err.operation, OverflowOperation::Mul);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn uint512_lcm_panics_on_overflow() {
+        let _ = Uint512::MAX.lcm(Uint512::from(2u32));
+    }
+
     #[test]
     fn uint512_partial_eq() {
         let test_cases = [(1, 1, true), (42, 42, true), (42, 24, false), (0, 0, true)]
@@ -18,6 +18,7 @@ use crate::{
 use super::conversion::{
     forward_try_from, from_and_to_bytes, primitive_to_wrapped_int, wrapped_int_to_primitive,
 };
+#[cfg(feature = "serde")]
 use super::impl_int_serde;
 use super::num_consts::NumConsts;
 
@@ -30,16 +31,18 @@ use super::num_consts::NumConsts;
 /// Use `from` to create instances of this and `u64` to get the value out:
 ///
 /// ```
-/// # use cosmwasm_std::Uint64;
+/// # use cosmwasm_core::Uint64;
 /// let a = Uint64::from(42u64);
 /// assert_eq!(a.u64(), 42);
 ///
 /// let b = Uint64::from(70u32);
 /// assert_eq!(b.u64(), 70);
 /// ```
-#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, schemars::JsonSchema)]
-pub struct Uint64(#[schemars(with = "String")] pub(crate) u64);
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Uint64(#[cfg_attr(feature = "schemars", schemars(with = "String"))] pub(crate) u64);
 
+#[cfg(feature = "serde")]
 impl_int_serde!(Uint64);
 forward_ref_partial_eq!(Uint64, Uint64);
 
@@ -141,7 +144,7 @@ impl Uint64 {
     /// # Examples
     ///
     /// ```
-    /// use cosmwasm_std::Uint64;
+    /// use cosmwasm_core::Uint64;
     ///
     /// let a = Uint64::MAX;
     /// let result = a.full_mul(2u32);
@@ -293,6 +296,33 @@ impl Uint64 {
             self.0 - other.0
         })
     }
+
+    /// Returns the greatest common divisor of `self` and `other`.
+    /// The greatest common divisor of 0 and 0 is defined to be 0.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        while !b.is_zero() {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    /// Returns the least common multiple of `self` and `other`,
+    /// which is 0 if one of them is 0.
+    pub fn checked_lcm(self, other: Self) -> Result<Self, OverflowError> {
+        if self.is_zero() || other.is_zero() {
+            return Ok(Self::zero());
+        }
+        (self / self.gcd(other)).checked_mul(other)
+    }
+
+    /// Same operation as `checked_lcm` except panicking on overflow.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn lcm(self, other: Self) -> Self {
+        self.checked_lcm(other)
+            .expect("attempt to multiply with overflow")
+    }
 }
 
 impl NumConsts for Uint64 {
@@ -1151,6 +1181,40 @@ mod tests {
         assert_eq!(b.abs_diff(a), expected);
     }
 
+    #[test]
+    fn uint64_gcd_works() {
+        let gcd = |a: u32, b: u32| Uint64::from(a).gcd(Uint64::from(b));
+        assert_eq!(gcd(0, 0), Uint64::zero());
+        assert_eq!(gcd(0, 7), Uint64::from(7u32));
+        assert_eq!(gcd(7, 0), Uint64::from(7u32));
+        assert_eq!(gcd(1, 1), Uint64::one());
+        assert_eq!(gcd(12, 18), Uint64::from(6u32));
+        assert_eq!(gcd(18, 12), Uint64::from(6u32));
+        assert_eq!(gcd(17, 5), Uint64::one());
+        assert_eq!(Uint64::MAX.gcd(Uint64::MAX), Uint64::MAX);
+        assert_eq!(Uint64::MAX.gcd(Uint64::one()), Uint64::one());
+    }
+
+    #[test]
+    fn uint64_lcm_works() {
+        let lcm = |a: u32, b: u32| Uint64::from(a).lcm(Uint64::from(b));
+        assert_eq!(lcm(0, 0), Uint64::zero());
+        assert_eq!(lcm(0, 7), Uint64::zero());
+        assert_eq!(lcm(4, 6), Uint64::from(12u32));
+        assert_eq!(lcm(6, 4), Uint64::from(12u32));
+        assert_eq!(lcm(17, 5), Uint64::from(85u32));
+        assert_eq!(Uint64::MAX.lcm(Uint64::MAX), Uint64::MAX);
+
+        let err = Uint64::MAX.checked_lcm(Uint64::from(2u32)).unwrap_err();
+        assert_eq!(err.operation, OverflowOperation::Mul);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn uint64_lcm_panics_on_overflow() {
+        let _ = Uint64::MAX.lcm(Uint64::from(2u32));
+    }
+
     #[test]
     fn uint64_partial_eq() {
         let test_cases = [(1, 1, true), (42, 42, true), (42, 24, false), (0, 0, true)]
@@ -18,8 +18,9 @@ use sha2::Sha256;
 use cosmwasm_core::{BLS12_381_G1_GENERATOR, BLS12_381_G1_POINT_LEN, BLS12_381_G2_POINT_LEN};
 use cosmwasm_crypto::{
     bls12_381_aggregate_g1, bls12_381_aggregate_g2, bls12_381_hash_to_g1, bls12_381_hash_to_g2,
-    bls12_381_pairing_equality, ed25519_batch_verify, ed25519_verify, secp256k1_recover_pubkey,
-    secp256k1_verify, secp256r1_recover_pubkey, secp256r1_verify, HashFunction,
+    bls12_381_pairing_equality, ed25519_batch_verify, ed25519_validate_pubkey, ed25519_verify,
+    hmac_sha256, secp256k1_recover_pubkey, secp256k1_verify, secp256r1_recover_pubkey,
+    secp256r1_verify, HashFunction,
 };
 use std::cmp::min;
 
@@ -311,6 +312,13 @@ fn bench_crypto(c: &mut Criterion) {
         });
     });
 
+    group.bench_function("ed25519_validate_pubkey", |b| {
+        let public_key = hex::decode(COSMOS_ED25519_PUBLIC_KEY_HEX).unwrap();
+        b.iter(|| {
+            ed25519_validate_pubkey(&public_key).unwrap();
+        });
+    });
+
     // Ed25519 batch verification of different batch lengths
     {
         let (messages, signatures, public_keys) = read_decode_cosmos_sigs();
@@ -368,6 +376,16 @@ fn bench_crypto(c: &mut Criterion) {
         }
     }
 
+    for n in [0, 1024, 64 * 1024] {
+        group.bench_function(format!("hmac_sha256_{n}_bytes"), |b| {
+            let key = [0x0b; 32];
+            let data = vec![0xdd; n];
+            b.iter(|| {
+                black_box(hmac_sha256(&key, &data));
+            });
+        });
+    }
+
     group.finish();
 }
 
@@ -1,3 +1,4 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
 use ed25519_zebra::{batch, Signature, VerificationKey};
 use rand_core::CryptoRngCore;
 
@@ -16,6 +17,12 @@ pub const EDDSA_PUBKEY_LEN: usize = 32;
 /// format:
 /// - signature: raw ED25519 signature (64 bytes).
 /// - public key: raw ED25519 public key (32 bytes).
+///
+/// Verification follows the [ZIP-215](https://zips.z.cash/zip-0215) rules, like CometBFT.
+/// Those accept small-order and non-canonically encoded public keys, which are rejected by
+/// [`ed25519_validate_pubkey`]. Only a wrong length of the signature or public key results in
+/// an error. Any other invalid input, e.g. a non-canonical signature scalar or a public key
+/// that is not a curve point, returns `Ok(false)`.
 pub fn ed25519_verify(message: &[u8], signature: &[u8], public_key: &[u8]) -> CryptoResult<bool> {
     // Validation
     let signature = read_signature(signature)?;
@@ -58,6 +65,8 @@ pub fn ed25519_verify(message: &[u8], signature: &[u8], public_key: &[u8]) -> Cr
 ///  - The "one-message, with zero signatures and zero public keys" case, is considered the empty case.
 ///  - The "one-public key, with zero messages and zero signatures" case, is considered the empty case.
 ///  - The empty case (no messages, no signatures and no public keys) returns true.
+///  - Signatures and public keys are validated like in [`ed25519_verify`]. A single signature or
+///    public key of the wrong length results in an error for the whole batch.
 pub fn ed25519_batch_verify<R>(
     rng: &mut R,
     messages: &[&[u8]],
@@ -111,36 +120,66 @@ where
     }
 }
 
+/// Checks that the given bytes are a valid ed25519 public key.
+///
+/// This rejects (with [`CryptoError::InvalidPubkeyFormat`])
+/// - keys that are not 32 bytes long,
+/// - encodings that do not decode to a point on the curve,
+/// - non-canonical encodings, i.e. a y-coordinate that is not reduced modulo the field prime
+///   or a set sign bit for an x-coordinate of zero,
+/// - small-order points, including the identity. Such keys allow creating signatures that are
+///   valid for many messages, which is why most chains do not accept them.
+///
+/// This is stricter than [`ed25519_verify`] and [`ed25519_batch_verify`], which accept such keys
+/// as required by ZIP-215. Use it to check keys before storing them, e.g. when registering
+/// a validator.
+pub fn ed25519_validate_pubkey(pubkey: &[u8]) -> CryptoResult<()> {
+    let bytes = read_pubkey(pubkey)?;
+    let point = CompressedEdwardsY(bytes)
+        .decompress()
+        .ok_or_else(CryptoError::invalid_pubkey_format)?;
+    if point.compress().to_bytes() != bytes || point.is_small_order() {
+        return Err(CryptoError::invalid_pubkey_format());
+    }
+    Ok(())
+}
+
 /// Error raised when signature is not 64 bytes long
-struct InvalidEd25519SignatureFormat;
+struct InvalidEd25519SignatureFormat {
+    actual: usize,
+}
 
 impl From<InvalidEd25519SignatureFormat> for CryptoError {
-    fn from(_original: InvalidEd25519SignatureFormat) -> Self {
-        CryptoError::invalid_signature_format()
+    fn from(original: InvalidEd25519SignatureFormat) -> Self {
+        CryptoError::invalid_signature_length(64, original.actual)
     }
 }
 
 fn read_signature(data: &[u8]) -> Result<[u8; 64], InvalidEd25519SignatureFormat> {
-    data.try_into().map_err(|_| InvalidEd25519SignatureFormat)
+    data.try_into()
+        .map_err(|_| InvalidEd25519SignatureFormat { actual: data.len() })
 }
 
 /// Error raised when pubkey is not 32 bytes long
-struct InvalidEd25519PubkeyFormat;
+struct InvalidEd25519PubkeyFormat {
+    actual: usize,
+}
 
 impl From<InvalidEd25519PubkeyFormat> for CryptoError {
-    fn from(_original: InvalidEd25519PubkeyFormat) -> Self {
-        CryptoError::invalid_pubkey_format()
+    fn from(original: InvalidEd25519PubkeyFormat) -> Self {
+        CryptoError::invalid_pubkey_length(EDDSA_PUBKEY_LEN, original.actual)
     }
 }
 
 fn read_pubkey(data: &[u8]) -> Result<[u8; 32], InvalidEd25519PubkeyFormat> {
-    data.try_into().map_err(|_| InvalidEd25519PubkeyFormat)
+    data.try_into()
+        .map_err(|_| InvalidEd25519PubkeyFormat { actual: data.len() })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloc::{string::String, vec, vec::Vec};
+    use alloc::{format, string::String, vec, vec::Vec};
     use ed25519_zebra::SigningKey;
     use rand_core::OsRng;
     use serde::Deserialize;
@@ -209,6 +248,139 @@ mod tests {
         assert!(!ed25519_verify(message, &signature_bytes, &other_public_key_bytes).unwrap());
     }
 
+    /// The eight points of small order (the torsion subgroup), canonically encoded.
+    /// The first one is the identity.
+    const SMALL_ORDER_PUBKEYS_HEX: [&str; 8] = [
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a",
+        "0000000000000000000000000000000000000000000000000000000000000080",
+        "26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc05",
+        "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc85",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac03fa",
+    ];
+
+    /// Encodings that are rejected for other reasons than their order
+    const MALFORMED_PUBKEYS_HEX: [(&str, &str); 4] = [
+        // y = 2 is not on the curve
+        (
+            "0200000000000000000000000000000000000000000000000000000000000000",
+            "not on curve",
+        ),
+        // y = p + 3, which is the point with y = 3 if reduced
+        (
+            "f0ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+            "non-canonical y",
+        ),
+        // y = p + 1, the identity if reduced
+        (
+            "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+            "non-canonical identity",
+        ),
+        // the identity with the sign bit set for x = 0
+        (
+            "0100000000000000000000000000000000000000000000000000000000000080",
+            "negative zero",
+        ),
+    ];
+
+    #[test]
+    fn test_ed25519_validate_pubkey() {
+        // valid
+        let pubkey = hex::decode(COSMOS_ED25519_PUBLIC_KEY_HEX).unwrap();
+        ed25519_validate_pubkey(&pubkey).unwrap();
+        for encoded in read_cosmos_sigs() {
+            ed25519_validate_pubkey(&hex::decode(&encoded.public_key).unwrap()).unwrap();
+        }
+        // y = 3 is a valid point (the canonical encoding of the non-canonical one above)
+        let mut y3 = [0u8; 32];
+        y3[0] = 3;
+        ed25519_validate_pubkey(&y3).unwrap();
+
+        // wrong length
+        for len in [0, 31, 33, 64] {
+            let err = ed25519_validate_pubkey(&vec![1u8; len]).unwrap_err();
+            assert!(matches!(err, CryptoError::InvalidPubkeyFormat { .. }));
+            assert_eq!(
+                err.to_string(),
+                format!("Invalid public key format (expected 32 bytes, got {len} bytes)")
+            );
+        }
+
+        // small order
+        for pubkey in SMALL_ORDER_PUBKEYS_HEX {
+            let err = ed25519_validate_pubkey(&hex::decode(pubkey).unwrap()).unwrap_err();
+            assert!(
+                matches!(err, CryptoError::InvalidPubkeyFormat { .. }),
+                "{pubkey}"
+            );
+        }
+
+        // malformed
+        for (pubkey, case) in MALFORMED_PUBKEYS_HEX {
+            let err = ed25519_validate_pubkey(&hex::decode(pubkey).unwrap()).unwrap_err();
+            assert!(
+                matches!(err, CryptoError::InvalidPubkeyFormat { .. }),
+                "{case}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ed25519_verify_follows_zip215() {
+        let message = COSMOS_ED25519_MSG.as_bytes();
+        let signature = hex::decode(COSMOS_ED25519_SIGNATURE_HEX).unwrap();
+        let pubkey = hex::decode(COSMOS_ED25519_PUBLIC_KEY_HEX).unwrap();
+        assert!(ed25519_verify(message, &signature, &pubkey).unwrap());
+
+        // verification failure
+        assert!(!ed25519_verify(b"other", &signature, &pubkey).unwrap());
+
+        // public keys rejected by ed25519_validate_pubkey do not result in an error
+        for pubkey in SMALL_ORDER_PUBKEYS_HEX
+            .into_iter()
+            .chain(MALFORMED_PUBKEYS_HEX.into_iter().map(|(pubkey, _)| pubkey))
+        {
+            let pubkey = hex::decode(pubkey).unwrap();
+            assert!(!ed25519_verify(message, &signature, &pubkey).unwrap());
+            assert!(!ed25519_batch_verify(
+                &mut OsRng,
+                &[message],
+                &[signature.as_slice()],
+                &[pubkey.as_slice()],
+            )
+            .unwrap());
+        }
+
+        // s + L instead of s (malleability) fails verification
+        let mut malleated = signature.clone();
+        malleated[32..].copy_from_slice(
+            &hex::decode("4c8c7872aa064e049dbb3013fbf29380d25bf5f0595bbe24655141438e7a101b")
+                .unwrap(),
+        );
+        assert!(!ed25519_verify(message, &malleated, &pubkey).unwrap());
+        assert!(!ed25519_batch_verify(
+            &mut OsRng,
+            &[message],
+            &[malleated.as_slice()],
+            &[pubkey.as_slice()],
+        )
+        .unwrap());
+
+        // wrong signature length
+        let err = ed25519_verify(message, &signature[..63], &pubkey).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid signature format (expected 64 bytes, got 63 bytes)"
+        );
+
+        // s with the highest bit set fails verification
+        let mut high_bit = signature;
+        high_bit[63] |= 0x80;
+        assert!(!ed25519_verify(message, &high_bit, &pubkey).unwrap());
+    }
+
     #[test]
     fn test_cosmos_ed25519_verify() {
         let secret_key = SigningKey::try_from(
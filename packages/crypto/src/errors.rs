@@ -1,5 +1,6 @@
+use alloc::format;
 use alloc::string::String;
-use core::fmt::Debug;
+use core::fmt::{Debug, Display};
 
 use crate::BT;
 
@@ -34,6 +35,27 @@ pub enum InvalidPoint {
     DecodingError {},
 }
 
+/// The expected and actual length of a malformed input in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputLength {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl Display for InputLength {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "expected {} bytes, got {} bytes",
+            self.expected, self.actual
+        )
+    }
+}
+
+fn format_length(length: &Option<InputLength>) -> String {
+    length.map(|l| format!(" ({l})")).unwrap_or_default()
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum CryptoError {
     #[error("Point aggregation error: {source}")]
@@ -42,12 +64,24 @@ pub enum CryptoError {
     BatchErr { msg: String, backtrace: BT },
     #[error("Crypto error: {msg}")]
     GenericErr { msg: String, backtrace: BT },
-    #[error("Invalid hash format")]
-    InvalidHashFormat { backtrace: BT },
-    #[error("Invalid public key format")]
-    InvalidPubkeyFormat { backtrace: BT },
-    #[error("Invalid signature format")]
-    InvalidSignatureFormat { backtrace: BT },
+    /// `length` is set if the hash has the wrong length
+    #[error("Invalid hash format{}", format_length(.length))]
+    InvalidHashFormat {
+        length: Option<InputLength>,
+        backtrace: BT,
+    },
+    /// `length` is set if the public key has the wrong length for its encoding
+    #[error("Invalid public key format{}", format_length(.length))]
+    InvalidPubkeyFormat {
+        length: Option<InputLength>,
+        backtrace: BT,
+    },
+    /// `length` is set if the signature has the wrong length
+    #[error("Invalid signature format{}", format_length(.length))]
+    InvalidSignatureFormat {
+        length: Option<InputLength>,
+        backtrace: BT,
+    },
     #[error("Invalid recovery parameter. Supported values: 0 and 1.")]
     InvalidRecoveryParam { backtrace: BT },
     #[error("Invalid point: {source}")]
@@ -78,18 +112,42 @@ impl CryptoError {
 
     pub fn invalid_hash_format() -> Self {
         CryptoError::InvalidHashFormat {
+            length: None,
+            backtrace: BT::capture(),
+        }
+    }
+
+    pub fn invalid_hash_length(expected: usize, actual: usize) -> Self {
+        CryptoError::InvalidHashFormat {
+            length: Some(InputLength { expected, actual }),
             backtrace: BT::capture(),
         }
     }
 
     pub fn invalid_pubkey_format() -> Self {
         CryptoError::InvalidPubkeyFormat {
+            length: None,
+            backtrace: BT::capture(),
+        }
+    }
+
+    pub fn invalid_pubkey_length(expected: usize, actual: usize) -> Self {
+        CryptoError::InvalidPubkeyFormat {
+            length: Some(InputLength { expected, actual }),
             backtrace: BT::capture(),
         }
     }
 
     pub fn invalid_signature_format() -> Self {
         CryptoError::InvalidSignatureFormat {
+            length: None,
+            backtrace: BT::capture(),
+        }
+    }
+
+    pub fn invalid_signature_length(expected: usize, actual: usize) -> Self {
+        CryptoError::InvalidSignatureFormat {
+            length: Some(InputLength { expected, actual }),
             backtrace: BT::capture(),
         }
     }
@@ -172,6 +230,99 @@ impl From<InvalidPoint> for CryptoError {
     }
 }
 
+impl From<InputLength> for cosmwasm_core::InputLength {
+    fn from(original: InputLength) -> Self {
+        cosmwasm_core::InputLength {
+            expected: original.expected,
+            actual: original.actual,
+        }
+    }
+}
+
+impl From<CryptoError> for cosmwasm_core::VerificationError {
+    fn from(original: CryptoError) -> Self {
+        use cosmwasm_core::{AggregationError, PairingEqualityError, VerificationError};
+
+        match original {
+            CryptoError::Aggregation {
+                source: Aggregation::Empty,
+                ..
+            } => VerificationError::Aggregation {
+                source: AggregationError::Empty,
+            },
+            CryptoError::Aggregation {
+                source: Aggregation::NotMultiple { .. },
+                ..
+            } => VerificationError::Aggregation {
+                source: AggregationError::NotMultiple,
+            },
+            CryptoError::PairingEquality {
+                source: PairingEquality::NotMultipleG1 { .. },
+                ..
+            } => VerificationError::PairingEquality {
+                source: PairingEqualityError::NotMultipleG1,
+            },
+            CryptoError::PairingEquality {
+                source: PairingEquality::NotMultipleG2 { .. },
+                ..
+            } => VerificationError::PairingEquality {
+                source: PairingEqualityError::NotMultipleG2,
+            },
+            CryptoError::PairingEquality {
+                source: PairingEquality::UnequalPointAmount { .. },
+                ..
+            } => VerificationError::PairingEquality {
+                source: PairingEqualityError::UnequalPointAmount,
+            },
+            CryptoError::InvalidHashFormat { length, .. } => VerificationError::InvalidHashFormat {
+                length: length.map(Into::into),
+            },
+            CryptoError::InvalidPubkeyFormat { length, .. } => {
+                VerificationError::InvalidPubkeyFormat {
+                    length: length.map(Into::into),
+                }
+            }
+            CryptoError::InvalidSignatureFormat { length, .. } => {
+                VerificationError::InvalidSignatureFormat {
+                    length: length.map(Into::into),
+                }
+            }
+            CryptoError::GenericErr { .. } => VerificationError::GenericErr,
+            CryptoError::InvalidRecoveryParam { .. } => VerificationError::InvalidRecoveryParam,
+            CryptoError::InvalidPoint { .. } => VerificationError::InvalidPoint,
+            CryptoError::BatchErr { .. } => VerificationError::BatchErr,
+            CryptoError::UnknownHashFunction { .. } => VerificationError::UnknownHashFunction,
+        }
+    }
+}
+
+impl From<CryptoError> for cosmwasm_core::RecoverPubkeyError {
+    fn from(original: CryptoError) -> Self {
+        use cosmwasm_core::RecoverPubkeyError;
+
+        match original {
+            CryptoError::InvalidHashFormat { length, .. } => {
+                RecoverPubkeyError::InvalidHashFormat {
+                    length: length.map(Into::into),
+                }
+            }
+            CryptoError::InvalidSignatureFormat { length, .. } => {
+                RecoverPubkeyError::InvalidSignatureFormat {
+                    length: length.map(Into::into),
+                }
+            }
+            CryptoError::GenericErr { .. } => RecoverPubkeyError::unknown_err(original.code()),
+            CryptoError::InvalidRecoveryParam { .. } => RecoverPubkeyError::InvalidRecoveryParam,
+            CryptoError::Aggregation { .. }
+            | CryptoError::PairingEquality { .. }
+            | CryptoError::BatchErr { .. }
+            | CryptoError::InvalidPubkeyFormat { .. }
+            | CryptoError::InvalidPoint { .. }
+            | CryptoError::UnknownHashFunction { .. } => panic!("Conversion not supported"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,4 +376,117 @@ mod tests {
             _ => panic!("wrong error type!"),
         }
     }
+
+    #[test]
+    fn invalid_hash_length_works() {
+        let error = CryptoError::invalid_hash_length(32, 31);
+        match &error {
+            CryptoError::InvalidHashFormat { length, .. } => assert_eq!(
+                *length,
+                Some(InputLength {
+                    expected: 32,
+                    actual: 31
+                })
+            ),
+            _ => panic!("wrong error type!"),
+        }
+        assert_eq!(
+            error.to_string(),
+            "Invalid hash format (expected 32 bytes, got 31 bytes)"
+        );
+        assert_eq!(error.code(), 3);
+    }
+
+    #[test]
+    fn invalid_signature_length_works() {
+        let error = CryptoError::invalid_signature_length(64, 65);
+        match &error {
+            CryptoError::InvalidSignatureFormat { length, .. } => assert_eq!(
+                *length,
+                Some(InputLength {
+                    expected: 64,
+                    actual: 65
+                })
+            ),
+            _ => panic!("wrong error type!"),
+        }
+        assert_eq!(
+            error.to_string(),
+            "Invalid signature format (expected 64 bytes, got 65 bytes)"
+        );
+        assert_eq!(error.code(), 4);
+    }
+
+    #[test]
+    fn invalid_pubkey_length_works() {
+        let error = CryptoError::invalid_pubkey_length(33, 0);
+        match &error {
+            CryptoError::InvalidPubkeyFormat { length, .. } => assert_eq!(
+                *length,
+                Some(InputLength {
+                    expected: 33,
+                    actual: 0
+                })
+            ),
+            _ => panic!("wrong error type!"),
+        }
+        assert_eq!(
+            error.to_string(),
+            "Invalid public key format (expected 33 bytes, got 0 bytes)"
+        );
+        assert_eq!(error.code(), 5);
+    }
+
+    #[test]
+    fn format_errors_without_length_display_works() {
+        assert_eq!(
+            CryptoError::invalid_hash_format().to_string(),
+            "Invalid hash format"
+        );
+        assert_eq!(
+            CryptoError::invalid_signature_format().to_string(),
+            "Invalid signature format"
+        );
+        assert_eq!(
+            CryptoError::invalid_pubkey_format().to_string(),
+            "Invalid public key format"
+        );
+    }
+
+    #[test]
+    fn into_verification_error_keeps_input_length() {
+        use cosmwasm_core::VerificationError;
+
+        let error: VerificationError = CryptoError::invalid_pubkey_length(65, 64).into();
+        assert_eq!(
+            error,
+            VerificationError::InvalidPubkeyFormat {
+                length: Some(cosmwasm_core::InputLength {
+                    expected: 65,
+                    actual: 64
+                })
+            }
+        );
+        let error: VerificationError = CryptoError::invalid_signature_format().into();
+        assert_eq!(
+            error,
+            VerificationError::InvalidSignatureFormat { length: None }
+        );
+    }
+
+    #[test]
+    fn into_recover_pubkey_error_keeps_input_length() {
+        use cosmwasm_core::RecoverPubkeyError;
+
+        let error: RecoverPubkeyError = CryptoError::invalid_hash_length(32, 0).into();
+        assert_eq!(
+            error,
+            RecoverPubkeyError::InvalidHashFormat {
+                length: Some(cosmwasm_core::InputLength {
+                    expected: 32,
+                    actual: 0
+                })
+            }
+        );
+    }
 }
@@ -0,0 +1,100 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Length of an HMAC-SHA256 output in bytes
+pub const HMAC_SHA256_LEN: usize = 32;
+
+/// Computes the HMAC-SHA256 of `data` using the given `key`, as specified in
+/// [RFC 2104](https://datatracker.ietf.org/doc/html/rfc2104).
+///
+/// Keys of any length are supported. Keys longer than the SHA-256 block size of 64 bytes
+/// are hashed first.
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; HMAC_SHA256_LEN] {
+    // HMAC accepts keys of any length, so this cannot fail
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    // Test vectors from https://datatracker.ietf.org/doc/html/rfc4231#section-4
+
+    #[test]
+    fn hmac_sha256_works_rfc4231_case1() {
+        let key = [0x0b; 20];
+        let data = b"Hi There";
+        assert_eq!(
+            hmac_sha256(&key, data),
+            hex!("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7")
+        );
+    }
+
+    #[test]
+    fn hmac_sha256_works_rfc4231_case2() {
+        // Test with a key shorter than the length of the HMAC output
+        let key = b"Jefe";
+        let data = b"what do ya want for nothing?";
+        assert_eq!(
+            hmac_sha256(key, data),
+            hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+    }
+
+    #[test]
+    fn hmac_sha256_works_rfc4231_case3() {
+        // Test with a combined length of key and data that is larger than 64 bytes
+        let key = [0xaa; 20];
+        let data = [0xdd; 50];
+        assert_eq!(
+            hmac_sha256(&key, &data),
+            hex!("773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe")
+        );
+    }
+
+    #[test]
+    fn hmac_sha256_works_rfc4231_case4() {
+        let key = hex!("0102030405060708090a0b0c0d0e0f10111213141516171819");
+        let data = [0xcd; 50];
+        assert_eq!(
+            hmac_sha256(&key, &data),
+            hex!("82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b")
+        );
+    }
+
+    #[test]
+    fn hmac_sha256_works_rfc4231_case5() {
+        // Test with a truncation of output to 128 bits
+        let key = [0x0c; 20];
+        let data = b"Test With Truncation";
+        assert_eq!(
+            hmac_sha256(&key, data)[..16],
+            hex!("a3b6167473100ee06e0c796c2955552b")
+        );
+    }
+
+    #[test]
+    fn hmac_sha256_works_rfc4231_case6() {
+        // Test with a key larger than 128 bytes (= block-size of SHA-384 and SHA-512)
+        let key = [0xaa; 131];
+        let data = b"Test Using Larger Than Block-Size Key - Hash Key First";
+        assert_eq!(
+            hmac_sha256(&key, data),
+            hex!("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")
+        );
+    }
+
+    #[test]
+    fn hmac_sha256_works_rfc4231_case7() {
+        // Test with a key and data that is larger than 128 bytes (= block-size of SHA-384 and SHA-512)
+        let key = [0xaa; 131];
+        let data = b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.";
+        assert_eq!(
+            hmac_sha256(&key, data),
+            hex!("9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2")
+        );
+    }
+}
@@ -10,6 +10,7 @@ mod bls12_381;
 mod ecdsa;
 mod ed25519;
 mod errors;
+mod hmac;
 mod identity_digest;
 mod secp256k1;
 mod secp256r1;
@@ -25,13 +26,15 @@ pub use crate::ecdsa::{ECDSA_PUBKEY_MAX_LEN, ECDSA_SIGNATURE_LEN, MESSAGE_HASH_M
 #[doc(hidden)]
 pub use crate::ed25519::EDDSA_PUBKEY_LEN;
 #[doc(hidden)]
-pub use crate::ed25519::{ed25519_batch_verify, ed25519_verify};
+pub use crate::ed25519::{ed25519_batch_verify, ed25519_validate_pubkey, ed25519_verify};
 #[doc(hidden)]
 pub use crate::errors::{
-    Aggregation as AggregationError, CryptoError, CryptoResult,
+    Aggregation as AggregationError, CryptoError, CryptoResult, InputLength,
     PairingEquality as PairingEqualityError,
 };
 #[doc(hidden)]
+pub use crate::hmac::{hmac_sha256, HMAC_SHA256_LEN};
+#[doc(hidden)]
 pub use crate::secp256k1::{secp256k1_recover_pubkey, secp256k1_verify};
 #[doc(hidden)]
 pub use crate::secp256r1::{secp256r1_recover_pubkey, secp256r1_verify};
@@ -5,7 +5,10 @@ use k256::{
     ecdsa::{RecoveryId, Signature, VerifyingKey}, // type aliases
 };
 
-use crate::ecdsa::{ECDSA_COMPRESSED_PUBKEY_LEN, ECDSA_UNCOMPRESSED_PUBKEY_LEN};
+use crate::ecdsa::{
+    ECDSA_COMPRESSED_PUBKEY_LEN, ECDSA_SIGNATURE_LEN, ECDSA_UNCOMPRESSED_PUBKEY_LEN,
+    MESSAGE_HASH_MAX_LEN,
+};
 use crate::errors::{CryptoError, CryptoResult};
 use crate::identity_digest::Identity256;
 
@@ -116,52 +119,69 @@ pub fn secp256k1_recover_pubkey(
 }
 
 /// Error raised when hash is not 32 bytes long
-struct InvalidSecp256k1HashFormat;
+struct InvalidSecp256k1HashFormat {
+    actual: usize,
+}
 
 impl From<InvalidSecp256k1HashFormat> for CryptoError {
-    fn from(_original: InvalidSecp256k1HashFormat) -> Self {
-        CryptoError::invalid_hash_format()
+    fn from(original: InvalidSecp256k1HashFormat) -> Self {
+        CryptoError::invalid_hash_length(MESSAGE_HASH_MAX_LEN, original.actual)
     }
 }
 
 fn read_hash(data: &[u8]) -> Result<[u8; 32], InvalidSecp256k1HashFormat> {
-    data.try_into().map_err(|_| InvalidSecp256k1HashFormat)
+    data.try_into()
+        .map_err(|_| InvalidSecp256k1HashFormat { actual: data.len() })
 }
 
 /// Error raised when signature is not 64 bytes long (32 bytes r, 32 bytes s)
-struct InvalidSecp256k1SignatureFormat;
+struct InvalidSecp256k1SignatureFormat {
+    actual: usize,
+}
 
 impl From<InvalidSecp256k1SignatureFormat> for CryptoError {
-    fn from(_original: InvalidSecp256k1SignatureFormat) -> Self {
-        CryptoError::invalid_signature_format()
+    fn from(original: InvalidSecp256k1SignatureFormat) -> Self {
+        CryptoError::invalid_signature_length(ECDSA_SIGNATURE_LEN, original.actual)
     }
 }
 
 fn read_signature(data: &[u8]) -> Result<[u8; 64], InvalidSecp256k1SignatureFormat> {
-    data.try_into().map_err(|_| InvalidSecp256k1SignatureFormat)
+    data.try_into()
+        .map_err(|_| InvalidSecp256k1SignatureFormat { actual: data.len() })
 }
 
 /// Error raised when public key is not in one of the two supported formats:
 /// 1. Uncompressed: 65 bytes starting with 0x04
 /// 2. Compressed: 33 bytes starting with 0x02 or 0x03
-struct InvalidSecp256k1PubkeyFormat;
+///
+/// The expected length is only known if the first byte is one of the supported prefixes.
+struct InvalidSecp256k1PubkeyFormat {
+    expected: Option<usize>,
+    actual: usize,
+}
 
 impl From<InvalidSecp256k1PubkeyFormat> for CryptoError {
-    fn from(_original: InvalidSecp256k1PubkeyFormat) -> Self {
-        CryptoError::invalid_pubkey_format()
+    fn from(original: InvalidSecp256k1PubkeyFormat) -> Self {
+        match original.expected {
+            Some(expected) => CryptoError::invalid_pubkey_length(expected, original.actual),
+            None => CryptoError::invalid_pubkey_format(),
+        }
     }
 }
 
 fn check_pubkey(data: &[u8]) -> Result<(), InvalidSecp256k1PubkeyFormat> {
-    let ok = match data.first() {
-        Some(0x02) | Some(0x03) => data.len() == ECDSA_COMPRESSED_PUBKEY_LEN,
-        Some(0x04) => data.len() == ECDSA_UNCOMPRESSED_PUBKEY_LEN,
-        _ => false,
+    let expected = match data.first() {
+        Some(0x02) | Some(0x03) => Some(ECDSA_COMPRESSED_PUBKEY_LEN),
+        Some(0x04) => Some(ECDSA_UNCOMPRESSED_PUBKEY_LEN),
+        _ => None,
     };
-    if ok {
+    if expected == Some(data.len()) {
         Ok(())
     } else {
-        Err(InvalidSecp256k1PubkeyFormat)
+        Err(InvalidSecp256k1PubkeyFormat {
+            expected,
+            actual: data.len(),
+        })
     }
 }
 
@@ -412,4 +432,45 @@ mod tests {
             err => panic!("Unexpected error: {err}"),
         }
     }
+
+    #[test]
+    fn secp256k1_verify_reports_input_lengths() {
+        let hash = [0u8; 32];
+        let signature = [1u8; 64];
+        let pubkey = [2u8; 33];
+
+        let err = secp256k1_verify(&hash[..31], &signature, &pubkey).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid hash format (expected 32 bytes, got 31 bytes)"
+        );
+        let err = secp256k1_verify(&hash, &[1u8; 65], &pubkey).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid signature format (expected 64 bytes, got 65 bytes)"
+        );
+        let err = secp256k1_verify(&hash, &signature, &pubkey[..32]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid public key format (expected 33 bytes, got 32 bytes)"
+        );
+        let mut uncompressed = [4u8; 66];
+        let err = secp256k1_verify(&hash, &signature, &uncompressed).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid public key format (expected 65 bytes, got 66 bytes)"
+        );
+        // unknown prefix, so no expected length
+        uncompressed[0] = 5;
+        let err = secp256k1_verify(&hash, &signature, &uncompressed[..65]).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid public key format");
+        let err = secp256k1_verify(&hash, &signature, &[]).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid public key format");
+
+        let err = secp256k1_recover_pubkey(&hash, &signature[..63], 0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid signature format (expected 64 bytes, got 63 bytes)"
+        );
+    }
 }
@@ -1,9 +1,13 @@
 use anyhow::{bail, ensure, Context, Result};
+use clap::{Arg, ArgAction, Command};
 use go::*;
 use inflector::cases::pascalcase::to_pascal_case;
 use schema::{documentation, schema_object_type, SchemaExt, TypeContext};
 use schemars::schema::{ObjectValidation, RootSchema, Schema, SchemaObject};
+use std::collections::HashSet;
 use std::fmt::Write;
+use std::path::Path;
+use std::{fs, iter};
 use utils::replace_acronyms;
 
 mod go;
@@ -11,35 +15,161 @@ mod schema;
 mod utils;
 
 fn main() -> Result<()> {
-    let root = cosmwasm_schema::schema_for!(cosmwasm_std::Reply);
-
-    let code = generate_go(root)?;
-    println!("{}", code);
+    let matches = Command::new("go-gen")
+        .about("Generates Go types from JSON schemas")
+        .arg(
+            Arg::new("SCHEMA")
+                .long("schema")
+                .value_name("FILE")
+                .help("The JSON schema file generated by cosmwasm-schema. This can be a single schema or a contract's API file. Defaults to the schema of cosmwasm_std::Reply.")
+                .num_args(1)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("TYPE")
+                .long("type")
+                .value_name("NAME")
+                .help("Only generates the type with the given name. Can be used multiple times.")
+                .num_args(1)
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("PACKAGE")
+                .long("package")
+                .value_name("NAME")
+                .help("Adds a package header with the given name")
+                .num_args(1)
+                .action(ArgAction::Set),
+        )
+        .arg(
+            Arg::new("OUTPUT")
+                .long("output")
+                .value_name("FILE")
+                .help("Writes the generated code to the given file instead of stdout")
+                .num_args(1)
+                .action(ArgAction::Set),
+        )
+        .get_matches();
+
+    let roots = match matches.get_one::<String>("SCHEMA") {
+        Some(path) => read_schemas(path)?,
+        None => vec![cosmwasm_schema::schema_for!(cosmwasm_std::Reply)],
+    };
+    let selected: Vec<String> = matches
+        .get_many::<String>("TYPE")
+        .map(|types| types.cloned().collect())
+        .unwrap_or_default();
+
+    let mut code = generate_go_for_schemas(&roots, &selected)?;
+    if let Some(package) = matches.get_one::<String>("PACKAGE") {
+        code = format!("package {package}\n\n{code}");
+    }
+    match matches.get_one::<String>("OUTPUT") {
+        Some(path) => fs::write(path, code).with_context(|| format!("failed to write {path}"))?,
+        None => println!("{}", code),
+    }
 
     Ok(())
 }
 
-/// Generates the Go code for the given schema
-fn generate_go(root: RootSchema) -> Result<String> {
-    let title = root
-        .schema
-        .metadata
-        .as_ref()
-        .and_then(|m| m.title.as_ref())
-        .context("failed to get type name")?;
+/// Reads the schemas from a file generated by cosmwasm-schema.
+/// This is either a single schema or a contract's API file (e.g. `schema/hackatom.json`),
+/// in which case the schemas of all messages and query responses are returned.
+fn read_schemas(path: impl AsRef<Path>) -> Result<Vec<RootSchema>> {
+    let path = path.as_ref();
+    let content =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+
+    if value.get("contract_name").is_none() {
+        let root = serde_json::from_value(value).context("failed to parse schema")?;
+        return Ok(vec![root]);
+    }
+
+    let mut roots = vec![];
+    for msg in ["instantiate", "execute", "query", "migrate", "sudo"] {
+        if let Some(schema) = value.get(msg).filter(|schema| !schema.is_null()) {
+            let root = serde_json::from_value(schema.clone())
+                .with_context(|| format!("failed to parse {msg} schema"))?;
+            roots.push(root);
+        }
+    }
+    if let Some(responses) = value.get("responses").and_then(|r| r.as_object()) {
+        for (query, schema) in responses {
+            let root = serde_json::from_value(schema.clone())
+                .with_context(|| format!("failed to parse response schema of {query}"))?;
+            roots.push(root);
+        }
+    }
+    Ok(roots)
+}
 
+/// Generates the Go code for all types in the given schemas, e.g. all messages of a contract.
+///
+/// Types provided by wasmvm are skipped and types that occur in multiple schemas are only
+/// generated once. If `selected` is not empty, only the types with the given names are included.
+///
+/// All types are processed before returning an error, such that the error lists all types
+/// containing unsupported constructs.
+fn generate_go_for_schemas(roots: &[RootSchema], selected: &[String]) -> Result<String> {
+    let mut seen = HashSet::new();
     let mut types = vec![];
-    build_type(title, &root.schema, &mut types)
-        .with_context(|| format!("failed to generate {title}"))?;
+    let mut failures = vec![];
+    for root in roots {
+        let title = root
+            .schema
+            .metadata
+            .as_ref()
+            .and_then(|m| m.title.as_deref())
+            .context("failed to get type name")?;
+
+        // the type itself and additional definitions
+        let definitions = root
+            .definitions
+            .iter()
+            .map(|(name, schema)| (name.as_str(), schema.object()));
+        for (name, schema) in iter::once((title, Ok(&root.schema))).chain(definitions) {
+            if !seen.insert(name) {
+                continue;
+            }
+            let mut new_types = vec![];
+            match schema.and_then(|schema| build_type(name, schema, &mut new_types)) {
+                Ok(()) => types.extend(new_types),
+                Err(e) => failures.push(format!("- {name}: {e:#}")),
+            }
+        }
+    }
+    if !failures.is_empty() {
+        bail!(
+            "failed to generate {} type(s) because of unsupported constructs:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
 
-    // go through additional definitions
-    for (name, additional_type) in &root.definitions {
-        additional_type
-            .object()
-            .map(|def| build_type(name, def, &mut types))
-            .and_then(|r| r)
-            .context("failed to generate additional definitions")?;
+    // anonymous structs can occur in multiple schemas as well
+    let mut names = HashSet::new();
+    types.retain(|ty| names.insert(ty.name.clone()));
+
+    if !selected.is_empty() {
+        let selected: Vec<_> = selected
+            .iter()
+            .map(|name| replace_acronyms(to_pascal_case(name)))
+            .collect();
+        let missing: Vec<_> = selected
+            .iter()
+            .filter(|name| !names.contains(*name))
+            .map(String::as_str)
+            .collect();
+        ensure!(
+            missing.is_empty(),
+            "type(s) not found: {}",
+            missing.join(", ")
+        );
+        types.retain(|ty| selected.contains(&ty.name));
     }
+
     let mut code = String::new();
     for ty in types {
         writeln!(&mut code, "{ty}")?;
@@ -71,6 +201,8 @@ fn build_type(name: &str, schema: &SchemaObject, structs: &mut Vec<GoStruct>) ->
         if let Some(strct) = strct {
             structs.push(strct);
         }
+    } else if schema::is_unit(schema) {
+        // `()`, e.g. the response of a query that returns nothing, does not need a type
     } else {
         anyhow::bail!("failed to determine type for '{name}'");
     }
@@ -198,6 +330,11 @@ mod tests {
 
     use super::*;
 
+    /// Generates the Go code for the given schema
+    fn generate_go(root: RootSchema) -> Result<String> {
+        generate_go_for_schemas(std::slice::from_ref(&root), &[])
+    }
+
     fn assert_code_eq(actual: String, expected: &str) {
         let actual_no_ws = actual.split_whitespace().collect::<Vec<_>>();
         let expected_no_ws = expected.split_whitespace().collect::<Vec<_>>();
@@ -342,6 +479,7 @@ mod tests {
         // compare_codes!(cosmwasm_std::AllBalanceResponse); // has different name in wasmvm
         compare_codes!(cosmwasm_std::DenomMetadataResponse);
         // compare_codes!(cosmwasm_std::AllDenomMetadataResponse); // uses `[]byte` instead of `*[]byte`
+        compare_codes!(cosmwasm_std::PaginatedBalancesResponse);
         // staking
         compare_codes!(cosmwasm_std::BondedDenomResponse);
         compare_codes!(cosmwasm_std::AllDelegationsResponse);
@@ -413,6 +551,118 @@ mod tests {
             .contains("failed to determine type for 'ShouldFail2'"));
     }
 
+    #[test]
+    fn contract_schemas_work() {
+        for contract in ["hackatom", "ibc-reflect"] {
+            let roots =
+                read_schemas(format!("../../contracts/{contract}/schema/{contract}.json")).unwrap();
+            let generated = generate_go_for_schemas(&roots, &[]).unwrap();
+            let expected = std::fs::read_to_string(format!("tests/{contract}.go")).unwrap();
+
+            assert_code_eq_ignore_docs(generated, &expected);
+        }
+    }
+
+    #[test]
+    fn generate_go_for_schemas_deduplicates_types() {
+        #[cw_serde]
+        struct Inner {
+            a: Uint128,
+        }
+        #[cw_serde]
+        struct A {
+            inner: Inner,
+        }
+        #[cw_serde]
+        struct B {
+            inner: Option<Inner>,
+        }
+
+        let roots = [
+            cosmwasm_schema::schema_for!(A),
+            cosmwasm_schema::schema_for!(B),
+        ];
+        let code = generate_go_for_schemas(&roots, &[]).unwrap();
+        assert_code_eq(
+            code,
+            r#"
+            type A struct {
+                Inner Inner `json:"inner"`
+            }
+            type Inner struct {
+                A string `json:"a"`
+            }
+            type B struct {
+                Inner *Inner `json:"inner,omitempty"`
+            }
+            "#,
+        );
+
+        let code =
+            generate_go_for_schemas(&roots, &["B".to_string(), "Inner".to_string()]).unwrap();
+        assert_code_eq(
+            code,
+            r#"
+            type Inner struct {
+                A string `json:"a"`
+            }
+            type B struct {
+                Inner *Inner `json:"inner,omitempty"`
+            }
+            "#,
+        );
+
+        let err = generate_go_for_schemas(&roots, &["C".to_string()]).unwrap_err();
+        assert_eq!(err.to_string(), "type(s) not found: C");
+    }
+
+    #[test]
+    fn unit_and_empty_responses_work() {
+        #[cw_serde]
+        struct Empty {}
+
+        // `()` serializes to `null` and needs no Go type
+        let code = generate_go(schemars::schema_for!(())).unwrap();
+        assert_code_eq(code, "");
+
+        let roots = [
+            cosmwasm_schema::schema_for!(()),
+            cosmwasm_schema::schema_for!(Empty),
+        ];
+        let code = generate_go_for_schemas(&roots, &[]).unwrap();
+        assert_code_eq(code, "type Empty struct { }");
+    }
+
+    #[test]
+    fn generate_go_for_schemas_lists_unsupported_constructs() {
+        #[cw_serde]
+        struct U128 {
+            a: u128,
+        }
+        #[cw_serde]
+        enum UnitVariant {
+            A,
+        }
+        #[cw_serde]
+        struct Supported {
+            a: String,
+        }
+
+        let roots = [
+            cosmwasm_schema::schema_for!(U128),
+            cosmwasm_schema::schema_for!(Supported),
+            cosmwasm_schema::schema_for!(UnitVariant),
+        ];
+        let err = generate_go_for_schemas(&roots, &[])
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("failed to generate 2 type(s) because of unsupported constructs:"));
+        assert!(err.contains("U128: "));
+        assert!(err.contains("unsupported integer format: uint128"));
+        assert!(err.contains("UnitVariant: failed to determine type for 'UnitVariant'"));
+        assert!(!err.contains("Supported:"));
+    }
+
     #[test]
     fn queries_work() {
         // compare_codes!(cosmwasm_std::QueryRequest<Empty>); // omit for now because it's huge
@@ -276,6 +276,14 @@ pub fn is_null(schema: &SchemaObject) -> bool {
         .is_some_and(|s| s.contains(&InstanceType::Null))
 }
 
+/// Returns `true` if the schema describes the unit type `()`, which is serialized as `null`.
+pub fn is_unit(schema: &SchemaObject) -> bool {
+    matches!(
+        schema.instance_type.as_ref(),
+        Some(SingleOrVec::Single(ty)) if **ty == InstanceType::Null
+    )
+}
+
 pub fn documentation(schema: &SchemaObject) -> Option<String> {
     schema.metadata.as_ref()?.description.as_ref().cloned()
 }
@@ -0,0 +1,13 @@
+type PaginatedBalancesResponse struct {
+	Balances Array[Coin] `json:"balances"`
+	// NextKey is the key to be passed to PageRequest.key to
+	// query the next page most efficiently. It will be empty if
+	// there are no more results.
+	NextKey *[]byte `json:"next_key,omitempty"` // in wasmvm, this has type `[]byte`
+}
+
+// Coin is a string representation of the sdk.Coin type (more portable than sdk.Int)
+type Coin struct {
+	Amount string `json:"amount"` // string encoing of decimal value, eg. "12.3456"
+	Denom  string `json:"denom"`  // type, eg. "ATOM"
+}
@@ -0,0 +1,114 @@
+type InstantiateMsg struct {
+	Beneficiary string `json:"beneficiary"`
+	Verifier string `json:"verifier"`
+}
+
+type ReleaseMsg struct {
+}
+
+type CpuLoopMsg struct {
+}
+
+type StorageLoopMsg struct {
+}
+
+type MemoryLoopMsg struct {
+}
+
+type MessageLoopMsg struct {
+}
+
+type AllocateLargeMemoryMsg struct {
+	Pages uint32 `json:"pages"`
+}
+
+type PanicMsg struct {
+}
+
+type UserErrorsInApiCallsMsg struct {
+}
+
+type ExecuteMsg struct {
+	// Releasing all funds in the contract to the beneficiary. This is the only "proper" action of this demo contract.
+	Release *ReleaseMsg `json:"release,omitempty"`
+	// Infinite loop to burn cpu cycles (only run when metering is enabled)
+	CpuLoop *CpuLoopMsg `json:"cpu_loop,omitempty"`
+	// Infinite loop making storage calls (to test when their limit hits)
+	StorageLoop *StorageLoopMsg `json:"storage_loop,omitempty"`
+	// Infinite loop reading and writing memory
+	MemoryLoop *MemoryLoopMsg `json:"memory_loop,omitempty"`
+	// Infinite loop sending message to itself
+	MessageLoop *MessageLoopMsg `json:"message_loop,omitempty"`
+	// Allocate large amounts of memory without consuming much gas
+	AllocateLargeMemory *AllocateLargeMemoryMsg `json:"allocate_large_memory,omitempty"`
+	// Trigger a panic to ensure framework handles gracefully
+	Panic *PanicMsg `json:"panic,omitempty"`
+	// Starting with CosmWasm 0.10, some API calls return user errors back to the contract. This triggers such user errors, ensuring the transaction does not fail in the backend.
+	UserErrorsInApiCalls *UserErrorsInApiCallsMsg `json:"user_errors_in_api_calls,omitempty"`
+}
+
+type VerifierMsg struct {
+}
+
+type OtherBalanceMsg struct {
+	Address string `json:"address"`
+}
+
+type RecurseMsg struct {
+	Depth uint32 `json:"depth"`
+	Work uint32 `json:"work"`
+}
+
+type GetIntMsg struct {
+}
+
+type QueryMsg struct {
+	// returns a human-readable representation of the verifier use to ensure query path works in integration tests
+	Verifier *VerifierMsg `json:"verifier,omitempty"`
+	// This returns cosmwasm_std::AllBalanceResponse to demo use of the querier
+	OtherBalance *OtherBalanceMsg `json:"other_balance,omitempty"`
+	// Recurse will execute a query into itself up to depth-times and return Each step of the recursion may perform some extra work to test gas metering (`work` rounds of sha256 on contract). Now that we have Env, we can auto-calculate the address to recurse into
+	Recurse *RecurseMsg `json:"recurse,omitempty"`
+	// GetInt returns a hardcoded u32 value
+	GetInt *GetIntMsg `json:"get_int,omitempty"`
+}
+
+// MigrateMsg allows a privileged contract administrator to run a migration on the contract. In this (demo) case it is just migrating from one hackatom code to the same code, but taking advantage of the migration step to set a new validator.
+//
+// Note that the contract doesn't enforce permissions here, this is done by blockchain logic (in the future by blockchain governance)
+type MigrateMsg struct {
+	Verifier string `json:"verifier"`
+}
+
+type StealFundsMsg struct {
+	Amount Array[Coin] `json:"amount"`
+	Recipient string `json:"recipient"`
+}
+
+// SudoMsg is only exposed for internal Cosmos SDK modules to call. This is showing how we can expose "admin" functionality than can not be called by external users or contracts, but only trusted (native/Go) code in the blockchain
+type SudoMsg struct {
+	StealFunds *StealFundsMsg `json:"steal_funds,omitempty"`
+}
+
+type Coin struct {
+	Amount string `json:"amount"`
+	Denom string `json:"denom"`
+}
+
+type IntResponse struct {
+	Int uint32 `json:"int"`
+}
+
+type AllBalanceResponse struct {
+	// Returns all non-zero coins held by this account.
+	Amount Array[Coin] `json:"amount"`
+}
+
+type RecurseResponse struct {
+	// hashed is the result of running sha256 "work+1" times on the contract's human address
+	Hashed []byte `json:"hashed"`
+}
+
+type VerifierResponse struct {
+	Verifier string `json:"verifier"`
+}
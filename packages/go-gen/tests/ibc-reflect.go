@@ -0,0 +1,54 @@
+// Just needs to know the code_id of a reflect contract to spawn sub-accounts
+type InstantiateMsg struct {
+	ReflectCodeID uint64 `json:"reflect_code_id"`
+}
+
+type AsyncAckMsg struct {
+	// The acknowledgement to send back
+	Ack IBCAcknowledgement `json:"ack"`
+	// Existing channel where the packet was received
+	ChannelID string `json:"channel_id"`
+	// Sequence number of the packet that was received
+	PacketSequence Uint64 `json:"packet_sequence"`
+}
+
+type ExecuteMsg struct {
+	AsyncAck *AsyncAckMsg `json:"async_ack,omitempty"`
+}
+
+type IBCAcknowledgement struct {
+	Data []byte `json:"data"`
+}
+
+type AccountMsg struct {
+	ChannelID string `json:"channel_id"`
+}
+
+type ListAccountsMsg struct {
+}
+
+type QueryMsg struct {
+	// Returns (reflect) account that is attached to this channel, or none.
+	Account *AccountMsg `json:"account,omitempty"`
+	// Returns all (channel, reflect_account) pairs. No pagination - this is a test contract
+	ListAccounts *ListAccountsMsg `json:"list_accounts,omitempty"`
+}
+
+// An empty struct that serves as a placeholder in different places, such as contracts that don't set a custom message.
+//
+// It is designed to be expressible in correct JSON and JSON Schema but contains no meaningful data. Previously we used enums without cases, but those cannot represented as valid JSON Schema (https://github.com/CosmWasm/cosmwasm/issues/451)
+type MigrateMsg struct {
+}
+
+type AccountResponse struct {
+	Account string `json:"account,omitempty"`
+}
+
+type ListAccountsResponse struct {
+	Accounts Array[AccountInfo] `json:"accounts"`
+}
+
+type AccountInfo struct {
+	Account string `json:"account"`
+	ChannelID string `json:"channel_id"`
+}
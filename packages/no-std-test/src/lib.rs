@@ -0,0 +1,35 @@
+//! This crate is not published. It only exists to ensure that the math types of
+//! cosmwasm-core can be used from a `#![no_std]` crate. CI builds it for the
+//! `thumbv7em-none-eabi` target, which does not have a standard library.
+
+#![no_std]
+
+use cosmwasm_core::{
+    CheckedMultiplyFractionError, Decimal256, Int256, OverflowError, Uint128, Uint256,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Adds up all amounts and fails on overflow.
+pub fn checked_sum(amounts: &[Uint256]) -> Result<Uint256, OverflowError> {
+    amounts
+        .iter()
+        .try_fold(Uint256::zero(), |total, amount| total.checked_add(*amount))
+}
+
+/// Multiplies the amount with the price and rounds down.
+pub fn value(amount: Uint128, price: Decimal256) -> Result<Uint256, CheckedMultiplyFractionError> {
+    Uint256::from(amount).checked_mul_floor(price)
+}
+
+/// Returns the signed difference between two amounts.
+pub fn difference(a: Uint128, b: Uint128) -> Int256 {
+    Int256::from(a.u128()) - Int256::from(b.u128())
+}
+
+const fn assert_serde<T: Serialize + DeserializeOwned>() {}
+
+const _: () = {
+    assert_serde::<Uint256>();
+    assert_serde::<Int256>();
+    assert_serde::<Decimal256>();
+};
@@ -1,11 +1,15 @@
 use std::collections::BTreeMap;
 
 use crate::error::bail;
+use crate::query_responses::to_snake_case;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{
+    braced,
     parse::{Parse, ParseStream},
-    parse_quote, Block, ExprStruct, Ident, Token,
+    parse_quote,
+    token::Brace,
+    Block, ExprStruct, Ident, Token,
 };
 
 pub fn write_api_impl(input: Options) -> Block {
@@ -60,6 +64,7 @@ pub fn generate_api_impl(input: &Options) -> ExprStruct {
         migrate,
         sudo,
         responses,
+        errors,
     } = input;
 
     parse_quote! {
@@ -72,6 +77,7 @@ pub fn generate_api_impl(input: &Options) -> ExprStruct {
             migrate: #migrate,
             sudo: #sudo,
             responses: #responses,
+            errors: #errors,
         }
     }
 }
@@ -80,6 +86,8 @@ pub fn generate_api_impl(input: &Options) -> ExprStruct {
 enum Value {
     Type(syn::Path),
     Str(syn::LitStr),
+    /// A mapping from query variants to response types, e.g. `{ Balance: BalanceResponse }`
+    Responses(Brace, Vec<ResponsePair>),
 }
 
 impl Value {
@@ -87,6 +95,7 @@ impl Value {
         match self {
             Self::Type(p) => Ok(p),
             Self::Str(other) => bail!(other, "expected a type"),
+            Self::Responses(brace, _) => Err(syn::Error::new(brace.span.join(), "expected a type")),
         }
     }
 
@@ -94,13 +103,30 @@ impl Value {
         match self {
             Self::Str(p) => Ok(p),
             Self::Type(other) => bail!(other, "expected a string literal"),
+            Self::Responses(brace, _) => Err(syn::Error::new(
+                brace.span.join(),
+                "expected a string literal",
+            )),
+        }
+    }
+
+    fn get_responses(self) -> syn::Result<(Brace, Vec<ResponsePair>)> {
+        match self {
+            Self::Responses(brace, pairs) => Ok((brace, pairs)),
+            Self::Type(other) => bail!(other, "expected a map of query variants to response types"),
+            Self::Str(other) => bail!(other, "expected a map of query variants to response types"),
         }
     }
 }
 
 impl Parse for Value {
     fn parse(input: ParseStream) -> syn::parse::Result<Self> {
-        if let Ok(p) = input.parse::<syn::Path>() {
+        if input.peek(Brace) {
+            let content;
+            let brace = braced!(content in input);
+            let pairs = content.parse_terminated(ResponsePair::parse, Token![,])?;
+            Ok(Self::Responses(brace, pairs.into_iter().collect()))
+        } else if let Ok(p) = input.parse::<syn::Path>() {
             Ok(Self::Type(p))
         } else {
             Ok(Self::Str(input.parse::<syn::LitStr>()?))
@@ -108,6 +134,23 @@ impl Parse for Value {
     }
 }
 
+/// A query variant name and its response type
+#[derive(Debug)]
+struct ResponsePair {
+    variant: Ident,
+    response: syn::Type,
+}
+
+impl Parse for ResponsePair {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let variant = input.parse::<syn::Ident>()?;
+        input.parse::<Token![:]>()?;
+        let response = input.parse::<syn::Type>()?;
+
+        Ok(Self { variant, response })
+    }
+}
+
 #[derive(Debug)]
 struct Pair((Ident, Value));
 
@@ -132,6 +175,7 @@ pub struct Options {
     migrate: TokenStream,
     sudo: TokenStream,
     responses: TokenStream,
+    errors: TokenStream,
 }
 
 impl Parse for Options {
@@ -184,15 +228,56 @@ impl Parse for Options {
             None => quote! { None },
         };
 
-        let (query, responses) = match map.remove(&parse_quote!(query)) {
-            Some(ty) => {
+        let explicit_responses = match map.remove(&parse_quote!(responses)) {
+            Some(responses) => Some(responses.get_responses()?),
+            None => None,
+        };
+
+        let (query, responses) = match (map.remove(&parse_quote!(query)), explicit_responses) {
+            (Some(ty), None) => {
                 let ty = ty.get_type()?;
                 (
                     quote! {Some(#crate_name::schema_for!(#ty))},
                     quote! { Some(<#ty as #crate_name::QueryResponses>::response_schemas().unwrap()) },
                 )
             }
-            None => (quote! { None }, quote! { None }),
+            (Some(ty), Some((_, pairs))) => {
+                let ty = ty.get_type()?;
+                let mut queries = BTreeMap::new();
+                for ResponsePair { variant, response } in pairs {
+                    let query = to_snake_case(&variant.to_string());
+                    if queries.insert(query.clone(), response).is_some() {
+                        bail!(variant, "duplicate response for query variant");
+                    }
+                }
+                let inserts = queries.into_iter().map(|(query, response)| {
+                    quote! {
+                        responses.insert(#query.to_string(), #crate_name::schema_for!(#response));
+                    }
+                });
+                // Explicit responses are merged into (and override) the ones from the
+                // `QueryResponses` implementation, if the query type has one.
+                (
+                    quote! {Some(#crate_name::schema_for!(#ty))},
+                    quote! {
+                        Some({
+                            #[allow(unused_imports)]
+                            use #crate_name::{DerivedResponsesFallback as _, DerivedResponsesViaTrait as _};
+                            #[allow(clippy::needless_borrow)]
+                            let mut responses = (&&#crate_name::DerivedResponses::<#ty>(::core::marker::PhantomData)).derived_response_schemas();
+                            #(#inserts)*
+                            responses
+                        })
+                    },
+                )
+            }
+            (None, Some((brace, _))) => {
+                return Err(syn::Error::new(
+                    brace.span.join(),
+                    "`responses` requires the `query` option",
+                ))
+            }
+            (None, None) => (quote! { None }, quote! { None }),
         };
 
         let migrate = match map.remove(&parse_quote!(migrate)) {
@@ -211,6 +296,14 @@ impl Parse for Options {
             None => quote! { None },
         };
 
+        let errors = match map.remove(&parse_quote!(errors)) {
+            Some(ty) => {
+                let ty = ty.get_type()?;
+                quote! {Some(#crate_name::schema_for!(#ty))}
+            }
+            None => quote! { None },
+        };
+
         if let Some((invalid_option, _)) = map.into_iter().next() {
             bail!(invalid_option, "unknown generate_api option");
         }
@@ -225,6 +318,7 @@ impl Parse for Options {
             migrate,
             sudo,
             responses,
+            errors,
         })
     }
 }
@@ -254,6 +348,7 @@ mod tests {
                     migrate: Some(::my_crate::cw_schema::schema_for!(MigrateMsg)),
                     sudo: Some(::my_crate::cw_schema::schema_for!(SudoMsg)),
                     responses: Some(<QueryMsg as ::my_crate::cw_schema::QueryResponses>::response_schemas().unwrap()),
+                    errors: None,
                 }
             }
         );
@@ -273,6 +368,7 @@ mod tests {
                     migrate: None,
                     sudo: None,
                     responses: None,
+                    errors: None,
                 }
             }
         );
@@ -294,6 +390,7 @@ mod tests {
                     migrate: None,
                     sudo: None,
                     responses: None,
+                    errors: None,
                 }
             }
         );
@@ -317,6 +414,7 @@ mod tests {
                     migrate: None,
                     sudo: None,
                     responses: None,
+                    errors: None,
                 }
             }
         );
@@ -342,11 +440,99 @@ mod tests {
                     migrate: Some(::cosmwasm_schema::schema_for!(MigrateMsg)),
                     sudo: Some(::cosmwasm_schema::schema_for!(SudoMsg)),
                     responses: Some(<QueryMsg as ::cosmwasm_schema::QueryResponses>::response_schemas().unwrap()),
+                    errors: None,
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn api_object_errors() {
+        assert_eq!(
+            generate_api_impl(&parse_quote! {
+                instantiate: InstantiateMsg,
+                errors: ContractError,
+            }),
+            parse_quote! {
+                ::cosmwasm_schema::Api {
+                    contract_name: ::std::env!("CARGO_PKG_NAME").to_string(),
+                    contract_version: ::std::env!("CARGO_PKG_VERSION").to_string(),
+                    instantiate: Some(::cosmwasm_schema::schema_for!(InstantiateMsg)),
+                    execute: None,
+                    query: None,
+                    migrate: None,
+                    sudo: None,
+                    responses: None,
+                    errors: Some(::cosmwasm_schema::schema_for!(ContractError)),
                 }
             }
         );
     }
 
+    #[test]
+    fn api_object_explicit_responses() {
+        assert_eq!(
+            generate_api_impl(&parse_quote! {
+                instantiate: InstantiateMsg,
+                query: QueryMsg,
+                responses: {
+                    Balance: BalanceResponse,
+                    AllAccounts: Vec<String>,
+                    Ping: (),
+                },
+            }),
+            parse_quote! {
+                ::cosmwasm_schema::Api {
+                    contract_name: ::std::env!("CARGO_PKG_NAME").to_string(),
+                    contract_version: ::std::env!("CARGO_PKG_VERSION").to_string(),
+                    instantiate: Some(::cosmwasm_schema::schema_for!(InstantiateMsg)),
+                    execute: None,
+                    query: Some(::cosmwasm_schema::schema_for!(QueryMsg)),
+                    migrate: None,
+                    sudo: None,
+                    responses: Some({
+                        #[allow(unused_imports)]
+                        use ::cosmwasm_schema::{DerivedResponsesFallback as _, DerivedResponsesViaTrait as _};
+                        #[allow(clippy::needless_borrow)]
+                        let mut responses = (&&::cosmwasm_schema::DerivedResponses::<QueryMsg>(::core::marker::PhantomData)).derived_response_schemas();
+                        responses.insert("all_accounts".to_string(), ::cosmwasm_schema::schema_for!(Vec<String>));
+                        responses.insert("balance".to_string(), ::cosmwasm_schema::schema_for!(BalanceResponse));
+                        responses.insert("ping".to_string(), ::cosmwasm_schema::schema_for!(()));
+                        responses
+                    }),
+                    errors: None,
+                }
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`responses` requires the `query` option")]
+    fn responses_without_query() {
+        let _options: Options = parse_quote! {
+            instantiate: InstantiateMsg,
+            responses: { Balance: BalanceResponse },
+        };
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate response for query variant")]
+    fn responses_with_duplicate_variant() {
+        let _options: Options = parse_quote! {
+            query: QueryMsg,
+            responses: { Balance: BalanceResponse, Balance: String },
+        };
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a map of query variants to response types")]
+    fn responses_with_type() {
+        let _options: Options = parse_quote! {
+            query: QueryMsg,
+            responses: BalanceResponse,
+        };
+    }
+
     #[test]
     #[should_panic(expected = "unknown generate_api option")]
     fn invalid_option() {
@@ -10,13 +10,28 @@ use self::context::Context;
 pub fn query_responses_derive_impl(input: ItemEnum) -> syn::Result<ItemImpl> {
     let ctx = context::get_context(&input)?;
 
-    let item_impl = if ctx.is_nested {
+    let has_nested_variants = input
+        .variants
+        .iter()
+        .map(context::is_nested_variant)
+        .collect::<syn::Result<Vec<_>>>()?
+        .contains(&true);
+
+    let item_impl = if ctx.is_nested || has_nested_variants {
         let crate_name = &ctx.crate_name;
         let ident = input.ident;
         let subquery_calls = input
             .variants
             .into_iter()
-            .map(|variant| parse_subquery(&ctx, variant))
+            .map(|variant| {
+                if ctx.is_nested || context::is_nested_variant(&variant)? {
+                    parse_subquery(&ctx, variant)
+                } else {
+                    // a regular query mixed with nested ones
+                    let mapping = parse_tuple(parse_query(&ctx, variant)?);
+                    Ok(parse_quote!(::std::collections::BTreeMap::from([#mapping])))
+                }
+            })
             .collect::<syn::Result<Vec<_>>>()?;
 
         // Handle generics if the type has any
@@ -134,7 +149,7 @@ fn parse_tuple((q, r): (String, Expr)) -> ExprTuple {
     }
 }
 
-fn to_snake_case(input: &str) -> String {
+pub(crate) fn to_snake_case(input: &str) -> String {
     // this was stolen from serde for consistent behavior
     let mut snake = String::new();
     for (i, ch) in input.char_indices() {
@@ -462,6 +477,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nested_variants_work() {
+        let input: ItemEnum = parse_quote! {
+            #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
+            #[serde(rename_all = "snake_case")]
+            pub enum ContractQueryMsg {
+                #[returns(ConfigResponse)]
+                Config {},
+                #[serde(untagged)]
+                #[query_responses(nested)]
+                Cw1(cw1::QueryMsg),
+            }
+        };
+        let result = query_responses_derive_impl(input).unwrap();
+        assert_eq!(
+            result,
+            parse_quote! {
+                #[automatically_derived]
+                #[cfg(not(target_arch = "wasm32"))]
+                impl ::cosmwasm_schema::QueryResponses for ContractQueryMsg {
+                    fn response_schemas_impl() -> ::std::collections::BTreeMap<String, ::cosmwasm_schema::schemars::schema::RootSchema> {
+                        let subqueries = [
+                            ::std::collections::BTreeMap::from([("config".to_string(), ::cosmwasm_schema::schema_for!(ConfigResponse))]),
+                            <cw1::QueryMsg as ::cosmwasm_schema::QueryResponses>::response_schemas_impl(),
+                        ];
+                        ::cosmwasm_schema::combine_subqueries::<2usize, ContractQueryMsg>(subqueries)
+                    }
+                }
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "a unit variant is not a valid subquery")]
+    fn nested_variant_must_wrap_query() {
+        let input: ItemEnum = parse_quote! {
+            #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
+            #[serde(rename_all = "snake_case")]
+            pub enum ContractQueryMsg {
+                #[returns(ConfigResponse)]
+                Config {},
+                #[query_responses(nested)]
+                Cw1,
+            }
+        };
+        query_responses_derive_impl(input).unwrap();
+    }
+
     #[test]
     fn nested_empty() {
         let input: ItemEnum = parse_quote! {
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use crate::error::bail;
-use syn::{parse_quote, Ident, ItemEnum, LitStr};
+use syn::{parse_quote, Ident, ItemEnum, LitStr, Variant};
 
 const ATTR_PATH: &str = "query_responses";
 
@@ -53,6 +53,32 @@ pub fn get_context(input: &ItemEnum) -> syn::Result<Context> {
     Ok(ctx)
 }
 
+/// Returns `true` if the variant is marked with `#[query_responses(nested)]`,
+/// which means its response schemas are collected from the nested query enum
+/// rather than from a `#[returns]` annotation.
+pub fn is_nested_variant(variant: &Variant) -> syn::Result<bool> {
+    let mut is_nested = false;
+
+    for attr in &variant.attrs {
+        if !attr.path().is_ident(ATTR_PATH) {
+            continue;
+        }
+
+        let meta_list = attr.meta.require_list()?;
+        meta_list.parse_nested_meta(|param| {
+            if param.path.is_ident("nested") {
+                is_nested = true;
+            } else {
+                bail!(param.path, "unrecognized QueryResponses variant param");
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(is_nested)
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashSet;
@@ -60,7 +86,7 @@ mod test {
     use quote::format_ident;
     use syn::parse_quote;
 
-    use super::get_context;
+    use super::{get_context, is_nested_variant};
 
     #[test]
     fn parse_context() {
@@ -79,4 +105,26 @@ mod test {
             HashSet::from([format_ident!("Item1"), format_ident!("Item2")])
         );
     }
+
+    #[test]
+    fn parse_nested_variant() {
+        let variant = parse_quote! {
+            #[serde(untagged)]
+            #[query_responses(nested)]
+            Cw1(cw1::QueryMsg)
+        };
+        assert!(is_nested_variant(&variant).unwrap());
+
+        let variant = parse_quote! {
+            #[returns(u64)]
+            Count {}
+        };
+        assert!(!is_nested_variant(&variant).unwrap());
+
+        let variant = parse_quote! {
+            #[query_responses(crate = "::my_crate::cw_schema")]
+            Count {}
+        };
+        assert!(is_nested_variant(&variant).is_err());
+    }
 }
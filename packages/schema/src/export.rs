@@ -1,40 +1,283 @@
 //! Export schema to file
 
-use std::fs::write;
-use std::path::Path;
+use std::fs::{read_to_string, write};
+use std::io;
+use std::path::{Path, PathBuf};
 
 use schemars::schema::RootSchema;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
 
 use crate::casing::to_snake_case;
 
+/// Options controlling how schema files are rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// If true, the keys of all JSON objects are sorted alphabetically.
+    /// This keeps the output stable when the order of struct fields or
+    /// schemars internals change, which results in smaller diffs in version control.
+    ///
+    /// Set this to false to get the key order produced by serde.
+    pub sort_keys: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self { sort_keys: true }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CheckError {
+    #[error("Schema file {} does not exist", path.display())]
+    Missing { path: PathBuf },
+    #[error("Schema file {} is out of date", path.display())]
+    Outdated { path: PathBuf },
+    #[error("Error reading schema file {}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+}
+
 /// Exports a schema, auto-generating filename based on the metadata title of the generated schema.
 pub fn export_schema(schema: &RootSchema, out_dir: &Path) {
-    let title = schema
+    export_schema_with_options(schema, out_dir, ExportOptions::default());
+}
+
+/// Like [`export_schema`] but allows customizing the output using [`ExportOptions`].
+pub fn export_schema_with_options(schema: &RootSchema, out_dir: &Path, options: ExportOptions) {
+    write_schema(schema, out_dir, &schema_title(schema), options);
+}
+
+/// Use this if you want to override the auto-detected name of the object.
+/// very useful when creating an alias for a type-alias.
+pub fn export_schema_with_title(schema: &RootSchema, out_dir: &Path, title: &str) {
+    write_schema(
+        &with_title(schema, title),
+        out_dir,
+        title,
+        ExportOptions::default(),
+    );
+}
+
+/// Checks that the file written by [`export_schema`] exists and is up to date,
+/// without writing anything. This allows detecting outdated schemas in CI.
+pub fn export_schema_check(schema: &RootSchema, out_dir: &Path) -> Result<(), CheckError> {
+    check_schema(schema, out_dir, &schema_title(schema))
+}
+
+/// Checks that the file written by [`export_schema_with_title`] exists and is up to date,
+/// without writing anything. This allows detecting outdated schemas in CI.
+pub fn export_schema_with_title_check(
+    schema: &RootSchema,
+    out_dir: &Path,
+    title: &str,
+) -> Result<(), CheckError> {
+    check_schema(&with_title(schema, title), out_dir, title)
+}
+
+/// Serializes the value to pretty-printed JSON. Object keys are sorted if
+/// requested in the options.
+pub(crate) fn to_json_string<T: Serialize + ?Sized>(
+    value: &T,
+    options: ExportOptions,
+) -> Result<String, serde_json::Error> {
+    let mut out = Vec::new();
+    to_json_writer(&mut out, value, options)?;
+    Ok(String::from_utf8(out).expect("serde_json produces valid UTF-8"))
+}
+
+/// Like [`to_json_string`] but writes into the given writer.
+pub(crate) fn to_json_writer<W: io::Write, T: Serialize + ?Sized>(
+    writer: W,
+    value: &T,
+    options: ExportOptions,
+) -> Result<(), serde_json::Error> {
+    if options.sort_keys {
+        let value = sort_keys(serde_json::to_value(value)?);
+        serde_json::to_writer_pretty(writer, &value)
+    } else {
+        serde_json::to_writer_pretty(writer, value)
+    }
+}
+
+/// Recursively sorts the keys of all objects.
+///
+/// This does not rely on the default `Map` implementation of serde_json being sorted,
+/// since it preserves insertion order if the `preserve_order` feature is enabled anywhere
+/// in the dependency tree.
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+fn schema_title(schema: &RootSchema) -> String {
+    schema
         .schema
         .metadata
         .as_ref()
         .map(|b| b.title.clone().unwrap_or_else(|| "untitled".to_string()))
-        .unwrap_or_else(|| "unknown".to_string());
-    write_schema(schema, out_dir, &title);
+        .unwrap_or_else(|| "unknown".to_string())
 }
 
-/// Use this if you want to override the auto-detected name of the object.
-/// very useful when creating an alias for a type-alias.
-pub fn export_schema_with_title(schema: &RootSchema, out_dir: &Path, title: &str) {
+fn with_title(schema: &RootSchema, title: &str) -> RootSchema {
     let mut schema = schema.clone();
     // set the title explicitly on the schema's metadata
     if let Some(metadata) = &mut schema.schema.metadata {
         metadata.title = Some(title.to_string());
     }
-    write_schema(&schema, out_dir, title);
+    schema
+}
+
+fn schema_path(out_dir: &Path, title: &str) -> PathBuf {
+    out_dir.join(format!("{}.json", to_snake_case(title)))
+}
+
+/// Renders the file content including a trailing newline.
+fn render_schema(schema: &RootSchema, options: ExportOptions) -> String {
+    to_json_string(schema, options).unwrap() + "\n"
 }
 
 /// Writes schema to file. Overwrites existing file.
 /// Panics on any error writing out the schema.
-fn write_schema(schema: &RootSchema, out_dir: &Path, title: &str) {
-    // first, we set the title as we wish
-    let path = out_dir.join(format!("{}.json", to_snake_case(title)));
-    let json = serde_json::to_string_pretty(schema).unwrap();
-    write(&path, json + "\n").unwrap();
+fn write_schema(schema: &RootSchema, out_dir: &Path, title: &str, options: ExportOptions) {
+    let path = schema_path(out_dir, title);
+    write(&path, render_schema(schema, options)).unwrap();
     println!("Created {}", path.to_str().unwrap());
 }
+
+fn check_schema(schema: &RootSchema, out_dir: &Path, title: &str) -> Result<(), CheckError> {
+    let path = schema_path(out_dir, title);
+    let existing = match read_to_string(&path) {
+        Ok(existing) => existing,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(CheckError::Missing { path })
+        }
+        Err(source) => return Err(CheckError::Io { path, source }),
+    };
+    if existing != render_schema(schema, ExportOptions::default()) {
+        return Err(CheckError::Outdated { path });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema_for;
+
+    use schemars::JsonSchema;
+    use std::fs::read;
+
+    #[allow(dead_code)]
+    #[derive(JsonSchema)]
+    struct Config {
+        owner: String,
+        admin: Option<String>,
+        count: u32,
+    }
+
+    #[test]
+    fn to_json_string_sorts_keys() {
+        let value = serde_json::json!({
+            "zebra": 1,
+            "alpha": { "second": [{ "b": 1, "a": 2 }], "first": null },
+        });
+        assert_eq!(
+            to_json_string(&value, ExportOptions::default()).unwrap(),
+            r#"{
+  "alpha": {
+    "first": null,
+    "second": [
+      {
+        "a": 2,
+        "b": 1
+      }
+    ]
+  },
+  "zebra": 1
+}"#
+        );
+    }
+
+    #[test]
+    fn export_schema_is_stable() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        export_schema(&schema_for!(Config), dir.path());
+        let first = read(&path).unwrap();
+        export_schema(&schema_for!(Config), dir.path());
+        let second = read(&path).unwrap();
+        assert_eq!(first, second);
+
+        let content = String::from_utf8(first).unwrap();
+        assert!(content.ends_with("}\n"));
+        // struct fields are in alphabetical order, not in declaration order
+        let admin = content.find("\"admin\"").unwrap();
+        let count = content.find("\"count\"").unwrap();
+        let owner = content.find("\"owner\"").unwrap();
+        assert!(admin < count && count < owner);
+    }
+
+    #[test]
+    fn export_schema_with_options_can_preserve_key_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema = schema_for!(Config);
+
+        export_schema_with_options(&schema, dir.path(), ExportOptions { sort_keys: false });
+        let content = read_to_string(dir.path().join("config.json")).unwrap();
+        assert_eq!(
+            content,
+            serde_json::to_string_pretty(&schema).unwrap() + "\n"
+        );
+    }
+
+    #[test]
+    fn export_schema_check_works() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema = schema_for!(Config);
+
+        // missing
+        let err = export_schema_check(&schema, dir.path()).unwrap_err();
+        assert!(matches!(err, CheckError::Missing { .. }));
+
+        // up to date
+        export_schema(&schema, dir.path());
+        export_schema_check(&schema, dir.path()).unwrap();
+
+        // stale
+        export_schema_with_options(&schema, dir.path(), ExportOptions { sort_keys: false });
+        let err = export_schema_check(&schema, dir.path()).unwrap_err();
+        match err {
+            CheckError::Outdated { path } => assert_eq!(path, dir.path().join("config.json")),
+            err => panic!("Unexpected error: {err:?}"),
+        }
+        // the check does not touch the file
+        let err = export_schema_check(&schema, dir.path()).unwrap_err();
+        assert!(matches!(err, CheckError::Outdated { .. }));
+    }
+
+    #[test]
+    fn export_schema_with_title_check_works() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema = schema_for!(Config);
+
+        export_schema_with_title(&schema, dir.path(), "OtherConfig");
+        export_schema_with_title_check(&schema, dir.path(), "OtherConfig").unwrap();
+
+        let err = export_schema_check(&schema, dir.path()).unwrap_err();
+        assert!(matches!(err, CheckError::Missing { .. }));
+    }
+}
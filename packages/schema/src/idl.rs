@@ -5,11 +5,13 @@ use std::collections::BTreeMap;
 use schemars::schema::RootSchema;
 use thiserror::Error;
 
+use crate::export::{to_json_string, to_json_writer, ExportOptions};
+
 /// The version of the CosmWasm IDL.
 ///
 /// Follows Semantic Versioning 2.0.0: <https://semver.org/>
 // To determine if a change is breaking, assume consumers allow unknown fields and bump accordingly.
-pub const IDL_VERSION: &str = "1.0.0";
+pub const IDL_VERSION: &str = "1.1.0";
 
 /// Rust representation of a contract's API.
 pub struct Api {
@@ -22,6 +24,8 @@ pub struct Api {
     pub sudo: Option<RootSchema>,
     /// A mapping of query variants to response types
     pub responses: Option<BTreeMap<String, RootSchema>>,
+    /// The error type returned by the contract's entry points
+    pub errors: Option<RootSchema>,
 }
 
 impl Api {
@@ -36,6 +40,7 @@ impl Api {
             migrate: self.migrate,
             sudo: self.sudo,
             responses: self.responses,
+            errors: self.errors,
         };
 
         if let Some(instantiate) = &mut json_api.instantiate {
@@ -80,61 +85,75 @@ pub struct JsonApi {
     migrate: Option<RootSchema>,
     sudo: Option<RootSchema>,
     responses: Option<BTreeMap<String, RootSchema>>,
+    /// Added in IDL 1.1.0. Omitted if not set to keep the output of existing contracts unchanged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errors: Option<RootSchema>,
 }
 
 impl JsonApi {
+    /// Renders the API as pretty-printed JSON with sorted object keys.
     pub fn to_string(&self) -> Result<String, EncodeError> {
-        serde_json::to_string_pretty(&self).map_err(Into::into)
+        self.to_string_with_options(ExportOptions::default())
+    }
+
+    pub fn to_string_with_options(&self, options: ExportOptions) -> Result<String, EncodeError> {
+        to_json_string(self, options).map_err(Into::into)
     }
 
+    /// Renders the individual schemas as pretty-printed JSON with sorted object keys.
     pub fn to_schema_files(&self) -> Result<Vec<(String, String)>, EncodeError> {
+        self.to_schema_files_with_options(ExportOptions::default())
+    }
+
+    pub fn to_schema_files_with_options(
+        &self,
+        options: ExportOptions,
+    ) -> Result<Vec<(String, String)>, EncodeError> {
         let mut result = Vec::new();
 
         if let Some(instantiate) = &self.instantiate {
             result.push((
                 "instantiate.json".to_string(),
-                serde_json::to_string_pretty(&instantiate)?,
+                to_json_string(instantiate, options)?,
             ));
         }
 
         if let Some(execute) = &self.execute {
             result.push((
                 "execute.json".to_string(),
-                serde_json::to_string_pretty(&execute)?,
+                to_json_string(execute, options)?,
             ));
         }
         if let Some(query) = &self.query {
-            result.push((
-                "query.json".to_string(),
-                serde_json::to_string_pretty(&query)?,
-            ));
+            result.push(("query.json".to_string(), to_json_string(query, options)?));
         }
         if let Some(migrate) = &self.migrate {
             result.push((
                 "migrate.json".to_string(),
-                serde_json::to_string_pretty(&migrate)?,
+                to_json_string(migrate, options)?,
             ));
         }
         if let Some(sudo) = &self.sudo {
-            result.push((
-                "sudo.json".to_string(),
-                serde_json::to_string_pretty(&sudo)?,
-            ));
+            result.push(("sudo.json".to_string(), to_json_string(sudo, options)?));
         }
         if let Some(responses) = &self.responses {
             for (name, response) in responses {
                 result.push((
                     format!("response_to_{name}.json"),
-                    serde_json::to_string_pretty(&response)?,
+                    to_json_string(response, options)?,
                 ));
             }
         }
+        if let Some(errors) = &self.errors {
+            result.push(("errors.json".to_string(), to_json_string(errors, options)?));
+        }
 
         Ok(result)
     }
 
+    /// Writes the API as pretty-printed JSON with sorted object keys.
     pub fn to_writer(&self, writer: impl std::io::Write) -> Result<(), EncodeError> {
-        serde_json::to_writer_pretty(writer, self).map_err(Into::into)
+        to_json_writer(writer, self, ExportOptions::default()).map_err(Into::into)
     }
 }
 
@@ -166,6 +185,7 @@ mod tests {
             migrate: None,
             sudo: None,
             responses: None,
+            errors: None,
         };
 
         let files = empty.render().to_schema_files().unwrap();
@@ -186,15 +206,66 @@ mod tests {
                 "TestMsg".to_string(),
                 schema_for!(TestMsg),
             )])),
+            errors: Some(schema_for!(TestMsg)),
         };
 
         let files = full.render().to_schema_files().unwrap();
-        assert_eq!(files.len(), 6);
+        assert_eq!(files.len(), 7);
         assert_eq!(files[0].0, "instantiate.json");
         assert_eq!(files[1].0, "execute.json");
         assert_eq!(files[2].0, "query.json");
         assert_eq!(files[3].0, "migrate.json");
         assert_eq!(files[4].0, "sudo.json");
         assert_eq!(files[5].0, "response_to_TestMsg.json");
+        assert_eq!(files[6].0, "errors.json");
+    }
+
+    #[test]
+    fn to_string_sorts_keys() {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct TestMsg {
+            zebra: u32,
+            alpha: u32,
+        }
+
+        let api = || Api {
+            contract_name: "my_contract".to_string(),
+            contract_version: "1.2.3".to_string(),
+            instantiate: Some(schema_for!(TestMsg)),
+            execute: None,
+            query: None,
+            migrate: None,
+            sudo: None,
+            responses: None,
+            errors: None,
+        };
+
+        // stable across runs
+        let json = api().render().to_string().unwrap();
+        assert_eq!(json, api().render().to_string().unwrap());
+
+        // top level keys are sorted
+        assert!(json.find("\"responses\"").unwrap() < json.find("\"sudo\"").unwrap());
+        let contract_name = json.find("\"contract_name\"").unwrap();
+        let idl_version = json.find("\"idl_version\"").unwrap();
+        let instantiate = json.find("\"instantiate\"").unwrap();
+        assert!(contract_name < idl_version && idl_version < instantiate);
+
+        // nested keys are sorted
+        let (_, instantiate) = &api().render().to_schema_files().unwrap()[0];
+        assert!(instantiate.find("\"alpha\"").unwrap() < instantiate.find("\"zebra\"").unwrap());
+
+        // serde order can be preserved
+        let json = api()
+            .render()
+            .to_string_with_options(ExportOptions { sort_keys: false })
+            .unwrap();
+        assert!(json.find("\"sudo\"").unwrap() < json.find("\"responses\"").unwrap());
+
+        // to_writer matches to_string
+        let mut written = Vec::new();
+        api().render().to_writer(&mut written).unwrap();
+        assert_eq!(written, api().render().to_string().unwrap().into_bytes());
     }
 }
@@ -5,9 +5,14 @@ mod query_response;
 mod remove;
 mod schema_for;
 
-pub use export::{export_schema, export_schema_with_title};
+pub use export::{
+    export_schema, export_schema_check, export_schema_with_options, export_schema_with_title,
+    export_schema_with_title_check, CheckError, ExportOptions,
+};
 pub use idl::{Api, IDL_VERSION};
-pub use query_response::{combine_subqueries, IntegrityError, QueryResponses};
+pub use query_response::{
+    combine_subqueries, try_combine_subqueries, IntegrityError, QueryResponses,
+};
 pub use remove::remove_schemas;
 
 // Re-exports
@@ -74,6 +79,13 @@ pub use cosmwasm_schema_derive::generate_api;
 /// - `execute` - execute msg type, empty by default
 /// - `migrate` - migrate msg type, empty by default
 /// - `sudo` - sudo msg type, empty by default
+/// - `errors` - the error type returned by the contract's entry points, empty by default.
+///   This type must implement `JsonSchema`.
+/// - `responses` - response types of the queries in the form `{ VariantName: Type, ... }`,
+///   empty by default. Requires `query`. This is useful if the query type does not implement
+///   [`QueryResponses`](crate::QueryResponses), e.g. because it is defined in a dependency.
+///   The responses are merged with the ones from `QueryResponses` if it is implemented,
+///   overriding them for the listed variants.
 ///
 /// # Example
 /// ```
@@ -91,8 +103,39 @@ pub use cosmwasm_schema_derive::generate_api;
 ///     migrate: MigrateMsg,
 /// };
 /// ```
+///
+/// Specifying the query responses explicitly:
+///
+/// ```
+/// use cosmwasm_schema::{cw_serde, write_api};
+///
+/// #[cw_serde]
+/// struct InstantiateMsg;
+///
+/// #[cw_serde]
+/// enum QueryMsg {
+///     Balance { address: String },
+///     Config {},
+/// }
+///
+/// #[cw_serde]
+/// struct ConfigResponse {
+///     owner: String,
+/// }
+///
+/// write_api! {
+///     instantiate: InstantiateMsg,
+///     query: QueryMsg,
+///     responses: {
+///         Balance: u128,
+///         Config: ConfigResponse,
+///     },
+/// };
+/// ```
 pub use cosmwasm_schema_derive::write_api;
 
 // For use in macro expansions
+#[doc(hidden)]
+pub use query_response::{DerivedResponses, DerivedResponsesFallback, DerivedResponsesViaTrait};
 pub use schemars;
 pub use serde;
@@ -1,4 +1,5 @@
 use std::collections::{BTreeMap, BTreeSet};
+use std::marker::PhantomData;
 
 use schemars::{schema::RootSchema, JsonSchema};
 use thiserror::Error;
@@ -62,6 +63,41 @@ pub use cosmwasm_schema_derive::QueryResponses;
 /// #     IcqHandle: String,
 /// # }
 /// ```
+///
+/// The `nested` flag can also be set on individual variants to mix regular queries with
+/// nested ones. Such variants should be `#[serde(untagged)]`, such that the queries of the
+/// nested enum are part of the outer enum.
+///
+/// ```
+/// # use cosmwasm_schema::QueryResponses;
+/// # use schemars::JsonSchema;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize, JsonSchema, QueryResponses)]
+/// #[serde(rename_all = "snake_case")]
+/// enum QueryMsg {
+///     #[returns(Vec<String>)]
+///     Denoms {},
+///     #[serde(untagged)]
+///     #[query_responses(nested)]
+///     Accounts(AccountQuery),
+/// }
+///
+/// #[derive(Serialize, Deserialize, JsonSchema, QueryResponses)]
+/// #[serde(rename_all = "snake_case")]
+/// enum AccountQuery {
+///     #[returns(String)]
+///     AccountInfo { account: String },
+/// }
+/// ```
+///
+/// If the same query occurs more than once, e.g. in two nested enums, generating the
+/// response schemas panics with [`IntegrityError::DuplicateQuery`].
+///
+/// Queries that return nothing can use `#[returns(())]`. The unit type is serialized as `null`
+/// and its response schema is `{"type": "null"}`. This is different from an empty struct such as
+/// `cosmwasm_std::Empty`, which is serialized as `{}` and described by an object schema without
+/// properties. Make sure the response type matches what the contract actually returns, since
+/// code generators rely on the schema to decode the response.
 pub trait QueryResponses: JsonSchema {
     fn response_schemas() -> Result<BTreeMap<String, RootSchema>, IntegrityError> {
         let response_schemas = Self::response_schemas_impl();
@@ -77,15 +113,61 @@ pub trait QueryResponses: JsonSchema {
 pub fn combine_subqueries<const N: usize, T>(
     subqueries: [BTreeMap<String, RootSchema>; N],
 ) -> BTreeMap<String, RootSchema> {
-    let sub_count = subqueries.iter().flatten().count();
-    let map: BTreeMap<_, _> = subqueries.into_iter().flatten().collect();
-    if map.len() != sub_count {
-        panic!(
-            "name collision in subqueries for {}",
+    match try_combine_subqueries(subqueries) {
+        Ok(map) => map,
+        Err(err) => panic!(
+            "name collision in subqueries for {}: {err}",
             std::any::type_name::<T>()
-        )
+        ),
+    }
+}
+
+/// Combines multiple response schemas into one.
+/// Returns [`IntegrityError::DuplicateQuery`] if the same query occurs more than once.
+pub fn try_combine_subqueries<const N: usize>(
+    subqueries: [BTreeMap<String, RootSchema>; N],
+) -> Result<BTreeMap<String, RootSchema>, IntegrityError> {
+    let mut map = BTreeMap::new();
+    for (query, schema) in subqueries.into_iter().flatten() {
+        if map.contains_key(&query) {
+            return Err(IntegrityError::DuplicateQuery { query });
+        }
+        map.insert(query, schema);
+    }
+    Ok(map)
+}
+
+/// Collects the response schemas of the query type `T` if it implements [`QueryResponses`]
+/// and returns an empty map otherwise.
+///
+/// Used in the expansion of `generate_api!` when response types are specified manually.
+/// This relies on autoref-based specialization: calling `derived_response_schemas` on
+/// `&&DerivedResponses<T>` picks [`DerivedResponsesViaTrait`] if `T` implements
+/// [`QueryResponses`] and falls back to [`DerivedResponsesFallback`] otherwise.
+/// This only works for concrete types.
+#[doc(hidden)]
+pub struct DerivedResponses<T>(pub PhantomData<T>);
+
+#[doc(hidden)]
+pub trait DerivedResponsesViaTrait {
+    fn derived_response_schemas(&self) -> BTreeMap<String, RootSchema>;
+}
+
+impl<T: QueryResponses> DerivedResponsesViaTrait for &DerivedResponses<T> {
+    fn derived_response_schemas(&self) -> BTreeMap<String, RootSchema> {
+        T::response_schemas().unwrap()
+    }
+}
+
+#[doc(hidden)]
+pub trait DerivedResponsesFallback {
+    fn derived_response_schemas(&self) -> BTreeMap<String, RootSchema>;
+}
+
+impl<T> DerivedResponsesFallback for DerivedResponses<T> {
+    fn derived_response_schemas(&self) -> BTreeMap<String, RootSchema> {
+        BTreeMap::new()
     }
-    map
 }
 
 #[derive(Debug, Error, PartialEq, Eq)]
@@ -101,6 +183,8 @@ pub enum IntegrityError {
         query_msg: BTreeSet<String>,
         responses: BTreeSet<String>,
     },
+    #[error("the query {query:?} is defined more than once")]
+    DuplicateQuery { query: String },
 }
 
 #[cfg(test)]
@@ -221,4 +305,52 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn try_combine_subqueries_works() {
+        let combined = try_combine_subqueries([
+            BTreeMap::from([("config".to_string(), schema_for!(String))]),
+            GoodMsg::response_schemas_impl(),
+        ])
+        .unwrap();
+        assert_eq!(combined.len(), 6);
+        assert_eq!(combined["config"], schema_for!(String));
+
+        let err = try_combine_subqueries([
+            GoodMsg::response_schemas_impl(),
+            BTreeMap::from([("supply".to_string(), schema_for!(String))]),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err,
+            IntegrityError::DuplicateQuery {
+                query: "supply".to_string()
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "the query \"supply\" is defined more than once"
+        );
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrow)] // the borrows select the implementation
+    fn derived_responses_works() {
+        // implements QueryResponses
+        let responses = (&&DerivedResponses::<GoodMsg>(PhantomData)).derived_response_schemas();
+        assert_eq!(responses, GoodMsg::response_schemas().unwrap());
+
+        // does not implement QueryResponses
+        let responses = (&&DerivedResponses::<String>(PhantomData)).derived_response_schemas();
+        assert_eq!(responses, BTreeMap::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "the query \"account_count\" is defined more than once")]
+    fn combine_subqueries_panics_for_duplicates() {
+        combine_subqueries::<2, UntaggedMsg>([
+            GoodMsg::response_schemas_impl(),
+            GoodMsg::response_schemas_impl(),
+        ]);
+    }
 }
@@ -99,6 +99,50 @@ fn test_basic_structure() {
     );
 }
 
+#[cw_serde]
+pub enum ContractError {
+    Unauthorized {},
+    CapExceeded { cap: u128 },
+}
+
+#[test]
+fn test_errors() {
+    let api_str = generate_api! {
+        name: "test",
+        version: "0.1.0",
+        instantiate: InstantiateMsg,
+        errors: ContractError,
+    }
+    .render()
+    .to_string()
+    .unwrap();
+
+    let api: Value = serde_json::from_str(&api_str).unwrap();
+    let errors = api.get("errors").unwrap();
+    assert_eq!(errors.get("title").unwrap(), "ContractError");
+    let variants: Vec<_> = errors
+        .get("oneOf")
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|variant| variant.get("required").unwrap()[0].as_str().unwrap())
+        .collect();
+    assert_eq!(variants, ["unauthorized", "cap_exceeded"]);
+
+    // errors are omitted if not set
+    let api_str = generate_api! {
+        name: "test",
+        version: "0.1.0",
+        instantiate: InstantiateMsg,
+    }
+    .render()
+    .to_string()
+    .unwrap();
+    let api: Value = serde_json::from_str(&api_str).unwrap();
+    assert!(api.get("errors").is_none());
+}
+
 // Test to reproduce https://github.com/CosmWasm/cosmwasm/issues/1527
 #[test]
 fn generate_api_works_when_only_types_are_imported() {
@@ -155,6 +199,113 @@ fn test_query_responses() {
     api.get("responses").unwrap().get("balance").unwrap();
 }
 
+/// Like `cosmwasm_std::Empty`
+#[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct Empty {}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsgWithEmptyResponses {
+    #[returns(())]
+    Ping {},
+    #[returns(Empty)]
+    Status {},
+}
+
+#[test]
+fn test_query_responses_unit_and_empty() {
+    let api_str = generate_api! {
+        instantiate: InstantiateMsg,
+        query: QueryMsgWithEmptyResponses,
+    }
+    .render()
+    .to_string()
+    .unwrap();
+
+    let api: Value = serde_json::from_str(&api_str).unwrap();
+    let responses = api.get("responses").unwrap();
+
+    // the unit type is serialized as `null`
+    assert_eq!(serde_json::to_value(()).unwrap(), Value::Null);
+    let ping = responses.get("ping").unwrap();
+    assert_eq!(ping.get("type").unwrap(), "null");
+
+    // an empty struct is serialized as `{}`
+    assert_eq!(
+        serde_json::to_value(Empty {}).unwrap(),
+        serde_json::json!({})
+    );
+    let status = responses.get("status").unwrap();
+    assert_eq!(status.get("type").unwrap(), "object");
+    assert_eq!(status.get("title").unwrap(), "Empty");
+    assert!(status.get("required").is_none());
+}
+
+/// A query enum without `QueryResponses`, e.g. from a dependency
+#[cw_serde]
+pub enum ExternalQueryMsg {
+    Balance { account: String },
+    Config {},
+}
+
+#[test]
+fn test_explicit_query_responses() {
+    let api_str = generate_api! {
+        instantiate: InstantiateMsg,
+        query: ExternalQueryMsg,
+        responses: {
+            Balance: u128,
+            Config: InstantiateMsg,
+        },
+    }
+    .render()
+    .to_string()
+    .unwrap();
+
+    let api: Value = serde_json::from_str(&api_str).unwrap();
+    let responses = api.get("responses").unwrap().as_object().unwrap();
+    assert_eq!(responses.keys().collect::<Vec<_>>(), ["balance", "config"]);
+    assert_eq!(responses["balance"].get("type").unwrap(), "integer");
+    assert_eq!(responses["config"].get("title").unwrap(), "InstantiateMsg");
+}
+
+#[test]
+fn test_explicit_query_responses_override_derived_ones() {
+    let api_str = generate_api! {
+        instantiate: InstantiateMsg,
+        query: QueryMsg,
+        responses: {
+            Balance: String,
+        },
+    }
+    .render()
+    .to_string()
+    .unwrap();
+
+    let api: Value = serde_json::from_str(&api_str).unwrap();
+    let responses = api.get("responses").unwrap().as_object().unwrap();
+    assert_eq!(responses.keys().collect::<Vec<_>>(), ["balance"]);
+    assert_eq!(responses["balance"].get("title").unwrap(), "String");
+
+    // merged with the derived ones
+    let api_str = generate_api! {
+        instantiate: InstantiateMsg,
+        query: QueryMsgWithEmptyResponses,
+        responses: {
+            Status: String,
+        },
+    }
+    .render()
+    .to_string()
+    .unwrap();
+
+    let api: Value = serde_json::from_str(&api_str).unwrap();
+    let responses = api.get("responses").unwrap().as_object().unwrap();
+    assert_eq!(responses.keys().collect::<Vec<_>>(), ["ping", "status"]);
+    assert_eq!(responses["ping"].get("type").unwrap(), "null");
+    assert_eq!(responses["status"].get("title").unwrap(), "String");
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsgWithGenerics<T> {
@@ -266,6 +417,40 @@ fn test_nested_query_responses() {
     .unwrap();
 }
 
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum MixedQueryMsg {
+    #[returns(String)]
+    Config {},
+    #[serde(untagged)]
+    #[query_responses(nested)]
+    Sub(SubQueryMsg1),
+}
+
+#[test]
+fn test_mixed_query_responses() {
+    let api_str = generate_api! {
+        instantiate: InstantiateMsg,
+        query: MixedQueryMsg,
+    }
+    .render()
+    .to_string()
+    .unwrap();
+
+    let api: Value = serde_json::from_str(&api_str).unwrap();
+    let responses = api.get("responses").unwrap().as_object().unwrap();
+    assert_eq!(responses.keys().collect::<Vec<_>>(), ["config", "variant1"]);
+
+    // the nested queries are part of the outer enum
+    let msg: MixedQueryMsg = serde_json::from_str(r#"{"variant1":{"test":"foo"}}"#).unwrap();
+    assert_eq!(
+        msg,
+        MixedQueryMsg::Sub(SubQueryMsg1::Variant1 {
+            test: "foo".to_string()
+        })
+    );
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 #[serde(untagged)]
@@ -303,7 +488,7 @@ enum NestedNameCollision {
 }
 
 #[test]
-#[should_panic = "name collision in subqueries for idl::NestedNameCollision"]
+#[should_panic = "name collision in subqueries for idl::NestedNameCollision: the query \"balance\" is defined more than once"]
 fn nested_name_collision_caught() {
     generate_api! {
         instantiate: InstantiateMsg,
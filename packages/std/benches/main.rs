@@ -0,0 +1,91 @@
+use bnum::types::{U256, U512};
+use criterion::{criterion_group, criterion_main, Criterion, PlottingBackend};
+use std::{hint::black_box, str::FromStr, time::Duration};
+
+use cosmwasm_std::{Uint256, Uint512};
+
+// Operands of different widths. The `u128` and `u256` divisors are handled
+// by the fast path for `Uint256` and `Uint512` respectively.
+const DIVIDEND_256: &str =
+    "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+const DIVIDEND_512: &str = "13407807929942597099574024998205846127479365820592393377723561443721764030073546976801874298166903427690031858186486050853753882811946569946433649006084095";
+const DIVISOR_64: &str = "18446744073709551557";
+const DIVISOR_128: &str = "340282366920938463463374607431768211297";
+const DIVISOR_256: &str =
+    "115792089237316195423570985008687907853269984665640564039457584007913129639747";
+const DIVISOR_512: &str = "13407807929942597099574024998205846127479365820592393377723561443721764030073546976801874298166903427690031858186486050853753882811946569946433649006084041";
+
+fn bench_uint256(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Uint256");
+
+    let dividend = Uint256::from_str(DIVIDEND_256).unwrap();
+    let dividend_bnum = U256::from_str(DIVIDEND_256).unwrap();
+    for (name, divisor) in [
+        ("u64", DIVISOR_64),
+        ("u128", DIVISOR_128),
+        ("u256", DIVISOR_256),
+    ] {
+        let divisor_bnum = U256::from_str(divisor).unwrap();
+        let divisor = Uint256::from_str(divisor).unwrap();
+
+        group.bench_function(format!("div_{name}"), |b| {
+            b.iter(|| black_box(dividend) / black_box(divisor));
+        });
+        group.bench_function(format!("rem_{name}"), |b| {
+            b.iter(|| black_box(dividend) % black_box(divisor));
+        });
+        group.bench_function(format!("div_{name}_bnum"), |b| {
+            b.iter(|| black_box(dividend_bnum) / black_box(divisor_bnum));
+        });
+        group.bench_function(format!("rem_{name}_bnum"), |b| {
+            b.iter(|| black_box(dividend_bnum) % black_box(divisor_bnum));
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_uint512(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Uint512");
+
+    let dividend = Uint512::from_str(DIVIDEND_512).unwrap();
+    let dividend_bnum = U512::from_str(DIVIDEND_512).unwrap();
+    for (name, divisor) in [
+        ("u64", DIVISOR_64),
+        ("u128", DIVISOR_128),
+        ("u256", DIVISOR_256),
+        ("u512", DIVISOR_512),
+    ] {
+        let divisor_bnum = U512::from_str(divisor).unwrap();
+        let divisor = Uint512::from_str(divisor).unwrap();
+
+        group.bench_function(format!("div_{name}"), |b| {
+            b.iter(|| black_box(dividend) / black_box(divisor));
+        });
+        group.bench_function(format!("rem_{name}"), |b| {
+            b.iter(|| black_box(dividend) % black_box(divisor));
+        });
+        group.bench_function(format!("div_{name}_bnum"), |b| {
+            b.iter(|| black_box(dividend_bnum) / black_box(divisor_bnum));
+        });
+        group.bench_function(format!("rem_{name}_bnum"), |b| {
+            b.iter(|| black_box(dividend_bnum) % black_box(divisor_bnum));
+        });
+    }
+
+    group.finish();
+}
+
+fn make_config() -> Criterion {
+    Criterion::default()
+        .plotting_backend(PlottingBackend::Plotters)
+        .without_plots()
+        .measurement_time(Duration::new(3, 0))
+}
+
+criterion_group!(
+    name = math;
+    config = make_config();
+    targets = bench_uint256, bench_uint512
+);
+criterion_main!(math);
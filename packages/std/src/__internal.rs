@@ -6,4 +6,5 @@
 //! Module for re-exporting implementation details from `core` to `std`
 //!
 
-pub use crate::__internal__forward_ref_partial_eq as forward_ref_partial_eq;
+pub use crate::requirements::capability_features;
+pub use cosmwasm_core::__internal::forward_ref_partial_eq;
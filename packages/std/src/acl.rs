@@ -0,0 +1,454 @@
+use crate::prelude::*;
+use crate::storage_keys::namespace_with_key;
+#[cfg(feature = "iterator")]
+use crate::storage_keys::to_length_prefixed_nested;
+use crate::{Addr, Api, Event, StdError, StdResult, Storage};
+#[cfg(feature = "iterator")]
+use crate::{CanonicalAddr, Order, PageRequest};
+
+/// The value stored for every role member. Storage does not support empty values.
+const MEMBER_MARKER: &[u8] = &[1];
+
+/// A role based access control list persisted in contract storage.
+///
+/// Every (role, address) pair is stored under its own key in a length-prefixed
+/// namespace `[namespace, role]` with the canonical address bytes as the key.
+/// This allows checking membership with a single read and listing all members of
+/// a role using a range query.
+///
+/// ## Examples
+///
+/// ```
+/// # use cosmwasm_std::testing::mock_dependencies;
+/// use cosmwasm_std::{Acl, Response};
+///
+/// const ACL: Acl = Acl::new("acl");
+///
+/// let mut deps = mock_dependencies();
+/// let admin = deps.api.addr_make("admin");
+/// let operator = deps.api.addr_make("operator");
+///
+/// // instantiate
+/// ACL.grant(&mut deps.storage, &deps.api, "admin", &admin).unwrap();
+///
+/// // execute by admin
+/// ACL.assert(&deps.storage, &deps.api, "admin", &admin).unwrap();
+/// ACL.grant(&mut deps.storage, &deps.api, "operator", &operator).unwrap();
+/// let _response: Response = Response::new().add_event(Acl::grant_event("operator", &operator));
+///
+/// // query with read-only access
+/// let deps = deps.as_ref();
+/// assert!(ACL.assert(deps.storage, deps.api, "admin", &operator).is_err());
+/// assert!(ACL.has(deps.storage, deps.api, "operator", &operator).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Acl {
+    namespace: &'static str,
+}
+
+impl Acl {
+    /// Creates an access control list stored under the given namespace.
+    ///
+    /// The namespace must not be used by any other storage item of the contract.
+    pub const fn new(namespace: &'static str) -> Self {
+        Self { namespace }
+    }
+
+    /// Grants the role to the given address.
+    ///
+    /// Returns true if the address did not have the role before and false if
+    /// it already had the role, in which case this is a no-op.
+    pub fn grant(
+        &self,
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        role: &str,
+        addr: &Addr,
+    ) -> StdResult<bool> {
+        let key = self.member_key(api, role, addr)?;
+        if storage.get(&key).is_some() {
+            return Ok(false);
+        }
+        storage.set(&key, MEMBER_MARKER);
+        Ok(true)
+    }
+
+    /// Revokes the role from the given address.
+    ///
+    /// Returns true if the address had the role before and false if
+    /// it did not have the role, in which case this is a no-op.
+    pub fn revoke(
+        &self,
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        role: &str,
+        addr: &Addr,
+    ) -> StdResult<bool> {
+        let key = self.member_key(api, role, addr)?;
+        if storage.get(&key).is_none() {
+            return Ok(false);
+        }
+        storage.remove(&key);
+        Ok(true)
+    }
+
+    /// Returns true if the given address has the role.
+    pub fn has(
+        &self,
+        storage: &dyn Storage,
+        api: &dyn Api,
+        role: &str,
+        addr: &Addr,
+    ) -> StdResult<bool> {
+        let key = self.member_key(api, role, addr)?;
+        Ok(storage.get(&key).is_some())
+    }
+
+    /// Returns an error if the given address does not have the role.
+    pub fn assert(
+        &self,
+        storage: &dyn Storage,
+        api: &dyn Api,
+        role: &str,
+        addr: &Addr,
+    ) -> StdResult<()> {
+        if self.has(storage, api, role, addr)? {
+            Ok(())
+        } else {
+            Err(StdError::generic_err(format!(
+                "Address {addr} does not have role {role}"
+            )))
+        }
+    }
+
+    /// Lists the members of a role ordered by their canonical address.
+    ///
+    /// `pagination.key` is the canonical address of the last member of the previous page.
+    /// The returned page starts after this member. `pagination.limit` is the maximum
+    /// number of members returned, where 0 means no limit.
+    #[cfg(feature = "iterator")]
+    pub fn list(
+        &self,
+        storage: &dyn Storage,
+        api: &dyn Api,
+        role: &str,
+        pagination: PageRequest,
+    ) -> StdResult<Vec<Addr>> {
+        let prefix = to_length_prefixed_nested(&[self.namespace.as_bytes(), role.as_bytes()]);
+        let (start, end) = match pagination.key {
+            None => (prefix.clone(), prefix_end(&prefix)),
+            Some(key) if pagination.reverse => {
+                let end = [prefix.as_slice(), key.as_slice()].concat();
+                (prefix.clone(), Some(end))
+            }
+            Some(key) => {
+                // Appending a zero byte makes the inclusive start bound exclusive
+                let mut start = [prefix.as_slice(), key.as_slice()].concat();
+                start.push(0);
+                (start, prefix_end(&prefix))
+            }
+        };
+        let order = if pagination.reverse {
+            Order::Descending
+        } else {
+            Order::Ascending
+        };
+        let limit = if pagination.limit == 0 {
+            usize::MAX
+        } else {
+            pagination.limit as usize
+        };
+
+        storage
+            .range_keys(Some(&start), end.as_deref(), order)
+            .take(limit)
+            .map(|key| api.addr_humanize(&CanonicalAddr::from(&key[prefix.len()..])))
+            .collect()
+    }
+
+    /// Creates an event documenting that the role was granted to the given address.
+    ///
+    /// The event has the type `acl` and the attributes `action` (`grant`), `role` and `address`.
+    pub fn grant_event(role: &str, addr: &Addr) -> Event {
+        Self::event("grant", role, addr)
+    }
+
+    /// Creates an event documenting that the role was revoked from the given address.
+    ///
+    /// The event has the type `acl` and the attributes `action` (`revoke`), `role` and `address`.
+    pub fn revoke_event(role: &str, addr: &Addr) -> Event {
+        Self::event("revoke", role, addr)
+    }
+
+    fn event(action: &str, role: &str, addr: &Addr) -> Event {
+        Event::new("acl")
+            .add_attribute("action", action)
+            .add_attribute("role", role)
+            .add_attribute("address", addr.as_str())
+    }
+
+    fn member_key(&self, api: &dyn Api, role: &str, addr: &Addr) -> StdResult<Vec<u8>> {
+        let canonical = api.addr_canonicalize(addr.as_str())?;
+        Ok(namespace_with_key(
+            &[self.namespace.as_bytes(), role.as_bytes()],
+            canonical.as_slice(),
+        ))
+    }
+}
+
+/// Returns the smallest key that is larger than all keys starting with `prefix`
+/// or `None` if there is no such key.
+#[cfg(feature = "iterator")]
+fn prefix_end(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut end = prefix.to_vec();
+    while let Some(last) = end.pop() {
+        if last < u8::MAX {
+            end.push(last + 1);
+            return Some(end);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::mock_dependencies;
+
+    const ACL: Acl = Acl::new("acl");
+
+    #[test]
+    fn grant_and_revoke_work() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+
+        assert!(!ACL.has(&deps.storage, &deps.api, "admin", &alice).unwrap());
+
+        assert!(ACL
+            .grant(&mut deps.storage, &deps.api, "admin", &alice)
+            .unwrap());
+        assert!(ACL.has(&deps.storage, &deps.api, "admin", &alice).unwrap());
+        // other addresses and roles are unaffected
+        assert!(!ACL.has(&deps.storage, &deps.api, "admin", &bob).unwrap());
+        assert!(!ACL
+            .has(&deps.storage, &deps.api, "operator", &alice)
+            .unwrap());
+
+        assert!(ACL
+            .revoke(&mut deps.storage, &deps.api, "admin", &alice)
+            .unwrap());
+        assert!(!ACL.has(&deps.storage, &deps.api, "admin", &alice).unwrap());
+    }
+
+    #[test]
+    fn grant_and_revoke_are_idempotent() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+
+        assert!(ACL
+            .grant(&mut deps.storage, &deps.api, "admin", &alice)
+            .unwrap());
+        assert!(!ACL
+            .grant(&mut deps.storage, &deps.api, "admin", &alice)
+            .unwrap());
+        assert!(ACL.has(&deps.storage, &deps.api, "admin", &alice).unwrap());
+
+        assert!(ACL
+            .revoke(&mut deps.storage, &deps.api, "admin", &alice)
+            .unwrap());
+        assert!(!ACL
+            .revoke(&mut deps.storage, &deps.api, "admin", &alice)
+            .unwrap());
+        assert!(!ACL.has(&deps.storage, &deps.api, "admin", &alice).unwrap());
+    }
+
+    #[test]
+    fn namespaces_are_separated() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        let other = Acl::new("other");
+
+        ACL.grant(&mut deps.storage, &deps.api, "admin", &alice)
+            .unwrap();
+        assert!(!other
+            .has(&deps.storage, &deps.api, "admin", &alice)
+            .unwrap());
+
+        // roles cannot collide with each other through the length prefix
+        ACL.grant(&mut deps.storage, &deps.api, "a", &alice)
+            .unwrap();
+        assert!(!ACL.has(&deps.storage, &deps.api, "ad", &alice).unwrap());
+    }
+
+    #[test]
+    fn assert_works() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+
+        let err = ACL
+            .assert(&deps.storage, &deps.api, "pauser", &alice)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(format!("Address {alice} does not have role pauser"))
+        );
+
+        ACL.grant(&mut deps.storage, &deps.api, "pauser", &alice)
+            .unwrap();
+        ACL.assert(&deps.storage, &deps.api, "pauser", &alice)
+            .unwrap();
+    }
+
+    #[test]
+    fn invalid_address_errors() {
+        let mut deps = mock_dependencies();
+        let invalid = Addr::unchecked("invalid");
+
+        ACL.grant(&mut deps.storage, &deps.api, "admin", &invalid)
+            .unwrap_err();
+        ACL.has(&deps.storage, &deps.api, "admin", &invalid)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn works_with_read_only_storage() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+        ACL.grant(&mut deps.storage, &deps.api, "admin", &alice)
+            .unwrap();
+
+        // a query only has access to Deps
+        let deps = deps.as_ref();
+        assert!(ACL.has(deps.storage, deps.api, "admin", &alice).unwrap());
+        ACL.assert(deps.storage, deps.api, "admin", &alice).unwrap();
+        #[cfg(feature = "iterator")]
+        assert_eq!(
+            ACL.list(deps.storage, deps.api, "admin", PageRequest::default())
+                .unwrap(),
+            [alice]
+        );
+    }
+
+    #[cfg(feature = "iterator")]
+    #[test]
+    fn list_works() {
+        let mut deps = mock_dependencies();
+        let mut members: Vec<Addr> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| deps.api.addr_make(name))
+            .collect();
+        for member in &members {
+            ACL.grant(&mut deps.storage, &deps.api, "operator", member)
+                .unwrap();
+        }
+        // members in other roles and namespaces are not listed
+        let outsider = deps.api.addr_make("outsider");
+        ACL.grant(&mut deps.storage, &deps.api, "operatorx", &outsider)
+            .unwrap();
+        ACL.grant(&mut deps.storage, &deps.api, "admin", &outsider)
+            .unwrap();
+        Acl::new("other")
+            .grant(&mut deps.storage, &deps.api, "operator", &outsider)
+            .unwrap();
+
+        // members are ordered by canonical address
+        members.sort_by_key(|addr| deps.api.addr_canonicalize(addr.as_str()).unwrap().to_vec());
+
+        let all = ACL
+            .list(&deps.storage, &deps.api, "operator", PageRequest::default())
+            .unwrap();
+        assert_eq!(all, members);
+
+        let reversed = ACL
+            .list(
+                &deps.storage,
+                &deps.api,
+                "operator",
+                PageRequest {
+                    key: None,
+                    limit: 0,
+                    reverse: true,
+                },
+            )
+            .unwrap();
+        let mut expected = members.clone();
+        expected.reverse();
+        assert_eq!(reversed, expected);
+
+        let empty = ACL
+            .list(&deps.storage, &deps.api, "pauser", PageRequest::default())
+            .unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[cfg(feature = "iterator")]
+    #[test]
+    fn list_paginates() {
+        let mut deps = mock_dependencies();
+        let mut members: Vec<Addr> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| deps.api.addr_make(name))
+            .collect();
+        for member in &members {
+            ACL.grant(&mut deps.storage, &deps.api, "operator", member)
+                .unwrap();
+        }
+        members.sort_by_key(|addr| deps.api.addr_canonicalize(addr.as_str()).unwrap().to_vec());
+
+        for reverse in [false, true] {
+            let mut expected = members.clone();
+            if reverse {
+                expected.reverse();
+            }
+
+            let mut collected = vec![];
+            let mut key = None;
+            loop {
+                let page = ACL
+                    .list(
+                        &deps.storage,
+                        &deps.api,
+                        "operator",
+                        PageRequest {
+                            key,
+                            limit: 2,
+                            reverse,
+                        },
+                    )
+                    .unwrap();
+                assert!(page.len() <= 2);
+                let Some(last) = page.last() else {
+                    break;
+                };
+                key = Some(deps.api.addr_canonicalize(last.as_str()).unwrap().into());
+                collected.extend(page);
+            }
+            assert_eq!(collected, expected);
+        }
+    }
+
+    #[cfg(feature = "iterator")]
+    #[test]
+    fn prefix_end_works() {
+        assert_eq!(prefix_end(b""), None);
+        assert_eq!(prefix_end(b"\xff\xff"), None);
+        assert_eq!(prefix_end(b"ab"), Some(b"ac".to_vec()));
+        assert_eq!(prefix_end(b"a\xff"), Some(b"b".to_vec()));
+    }
+
+    #[test]
+    fn events_work() {
+        let addr = Addr::unchecked("cosmwasm1abc");
+
+        let event = Acl::grant_event("admin", &addr);
+        assert_eq!(event.ty, "acl");
+        assert_eq!(event.attributes[0].value, "grant");
+        assert_eq!(event.attributes[1].value, "admin");
+        assert_eq!(event.attributes[2].value, "cosmwasm1abc");
+
+        let event = Acl::revoke_event("admin", &addr);
+        assert_eq!(event.ty, "acl");
+        assert_eq!(event.attributes[0].value, "revoke");
+    }
+}
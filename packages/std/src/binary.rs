@@ -1,11 +1,13 @@
 use alloc::{string::String, vec::Vec};
+use core::borrow::Borrow;
 use core::fmt;
-use core::ops::Deref;
+use core::ops::{Bound, Deref, RangeBounds};
 
 use serde::{de, ser, Deserialize, Deserializer, Serialize};
+use subtle::ConstantTimeEq;
 
 use crate::{
-    encoding::{from_base64, to_base64},
+    encoding::{from_base64, from_hex, to_base64, to_hex},
     errors::{StdError, StdResult},
 };
 
@@ -14,6 +16,12 @@ use crate::{
 ///
 /// This is only needed as serde-json-{core,wasm} has a horrible encoding for Vec<u8>.
 /// See also <https://github.com/CosmWasm/cosmwasm/blob/main/docs/MESSAGE_TYPES.md>.
+///
+/// `Eq`, `Ord` and `Hash` behave exactly like the ones of the underlying bytes (`[u8]`),
+/// i.e. values are ordered lexicographically byte by byte and a shorter value that is a prefix
+/// of a longer one is ordered first. This is guaranteed to stay stable, such that `Binary`
+/// can safely be used as a key in sorted collections. Together with the `Borrow<[u8]>`
+/// implementation, this allows looking up `Binary` keys in maps and sets by `&[u8]`.
 #[derive(Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, schemars::JsonSchema)]
 pub struct Binary(#[schemars(with = "String")] Vec<u8>);
 
@@ -35,12 +43,44 @@ impl Binary {
         to_base64(&self.0)
     }
 
+    /// take an (untrusted) string and decode it into bytes.
+    /// fails if it is not valid hex (without `0x` prefix, upper or lower case)
+    pub fn from_hex(input: &str) -> StdResult<Self> {
+        from_hex(input).map(Self::new)
+    }
+
+    /// encode to lower case hex string (without `0x` prefix)
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.0)
+    }
+
     pub fn as_slice(&self) -> &[u8] {
         self.0.as_slice()
     }
 
+    /// Compares the content with the given bytes in constant time.
+    ///
+    /// Use this instead of `==` when comparing secret material like hash preimages or
+    /// MACs against user input, such that the comparison does not leak through the
+    /// execution time how many leading bytes match. Only the length is not kept secret,
+    /// i.e. values of different lengths are rejected immediately.
+    ///
+    /// ```
+    /// # use cosmwasm_std::Binary;
+    /// let secret = Binary::from(b"secret preimage");
+    /// assert!(secret.ct_eq(b"secret preimage"));
+    /// assert!(!secret.ct_eq(b"secret preimagf"));
+    /// assert!(!secret.ct_eq(b"secret"));
+    /// ```
+    pub fn ct_eq(&self, other: &[u8]) -> bool {
+        self.0.as_slice().ct_eq(other).into()
+    }
+
     /// Copies content into fixed-sized array.
     ///
+    /// Returns an [`StdError::InvalidDataSize`] error containing the expected and actual
+    /// length if the length of the data does not match the array length.
+    ///
     /// # Examples
     ///
     /// Copy to array of explicit length
@@ -69,6 +109,72 @@ impl Binary {
         out.copy_from_slice(&self.0);
         Ok(out)
     }
+
+    /// Returns `true` if the data starts with the given prefix.
+    pub fn starts_with(&self, prefix: &[u8]) -> bool {
+        self.0.starts_with(prefix)
+    }
+
+    /// Returns the data after the given prefix or `None` if the data does not
+    /// start with the prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cosmwasm_std::Binary;
+    /// let binary = Binary::from(b"prefix:value");
+    /// assert_eq!(binary.strip_prefix(b"prefix:"), Some(b"value".as_slice()));
+    /// assert_eq!(binary.strip_prefix(b"other:"), None);
+    /// ```
+    pub fn strip_prefix(&self, prefix: &[u8]) -> Option<&[u8]> {
+        self.0.strip_prefix(prefix)
+    }
+
+    /// Creates a new `Binary` containing this data followed by `other`.
+    pub fn concat(&self, other: &[u8]) -> Self {
+        let mut out = Vec::with_capacity(self.0.len() + other.len());
+        out.extend_from_slice(&self.0);
+        out.extend_from_slice(other);
+        Self(out)
+    }
+
+    /// Copies the given range of the data into a new `Binary`.
+    ///
+    /// Unlike indexing a slice, this does not panic but returns an error if the range
+    /// is out of bounds or its start is greater than its end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cosmwasm_std::Binary;
+    /// let binary = Binary::from(&[1, 2, 3, 4, 5]);
+    /// assert_eq!(binary.slice(1..3).unwrap(), [2, 3]);
+    /// assert_eq!(binary.slice(3..).unwrap(), [4, 5]);
+    /// assert!(binary.slice(3..6).is_err());
+    /// ```
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> StdResult<Self> {
+        checked_range(&self.0, range).map(Self::from)
+    }
+}
+
+/// Returns the subslice of `data` in the given range or an error if the range is invalid.
+pub(crate) fn checked_range(data: &[u8], range: impl RangeBounds<usize>) -> StdResult<&[u8]> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.saturating_add(1),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => data.len(),
+    };
+    data.get(start..end).ok_or_else(|| {
+        StdError::generic_err(format!(
+            "Range {start}..{end} is invalid for data of length {}",
+            data.len()
+        ))
+    })
 }
 
 impl fmt::Display for Binary {
@@ -109,6 +215,14 @@ impl AsRef<[u8]> for Binary {
     }
 }
 
+/// This must only be implemented as long as `Eq`, `Ord` and `Hash` of `Binary`
+/// are equivalent to the ones of `[u8]`.
+impl Borrow<[u8]> for Binary {
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
 // Slice
 impl From<&[u8]> for Binary {
     fn from(binary: &[u8]) -> Self {
@@ -272,6 +386,9 @@ mod tests {
     use super::*;
     use crate::assert_hash_works;
     use crate::errors::StdError;
+    use crate::HexBinary;
+    use proptest::{prop_assert_eq, property_test};
+    use std::collections::{BTreeMap, HashMap};
 
     #[test]
     fn to_array_works() {
@@ -297,6 +414,8 @@ mod tests {
             }
             err => panic!("Unexpected error: {err:?}"),
         }
+        let error = binary.to_array::<32>().unwrap_err();
+        assert_eq!(error.to_string(), "Invalid data size: expected=32 actual=3");
 
         // long array (32 bytes)
         let binary = Binary::from_base64("t119JOQox4WUQEmO/nyqOZfO+wjJm91YG2sfn4ZglvA=").unwrap();
@@ -354,6 +473,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn hex_encoding_works() {
+        for (value, encoded) in [
+            (&b""[..], ""),
+            (&b"hello"[..], "68656c6c6f"),
+            (&b"\x0C\xBB\x00\x11\xFA\x01"[..], "0cbb0011fa01"),
+        ] {
+            let value = Binary::from(value);
+            assert_eq!(value.to_hex(), encoded);
+            assert_eq!(Binary::from_hex(encoded).unwrap(), value);
+            assert_eq!(Binary::from_hex(&encoded.to_uppercase()).unwrap(), value);
+        }
+
+        // odd
+        match Binary::from_hex("123").unwrap_err() {
+            StdError::InvalidHex { msg, .. } => assert_eq!(msg, "Odd number of digits"),
+            err => panic!("Unexpected error: {err:?}"),
+        }
+        // 0x prefixed
+        match Binary::from_hex("0xaa").unwrap_err() {
+            StdError::InvalidHex { msg, .. } => {
+                assert_eq!(msg, "Invalid character 'x' at position 1")
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn hex_binary_conversion_works() {
+        let binary = Binary::from_hex("0cbb0011fa01").unwrap();
+        let hex_binary = HexBinary::from(binary.clone());
+        assert_eq!(hex_binary.to_hex(), binary.to_hex());
+        assert_eq!(Binary::from(hex_binary), binary);
+
+        let hex_binary = HexBinary::from_hex("68656c6c6f").unwrap();
+        assert_eq!(Binary::from(hex_binary).to_base64(), "aGVsbG8=");
+    }
+
     #[test]
     fn from_slice_works() {
         let original: &[u8] = &[0u8, 187, 61, 11, 250, 0];
@@ -560,6 +717,108 @@ mod tests {
         assert_hash_works!(a, b);
     }
 
+    #[test]
+    fn binary_starts_with_and_strip_prefix_work() {
+        let data = Binary::from(b"key:value");
+        assert!(data.starts_with(b""));
+        assert!(data.starts_with(b"key:"));
+        assert!(data.starts_with(b"key:value"));
+        assert!(!data.starts_with(b"value"));
+        assert!(!data.starts_with(b"key:value:"));
+
+        assert_eq!(data.strip_prefix(b""), Some(b"key:value".as_slice()));
+        assert_eq!(data.strip_prefix(b"key:"), Some(b"value".as_slice()));
+        assert_eq!(data.strip_prefix(b"key:value"), Some(b"".as_slice()));
+        assert_eq!(data.strip_prefix(b"value"), None);
+    }
+
+    #[test]
+    fn binary_concat_works() {
+        let data = Binary::from(b"key:");
+        assert_eq!(data.concat(b"value"), Binary::from(b"key:value"));
+        assert_eq!(data.concat(b""), data);
+        assert_eq!(Binary::default().concat(b"value"), Binary::from(b"value"));
+        // original is unchanged
+        assert_eq!(data, Binary::from(b"key:"));
+    }
+
+    #[test]
+    fn binary_slice_works() {
+        let data = Binary::from(&[1, 2, 3, 4, 5]);
+        assert_eq!(data.slice(..).unwrap(), data);
+        assert_eq!(data.slice(1..3).unwrap(), [2, 3]);
+        assert_eq!(data.slice(1..=3).unwrap(), [2, 3, 4]);
+        assert_eq!(data.slice(..2).unwrap(), [1, 2]);
+        assert_eq!(data.slice(3..).unwrap(), [4, 5]);
+        assert_eq!(data.slice(5..).unwrap(), Binary::default());
+        assert_eq!(data.slice(2..2).unwrap(), Binary::default());
+
+        // out of bounds
+        let err = data.slice(3..6).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Range 3..6 is invalid for data of length 5"
+        );
+        data.slice(6..).unwrap_err();
+        data.slice(..=5).unwrap_err();
+        data.slice(..=usize::MAX).unwrap_err();
+
+        // start greater than end
+        #[allow(clippy::reversed_empty_ranges)]
+        let err = data.slice(3..2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Range 3..2 is invalid for data of length 5"
+        );
+    }
+
+    #[test]
+    fn binary_implements_borrow_for_lookups_by_slice() {
+        let a = Binary::from(b"alpha");
+        let b = Binary::from(b"beta");
+
+        let mut tree = BTreeMap::new();
+        tree.insert(a.clone(), 1);
+        tree.insert(b.clone(), 2);
+        assert_eq!(tree.get(b"alpha".as_slice()), Some(&1));
+        assert_eq!(tree.get(b"beta".as_slice()), Some(&2));
+        assert_eq!(tree.get(b"gamma".as_slice()), None);
+
+        let mut map = HashMap::new();
+        map.insert(a, 1);
+        map.insert(b, 2);
+        assert_eq!(map.get(b"alpha".as_slice()), Some(&1));
+        assert_eq!(map.get(b"beta".as_slice()), Some(&2));
+        assert_eq!(map.get(b"gamma".as_slice()), None);
+    }
+
+    #[test]
+    fn binary_ord_is_lexicographic() {
+        let empty = Binary::default();
+        let a = Binary::from(&[0x00]);
+        let b = Binary::from(&[0x00, 0x00]);
+        let c = Binary::from(&[0x01]);
+        let d = Binary::from(&[0xff, 0x00]);
+        // shorter prefix comes first, then byte by byte
+        assert!(empty < a);
+        assert!(a < b);
+        assert!(b < c);
+        assert!(c < d);
+
+        let mut sorted = vec![d.clone(), b.clone(), empty.clone(), c.clone(), a.clone()];
+        sorted.sort();
+        assert_eq!(sorted, vec![empty, a, b, c, d]);
+    }
+
+    #[property_test]
+    fn binary_ord_matches_vec_ord(a: Vec<u8>, b: Vec<u8>) {
+        let binary_a = Binary::from(a.clone());
+        let binary_b = Binary::from(b.clone());
+        prop_assert_eq!(binary_a.cmp(&binary_b), a.cmp(&b));
+        prop_assert_eq!(binary_a.partial_cmp(&binary_b), a.partial_cmp(&b));
+        prop_assert_eq!(binary_a == binary_b, a == b);
+    }
+
     #[test]
     fn binary_implements_partial_eq_with_vector() {
         let a = Binary(vec![5u8; 3]);
@@ -571,6 +830,19 @@ mod tests {
         assert_ne!(c, a);
     }
 
+    #[test]
+    fn binary_ct_eq_works() {
+        let a = Binary::from(b"\xAA\xBB\xCC");
+        assert!(a.ct_eq(b"\xAA\xBB\xCC"));
+        assert!(a.ct_eq(&Binary::from(b"\xAA\xBB\xCC")));
+        assert!(!a.ct_eq(b"\xAA\xBB\xCD"));
+        assert!(!a.ct_eq(b"\x00\xBB\xCC"));
+        assert!(!a.ct_eq(b"\xAA\xBB"));
+        assert!(!a.ct_eq(b"\xAA\xBB\xCC\xDD"));
+        assert!(!a.ct_eq(b""));
+        assert!(Binary::default().ct_eq(b""));
+    }
+
     #[test]
     fn binary_implements_partial_eq_with_slice_and_array() {
         let a = Binary(vec![0xAA, 0xBB]);
@@ -593,4 +865,19 @@ mod tests {
         assert_ne!(a, [0x11, 0x22]);
         assert_ne!([0x11, 0x22], a);
     }
+
+    #[test]
+    fn binary_comparisons_need_no_type_annotations() {
+        let a = Binary::from(b"\xAA\xBB");
+
+        // the common forms in tests all compile without hints
+        assert!(a == Binary::from(b"\xAA\xBB"));
+        assert!(a == b"\xAA\xBB");
+        assert!(a == *b"\xAA\xBB");
+        assert!(a == b"\xAA\xBB".to_vec());
+        assert!(a == a.as_slice());
+        assert!(a == vec![0xAA, 0xBB]);
+        assert_eq!(a, [0xAA, 0xBB]);
+        assert_eq!(a.to_vec(), a);
+    }
 }
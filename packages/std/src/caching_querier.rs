@@ -0,0 +1,313 @@
+use alloc::collections::BTreeMap;
+use core::cell::RefCell;
+
+use crate::prelude::*;
+#[cfg(feature = "cosmwasm_1_2")]
+use crate::query::CodeInfoResponse;
+use crate::query::{ContractInfoResponse, CustomQuery, QueryRequest, WasmQuery};
+use crate::results::Empty;
+use crate::{from_json, Binary, QuerierWrapper, StdResult};
+
+/// The default for [`CachingQuerier::with_max_entries`]
+const DEFAULT_MAX_ENTRIES: usize = 256;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum CacheKey {
+    ContractInfo {
+        contract_addr: String,
+    },
+    #[cfg(feature = "cosmwasm_1_2")]
+    CodeInfo {
+        code_id: u64,
+    },
+    Raw {
+        contract_addr: String,
+        key: Binary,
+    },
+}
+
+/// A wrapper around [`QuerierWrapper`] that remembers the responses of contract info,
+/// code info and raw queries, such that repeating the same query does not cost another
+/// call to the host.
+///
+/// Only successful responses are cached. Once the cache holds the maximum number of entries,
+/// new responses are not cached anymore until [`CachingQuerier::invalidate`] is called.
+///
+/// ## Consistency
+///
+/// The cached responses are not updated when the queried state changes. This is safe within
+/// a single call of an entry point, where the queried state cannot change. It is not safe to
+/// keep a `CachingQuerier` around while the state can change, e.g. when the contract itself
+/// writes to the queried keys or across submessages in the same transaction. Call
+/// [`CachingQuerier::invalidate`] in those cases or create a new one.
+///
+/// ## Examples
+///
+/// ```
+/// # use cosmwasm_std::{testing::mock_dependencies, CachingQuerier};
+/// let deps = mock_dependencies();
+/// let querier = CachingQuerier::new(deps.as_ref().querier);
+/// // Both calls send the same query, but only the first one reaches the querier.
+/// let first = querier.query_wasm_raw("contract", b"config".as_slice());
+/// let second = querier.query_wasm_raw("contract", b"config".as_slice());
+/// ```
+pub struct CachingQuerier<'a, C: CustomQuery = Empty> {
+    querier: QuerierWrapper<'a, C>,
+    cache: RefCell<BTreeMap<CacheKey, Binary>>,
+    max_entries: usize,
+}
+
+impl<'a, C: CustomQuery> CachingQuerier<'a, C> {
+    /// Creates a new cache for the given querier, which holds up to 256 responses.
+    pub fn new(querier: QuerierWrapper<'a, C>) -> Self {
+        Self::with_max_entries(querier, DEFAULT_MAX_ENTRIES)
+    }
+
+    /// Creates a new cache for the given querier, which holds up to `max_entries` responses.
+    pub fn with_max_entries(querier: QuerierWrapper<'a, C>, max_entries: usize) -> Self {
+        CachingQuerier {
+            querier,
+            cache: RefCell::new(BTreeMap::new()),
+            max_entries,
+        }
+    }
+
+    /// Returns the wrapped querier, which can be used for queries that should not be cached.
+    pub fn querier(&self) -> QuerierWrapper<'a, C> {
+        self.querier
+    }
+
+    /// The number of cached responses
+    pub fn len(&self) -> usize {
+        self.cache.borrow().len()
+    }
+
+    /// Returns `true` if no responses are cached
+    pub fn is_empty(&self) -> bool {
+        self.cache.borrow().is_empty()
+    }
+
+    /// Removes all cached responses, such that the next queries reach the querier again.
+    pub fn invalidate(&self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    /// Like [`QuerierWrapper::query_wasm_contract_info`], but cached.
+    pub fn query_wasm_contract_info(
+        &self,
+        contract_addr: impl Into<String>,
+    ) -> StdResult<ContractInfoResponse> {
+        let contract_addr = contract_addr.into();
+        let request = WasmQuery::ContractInfo {
+            contract_addr: contract_addr.clone(),
+        };
+        let raw = self.query_cached(CacheKey::ContractInfo { contract_addr }, request)?;
+        from_json(raw)
+    }
+
+    /// Like [`QuerierWrapper::query_wasm_code_info`], but cached.
+    #[cfg(feature = "cosmwasm_1_2")]
+    pub fn query_wasm_code_info(&self, code_id: u64) -> StdResult<CodeInfoResponse> {
+        let request = WasmQuery::CodeInfo { code_id };
+        let raw = self.query_cached(CacheKey::CodeInfo { code_id }, request)?;
+        from_json(raw)
+    }
+
+    /// Like [`QuerierWrapper::query_wasm_raw`], but cached.
+    pub fn query_wasm_raw(
+        &self,
+        contract_addr: impl Into<String>,
+        key: impl Into<Binary>,
+    ) -> StdResult<Option<Vec<u8>>> {
+        let contract_addr = contract_addr.into();
+        let key = key.into();
+        let request = WasmQuery::Raw {
+            contract_addr: contract_addr.clone(),
+            key: key.clone(),
+        };
+        let raw = self.query_cached(CacheKey::Raw { contract_addr, key }, request)?;
+        // an empty response means the key does not exist (see `QuerierWrapper::query_wasm_raw`)
+        if raw.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(raw.into()))
+        }
+    }
+
+    fn query_cached(&self, key: CacheKey, request: WasmQuery) -> StdResult<Binary> {
+        if let Some(raw) = self.cache.borrow().get(&key) {
+            return Ok(raw.clone());
+        }
+        let raw = self.querier.query_raw(&QueryRequest::Wasm(request))?;
+        let mut cache = self.cache.borrow_mut();
+        if cache.len() < self.max_entries {
+            cache.insert(key, raw.clone());
+        }
+        Ok(raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::MockQuerier;
+    use crate::{
+        to_json_binary, Addr, ContractResult, Querier, QuerierResult, StdError, SystemError,
+        SystemResult,
+    };
+    use core::cell::Cell;
+
+    /// Counts the queries that reach the wrapped querier
+    struct CountingQuerier {
+        querier: MockQuerier,
+        count: Cell<usize>,
+    }
+
+    impl CountingQuerier {
+        fn new() -> Self {
+            let mut querier = MockQuerier::new(&[]);
+            querier.update_wasm(|query| -> QuerierResult {
+                let response = match query {
+                    WasmQuery::ContractInfo { contract_addr } if contract_addr == "pool" => {
+                        to_json_binary(&contract_info()).unwrap()
+                    }
+                    #[cfg(feature = "cosmwasm_1_2")]
+                    WasmQuery::CodeInfo { code_id: 7 } => to_json_binary(&CodeInfoResponse::new(
+                        7,
+                        Addr::unchecked("creator"),
+                        crate::Checksum::generate(b"wasm"),
+                    ))
+                    .unwrap(),
+                    WasmQuery::Raw { key, .. } if key.as_slice() == b"config" => {
+                        Binary::from(b"value")
+                    }
+                    WasmQuery::Raw { .. } => Binary::default(),
+                    _ => {
+                        return SystemResult::Err(SystemError::NoSuchContract {
+                            addr: "unknown".to_string(),
+                        })
+                    }
+                };
+                SystemResult::Ok(ContractResult::Ok(response))
+            });
+            CountingQuerier {
+                querier,
+                count: Cell::new(0),
+            }
+        }
+    }
+
+    impl Querier for CountingQuerier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            self.count.set(self.count.get() + 1);
+            self.querier.raw_query(bin_request)
+        }
+    }
+
+    fn contract_info() -> ContractInfoResponse {
+        ContractInfoResponse::new(1, Addr::unchecked("creator"), None, false, None)
+    }
+
+    #[test]
+    fn query_wasm_contract_info_is_cached() {
+        let backend = CountingQuerier::new();
+        let querier = CachingQuerier::<Empty>::new(QuerierWrapper::new(&backend));
+
+        assert_eq!(
+            querier.query_wasm_contract_info("pool").unwrap(),
+            contract_info()
+        );
+        assert_eq!(backend.count.get(), 1);
+        assert_eq!(
+            querier.query_wasm_contract_info("pool").unwrap(),
+            contract_info()
+        );
+        assert_eq!(backend.count.get(), 1);
+        assert_eq!(querier.len(), 1);
+    }
+
+    #[cfg(feature = "cosmwasm_1_2")]
+    #[test]
+    fn query_wasm_code_info_is_cached() {
+        let backend = CountingQuerier::new();
+        let querier = CachingQuerier::<Empty>::new(QuerierWrapper::new(&backend));
+
+        let info = querier.query_wasm_code_info(7).unwrap();
+        assert_eq!(info.code_id, 7);
+        assert_eq!(querier.query_wasm_code_info(7).unwrap(), info);
+        assert_eq!(backend.count.get(), 1);
+    }
+
+    #[test]
+    fn query_wasm_raw_is_cached() {
+        let backend = CountingQuerier::new();
+        let querier = CachingQuerier::<Empty>::new(QuerierWrapper::new(&backend));
+
+        for _ in 0..2 {
+            let value = querier
+                .query_wasm_raw("pool", b"config".as_slice())
+                .unwrap();
+            assert_eq!(value, Some(b"value".to_vec()));
+            let value = querier
+                .query_wasm_raw("pool", b"missing".as_slice())
+                .unwrap();
+            assert_eq!(value, None);
+        }
+        assert_eq!(backend.count.get(), 2);
+
+        // different contract, same key
+        querier
+            .query_wasm_raw("other", b"config".as_slice())
+            .unwrap();
+        assert_eq!(backend.count.get(), 3);
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let backend = CountingQuerier::new();
+        let querier = CachingQuerier::<Empty>::new(QuerierWrapper::new(&backend));
+
+        for _ in 0..2 {
+            let err = querier.query_wasm_contract_info("unknown").unwrap_err();
+            assert!(matches!(err, StdError::GenericErr { .. }));
+        }
+        assert_eq!(backend.count.get(), 2);
+        assert!(querier.is_empty());
+    }
+
+    #[test]
+    fn invalidate_works() {
+        let backend = CountingQuerier::new();
+        let querier = CachingQuerier::<Empty>::new(QuerierWrapper::new(&backend));
+
+        querier.query_wasm_contract_info("pool").unwrap();
+        querier.invalidate();
+        assert!(querier.is_empty());
+        querier.query_wasm_contract_info("pool").unwrap();
+        assert_eq!(backend.count.get(), 2);
+    }
+
+    #[test]
+    fn max_entries_works() {
+        let backend = CountingQuerier::new();
+        let querier = CachingQuerier::<Empty>::with_max_entries(QuerierWrapper::new(&backend), 1);
+
+        querier.query_wasm_contract_info("pool").unwrap();
+        querier
+            .query_wasm_raw("pool", b"config".as_slice())
+            .unwrap();
+        assert_eq!(querier.len(), 1);
+
+        // the first response is cached, the second one is not
+        querier.query_wasm_contract_info("pool").unwrap();
+        assert_eq!(backend.count.get(), 2);
+        querier
+            .query_wasm_raw("pool", b"config".as_slice())
+            .unwrap();
+        assert_eq!(backend.count.get(), 3);
+
+        // uncached access is still available
+        querier.querier().query_wasm_contract_info("pool").unwrap();
+        assert_eq!(backend.count.get(), 4);
+    }
+}
@@ -3,17 +3,22 @@ use core::fmt;
 use schemars::JsonSchema;
 use serde::{de, ser, Deserialize, Deserializer, Serialize};
 use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
 use thiserror::Error;
 
+use crate::encoding::{from_base64, to_base64};
 use crate::prelude::*;
-use crate::{StdError, StdResult};
+use crate::{HexBinary, StdError, StdResult};
 
 /// A SHA-256 checksum of a Wasm blob, used to identify a Wasm code.
 /// This must remain stable since this checksum is stored in the blockchain state.
 ///
 /// This is often referred to as "code ID" in go-cosmwasm, even if code ID
 /// usually refers to an auto-incrementing number.
-#[derive(JsonSchema, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+///
+/// Checksums are ordered by their bytes, which is the same as the order of their
+/// lowercase hex representations. This allows using them as keys in a `BTreeMap`.
+#[derive(JsonSchema, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Checksum(#[schemars(with = "String")] [u8; 32]);
 
 impl Checksum {
@@ -21,6 +26,25 @@ impl Checksum {
         Checksum(Sha256::digest(wasm).into())
     }
 
+    /// Creates a checksum of all data read from the given reader.
+    ///
+    /// This is useful for hashing large Wasm files without loading them into memory at once.
+    #[cfg(feature = "std")]
+    pub fn generate_from_reader<R: std::io::Read>(mut reader: R) -> std::io::Result<Self> {
+        let mut hasher = Sha256::new();
+        let mut buffer = [0u8; 8192];
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            hasher.update(&buffer[..read]);
+        }
+        Ok(Checksum(hasher.finalize().into()))
+    }
+
     /// Tries to parse the given hex string into a checksum.
     /// Errors if the string contains non-hex characters or does not contain 32 bytes.
     pub fn from_hex(input: &str) -> StdResult<Self> {
@@ -30,6 +54,55 @@ impl Checksum {
         Ok(Self(binary))
     }
 
+    /// Parses a hex string of exactly 64 characters into a checksum.
+    ///
+    /// In contrast to [`Checksum::from_hex`], the length is validated before any
+    /// hex digit is decoded. Both upper- and lowercase digits are accepted.
+    /// No allocations happen unless an error is returned.
+    pub fn from_hex_str(input: &str) -> StdResult<Self> {
+        const HEX_LENGTH: usize = 64;
+
+        if input.len() != HEX_LENGTH {
+            return Err(StdError::invalid_data_size(HEX_LENGTH, input.len()));
+        }
+
+        let mut binary = [0u8; 32];
+        for (index, byte) in input.bytes().enumerate() {
+            let nibble = match byte {
+                b'0'..=b'9' => byte - b'0',
+                b'a'..=b'f' => byte - b'a' + 10,
+                b'A'..=b'F' => byte - b'A' + 10,
+                _ => {
+                    return Err(StdError::invalid_hex(
+                        hex::FromHexError::InvalidHexCharacter {
+                            c: input[index..].chars().next().unwrap_or_default(),
+                            index,
+                        },
+                    ))
+                }
+            };
+            binary[index / 2] |= if index % 2 == 0 { nibble << 4 } else { nibble };
+        }
+
+        Ok(Self(binary))
+    }
+
+    /// Tries to parse the given base64 string into a checksum.
+    /// Errors if the string is not valid base64 or does not contain 32 bytes.
+    pub fn from_base64(input: &str) -> StdResult<Self> {
+        let binary = from_base64(input)?;
+        Self::try_from(binary.as_slice())
+            .map_err(|ChecksumError| StdError::invalid_data_size(32, binary.len()))
+    }
+
+    /// Compares two checksums in constant time.
+    ///
+    /// Prefer this over `==` when comparing a user-supplied checksum against a
+    /// stored one, such that the comparison does not leak timing information.
+    pub fn ct_eq(&self, other: &Checksum) -> bool {
+        self.0[..].ct_eq(&other.0[..]).into()
+    }
+
     /// Creates a lowercase hex encoded copy of this checksum.
     ///
     /// This takes an owned `self` instead of a reference because `Checksum` is cheap to `Copy`.
@@ -37,6 +110,13 @@ impl Checksum {
         self.to_string()
     }
 
+    /// Creates a base64 encoded copy of this checksum.
+    ///
+    /// This takes an owned `self` instead of a reference because `Checksum` is cheap to `Copy`.
+    pub fn to_base64(self) -> String {
+        to_base64(self.0)
+    }
+
     /// Returns a reference to the inner bytes of this checksum as a slice.
     /// If you need a reference to the array, use [`AsRef::as_ref`].
     pub fn as_slice(&self) -> &[u8] {
@@ -147,17 +227,32 @@ impl TryFrom<&[u8]> for Checksum {
     }
 }
 
+impl TryFrom<HexBinary> for Checksum {
+    type Error = ChecksumError;
+
+    fn try_from(value: HexBinary) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_slice())
+    }
+}
+
 impl From<Checksum> for Vec<u8> {
     fn from(original: Checksum) -> Vec<u8> {
         original.0.into()
     }
 }
 
+impl From<Checksum> for HexBinary {
+    fn from(original: Checksum) -> HexBinary {
+        HexBinary::from(original.0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use crate::to_json_string;
+    use alloc::collections::BTreeMap;
 
     #[test]
     fn generate_works() {
@@ -207,6 +302,64 @@ mod tests {
         assert!(Checksum::from_hex(too_long).is_err());
     }
 
+    #[test]
+    fn from_hex_str_works() {
+        // echo -n "hij" | sha256sum
+        let checksum = "722c8c993fd75a7627d69ed941344fe2a1423a3e75efd3e6778a142884227104";
+        let parsed = Checksum::from_hex_str(checksum).unwrap();
+        assert_eq!(parsed, Checksum::generate(b"hij"));
+        assert_eq!(parsed.to_hex(), checksum);
+
+        // uppercase
+        let parsed = Checksum::from_hex_str(&checksum.to_uppercase()).unwrap();
+        assert_eq!(parsed, Checksum::generate(b"hij"));
+
+        // invalid length
+        let too_short = "722c8c993fd75a7627d69ed941344fe2a1423a3e75efd3e6778a1428842271";
+        match Checksum::from_hex_str(too_short).unwrap_err() {
+            StdError::InvalidDataSize {
+                expected, actual, ..
+            } => {
+                assert_eq!(expected, 64);
+                assert_eq!(actual, 62);
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+        let too_long = "722c8c993fd75a7627d69ed941344fe2a1423a3e75efd3e6778a14288422710400";
+        assert!(matches!(
+            Checksum::from_hex_str(too_long).unwrap_err(),
+            StdError::InvalidDataSize { .. }
+        ));
+
+        // invalid character
+        let invalid_char = "722c8c993fd75a7627d69ed941344fe2a1423a3e75efd3e6778a1428842271g4";
+        match Checksum::from_hex_str(invalid_char).unwrap_err() {
+            StdError::InvalidHex { msg, .. } => {
+                assert_eq!(msg, "Invalid character 'g' at position 62")
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+        let multi_byte_char = "722c8c993fd75a7627d69ed941344fe2a1423a3e75efd3e6778a14288422ü04";
+        match Checksum::from_hex_str(multi_byte_char).unwrap_err() {
+            StdError::InvalidHex { msg, .. } => {
+                assert_eq!(msg, "Invalid character 'ü' at position 60")
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn ct_eq_works() {
+        let checksum1 = Checksum::generate(b"hij");
+        let checksum2 = Checksum::generate(b"hij");
+        let checksum3 = Checksum::generate(b"klm");
+
+        assert!(checksum1.ct_eq(&checksum2));
+        assert!(checksum2.ct_eq(&checksum1));
+        assert!(!checksum1.ct_eq(&checksum3));
+        assert!(!checksum3.ct_eq(&checksum1));
+    }
+
     #[test]
     fn to_hex_works() {
         let wasm = vec![0x68, 0x69, 0x6a];
@@ -218,6 +371,100 @@ mod tests {
         );
     }
 
+    #[test]
+    fn base64_works() {
+        let checksum = Checksum::generate(b"hij");
+        let encoded = checksum.to_base64();
+        // echo -n "hij" | sha256sum | xxd -r -p | base64
+        assert_eq!(encoded, "ciyMmT/XWnYn1p7ZQTRP4qFCOj5179Pmd4oUKIQicQQ=");
+        assert_eq!(Checksum::from_base64(&encoded).unwrap(), checksum);
+
+        // wrong length
+        match Checksum::from_base64("AAEC").unwrap_err() {
+            StdError::InvalidDataSize {
+                expected, actual, ..
+            } => {
+                assert_eq!(expected, 32);
+                assert_eq!(actual, 3);
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        // invalid base64
+        match Checksum::from_base64("!!!").unwrap_err() {
+            StdError::InvalidBase64 { .. } => {}
+            err => panic!("Unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn try_from_slice_checks_length() {
+        let checksum = Checksum::generate(b"hij");
+        assert_eq!(Checksum::try_from(checksum.as_slice()).unwrap(), checksum);
+        Checksum::try_from([1u8; 31].as_slice()).unwrap_err();
+        Checksum::try_from([1u8; 33].as_slice()).unwrap_err();
+    }
+
+    #[test]
+    fn ordering_works() {
+        let low = Checksum::from([0x00; 32]);
+        let mid = Checksum::from([0x7f; 32]);
+        let high = Checksum::from([0xff; 32]);
+        assert!(low < mid);
+        assert!(mid < high);
+        // ordering is consistent with the hex representation
+        assert_eq!(mid.cmp(&high), mid.to_hex().cmp(&high.to_hex()));
+
+        let mut map = BTreeMap::new();
+        map.insert(high, "high");
+        map.insert(low, "low");
+        map.insert(mid, "mid");
+        let values: Vec<_> = map.values().copied().collect();
+        assert_eq!(values, ["low", "mid", "high"]);
+    }
+
+    #[test]
+    fn generate_from_reader_works() {
+        let data = vec![0xab; 20_000];
+        let checksum = Checksum::generate_from_reader(data.as_slice()).unwrap();
+        assert_eq!(checksum, Checksum::generate(&data));
+
+        let empty = Checksum::generate_from_reader(std::io::empty()).unwrap();
+        assert_eq!(empty, Checksum::generate(&[]));
+    }
+
+    #[test]
+    fn try_from_hex_binary_works() {
+        // echo -n "hij" | sha256sum
+        let hex =
+            HexBinary::from_hex("722c8c993fd75a7627d69ed941344fe2a1423a3e75efd3e6778a142884227104")
+                .unwrap();
+        let checksum = Checksum::try_from(hex).unwrap();
+        assert_eq!(checksum, Checksum::generate(b"hij"));
+
+        // too short
+        let hex = HexBinary::from_hex("722c8c993fd75a7627d69ed941344fe2").unwrap();
+        Checksum::try_from(hex).unwrap_err();
+
+        // too long
+        let hex = HexBinary::from([0xab; 33]);
+        Checksum::try_from(hex).unwrap_err();
+
+        // empty
+        Checksum::try_from(HexBinary::default()).unwrap_err();
+    }
+
+    #[test]
+    fn into_hex_binary_works() {
+        let checksum = Checksum::generate(b"hij");
+        let hex = HexBinary::from(checksum);
+        assert_eq!(hex.as_slice(), checksum.as_slice());
+        assert_eq!(hex.to_hex(), checksum.to_hex());
+
+        // round trip
+        assert_eq!(Checksum::try_from(hex).unwrap(), checksum);
+    }
+
     #[test]
     fn into_vec_works() {
         let checksum = Checksum::generate(&[12u8; 17]);
@@ -3,8 +3,9 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::prelude::*;
-use crate::CoinFromStrError;
 use crate::Uint128;
+use crate::{format_amount, DenomMetadata};
+use crate::{CoinFromStrError, Decimal, StdResult};
 
 #[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema)]
 pub struct Coin {
@@ -19,6 +20,77 @@ impl Coin {
             denom: denom.into(),
         }
     }
+
+    /// Formats the coin for end users using the display unit of the given metadata,
+    /// e.g. "1.5 ATOM" for 1500000uatom. The metadata's symbol is used as the unit
+    /// name if set, the display denom otherwise.
+    ///
+    /// If the metadata does not belong to this coin's denom or does not contain
+    /// the display unit, this falls back to the [`Display`](fmt::Display) implementation
+    /// ("1500000uatom").
+    pub fn display_with(&self, metadata: &DenomMetadata) -> String {
+        if self.denom != metadata.base {
+            return self.to_string();
+        }
+        match format_amount(self.amount, metadata) {
+            Ok(amount) => {
+                let unit = if metadata.symbol.is_empty() {
+                    &metadata.display
+                } else {
+                    &metadata.symbol
+                };
+                format!("{amount} {unit}")
+            }
+            Err(_) => self.to_string(),
+        }
+    }
+
+    /// Multiplies the amount by the ratio `numerator / denominator`, rounding down.
+    ///
+    /// The product is calculated with 256 bits before dividing, so this is precise for
+    /// all inputs. Fails if `denominator` is zero or the result does not fit into a [`Uint128`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use cosmwasm_std::coin;
+    ///
+    /// let fee = coin(1000, "ucosm").try_mul_ratio(3, 100).unwrap();
+    /// assert_eq!(fee, coin(30, "ucosm"));
+    ///
+    /// let share = coin(100, "ucosm").try_mul_ratio(1, 3).unwrap();
+    /// assert_eq!(share, coin(33, "ucosm")); // 33.33 rounds down
+    ///
+    /// assert!(coin(100, "ucosm").try_mul_ratio(1, 0).is_err());
+    /// ```
+    pub fn try_mul_ratio(self, numerator: u128, denominator: u128) -> StdResult<Coin> {
+        let amount = self.amount.checked_mul_floor((numerator, denominator))?;
+        Ok(Coin {
+            denom: self.denom,
+            amount,
+        })
+    }
+
+    /// Multiplies the amount by the given decimal, rounding down.
+    ///
+    /// Like [`Coin::try_mul_ratio`], the product is calculated with 256 bits,
+    /// so this only fails if the result does not fit into a [`Uint128`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use cosmwasm_std::{coin, Decimal};
+    ///
+    /// let reward = coin(1234, "ucosm").try_mul_decimal(Decimal::percent(5)).unwrap();
+    /// assert_eq!(reward, coin(61, "ucosm")); // 61.7 rounds down
+    /// ```
+    pub fn try_mul_decimal(self, factor: Decimal) -> StdResult<Coin> {
+        let amount = self.amount.checked_mul_floor(factor)?;
+        Ok(Coin {
+            denom: self.denom,
+            amount,
+        })
+    }
 }
 
 impl fmt::Debug for Coin {
@@ -114,6 +186,7 @@ pub fn has_coins(coins: &[Coin], required: &Coin) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{DenomUnit, StdError};
 
     #[test]
     fn coin_implements_display() {
@@ -195,6 +268,49 @@ mod tests {
         assert!(has_coins(&wallet, &coin(777, "ETH")));
     }
 
+    #[test]
+    fn display_with_works() {
+        let mut metadata = DenomMetadata {
+            base: "uatom".to_string(),
+            display: "atom".to_string(),
+            symbol: "ATOM".to_string(),
+            denom_units: vec![
+                DenomUnit {
+                    denom: "uatom".to_string(),
+                    exponent: 0,
+                    aliases: vec!["microatom".to_string()],
+                },
+                DenomUnit {
+                    denom: "atom".to_string(),
+                    exponent: 6,
+                    aliases: vec![],
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(coin(1_500_000, "uatom").display_with(&metadata), "1.5 ATOM");
+        assert_eq!(coin(0, "uatom").display_with(&metadata), "0 ATOM");
+        assert_eq!(coin(1, "uatom").display_with(&metadata), "0.000001 ATOM");
+
+        // other denom
+        assert_eq!(
+            coin(1_500_000, "ucosm").display_with(&metadata),
+            "1500000ucosm"
+        );
+
+        // no symbol
+        metadata.symbol = String::new();
+        assert_eq!(coin(1_500_000, "uatom").display_with(&metadata), "1.5 atom");
+
+        // missing display unit
+        metadata.denom_units.pop();
+        assert_eq!(
+            coin(1_500_000, "uatom").display_with(&metadata),
+            "1500000uatom"
+        );
+    }
+
     #[test]
     fn parse_coin() {
         let expected = Coin::new(123u128, "ucosm");
@@ -249,4 +365,91 @@ mod tests {
         let coin = Coin::new(123u128, "ucosm");
         assert_eq!(format!("{coin:?}"), r#"Coin { 123 "ucosm" }"#);
     }
+
+    #[test]
+    fn try_mul_ratio_works() {
+        assert_eq!(
+            coin(1000, "ucosm").try_mul_ratio(3, 100).unwrap(),
+            coin(30, "ucosm")
+        );
+        assert_eq!(
+            coin(100, "ucosm").try_mul_ratio(1, 3).unwrap(),
+            coin(33, "ucosm")
+        );
+        assert_eq!(
+            coin(100, "ucosm").try_mul_ratio(2, 3).unwrap(),
+            coin(66, "ucosm")
+        );
+        assert_eq!(
+            coin(100, "ucosm").try_mul_ratio(0, 3).unwrap(),
+            coin(0, "ucosm")
+        );
+        assert_eq!(
+            coin(0, "ucosm").try_mul_ratio(5, 3).unwrap(),
+            coin(0, "ucosm")
+        );
+
+        // the intermediate product does not overflow
+        assert_eq!(
+            coin(u128::MAX, "ucosm")
+                .try_mul_ratio(u128::MAX, u128::MAX)
+                .unwrap(),
+            coin(u128::MAX, "ucosm")
+        );
+        assert_eq!(
+            coin(u128::MAX, "ucosm")
+                .try_mul_ratio(u128::MAX - 1, u128::MAX)
+                .unwrap(),
+            coin(u128::MAX - 1, "ucosm")
+        );
+    }
+
+    #[test]
+    fn try_mul_ratio_errors() {
+        let err = coin(100, "ucosm").try_mul_ratio(1, 0).unwrap_err();
+        assert!(matches!(err, StdError::DivideByZero { .. }));
+
+        let err = coin(u128::MAX, "ucosm").try_mul_ratio(2, 1).unwrap_err();
+        assert!(matches!(err, StdError::ConversionOverflow { .. }));
+    }
+
+    #[test]
+    fn try_mul_decimal_works() {
+        assert_eq!(
+            coin(1234, "ucosm")
+                .try_mul_decimal(Decimal::percent(5))
+                .unwrap(),
+            coin(61, "ucosm")
+        );
+        assert_eq!(
+            coin(1234, "ucosm")
+                .try_mul_decimal(Decimal::percent(150))
+                .unwrap(),
+            coin(1851, "ucosm")
+        );
+        assert_eq!(
+            coin(1234, "ucosm").try_mul_decimal(Decimal::one()).unwrap(),
+            coin(1234, "ucosm")
+        );
+        assert_eq!(
+            coin(1234, "ucosm")
+                .try_mul_decimal(Decimal::zero())
+                .unwrap(),
+            coin(0, "ucosm")
+        );
+        assert_eq!(
+            coin(u128::MAX, "ucosm")
+                .try_mul_decimal(Decimal::permille(999))
+                .unwrap(),
+            coin(
+                u128::MAX / 1000 * 999 + (u128::MAX % 1000) * 999 / 1000,
+                "ucosm"
+            )
+        );
+
+        let err = coin(u128::MAX, "ucosm")
+            .try_mul_decimal(Decimal::percent(101))
+            .unwrap_err();
+        assert!(matches!(err, StdError::ConversionOverflow { .. }));
+    }
 }
@@ -3,7 +3,9 @@ use core::fmt;
 use core::str::FromStr;
 
 use crate::prelude::*;
-use crate::{Coin, CoinsError, OverflowError, OverflowOperation, StdError, StdResult, Uint128};
+use crate::{
+    Coin, CoinsError, Int128, OverflowError, OverflowOperation, StdError, StdResult, Uint128,
+};
 
 /// A collection of coins, similar to Cosmos SDK's `sdk.Coins` struct.
 ///
@@ -209,6 +211,127 @@ impl Coins {
         Ok(())
     }
 
+    /// Adds all coins of `other` to this collection.
+    /// Errors in case of overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cosmwasm_std::{coin, Coins};
+    /// let a: Coins = [coin(100, "uatom"), coin(50, "uosmo")].try_into().unwrap();
+    /// let b: Coins = [coin(20, "uatom"), coin(70, "uusd")].try_into().unwrap();
+    ///
+    /// let sum = a.checked_add(b).unwrap();
+    /// assert_eq!(sum.to_string(), "120uatom,50uosmo,70uusd");
+    /// ```
+    pub fn checked_add(mut self, other: Coins) -> Result<Coins, CoinsError> {
+        for (denom, coin) in other.0 {
+            match self.0.get_mut(&denom) {
+                Some(existing) => existing.amount = existing.amount.checked_add(coin.amount)?,
+                None => {
+                    self.0.insert(denom, coin);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Subtracts all coins of `other` from this collection.
+    /// Errors if `other` contains more of a denom than this collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cosmwasm_std::{coin, Coins, CoinsError, Uint128};
+    /// let balance: Coins = [coin(100, "uatom"), coin(50, "uosmo")].try_into().unwrap();
+    ///
+    /// let fees: Coins = coin(50, "uosmo").into();
+    /// let rest = balance.clone().checked_sub(&fees).unwrap();
+    /// assert_eq!(rest.to_string(), "100uatom");
+    ///
+    /// let fees: Coins = coin(60, "uosmo").into();
+    /// let err = balance.checked_sub(&fees).unwrap_err();
+    /// assert_eq!(
+    ///     err,
+    ///     CoinsError::InsufficientFunds {
+    ///         denom: "uosmo".to_string(),
+    ///         available: Uint128::new(50),
+    ///         required: Uint128::new(60),
+    ///     }
+    /// );
+    /// ```
+    pub fn checked_sub(mut self, other: &Coins) -> Result<Coins, CoinsError> {
+        for (denom, coin) in &other.0 {
+            let available = self.amount_of(denom);
+            let remaining =
+                available
+                    .checked_sub(coin.amount)
+                    .map_err(|_| CoinsError::InsufficientFunds {
+                        denom: denom.clone(),
+                        available,
+                        required: coin.amount,
+                    })?;
+            if remaining.is_zero() {
+                self.0.remove(denom);
+            } else if let Some(existing) = self.0.get_mut(denom) {
+                existing.amount = remaining;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Multiplies the amounts of all coins by `factor`.
+    /// Errors in case of overflow. Scaling by zero results in an empty collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cosmwasm_std::{coin, Coins, Uint128};
+    /// let coins: Coins = [coin(100, "uatom"), coin(50, "uosmo")].try_into().unwrap();
+    ///
+    /// let scaled = coins.scale(Uint128::new(3)).unwrap();
+    /// assert_eq!(scaled.to_string(), "300uatom,150uosmo");
+    /// ```
+    pub fn scale(mut self, factor: Uint128) -> Result<Coins, CoinsError> {
+        if factor.is_zero() {
+            return Ok(Coins::default());
+        }
+        for coin in self.0.values_mut() {
+            coin.amount = coin.amount.checked_mul(factor)?;
+        }
+        Ok(self)
+    }
+
+    /// Returns the signed difference `self - other` for every denom present in
+    /// either of the two collections.
+    ///
+    /// The delta is positive if `self` contains more of a denom than `other`, negative if
+    /// it contains less, and zero if both contain the same amount.
+    /// Errors if a delta does not fit into an [`Int128`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cosmwasm_std::{coin, Coins, Int128};
+    /// let current: Coins = [coin(100, "uatom"), coin(50, "uosmo")].try_into().unwrap();
+    /// let target: Coins = [coin(80, "uatom"), coin(70, "uusd")].try_into().unwrap();
+    ///
+    /// let diff = current.signed_diff(&target).unwrap();
+    /// assert_eq!(diff["uatom"], Int128::new(20));
+    /// assert_eq!(diff["uosmo"], Int128::new(50));
+    /// assert_eq!(diff["uusd"], Int128::new(-70));
+    /// ```
+    pub fn signed_diff(&self, other: &Coins) -> StdResult<BTreeMap<String, Int128>> {
+        self.0
+            .keys()
+            .chain(other.0.keys())
+            .map(|denom| {
+                let delta = signed_delta(self.amount_of(denom), other.amount_of(denom))?;
+                Ok((denom.clone(), delta))
+            })
+            .collect()
+    }
+
     /// Returns an iterator over the coins.
     ///
     /// # Examples
@@ -235,6 +358,19 @@ impl Coins {
     }
 }
 
+/// Calculates `minuend - subtrahend` as a signed integer
+fn signed_delta(minuend: Uint128, subtrahend: Uint128) -> Result<Int128, OverflowError> {
+    let (minuend, subtrahend) = (minuend.u128(), subtrahend.u128());
+    let delta = if minuend >= subtrahend {
+        i128::try_from(minuend - subtrahend).ok()
+    } else {
+        0i128.checked_sub_unsigned(subtrahend - minuend)
+    };
+    delta
+        .map(Int128::new)
+        .ok_or_else(|| OverflowError::new(OverflowOperation::Sub))
+}
+
 impl IntoIterator for Coins {
     type Item = Coin;
     type IntoIter = CoinsIntoIter;
@@ -503,6 +639,193 @@ mod tests {
         assert_eq!(iter.size_hint(), (3, Some(3)));
     }
 
+    #[test]
+    fn signed_diff_works() {
+        let a: Coins = [coin(100, "uatom"), coin(50, "uosmo"), coin(7, "ueq")]
+            .try_into()
+            .unwrap();
+        let b: Coins = [coin(80, "uatom"), coin(70, "uusd"), coin(7, "ueq")]
+            .try_into()
+            .unwrap();
+
+        let diff = a.signed_diff(&b).unwrap();
+        assert_eq!(
+            diff,
+            BTreeMap::from([
+                ("uatom".to_string(), Int128::new(20)),
+                ("ueq".to_string(), Int128::zero()),
+                ("uosmo".to_string(), Int128::new(50)),
+                ("uusd".to_string(), Int128::new(-70)),
+            ])
+        );
+
+        // the other way round negates all deltas
+        let reverse = b.signed_diff(&a).unwrap();
+        for (denom, delta) in diff {
+            assert_eq!(reverse[&denom], -delta);
+        }
+
+        // empty collections
+        assert_eq!(
+            Coins::default().signed_diff(&Coins::default()).unwrap(),
+            BTreeMap::new()
+        );
+        assert_eq!(
+            Coins::default().signed_diff(&a).unwrap(),
+            BTreeMap::from([
+                ("uatom".to_string(), Int128::new(-100)),
+                ("ueq".to_string(), Int128::new(-7)),
+                ("uosmo".to_string(), Int128::new(-50)),
+            ])
+        );
+    }
+
+    #[test]
+    fn signed_diff_guards_overflow() {
+        let max = i128::MAX as u128;
+        let large: Coins = coin(max + 1, "uatom").into();
+        let one: Coins = coin(1, "uatom").into();
+        let empty = Coins::default();
+
+        // large values with a small delta are fine
+        let almost_large: Coins = coin(max, "uatom").into();
+        assert_eq!(
+            large.signed_diff(&almost_large).unwrap()["uatom"],
+            Int128::one()
+        );
+
+        // the largest positive delta is i128::MAX
+        assert_eq!(large.signed_diff(&one).unwrap()["uatom"], Int128::MAX);
+        let err = large.signed_diff(&empty).unwrap_err();
+        assert!(matches!(err, StdError::Overflow { .. }));
+
+        // the largest negative delta is i128::MIN
+        assert_eq!(empty.signed_diff(&large).unwrap()["uatom"], Int128::MIN);
+        let larger: Coins = coin(max + 2, "uatom").into();
+        let err = empty.signed_diff(&larger).unwrap_err();
+        assert!(matches!(err, StdError::Overflow { .. }));
+        let err = empty
+            .signed_diff(&coin(u128::MAX, "uatom").into())
+            .unwrap_err();
+        assert!(matches!(err, StdError::Overflow { .. }));
+    }
+
+    #[test]
+    fn checked_add_works() {
+        let coins = mock_coins();
+
+        // adding empty coins
+        assert_eq!(coins.clone().checked_add(Coins::default()).unwrap(), coins);
+        assert_eq!(Coins::default().checked_add(coins.clone()).unwrap(), coins);
+
+        // existing and new denoms
+        let other: Coins = [coin(1, "uatom"), coin(5, "uosmo")].try_into().unwrap();
+        let sum = coins.checked_add(other).unwrap();
+        assert_eq!(sum.len(), 4);
+        assert_eq!(sum.amount_of("uatom").u128(), 12346);
+        assert_eq!(sum.amount_of("uosmo").u128(), 5);
+        assert_eq!(sum.amount_of("ibc/1234ABCD").u128(), 69420);
+
+        // overflow
+        let max: Coins = [coin(u128::MAX, "uatom"), coin(1, "uosmo")]
+            .try_into()
+            .unwrap();
+        let err = max.checked_add(coin(1, "uatom").into()).unwrap_err();
+        assert_eq!(
+            err,
+            CoinsError::Overflow(OverflowError::new(OverflowOperation::Add))
+        );
+    }
+
+    #[test]
+    fn checked_sub_works() {
+        let coins = mock_coins();
+
+        // subtracting empty coins
+        assert_eq!(coins.clone().checked_sub(&Coins::default()).unwrap(), coins);
+
+        // partial and full amounts
+        let other: Coins = [coin(345, "uatom"), coin(69420, "ibc/1234ABCD")]
+            .try_into()
+            .unwrap();
+        let rest = coins.clone().checked_sub(&other).unwrap();
+        assert_eq!(rest.len(), 2);
+        assert_eq!(rest.amount_of("uatom").u128(), 12000);
+        assert_eq!(rest.amount_of("ibc/1234ABCD").u128(), 0);
+        assert_eq!(rest.denoms(), ["factory/osmo1234abcd/subdenom", "uatom"]);
+
+        // subtracting everything
+        assert_eq!(coins.clone().checked_sub(&coins).unwrap(), Coins::default());
+
+        // underflow
+        let err = coins
+            .clone()
+            .checked_sub(&coin(12346, "uatom").into())
+            .unwrap_err();
+        assert_eq!(
+            err,
+            CoinsError::InsufficientFunds {
+                denom: "uatom".to_string(),
+                available: Uint128::new(12345),
+                required: Uint128::new(12346),
+            }
+        );
+
+        // missing denom
+        let err = coins.checked_sub(&coin(1, "uosmo").into()).unwrap_err();
+        assert_eq!(
+            err,
+            CoinsError::InsufficientFunds {
+                denom: "uosmo".to_string(),
+                available: Uint128::zero(),
+                required: Uint128::one(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Insufficient funds: 0uosmo is less than 1uosmo"
+        );
+    }
+
+    #[test]
+    fn scale_works() {
+        let coins = mock_coins();
+
+        assert_eq!(coins.clone().scale(Uint128::one()).unwrap(), coins);
+        assert_eq!(
+            coins.clone().scale(Uint128::zero()).unwrap(),
+            Coins::default()
+        );
+
+        let scaled = coins.scale(Uint128::new(2)).unwrap();
+        assert_eq!(scaled.len(), 3);
+        assert_eq!(scaled.amount_of("uatom").u128(), 24690);
+        assert_eq!(scaled.amount_of("ibc/1234ABCD").u128(), 138840);
+        assert_eq!(
+            scaled.amount_of("factory/osmo1234abcd/subdenom").u128(),
+            177776
+        );
+
+        // overflow
+        let large: Coins = [coin(u128::MAX / 2 + 1, "uatom"), coin(1, "uosmo")]
+            .try_into()
+            .unwrap();
+        let err = large.scale(Uint128::new(2)).unwrap_err();
+        assert_eq!(
+            err,
+            CoinsError::Overflow(OverflowError::new(OverflowOperation::Mul))
+        );
+    }
+
+    #[test]
+    fn coins_error_converts_to_std_error() {
+        let err: StdError = CoinsError::DuplicateDenom.into();
+        assert!(err.to_string().contains("Creating Coins: Duplicate denom"));
+
+        let err: StdError = CoinsError::Overflow(OverflowError::new(OverflowOperation::Add)).into();
+        assert!(matches!(err, StdError::Overflow { .. }));
+    }
+
     #[test]
     fn can_iterate_owned() {
         let coins = mock_coins();
@@ -0,0 +1,49 @@
+use alloc::string::{String, ToString};
+
+use crate::{OverflowError, StdError, Uint128};
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum CoinsError {
+    #[error("Duplicate denom")]
+    DuplicateDenom,
+    #[error("Insufficient funds: {available}{denom} is less than {required}{denom}")]
+    InsufficientFunds {
+        denom: String,
+        available: Uint128,
+        required: Uint128,
+    },
+    #[error("Overflow: {0}")]
+    Overflow(#[from] OverflowError),
+}
+
+impl From<CoinsError> for StdError {
+    fn from(value: CoinsError) -> Self {
+        match value {
+            CoinsError::DuplicateDenom => Self::generic_err(format!("Creating Coins: {value}")),
+            CoinsError::InsufficientFunds { .. } => Self::generic_err(value.to_string()),
+            CoinsError::Overflow(source) => Self::overflow(source),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum CoinFromStrError {
+    #[error("Missing denominator")]
+    MissingDenom,
+    #[error("Missing amount or non-digit characters in amount")]
+    MissingAmount,
+    #[error("Invalid amount: {_0}")]
+    InvalidAmount(core::num::ParseIntError),
+}
+
+impl From<core::num::ParseIntError> for CoinFromStrError {
+    fn from(value: core::num::ParseIntError) -> Self {
+        Self::InvalidAmount(value)
+    }
+}
+
+impl From<CoinFromStrError> for StdError {
+    fn from(value: CoinFromStrError) -> Self {
+        Self::generic_err(format!("Parsing Coin: {value}"))
+    }
+}
@@ -1,7 +1,15 @@
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::{Binary, Timestamp};
+use crate::prelude::*;
+use crate::{
+    from_json, from_msgpack, to_json_binary, to_msgpack_binary, Binary, StdError, StdResult,
+    Timestamp,
+};
+
+/// The encodings supported by [`EurekaPayload::encode_from`] and [`EurekaPayload::decode_as`]
+const ENCODING_JSON: &str = "json";
+const ENCODING_MSGPACK: &str = "msgpack";
 
 /// Payload value should be encoded in a format defined by the channel version,
 /// and the module on the other side should know how to parse this.
@@ -19,6 +27,55 @@ pub struct EurekaPayload {
     pub value: Binary,
 }
 
+impl EurekaPayload {
+    /// Creates a payload by encoding `value` with the given encoding,
+    /// which must be `"json"` or `"msgpack"`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_std::EurekaPayload;
+    /// let payload = EurekaPayload::encode_from("port", "v1", "json", &["a", "b"]).unwrap();
+    /// assert_eq!(payload.encoding, "json");
+    /// assert_eq!(payload.value.as_slice(), br#"["a","b"]"#);
+    ///
+    /// let decoded: Vec<String> = payload.decode_as().unwrap();
+    /// assert_eq!(decoded, ["a", "b"]);
+    /// ```
+    pub fn encode_from<T: Serialize>(
+        destination_port: impl Into<String>,
+        version: impl Into<String>,
+        encoding: &str,
+        value: &T,
+    ) -> StdResult<Self> {
+        let value = match encoding {
+            ENCODING_JSON => to_json_binary(value)?,
+            ENCODING_MSGPACK => to_msgpack_binary(value)?,
+            _ => return Err(unsupported_encoding(encoding)),
+        };
+        Ok(EurekaPayload {
+            destination_port: destination_port.into(),
+            version: version.into(),
+            encoding: encoding.to_string(),
+            value,
+        })
+    }
+
+    /// Decodes the value according to the payload's encoding,
+    /// which must be `"json"` or `"msgpack"`.
+    pub fn decode_as<T: DeserializeOwned>(&self) -> StdResult<T> {
+        match self.encoding.as_str() {
+            ENCODING_JSON => from_json(&self.value),
+            ENCODING_MSGPACK => from_msgpack(&self.value),
+            encoding => Err(unsupported_encoding(encoding)),
+        }
+    }
+}
+
+fn unsupported_encoding(encoding: &str) -> StdError {
+    StdError::generic_err(format!("Unsupported payload encoding: {encoding}"))
+}
+
 /// These are messages in the IBC lifecycle using the new Eureka approach. Only usable by IBC-enabled contracts
 #[non_exhaustive]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -35,9 +92,10 @@ pub enum EurekaMsg {
 
 #[cfg(test)]
 mod tests {
+    use serde::Deserialize;
     use serde_json::to_string;
 
-    use crate::EurekaPayload;
+    use super::*;
 
     #[test]
     fn eureka_payload_serialize() {
@@ -50,4 +108,55 @@ mod tests {
         let expected = r#"{"destination_port":"receiving-contract-port","version":"v1","encoding":"json","value":"Zm9v"}"#;
         assert_eq!(to_string(&packet).unwrap(), expected);
     }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Transfer {
+        denom: String,
+        amount: u64,
+    }
+
+    #[test]
+    fn eureka_payload_encode_from_and_decode_as_work() {
+        let transfer = Transfer {
+            denom: "uatom".to_string(),
+            amount: 123,
+        };
+
+        let payload = EurekaPayload::encode_from("port", "v1", "json", &transfer).unwrap();
+        assert_eq!(payload.destination_port, "port");
+        assert_eq!(payload.version, "v1");
+        assert_eq!(payload.encoding, "json");
+        assert_eq!(
+            payload.value.as_slice(),
+            br#"{"denom":"uatom","amount":123}"#
+        );
+        assert_eq!(payload.decode_as::<Transfer>().unwrap(), transfer);
+
+        let payload = EurekaPayload::encode_from("port", "v1", "msgpack", &transfer).unwrap();
+        assert_eq!(payload.encoding, "msgpack");
+        assert_eq!(payload.value, to_msgpack_binary(&transfer).unwrap());
+        assert_eq!(payload.decode_as::<Transfer>().unwrap(), transfer);
+
+        // wrong type
+        payload.decode_as::<String>().unwrap_err();
+    }
+
+    #[test]
+    fn eureka_payload_rejects_unsupported_encoding() {
+        let err = EurekaPayload::encode_from("port", "v1", "protobuf", &1u8).unwrap_err();
+        assert!(
+            matches!(err, StdError::GenericErr { msg, .. } if msg == "Unsupported payload encoding: protobuf")
+        );
+
+        let payload = EurekaPayload {
+            destination_port: "port".to_string(),
+            version: "v1".to_string(),
+            encoding: "JSON".to_string(),
+            value: b"1".into(),
+        };
+        let err = payload.decode_as::<u8>().unwrap_err();
+        assert!(
+            matches!(err, StdError::GenericErr { msg, .. } if msg == "Unsupported payload encoding: JSON")
+        );
+    }
 }
@@ -76,6 +76,10 @@ extern "C" fn requires_cosmwasm_2_1() {}
 #[no_mangle]
 extern "C" fn requires_cosmwasm_2_2() {}
 
+#[cfg(feature = "cosmwasm_2_3")]
+#[no_mangle]
+extern "C" fn requires_cosmwasm_2_3() {}
+
 /// interface_version_* exports mark which Wasm VM interface level this contract is compiled for.
 /// They can be checked by cosmwasm_vm.
 /// Update this whenever the Wasm VM interface breaks.
@@ -1,10 +1,13 @@
 use alloc::{string::String, vec::Vec};
+use core::borrow::Borrow;
 use core::fmt;
-use core::ops::Deref;
+use core::ops::{Deref, RangeBounds};
 
 use serde::{de, ser, Deserialize, Deserializer, Serialize};
+use subtle::ConstantTimeEq;
 
 use crate::{
+    binary::checked_range,
     encoding::{from_hex, to_hex},
     Binary, StdError, StdResult,
 };
@@ -14,6 +17,11 @@ use crate::{
 ///
 /// This is similar to `cosmwasm_std::Binary` but uses hex.
 /// See also <https://github.com/CosmWasm/cosmwasm/blob/main/docs/MESSAGE_TYPES.md>.
+///
+/// Just like for `Binary`, `Eq`, `Ord` and `Hash` behave exactly like the ones of the
+/// underlying bytes (`[u8]`), i.e. values are ordered lexicographically byte by byte.
+/// This is guaranteed to stay stable. Together with the `Borrow<[u8]>` implementation,
+/// this allows looking up `HexBinary` keys in maps and sets by `&[u8]`.
 #[derive(Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, schemars::JsonSchema)]
 pub struct HexBinary(#[schemars(with = "String")] Vec<u8>);
 
@@ -30,8 +38,29 @@ impl HexBinary {
         self.0.as_slice()
     }
 
+    /// Compares the content with the given bytes in constant time.
+    ///
+    /// Use this instead of `==` when comparing secret material like hash preimages or
+    /// MACs against user input, such that the comparison does not leak through the
+    /// execution time how many leading bytes match. Only the length is not kept secret,
+    /// i.e. values of different lengths are rejected immediately.
+    ///
+    /// ```
+    /// # use cosmwasm_std::HexBinary;
+    /// let secret = HexBinary::from(b"secret preimage");
+    /// assert!(secret.ct_eq(b"secret preimage"));
+    /// assert!(!secret.ct_eq(b"secret preimagf"));
+    /// assert!(!secret.ct_eq(b"secret"));
+    /// ```
+    pub fn ct_eq(&self, other: &[u8]) -> bool {
+        self.0.as_slice().ct_eq(other).into()
+    }
+
     /// Copies content into fixed-sized array.
     ///
+    /// Returns an [`StdError::InvalidDataSize`] error containing the expected and actual
+    /// length if the length of the data does not match the array length.
+    ///
     /// # Examples
     ///
     /// Copy to array of explicit length
@@ -60,6 +89,33 @@ impl HexBinary {
         out.copy_from_slice(&self.0);
         Ok(out)
     }
+
+    /// Returns `true` if the data starts with the given prefix.
+    pub fn starts_with(&self, prefix: &[u8]) -> bool {
+        self.0.starts_with(prefix)
+    }
+
+    /// Returns the data after the given prefix or `None` if the data does not
+    /// start with the prefix.
+    pub fn strip_prefix(&self, prefix: &[u8]) -> Option<&[u8]> {
+        self.0.strip_prefix(prefix)
+    }
+
+    /// Creates a new `HexBinary` containing this data followed by `other`.
+    pub fn concat(&self, other: &[u8]) -> Self {
+        let mut out = Vec::with_capacity(self.0.len() + other.len());
+        out.extend_from_slice(&self.0);
+        out.extend_from_slice(other);
+        Self(out)
+    }
+
+    /// Copies the given range of the data into a new `HexBinary`.
+    ///
+    /// Unlike indexing a slice, this does not panic but returns an error if the range
+    /// is out of bounds or its start is greater than its end.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> StdResult<Self> {
+        checked_range(&self.0, range).map(Self::from)
+    }
 }
 
 impl fmt::Display for HexBinary {
@@ -100,6 +156,14 @@ impl AsRef<[u8]> for HexBinary {
     }
 }
 
+/// This must only be implemented as long as `Eq`, `Ord` and `Hash` of `HexBinary`
+/// are equivalent to the ones of `[u8]`.
+impl Borrow<[u8]> for HexBinary {
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
 // Slice
 impl From<&[u8]> for HexBinary {
     fn from(binary: &[u8]) -> Self {
@@ -275,6 +339,8 @@ mod tests {
     use super::*;
 
     use crate::{assert_hash_works, StdError};
+    use proptest::{prop_assert_eq, property_test};
+    use std::collections::{BTreeMap, HashMap};
 
     #[test]
     fn from_hex_works() {
@@ -371,6 +437,8 @@ mod tests {
             }
             err => panic!("Unexpected error: {err:?}"),
         }
+        let error = binary.to_array::<32>().unwrap_err();
+        assert_eq!(error.to_string(), "Invalid data size: expected=32 actual=3");
 
         // long array (32 bytes)
         let binary =
@@ -634,6 +702,111 @@ mod tests {
         assert_hash_works!(a, b);
     }
 
+    #[test]
+    fn hex_binary_starts_with_and_strip_prefix_work() {
+        let data = HexBinary::from(b"key:value");
+        assert!(data.starts_with(b""));
+        assert!(data.starts_with(b"key:"));
+        assert!(data.starts_with(b"key:value"));
+        assert!(!data.starts_with(b"value"));
+        assert!(!data.starts_with(b"key:value:"));
+
+        assert_eq!(data.strip_prefix(b""), Some(b"key:value".as_slice()));
+        assert_eq!(data.strip_prefix(b"key:"), Some(b"value".as_slice()));
+        assert_eq!(data.strip_prefix(b"key:value"), Some(b"".as_slice()));
+        assert_eq!(data.strip_prefix(b"value"), None);
+    }
+
+    #[test]
+    fn hex_binary_concat_works() {
+        let data = HexBinary::from(b"key:");
+        assert_eq!(data.concat(b"value"), HexBinary::from(b"key:value"));
+        assert_eq!(data.concat(b""), data);
+        assert_eq!(
+            HexBinary::default().concat(b"value"),
+            HexBinary::from(b"value")
+        );
+        // original is unchanged
+        assert_eq!(data, HexBinary::from(b"key:"));
+    }
+
+    #[test]
+    fn hex_binary_slice_works() {
+        let data = HexBinary::from(&[1, 2, 3, 4, 5]);
+        assert_eq!(data.slice(..).unwrap(), data);
+        assert_eq!(data.slice(1..3).unwrap(), [2, 3]);
+        assert_eq!(data.slice(1..=3).unwrap(), [2, 3, 4]);
+        assert_eq!(data.slice(..2).unwrap(), [1, 2]);
+        assert_eq!(data.slice(3..).unwrap(), [4, 5]);
+        assert_eq!(data.slice(5..).unwrap(), HexBinary::default());
+        assert_eq!(data.slice(2..2).unwrap(), HexBinary::default());
+
+        // out of bounds
+        let err = data.slice(3..6).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Range 3..6 is invalid for data of length 5"
+        );
+        data.slice(6..).unwrap_err();
+        data.slice(..=5).unwrap_err();
+        data.slice(..=usize::MAX).unwrap_err();
+
+        // start greater than end
+        #[allow(clippy::reversed_empty_ranges)]
+        let err = data.slice(3..2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Range 3..2 is invalid for data of length 5"
+        );
+    }
+
+    #[test]
+    fn hex_binary_implements_borrow_for_lookups_by_slice() {
+        let a = HexBinary::from(b"alpha");
+        let b = HexBinary::from(b"beta");
+
+        let mut tree = BTreeMap::new();
+        tree.insert(a.clone(), 1);
+        tree.insert(b.clone(), 2);
+        assert_eq!(tree.get(b"alpha".as_slice()), Some(&1));
+        assert_eq!(tree.get(b"beta".as_slice()), Some(&2));
+        assert_eq!(tree.get(b"gamma".as_slice()), None);
+
+        let mut map = HashMap::new();
+        map.insert(a, 1);
+        map.insert(b, 2);
+        assert_eq!(map.get(b"alpha".as_slice()), Some(&1));
+        assert_eq!(map.get(b"beta".as_slice()), Some(&2));
+        assert_eq!(map.get(b"gamma".as_slice()), None);
+    }
+
+    #[test]
+    fn hex_binary_ord_is_lexicographic() {
+        let empty = HexBinary::default();
+        let a = HexBinary::from(&[0x00]);
+        let b = HexBinary::from(&[0x00, 0x00]);
+        let c = HexBinary::from(&[0x01]);
+        let d = HexBinary::from(&[0xff, 0x00]);
+        // shorter prefix comes first, then byte by byte
+        assert!(empty < a);
+        assert!(a < b);
+        assert!(b < c);
+        assert!(c < d);
+
+        let mut sorted = vec![d.clone(), b.clone(), empty.clone(), c.clone(), a.clone()];
+        sorted.sort();
+        assert_eq!(sorted, vec![empty, a, b, c, d]);
+    }
+
+    #[property_test]
+    fn hex_binary_ord_matches_vec_ord(a: Vec<u8>, b: Vec<u8>) {
+        let binary_a = HexBinary::from(a.clone());
+        let binary_b = HexBinary::from(b.clone());
+        prop_assert_eq!(binary_a.cmp(&binary_b), a.cmp(&b));
+        prop_assert_eq!(binary_a.partial_cmp(&binary_b), a.partial_cmp(&b));
+        prop_assert_eq!(binary_a == binary_b, a == b);
+    }
+
     #[test]
     fn hex_binary_implements_partial_eq_with_vector() {
         let a = HexBinary(vec![5u8; 3]);
@@ -645,6 +818,19 @@ mod tests {
         assert_ne!(c, a);
     }
 
+    #[test]
+    fn hex_binary_ct_eq_works() {
+        let a = HexBinary::from(b"\xAA\xBB\xCC");
+        assert!(a.ct_eq(b"\xAA\xBB\xCC"));
+        assert!(a.ct_eq(&HexBinary::from(b"\xAA\xBB\xCC")));
+        assert!(!a.ct_eq(b"\xAA\xBB\xCD"));
+        assert!(!a.ct_eq(b"\x00\xBB\xCC"));
+        assert!(!a.ct_eq(b"\xAA\xBB"));
+        assert!(!a.ct_eq(b"\xAA\xBB\xCC\xDD"));
+        assert!(!a.ct_eq(b""));
+        assert!(HexBinary::default().ct_eq(b""));
+    }
+
     #[test]
     fn hex_binary_implements_partial_eq_with_slice_and_array() {
         let a = HexBinary(vec![0xAA, 0xBB]);
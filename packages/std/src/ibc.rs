@@ -3,14 +3,14 @@
 
 use core::cmp::{Ord, Ordering, PartialOrd};
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::coin::Coin;
 use crate::prelude::*;
 use crate::results::{Attribute, CosmosMsg, Empty, Event, SubMsg};
-use crate::StdResult;
-use crate::{to_json_binary, Binary};
-use crate::{Addr, Timestamp};
+use crate::{from_json, to_json_binary, Binary};
+use crate::{Addr, Env, Timestamp};
+use crate::{StdAck, StdResult};
 
 mod callbacks;
 mod transfer_msg_builder;
@@ -200,6 +200,23 @@ impl IbcTimeout {
     pub fn timestamp(&self) -> Option<Timestamp> {
         self.timestamp
     }
+
+    /// Returns true if the timeout has passed at the block described by `env`,
+    /// i.e. if the block height or the block time reached the respective timeout.
+    /// If both are set, reaching either of them is enough.
+    ///
+    /// Timeouts are measured on the chain receiving the packet, so this is only meaningful
+    /// with the `env` of that chain, e.g. in `ibc_packet_receive`. The block timeout's
+    /// revision is ignored because it is not part of the [`Env`].
+    pub fn is_expired(&self, env: &Env) -> bool {
+        let block_expired = self
+            .block
+            .is_some_and(|block| env.block.height >= block.height);
+        let timestamp_expired = self
+            .timestamp
+            .is_some_and(|timestamp| env.block.time >= timestamp);
+        block_expired || timestamp_expired
+    }
 }
 
 impl From<Timestamp> for IbcTimeout {
@@ -327,6 +344,58 @@ impl IbcPacket {
             timeout,
         }
     }
+
+    /// Deserializes the JSON encoded packet data into `T`.
+    ///
+    /// ```
+    /// # use cosmwasm_std::{IbcEndpoint, IbcPacket, IbcTimeout, Timestamp};
+    /// # use serde::Deserialize;
+    /// # let endpoint = IbcEndpoint { port_id: "port".to_string(), channel_id: "channel-0".to_string() };
+    /// # let timeout = IbcTimeout::with_timestamp(Timestamp::from_seconds(1));
+    /// #[derive(Deserialize)]
+    /// struct Ping {
+    ///     count: u32,
+    /// }
+    ///
+    /// let packet = IbcPacket::new(br#"{"count":3}"#, endpoint.clone(), endpoint, 1, timeout);
+    /// let ping: Ping = packet.data_json()?;
+    /// assert_eq!(ping.count, 3);
+    /// # Ok::<(), cosmwasm_std::StdError>(())
+    /// ```
+    pub fn data_json<T: DeserializeOwned>(&self) -> StdResult<T> {
+        from_json(&self.data)
+    }
+
+    /// Like [`IbcPacket::data_json`] but turns a deserialization error into an error
+    /// acknowledgement. This allows `ibc_packet_receive` to reject malformed packets
+    /// with an error ack instead of failing the transaction.
+    ///
+    /// ```
+    /// # use cosmwasm_std::{
+    /// #     DepsMut, Env, IbcPacketReceiveMsg, IbcReceiveResponse, Never, StdAck,
+    /// # };
+    /// # use serde::Deserialize;
+    /// #[derive(Deserialize)]
+    /// struct Ping {
+    ///     count: u32,
+    /// }
+    ///
+    /// pub fn ibc_packet_receive(
+    ///     _deps: DepsMut,
+    ///     _env: Env,
+    ///     msg: IbcPacketReceiveMsg,
+    /// ) -> Result<IbcReceiveResponse, Never> {
+    ///     let ping: Ping = match msg.packet.data_json_or_error_ack() {
+    ///         Ok(ping) => ping,
+    ///         Err(ack) => return Ok(IbcReceiveResponse::new(ack)),
+    ///     };
+    ///     Ok(IbcReceiveResponse::new(StdAck::success(ping.count.to_be_bytes())))
+    /// }
+    /// ```
+    pub fn data_json_or_error_ack<T: DeserializeOwned>(&self) -> Result<T, StdAck> {
+        self.data_json()
+            .map_err(|err| StdAck::error(format!("Invalid packet data: {err}")))
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -347,6 +416,69 @@ impl IbcAcknowledgement {
             data: to_json_binary(data)?,
         })
     }
+
+    /// Parses the acknowledgement data as a [`StdAck`]. The JSON format is tried first,
+    /// then the protobuf `Acknowledgement` envelope of ibc-go (see [`StdAck::from_proto_bytes`]).
+    ///
+    /// ```
+    /// # use cosmwasm_std::{IbcAcknowledgement, StdAck};
+    /// let json = IbcAcknowledgement::new(StdAck::error("kaputt"));
+    /// assert_eq!(json.std_ack().unwrap(), StdAck::error("kaputt"));
+    ///
+    /// let proto = IbcAcknowledgement::new(StdAck::success(b"\x01").to_proto_bytes());
+    /// assert_eq!(proto.std_ack().unwrap(), StdAck::success(b"\x01"));
+    /// ```
+    pub fn std_ack(&self) -> StdResult<StdAck> {
+        from_json(&self.data).or_else(|_| StdAck::from_proto_bytes(&self.data))
+    }
+
+    /// Returns the result data if this is a success acknowledgement in the standard format
+    /// (see [`IbcAcknowledgement::std_ack`]) and `None` otherwise.
+    ///
+    /// ```
+    /// # use cosmwasm_std::{Binary, IbcAcknowledgement};
+    /// let ack = IbcAcknowledgement::new(br#"{"result":"AQ=="}"#);
+    /// assert_eq!(ack.as_success(), Some(Binary::from(b"\x01")));
+    /// assert_eq!(ack.as_error(), None);
+    /// ```
+    pub fn as_success(&self) -> Option<Binary> {
+        match self.std_ack() {
+            Ok(StdAck::Success(data)) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns the error message if this is an error acknowledgement in the standard format
+    /// (see [`IbcAcknowledgement::std_ack`]) and `None` otherwise.
+    ///
+    /// ```
+    /// # use cosmwasm_std::IbcAcknowledgement;
+    /// let ack = IbcAcknowledgement::new(br#"{"error":"kaputt"}"#);
+    /// assert_eq!(ack.as_error(), Some("kaputt".to_string()));
+    /// assert_eq!(ack.as_success(), None);
+    /// ```
+    pub fn as_error(&self) -> Option<String> {
+        match self.std_ack() {
+            Ok(StdAck::Error(err)) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this is a success acknowledgement in the standard format.
+    ///
+    /// Note that both this and [`IbcAcknowledgement::is_error`] return `false`
+    /// for acknowledgements in a non-standard format.
+    pub fn is_success(&self) -> bool {
+        self.as_success().is_some()
+    }
+
+    /// Returns `true` if this is an error acknowledgement in the standard format.
+    ///
+    /// Note that both this and [`IbcAcknowledgement::is_success`] return `false`
+    /// for acknowledgements in a non-standard format.
+    pub fn is_error(&self) -> bool {
+        self.as_error().is_some()
+    }
 }
 
 /// The message that is passed into `ibc_channel_open`
@@ -913,6 +1045,137 @@ mod tests {
         assert_eq!(encoded.as_str(), expected);
     }
 
+    #[test]
+    #[cfg(feature = "cosmwasm_2_2")]
+    fn serialize_pay_packet_fee_msgs() {
+        use crate::coins;
+
+        let fee = IbcFee {
+            receive_fee: coins(100, "token"),
+            ack_fee: coins(201, "token"),
+            timeout_fee: vec![],
+        };
+
+        let msg = IbcMsg::PayPacketFee {
+            port_id: "transfer".to_string(),
+            channel_id: "channel-0".to_string(),
+            fee: fee.clone(),
+            relayers: vec![],
+        };
+        let encoded = to_string(&msg).unwrap();
+        let expected = r#"{"pay_packet_fee":{"port_id":"transfer","channel_id":"channel-0","fee":{"receive_fee":[{"denom":"token","amount":"100"}],"ack_fee":[{"denom":"token","amount":"201"}],"timeout_fee":[]},"relayers":[]}}"#;
+        assert_eq!(encoded.as_str(), expected);
+        assert_eq!(from_json::<IbcMsg>(expected).unwrap(), msg);
+
+        let msg = IbcMsg::PayPacketFeeAsync {
+            port_id: "transfer".to_string(),
+            channel_id: "channel-0".to_string(),
+            sequence: 42,
+            fee,
+            relayers: vec![],
+        };
+        let encoded = to_string(&msg).unwrap();
+        let expected = r#"{"pay_packet_fee_async":{"port_id":"transfer","channel_id":"channel-0","sequence":42,"fee":{"receive_fee":[{"denom":"token","amount":"100"}],"ack_fee":[{"denom":"token","amount":"201"}],"timeout_fee":[]},"relayers":[]}}"#;
+        assert_eq!(encoded.as_str(), expected);
+        assert_eq!(from_json::<IbcMsg>(expected).unwrap(), msg);
+    }
+
+    fn mock_packet(data: &[u8]) -> IbcPacket {
+        let endpoint = IbcEndpoint {
+            port_id: "port".to_string(),
+            channel_id: "channel-0".to_string(),
+        };
+        IbcPacket::new(
+            data,
+            endpoint.clone(),
+            endpoint,
+            42,
+            IbcTimeout::with_timestamp(Timestamp::from_seconds(1)),
+        )
+    }
+
+    #[test]
+    fn ibc_packet_data_json_works() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Ping {
+            count: u32,
+        }
+
+        let packet = mock_packet(br#"{"count":3}"#);
+        assert_eq!(packet.data_json::<Ping>().unwrap(), Ping { count: 3 });
+        assert_eq!(
+            packet.data_json_or_error_ack::<Ping>().unwrap(),
+            Ping { count: 3 }
+        );
+
+        let packet = mock_packet(br#"{"count":"three"}"#);
+        packet.data_json::<Ping>().unwrap_err();
+        let ack = packet.data_json_or_error_ack::<Ping>().unwrap_err();
+        assert!(ack.is_error());
+        let msg = ack.unwrap_err();
+        assert!(msg.starts_with("Invalid packet data: "), "{msg}");
+
+        let ack = mock_packet(b"")
+            .data_json_or_error_ack::<Ping>()
+            .unwrap_err();
+        assert!(ack.is_error());
+    }
+
+    #[test]
+    fn ibc_acknowledgement_std_ack_works() {
+        let ack = IbcAcknowledgement::new(br#"{"result":"AQ=="}"#);
+        assert_eq!(ack.std_ack().unwrap(), StdAck::success(b"\x01"));
+        let ack = IbcAcknowledgement::new(br#"{"error":"kaputt"}"#);
+        assert_eq!(ack.std_ack().unwrap(), StdAck::error("kaputt"));
+
+        let ack = IbcAcknowledgement::new(b"\xaa\x01\x01\x01");
+        assert_eq!(ack.std_ack().unwrap(), StdAck::success(b"\x01"));
+        let ack = IbcAcknowledgement::new(b"\xb2\x01\x06kaputt");
+        assert_eq!(ack.std_ack().unwrap(), StdAck::error("kaputt"));
+
+        // neither JSON nor protobuf
+        IbcAcknowledgement::new(b"\x01").std_ack().unwrap_err();
+        IbcAcknowledgement::new(br#"{"foo":"bar"}"#)
+            .std_ack()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn ibc_acknowledgement_as_success_and_as_error_work() {
+        // ICS-20 success (FungibleTokenPacketSuccess)
+        let ack = IbcAcknowledgement::new(br#"{"result":"AQ=="}"#);
+        assert_eq!(ack.as_success(), Some(Binary::from(b"\x01")));
+        assert_eq!(ack.as_error(), None);
+        assert!(ack.is_success());
+        assert!(!ack.is_error());
+
+        // ICS-20 error as emitted by ibc-go
+        let ack = IbcAcknowledgement::new(
+            br#"{"error":"ABCI code: 1: error handling packet: see events for details"}"#,
+        );
+        assert_eq!(ack.as_success(), None);
+        assert_eq!(
+            ack.as_error().unwrap(),
+            "ABCI code: 1: error handling packet: see events for details"
+        );
+        assert!(!ack.is_success());
+        assert!(ack.is_error());
+
+        // protobuf envelope
+        let ack = IbcAcknowledgement::new(b"\xb2\x01\x06kaputt");
+        assert_eq!(ack.as_error().unwrap(), "kaputt");
+        assert!(ack.is_error());
+
+        // neither success nor error
+        for data in [&b"\x01"[..], br#"{"foo":"bar"}"#, b""] {
+            let ack = IbcAcknowledgement::new(data);
+            assert_eq!(ack.as_success(), None);
+            assert_eq!(ack.as_error(), None);
+            assert!(!ack.is_success());
+            assert!(!ack.is_error());
+        }
+    }
+
     #[test]
     fn ibc_timeout_serialize() {
         let timestamp = IbcTimeout::with_timestamp(Timestamp::from_nanos(684816844));
@@ -937,6 +1200,37 @@ mod tests {
         assert_eq!(to_string(&both).unwrap(), expected);
     }
 
+    #[test]
+    fn ibc_timeout_is_expired_works() {
+        use crate::testing::mock_env;
+
+        let mut env = mock_env();
+        env.block.height = 100;
+        env.block.time = Timestamp::from_seconds(1000);
+        let block = |height| IbcTimeoutBlock {
+            revision: 1,
+            height,
+        };
+        let just_before = Timestamp::from_nanos(999_999_999_999);
+        let just_after = Timestamp::from_nanos(1_000_000_000_001);
+
+        // block only
+        assert!(!IbcTimeout::with_block(block(101)).is_expired(&env));
+        assert!(IbcTimeout::with_block(block(100)).is_expired(&env));
+        assert!(IbcTimeout::with_block(block(99)).is_expired(&env));
+
+        // timestamp only
+        assert!(!IbcTimeout::with_timestamp(just_after).is_expired(&env));
+        assert!(IbcTimeout::with_timestamp(env.block.time).is_expired(&env));
+        assert!(IbcTimeout::with_timestamp(just_before).is_expired(&env));
+
+        // both set: either one expiring is enough
+        assert!(!IbcTimeout::with_both(block(101), just_after).is_expired(&env));
+        assert!(IbcTimeout::with_both(block(100), just_after).is_expired(&env));
+        assert!(IbcTimeout::with_both(block(101), env.block.time).is_expired(&env));
+        assert!(IbcTimeout::with_both(block(99), just_before).is_expired(&env));
+    }
+
     #[test]
     #[allow(clippy::eq_op)]
     fn ibc_timeout_block_ord() {
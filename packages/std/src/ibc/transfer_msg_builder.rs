@@ -1,5 +1,10 @@
+use alloc::collections::BTreeMap;
+use serde::de::IgnoredAny;
+
+use crate::prelude::*;
 use crate::{
-    to_json_string, Coin, IbcCallbackRequest, IbcDstCallback, IbcMsg, IbcSrcCallback, IbcTimeout,
+    from_json, to_json_string, Api, Coin, Env, IbcCallbackRequest, IbcDstCallback, IbcMsg,
+    IbcSrcCallback, IbcTimeout, StdError, StdResult,
 };
 
 // these are the different memo types and at the same time the states
@@ -23,9 +28,21 @@ pub struct WithCallbacks {
     src_callback: IbcSrcCallback,
     dst_callback: IbcDstCallback,
 }
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WithMemoAndCallbacks {
+    /// The user memo with the callback entries merged into it
+    memo: String,
+    callbacks: IbcCallbackRequest,
+}
 
 pub trait MemoSource {
     fn into_memo(self) -> Option<String>;
+
+    /// The IBC callbacks requested in the memo, if any.
+    /// This is used by [`TransferMsgBuilder::validate`].
+    fn callbacks(&self) -> Option<IbcCallbackRequest> {
+        None
+    }
 }
 
 impl MemoSource for EmptyMemo {
@@ -44,12 +61,20 @@ impl MemoSource for WithSrcCallback {
     fn into_memo(self) -> Option<String> {
         Some(to_json_string(&IbcCallbackRequest::source(self.src_callback)).unwrap())
     }
+
+    fn callbacks(&self) -> Option<IbcCallbackRequest> {
+        Some(IbcCallbackRequest::source(self.src_callback.clone()))
+    }
 }
 
 impl MemoSource for WithDstCallback {
     fn into_memo(self) -> Option<String> {
         Some(to_json_string(&IbcCallbackRequest::destination(self.dst_callback)).unwrap())
     }
+
+    fn callbacks(&self) -> Option<IbcCallbackRequest> {
+        Some(IbcCallbackRequest::destination(self.dst_callback.clone()))
+    }
 }
 
 impl MemoSource for WithCallbacks {
@@ -62,9 +87,107 @@ impl MemoSource for WithCallbacks {
             .unwrap(),
         )
     }
+
+    fn callbacks(&self) -> Option<IbcCallbackRequest> {
+        Some(IbcCallbackRequest::both(
+            self.src_callback.clone(),
+            self.dst_callback.clone(),
+        ))
+    }
+}
+
+impl MemoSource for WithMemoAndCallbacks {
+    fn into_memo(self) -> Option<String> {
+        Some(self.memo)
+    }
+
+    fn callbacks(&self) -> Option<IbcCallbackRequest> {
+        Some(self.callbacks.clone())
+    }
+}
+
+/// Merges the callback entries into the given memo, which has to be a JSON object.
+///
+/// The callback entries are appended after the existing entries of the memo.
+/// Fails if the memo already contains one of the callback keys, since the callbacks
+/// middleware would only see one of the two values.
+fn merge_memo(memo: &str, callbacks: &IbcCallbackRequest) -> StdResult<String> {
+    let existing_keys = from_json::<BTreeMap<String, IgnoredAny>>(memo)
+        .map_err(|_| StdError::generic_err("Memo must be a JSON object to add callbacks to it"))?;
+
+    let callback_keys = [
+        ("src_callback", callbacks.src_callback.is_some()),
+        ("dest_callback", callbacks.dest_callback.is_some()),
+    ];
+    for (key, _) in callback_keys.iter().filter(|(_, present)| *present) {
+        if existing_keys.contains_key(*key) {
+            return Err(StdError::generic_err(format!(
+                "Memo already contains the key `{key}`"
+            )));
+        }
+    }
+
+    // Both are valid JSON objects at this point, so we can splice the entries of the callback
+    // object into the memo object without re-serializing the user's data.
+    let memo = memo.trim();
+    let memo_entries = memo[1..memo.len() - 1].trim();
+    let callback_json = to_json_string(callbacks)?;
+    let callback_entries = &callback_json[1..callback_json.len() - 1];
+    if memo_entries.is_empty() {
+        Ok(format!("{{{callback_entries}}}"))
+    } else {
+        Ok(format!("{{{memo_entries},{callback_entries}}}"))
+    }
 }
 
 impl<M: MemoSource> TransferMsgBuilder<M> {
+    /// Sets the timeout to the given number of seconds after the current block time.
+    pub fn with_timeout_relative(self, env: &Env, seconds: u64) -> Self {
+        Self {
+            timeout: env.block.time.plus_seconds(seconds).into(),
+            ..self
+        }
+    }
+
+    /// Checks the callbacks requested in the memo.
+    ///
+    /// This makes sure that the gas limits of the callbacks are not zero and,
+    /// if an [`Api`] is provided, that the source callback address is valid.
+    /// The destination callback address belongs to another chain and can only be checked for
+    /// being non-empty.
+    ///
+    /// Note that timeout callbacks are only delivered to the source callback address,
+    /// so you need a source callback if you want to be notified about a timeout.
+    pub fn validate(&self, api: Option<&dyn Api>) -> StdResult<()> {
+        let Some(callbacks) = self.memo.callbacks() else {
+            return Ok(());
+        };
+
+        if let Some(src_callback) = &callbacks.src_callback {
+            if src_callback.gas_limit.is_some_and(|limit| limit.is_zero()) {
+                return Err(StdError::generic_err(
+                    "Gas limit of the source callback must not be zero",
+                ));
+            }
+            if let Some(api) = api {
+                api.addr_validate(src_callback.address.as_str())?;
+            }
+        }
+        if let Some(dst_callback) = &callbacks.dest_callback {
+            if dst_callback.gas_limit.is_some_and(|limit| limit.is_zero()) {
+                return Err(StdError::generic_err(
+                    "Gas limit of the destination callback must not be zero",
+                ));
+            }
+            if dst_callback.address.is_empty() {
+                return Err(StdError::generic_err(
+                    "Address of the destination callback must not be empty",
+                ));
+            }
+        }
+        Ok(())
+    }
+
     pub fn build(self) -> IbcMsg {
         IbcMsg::Transfer {
             channel_id: self.channel_id,
@@ -168,6 +291,26 @@ impl TransferMsgBuilder<WithSrcCallback> {
             },
         }
     }
+
+    /// Adds the callback entries to the given memo, which has to be a JSON object.
+    ///
+    /// Returns an error if the memo is not a JSON object or already contains a callback key.
+    pub fn with_memo(
+        self,
+        memo: impl Into<String>,
+    ) -> StdResult<TransferMsgBuilder<WithMemoAndCallbacks>> {
+        let callbacks = IbcCallbackRequest::source(self.memo.src_callback);
+        Ok(TransferMsgBuilder {
+            channel_id: self.channel_id,
+            to_address: self.to_address,
+            amount: self.amount,
+            timeout: self.timeout,
+            memo: WithMemoAndCallbacks {
+                memo: merge_memo(&memo.into(), &callbacks)?,
+                callbacks,
+            },
+        })
+    }
 }
 
 impl TransferMsgBuilder<WithDstCallback> {
@@ -190,10 +333,53 @@ impl TransferMsgBuilder<WithDstCallback> {
             },
         }
     }
+
+    /// Adds the callback entries to the given memo, which has to be a JSON object.
+    ///
+    /// Returns an error if the memo is not a JSON object or already contains a callback key.
+    pub fn with_memo(
+        self,
+        memo: impl Into<String>,
+    ) -> StdResult<TransferMsgBuilder<WithMemoAndCallbacks>> {
+        let callbacks = IbcCallbackRequest::destination(self.memo.dst_callback);
+        Ok(TransferMsgBuilder {
+            channel_id: self.channel_id,
+            to_address: self.to_address,
+            amount: self.amount,
+            timeout: self.timeout,
+            memo: WithMemoAndCallbacks {
+                memo: merge_memo(&memo.into(), &callbacks)?,
+                callbacks,
+            },
+        })
+    }
+}
+
+impl TransferMsgBuilder<WithCallbacks> {
+    /// Adds the callback entries to the given memo, which has to be a JSON object.
+    ///
+    /// Returns an error if the memo is not a JSON object or already contains a callback key.
+    pub fn with_memo(
+        self,
+        memo: impl Into<String>,
+    ) -> StdResult<TransferMsgBuilder<WithMemoAndCallbacks>> {
+        let callbacks = IbcCallbackRequest::both(self.memo.src_callback, self.memo.dst_callback);
+        Ok(TransferMsgBuilder {
+            channel_id: self.channel_id,
+            to_address: self.to_address,
+            amount: self.amount,
+            timeout: self.timeout,
+            memo: WithMemoAndCallbacks {
+                memo: merge_memo(&memo.into(), &callbacks)?,
+                callbacks,
+            },
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::testing::{mock_env, MockApi};
     use crate::{coin, Addr, Timestamp, Uint64};
 
     use super::*;
@@ -295,4 +481,246 @@ mod tests {
         );
         assert_eq!(with_both_callbacks1, with_both_callbacks2);
     }
+
+    #[test]
+    fn with_memo_merges_callbacks_into_memo() {
+        let src_callback = IbcSrcCallback {
+            address: Addr::unchecked("src"),
+            gas_limit: Some(Uint64::new(12345)),
+        };
+        let dst_callback = IbcDstCallback {
+            address: "dst".to_string(),
+            gas_limit: None,
+        };
+        let builder = TransferMsgBuilder::new(
+            "channel-0",
+            "cosmos1example",
+            coin(10, "ucoin"),
+            Timestamp::from_seconds(12345),
+        );
+        let user_memo = r#"{"forward":{"receiver":"osmo1receiver","port":"transfer"}}"#;
+
+        // both callbacks
+        let msg = builder
+            .clone()
+            .with_src_callback(src_callback.clone())
+            .with_dst_callback(dst_callback.clone())
+            .with_memo(user_memo)
+            .unwrap()
+            .build();
+        assert_eq!(
+            msg,
+            IbcMsg::Transfer {
+                channel_id: "channel-0".to_string(),
+                to_address: "cosmos1example".to_string(),
+                amount: coin(10, "ucoin"),
+                timeout: Timestamp::from_seconds(12345).into(),
+                memo: Some(
+                    r#"{"forward":{"receiver":"osmo1receiver","port":"transfer"},"src_callback":{"address":"src","gas_limit":"12345"},"dest_callback":{"address":"dst"}}"#
+                        .to_string()
+                ),
+            }
+        );
+
+        // source callback only
+        let msg = builder
+            .clone()
+            .with_src_callback(src_callback.clone())
+            .with_memo(user_memo)
+            .unwrap()
+            .build();
+        let IbcMsg::Transfer { memo, .. } = msg else {
+            panic!("unexpected message");
+        };
+        assert_eq!(
+            memo.unwrap(),
+            r#"{"forward":{"receiver":"osmo1receiver","port":"transfer"},"src_callback":{"address":"src","gas_limit":"12345"}}"#
+        );
+
+        // destination callback only, with whitespace around the memo
+        let msg = builder
+            .clone()
+            .with_dst_callback(dst_callback.clone())
+            .with_memo(" { \"forward\" : {} }\n")
+            .unwrap()
+            .build();
+        let IbcMsg::Transfer { memo, .. } = msg else {
+            panic!("unexpected message");
+        };
+        assert_eq!(
+            memo.unwrap(),
+            r#"{"forward" : {},"dest_callback":{"address":"dst"}}"#
+        );
+
+        // empty object
+        let msg = builder
+            .with_src_callback(src_callback)
+            .with_memo("{}")
+            .unwrap()
+            .build();
+        let IbcMsg::Transfer { memo, .. } = msg else {
+            panic!("unexpected message");
+        };
+        assert_eq!(
+            memo.unwrap(),
+            r#"{"src_callback":{"address":"src","gas_limit":"12345"}}"#
+        );
+    }
+
+    #[test]
+    fn with_memo_errors_for_conflicting_keys() {
+        let src_callback = IbcSrcCallback {
+            address: Addr::unchecked("src"),
+            gas_limit: None,
+        };
+        let dst_callback = IbcDstCallback {
+            address: "dst".to_string(),
+            gas_limit: None,
+        };
+        let builder = TransferMsgBuilder::new(
+            "channel-0",
+            "cosmos1example",
+            coin(10, "ucoin"),
+            Timestamp::from_seconds(12345),
+        );
+
+        let err = builder
+            .clone()
+            .with_src_callback(src_callback.clone())
+            .with_dst_callback(dst_callback.clone())
+            .with_memo(r#"{"forward":{},"dest_callback":{"address":"other"}}"#)
+            .unwrap_err();
+        match err {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, "Memo already contains the key `dest_callback`")
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        let err = builder
+            .clone()
+            .with_src_callback(src_callback.clone())
+            .with_memo(r#"{"src_callback":{"address":"other"}}"#)
+            .unwrap_err();
+        match err {
+            StdError::GenericErr { msg, .. } => {
+                assert_eq!(msg, "Memo already contains the key `src_callback`")
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        // memo has to be a JSON object
+        for memo in ["memo", r#"["src_callback"]"#, "", "{"] {
+            let err = builder
+                .clone()
+                .with_dst_callback(dst_callback.clone())
+                .with_memo(memo)
+                .unwrap_err();
+            match err {
+                StdError::GenericErr { msg, .. } => {
+                    assert_eq!(msg, "Memo must be a JSON object to add callbacks to it")
+                }
+                err => panic!("Unexpected error: {err:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn with_timeout_relative_works() {
+        let env = mock_env();
+        let msg = TransferMsgBuilder::new(
+            "channel-0",
+            "cosmos1example",
+            coin(10, "ucoin"),
+            Timestamp::from_seconds(12345),
+        )
+        .with_timeout_relative(&env, 600)
+        .build();
+        let IbcMsg::Transfer { timeout, .. } = msg else {
+            panic!("unexpected message");
+        };
+        assert_eq!(timeout, env.block.time.plus_seconds(600).into());
+    }
+
+    #[test]
+    fn validate_works() {
+        let mock_api = MockApi::default();
+        let api: Option<&dyn Api> = Some(&mock_api);
+        let builder = TransferMsgBuilder::new(
+            "channel-0",
+            "cosmos1example",
+            coin(10, "ucoin"),
+            Timestamp::from_seconds(12345),
+        );
+        let src_callback = IbcSrcCallback {
+            address: mock_api.addr_make("contract"),
+            gas_limit: Some(Uint64::new(100_000)),
+        };
+        let dst_callback = IbcDstCallback {
+            address: "osmo1receiver".to_string(),
+            gas_limit: None,
+        };
+
+        // no callbacks
+        builder.validate(api).unwrap();
+        builder.clone().with_memo("memo").validate(api).unwrap();
+
+        // valid callbacks
+        let with_callbacks = builder
+            .clone()
+            .with_src_callback(src_callback.clone())
+            .with_dst_callback(dst_callback.clone());
+        with_callbacks.validate(None).unwrap();
+        with_callbacks.validate(api).unwrap();
+        with_callbacks
+            .clone()
+            .with_memo("{}")
+            .unwrap()
+            .validate(api)
+            .unwrap();
+
+        // invalid source address is only detected with an Api
+        let invalid_src = builder.clone().with_src_callback(IbcSrcCallback {
+            address: Addr::unchecked("foo"),
+            gas_limit: None,
+        });
+        invalid_src.validate(None).unwrap();
+        invalid_src.validate(api).unwrap_err();
+
+        // zero gas limits
+        let err = builder
+            .clone()
+            .with_src_callback(IbcSrcCallback {
+                gas_limit: Some(Uint64::zero()),
+                ..src_callback
+            })
+            .validate(None)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Gas limit of the source callback must not be zero"));
+        let err = builder
+            .clone()
+            .with_dst_callback(IbcDstCallback {
+                gas_limit: Some(Uint64::zero()),
+                ..dst_callback
+            })
+            .validate(None)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Gas limit of the destination callback must not be zero"));
+
+        // empty destination address
+        let err = builder
+            .with_dst_callback(IbcDstCallback {
+                address: String::new(),
+                gas_limit: None,
+            })
+            .validate(None)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Address of the destination callback must not be empty"));
+    }
 }
@@ -17,7 +17,7 @@ use crate::{
 use crate::{Addr, CanonicalAddr};
 #[cfg(feature = "cosmwasm_2_1")]
 use crate::{AggregationError, HashFunction, PairingEqualityError};
-use crate::{RecoverPubkeyError, StdError, StdResult, SystemError, VerificationError};
+use crate::{InputLength, RecoverPubkeyError, StdError, StdResult, SystemError, VerificationError};
 
 /// An upper bound for typical canonical address lengths (e.g. 20 in Cosmos SDK/Ethereum or 32 in Nano/Substrate)
 const CANONICAL_ADDRESS_BUFFER_LENGTH: usize = 64;
@@ -102,6 +102,17 @@ extern "C" {
     /// greater than 1 in case of error.
     fn ed25519_batch_verify(messages_ptr: u32, signatures_ptr: u32, public_keys_ptr: u32) -> u32;
 
+    /// Checks that the given bytes are a valid ed25519 public key.
+    /// Returns 0 if the key is valid and 1 if it is not.
+    #[cfg(feature = "cosmwasm_2_3")]
+    fn ed25519_validate_pubkey(public_key_ptr: u32) -> u32;
+
+    /// Computes the HMAC-SHA256 of the data using the given key and writes the
+    /// 32 byte result into the region at `out_ptr`.
+    /// Returns 0 on success.
+    #[cfg(feature = "cosmwasm_2_3")]
+    fn hmac_sha256(key_ptr: u32, data_ptr: u32, out_ptr: u32) -> u32;
+
     /// Writes a debug message (UFT-8 encoded) to the host for debugging purposes.
     /// The host is free to log or process this in any way it considers appropriate.
     /// In production environments it is expected that those messages are discarded.
@@ -133,6 +144,7 @@ impl Storage for ExternalStorage {
             return None;
         }
 
+        // an existing key always results in a region, even if the value is empty
         let value_ptr = read as *mut Region<Owned>;
         let data = unsafe { Region::from_heap_ptr(ptr::NonNull::new(value_ptr).unwrap()) };
 
@@ -550,9 +562,15 @@ impl Api for ExternalApi {
             0 => Ok(true),
             1 => Ok(false),
             2 => panic!("MessageTooLong must not happen. This is a bug in the VM."),
-            3 => Err(VerificationError::InvalidHashFormat),
-            4 => Err(VerificationError::InvalidSignatureFormat),
-            5 => Err(VerificationError::InvalidPubkeyFormat),
+            3 => Err(VerificationError::InvalidHashFormat {
+                length: length_mismatch(32, message_hash.len()),
+            }),
+            4 => Err(VerificationError::InvalidSignatureFormat {
+                length: length_mismatch(64, signature.len()),
+            }),
+            5 => Err(VerificationError::InvalidPubkeyFormat {
+                length: ecdsa_pubkey_length_mismatch(public_key),
+            }),
             10 => Err(VerificationError::GenericErr),
             error_code => Err(VerificationError::unknown_err(error_code)),
         }
@@ -584,8 +602,12 @@ impl Api for ExternalApi {
                 Ok(pubkey)
             }
             2 => panic!("MessageTooLong must not happen. This is a bug in the VM."),
-            3 => Err(RecoverPubkeyError::InvalidHashFormat),
-            4 => Err(RecoverPubkeyError::InvalidSignatureFormat),
+            3 => Err(RecoverPubkeyError::InvalidHashFormat {
+                length: length_mismatch(32, message_hash.len()),
+            }),
+            4 => Err(RecoverPubkeyError::InvalidSignatureFormat {
+                length: length_mismatch(64, signature.len()),
+            }),
             6 => Err(RecoverPubkeyError::InvalidRecoveryParam),
             error_code => Err(RecoverPubkeyError::unknown_err(error_code)),
         }
@@ -610,9 +632,15 @@ impl Api for ExternalApi {
             0 => Ok(true),
             1 => Ok(false),
             2 => panic!("MessageTooLong must not happen. This is a bug in the VM."),
-            3 => Err(VerificationError::InvalidHashFormat),
-            4 => Err(VerificationError::InvalidSignatureFormat),
-            5 => Err(VerificationError::InvalidPubkeyFormat),
+            3 => Err(VerificationError::InvalidHashFormat {
+                length: length_mismatch(32, message_hash.len()),
+            }),
+            4 => Err(VerificationError::InvalidSignatureFormat {
+                length: length_mismatch(64, signature.len()),
+            }),
+            5 => Err(VerificationError::InvalidPubkeyFormat {
+                length: ecdsa_pubkey_length_mismatch(public_key),
+            }),
             10 => Err(VerificationError::GenericErr),
             error_code => Err(VerificationError::unknown_err(error_code)),
         }
@@ -645,8 +673,12 @@ impl Api for ExternalApi {
                 Ok(pubkey)
             }
             2 => panic!("MessageTooLong must not happen. This is a bug in the VM."),
-            3 => Err(RecoverPubkeyError::InvalidHashFormat),
-            4 => Err(RecoverPubkeyError::InvalidSignatureFormat),
+            3 => Err(RecoverPubkeyError::InvalidHashFormat {
+                length: length_mismatch(32, message_hash.len()),
+            }),
+            4 => Err(RecoverPubkeyError::InvalidSignatureFormat {
+                length: length_mismatch(64, signature.len()),
+            }),
             6 => Err(RecoverPubkeyError::InvalidRecoveryParam),
             error_code => Err(RecoverPubkeyError::unknown_err(error_code)),
         }
@@ -671,8 +703,12 @@ impl Api for ExternalApi {
             1 => Ok(false),
             2 => panic!("Error code 2 unused since CosmWasm 0.15. This is a bug in the VM."),
             3 => panic!("InvalidHashFormat must not happen. This is a bug in the VM."),
-            4 => Err(VerificationError::InvalidSignatureFormat),
-            5 => Err(VerificationError::InvalidPubkeyFormat),
+            4 => Err(VerificationError::InvalidSignatureFormat {
+                length: length_mismatch(64, signature.len()),
+            }),
+            5 => Err(VerificationError::InvalidPubkeyFormat {
+                length: length_mismatch(32, public_key.len()),
+            }),
             10 => Err(VerificationError::GenericErr),
             error_code => Err(VerificationError::unknown_err(error_code)),
         }
@@ -703,13 +739,56 @@ impl Api for ExternalApi {
             1 => Ok(false),
             2 => panic!("Error code 2 unused since CosmWasm 0.15. This is a bug in the VM."),
             3 => panic!("InvalidHashFormat must not happen. This is a bug in the VM."),
-            4 => Err(VerificationError::InvalidSignatureFormat),
-            5 => Err(VerificationError::InvalidPubkeyFormat),
+            // The VM does not tell which element of the batch was rejected
+            4 => Err(VerificationError::InvalidSignatureFormat { length: None }),
+            5 => Err(VerificationError::InvalidPubkeyFormat { length: None }),
             10 => Err(VerificationError::GenericErr),
             error_code => Err(VerificationError::unknown_err(error_code)),
         }
     }
 
+    #[cfg(feature = "cosmwasm_2_3")]
+    fn ed25519_validate_pubkey(&self, public_key: &[u8]) -> bool {
+        // The VM rejects longer keys with an error, so we don't pass on keys of the wrong length
+        if public_key.len() != 32 {
+            return false;
+        }
+
+        let pubkey_send = Region::from_slice(public_key);
+        let pubkey_send_ptr = pubkey_send.as_ptr() as u32;
+
+        let result = unsafe { ed25519_validate_pubkey(pubkey_send_ptr) };
+        match result {
+            0 => true,
+            1 => false,
+            error_code => panic!(
+                "Unexpected error code {error_code} from ed25519_validate_pubkey. This is a bug in the VM."
+            ),
+        }
+    }
+
+    #[cfg(feature = "cosmwasm_2_3")]
+    fn hmac_sha256(&self, key: &[u8], data: &[u8]) -> [u8; 32] {
+        let mac = [0_u8; 32];
+
+        let send_key = Region::from_slice(key);
+        let send_key_ptr = send_key.as_ptr() as u32;
+
+        let send_data = Region::from_slice(data);
+        let send_data_ptr = send_data.as_ptr() as u32;
+
+        let out = Region::from_slice(&mac);
+        let out_ptr = out.as_ptr() as u32;
+        let result = unsafe { hmac_sha256(send_key_ptr, send_data_ptr, out_ptr) };
+
+        match result {
+            0 => mac,
+            error_code => panic!(
+                "Unexpected error code {error_code} from hmac_sha256. This is a bug in the VM."
+            ),
+        }
+    }
+
     fn debug(&self, message: &str) {
         // keep the boxes in scope, so we free it at the end (don't cast to pointers same line as Region::from_slice)
         let region = Region::from_slice(message.as_bytes());
@@ -718,6 +797,24 @@ impl Api for ExternalApi {
     }
 }
 
+/// The length details of an input rejected by the VM, if its length is the reason.
+///
+/// The VM only returns an error code, so those are reconstructed from the input.
+fn length_mismatch(expected: usize, actual: usize) -> Option<InputLength> {
+    (expected != actual).then_some(InputLength { expected, actual })
+}
+
+/// Like [`length_mismatch`] for a SEC1 encoded ECDSA public key, whose expected length
+/// depends on its first byte.
+fn ecdsa_pubkey_length_mismatch(pubkey: &[u8]) -> Option<InputLength> {
+    let expected = match pubkey.first() {
+        Some(0x02) | Some(0x03) => 33,
+        Some(0x04) => 65,
+        _ => return None,
+    };
+    length_mismatch(expected, pubkey.len())
+}
+
 /// Takes a pointer to a Region and reads the data into a String.
 /// This is for trusted string sources only.
 unsafe fn consume_string_region_written_by_vm(from: *mut Region<Owned>) -> String {
@@ -10,10 +10,13 @@ extern crate alloc;
 
 // Exposed on all platforms
 
-mod __internal;
+#[doc(hidden)]
+pub mod __internal;
+mod acl;
 mod addresses;
 mod assertions;
 mod binary;
+mod caching_querier;
 mod checksum;
 mod coin;
 mod coins;
@@ -22,19 +25,19 @@ mod deps;
 mod encoding;
 mod errors;
 mod eureka;
-mod forward_ref;
 mod hex_binary;
 mod ibc;
 mod import_helpers;
 #[cfg(feature = "iterator")]
 mod iterator;
-mod math;
 mod metadata;
 mod msgpack;
 mod never;
 mod pagination;
 mod panic;
+mod proto;
 mod query;
+mod requirements;
 mod results;
 mod sections;
 mod serde;
@@ -52,8 +55,12 @@ pub(crate) mod prelude;
 /// contract devs to use it directly.
 pub mod storage_keys;
 
+pub mod ownership;
+
+pub use crate::acl::Acl;
 pub use crate::addresses::{instantiate2_address, Addr, CanonicalAddr, Instantiate2AddressError};
 pub use crate::binary::Binary;
+pub use crate::caching_querier::CachingQuerier;
 pub use crate::checksum::{Checksum, ChecksumError};
 pub use crate::coin::{coin, coins, has_coins, Coin};
 pub use crate::coins::Coins;
@@ -62,9 +69,9 @@ pub use crate::encoding::{from_base64, from_hex, to_base64, to_hex};
 pub use crate::errors::{
     AggregationError, CheckedFromRatioError, CheckedMultiplyFractionError,
     CheckedMultiplyRatioError, CoinFromStrError, CoinsError, ConversionOverflowError,
-    DivideByZeroError, DivisionError, OverflowError, OverflowOperation, PairingEqualityError,
-    RecoverPubkeyError, RoundDownOverflowError, RoundUpOverflowError, StdError, StdResult,
-    SystemError, VerificationError,
+    DivideByZeroError, DivisionError, InputLength, OverflowError, OverflowOperation,
+    PairingEqualityError, RecoverPubkeyError, RoundDownOverflowError, RoundUpOverflowError,
+    StdError, StdResult, SystemError, VerificationError,
 };
 pub use crate::eureka::{EurekaMsg, EurekaPayload};
 pub use crate::hex_binary::HexBinary;
@@ -79,31 +86,31 @@ pub use crate::ibc::{
 };
 #[cfg(feature = "iterator")]
 pub use crate::iterator::{Order, Record};
-pub use crate::math::{
-    Decimal, Decimal256, Decimal256RangeExceeded, DecimalRangeExceeded, Fraction, Int128, Int256,
-    Int512, Int64, Isqrt, SignedDecimal, SignedDecimal256, SignedDecimal256RangeExceeded,
-    SignedDecimalRangeExceeded, Uint128, Uint256, Uint512, Uint64,
-};
-pub use crate::metadata::{DenomMetadata, DenomUnit};
+pub use crate::metadata::{format_amount, parse_amount, DenomMetadata, DenomUnit};
 pub use crate::msgpack::{from_msgpack, to_msgpack_binary, to_msgpack_vec};
 pub use crate::never::Never;
 pub use crate::pagination::PageRequest;
+#[cfg(feature = "iterator")]
+pub use crate::pagination::{paginate_prefix, paginate_range};
 pub use crate::query::{
     AllBalanceResponse, AllDelegationsResponse, AllDenomMetadataResponse, AllValidatorsResponse,
     BalanceResponse, BankQuery, BondedDenomResponse, ChannelResponse, CodeInfoResponse,
-    ContractInfoResponse, CustomQuery, DecCoin, Delegation, DelegationResponse,
-    DelegationRewardsResponse, DelegationTotalRewardsResponse, DelegatorReward,
-    DelegatorValidatorsResponse, DelegatorWithdrawAddressResponse, DenomMetadataResponse,
-    DistributionQuery, FeeEnabledChannelResponse, FullDelegation, GrpcQuery, IbcQuery,
-    ListChannelsResponse, PortIdResponse, QueryRequest, StakingQuery, SupplyResponse, Validator,
+    CommunityPoolResponse, ContractInfoResponse, ContractsByCodeResponse, CustomQuery, DecCoin,
+    Delegation, DelegationResponse, DelegationRewardsResponse, DelegationTotalRewardsResponse,
+    DelegatorReward, DelegatorValidatorsResponse, DelegatorWithdrawAddressResponse,
+    DenomMetadataResponse, DistributionQuery, FeeEnabledChannelResponse, FullDelegation, GrpcQuery,
+    IbcQuery, ListChannelsResponse, PaginatedBalancesResponse, PortIdResponse, QueryRequest,
+    StakingQuery, SupplyResponse, UnbondingDelegation, UnbondingDelegationsResponse, Validator,
     ValidatorResponse, WasmQuery,
 };
 #[cfg(all(feature = "stargate", feature = "cosmwasm_1_2"))]
 pub use crate::results::WeightedVoteOption;
 pub use crate::results::{
-    attr, wasm_execute, wasm_instantiate, AnyMsg, Attribute, BankMsg, ContractResult, CosmosMsg,
-    CustomMsg, Empty, Event, MsgResponse, QueryResponse, Reply, ReplyOn, Response, SubMsg,
-    SubMsgResponse, SubMsgResult, SystemResult, WasmMsg,
+    attr, parse_execute_response_data, parse_instantiate_response_data, wasm_execute,
+    wasm_instantiate, AnyMsg, Attribute, AuthzGrant, AuthzMsg, BankMsg, ContractResult, CosmosMsg,
+    CustomMsg, Empty, Event, ExecuteResponseData, InstantiateResponseData, MsgResponse,
+    QueryResponse, Reply, ReplyOn, Response, SubMsg, SubMsgResponse, SubMsgResult, SystemResult,
+    TypedEvent, WasmMsg,
 };
 #[cfg(feature = "staking")]
 pub use crate::results::{DistributionMsg, StakingMsg};
@@ -111,14 +118,21 @@ pub use crate::results::{DistributionMsg, StakingMsg};
 pub use crate::results::{GovMsg, VoteOption};
 #[allow(deprecated)]
 pub use crate::serde::{
-    from_binary, from_json, from_slice, to_binary, to_json_binary, to_json_string, to_json_vec,
-    to_vec,
+    from_binary, from_json, from_json_with_depth, from_slice, to_binary, to_json_binary,
+    to_json_string, to_json_vec, to_vec,
 };
 pub use crate::stdack::StdAck;
 pub use crate::storage::MemoryStorage;
 pub use crate::timestamp::Timestamp;
 pub use crate::traits::{Api, HashFunction, Querier, QuerierResult, QuerierWrapper, Storage};
 pub use crate::types::{BlockInfo, ContractInfo, Env, MessageInfo, MigrateInfo, TransactionInfo};
+#[doc(hidden)]
+pub use cosmwasm_core::impl_mul_fraction;
+pub use cosmwasm_core::{
+    Decimal, Decimal256, Decimal256RangeExceeded, DecimalRangeExceeded, Fraction, Int128, Int256,
+    Int512, Int64, Isqrt, SignedDecimal, SignedDecimal256, SignedDecimal256RangeExceeded,
+    SignedDecimalRangeExceeded, Uint128, Uint256, Uint512, Uint64,
+};
 
 #[cfg(feature = "abort")]
 mod _warning {
@@ -2,6 +2,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::prelude::*;
+use crate::{StdError, StdResult, Uint128};
 
 /// Replicates the cosmos-sdk bank module Metadata type
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq, JsonSchema)]
@@ -16,6 +17,30 @@ pub struct DenomMetadata {
     pub uri_hash: String,
 }
 
+impl DenomMetadata {
+    /// Returns the number of decimal places between the base unit and the display unit,
+    /// e.g. 6 for uatom and atom.
+    fn display_exponent(&self) -> StdResult<u32> {
+        let exponent_of = |denom: &str| {
+            self.denom_units
+                .iter()
+                .find(|unit| unit.denom == denom)
+                .map(|unit| unit.exponent)
+        };
+        let display = exponent_of(&self.display).ok_or_else(|| {
+            StdError::generic_err(format!(
+                "Display denom unit '{}' not found in metadata",
+                self.display
+            ))
+        })?;
+        // The base unit usually has exponent 0, but it is not required to be listed
+        let base = exponent_of(&self.base).unwrap_or_default();
+        display.checked_sub(base).ok_or_else(|| {
+            StdError::generic_err("Display unit exponent is smaller than base unit exponent")
+        })
+    }
+}
+
 /// Replicates the cosmos-sdk bank module DenomUnit type
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq, JsonSchema)]
 pub struct DenomUnit {
@@ -23,3 +48,230 @@ pub struct DenomUnit {
     pub exponent: u32,
     pub aliases: Vec<String>,
 }
+
+/// Converts an amount in the base unit to a decimal string in the display unit
+/// of the given metadata, e.g. 1500000 uatom to "1.5" atom.
+///
+/// Trailing fractional zeros are removed. Errors if the display unit is not
+/// part of the metadata's `denom_units`.
+///
+/// ## Examples
+///
+/// ```
+/// # use cosmwasm_std::{format_amount, DenomMetadata, DenomUnit, Uint128};
+/// let metadata = DenomMetadata {
+///     base: "uatom".to_string(),
+///     display: "atom".to_string(),
+///     denom_units: vec![
+///         DenomUnit { denom: "uatom".to_string(), exponent: 0, aliases: vec![] },
+///         DenomUnit { denom: "atom".to_string(), exponent: 6, aliases: vec![] },
+///     ],
+///     ..Default::default()
+/// };
+/// assert_eq!(format_amount(Uint128::new(1_500_000), &metadata).unwrap(), "1.5");
+/// ```
+pub fn format_amount(amount: Uint128, metadata: &DenomMetadata) -> StdResult<String> {
+    let exponent = metadata.display_exponent()? as usize;
+    let digits = amount.to_string();
+    if exponent == 0 {
+        return Ok(digits);
+    }
+
+    // pad with leading zeros such that there is at least one whole digit
+    let digits = format!("{digits:0>width$}", width = exponent + 1);
+    let (whole, fractional) = digits.split_at(digits.len() - exponent);
+    let fractional = fractional.trim_end_matches('0');
+    if fractional.is_empty() {
+        Ok(whole.to_string())
+    } else {
+        Ok(format!("{whole}.{fractional}"))
+    }
+}
+
+/// Converts a decimal string in the display unit of the given metadata to an amount
+/// in the base unit, e.g. "1.5" atom to 1500000 uatom.
+///
+/// Possible inputs: "1.5", "1", "0.000001", "1.500000"
+/// Disallowed: "", ".5", "1.", "-1", "1e6", " 1"
+///
+/// This never performs any kind of rounding. More fractional digits than the exponent
+/// of the display unit, even zeros, result in an error.
+pub fn parse_amount(input: &str, metadata: &DenomMetadata) -> StdResult<Uint128> {
+    let exponent = metadata.display_exponent()? as usize;
+
+    let (whole, fractional) = match input.split_once('.') {
+        Some((whole, fractional)) => (whole, Some(fractional)),
+        None => (input, None),
+    };
+    let is_number = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !is_number(whole) {
+        return Err(StdError::generic_err("Error parsing whole"));
+    }
+    let fractional = match fractional {
+        Some(fractional) if !is_number(fractional) => {
+            return Err(StdError::generic_err("Error parsing fractional"))
+        }
+        Some(fractional) => fractional,
+        None => "",
+    };
+    if fractional.len() > exponent {
+        return Err(StdError::generic_err(format!(
+            "Cannot parse more than {exponent} fractional digits"
+        )));
+    }
+
+    let digits = format!("{whole}{fractional:0<exponent$}");
+    // Only ASCII digits are left, so parsing can only fail if the value is too big
+    digits
+        .parse::<u128>()
+        .map(Uint128::new)
+        .map_err(|_| StdError::generic_err("Value too big"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(base: &str, display: &str, exponent: u32) -> DenomMetadata {
+        DenomMetadata {
+            base: base.to_string(),
+            display: display.to_string(),
+            symbol: display.to_uppercase(),
+            denom_units: vec![
+                DenomUnit {
+                    denom: base.to_string(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: display.to_string(),
+                    exponent,
+                    aliases: vec![],
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn format_amount_works() {
+        let atom = metadata("uatom", "atom", 6);
+        let cases = [
+            (0, "0"),
+            (1, "0.000001"),
+            (100, "0.0001"),
+            (1_000_000, "1"),
+            (1_500_000, "1.5"),
+            (1_234_567, "1.234567"),
+            (123_000_000_000, "123000"),
+        ];
+        for (amount, expected) in cases {
+            assert_eq!(
+                format_amount(Uint128::new(amount), &atom).unwrap(),
+                expected
+            );
+        }
+
+        let eth = metadata("wei", "eth", 18);
+        assert_eq!(format_amount(Uint128::zero(), &eth).unwrap(), "0");
+        assert_eq!(
+            format_amount(Uint128::one(), &eth).unwrap(),
+            "0.000000000000000001"
+        );
+        assert_eq!(
+            format_amount(Uint128::new(1_500_000_000_000_000_000), &eth).unwrap(),
+            "1.5"
+        );
+        assert_eq!(
+            format_amount(Uint128::MAX, &eth).unwrap(),
+            "340282366920938463463.374607431768211455"
+        );
+
+        // display unit equals base unit
+        let plain = metadata("token", "token", 0);
+        assert_eq!(format_amount(Uint128::new(42), &plain).unwrap(), "42");
+    }
+
+    #[test]
+    fn format_amount_errors_for_missing_display_unit() {
+        let mut atom = metadata("uatom", "atom", 6);
+        atom.display = "matom".to_string();
+        let err = format_amount(Uint128::new(1), &atom).unwrap_err();
+        assert!(err.to_string().contains("'matom' not found"));
+        let err = parse_amount("1", &atom).unwrap_err();
+        assert!(err.to_string().contains("'matom' not found"));
+    }
+
+    #[test]
+    fn parse_amount_works() {
+        let atom = metadata("uatom", "atom", 6);
+        let cases = [
+            ("0", 0),
+            ("0.0", 0),
+            ("000", 0),
+            ("0.000001", 1),
+            ("1", 1_000_000),
+            ("1.5", 1_500_000),
+            ("1.500000", 1_500_000),
+            ("01.5", 1_500_000),
+            ("1.234567", 1_234_567),
+            ("123000", 123_000_000_000),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                parse_amount(input, &atom).unwrap(),
+                Uint128::new(expected),
+                "input: {input}"
+            );
+        }
+
+        let eth = metadata("wei", "eth", 18);
+        assert_eq!(
+            parse_amount("0.000000000000000001", &eth).unwrap(),
+            Uint128::one()
+        );
+        assert_eq!(
+            parse_amount("340282366920938463463.374607431768211455", &eth).unwrap(),
+            Uint128::MAX
+        );
+    }
+
+    #[test]
+    fn parse_amount_rejects_invalid_input() {
+        let atom = metadata("uatom", "atom", 6);
+        for input in [
+            "", ".5", "1.", "-1", "+1", "1e6", " 1", "1 ", "1.2.3", "1,5", "abc",
+        ] {
+            parse_amount(input, &atom).unwrap_err();
+        }
+
+        // dust beyond the exponent is rejected rather than truncated
+        let err = parse_amount("1.0000001", &atom).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Cannot parse more than 6 fractional digits"));
+        parse_amount("0.0000000", &atom).unwrap_err();
+
+        // overflow
+        let eth = metadata("wei", "eth", 18);
+        let err = parse_amount("340282366920938463463.374607431768211456", &eth).unwrap_err();
+        assert!(err.to_string().contains("Value too big"));
+    }
+
+    #[test]
+    fn format_and_parse_round_trip() {
+        for (metadata, amounts) in [
+            (metadata("uatom", "atom", 6), [0, 1, 1_500_000, 987_654_321]),
+            (
+                metadata("wei", "eth", 18),
+                [0, 1, 10u128.pow(18), u128::MAX],
+            ),
+        ] {
+            for amount in amounts {
+                let amount = Uint128::new(amount);
+                let formatted = format_amount(amount, &metadata).unwrap();
+                assert_eq!(parse_amount(&formatted, &metadata).unwrap(), amount);
+            }
+        }
+    }
+}
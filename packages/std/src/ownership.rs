@@ -0,0 +1,413 @@
+//! A two-step ownership transfer for contracts with a single owner.
+//!
+//! The current owner proposes a new owner, optionally with an expiry, and the
+//! proposed owner has to accept the ownership before it is transferred. This
+//! ensures ownership is never transferred to an address nobody controls.
+//!
+//! All functions return an [`Event`] of type `ownership` documenting the change,
+//! which can be added to the contract's response.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use cosmwasm_std::testing::{mock_dependencies, mock_env};
+//! use cosmwasm_std::{ownership, Response};
+//!
+//! let mut deps = mock_dependencies();
+//! let env = mock_env();
+//! let alice = deps.api.addr_make("alice");
+//! let bob = deps.api.addr_make("bob");
+//!
+//! // instantiate
+//! ownership::initialize(&mut deps.storage, &alice).unwrap();
+//!
+//! // execute by alice
+//! let event = ownership::propose_transfer(&mut deps.storage, &env, &alice, &bob, None).unwrap();
+//! let _response: Response = Response::new().add_event(event);
+//!
+//! // execute by bob
+//! let event = ownership::accept(&mut deps.storage, &env, &bob).unwrap();
+//! let _response: Response = Response::new().add_event(event);
+//!
+//! ownership::assert_owner(&deps.storage, &bob).unwrap();
+//! assert!(ownership::assert_owner(&deps.storage, &alice).is_err());
+//! ```
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::storage_keys::to_length_prefixed;
+use crate::{from_json, to_json_vec, Addr, Env, Event, StdError, StdResult, Storage, Timestamp};
+
+/// The namespace the ownership state is stored under.
+/// Contracts using this module must not use it for anything else.
+const NAMESPACE: &[u8] = b"__cosmwasm_ownership";
+
+/// The ownership state of a contract as stored and returned by [`query_ownership`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct OwnershipResponse {
+    /// The current owner or `None` if the ownership was renounced
+    pub owner: Option<Addr>,
+    /// The address the ownership was proposed to, if any
+    pub pending_owner: Option<Addr>,
+    /// The time after which the pending owner can no longer accept the ownership.
+    /// `None` if the proposal does not expire.
+    pub pending_expiry: Option<Timestamp>,
+}
+
+/// Sets the initial owner of the contract. This is typically done in `instantiate`.
+///
+/// Errors if the ownership was initialized before.
+pub fn initialize(storage: &mut dyn Storage, owner: &Addr) -> StdResult<Event> {
+    if storage.get(&state_key()).is_some() {
+        return Err(StdError::generic_err("Ownership is already initialized"));
+    }
+    save(
+        storage,
+        &OwnershipResponse {
+            owner: Some(owner.clone()),
+            pending_owner: None,
+            pending_expiry: None,
+        },
+    )?;
+    Ok(event("initialize").add_attribute("owner", owner.as_str()))
+}
+
+/// Returns an error if the given address is not the current owner.
+/// This always errors after the ownership was renounced.
+pub fn assert_owner(storage: &dyn Storage, addr: &Addr) -> StdResult<()> {
+    match load(storage)?.owner {
+        Some(owner) if owner == *addr => Ok(()),
+        Some(_) => Err(StdError::generic_err(format!(
+            "Address {addr} is not the owner"
+        ))),
+        None => Err(StdError::generic_err("Ownership was renounced")),
+    }
+}
+
+/// Proposes to transfer the ownership to `new_owner`. Only the current owner can do this.
+///
+/// The transfer only happens once the new owner calls [`accept`] before the optional
+/// `expiry`. A previous proposal is replaced by this one.
+pub fn propose_transfer(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    new_owner: &Addr,
+    expiry: Option<Timestamp>,
+) -> StdResult<Event> {
+    assert_owner(storage, sender)?;
+    if expiry.is_some_and(|expiry| expiry <= env.block.time) {
+        return Err(StdError::generic_err("Expiry must be in the future"));
+    }
+
+    let mut state = load(storage)?;
+    state.pending_owner = Some(new_owner.clone());
+    state.pending_expiry = expiry;
+    save(storage, &state)?;
+
+    let mut event = event("propose_transfer")
+        .add_attribute("owner", sender.as_str())
+        .add_attribute("pending_owner", new_owner.as_str());
+    if let Some(expiry) = expiry {
+        event = event.add_attribute("expiry", expiry.to_string());
+    }
+    Ok(event)
+}
+
+/// Accepts a proposed ownership transfer. Only the pending owner can do this
+/// and only before the proposal expired.
+pub fn accept(storage: &mut dyn Storage, env: &Env, sender: &Addr) -> StdResult<Event> {
+    let mut state = load(storage)?;
+    match &state.pending_owner {
+        Some(pending_owner) if pending_owner == sender => {}
+        Some(_) => {
+            return Err(StdError::generic_err(format!(
+                "Address {sender} is not the pending owner"
+            )))
+        }
+        None => return Err(StdError::generic_err("No ownership transfer pending")),
+    }
+    if state
+        .pending_expiry
+        .is_some_and(|expiry| env.block.time >= expiry)
+    {
+        return Err(StdError::generic_err("Ownership transfer expired"));
+    }
+
+    let previous_owner = state.owner.replace(sender.clone());
+    state.pending_owner = None;
+    state.pending_expiry = None;
+    save(storage, &state)?;
+
+    let mut event = event("accept");
+    if let Some(previous_owner) = previous_owner {
+        event = event.add_attribute("previous_owner", previous_owner.as_str());
+    }
+    Ok(event.add_attribute("owner", sender.as_str()))
+}
+
+/// Gives up the ownership for good and cancels any pending transfer.
+/// Only the current owner can do this.
+pub fn renounce(storage: &mut dyn Storage, sender: &Addr) -> StdResult<Event> {
+    assert_owner(storage, sender)?;
+    save(
+        storage,
+        &OwnershipResponse {
+            owner: None,
+            pending_owner: None,
+            pending_expiry: None,
+        },
+    )?;
+    Ok(event("renounce").add_attribute("previous_owner", sender.as_str()))
+}
+
+/// Returns the current ownership state.
+pub fn query_ownership(storage: &dyn Storage) -> StdResult<OwnershipResponse> {
+    load(storage)
+}
+
+fn state_key() -> Vec<u8> {
+    to_length_prefixed(NAMESPACE)
+}
+
+fn load(storage: &dyn Storage) -> StdResult<OwnershipResponse> {
+    let data = storage
+        .get(&state_key())
+        .ok_or_else(|| StdError::generic_err("Ownership is not initialized"))?;
+    from_json(data)
+}
+
+fn save(storage: &mut dyn Storage, state: &OwnershipResponse) -> StdResult<()> {
+    storage.set(&state_key(), &to_json_vec(state)?);
+    Ok(())
+}
+
+fn event(action: &str) -> Event {
+    Event::new("ownership").add_attribute("action", action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{mock_dependencies, mock_env};
+
+    #[test]
+    fn initialize_works() {
+        let mut deps = mock_dependencies();
+        let alice = deps.api.addr_make("alice");
+
+        let err = query_ownership(&deps.storage).unwrap_err();
+        assert_eq!(err, StdError::generic_err("Ownership is not initialized"));
+
+        let event = initialize(&mut deps.storage, &alice).unwrap();
+        assert_eq!(
+            event,
+            Event::new("ownership")
+                .add_attribute("action", "initialize")
+                .add_attribute("owner", alice.as_str())
+        );
+        assert_eq!(
+            query_ownership(&deps.storage).unwrap(),
+            OwnershipResponse {
+                owner: Some(alice.clone()),
+                pending_owner: None,
+                pending_expiry: None,
+            }
+        );
+        assert_owner(&deps.storage, &alice).unwrap();
+
+        // cannot be initialized twice
+        let bob = deps.api.addr_make("bob");
+        let err = initialize(&mut deps.storage, &bob).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Ownership is already initialized")
+        );
+        assert_owner(&deps.storage, &alice).unwrap();
+    }
+
+    #[test]
+    fn transfer_works() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        initialize(&mut deps.storage, &alice).unwrap();
+
+        // only the owner can propose
+        let err = propose_transfer(&mut deps.storage, &env, &bob, &bob, None).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(format!("Address {bob} is not the owner"))
+        );
+
+        let expiry = env.block.time.plus_seconds(60);
+        let event = propose_transfer(&mut deps.storage, &env, &alice, &bob, Some(expiry)).unwrap();
+        assert_eq!(
+            event,
+            Event::new("ownership")
+                .add_attribute("action", "propose_transfer")
+                .add_attribute("owner", alice.as_str())
+                .add_attribute("pending_owner", bob.as_str())
+                .add_attribute("expiry", expiry.to_string())
+        );
+        // the owner does not change until the transfer is accepted
+        assert_owner(&deps.storage, &alice).unwrap();
+        assert_eq!(
+            query_ownership(&deps.storage).unwrap(),
+            OwnershipResponse {
+                owner: Some(alice.clone()),
+                pending_owner: Some(bob.clone()),
+                pending_expiry: Some(expiry),
+            }
+        );
+
+        let event = accept(&mut deps.storage, &env, &bob).unwrap();
+        assert_eq!(
+            event,
+            Event::new("ownership")
+                .add_attribute("action", "accept")
+                .add_attribute("previous_owner", alice.as_str())
+                .add_attribute("owner", bob.as_str())
+        );
+        assert_owner(&deps.storage, &bob).unwrap();
+        assert_owner(&deps.storage, &alice).unwrap_err();
+        assert_eq!(
+            query_ownership(&deps.storage).unwrap(),
+            OwnershipResponse {
+                owner: Some(bob.clone()),
+                pending_owner: None,
+                pending_expiry: None,
+            }
+        );
+
+        // the proposal is consumed
+        let err = accept(&mut deps.storage, &env, &bob).unwrap_err();
+        assert_eq!(err, StdError::generic_err("No ownership transfer pending"));
+    }
+
+    #[test]
+    fn accept_by_wrong_address_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let mallory = deps.api.addr_make("mallory");
+        initialize(&mut deps.storage, &alice).unwrap();
+        propose_transfer(&mut deps.storage, &env, &alice, &bob, None).unwrap();
+
+        for sender in [&mallory, &alice] {
+            let err = accept(&mut deps.storage, &env, sender).unwrap_err();
+            assert_eq!(
+                err,
+                StdError::generic_err(format!("Address {sender} is not the pending owner"))
+            );
+        }
+        assert_owner(&deps.storage, &alice).unwrap();
+
+        accept(&mut deps.storage, &env, &bob).unwrap();
+        assert_owner(&deps.storage, &bob).unwrap();
+    }
+
+    #[test]
+    fn expiry_is_enforced() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        initialize(&mut deps.storage, &alice).unwrap();
+
+        // the expiry must be in the future
+        for expiry in [env.block.time, env.block.time.minus_seconds(1)] {
+            let err =
+                propose_transfer(&mut deps.storage, &env, &alice, &bob, Some(expiry)).unwrap_err();
+            assert_eq!(err, StdError::generic_err("Expiry must be in the future"));
+        }
+
+        let expiry = env.block.time.plus_seconds(60);
+        propose_transfer(&mut deps.storage, &env, &alice, &bob, Some(expiry)).unwrap();
+
+        env.block.time = expiry;
+        let err = accept(&mut deps.storage, &env, &bob).unwrap_err();
+        assert_eq!(err, StdError::generic_err("Ownership transfer expired"));
+        assert_owner(&deps.storage, &alice).unwrap();
+
+        // accepting right before the expiry works
+        env.block.time = expiry.minus_nanos(1);
+        accept(&mut deps.storage, &env, &bob).unwrap();
+        assert_owner(&deps.storage, &bob).unwrap();
+    }
+
+    #[test]
+    fn propose_transfer_overwrites_previous_proposal() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        let carol = deps.api.addr_make("carol");
+        initialize(&mut deps.storage, &alice).unwrap();
+
+        let expiry = env.block.time.plus_seconds(60);
+        propose_transfer(&mut deps.storage, &env, &alice, &bob, Some(expiry)).unwrap();
+        // the second proposal replaces the pending owner and the expiry
+        propose_transfer(&mut deps.storage, &env, &alice, &carol, None).unwrap();
+        assert_eq!(
+            query_ownership(&deps.storage).unwrap(),
+            OwnershipResponse {
+                owner: Some(alice.clone()),
+                pending_owner: Some(carol.clone()),
+                pending_expiry: None,
+            }
+        );
+
+        let err = accept(&mut deps.storage, &env, &bob).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(format!("Address {bob} is not the pending owner"))
+        );
+        accept(&mut deps.storage, &env, &carol).unwrap();
+        assert_owner(&deps.storage, &carol).unwrap();
+    }
+
+    #[test]
+    fn renounce_works() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let alice = deps.api.addr_make("alice");
+        let bob = deps.api.addr_make("bob");
+        initialize(&mut deps.storage, &alice).unwrap();
+        propose_transfer(&mut deps.storage, &env, &alice, &bob, None).unwrap();
+
+        // only the owner can renounce
+        renounce(&mut deps.storage, &bob).unwrap_err();
+
+        let event = renounce(&mut deps.storage, &alice).unwrap();
+        assert_eq!(
+            event,
+            Event::new("ownership")
+                .add_attribute("action", "renounce")
+                .add_attribute("previous_owner", alice.as_str())
+        );
+        assert_eq!(
+            query_ownership(&deps.storage).unwrap(),
+            OwnershipResponse {
+                owner: None,
+                pending_owner: None,
+                pending_expiry: None,
+            }
+        );
+
+        // nobody is the owner anymore
+        for addr in [&alice, &bob] {
+            let err = assert_owner(&deps.storage, addr).unwrap_err();
+            assert_eq!(err, StdError::generic_err("Ownership was renounced"));
+        }
+        // the pending transfer is cancelled
+        let err = accept(&mut deps.storage, &env, &bob).unwrap_err();
+        assert_eq!(err, StdError::generic_err("No ownership transfer pending"));
+        // and ownership cannot be initialized again
+        initialize(&mut deps.storage, &bob).unwrap_err();
+    }
+}
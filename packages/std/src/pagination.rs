@@ -1,12 +1,406 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "iterator")]
+use crate::prelude::*;
+#[cfg(feature = "iterator")]
+use crate::storage_keys::range_with_prefix;
 use crate::Binary;
+#[cfg(feature = "iterator")]
+use crate::{Order, Record, StdError, StdResult, Storage};
 
 /// Simplified version of the PageRequest type for pagination from the cosmos-sdk
 #[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq, JsonSchema)]
 pub struct PageRequest {
+    /// The key to start the page at, usually the `next_key` of the previous response.
+    /// `None` starts at the beginning (or the end if `reverse` is set).
     pub key: Option<Binary>,
     pub limit: u32,
+    /// Returns the results in descending order if set
+    #[serde(default)]
     pub reverse: bool,
 }
+
+impl PageRequest {
+    /// Requests the results in descending order.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_std::PageRequest;
+    /// let request = PageRequest {
+    ///     limit: 10,
+    ///     ..Default::default()
+    /// }
+    /// .reverse();
+    /// assert!(request.reverse);
+    /// ```
+    pub fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Continues the pagination at the given key, usually the `next_key` of the previous response.
+    pub fn with_key(mut self, key: Binary) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+/// Loads one page of the key/value pairs in the given range and maps them using `f`.
+///
+/// Returns the mapped items and the key to continue the pagination at, which is `None`
+/// if there are no more items.
+///
+/// In contrast to [`Storage::range`], both bounds are _exclusive_ and relative to the
+/// iteration `order`: the page starts right after `start` and ends right before `end`.
+/// I.e. for [`Order::Ascending`] the keys are greater than `start` and less than `end`,
+/// for [`Order::Descending`] they are less than `start` and greater than `end`.
+/// This way, the returned key can be passed as `start` for the next page in both orders.
+///
+/// At most `limit` items are loaded, which keeps the gas cost of a call bounded. Make sure
+/// to cap limits provided by users. Errors if `limit` is 0, since that would not make progress.
+/// If `f` fails, its error is returned unchanged. Since `f` receives the key, it can add the
+/// key to the error if needed.
+///
+/// ## Examples
+///
+/// ```
+/// # use cosmwasm_std::{paginate_range, MemoryStorage, Order, StdResult, Storage};
+/// let mut storage = MemoryStorage::new();
+/// for key in [b"a", b"b", b"c"] {
+///     storage.set(key, b"1");
+/// }
+///
+/// let (page, next) = paginate_range(&storage, None, None, Order::Ascending, 2, |k, _| {
+///     Ok(k.to_vec())
+/// })?;
+/// assert_eq!(page, [b"a", b"b"]);
+/// assert_eq!(next.as_deref(), Some(b"b".as_slice()));
+///
+/// let (page, next) =
+///     paginate_range(&storage, next.as_deref(), None, Order::Ascending, 2, |k, _| {
+///         Ok(k.to_vec())
+///     })?;
+/// assert_eq!(page, [b"c"]);
+/// assert_eq!(next, None);
+/// # StdResult::Ok(())
+/// ```
+#[cfg(feature = "iterator")]
+pub fn paginate_range<T>(
+    storage: &dyn Storage,
+    start: Option<&[u8]>,
+    end: Option<&[u8]>,
+    order: Order,
+    limit: usize,
+    f: impl FnMut(&[u8], &[u8]) -> StdResult<T>,
+) -> StdResult<(Vec<T>, Option<Vec<u8>>)> {
+    let (min, max) = range_bounds(start, end, order);
+    paginate(storage.range(min.as_deref(), max, order), limit, f)
+}
+
+/// Loads one page of the key/value pairs whose key starts with `prefix` and maps them using `f`.
+///
+/// This works like [`paginate_range`], but `start`, `end`, the keys passed to `f` and the
+/// returned key are relative to the prefix (see
+/// [`range_with_prefix`](crate::storage_keys::range_with_prefix)).
+#[cfg(feature = "iterator")]
+pub fn paginate_prefix<T>(
+    storage: &dyn Storage,
+    prefix: &[u8],
+    start: Option<&[u8]>,
+    end: Option<&[u8]>,
+    order: Order,
+    limit: usize,
+    f: impl FnMut(&[u8], &[u8]) -> StdResult<T>,
+) -> StdResult<(Vec<T>, Option<Vec<u8>>)> {
+    let (min, max) = range_bounds(start, end, order);
+    paginate(
+        range_with_prefix(storage, prefix, min.as_deref(), max, order),
+        limit,
+        f,
+    )
+}
+
+/// Converts the exclusive bounds in iteration order into the bounds of [`Storage::range`]
+/// (inclusive minimum, exclusive maximum).
+#[cfg(feature = "iterator")]
+fn range_bounds<'a>(
+    start: Option<&'a [u8]>,
+    end: Option<&'a [u8]>,
+    order: Order,
+) -> (Option<Vec<u8>>, Option<&'a [u8]>) {
+    // The smallest key greater than `key` is `key` followed by a zero byte
+    let successor = |key: &[u8]| [key, &[0]].concat();
+    match order {
+        Order::Ascending => (start.map(successor), end),
+        Order::Descending => (end.map(successor), start),
+    }
+}
+
+#[cfg(feature = "iterator")]
+fn paginate<T>(
+    mut records: impl Iterator<Item = Record>,
+    limit: usize,
+    mut f: impl FnMut(&[u8], &[u8]) -> StdResult<T>,
+) -> StdResult<(Vec<T>, Option<Vec<u8>>)> {
+    if limit == 0 {
+        return Err(StdError::generic_err(
+            "Pagination limit must be greater than 0",
+        ));
+    }
+
+    let mut items = Vec::new();
+    let mut last_key = None;
+    for (key, value) in records.by_ref().take(limit) {
+        items.push(f(&key, &value)?);
+        last_key = Some(key);
+    }
+
+    // Only return a key if there is another page, such that the last page is not empty
+    let next_key = if records.next().is_some() {
+        last_key
+    } else {
+        None
+    };
+    Ok((items, next_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "iterator")]
+    use crate::MemoryStorage;
+    use crate::{from_json, to_json_string};
+    #[cfg(feature = "iterator")]
+    use proptest::{prop_assert_eq, property_test};
+
+    #[test]
+    fn builder_methods_work() {
+        let request = PageRequest {
+            key: None,
+            limit: 10,
+            reverse: false,
+        };
+        assert_eq!(
+            request.clone().reverse().with_key(Binary::from(b"foo")),
+            PageRequest {
+                key: Some(Binary::from(b"foo")),
+                limit: 10,
+                reverse: true,
+            }
+        );
+        assert_eq!(request.clone().with_key(Binary::from(b"bar")).limit, 10);
+        assert!(!request.with_key(Binary::from(b"bar")).reverse);
+    }
+
+    #[test]
+    fn serialization_works() {
+        let request = PageRequest {
+            key: None,
+            limit: 10,
+            reverse: false,
+        };
+        let json = to_json_string(&request).unwrap();
+        assert_eq!(json, r#"{"key":null,"limit":10,"reverse":false}"#);
+        assert_eq!(from_json::<PageRequest>(&json).unwrap(), request);
+
+        let request = request.reverse().with_key(Binary::from(b"foo"));
+        let json = to_json_string(&request).unwrap();
+        assert_eq!(json, r#"{"key":"Zm9v","limit":10,"reverse":true}"#);
+        assert_eq!(from_json::<PageRequest>(&json).unwrap(), request);
+
+        // key and reverse can be omitted
+        let request: PageRequest = from_json(r#"{"limit":5}"#).unwrap();
+        assert_eq!(
+            request,
+            PageRequest {
+                key: None,
+                limit: 5,
+                reverse: false,
+            }
+        );
+    }
+
+    #[test]
+    fn page_request_has_defined_json_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(PageRequest)).unwrap();
+        assert_eq!(schema["title"], "PageRequest");
+        assert_eq!(schema["required"], serde_json::json!(["limit"]));
+        assert_eq!(schema["properties"]["reverse"]["type"], "boolean");
+        assert_eq!(schema["properties"]["reverse"]["default"], false);
+    }
+
+    #[cfg(feature = "iterator")]
+    fn storage_with(keys: &[&[u8]]) -> MemoryStorage {
+        let mut storage = MemoryStorage::new();
+        for key in keys {
+            storage.set(key, &[key.len() as u8]);
+        }
+        storage
+    }
+
+    #[cfg(feature = "iterator")]
+    fn keys(
+        storage: &dyn Storage,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+        limit: usize,
+    ) -> (Vec<Vec<u8>>, Option<Vec<u8>>) {
+        paginate_range(storage, start, end, order, limit, |key, _| Ok(key.to_vec())).unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn paginate_range_works() {
+        let storage = storage_with(&[b"a", b"b", b"c", b"d"]);
+
+        // ascending
+        let (page, next) = keys(&storage, None, None, Order::Ascending, 3);
+        assert_eq!(page, [b"a", b"b", b"c"]);
+        assert_eq!(next.as_deref(), Some(b"c".as_slice()));
+        let (page, next) = keys(&storage, next.as_deref(), None, Order::Ascending, 3);
+        assert_eq!(page, [b"d"]);
+        assert_eq!(next, None);
+
+        // descending
+        let (page, next) = keys(&storage, None, None, Order::Descending, 2);
+        assert_eq!(page, [b"d", b"c"]);
+        assert_eq!(next.as_deref(), Some(b"c".as_slice()));
+        let (page, next) = keys(&storage, next.as_deref(), None, Order::Descending, 2);
+        assert_eq!(page, [b"b", b"a"]);
+        assert_eq!(next, None);
+
+        // both bounds are exclusive
+        let (page, next) = keys(&storage, Some(b"a"), Some(b"d"), Order::Ascending, 10);
+        assert_eq!(page, [b"b", b"c"]);
+        assert_eq!(next, None);
+        let (page, next) = keys(&storage, Some(b"d"), Some(b"a"), Order::Descending, 10);
+        assert_eq!(page, [b"c", b"b"]);
+        assert_eq!(next, None);
+
+        // a page ending exactly at the last item has no next key
+        let (page, next) = keys(&storage, None, None, Order::Ascending, 4);
+        assert_eq!(page.len(), 4);
+        assert_eq!(next, None);
+
+        // empty range
+        let (page, next) = keys(&storage, Some(b"d"), None, Order::Ascending, 4);
+        assert_eq!(page, Vec::<Vec<u8>>::new());
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn paginate_range_fails_for_zero_limit() {
+        let storage = storage_with(&[b"a"]);
+        let err = paginate_range(&storage, None, None, Order::Ascending, 0, |key, _| {
+            Ok(key.to_vec())
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Pagination limit must be greater than 0"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn paginate_range_returns_error_of_failing_entry() {
+        let storage = storage_with(&[b"a", b"bb", b"c"]);
+        let err = paginate_range(&storage, None, None, Order::Ascending, 10, |key, value| {
+            if value == [2] {
+                Err(StdError::not_found("thing"))
+            } else {
+                Ok(key.to_vec())
+            }
+        })
+        .unwrap_err();
+        match err {
+            StdError::NotFound { kind, .. } => assert_eq!(kind, "thing"),
+            err => panic!("Unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn paginate_prefix_works() {
+        let storage = storage_with(&[b"aa", b"ab", b"ac", b"b", b"\xff"]);
+
+        let (page, next) = paginate_prefix(
+            &storage,
+            b"a",
+            None,
+            None,
+            Order::Ascending,
+            2,
+            |key, value| Ok((key.to_vec(), value.to_vec())),
+        )
+        .unwrap();
+        assert_eq!(page, [(b"a".to_vec(), vec![2]), (b"b".to_vec(), vec![2])]);
+        assert_eq!(next.as_deref(), Some(b"b".as_slice()));
+
+        let (page, next) = paginate_prefix(
+            &storage,
+            b"a",
+            next.as_deref(),
+            None,
+            Order::Ascending,
+            2,
+            |key, _| Ok(key.to_vec()),
+        )
+        .unwrap();
+        assert_eq!(page, [b"c"]);
+        assert_eq!(next, None);
+
+        let (page, next) = paginate_prefix(
+            &storage,
+            b"a",
+            Some(b"c"),
+            None,
+            Order::Descending,
+            5,
+            |key, _| Ok(key.to_vec()),
+        )
+        .unwrap();
+        assert_eq!(page, [b"b", b"a"]);
+        assert_eq!(next, None);
+    }
+
+    /// Loads all pages and concatenates them
+    #[cfg(feature = "iterator")]
+    fn all_pages(storage: &dyn Storage, order: Order, limit: usize) -> Vec<Record> {
+        let mut out = Vec::new();
+        let mut start = None;
+        loop {
+            let (page, next) =
+                paginate_range(storage, start.as_deref(), None, order, limit, |k, v| {
+                    Ok((k.to_vec(), v.to_vec()))
+                })
+                .unwrap();
+            assert!(page.len() <= limit);
+            out.extend(page);
+            match next {
+                Some(next) => start = Some(next),
+                None => return out,
+            }
+        }
+    }
+
+    #[cfg(feature = "iterator")]
+    #[property_test]
+    fn paginate_range_pages_cover_whole_range(entries: Vec<(Vec<u8>, u8)>, limit: u8) {
+        let mut storage = MemoryStorage::new();
+        for (key, value) in entries {
+            // empty keys and values are not allowed
+            storage.set(&[key.as_slice(), &[0x80]].concat(), &[value]);
+        }
+        let limit = usize::from(limit % 10) + 1;
+
+        for order in [Order::Ascending, Order::Descending] {
+            let expected: Vec<Record> = storage.range(None, None, order).collect();
+            prop_assert_eq!(all_pages(&storage, order, limit), expected);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! A minimal protobuf encoder and decoder for the few messages we need to handle
+//! without pulling a protobuf library into every contract.
+
+use crate::prelude::*;
+use crate::{StdError, StdResult};
+
+/// Protobuf wire type for varint fields
+const WIRE_TYPE_VARINT: u64 = 0;
+/// Protobuf wire type for 64-bit fields
+const WIRE_TYPE_I64: u64 = 1;
+/// Protobuf wire type for length-delimited fields (bytes, string, messages)
+const WIRE_TYPE_LEN: u64 = 2;
+/// Protobuf wire type for 32-bit fields
+const WIRE_TYPE_I32: u64 = 5;
+
+/// Appends a length-delimited field (bytes, string or message) to `out`
+pub(crate) fn encode_bytes_field(out: &mut Vec<u8>, field: u64, value: &[u8]) {
+    encode_varint(out, (field << 3) | WIRE_TYPE_LEN);
+    encode_varint(out, value.len() as u64);
+    out.extend_from_slice(value);
+}
+
+/// Calls `f` with the field number and value of every length-delimited field
+/// (bytes, string or message) of the given message in order of appearance.
+///
+/// Fields of other wire types are skipped. Errors in the encoding are reported
+/// as [`StdError::ParseErr`] with the given `target_type`.
+pub(crate) fn decode_bytes_fields<'a>(
+    data: &'a [u8],
+    target_type: &str,
+    mut f: impl FnMut(u64, &'a [u8]) -> StdResult<()>,
+) -> StdResult<()> {
+    let err = |msg: String| StdError::parse_err(target_type, msg);
+
+    let mut rest = data;
+    while !rest.is_empty() {
+        let key = decode_varint(&mut rest).map_err(err)?;
+        let (field, wire_type) = (key >> 3, key & 0x07);
+        match wire_type {
+            WIRE_TYPE_VARINT => {
+                decode_varint(&mut rest).map_err(err)?;
+            }
+            WIRE_TYPE_I64 => {
+                take(&mut rest, 8).map_err(err)?;
+            }
+            WIRE_TYPE_LEN => {
+                let len = decode_varint(&mut rest).map_err(err)?;
+                let len = usize::try_from(len).map_err(|_| err("length too large".to_string()))?;
+                let value = take(&mut rest, len).map_err(err)?;
+                f(field, value)?;
+            }
+            WIRE_TYPE_I32 => {
+                take(&mut rest, 4).map_err(err)?;
+            }
+            _ => return Err(err(format!("unsupported wire type {wire_type}"))),
+        }
+    }
+    Ok(())
+}
+
+fn encode_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Reads a varint from the start of `data` and advances it
+fn decode_varint(data: &mut &[u8]) -> Result<u64, String> {
+    let mut value = 0u64;
+    for (i, byte) in data.iter().enumerate().take(10) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            *data = &data[i + 1..];
+            return Ok(value);
+        }
+    }
+    Err("invalid varint".to_string())
+}
+
+/// Takes `len` bytes from the start of `data` and advances it
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+    if data.len() < len {
+        return Err("unexpected end of data".to_string());
+    }
+    let (value, rest) = data.split_at(len);
+    *data = rest;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn varint_round_trip_works() {
+        for value in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut out = Vec::new();
+            encode_varint(&mut out, value);
+            let mut data = out.as_slice();
+            assert_eq!(decode_varint(&mut data).unwrap(), value);
+            assert!(data.is_empty());
+        }
+
+        let mut out = Vec::new();
+        encode_varint(&mut out, 300);
+        assert_eq!(out, [0xac, 0x02]);
+    }
+
+    #[test]
+    fn decode_bytes_fields_works() {
+        let mut data = Vec::new();
+        encode_bytes_field(&mut data, 1, b"foo");
+        // varint field 2 = 150
+        data.extend_from_slice(&[0x10, 0x96, 0x01]);
+        // 64-bit field 3
+        data.extend_from_slice(&[0x19, 1, 2, 3, 4, 5, 6, 7, 8]);
+        // 32-bit field 4
+        data.extend_from_slice(&[0x25, 1, 2, 3, 4]);
+        encode_bytes_field(&mut data, 1000, b"");
+
+        let mut fields = Vec::new();
+        decode_bytes_fields(&data, "Test", |field, value| {
+            fields.push((field, value.to_vec()));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(fields, [(1, b"foo".to_vec()), (1000, vec![])]);
+    }
+
+    #[test]
+    fn decode_bytes_fields_fails_for_invalid_data() {
+        let cases: [(&[u8], &str); 4] = [
+            (&[0x0a, 0x05, b'a'], "unexpected end of data"),
+            (&[0x0a], "invalid varint"),
+            (&[0x0a, 0xff], "invalid varint"),
+            (&[0x0b], "unsupported wire type 3"),
+        ];
+        for (data, msg) in cases {
+            let err = decode_bytes_fields(data, "Test", |_, _| Ok(())).unwrap_err();
+            match err {
+                StdError::ParseErr {
+                    target_type,
+                    msg: actual,
+                    ..
+                } => {
+                    assert_eq!(target_type, "Test");
+                    assert_eq!(actual, msg);
+                }
+                err => panic!("Unexpected error: {err:?}"),
+            }
+        }
+    }
+}
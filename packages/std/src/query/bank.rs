@@ -35,6 +35,14 @@ pub enum BankQuery {
     /// Return value is AllDenomMetadataResponse
     #[cfg(feature = "cosmwasm_1_3")]
     AllDenomMetadata { pagination: Option<PageRequest> },
+    /// This calls into the native bank module for all denominations held by the given address.
+    /// In contrast to `AllBalances`, the results are paginated.
+    /// Return value is PaginatedBalancesResponse
+    #[cfg(feature = "cosmwasm_2_3")]
+    AllBalancesPaginated {
+        address: String,
+        pagination: Option<PageRequest>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -104,6 +112,25 @@ impl_response_constructor!(
 
 impl QueryResponseType for AllDenomMetadataResponse {}
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub struct PaginatedBalancesResponse {
+    /// The non-zero coins held by the account, ordered by denom.
+    pub balances: Vec<Coin>,
+    /// The key to pass to the next query to get the next page.
+    /// This is `None` if there are no more results.
+    pub next_key: Option<Binary>,
+}
+
+impl_response_constructor!(
+    PaginatedBalancesResponse,
+    balances: Vec<Coin>,
+    next_key: Option<Binary>
+);
+
+impl QueryResponseType for PaginatedBalancesResponse {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -24,6 +24,11 @@ pub enum DistributionQuery {
     /// See <https://github.com/cosmos/cosmos-sdk/blob/b0acf60e6c39f7ab023841841fc0b751a12c13ff/proto/cosmos/distribution/v1beta1/query.proto#L202-L210>
     #[cfg(feature = "cosmwasm_1_4")]
     DelegatorValidators { delegator_address: String },
+    /// Returns the funds currently held by the community pool.
+    ///
+    /// See <https://github.com/cosmos/cosmos-sdk/blob/v0.50.9/proto/cosmos/distribution/v1beta1/query.proto>
+    #[cfg(feature = "cosmwasm_2_3")]
+    CommunityPool {},
 }
 
 /// See <https://github.com/cosmos/cosmos-sdk/blob/c74e2887b0b73e81d48c2f33e6b1020090089ee0/proto/cosmos/distribution/v1beta1/query.proto#L232-L240>
@@ -113,3 +118,15 @@ pub struct DelegatorValidatorsResponse {
 
 impl_response_constructor!(DelegatorValidatorsResponse, validators: Vec<String>);
 impl QueryResponseType for DelegatorValidatorsResponse {}
+
+/// See <https://github.com/cosmos/cosmos-sdk/blob/v0.50.9/proto/cosmos/distribution/v1beta1/query.proto>
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub struct CommunityPoolResponse {
+    /// The funds in the community pool. This usually contains fractional amounts.
+    pub pool: Vec<DecCoin>,
+}
+
+impl_response_constructor!(CommunityPoolResponse, pool: Vec<DecCoin>);
+impl QueryResponseType for CommunityPoolResponse {}
@@ -68,6 +68,18 @@ pub enum QueryRequest<C = Empty> {
     Wasm(WasmQuery),
     #[cfg(feature = "cosmwasm_2_0")]
     Grpc(GrpcQuery),
+    /// Batches multiple queries into a single call to the host.
+    ///
+    /// The host processes all sub-queries atomically (i.e. without re-entrancy between them)
+    /// and responds with a JSON encoded `Vec<QuerierResult>` with one entry per sub-query,
+    /// in the same order as the requests.
+    ///
+    /// Nested `Multi` queries are not supported. The host may also limit the number of
+    /// sub-queries per batch (64 in cosmwasm-vm).
+    ///
+    /// Use [`query_multi`](crate::QuerierWrapper::query_multi) to send this query.
+    #[cfg(feature = "cosmwasm_2_3")]
+    Multi(Vec<QueryRequest<C>>),
 }
 
 /// Queries the chain using a grpc query.
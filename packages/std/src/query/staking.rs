@@ -2,7 +2,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::prelude::*;
-use crate::{Addr, Coin, Decimal};
+use crate::{Addr, Coin, Decimal, Timestamp};
 
 use super::query_response::QueryResponseType;
 
@@ -32,6 +32,12 @@ pub enum StakingQuery {
         /// The validator's address (e.g. (e.g. cosmosvaloper1...))
         address: String,
     },
+    /// Returns all unbonding delegations of the given delegator, i.e. tokens that were
+    /// undelegated but are not yet released because the unbonding period did not pass yet.
+    ///
+    /// The query response type is `UnbondingDelegationsResponse`.
+    #[cfg(feature = "cosmwasm_2_3")]
+    UnbondingDelegations { delegator: String },
 }
 
 /// BondedDenomResponse is data format returned from StakingRequest::BondedDenom query
@@ -146,6 +152,63 @@ impl FullDelegation {
     }
 }
 
+/// The data format returned from StakingRequest::UnbondingDelegations query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[non_exhaustive]
+pub struct UnbondingDelegationsResponse {
+    pub delegations: Vec<UnbondingDelegation>,
+}
+
+impl QueryResponseType for UnbondingDelegationsResponse {}
+
+impl_response_constructor!(
+    UnbondingDelegationsResponse,
+    delegations: Vec<UnbondingDelegation>
+);
+
+/// An amount of tokens that is currently unbonding from a validator.
+///
+/// Instances are created in the querier.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[non_exhaustive]
+pub struct UnbondingDelegation {
+    pub delegator: Addr,
+    /// A validator address (e.g. cosmosvaloper1...)
+    pub validator_address: String,
+    /// How much is unbonding
+    pub amount: Coin,
+    /// The time at which the tokens are released to the delegator
+    pub completion_time: Timestamp,
+}
+
+impl_response_constructor!(
+    UnbondingDelegation,
+    delegator: Addr,
+    validator_address: String,
+    amount: Coin,
+    completion_time: Timestamp
+);
+
+impl UnbondingDelegation {
+    /// Creates a new unbonding delegation.
+    ///
+    /// If fields get added to the [`UnbondingDelegation`] struct in the future, this constructor will
+    /// provide default values for them, but these default values may not be sensible.
+    pub fn create(
+        delegator: Addr,
+        validator_address: String,
+        amount: Coin,
+        completion_time: Timestamp,
+    ) -> Self {
+        Self {
+            delegator,
+            validator_address,
+            amount,
+            completion_time,
+        }
+    }
+}
+
 /// The data format returned from StakingRequest::AllValidators query
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[non_exhaustive]
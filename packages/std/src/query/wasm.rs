@@ -30,6 +30,17 @@ pub enum WasmQuery {
     /// Returns a [`CodeInfoResponse`] with metadata of the code
     #[cfg(feature = "cosmwasm_1_2")]
     CodeInfo { code_id: u64 },
+    /// Returns a [`ContractsByCodeResponse`] with the addresses of the contracts
+    /// instantiated from the given code
+    #[cfg(feature = "cosmwasm_2_3")]
+    ContractsByCode {
+        code_id: u64,
+        /// If set, only contracts after this address are returned
+        start_after: Option<String>,
+        /// The maximum number of contracts to return. The chain may apply
+        /// a default and an upper limit.
+        limit: Option<u32>,
+    },
 }
 
 #[non_exhaustive]
@@ -83,6 +94,17 @@ impl_response_constructor!(
 
 impl QueryResponseType for CodeInfoResponse {}
 
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ContractsByCodeResponse {
+    /// The addresses of the contracts in the order of their instantiation
+    pub contracts: Vec<String>,
+}
+
+impl_response_constructor!(ContractsByCodeResponse, contracts: Vec<String>);
+
+impl QueryResponseType for ContractsByCodeResponse {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,6 +133,32 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "cosmwasm_2_3")]
+    fn wasm_query_contracts_by_code_serialization() {
+        let query = WasmQuery::ContractsByCode {
+            code_id: 70,
+            start_after: Some("aabbccdd456".to_string()),
+            limit: Some(10),
+        };
+        let json = to_json_binary(&query).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&json),
+            r#"{"contracts_by_code":{"code_id":70,"start_after":"aabbccdd456","limit":10}}"#,
+        );
+
+        let query = WasmQuery::ContractsByCode {
+            code_id: 70,
+            start_after: None,
+            limit: None,
+        };
+        let json = to_json_binary(&query).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&json),
+            r#"{"contracts_by_code":{"code_id":70,"start_after":null,"limit":null}}"#,
+        );
+    }
+
     #[test]
     fn contract_info_response_serialization() {
         let response = ContractInfoResponse {
@@ -146,4 +194,16 @@ mod tests {
             r#"{"code_id":67,"creator":"jane","checksum":"f7bb7b18fb01bbf425cf4ed2cd4b7fb26a019a7fc75a4dc87e8a0b768c501f00"}"#,
         );
     }
+
+    #[test]
+    fn contracts_by_code_response_serialization() {
+        let response = ContractsByCodeResponse {
+            contracts: vec!["jane".to_string(), "joe".to_string()],
+        };
+        let json = to_json_binary(&response).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&json),
+            r#"{"contracts":["jane","joe"]}"#,
+        );
+    }
 }
@@ -0,0 +1,178 @@
+//! Declaring the capabilities a contract requires in one place.
+//!
+//! See [`declare_contract_requirements!`](crate::declare_contract_requirements) for details.
+
+/// Whether the cosmwasm-std feature for each built-in capability is enabled.
+///
+/// The feature flags are evaluated when compiling cosmwasm-std, so those can be used
+/// by macros expanded in contract code.
+#[doc(hidden)]
+#[allow(non_upper_case_globals)]
+pub mod capability_features {
+    pub const iterator: bool = cfg!(feature = "iterator");
+    pub const staking: bool = cfg!(feature = "staking");
+    pub const stargate: bool = cfg!(feature = "stargate");
+    pub const eureka: bool = cfg!(feature = "eureka");
+    pub const cosmwasm_1_1: bool = cfg!(feature = "cosmwasm_1_1");
+    pub const cosmwasm_1_2: bool = cfg!(feature = "cosmwasm_1_2");
+    pub const cosmwasm_1_3: bool = cfg!(feature = "cosmwasm_1_3");
+    pub const cosmwasm_1_4: bool = cfg!(feature = "cosmwasm_1_4");
+    pub const cosmwasm_2_0: bool = cfg!(feature = "cosmwasm_2_0");
+    pub const cosmwasm_2_1: bool = cfg!(feature = "cosmwasm_2_1");
+    pub const cosmwasm_2_2: bool = cfg!(feature = "cosmwasm_2_2");
+    pub const cosmwasm_2_3: bool = cfg!(feature = "cosmwasm_2_3");
+}
+
+/// Declares all capabilities a contract requires in one place.
+///
+/// The [built-in capabilities](https://github.com/CosmWasm/cosmwasm/blob/main/docs/CAPABILITIES-BUILT-IN.md)
+/// (`iterator`, `staking`, `stargate`, `eureka` and `cosmwasm_X_Y`) are exported by cosmwasm-std
+/// as soon as the corresponding feature is enabled. For those, this macro checks at compile time
+/// that the feature is enabled and fails to compile otherwise. This catches a declared capability
+/// that is not backed by the `Cargo.toml` of the contract.
+///
+/// All other capabilities (e.g. chain specific ones like `token_factory`) are exported as
+/// `requires_*` marker functions by this macro. See
+/// [CAPABILITIES.md](https://github.com/CosmWasm/cosmwasm/blob/main/docs/CAPABILITIES.md)
+/// for how the chain uses them.
+///
+/// ## Limits
+///
+/// - Cargo features are unified across the dependency graph. If any dependency enables a feature
+///   of cosmwasm-std, the check passes even if the contract itself does not enable it.
+/// - Enabled features that are not declared here are not detected. Their capabilities are still
+///   exported by cosmwasm-std, so the chain sees all of them in any case.
+/// - Calls to APIs that need a capability (e.g. [`Storage::range`](crate::Storage::range)
+///   for `iterator`) cannot be tied to this declaration. Those APIs are only available with
+///   the corresponding feature, which in turn exports the capability.
+///
+/// This macro should be invoked at most once per contract, at the crate root.
+///
+/// ## Examples
+///
+/// ```
+/// # // the built-in capabilities checked here have to be enabled in the doc test build
+/// # #[cfg(feature = "iterator")]
+/// # mod contract {
+/// use cosmwasm_std::declare_contract_requirements;
+///
+/// declare_contract_requirements!(iterator, token_factory);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! declare_contract_requirements {
+    ($($capability:ident),* $(,)?) => {
+        $(
+            $crate::__internal__declare_requirement!($capability);
+        )*
+    };
+}
+
+/// Checks the feature of a built-in capability or exports the marker of a custom one.
+/// Only meant to be used by [`declare_contract_requirements!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __internal__declare_requirement {
+    (iterator) => {
+        $crate::__internal__require_feature!(iterator);
+    };
+    (staking) => {
+        $crate::__internal__require_feature!(staking);
+    };
+    (stargate) => {
+        $crate::__internal__require_feature!(stargate);
+    };
+    (eureka) => {
+        $crate::__internal__require_feature!(eureka);
+    };
+    (cosmwasm_1_1) => {
+        $crate::__internal__require_feature!(cosmwasm_1_1);
+    };
+    (cosmwasm_1_2) => {
+        $crate::__internal__require_feature!(cosmwasm_1_2);
+    };
+    (cosmwasm_1_3) => {
+        $crate::__internal__require_feature!(cosmwasm_1_3);
+    };
+    (cosmwasm_1_4) => {
+        $crate::__internal__require_feature!(cosmwasm_1_4);
+    };
+    (cosmwasm_2_0) => {
+        $crate::__internal__require_feature!(cosmwasm_2_0);
+    };
+    (cosmwasm_2_1) => {
+        $crate::__internal__require_feature!(cosmwasm_2_1);
+    };
+    (cosmwasm_2_2) => {
+        $crate::__internal__require_feature!(cosmwasm_2_2);
+    };
+    (cosmwasm_2_3) => {
+        $crate::__internal__require_feature!(cosmwasm_2_3);
+    };
+    ($capability:ident) => {
+        const _: () = {
+            // Same as the markers in cosmwasm-std's exports, which are only compiled for Wasm
+            #[cfg(target_arch = "wasm32")]
+            #[export_name = concat!("requires_", stringify!($capability))]
+            extern "C" fn requires() {}
+        };
+    };
+}
+
+/// Only meant to be used by [`declare_contract_requirements!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __internal__require_feature {
+    ($capability:ident) => {
+        const _: () = ::core::assert!(
+            $crate::__internal::capability_features::$capability,
+            concat!(
+                "The capability `",
+                stringify!($capability),
+                "` is declared, but the `",
+                stringify!($capability),
+                "` feature of cosmwasm-std is not enabled."
+            )
+        );
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::capability_features;
+
+    // Expands in a test build. Custom capabilities are only exported for Wasm,
+    // so this only checks that the expansion compiles.
+    crate::declare_contract_requirements!(token_factory, osmosis,);
+    crate::declare_contract_requirements!();
+
+    #[cfg(feature = "iterator")]
+    crate::declare_contract_requirements!(iterator);
+    #[cfg(feature = "stargate")]
+    crate::declare_contract_requirements!(stargate, custom_capability);
+    #[cfg(feature = "cosmwasm_2_0")]
+    crate::declare_contract_requirements!(cosmwasm_1_1, cosmwasm_1_4, cosmwasm_2_0);
+
+    #[test]
+    fn capability_features_match_enabled_features() {
+        let values = [
+            capability_features::iterator,
+            capability_features::staking,
+            capability_features::stargate,
+            capability_features::eureka,
+            capability_features::cosmwasm_1_1,
+            capability_features::cosmwasm_2_0,
+            capability_features::cosmwasm_2_3,
+        ];
+        let expected = [
+            cfg!(feature = "iterator"),
+            cfg!(feature = "staking"),
+            cfg!(feature = "stargate"),
+            cfg!(feature = "eureka"),
+            cfg!(feature = "cosmwasm_1_1"),
+            cfg!(feature = "cosmwasm_2_0"),
+            cfg!(feature = "cosmwasm_2_3"),
+        ];
+        assert_eq!(values, expected);
+    }
+}
@@ -61,6 +61,90 @@ impl<S> ContractResult<S> {
     pub fn is_err(&self) -> bool {
         matches!(self, ContractResult::Err(_))
     }
+
+    /// Converts the result into an `Option` of the success value, discarding the error.
+    ///
+    /// ```
+    /// # use cosmwasm_std::ContractResult;
+    /// assert_eq!(ContractResult::Ok(7).ok(), Some(7));
+    /// assert_eq!(ContractResult::<u32>::Err("broken".to_string()).ok(), None);
+    /// ```
+    pub fn ok(self) -> Option<S> {
+        self.into_result().ok()
+    }
+
+    /// Converts the result into an `Option` of the error message, discarding the success value.
+    ///
+    /// ```
+    /// # use cosmwasm_std::ContractResult;
+    /// assert_eq!(ContractResult::Ok(7).err(), None);
+    /// assert_eq!(
+    ///     ContractResult::<u32>::Err("broken".to_string()).err(),
+    ///     Some("broken".to_string())
+    /// );
+    /// ```
+    pub fn err(self) -> Option<String> {
+        self.into_result().err()
+    }
+
+    /// Maps the success value using the given function, leaving an error untouched.
+    ///
+    /// ```
+    /// # use cosmwasm_std::ContractResult;
+    /// assert_eq!(ContractResult::Ok(7).map(|x| x * 2), ContractResult::Ok(14));
+    /// let result: ContractResult<u32> = ContractResult::Err("broken".to_string());
+    /// assert_eq!(result.map(|x| x * 2), ContractResult::Err("broken".to_string()));
+    /// ```
+    pub fn map<U, F: FnOnce(S) -> U>(self, op: F) -> ContractResult<U> {
+        self.into_result().map(op).into()
+    }
+
+    /// Maps the error message using the given function, leaving a success value untouched.
+    ///
+    /// ```
+    /// # use cosmwasm_std::ContractResult;
+    /// let result: ContractResult<u32> = ContractResult::Err("broken".to_string());
+    /// assert_eq!(
+    ///     result.map_err(|err| format!("Sub call failed: {err}")),
+    ///     ContractResult::Err("Sub call failed: broken".to_string())
+    /// );
+    /// ```
+    pub fn map_err<E: ToString, F: FnOnce(String) -> E>(self, op: F) -> ContractResult<S> {
+        self.into_result().map_err(op).into()
+    }
+
+    /// Calls `op` with the success value and returns its result, or returns the error untouched.
+    ///
+    /// ```
+    /// # use cosmwasm_std::ContractResult;
+    /// fn half(x: u32) -> ContractResult<u32> {
+    ///     if x % 2 == 0 {
+    ///         ContractResult::Ok(x / 2)
+    ///     } else {
+    ///         ContractResult::Err(format!("{x} is odd"))
+    ///     }
+    /// }
+    /// assert_eq!(ContractResult::Ok(8).and_then(half), ContractResult::Ok(4));
+    /// assert_eq!(ContractResult::Ok(7).and_then(half), ContractResult::Err("7 is odd".to_string()));
+    /// ```
+    pub fn and_then<U, F: FnOnce(S) -> ContractResult<U>>(self, op: F) -> ContractResult<U> {
+        match self {
+            ContractResult::Ok(value) => op(value),
+            ContractResult::Err(err) => ContractResult::Err(err),
+        }
+    }
+
+    /// Returns the success value or computes a value from the error message.
+    ///
+    /// ```
+    /// # use cosmwasm_std::ContractResult;
+    /// assert_eq!(ContractResult::Ok(7).unwrap_or_else(|err| err.len()), 7);
+    /// let result: ContractResult<usize> = ContractResult::Err("broken".to_string());
+    /// assert_eq!(result.unwrap_or_else(|err| err.len()), 6);
+    /// ```
+    pub fn unwrap_or_else<F: FnOnce(String) -> S>(self, op: F) -> S {
+        self.into_result().unwrap_or_else(op)
+    }
 }
 
 impl<S: fmt::Debug> ContractResult<S> {
@@ -149,6 +233,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn combinators_work() {
+        let ok: ContractResult<u32> = ContractResult::Ok(7);
+        let err: ContractResult<u32> = ContractResult::Err("broken".to_string());
+
+        assert_eq!(ok.clone().ok(), Some(7));
+        assert_eq!(err.clone().ok(), None);
+        assert_eq!(ok.clone().err(), None);
+        assert_eq!(err.clone().err(), Some("broken".to_string()));
+
+        assert_eq!(ok.clone().map(|x| x + 1), ContractResult::Ok(8));
+        assert_eq!(err.clone().map(|x| x + 1), err);
+
+        assert_eq!(ok.clone().map_err(|e| e + "!"), ok);
+        assert_eq!(
+            err.clone().map_err(|e| e + "!"),
+            ContractResult::Err("broken!".to_string())
+        );
+
+        assert_eq!(
+            ok.clone().and_then(|x| ContractResult::Ok(x.to_string())),
+            ContractResult::Ok("7".to_string())
+        );
+        assert_eq!(
+            ok.clone()
+                .and_then(|_| ContractResult::<String>::Err("nope".to_string())),
+            ContractResult::Err("nope".to_string())
+        );
+        assert_eq!(
+            err.clone().and_then(|x| ContractResult::Ok(x.to_string())),
+            ContractResult::Err("broken".to_string())
+        );
+
+        assert_eq!(ok.unwrap_or_else(|_| 0), 7);
+        assert_eq!(err.unwrap_or_else(|e| e.len() as u32), 6);
+    }
+
+    #[test]
+    fn combinators_do_not_change_serialization() {
+        let ok: ContractResult<u32> = from_json(br#"{"ok":7}"#).unwrap();
+        let mapped = ok.map(|x| x * 2).and_then(ContractResult::Ok);
+        assert_eq!(to_json_vec(&mapped).unwrap(), br#"{"ok":14}"#);
+
+        let err: ContractResult<u32> = from_json(br#"{"error":"broken"}"#).unwrap();
+        let mapped = err.map(|x| x * 2).map_err(|e| e);
+        assert_eq!(to_json_vec(&mapped).unwrap(), br#"{"error":"broken"}"#);
+    }
+
     #[test]
     fn can_convert_from_core_result() {
         let original: Result<Response, StdError> = Ok(Response::default());
@@ -14,7 +14,9 @@ use crate::prelude::*;
 #[cfg(all(feature = "stargate", feature = "cosmwasm_1_2"))]
 use crate::Decimal;
 use crate::StdResult;
-use crate::{to_json_binary, Binary};
+use crate::{to_json_binary, Binary, Timestamp};
+#[cfg(feature = "cosmwasm_1_2")]
+use crate::{Checksum, CustomQuery, QuerierWrapper};
 
 use super::Empty;
 
@@ -88,6 +90,12 @@ pub enum CosmosMsg<T = Empty> {
     Gov(GovMsg),
     #[cfg(feature = "eureka")]
     Eureka(EurekaMsg),
+    /// Messages of the authz module, e.g. to execute messages on behalf of a granter.
+    ///
+    /// This is feature-gated at compile time with `cosmwasm_2_0` because
+    /// it relies on messages encoded as [`AnyMsg`].
+    #[cfg(feature = "cosmwasm_2_0")]
+    Authz(AuthzMsg),
 }
 
 impl<T> CosmosMsg<T> {
@@ -114,6 +122,8 @@ impl<T> CosmosMsg<T> {
             CosmosMsg::Gov(msg) => CosmosMsg::Gov(msg),
             #[cfg(feature = "eureka")]
             CosmosMsg::Eureka(msg) => CosmosMsg::Eureka(msg),
+            #[cfg(feature = "cosmwasm_2_0")]
+            CosmosMsg::Authz(msg) => CosmosMsg::Authz(msg),
         })
     }
 }
@@ -409,6 +419,36 @@ pub struct WeightedVoteOption {
     pub weight: Decimal,
 }
 
+/// The message types of the authz module.
+///
+/// See https://github.com/cosmos/cosmos-sdk/blob/v0.50.9/proto/cosmos/authz/v1beta1/tx.proto
+#[non_exhaustive]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthzMsg {
+    /// This maps directly to [MsgExec](https://github.com/cosmos/cosmos-sdk/blob/v0.50.9/proto/cosmos/authz/v1beta1/tx.proto#L52-L63).
+    /// The messages are executed on behalf of their signers, who must have granted
+    /// the `grantee` the corresponding authorizations.
+    MsgExec { grantee: String, msgs: Vec<AnyMsg> },
+    /// This maps directly to [MsgGrant](https://github.com/cosmos/cosmos-sdk/blob/v0.50.9/proto/cosmos/authz/v1beta1/tx.proto#L37-L46).
+    MsgGrant {
+        granter: String,
+        grantee: String,
+        grant: AuthzGrant,
+    },
+}
+
+/// An authorization granted in [`AuthzMsg::MsgGrant`].
+///
+/// See [Grant](https://github.com/cosmos/cosmos-sdk/blob/v0.50.9/proto/cosmos/authz/v1beta1/authz.proto#L26-L34)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AuthzGrant {
+    /// The authorization encoded as a protobuf `Any`, e.g. a `GenericAuthorization`
+    pub authorization: AnyMsg,
+    /// The time at which the grant expires. `None` means the grant does not expire.
+    pub expiration: Option<Timestamp>,
+}
+
 /// Shortcut helper as the construction of WasmMsg::Instantiate can be quite verbose in contract code.
 ///
 /// When using this, `admin` is always unset. If you need more flexibility, create the message directly.
@@ -442,6 +482,32 @@ pub fn wasm_execute(
     })
 }
 
+impl WasmMsg {
+    /// Creates a [`WasmMsg::Migrate`] after checking that the code with ID `new_code_id`
+    /// has the `expected_checksum`, using [`QuerierWrapper::assert_code_checksum`].
+    ///
+    /// Note that the check happens now, while the migration happens later when the message
+    /// is executed after this contract call returned. Code is immutable once stored, so the
+    /// checksum of a code ID cannot change in between. However, the check does not cover
+    /// anything else about the migration, e.g. whether this contract is (still) the admin
+    /// of `contract_addr` or which code the contract is running when the message executes.
+    #[cfg(feature = "cosmwasm_1_2")]
+    pub fn migrate_checked<C: CustomQuery>(
+        querier: &QuerierWrapper<C>,
+        contract_addr: impl Into<String>,
+        new_code_id: u64,
+        expected_checksum: &Checksum,
+        msg: &impl Serialize,
+    ) -> StdResult<Self> {
+        querier.assert_code_checksum(new_code_id, expected_checksum)?;
+        Ok(WasmMsg::Migrate {
+            contract_addr: contract_addr.into(),
+            new_code_id,
+            msg: to_json_binary(msg)?,
+        })
+    }
+}
+
 impl<T> From<BankMsg> for CosmosMsg<T> {
     fn from(msg: BankMsg) -> Self {
         CosmosMsg::Bank(msg)
@@ -498,6 +564,13 @@ impl<T> From<EurekaMsg> for CosmosMsg<T> {
     }
 }
 
+#[cfg(feature = "cosmwasm_2_0")]
+impl<T> From<AuthzMsg> for CosmosMsg<T> {
+    fn from(msg: AuthzMsg) -> Self {
+        CosmosMsg::Authz(msg)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -546,6 +619,43 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn bank_msg_serializes_to_correct_json() {
+        let msg = BankMsg::Send {
+            to_address: "you".to_string(),
+            amount: coins(1015, "earth"),
+        };
+        let json = crate::to_json_string(&msg).unwrap();
+        assert_eq!(
+            json,
+            r#"{"send":{"to_address":"you","amount":[{"denom":"earth","amount":"1015"}]}}"#,
+        );
+        assert_eq!(crate::from_json::<BankMsg>(&json).unwrap(), msg);
+
+        let msg = BankMsg::Burn {
+            amount: vec![coin(200, "feathers"), coin(300, "stones")],
+        };
+        let json = crate::to_json_string(&msg).unwrap();
+        assert_eq!(
+            json,
+            r#"{"burn":{"amount":[{"denom":"feathers","amount":"200"},{"denom":"stones","amount":"300"}]}}"#,
+        );
+        assert_eq!(crate::from_json::<BankMsg>(&json).unwrap(), msg);
+    }
+
+    #[test]
+    fn bank_msg_has_defined_json_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(BankMsg)).unwrap();
+        assert_eq!(schema["title"], "BankMsg");
+        let variants: Vec<_> = schema["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|variant| variant["required"][0].as_str().unwrap())
+            .collect();
+        assert_eq!(variants, ["send", "burn"]);
+    }
+
     #[test]
     fn wasm_msg_serializes_to_correct_json() {
         // Instantiate with admin
@@ -657,6 +767,107 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "cosmwasm_2_0")]
+    fn authz_msg_serializes_to_correct_json() {
+        let any = AnyMsg {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: Binary::from_base64("5yu/rQ+HrMcxH1zdga7P5hpGMLE=").unwrap(),
+        };
+
+        // MsgExec
+        let msg: CosmosMsg = AuthzMsg::MsgExec {
+            grantee: "grantee".to_string(),
+            msgs: vec![any.clone()],
+        }
+        .into();
+        let json = crate::to_json_string(&msg).unwrap();
+        assert_eq!(
+            json,
+            r#"{"authz":{"msg_exec":{"grantee":"grantee","msgs":[{"type_url":"/cosmos.bank.v1beta1.MsgSend","value":"5yu/rQ+HrMcxH1zdga7P5hpGMLE="}]}}}"#,
+        );
+        assert_eq!(crate::from_json::<CosmosMsg>(&json).unwrap(), msg);
+
+        // MsgGrant
+        let msg: CosmosMsg = AuthzMsg::MsgGrant {
+            granter: "granter".to_string(),
+            grantee: "grantee".to_string(),
+            grant: AuthzGrant {
+                authorization: AnyMsg {
+                    type_url: "/cosmos.authz.v1beta1.GenericAuthorization".to_string(),
+                    value: Binary::from_base64("ChwvY29zbW9zLmJhbmsudjFiZXRhMS5Nc2dTZW5k").unwrap(),
+                },
+                expiration: Some(Timestamp::from_seconds(1_700_000_000)),
+            },
+        }
+        .into();
+        let json = crate::to_json_string(&msg).unwrap();
+        assert_eq!(
+            json,
+            r#"{"authz":{"msg_grant":{"granter":"granter","grantee":"grantee","grant":{"authorization":{"type_url":"/cosmos.authz.v1beta1.GenericAuthorization","value":"ChwvY29zbW9zLmJhbmsudjFiZXRhMS5Nc2dTZW5k"},"expiration":"1700000000000000000"}}}}"#,
+        );
+        assert_eq!(crate::from_json::<CosmosMsg>(&json).unwrap(), msg);
+
+        // no expiration
+        let grant = AuthzGrant {
+            authorization: any,
+            expiration: None,
+        };
+        let json = crate::to_json_string(&grant).unwrap();
+        assert_eq!(
+            json,
+            r#"{"authorization":{"type_url":"/cosmos.bank.v1beta1.MsgSend","value":"5yu/rQ+HrMcxH1zdga7P5hpGMLE="},"expiration":null}"#,
+        );
+        assert_eq!(crate::from_json::<AuthzGrant>(&json).unwrap(), grant);
+    }
+
+    #[test]
+    #[cfg(feature = "cosmwasm_2_0")]
+    fn authz_msg_has_defined_json_schema() {
+        let schema = schemars::schema_for!(AuthzMsg);
+        assert_eq!("AuthzMsg", schema.schema.metadata.unwrap().title.unwrap());
+
+        // the variant is part of the CosmosMsg schema
+        let schema = serde_json::to_value(schemars::schema_for!(CosmosMsg)).unwrap();
+        let variants = schema["oneOf"].as_array().unwrap();
+        assert!(variants
+            .iter()
+            .any(|variant| variant["required"] == serde_json::json!(["authz"])));
+        assert!(schema["definitions"]["AuthzGrant"].is_object());
+    }
+
+    #[cfg(feature = "cosmwasm_1_2")]
+    #[test]
+    fn wasm_msg_migrate_checked_works() {
+        use crate::testing::MockQuerier;
+        use crate::{Addr, CodeInfoResponse};
+
+        let checksum = Checksum::generate(b"reviewed code");
+        let querier: MockQuerier = MockQuerier::new(&[]).with_code_info(CodeInfoResponse::new(
+            7,
+            Addr::unchecked("creator"),
+            checksum,
+        ));
+        let querier = QuerierWrapper::<Empty>::new(&querier);
+
+        let msg = WasmMsg::migrate_checked(&querier, "contract", 7, &checksum, &"migrate").unwrap();
+        assert_eq!(
+            msg,
+            WasmMsg::Migrate {
+                contract_addr: "contract".to_string(),
+                new_code_id: 7,
+                msg: Binary::from(br#""migrate""#),
+            }
+        );
+
+        let other = Checksum::generate(b"other code");
+        let err =
+            WasmMsg::migrate_checked(&querier, "contract", 7, &other, &"migrate").unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch for code ID 7"));
+
+        WasmMsg::migrate_checked(&querier, "contract", 8, &checksum, &"migrate").unwrap_err();
+    }
+
     #[test]
     fn wasm_msg_debug_decodes_binary_string_when_possible() {
         #[cosmwasm_schema::cw_serde]
@@ -3,6 +3,10 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::prelude::*;
+use crate::{StdError, StdResult};
+
+/// The minimum length of an event type (after trimming whitespace) accepted by the runtime
+const EVENT_TYPE_MIN_LENGTH: usize = 3;
 
 /// A full [*Cosmos SDK* event].
 ///
@@ -37,6 +41,27 @@ impl Event {
         }
     }
 
+    /// Create a new event with the given type and an empty list of attributes.
+    ///
+    /// In contrast to [`Event::new`], this validates the type the same way the runtime does:
+    /// the type must have at least 3 characters after trimming whitespace.
+    /// Note that the runtime prepends a `wasm-` prefix to the type.
+    ///
+    /// ```
+    /// # use cosmwasm_std::Event;
+    /// assert!(Event::try_new("transfer").is_ok());
+    /// assert!(Event::try_new(" a ").is_err());
+    /// ```
+    pub fn try_new(ty: impl Into<String>) -> StdResult<Self> {
+        let ty = ty.into();
+        if ty.trim().len() < EVENT_TYPE_MIN_LENGTH {
+            return Err(StdError::generic_err(format!(
+                "Event type too short: `{ty}`. Must be at least {EVENT_TYPE_MIN_LENGTH} characters."
+            )));
+        }
+        Ok(Self::new(ty))
+    }
+
     /// Add an attribute to the event.
     pub fn add_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.attributes.push(Attribute {
@@ -57,6 +82,106 @@ impl Event {
         self.attributes.extend(attrs.into_iter().map(A::into));
         self
     }
+
+    /// Add an attribute to the event after validating the key the same way the runtime does:
+    /// the key must not be empty after trimming whitespace and must not start with an
+    /// underscore, which is reserved for attributes added by the runtime.
+    ///
+    /// Use this instead of [`Event::add_attribute`] to get an error instead of a failing
+    /// transaction when the key is not known at compile time.
+    pub fn add_attribute_checked(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> StdResult<Self> {
+        let key = key.into();
+        validate_attribute_key(&key)?;
+        self.attributes.push(Attribute {
+            key,
+            value: value.into(),
+        });
+        Ok(self)
+    }
+
+    /// Returns the first attribute with the given key, if any.
+    ///
+    /// ```
+    /// # use cosmwasm_std::Event;
+    /// let event = Event::new("transfer").add_attribute("recipient", "bob");
+    /// assert_eq!(event.find_attribute("recipient").unwrap().value, "bob");
+    /// assert!(event.find_attribute("sender").is_none());
+    /// ```
+    pub fn find_attribute(&self, key: &str) -> Option<&Attribute> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.key == key)
+    }
+
+    /// Returns `true` if the event has at least one attribute with the given key.
+    pub fn has_attribute(&self, key: &str) -> bool {
+        self.find_attribute(key).is_some()
+    }
+
+    /// Returns all attributes with the given key in order.
+    /// Use this for events that may contain the same key multiple times.
+    pub fn attributes_with_key<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> impl Iterator<Item = &'a Attribute> + 'a {
+        self.attributes
+            .iter()
+            .filter(move |attribute| attribute.key == key)
+    }
+}
+
+fn validate_attribute_key(key: &str) -> StdResult<()> {
+    if key.trim().is_empty() {
+        return Err(StdError::generic_err("Attribute key must not be empty"));
+    }
+    if key.trim().starts_with('_') {
+        return Err(StdError::generic_err(format!(
+            "Attribute key `{key}` is invalid - keys starting with an underscore are reserved"
+        )));
+    }
+    Ok(())
+}
+
+/// A type that can be emitted as an [`Event`].
+///
+/// This allows defining events as Rust types instead of assembling them in place,
+/// which ensures the same event is always emitted with the same type and attributes.
+///
+/// ```
+/// # use cosmwasm_std::{Event, Response, TypedEvent, Uint128};
+/// struct Transfer {
+///     recipient: String,
+///     amount: Uint128,
+/// }
+///
+/// impl TypedEvent for Transfer {
+///     fn event_type() -> &'static str {
+///         "transfer"
+///     }
+///
+///     fn into_event(self) -> Event {
+///         Event::new(Self::event_type())
+///             .add_attribute("recipient", self.recipient)
+///             .add_attribute("amount", self.amount)
+///     }
+/// }
+///
+/// let response: Response = Response::new().add_typed_event(Transfer {
+///     recipient: "alice".to_string(),
+///     amount: Uint128::new(42),
+/// });
+/// assert_eq!(response.events[0].ty, "transfer");
+/// ```
+pub trait TypedEvent {
+    /// The event type, i.e. the value of [`Event::ty`]
+    fn event_type() -> &'static str;
+
+    /// Converts the value into an event of type [`TypedEvent::event_type`]
+    fn into_event(self) -> Event;
 }
 
 /// An key value pair that is used in the context of event attributes in logs
@@ -151,6 +276,110 @@ mod tests {
         Attribute::new("_", "value");
     }
 
+    #[test]
+    fn event_find_attribute_works() {
+        let event = Event::new("test").add_attributes([("foo", "bar"), ("baz", "1"), ("baz", "2")]);
+
+        assert_eq!(event.find_attribute("foo"), Some(&attr("foo", "bar")));
+        assert_eq!(event.find_attribute("baz"), Some(&attr("baz", "1")));
+        assert_eq!(event.find_attribute("bar"), None);
+        assert_eq!(event.find_attribute(""), None);
+
+        assert!(event.has_attribute("foo"));
+        assert!(event.has_attribute("baz"));
+        assert!(!event.has_attribute("bar"));
+        assert!(!Event::new("empty").has_attribute("foo"));
+    }
+
+    #[test]
+    fn event_attributes_with_key_works() {
+        let event = Event::new("test").add_attributes([("baz", "1"), ("foo", "bar"), ("baz", "2")]);
+
+        let values: Vec<_> = event
+            .attributes_with_key("baz")
+            .map(|attribute| attribute.value.as_str())
+            .collect();
+        assert_eq!(values, ["1", "2"]);
+        assert_eq!(event.attributes_with_key("foo").count(), 1);
+        assert_eq!(event.attributes_with_key("bar").count(), 0);
+    }
+
+    #[test]
+    fn event_try_new_works() {
+        let event = Event::try_new("test").unwrap();
+        assert_eq!(event, Event::new("test"));
+        // the type is not trimmed
+        assert_eq!(Event::try_new(" abc ").unwrap().ty, " abc ");
+
+        for ty in ["", "a", "ab", "  ab  ", "   "] {
+            let err = Event::try_new(ty).unwrap_err();
+            assert!(err.to_string().contains("Event type too short"), "{err}");
+        }
+    }
+
+    #[test]
+    fn event_add_attribute_checked_works() {
+        let event = Event::new("test")
+            .add_attribute_checked("foo", "bar")
+            .unwrap()
+            .add_attribute_checked("empty", "")
+            .unwrap();
+        assert_eq!(
+            event,
+            Event::new("test").add_attributes([("foo", "bar"), ("empty", "")])
+        );
+
+        // empty keys
+        for key in ["", " ", "\t"] {
+            let err = Event::new("test")
+                .add_attribute_checked(key, "value")
+                .unwrap_err();
+            assert!(err.to_string().contains("must not be empty"), "{err}");
+        }
+    }
+
+    #[test]
+    fn event_add_attribute_checked_rejects_reserved_keys() {
+        for key in ["_contract_address", "_", " _foo"] {
+            let err = Event::new("test")
+                .add_attribute_checked(key, "value")
+                .unwrap_err();
+            assert!(err.to_string().contains("reserved"), "{err}");
+        }
+        // underscores are only reserved as a prefix
+        Event::new("test")
+            .add_attribute_checked("foo_bar_", "value")
+            .unwrap();
+    }
+
+    #[test]
+    fn typed_event_works() {
+        struct Transfer {
+            recipient: String,
+            amount: Uint128,
+        }
+
+        impl TypedEvent for Transfer {
+            fn event_type() -> &'static str {
+                "transfer"
+            }
+
+            fn into_event(self) -> Event {
+                Event::new(Self::event_type())
+                    .add_attribute("recipient", self.recipient)
+                    .add_attribute("amount", self.amount)
+            }
+        }
+
+        let event = Transfer {
+            recipient: "alice".to_string(),
+            amount: Uint128::new(42),
+        }
+        .into_event();
+        assert_eq!(event.ty, Transfer::event_type());
+        assert_eq!(event.attributes, [("recipient", "alice"), ("amount", "42")]);
+    }
+
     #[test]
     fn attr_works_for_different_types() {
         let expected = ("foo", "42");
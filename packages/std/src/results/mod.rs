@@ -13,15 +13,19 @@ pub use contract_result::ContractResult;
 #[cfg(all(feature = "stargate", feature = "cosmwasm_1_2"))]
 pub use cosmos_msg::WeightedVoteOption;
 pub use cosmos_msg::{
-    wasm_execute, wasm_instantiate, AnyMsg, BankMsg, CosmosMsg, CustomMsg, WasmMsg,
+    wasm_execute, wasm_instantiate, AnyMsg, AuthzGrant, AuthzMsg, BankMsg, CosmosMsg, CustomMsg,
+    WasmMsg,
 };
 #[cfg(feature = "staking")]
 pub use cosmos_msg::{DistributionMsg, StakingMsg};
 #[cfg(feature = "stargate")]
 pub use cosmos_msg::{GovMsg, VoteOption};
 pub use empty::Empty;
-pub use events::{attr, Attribute, Event};
+pub use events::{attr, Attribute, Event, TypedEvent};
 pub use query::QueryResponse;
 pub use response::Response;
-pub use submessages::{MsgResponse, Reply, ReplyOn, SubMsg, SubMsgResponse, SubMsgResult};
+pub use submessages::{
+    parse_execute_response_data, parse_instantiate_response_data, ExecuteResponseData,
+    InstantiateResponseData, MsgResponse, Reply, ReplyOn, SubMsg, SubMsgResponse, SubMsgResult,
+};
 pub use system_result::SystemResult;
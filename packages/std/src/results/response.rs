@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use crate::prelude::*;
 use crate::Binary;
 
-use super::{Attribute, CosmosMsg, Empty, Event, SubMsg};
+use super::{Attribute, CosmosMsg, Empty, Event, SubMsg, TypedEvent};
 
 /// A response of a contract entry point, such as `instantiate`, `execute` or `migrate`.
 ///
@@ -134,6 +134,15 @@ impl<T> Response<T> {
         self
     }
 
+    /// Adds an extra event of a [`TypedEvent`] type to the response, separate from the
+    /// main `wasm` event.
+    ///
+    /// The `wasm-` prefix will be appended by the runtime to the provided type
+    /// of event.
+    pub fn add_typed_event(self, event: impl TypedEvent) -> Self {
+        self.add_event(event.into_event())
+    }
+
     /// Bulk add attributes included in the main `wasm` event.
     ///
     /// Anything that can be turned into an iterator and yields something
@@ -233,6 +242,68 @@ impl<T> Response<T> {
         self
     }
 
+    /// Merges `other` into this response.
+    ///
+    /// The messages, attributes and events of `other` are appended to the ones of this response,
+    /// preserving their order. The data of this response takes priority. It is only taken from
+    /// `other` if this response has no data.
+    ///
+    /// This is useful when partial responses are built in helper functions:
+    ///
+    /// ```
+    /// # use cosmwasm_std::{Response, StdResult};
+    /// fn handle_a() -> StdResult<Response> {
+    ///     Ok(Response::new().add_attribute("action", "a"))
+    /// }
+    ///
+    /// fn handle_b() -> StdResult<Response> {
+    ///     Ok(Response::new().add_attribute("action", "b").set_data(b"b"))
+    /// }
+    ///
+    /// let response = handle_a()?.merge(handle_b()?);
+    /// assert_eq!(response.attributes, [("action", "a"), ("action", "b")]);
+    /// assert_eq!(response.data.unwrap(), b"b");
+    /// # Ok::<(), cosmwasm_std::StdError>(())
+    /// ```
+    pub fn merge(mut self, other: Response<T>) -> Self {
+        self.messages.extend(other.messages);
+        self.attributes.extend(other.attributes);
+        self.events.extend(other.events);
+        if self.data.is_none() {
+            self.data = other.data;
+        }
+        self
+    }
+
+    /// Merges all given responses into one, in order. See [`Response::merge`] for details.
+    ///
+    /// The data of the first response that has data is used.
+    pub fn merge_all(responses: impl IntoIterator<Item = Response<T>>) -> Self {
+        responses
+            .into_iter()
+            .fold(Self::default(), |merged, response| merged.merge(response))
+    }
+
+    /// Returns the value of the first attribute with key `attr_key` in the first event
+    /// of type `event_type` that has such an attribute.
+    ///
+    /// Only the events of the response are searched, not [`Response::attributes`].
+    ///
+    /// ```
+    /// # use cosmwasm_std::{Event, Response};
+    /// let response: Response = Response::new()
+    ///     .add_event(Event::new("transfer").add_attribute("amount", "12"));
+    /// assert_eq!(response.find_event_attribute("transfer", "amount"), Some("12"));
+    /// assert_eq!(response.find_event_attribute("transfer", "denom"), None);
+    /// ```
+    pub fn find_event_attribute(&self, event_type: &str, attr_key: &str) -> Option<&str> {
+        self.events
+            .iter()
+            .filter(|event| event.ty == event_type)
+            .find_map(|event| event.find_attribute(attr_key))
+            .map(|attribute| attribute.value.as_str())
+    }
+
     /// Convert this [`Response<T>`] to a [`Response<U>`] with a different custom message type.
     /// This allows easier interactions between code written for a specific chain and
     /// code written for multiple chains.
@@ -388,6 +459,92 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn merge_works() {
+        let first = Response::<Empty>::new()
+            .add_attribute("action", "first")
+            .add_message(BankMsg::Burn {
+                amount: coins(1, "earth"),
+            })
+            .add_event(Event::new("first_event"));
+        let second = Response::<Empty>::new()
+            .add_attribute("action", "second")
+            .add_attribute("foo", "bar")
+            .add_message(BankMsg::Burn {
+                amount: coins(2, "earth"),
+            })
+            .add_event(Event::new("second_event"))
+            .set_data(b"second");
+
+        let merged = first.clone().merge(second.clone());
+        assert_eq!(
+            merged.attributes,
+            [("action", "first"), ("action", "second"), ("foo", "bar")]
+        );
+        assert_eq!(
+            merged.messages,
+            [first.messages[0].clone(), second.messages[0].clone()]
+        );
+        assert_eq!(
+            merged.events,
+            [Event::new("first_event"), Event::new("second_event")]
+        );
+        // data is taken from other if unset
+        assert_eq!(merged.data, Some(Binary::from(b"second")));
+
+        // data of self takes priority
+        let merged = first.set_data(b"first").merge(second);
+        assert_eq!(merged.data, Some(Binary::from(b"first")));
+
+        // merging empty responses is a no-op
+        let response = Response::<Empty>::new()
+            .add_attribute("foo", "bar")
+            .set_data(b"data");
+        assert_eq!(response.clone().merge(Response::new()), response);
+        assert_eq!(Response::new().merge(response.clone()), response);
+    }
+
+    #[test]
+    fn merge_all_works() {
+        let merged = Response::<Empty>::merge_all([
+            Response::new().add_attribute("index", "0"),
+            Response::new().add_attribute("index", "1").set_data(b"1"),
+            Response::new().add_attribute("index", "2").set_data(b"2"),
+        ]);
+        assert_eq!(
+            merged.attributes,
+            [("index", "0"), ("index", "1"), ("index", "2")]
+        );
+        assert_eq!(merged.data, Some(Binary::from(b"1")));
+
+        assert_eq!(Response::<Empty>::merge_all([]), Response::new());
+    }
+
+    #[test]
+    fn find_event_attribute_works() {
+        let response = Response::<Empty>::new()
+            .add_attribute("action", "ignored")
+            .add_event(Event::new("transfer").add_attribute("amount", "1"))
+            .add_event(Event::new("mint").add_attribute("amount", "2"))
+            .add_event(Event::new("transfer").add_attribute("denom", "earth"))
+            .add_event(Event::new("transfer").add_attribute("amount", "3"));
+
+        assert_eq!(
+            response.find_event_attribute("transfer", "amount"),
+            Some("1")
+        );
+        assert_eq!(response.find_event_attribute("mint", "amount"), Some("2"));
+        // keeps searching in later events of the same type
+        assert_eq!(
+            response.find_event_attribute("transfer", "denom"),
+            Some("earth")
+        );
+        assert_eq!(response.find_event_attribute("mint", "denom"), None);
+        assert_eq!(response.find_event_attribute("burn", "amount"), None);
+        // response attributes are not searched
+        assert_eq!(response.find_event_attribute("wasm", "action"), None);
+    }
+
     #[test]
     fn change_custom_works() {
         let response: Response<Empty> = Response {
@@ -1,8 +1,12 @@
+use core::any::type_name;
 use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use crate::prelude::*;
-use crate::Binary;
+use crate::proto::decode_bytes_fields;
+use crate::{from_json, from_msgpack, to_json_binary, to_msgpack_binary};
+use crate::{Binary, StdError, StdResult};
 
 use super::{CosmosMsg, Empty, Event};
 
@@ -139,6 +143,48 @@ impl<T> SubMsg<T> {
         self
     }
 
+    /// Add a JSON encoded payload to the submessage.
+    /// Use [`Reply::payload_json`] to decode it in the `reply` entry point.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_schema::cw_serde;
+    /// # use cosmwasm_std::{coins, BankMsg, Reply, SubMsg, SubMsgResult};
+    /// # let msg = BankMsg::Send { to_address: String::from("you"), amount: coins(1015, "earth") };
+    /// #[cw_serde]
+    /// struct Payload {
+    ///     recipient: String,
+    /// }
+    ///
+    /// let sub_msg: SubMsg = SubMsg::reply_on_success(msg, 1234)
+    ///     .with_payload_json(&Payload { recipient: "alice".to_string() })
+    ///     .unwrap();
+    /// assert_eq!(sub_msg.payload_bytes(), br#"{"recipient":"alice"}"#);
+    ///
+    /// // The chain passes the payload back in the reply
+    /// # let reply = Reply { id: 1234, payload: sub_msg.payload, gas_used: 0, result: SubMsgResult::Err("".to_string()) };
+    /// let payload: Payload = reply.payload_json().unwrap();
+    /// assert_eq!(payload.recipient, "alice");
+    /// ```
+    pub fn with_payload_json<P: Serialize>(self, payload: &P) -> StdResult<Self> {
+        Ok(self.with_payload(to_json_binary(payload)?))
+    }
+
+    /// Add a MessagePack encoded payload to the submessage.
+    /// Use [`Reply::payload_msgpack`] to decode it in the `reply` entry point.
+    ///
+    /// This results in a smaller payload than [`SubMsg::with_payload_json`].
+    pub fn with_payload_msgpack<P: Serialize>(self, payload: &P) -> StdResult<Self> {
+        Ok(self.with_payload(to_msgpack_binary(payload)?))
+    }
+
+    /// Returns the raw payload bytes. Use this if you encode the payload yourself
+    /// (see [`SubMsg::with_payload`]).
+    pub fn payload_bytes(&self) -> &[u8] {
+        self.payload.as_slice()
+    }
+
     fn reply_on(msg: CosmosMsg<T>, id: u64, reply_on: ReplyOn) -> Self {
         SubMsg {
             id,
@@ -189,6 +235,71 @@ pub struct Reply {
     pub result: SubMsgResult,
 }
 
+impl Reply {
+    /// Decodes a JSON encoded payload, as set via [`SubMsg::with_payload_json`].
+    ///
+    /// Errors if the payload is empty or cannot be decoded into `P`.
+    ///
+    /// ## Examples
+    ///
+    /// Using an enum as the payload allows dispatching replies to different handlers
+    /// based on what the contract stored when emitting the submessage:
+    ///
+    /// ```
+    /// # use cosmwasm_schema::cw_serde;
+    /// # use cosmwasm_std::{
+    /// #     entry_point, Binary, DepsMut, Env, Reply, Response, StdResult, SubMsgResult,
+    /// # };
+    /// #[cw_serde]
+    /// enum ReplyPayload {
+    ///     Instantiated { label: String },
+    ///     Swapped { min_out: u64 },
+    /// }
+    ///
+    /// #[entry_point]
+    /// pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> StdResult<Response> {
+    ///     match reply.payload_json()? {
+    ///         ReplyPayload::Instantiated { label } => handle_instantiated(deps, reply, label),
+    ///         ReplyPayload::Swapped { min_out } => handle_swapped(deps, reply, min_out),
+    ///     }
+    /// }
+    ///
+    /// fn handle_instantiated(deps: DepsMut, reply: Reply, label: String) -> StdResult<Response> {
+    ///     // ...
+    ///     Ok(Response::new().add_attribute("label", label))
+    /// }
+    ///
+    /// fn handle_swapped(deps: DepsMut, reply: Reply, min_out: u64) -> StdResult<Response> {
+    ///     // ...
+    ///     Ok(Response::new().add_attribute("min_out", min_out.to_string()))
+    /// }
+    /// ```
+    pub fn payload_json<P: DeserializeOwned>(&self) -> StdResult<P> {
+        self.ensure_payload::<P>()?;
+        from_json(&self.payload)
+    }
+
+    /// Decodes a MessagePack encoded payload, as set via [`SubMsg::with_payload_msgpack`].
+    ///
+    /// Errors if the payload is empty or cannot be decoded into `P`.
+    pub fn payload_msgpack<P: DeserializeOwned>(&self) -> StdResult<P> {
+        self.ensure_payload::<P>()?;
+        from_msgpack(&self.payload)
+    }
+
+    /// Deserializing an empty payload results in a cryptic EOF error,
+    /// so we check for it explicitly.
+    fn ensure_payload<P>(&self) -> StdResult<()> {
+        if self.payload.is_empty() {
+            return Err(StdError::parse_err(
+                type_name::<P>(),
+                "Reply payload is empty. Did you forget to set it on the SubMsg?",
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// This is the result type that is returned from a sub message execution.
 ///
 /// We use a custom type here instead of Rust's Result because we want to be able to
@@ -260,6 +371,62 @@ impl SubMsgResult {
     pub fn is_err(&self) -> bool {
         matches!(self, SubMsgResult::Err(_))
     }
+
+    /// Converts the result into an `Option` of the response, discarding the error.
+    ///
+    /// ```
+    /// # use cosmwasm_std::SubMsgResult;
+    /// let result = SubMsgResult::Err("broken".to_string());
+    /// assert_eq!(result.ok(), None);
+    /// ```
+    pub fn ok(self) -> Option<SubMsgResponse> {
+        self.into_result().ok()
+    }
+
+    /// Converts the result into an `Option` of the error message, discarding the response.
+    ///
+    /// ```
+    /// # use cosmwasm_std::SubMsgResult;
+    /// let result = SubMsgResult::Err("broken".to_string());
+    /// assert_eq!(result.err(), Some("broken".to_string()));
+    /// ```
+    pub fn err(self) -> Option<String> {
+        self.into_result().err()
+    }
+
+    /// Maps the error message using the given function, leaving a response untouched.
+    ///
+    /// In contrast to [`ContractResult`](crate::ContractResult), the success type is fixed,
+    /// so there is no `map` or `and_then`. Use [`SubMsgResult::into_result`] for those.
+    ///
+    /// ```
+    /// # use cosmwasm_std::SubMsgResult;
+    /// let result = SubMsgResult::Err("broken".to_string());
+    /// assert_eq!(
+    ///     result.map_err(|err| format!("Transfer failed: {err}")),
+    ///     SubMsgResult::Err("Transfer failed: broken".to_string())
+    /// );
+    /// ```
+    pub fn map_err<E: ToString, F: FnOnce(String) -> E>(self, op: F) -> SubMsgResult {
+        self.into_result().map_err(op).into()
+    }
+
+    /// Returns the response or computes one from the error message.
+    ///
+    /// ```
+    /// # use cosmwasm_std::{Event, SubMsgResponse, SubMsgResult};
+    /// # #[allow(deprecated)]
+    /// let fallback = |err: String| SubMsgResponse {
+    ///     events: vec![Event::new("failed").add_attribute("error", err)],
+    ///     data: None,
+    ///     msg_responses: vec![],
+    /// };
+    /// let response = SubMsgResult::Err("broken".to_string()).unwrap_or_else(fallback);
+    /// assert_eq!(response.events[0].ty, "failed");
+    /// ```
+    pub fn unwrap_or_else<F: FnOnce(String) -> SubMsgResponse>(self, op: F) -> SubMsgResponse {
+        self.into_result().unwrap_or_else(op)
+    }
 }
 
 impl<E: ToString> From<Result<SubMsgResponse, E>> for SubMsgResult {
@@ -310,6 +477,96 @@ pub struct MsgResponse {
     pub value: Binary,
 }
 
+impl SubMsgResponse {
+    /// Returns the first entry of `msg_responses` with the given type URL.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_std::{parse_instantiate_response_data, StdResult, SubMsgResponse};
+    /// fn instantiated_address(response: &SubMsgResponse) -> StdResult<Option<String>> {
+    ///     let Some(msg_response) =
+    ///         response.find_msg_response("/cosmwasm.wasm.v1.MsgInstantiateContractResponse")
+    ///     else {
+    ///         return Ok(None);
+    ///     };
+    ///     let parsed = parse_instantiate_response_data(&msg_response.value)?;
+    ///     Ok(Some(parsed.contract_address))
+    /// }
+    /// ```
+    pub fn find_msg_response(&self, type_url: &str) -> Option<&MsgResponse> {
+        self.msg_responses
+            .iter()
+            .find(|response| response.type_url == type_url)
+    }
+}
+
+/// The content of wasmd's `MsgInstantiateContractResponse` and `MsgInstantiateContract2Response`.
+/// See [`parse_instantiate_response_data`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstantiateResponseData {
+    /// The address of the new contract
+    pub contract_address: String,
+    /// The data returned by the contract's `instantiate` entry point
+    pub data: Option<Binary>,
+}
+
+/// The content of wasmd's `MsgExecuteContractResponse`.
+/// See [`parse_execute_response_data`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecuteResponseData {
+    /// The data returned by the contract's `execute` entry point
+    pub data: Option<Binary>,
+}
+
+/// Decodes the protobuf encoded `MsgInstantiateContractResponse` (or `MsgInstantiateContract2Response`,
+/// which has the same fields) of wasmd, e.g. the `value` of the corresponding [`MsgResponse`].
+///
+/// Unknown fields are ignored. Errors if the contract address is missing.
+pub fn parse_instantiate_response_data(data: &[u8]) -> StdResult<InstantiateResponseData> {
+    const TARGET: &str = "MsgInstantiateContractResponse";
+
+    let mut contract_address = None;
+    let mut response_data = None;
+    decode_bytes_fields(data, TARGET, |field, value| {
+        match field {
+            1 => {
+                let address = core::str::from_utf8(value).map_err(|_| {
+                    StdError::parse_err(TARGET, "contract address is not valid UTF-8")
+                })?;
+                contract_address = Some(address.to_string());
+            }
+            2 => response_data = Some(Binary::from(value)),
+            _ => {}
+        }
+        Ok(())
+    })?;
+
+    Ok(InstantiateResponseData {
+        contract_address: contract_address
+            .filter(|address| !address.is_empty())
+            .ok_or_else(|| StdError::parse_err(TARGET, "missing contract address"))?,
+        data: response_data,
+    })
+}
+
+/// Decodes the protobuf encoded `MsgExecuteContractResponse` of wasmd,
+/// e.g. the `value` of the corresponding [`MsgResponse`].
+///
+/// Unknown fields are ignored.
+pub fn parse_execute_response_data(data: &[u8]) -> StdResult<ExecuteResponseData> {
+    let mut response_data = None;
+    decode_bytes_fields(data, "MsgExecuteContractResponse", |field, value| {
+        if field == 1 {
+            response_data = Some(Binary::from(value));
+        }
+        Ok(())
+    })?;
+    Ok(ExecuteResponseData {
+        data: response_data,
+    })
+}
+
 #[cfg(test)]
 #[allow(deprecated)]
 mod tests {
@@ -376,8 +633,105 @@ mod tests {
         };
         let sub_msg: SubMsg = SubMsg::reply_never(msg);
         assert_eq!(sub_msg.payload, Binary::default());
+        assert_eq!(sub_msg.payload_bytes(), b"");
         let sub_msg = sub_msg.with_payload(vec![0xAA, 3, 5, 1, 2]);
         assert_eq!(sub_msg.payload, Binary::new(vec![0xAA, 3, 5, 1, 2]));
+        assert_eq!(sub_msg.payload_bytes(), [0xAA, 3, 5, 1, 2]);
+    }
+
+    #[cosmwasm_schema::cw_serde]
+    enum TestPayload {
+        Instantiated { label: String },
+        Swapped { min_out: u64 },
+    }
+
+    fn reply_with_payload(payload: Binary) -> Reply {
+        Reply {
+            id: 1,
+            payload,
+            gas_used: 0,
+            result: SubMsgResult::Err("failed".to_string()),
+        }
+    }
+
+    #[test]
+    fn sub_msg_with_payload_json_works() {
+        let msg = BankMsg::Send {
+            to_address: String::from("you"),
+            amount: coins(1015, "earth"),
+        };
+        let payload = TestPayload::Swapped { min_out: 42 };
+        let sub_msg: SubMsg = SubMsg::reply_always(msg, 1)
+            .with_payload_json(&payload)
+            .unwrap();
+        assert_eq!(sub_msg.payload.as_slice(), br#"{"swapped":{"min_out":42}}"#);
+
+        let reply = reply_with_payload(sub_msg.payload);
+        assert_eq!(reply.payload_json::<TestPayload>().unwrap(), payload);
+    }
+
+    #[test]
+    fn sub_msg_with_payload_msgpack_works() {
+        let msg = BankMsg::Send {
+            to_address: String::from("you"),
+            amount: coins(1015, "earth"),
+        };
+        let payload = TestPayload::Instantiated {
+            label: "my instance".to_string(),
+        };
+        let sub_msg: SubMsg = SubMsg::reply_always(msg, 1)
+            .with_payload_msgpack(&payload)
+            .unwrap();
+        assert_eq!(sub_msg.payload, crate::to_msgpack_binary(&payload).unwrap());
+
+        let reply = reply_with_payload(sub_msg.payload);
+        assert_eq!(reply.payload_msgpack::<TestPayload>().unwrap(), payload);
+        // wrong encoding
+        reply.payload_json::<TestPayload>().unwrap_err();
+    }
+
+    #[test]
+    fn reply_payload_errors_for_empty_payload() {
+        let reply = reply_with_payload(Binary::default());
+
+        for err in [
+            reply.payload_json::<TestPayload>().unwrap_err(),
+            reply.payload_msgpack::<TestPayload>().unwrap_err(),
+        ] {
+            match err {
+                StdError::ParseErr {
+                    target_type, msg, ..
+                } => {
+                    assert!(target_type.ends_with("TestPayload"));
+                    assert_eq!(
+                        msg,
+                        "Reply payload is empty. Did you forget to set it on the SubMsg?"
+                    );
+                }
+                err => panic!("Unexpected error: {err:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn reply_payload_errors_for_mismatched_type() {
+        #[cosmwasm_schema::cw_serde]
+        struct OtherPayload {
+            amount: u64,
+        }
+
+        let reply = reply_with_payload(br#"{"swapped":{"min_out":42}}"#.into());
+        match reply.payload_json::<OtherPayload>().unwrap_err() {
+            StdError::ParseErr { target_type, .. } => {
+                assert!(target_type.ends_with("OtherPayload"))
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        let reply = reply_with_payload(
+            crate::to_msgpack_binary(&TestPayload::Swapped { min_out: 42 }).unwrap(),
+        );
+        reply.payload_msgpack::<OtherPayload>().unwrap_err();
     }
 
     #[test]
@@ -529,6 +883,49 @@ mod tests {
         assert!(!success.is_err());
     }
 
+    #[test]
+    fn sub_msg_result_combinators_work() {
+        let response = SubMsgResponse {
+            events: vec![Event::new("wasm")],
+            data: None,
+            msg_responses: vec![],
+        };
+        let ok = SubMsgResult::Ok(response.clone());
+        let err = SubMsgResult::Err("broken".to_string());
+
+        assert_eq!(ok.clone().ok(), Some(response.clone()));
+        assert_eq!(err.clone().ok(), None);
+        assert_eq!(ok.clone().err(), None);
+        assert_eq!(err.clone().err(), Some("broken".to_string()));
+
+        assert_eq!(ok.clone().map_err(|e| e + "!"), ok);
+        assert_eq!(
+            err.clone().map_err(|e| e + "!"),
+            SubMsgResult::Err("broken!".to_string())
+        );
+
+        let fallback = |_| SubMsgResponse {
+            events: vec![],
+            data: None,
+            msg_responses: vec![],
+        };
+        assert_eq!(ok.unwrap_or_else(fallback), response);
+        assert!(err.unwrap_or_else(fallback).events.is_empty());
+    }
+
+    #[test]
+    fn sub_msg_result_combinators_do_not_change_serialization() {
+        let json = br#"{"ok":{"events":[],"data":null,"msg_responses":[]}}"#;
+        let result: SubMsgResult = from_json(json).unwrap();
+        assert_eq!(to_json_vec(&result.map_err(|e| e)).unwrap(), json);
+
+        let json = br#"{"error":"broken"}"#;
+        let result: SubMsgResult = from_json(json).unwrap();
+        assert_eq!(to_json_vec(&result.clone().map_err(|e| e)).unwrap(), json);
+        let round_trip: SubMsgResult = result.into_result().into();
+        assert_eq!(to_json_vec(&round_trip).unwrap(), json);
+    }
+
     #[test]
     fn sub_msg_result_can_convert_from_core_result() {
         let original: Result<SubMsgResponse, StdError> = Ok(SubMsgResponse {
@@ -647,4 +1044,112 @@ mod tests {
         );
         assert_eq!(reply.gas_used, 0);
     }
+
+    #[test]
+    fn find_msg_response_works() {
+        let response = SubMsgResponse {
+            events: vec![],
+            data: None,
+            msg_responses: vec![
+                MsgResponse {
+                    type_url: "/cosmos.bank.v1beta1.MsgSendResponse".to_string(),
+                    value: Binary::default(),
+                },
+                MsgResponse {
+                    type_url: "/cosmwasm.wasm.v1.MsgExecuteContractResponse".to_string(),
+                    value: Binary::from(b"\x0a\x01\x01"),
+                },
+                MsgResponse {
+                    type_url: "/cosmwasm.wasm.v1.MsgExecuteContractResponse".to_string(),
+                    value: Binary::from(b"\x0a\x01\x02"),
+                },
+            ],
+        };
+
+        let found = response
+            .find_msg_response("/cosmwasm.wasm.v1.MsgExecuteContractResponse")
+            .unwrap();
+        assert_eq!(found.value, Binary::from(b"\x0a\x01\x01"));
+        assert!(response
+            .find_msg_response("/cosmwasm.wasm.v1.MsgInstantiateContractResponse")
+            .is_none());
+    }
+
+    #[test]
+    fn parse_instantiate_response_data_works() {
+        const ADDRESS: &str = "wasm14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9s4hmalr";
+
+        // address and data, as returned by wasmd
+        let data = hex::decode("0a3f7761736d3134686a32746176713866706573647778786375343472747933686839307668756a7276636d73746c347a723374786d667677397334686d616c72120b7b226f6b223a747275657d").unwrap();
+        let parsed = parse_instantiate_response_data(&data).unwrap();
+        assert_eq!(
+            parsed,
+            InstantiateResponseData {
+                contract_address: ADDRESS.to_string(),
+                data: Some(Binary::from(br#"{"ok":true}"#)),
+            }
+        );
+
+        // only address
+        let data = hex::decode("0a3f7761736d3134686a32746176713866706573647778786375343472747933686839307668756a7276636d73746c347a723374786d667677397334686d616c72").unwrap();
+        let parsed = parse_instantiate_response_data(&data).unwrap();
+        assert_eq!(
+            parsed,
+            InstantiateResponseData {
+                contract_address: ADDRESS.to_string(),
+                data: None,
+            }
+        );
+
+        // trailing unknown fields (bytes field 3, varint field 4)
+        let mut data = hex::decode("0a3f7761736d3134686a32746176713866706573647778786375343472747933686839307668756a7276636d73746c347a723374786d667677397334686d616c72120101").unwrap();
+        data.extend_from_slice(b"\x1a\x03abc\x20\x96\x01");
+        let parsed = parse_instantiate_response_data(&data).unwrap();
+        assert_eq!(
+            parsed,
+            InstantiateResponseData {
+                contract_address: ADDRESS.to_string(),
+                data: Some(Binary::from(b"\x01")),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_instantiate_response_data_fails_for_invalid_data() {
+        // missing address
+        let err = parse_instantiate_response_data(b"\x12\x01\x01").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error parsing into type MsgInstantiateContractResponse: missing contract address"
+        );
+        let err = parse_instantiate_response_data(b"").unwrap_err();
+        assert!(matches!(err, StdError::ParseErr { .. }));
+
+        // invalid UTF-8
+        let err = parse_instantiate_response_data(b"\x0a\x01\xff").unwrap_err();
+        assert!(matches!(err, StdError::ParseErr { .. }));
+
+        // truncated
+        let err = parse_instantiate_response_data(b"\x0a\x3fwasm1").unwrap_err();
+        assert!(matches!(err, StdError::ParseErr { .. }));
+    }
+
+    #[test]
+    fn parse_execute_response_data_works() {
+        // with data, as returned by wasmd
+        let parsed = parse_execute_response_data(b"\x0a\x06foobar").unwrap();
+        assert_eq!(parsed.data, Some(Binary::from(b"foobar")));
+
+        // without data
+        let parsed = parse_execute_response_data(b"").unwrap();
+        assert_eq!(parsed, ExecuteResponseData { data: None });
+
+        // trailing unknown fields
+        let parsed = parse_execute_response_data(b"\x0a\x01\x01\x12\x02ab\x18\x01").unwrap();
+        assert_eq!(parsed.data, Some(Binary::from(b"\x01")));
+
+        // truncated
+        let err = parse_execute_response_data(b"\x0a\x06foo").unwrap_err();
+        assert!(matches!(err, StdError::ParseErr { .. }));
+    }
 }
@@ -19,14 +19,74 @@ pub fn from_binary<T: DeserializeOwned>(value: &Binary) -> StdResult<T> {
     from_json(value)
 }
 
+/// The maximum nesting depth of JSON arrays and objects accepted by the deserializer.
+/// serde-json-wasm enforces this limit itself while parsing.
+const JSON_MAX_DEPTH: usize = 127;
+
 /// Deserializes the given JSON bytes to a data structure.
 ///
 /// Errors if the input is not valid JSON or cannot be deserialized to the given type.
+/// Arrays and objects must not be nested deeper than 127 levels.
+/// Use [`from_json_with_depth`] for a lower limit.
 pub fn from_json<T: DeserializeOwned>(value: impl AsRef<[u8]>) -> StdResult<T> {
     serde_json_wasm::from_slice(value.as_ref())
         .map_err(|e| StdError::parse_err(type_name::<T>(), e))
 }
 
+/// Deserializes the given JSON bytes to a data structure, allowing arrays and objects
+/// to be nested at most `max_depth` levels deep.
+///
+/// A `max_depth` below 127 is checked by scanning the input before deserialization.
+/// Higher values have the same effect as 127, since the deserializer never accepts
+/// deeper nesting.
+///
+/// Errors if the input is not valid JSON, exceeds the maximum depth or
+/// cannot be deserialized to the given type.
+pub fn from_json_with_depth<T: DeserializeOwned>(
+    value: impl AsRef<[u8]>,
+    max_depth: usize,
+) -> StdResult<T> {
+    let value = value.as_ref();
+    if max_depth < JSON_MAX_DEPTH {
+        check_json_depth(value, max_depth).map_err(|e| StdError::parse_err(type_name::<T>(), e))?;
+    }
+    from_json(value)
+}
+
+/// Scans the JSON input and errors if arrays and objects are nested deeper than `max_depth`.
+///
+/// This does not validate the input. Invalid JSON is rejected by the deserializer later on.
+fn check_json_depth(json: &[u8], max_depth: usize) -> Result<(), String> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for byte in json {
+        if in_string {
+            match (escaped, byte) {
+                (true, _) => escaped = false,
+                (false, b'\\') => escaped = true,
+                (false, b'"') => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match byte {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                depth += 1;
+                if depth > max_depth {
+                    return Err(format!("Recursion limit of {max_depth} exceeded"));
+                }
+            }
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 #[deprecated = "use to_json_vec instead"]
 pub fn to_vec<T>(data: &T) -> StdResult<Vec<u8>>
 where
@@ -139,6 +199,82 @@ mod tests {
         assert_eq!(deserialized, expected);
     }
 
+    #[test]
+    fn from_json_enforces_max_depth() {
+        use serde::de::IgnoredAny;
+
+        fn nested_arrays(depth: usize) -> String {
+            "[".repeat(depth) + &"]".repeat(depth)
+        }
+
+        // at the limit
+        from_json::<IgnoredAny>(nested_arrays(127)).unwrap();
+
+        // above the limit
+        let err = from_json::<IgnoredAny>(nested_arrays(128)).unwrap_err();
+        match err {
+            StdError::ParseErr { msg, .. } => assert_eq!(
+                msg,
+                "JSON is nested too deeply, exceeded the recursion limit."
+            ),
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        // way above the limit does not overflow the stack
+        let err = from_json::<IgnoredAny>(nested_arrays(100_000)).unwrap_err();
+        assert!(matches!(err, StdError::ParseErr { .. }));
+
+        // objects count as well
+        let nested_objects = r#"{"a":"#.repeat(128) + "1" + &"}".repeat(128);
+        let err = from_json::<IgnoredAny>(nested_objects).unwrap_err();
+        match err {
+            StdError::ParseErr { msg, .. } => assert_eq!(
+                msg,
+                "JSON is nested too deeply, exceeded the recursion limit."
+            ),
+            err => panic!("Unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn from_json_with_depth_works() {
+        use serde::de::IgnoredAny;
+
+        from_json_with_depth::<IgnoredAny>("[[[]]]", 3).unwrap();
+        let err = from_json_with_depth::<IgnoredAny>("[[[]]]", 2).unwrap_err();
+        match err {
+            StdError::ParseErr { msg, .. } => assert_eq!(msg, "Recursion limit of 2 exceeded"),
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        // limits above the deserializer's limit have the same effect as the default
+        let nested = "[".repeat(128) + &"]".repeat(128);
+        let err = from_json_with_depth::<IgnoredAny>(&nested, 200).unwrap_err();
+        match err {
+            StdError::ParseErr { msg, .. } => assert_eq!(
+                msg,
+                "JSON is nested too deeply, exceeded the recursion limit."
+            ),
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        // brackets in strings are ignored
+        let deserialized: SomeMsg = from_json_with_depth(
+            br#"{"release_all":{"image":"[[[{\"{{","amount":42,"time":1,"karma":-17}}"#,
+            2,
+        )
+        .unwrap();
+        assert_eq!(
+            deserialized,
+            SomeMsg::ReleaseAll {
+                image: r#"[[[{"{{"#.to_string(),
+                amount: 42,
+                time: 1,
+                karma: -17,
+            }
+        );
+    }
+
     #[test]
     fn from_json_or_binary() {
         let msg = SomeMsg::Refund {};
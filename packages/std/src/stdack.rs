@@ -2,8 +2,15 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::prelude::*;
+use crate::proto::{decode_bytes_fields, encode_bytes_field};
 use crate::to_json_binary;
 use crate::Binary;
+use crate::{StdError, StdResult};
+
+/// Protobuf field number of `result` in ibc-go's `Acknowledgement` message
+const PROTO_FIELD_RESULT: u64 = 21;
+/// Protobuf field number of `error` in ibc-go's `Acknowledgement` message
+const PROTO_FIELD_ERROR: u64 = 22;
 
 /// This is a standard IBC acknowledgement type. IBC application are free
 /// to use any acknowledgement format they want. However, for compatibility
@@ -98,6 +105,50 @@ impl StdAck {
         to_json_binary(&self).unwrap()
     }
 
+    /// Serializes the ack to binary using the protobuf `Acknowledgement` envelope
+    /// of ibc-go, i.e. as `result` (field 21) or `error` (field 22).
+    ///
+    /// Use this when talking to IBC applications or middlewares that expect the
+    /// protobuf format instead of JSON.
+    ///
+    /// ```
+    /// # use cosmwasm_std::StdAck;
+    /// let ack = StdAck::success(b"\x01");
+    /// assert_eq!(ack.to_proto_bytes(), b"\xaa\x01\x01\x01");
+    /// assert_eq!(StdAck::from_proto_bytes(&ack.to_proto_bytes()).unwrap(), ack);
+    /// ```
+    pub fn to_proto_bytes(&self) -> Binary {
+        let (field, value) = match self {
+            StdAck::Success(data) => (PROTO_FIELD_RESULT, data.as_slice()),
+            StdAck::Error(err) => (PROTO_FIELD_ERROR, err.as_bytes()),
+        };
+        let mut out = Vec::with_capacity(value.len() + 7);
+        encode_bytes_field(&mut out, field, value);
+        Binary::new(out)
+    }
+
+    /// Parses an ack in the protobuf `Acknowledgement` format of ibc-go.
+    /// See [`StdAck::to_proto_bytes`].
+    ///
+    /// Unknown fields are skipped. As usual for a protobuf `oneof`, the last
+    /// occurrence of `result` or `error` wins. Errors if neither of them is set.
+    pub fn from_proto_bytes(data: &[u8]) -> StdResult<Self> {
+        let mut ack = None;
+        decode_bytes_fields(data, "StdAck", |field, value| {
+            match field {
+                PROTO_FIELD_RESULT => ack = Some(StdAck::success(value)),
+                PROTO_FIELD_ERROR => {
+                    let err = core::str::from_utf8(value)
+                        .map_err(|_| proto_err("error is not valid UTF-8"))?;
+                    ack = Some(StdAck::error(err));
+                }
+                _ => {}
+            }
+            Ok(())
+        })?;
+        ack.ok_or_else(|| proto_err("neither result nor error set"))
+    }
+
     pub fn unwrap(self) -> Binary {
         match self {
             StdAck::Success(data) => data,
@@ -113,6 +164,10 @@ impl StdAck {
     }
 }
 
+fn proto_err(msg: impl ToString) -> StdError {
+    StdError::parse_err("StdAck", msg)
+}
+
 impl From<StdAck> for Binary {
     fn from(original: StdAck) -> Binary {
         original.to_binary()
@@ -122,6 +177,8 @@ impl From<StdAck> for Binary {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::from_json;
+    use proptest::{prop_assert_eq, property_test};
 
     #[test]
     fn stdack_success_works() {
@@ -161,4 +218,95 @@ mod tests {
         let ack2 = StdAck::error("kaputt");
         assert_eq!(ack2.to_binary(), br#"{"error":"kaputt"}"#);
     }
+
+    #[test]
+    fn stdack_to_proto_bytes_works() {
+        // ibc-go's NewResultAcknowledgement([]byte{byte(1)}) as used by ICS-20
+        let ack = StdAck::success(b"\x01");
+        assert_eq!(ack.to_proto_bytes(), b"\xaa\x01\x01\x01");
+
+        // ibc-go's NewErrorAcknowledgement for an error with ABCI code 1
+        let ack = StdAck::error("ABCI code: 1: error handling packet: see events for details");
+        let mut expected = b"\xb2\x01\x3b".to_vec();
+        expected.extend_from_slice(b"ABCI code: 1: error handling packet: see events for details");
+        assert_eq!(ack.to_proto_bytes(), expected);
+
+        // empty values are still encoded since they are part of a oneof
+        assert_eq!(StdAck::success(b"").to_proto_bytes(), b"\xaa\x01\x00");
+        assert_eq!(StdAck::error("").to_proto_bytes(), b"\xb2\x01\x00");
+
+        // multi byte length
+        let ack = StdAck::success(vec![0xff; 300]);
+        let bytes = ack.to_proto_bytes();
+        assert_eq!(&bytes[..4], b"\xaa\x01\xac\x02");
+        assert_eq!(bytes.len(), 304);
+    }
+
+    #[test]
+    fn stdack_from_proto_bytes_works() {
+        let ack = StdAck::from_proto_bytes(b"\xaa\x01\x01\x01").unwrap();
+        assert_eq!(ack, StdAck::success(b"\x01"));
+
+        let ack = StdAck::from_proto_bytes(b"\xb2\x01\x06kaputt").unwrap();
+        assert_eq!(ack, StdAck::error("kaputt"));
+
+        let ack = StdAck::from_proto_bytes(b"\xaa\x01\x00").unwrap();
+        assert_eq!(ack, StdAck::success(b""));
+
+        // unknown fields of all wire types are skipped
+        let ack = StdAck::from_proto_bytes(
+            b"\x08\x96\x01\x11\x01\x02\x03\x04\x05\x06\x07\x08\x1a\x02ab\x25\x01\x02\x03\x04\xaa\x01\x01\x07",
+        )
+        .unwrap();
+        assert_eq!(ack, StdAck::success(b"\x07"));
+
+        // last value of the oneof wins
+        let ack = StdAck::from_proto_bytes(b"\xaa\x01\x01\x01\xb2\x01\x03bad").unwrap();
+        assert_eq!(ack, StdAck::error("bad"));
+        let ack = StdAck::from_proto_bytes(b"\xb2\x01\x03bad\xaa\x01\x01\x01").unwrap();
+        assert_eq!(ack, StdAck::success(b"\x01"));
+    }
+
+    #[test]
+    fn stdack_from_proto_bytes_errors_for_invalid_data() {
+        for data in [
+            // empty
+            &b""[..],
+            // only unknown fields
+            b"\x08\x01",
+            // truncated value
+            b"\xaa\x01\x05\x01\x02",
+            // truncated key
+            b"\xaa",
+            // error is not UTF-8
+            b"\xb2\x01\x01\xff",
+            // unsupported wire type (start group)
+            b"\xab\x01",
+            // JSON
+            br#"{"result":"AQ=="}"#,
+        ] {
+            let err = StdAck::from_proto_bytes(data).unwrap_err();
+            assert!(matches!(err, StdError::ParseErr { .. }), "{err:?}");
+        }
+    }
+
+    #[property_test]
+    fn stdack_success_roundtrips(data: Vec<u8>) {
+        let ack = StdAck::success(data);
+        prop_assert_eq!(
+            StdAck::from_proto_bytes(&ack.to_proto_bytes()).unwrap(),
+            ack.clone()
+        );
+        prop_assert_eq!(from_json::<StdAck>(ack.to_binary()).unwrap(), ack);
+    }
+
+    #[property_test]
+    fn stdack_error_roundtrips(err: String) {
+        let ack = StdAck::error(err);
+        prop_assert_eq!(
+            StdAck::from_proto_bytes(&ack.to_proto_bytes()).unwrap(),
+            ack.clone()
+        );
+        prop_assert_eq!(from_json::<StdAck>(ack.to_binary()).unwrap(), ack);
+    }
 }
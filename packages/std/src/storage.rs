@@ -12,7 +12,7 @@ use crate::traits::Storage;
 
 #[derive(Default)]
 pub struct MemoryStorage {
-    data: BTreeMap<Vec<u8>, Vec<u8>>,
+    pub(crate) data: BTreeMap<Vec<u8>, Vec<u8>>,
 }
 
 impl MemoryStorage {
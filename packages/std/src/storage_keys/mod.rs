@@ -1,5 +1,11 @@
 mod length_prefixed;
+#[cfg(feature = "iterator")]
+mod prefixed_range;
 
 // Please note that the entire storage_keys module is public. So be careful
 // when adding elements here.
 pub use length_prefixed::{namespace_with_key, to_length_prefixed, to_length_prefixed_nested};
+#[cfg(feature = "iterator")]
+pub use prefixed_range::{
+    prefix_end, range_keys_with_prefix, range_values_with_prefix, range_with_prefix,
+};
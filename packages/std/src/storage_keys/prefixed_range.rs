@@ -0,0 +1,260 @@
+//! Iteration over all keys sharing a common prefix, e.g. all keys of a namespace
+//! created by [`to_length_prefixed`](super::to_length_prefixed).
+//!
+//! The bounds passed to these functions are relative to the prefix and the prefix
+//! is stripped from the returned keys.
+
+use crate::prelude::*;
+use crate::{Order, Record, Storage};
+
+/// Returns the smallest key that is greater than all keys starting with `prefix`,
+/// i.e. the exclusive upper bound for ranging over the prefix.
+///
+/// Trailing 0xFF bytes cannot be incremented and are dropped. `None` is returned
+/// if there is no such bound because the prefix is empty or consists of 0xFF bytes only.
+/// In this case the range over the prefix is unbounded at the end.
+pub fn prefix_end(prefix: &[u8]) -> Option<Vec<u8>> {
+    let last = prefix.iter().rposition(|&byte| byte != 0xFF)?;
+    let mut end = prefix[..=last].to_vec();
+    end[last] += 1;
+    Some(end)
+}
+
+/// Calculates the absolute bounds for ranging over `prefix` with the relative bounds `start`
+/// (inclusive) and `end` (exclusive).
+fn prefixed_bounds(
+    prefix: &[u8],
+    start: Option<&[u8]>,
+    end: Option<&[u8]>,
+) -> (Vec<u8>, Option<Vec<u8>>) {
+    let start = concat(prefix, start.unwrap_or_default());
+    let end = match end {
+        Some(end) => Some(concat(prefix, end)),
+        None => prefix_end(prefix),
+    };
+    (start, end)
+}
+
+fn concat(prefix: &[u8], key: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(prefix.len() + key.len());
+    out.extend_from_slice(prefix);
+    out.extend_from_slice(key);
+    out
+}
+
+/// Iterates over all key/value pairs whose key starts with `prefix`, either forwards or backwards.
+///
+/// The bounds `start` (inclusive) and `end` (exclusive) are relative to the prefix
+/// and the prefix is removed from the returned keys.
+///
+/// ## Examples
+///
+/// ```
+/// # use cosmwasm_std::{MemoryStorage, Order, Storage};
+/// use cosmwasm_std::storage_keys::{range_with_prefix, to_length_prefixed};
+///
+/// let mut storage = MemoryStorage::new();
+/// let namespace = to_length_prefixed(b"scores");
+/// for (key, value) in [(b"alice", b"10"), (b"bobby", b"20"), (b"carol", b"30")] {
+///     storage.set(&[namespace.as_slice(), key.as_slice()].concat(), value);
+/// }
+/// storage.set(b"other", b"40");
+///
+/// // latest two entries
+/// let latest: Vec<_> = range_with_prefix(&storage, &namespace, None, None, Order::Descending)
+///     .take(2)
+///     .collect();
+/// assert_eq!(
+///     latest,
+///     [(b"carol".to_vec(), b"30".to_vec()), (b"bobby".to_vec(), b"20".to_vec())]
+/// );
+/// ```
+pub fn range_with_prefix<'a>(
+    storage: &'a dyn Storage,
+    prefix: &[u8],
+    start: Option<&[u8]>,
+    end: Option<&[u8]>,
+    order: Order,
+) -> Box<dyn Iterator<Item = Record> + 'a> {
+    let (start, end) = prefixed_bounds(prefix, start, end);
+    let prefix_len = prefix.len();
+    Box::new(
+        storage
+            .range(Some(&start), end.as_deref(), order)
+            .map(move |(key, value)| (key[prefix_len..].to_vec(), value)),
+    )
+}
+
+/// Iterates over all keys starting with `prefix`, either forwards or backwards.
+///
+/// The bounds `start` (inclusive) and `end` (exclusive) are relative to the prefix
+/// and the prefix is removed from the returned keys.
+pub fn range_keys_with_prefix<'a>(
+    storage: &'a dyn Storage,
+    prefix: &[u8],
+    start: Option<&[u8]>,
+    end: Option<&[u8]>,
+    order: Order,
+) -> Box<dyn Iterator<Item = Vec<u8>> + 'a> {
+    let (start, end) = prefixed_bounds(prefix, start, end);
+    let prefix_len = prefix.len();
+    Box::new(
+        storage
+            .range_keys(Some(&start), end.as_deref(), order)
+            .map(move |key| key[prefix_len..].to_vec()),
+    )
+}
+
+/// Iterates over the values of all keys starting with `prefix`, either forwards or backwards.
+///
+/// The bounds `start` (inclusive) and `end` (exclusive) are relative to the prefix.
+pub fn range_values_with_prefix<'a>(
+    storage: &'a dyn Storage,
+    prefix: &[u8],
+    start: Option<&[u8]>,
+    end: Option<&[u8]>,
+    order: Order,
+) -> Box<dyn Iterator<Item = Vec<u8>> + 'a> {
+    let (start, end) = prefixed_bounds(prefix, start, end);
+    storage.range_values(Some(&start), end.as_deref(), order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage_keys::to_length_prefixed;
+    use crate::MemoryStorage;
+
+    /// Bytes around the edges of the byte range
+    const EDGE_BYTES: [u8; 4] = [0x00, 0x01, 0xFE, 0xFF];
+
+    /// All keys of length 1 to `max_len` consisting of [`EDGE_BYTES`]
+    fn edge_keys(max_len: usize) -> Vec<Vec<u8>> {
+        let mut keys = vec![vec![]];
+        let mut out = vec![];
+        for _ in 0..max_len {
+            keys = keys
+                .iter()
+                .flat_map(|key| {
+                    EDGE_BYTES.iter().map(move |&byte| {
+                        let mut key = key.clone();
+                        key.push(byte);
+                        key
+                    })
+                })
+                .collect();
+            out.extend(keys.iter().cloned());
+        }
+        out
+    }
+
+    #[test]
+    fn prefix_end_works() {
+        assert_eq!(prefix_end(b""), None);
+        assert_eq!(prefix_end(b"\x00"), Some(b"\x01".to_vec()));
+        assert_eq!(prefix_end(b"\xFE"), Some(b"\xFF".to_vec()));
+        assert_eq!(prefix_end(b"\xFF"), None);
+        assert_eq!(prefix_end(b"\xFF\xFF\xFF"), None);
+        assert_eq!(prefix_end(b"foo"), Some(b"fop".to_vec()));
+        assert_eq!(prefix_end(b"\x00\x03foo"), Some(b"\x00\x03fop".to_vec()));
+        assert_eq!(prefix_end(b"a\xFF"), Some(b"b".to_vec()));
+        assert_eq!(prefix_end(b"a\xFF\xFF"), Some(b"b".to_vec()));
+        assert_eq!(prefix_end(b"\xFF\xFEa\xFF"), Some(b"\xFF\xFEb".to_vec()));
+        assert_eq!(prefix_end(b"\xFF\xFE\xFF"), Some(b"\xFF\xFF".to_vec()));
+    }
+
+    #[test]
+    fn prefix_end_is_exclusive_upper_bound() {
+        for prefix in edge_keys(3) {
+            let end = prefix_end(&prefix);
+            for key in edge_keys(4) {
+                let below_end = match &end {
+                    Some(end) => key < *end,
+                    None => true,
+                };
+                let in_range = key >= prefix && below_end;
+                assert_eq!(in_range, key.starts_with(&prefix), "{prefix:?} {key:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn range_with_prefix_works_at_namespace_boundaries() {
+        let mut storage = MemoryStorage::new();
+        let keys = edge_keys(3);
+        for key in &keys {
+            storage.set(key, key);
+        }
+
+        let mut bounds = vec![None];
+        bounds.extend(edge_keys(1).into_iter().map(Some));
+        bounds.push(Some(b"\xFF\xFF".to_vec()));
+
+        let mut prefixes = vec![vec![]];
+        prefixes.extend(edge_keys(2));
+
+        for prefix in &prefixes {
+            for start in &bounds {
+                for end in &bounds {
+                    let mut expected: Vec<Vec<u8>> = keys
+                        .iter()
+                        .filter(|key| key.starts_with(prefix))
+                        .map(|key| key[prefix.len()..].to_vec())
+                        .filter(|key| start.iter().all(|start| key >= start))
+                        .filter(|key| end.iter().all(|end| key < end))
+                        .collect();
+                    expected.sort();
+
+                    for order in [Order::Ascending, Order::Descending] {
+                        if order == Order::Descending {
+                            expected.reverse();
+                        }
+                        let (start, end) = (start.as_deref(), end.as_deref());
+
+                        let records: Vec<_> =
+                            range_with_prefix(&storage, prefix, start, end, order).collect();
+                        let expected_records: Vec<_> = expected
+                            .iter()
+                            .map(|key| (key.clone(), [prefix.as_slice(), key.as_slice()].concat()))
+                            .collect();
+                        assert_eq!(records, expected_records, "{prefix:?} {start:?} {end:?}");
+
+                        let keys: Vec<_> =
+                            range_keys_with_prefix(&storage, prefix, start, end, order).collect();
+                        assert_eq!(keys, expected);
+
+                        let values: Vec<_> =
+                            range_values_with_prefix(&storage, prefix, start, end, order).collect();
+                        let expected_values: Vec<_> = expected_records
+                            .into_iter()
+                            .map(|(_, value)| value)
+                            .collect();
+                        assert_eq!(values, expected_values);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn range_with_prefix_ignores_other_namespaces() {
+        let mut storage = MemoryStorage::new();
+        let namespace = to_length_prefixed(b"foo");
+        storage.set(&[namespace.as_slice(), b"a"].concat(), b"1");
+        storage.set(&[namespace.as_slice(), b"b"].concat(), b"2");
+        storage.set(b"\x00\x03fon", b"3");
+        storage.set(b"\x00\x03fop", b"4");
+        storage.set(&to_length_prefixed(b"fo"), b"5");
+        storage.set(&to_length_prefixed(b"foo\x00"), b"6");
+
+        let records: Vec<_> =
+            range_with_prefix(&storage, &namespace, None, None, Order::Descending).collect();
+        assert_eq!(
+            records,
+            [
+                (b"b".to_vec(), b"2".to_vec()),
+                (b"a".to_vec(), b"1".to_vec())
+            ]
+        );
+    }
+}
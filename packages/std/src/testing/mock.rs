@@ -9,6 +9,7 @@ use bech32::{encode, Bech32, Hrp};
 use core::marker::PhantomData;
 #[cfg(feature = "cosmwasm_1_3")]
 use core::ops::Bound;
+use core::time::Duration;
 use rand_core::OsRng;
 use serde::de::DeserializeOwned;
 #[cfg(feature = "stargate")]
@@ -23,15 +24,19 @@ use crate::ibc::{
     IbcEndpoint, IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg,
     IbcTimeoutBlock,
 };
+#[cfg(feature = "cosmwasm_1_2")]
+use crate::query::CodeInfoResponse;
 #[cfg(feature = "cosmwasm_1_1")]
 use crate::query::SupplyResponse;
+#[cfg(all(feature = "staking", feature = "cosmwasm_2_3"))]
+use crate::query::UnbondingDelegationsResponse;
 use crate::query::{
     AllBalanceResponse, BalanceResponse, BankQuery, CustomQuery, QueryRequest, WasmQuery,
 };
 #[cfg(feature = "staking")]
 use crate::query::{
     AllDelegationsResponse, AllValidatorsResponse, BondedDenomResponse, DelegationResponse,
-    FullDelegation, StakingQuery, Validator, ValidatorResponse,
+    FullDelegation, StakingQuery, UnbondingDelegation, Validator, ValidatorResponse,
 };
 #[cfg(feature = "cosmwasm_1_3")]
 use crate::query::{DelegatorWithdrawAddressResponse, DistributionQuery};
@@ -39,6 +44,8 @@ use crate::results::{ContractResult, Empty, SystemResult};
 use crate::storage::MemoryStorage;
 use crate::traits::{Api, Querier, QuerierResult};
 use crate::types::{BlockInfo, ContractInfo, Env, MessageInfo, TransactionInfo};
+#[cfg(feature = "cosmwasm_2_0")]
+use crate::GrpcQuery;
 use crate::{from_json, to_json_binary, Binary, Uint128};
 #[cfg(feature = "cosmwasm_1_3")]
 use crate::{
@@ -270,6 +277,14 @@ impl Api for MockApi {
         )?)
     }
 
+    fn ed25519_validate_pubkey(&self, public_key: &[u8]) -> bool {
+        cosmwasm_crypto::ed25519_validate_pubkey(public_key).is_ok()
+    }
+
+    fn hmac_sha256(&self, key: &[u8], data: &[u8]) -> [u8; 32] {
+        cosmwasm_crypto::hmac_sha256(key, data)
+    }
+
     fn debug(&self, #[allow(unused)] message: &str) {
         println!("{message}");
     }
@@ -392,6 +407,8 @@ fn validate_length(bytes: &[u8]) -> StdResult<()> {
 ///
 /// // `env3` is one block and 5.5 seconds later
 /// ```
+///
+/// In order to simulate a longer sequence of blocks, use [`Envs`].
 pub fn mock_env() -> Env {
     let contract_addr = MockApi::default().addr_make("cosmos2contract");
     Env {
@@ -407,6 +424,128 @@ pub fn mock_env() -> Env {
     }
 }
 
+/// Options for creating an [`Envs`] instance
+#[derive(Clone, Debug)]
+pub struct EnvsOptions {
+    /// The bech32 prefix used for the contract address
+    pub bech32_prefix: &'static str,
+    /// The amount of time by which `block.time` is increased for every new environment
+    pub block_time_step: Duration,
+    /// The block height of the first environment
+    pub initial_height: u64,
+    /// The block time of the first environment
+    pub initial_time: Timestamp,
+    pub chain_id: String,
+}
+
+impl Default for EnvsOptions {
+    /// The default options produce the same block and contract as [`mock_env`]
+    /// for the first environment and advance by 5 seconds per block.
+    fn default() -> Self {
+        EnvsOptions {
+            bech32_prefix: BECH32_PREFIX,
+            block_time_step: Duration::from_secs(5),
+            initial_height: 12_345,
+            initial_time: Timestamp::from_nanos(1_571_797_419_879_305_533),
+            chain_id: "cosmos-testnet-14002".to_string(),
+        }
+    }
+}
+
+/// Creates a sequence of environments for testing, one per block.
+///
+/// Every environment is one block later than the previous one, i.e. `block.height`
+/// increases by 1 and `block.time` increases by [`EnvsOptions::block_time_step`].
+/// Since every environment belongs to a new block, `transaction.index` is reset to 0 for
+/// each of them. If you want to simulate multiple transactions in the same block,
+/// clone the environment and increment the index manually.
+///
+/// ## Examples
+///
+/// ```
+/// use core::time::Duration;
+/// use cosmwasm_std::testing::{Envs, EnvsOptions};
+///
+/// let mut envs = Envs::with_options(EnvsOptions {
+///     block_time_step: Duration::from_millis(5_500),
+///     ..Default::default()
+/// });
+///
+/// let env1 = envs.next().unwrap();
+/// let env2 = envs.next().unwrap();
+/// assert_eq!(env2.block.height, env1.block.height + 1);
+/// assert_eq!(env2.block.time, env1.block.time.plus_nanos(5_500_000_000));
+/// assert_eq!(env2.transaction.unwrap().index, 0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Envs {
+    chain_id: String,
+    contract_address: Addr,
+    block_time_step: Duration,
+    next_height: u64,
+    next_time: Timestamp,
+}
+
+impl Envs {
+    /// Creates an instance with the given bech32 prefix and default options otherwise
+    pub fn new(bech32_prefix: &'static str) -> Self {
+        Self::with_options(EnvsOptions {
+            bech32_prefix,
+            ..Default::default()
+        })
+    }
+
+    pub fn with_options(options: EnvsOptions) -> Self {
+        let api = MockApi::default().with_prefix(options.bech32_prefix);
+        Envs {
+            chain_id: options.chain_id,
+            contract_address: api.addr_make("cosmos2contract"),
+            block_time_step: options.block_time_step,
+            next_height: options.initial_height,
+            next_time: options.initial_time,
+        }
+    }
+
+    /// Creates the environment for the next block.
+    ///
+    /// Panics if the block height or time overflows.
+    pub fn make(&mut self) -> Env {
+        let height = self.next_height;
+        let time = self.next_time;
+        self.next_height = height.checked_add(1).expect("Block height overflow");
+        self.next_time = time
+            .checked_add_duration(self.block_time_step)
+            .expect("Block time overflow");
+
+        Env {
+            block: BlockInfo {
+                height,
+                time,
+                chain_id: self.chain_id.clone(),
+            },
+            transaction: Some(TransactionInfo { index: 0 }),
+            contract: ContractInfo {
+                address: self.contract_address.clone(),
+            },
+        }
+    }
+}
+
+impl Default for Envs {
+    fn default() -> Self {
+        Self::with_options(EnvsOptions::default())
+    }
+}
+
+/// An infinite iterator of environments, one per block
+impl Iterator for Envs {
+    type Item = Env;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.make())
+    }
+}
+
 /// Just set sender and funds for the message.
 /// This is intended for use in test code only.
 #[deprecated(note = "This is inconvenient and unsafe. Use message_info instead.")]
@@ -592,6 +731,8 @@ pub struct MockQuerier<C: DeserializeOwned = Empty> {
     #[cfg(feature = "cosmwasm_1_3")]
     pub distribution: DistributionQuerier,
     wasm: WasmQuerier,
+    #[cfg(feature = "cosmwasm_2_0")]
+    grpc: GrpcQuerier,
     #[cfg(feature = "stargate")]
     pub ibc: IbcQuerier,
     /// A handler to handle custom queries. This is set to a dummy handler that
@@ -610,6 +751,8 @@ impl<C: DeserializeOwned> MockQuerier<C> {
             #[cfg(feature = "staking")]
             staking: StakingQuerier::default(),
             wasm: WasmQuerier::default(),
+            #[cfg(feature = "cosmwasm_2_0")]
+            grpc: GrpcQuerier::default(),
             #[cfg(feature = "stargate")]
             ibc: IbcQuerier::default(),
             // strange argument notation suggested as a workaround here: https://github.com/rust-lang/rust/issues/41078#issuecomment-294296365
@@ -628,6 +771,78 @@ impl<C: DeserializeOwned> MockQuerier<C> {
         self.wasm.update_handler(handler)
     }
 
+    /// Registers a handler for `GrpcQuery`s with the given path,
+    /// e.g. "/cosmos.bank.v1beta1.Query/Balance".
+    ///
+    /// The handler receives the protobuf encoded request data and returns the protobuf
+    /// encoded response data. Registering a handler for a path that already has one
+    /// replaces the old handler. Queries for paths without a handler return
+    /// a `SystemError::UnsupportedRequest`.
+    #[cfg(feature = "cosmwasm_2_0")]
+    pub fn update_grpc<GH>(&mut self, path: impl Into<String>, handler: GH)
+    where
+        GH: Fn(&Binary) -> Binary + 'static,
+    {
+        self.grpc.update_handler(path.into(), handler)
+    }
+
+    /// Sets the contracts returned for `WasmQuery::ContractsByCode` with the given code ID.
+    /// The contracts are returned in the given order.
+    #[cfg(feature = "cosmwasm_2_3")]
+    pub fn with_contracts_by_code(mut self, code_id: u64, contracts: Vec<Addr>) -> Self {
+        self.wasm.set_contracts_by_code(code_id, contracts);
+        self
+    }
+
+    /// Registers the response for `WasmQuery::CodeInfo` with the code ID of the given info.
+    ///
+    /// Queries for code IDs without registered info are forwarded to the handler set via
+    /// [`MockQuerier::update_wasm`].
+    #[cfg(feature = "cosmwasm_1_2")]
+    pub fn with_code_info(mut self, info: CodeInfoResponse) -> Self {
+        self.wasm.set_code_info(info);
+        self
+    }
+
+    /// Registers a handler for `WasmQuery::Smart` queries to the given contract.
+    /// The handler receives the raw query message and returns the contract's response,
+    /// which allows simulating the query logic of another contract.
+    ///
+    /// Smart queries to contracts without a registered handler as well as all other
+    /// Wasm queries are forwarded to the handler set via [`MockQuerier::update_wasm`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use cosmwasm_std::testing::MockQuerier;
+    /// # use cosmwasm_schema::cw_serde;
+    /// # use cosmwasm_std::{from_json, to_json_binary, ContractResult, QuerierWrapper, Empty};
+    /// #[cw_serde]
+    /// enum SiblingQueryMsg {
+    ///     Double { value: u64 },
+    /// }
+    ///
+    /// let querier: MockQuerier = MockQuerier::new(&[]).with_smart_handler("sibling", |msg| {
+    ///     match from_json(msg) {
+    ///         Ok(SiblingQueryMsg::Double { value }) => to_json_binary(&(value * 2)).into(),
+    ///         Err(err) => ContractResult::Err(err.to_string()),
+    ///     }
+    /// });
+    ///
+    /// let wrapper = QuerierWrapper::<Empty>::new(&querier);
+    /// let doubled: u64 = wrapper
+    ///     .query_wasm_smart("sibling", &SiblingQueryMsg::Double { value: 21 })
+    ///     .unwrap();
+    /// assert_eq!(doubled, 42);
+    /// ```
+    pub fn with_smart_handler<SH>(mut self, contract_addr: impl Into<String>, handler: SH) -> Self
+    where
+        SH: Fn(Binary) -> ContractResult<Binary> + 'static,
+    {
+        self.wasm.set_smart_handler(contract_addr.into(), handler);
+        self
+    }
+
     pub fn with_custom_handler<CH>(mut self, handler: CH) -> Self
     where
         CH: Fn(&C) -> MockQuerierCustomHandlerResult + 'static,
@@ -676,11 +891,15 @@ impl<C: CustomQuery + DeserializeOwned> MockQuerier<C> {
                 kind: "Stargate".to_string(),
             }),
             #[cfg(feature = "cosmwasm_2_0")]
-            QueryRequest::Grpc(_) => SystemResult::Err(SystemError::UnsupportedRequest {
-                kind: "GRPC".to_string(),
-            }),
+            QueryRequest::Grpc(grpc_query) => self.grpc.query(grpc_query),
             #[cfg(feature = "stargate")]
             QueryRequest::Ibc(msg) => self.ibc.query(msg),
+            #[cfg(feature = "cosmwasm_2_3")]
+            QueryRequest::Multi(requests) => {
+                let results: Vec<QuerierResult> =
+                    requests.iter().map(|req| self.handle_query(req)).collect();
+                SystemResult::Ok(to_json_binary(&results).into())
+            }
         }
     }
 }
@@ -691,11 +910,29 @@ struct WasmQuerier {
     ///
     /// Use box to avoid the need of generic type.
     handler: Box<dyn for<'a> Fn(&'a WasmQuery) -> QuerierResult>,
+    /// The contracts returned for `WasmQuery::ContractsByCode`, by code ID.
+    /// Queries for code IDs not in this map are forwarded to the handler.
+    #[cfg(feature = "cosmwasm_2_3")]
+    contracts_by_code: BTreeMap<u64, Vec<Addr>>,
+    /// The responses for `WasmQuery::CodeInfo`, by code ID.
+    /// Queries for code IDs not in this map are forwarded to the handler.
+    #[cfg(feature = "cosmwasm_1_2")]
+    code_infos: BTreeMap<u64, CodeInfoResponse>,
+    /// Handlers for `WasmQuery::Smart`, by contract address.
+    /// Queries to contracts not in this map are forwarded to the handler.
+    smart_handlers: BTreeMap<String, Box<dyn Fn(Binary) -> ContractResult<Binary>>>,
 }
 
 impl WasmQuerier {
     fn new(handler: Box<dyn for<'a> Fn(&'a WasmQuery) -> QuerierResult>) -> Self {
-        Self { handler }
+        Self {
+            handler,
+            #[cfg(feature = "cosmwasm_2_3")]
+            contracts_by_code: BTreeMap::new(),
+            #[cfg(feature = "cosmwasm_1_2")]
+            code_infos: BTreeMap::new(),
+            smart_handlers: BTreeMap::new(),
+        }
     }
 
     fn update_handler<WH>(&mut self, handler: WH)
@@ -705,7 +942,64 @@ impl WasmQuerier {
         self.handler = Box::from(handler)
     }
 
+    #[cfg(feature = "cosmwasm_2_3")]
+    fn set_contracts_by_code(&mut self, code_id: u64, contracts: Vec<Addr>) {
+        self.contracts_by_code.insert(code_id, contracts);
+    }
+
+    #[cfg(feature = "cosmwasm_1_2")]
+    fn set_code_info(&mut self, info: CodeInfoResponse) {
+        self.code_infos.insert(info.code_id, info);
+    }
+
+    fn set_smart_handler<SH>(&mut self, contract_addr: String, handler: SH)
+    where
+        SH: Fn(Binary) -> ContractResult<Binary> + 'static,
+    {
+        self.smart_handlers
+            .insert(contract_addr, Box::from(handler));
+    }
+
     fn query(&self, request: &WasmQuery) -> QuerierResult {
+        if let WasmQuery::Smart { contract_addr, msg } = request {
+            if let Some(handler) = self.smart_handlers.get(contract_addr) {
+                return SystemResult::Ok(handler(msg.clone()));
+            }
+        }
+
+        #[cfg(feature = "cosmwasm_1_2")]
+        if let WasmQuery::CodeInfo { code_id } = request {
+            if let Some(info) = self.code_infos.get(code_id) {
+                return SystemResult::Ok(to_json_binary(info).into());
+            }
+        }
+
+        #[cfg(feature = "cosmwasm_2_3")]
+        if let WasmQuery::ContractsByCode {
+            code_id,
+            start_after,
+            limit,
+        } = request
+        {
+            if let Some(contracts) = self.contracts_by_code.get(code_id) {
+                let start = match start_after {
+                    Some(start_after) => contracts
+                        .iter()
+                        .position(|addr| addr.as_str() == start_after)
+                        .map_or(contracts.len(), |pos| pos + 1),
+                    None => 0,
+                };
+                let limit = limit.map_or(usize::MAX, |limit| limit as usize);
+                let contracts = contracts[start..]
+                    .iter()
+                    .take(limit)
+                    .map(|addr| addr.to_string())
+                    .collect();
+                let response = crate::ContractsByCodeResponse { contracts };
+                return SystemResult::Ok(to_json_binary(&response).into());
+            }
+        }
+
         (*self.handler)(request)
     }
 }
@@ -727,6 +1021,10 @@ impl Default for WasmQuerier {
                 WasmQuery::CodeInfo { code_id, .. } => {
                     SystemError::NoSuchCode { code_id: *code_id }
                 }
+                #[cfg(feature = "cosmwasm_2_3")]
+                WasmQuery::ContractsByCode { code_id, .. } => {
+                    SystemError::NoSuchCode { code_id: *code_id }
+                }
             };
             SystemResult::Err(err)
         });
@@ -734,6 +1032,37 @@ impl Default for WasmQuerier {
     }
 }
 
+#[cfg(feature = "cosmwasm_2_0")]
+type GrpcHandler = Box<dyn Fn(&Binary) -> Binary>;
+
+#[cfg(feature = "cosmwasm_2_0")]
+#[derive(Default)]
+struct GrpcQuerier {
+    /// Handlers for gRPC queries, by path.
+    ///
+    /// Use box to avoid the need of generic type.
+    handlers: BTreeMap<String, GrpcHandler>,
+}
+
+#[cfg(feature = "cosmwasm_2_0")]
+impl GrpcQuerier {
+    fn update_handler<GH>(&mut self, path: String, handler: GH)
+    where
+        GH: Fn(&Binary) -> Binary + 'static,
+    {
+        self.handlers.insert(path, Box::from(handler));
+    }
+
+    fn query(&self, request: &GrpcQuery) -> QuerierResult {
+        match self.handlers.get(&request.path) {
+            Some(handler) => SystemResult::Ok(ContractResult::Ok(handler(&request.data))),
+            None => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: format!("GRPC {}", request.path),
+            }),
+        }
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct BankQuerier {
     #[allow(dead_code)]
@@ -744,6 +1073,8 @@ pub struct BankQuerier {
     balances: BTreeMap<String, Vec<Coin>>,
     /// Vec<Metadata>
     denom_metadata: BTreeMap<Vec<u8>, DenomMetadata>,
+    /// BTreeMap<denom, amount> of all coins burned via [`BankQuerier::burn`]
+    burned: BTreeMap<String, Uint128>,
 }
 
 impl BankQuerier {
@@ -757,7 +1088,52 @@ impl BankQuerier {
             supplies: Self::calculate_supplies(&balances),
             balances,
             denom_metadata: BTreeMap::new(),
+            burned: BTreeMap::new(),
+        }
+    }
+
+    /// Removes the given coins from the balance of the given address and the total supply,
+    /// like the chain does when processing a `BankMsg::Burn` sent by that address.
+    ///
+    /// The querier does not see the messages a contract emits. Call this for every
+    /// `BankMsg::Burn` in a `Response` to keep balances up to date and assert the burned
+    /// amounts via [`BankQuerier::burned`].
+    ///
+    /// Errors if the address does not have enough funds. In this case the state is not changed.
+    pub fn burn(&mut self, addr: impl Into<String>, amount: &[Coin]) -> StdResult<()> {
+        let addr = addr.into();
+        let mut balance = self.balances.get(&addr).cloned().unwrap_or_default();
+
+        for coin in amount {
+            let available = balance
+                .iter_mut()
+                .find(|c| c.denom == coin.denom)
+                .map(|c| &mut c.amount);
+            match available {
+                Some(available) if *available >= coin.amount => *available -= coin.amount,
+                _ => {
+                    return Err(StdError::generic_err(format!(
+                        "Insufficient funds to burn {coin} from {addr}"
+                    )))
+                }
+            }
         }
+        balance.retain(|c| !c.amount.is_zero());
+
+        for coin in amount {
+            *self
+                .burned
+                .entry(coin.denom.clone())
+                .or_insert_with(Uint128::zero) += coin.amount;
+        }
+        self.balances.insert(addr, balance);
+        self.supplies = Self::calculate_supplies(&self.balances);
+        Ok(())
+    }
+
+    /// Returns the total amount of the given denom burned via [`BankQuerier::burn`].
+    pub fn burned(&self, denom: &str) -> Uint128 {
+        self.burned.get(denom).copied().unwrap_or_default()
     }
 
     /// set a new balance for the given address and return the old balance
@@ -886,6 +1262,57 @@ impl BankQuerier {
                 let metadata_res = AllDenomMetadataResponse { metadata, next_key };
                 to_json_binary(&metadata_res).into()
             }
+            #[cfg(feature = "cosmwasm_2_3")]
+            BankQuery::AllBalancesPaginated {
+                address,
+                pagination,
+            } => {
+                let default_pagination = PageRequest {
+                    key: None,
+                    limit: 100,
+                    reverse: false,
+                };
+                let pagination = pagination.as_ref().unwrap_or(&default_pagination);
+
+                let balances: BTreeMap<&[u8], &Coin> = self
+                    .balances
+                    .get(address)
+                    .into_iter()
+                    .flatten()
+                    .map(|coin| (coin.denom.as_bytes(), coin))
+                    .collect();
+
+                // range of all denoms after the given key (or until the key for reverse)
+                let range = match (pagination.reverse, &pagination.key) {
+                    (_, None) => (Bound::Unbounded, Bound::Unbounded),
+                    (true, Some(key)) => (Bound::Unbounded, Bound::Included(key.as_slice())),
+                    (false, Some(key)) => (Bound::Included(key.as_slice()), Bound::Unbounded),
+                };
+                let iter = balances.range::<[u8], _>(range);
+                // using dynamic dispatch here to reduce code duplication and since this is only testing code
+                let iter: Box<dyn Iterator<Item = _>> = if pagination.reverse {
+                    Box::new(iter.rev())
+                } else {
+                    Box::new(iter)
+                };
+
+                let mut balances: Vec<Coin> = iter
+                    // take the requested amount + 1 to get the next key
+                    .take((pagination.limit.saturating_add(1)) as usize)
+                    .map(|(_, coin)| (*coin).clone())
+                    .collect();
+
+                // if we took more than requested, remove the last element (the next key),
+                // otherwise this is the last batch
+                let next_key = if balances.len() > pagination.limit as usize {
+                    balances.pop().map(|c| Binary::from(c.denom.as_bytes()))
+                } else {
+                    None
+                };
+
+                let bank_res = crate::PaginatedBalancesResponse { balances, next_key };
+                to_json_binary(&bank_res).into()
+            }
         };
         // system result is always ok in the mock implementation
         SystemResult::Ok(contract_result)
@@ -975,6 +1402,9 @@ pub struct StakingQuerier {
     denom: String,
     validators: Vec<Validator>,
     delegations: Vec<FullDelegation>,
+    /// Only used by `StakingQuery::UnbondingDelegations`, which requires the `cosmwasm_2_3` feature
+    #[cfg_attr(not(feature = "cosmwasm_2_3"), allow(dead_code))]
+    unbonding_delegations: Vec<UnbondingDelegation>,
 }
 
 #[cfg(feature = "staking")]
@@ -984,6 +1414,7 @@ impl StakingQuerier {
             denom: denom.to_string(),
             validators: validators.to_vec(),
             delegations: delegations.to_vec(),
+            unbonding_delegations: vec![],
         }
     }
 
@@ -999,6 +1430,12 @@ impl StakingQuerier {
         self.delegations = delegations.to_vec();
     }
 
+    /// Sets the unbonding delegations returned by `StakingQuery::UnbondingDelegations`.
+    /// This replaces all previously set unbonding delegations.
+    pub fn set_unbonding_delegations(&mut self, unbonding_delegations: &[UnbondingDelegation]) {
+        self.unbonding_delegations = unbonding_delegations.to_vec();
+    }
+
     pub fn query(&self, request: &StakingQuery) -> QuerierResult {
         let contract_result: ContractResult<Binary> = match request {
             StakingQuery::BondedDenom {} => {
@@ -1046,6 +1483,17 @@ impl StakingQuerier {
                 };
                 to_json_binary(&res).into()
             }
+            #[cfg(feature = "cosmwasm_2_3")]
+            StakingQuery::UnbondingDelegations { delegator } => {
+                let delegations: Vec<_> = self
+                    .unbonding_delegations
+                    .iter()
+                    .filter(|d| d.delegator.as_str() == delegator)
+                    .cloned()
+                    .collect();
+                let res = UnbondingDelegationsResponse { delegations };
+                to_json_binary(&res).into()
+            }
         };
         // system result is always ok in the mock implementation
         SystemResult::Ok(contract_result)
@@ -1060,6 +1508,9 @@ pub struct DistributionQuerier {
     rewards: BTreeMap<String, BTreeMap<String, Vec<DecCoin>>>,
     /// Mock of validators that a delegator has bonded to.
     validators: BTreeMap<String, BTreeSet<String>>,
+    /// Mock of the funds held by the community pool.
+    #[cfg_attr(not(feature = "cosmwasm_2_3"), allow(dead_code))]
+    community_pool: Vec<DecCoin>,
 }
 
 #[cfg(feature = "cosmwasm_1_3")]
@@ -1074,6 +1525,12 @@ impl DistributionQuerier {
         }
     }
 
+    /// Sets the funds held by the community pool.
+    pub fn with_community_pool(mut self, pool: Vec<DecCoin>) -> Self {
+        self.community_pool = pool;
+        self
+    }
+
     pub fn set_withdraw_address(
         &mut self,
         delegator_address: impl Into<String>,
@@ -1189,6 +1646,13 @@ impl DistributionQuerier {
                 };
                 to_json_binary(&res).into()
             }
+            #[cfg(feature = "cosmwasm_2_3")]
+            DistributionQuery::CommunityPool {} => {
+                let res = crate::CommunityPoolResponse {
+                    pool: self.community_pool.clone(),
+                };
+                to_json_binary(&res).into()
+            }
         };
         // system result is always ok in the mock implementation
         SystemResult::Ok(contract_result)
@@ -1225,7 +1689,9 @@ mod tests {
     use super::*;
     #[cfg(feature = "cosmwasm_1_3")]
     use crate::DenomUnit;
-    use crate::{coin, coins, instantiate2_address, ContractInfoResponse, HexBinary, Response};
+    use crate::{
+        coin, coins, instantiate2_address, ContractInfoResponse, HexBinary, InputLength, Response,
+    };
     #[cfg(feature = "staking")]
     use crate::{Decimal, Delegation};
     use base64::{engine::general_purpose, Engine};
@@ -1264,6 +1730,73 @@ mod tests {
         assert_eq!(contract_address, Addr::unchecked(MOCK_CONTRACT_ADDR));
     }
 
+    #[test]
+    fn envs_works() {
+        let mut envs = Envs::default();
+
+        let env = envs.next().unwrap();
+        assert_eq!(env.block, mock_env().block);
+        assert_eq!(env.contract, mock_env().contract);
+        assert_eq!(env.transaction, Some(TransactionInfo { index: 0 }));
+
+        let env2 = envs.next().unwrap();
+        assert_eq!(env2.block.height, 12_346);
+        assert_eq!(env2.block.time, env.block.time.plus_seconds(5));
+        assert_eq!(env2.block.chain_id, env.block.chain_id);
+        assert_eq!(env2.transaction, Some(TransactionInfo { index: 0 }));
+        assert_eq!(env2.contract, env.contract);
+
+        let env3 = envs.make();
+        assert_eq!(env3.block.height, 12_347);
+        assert_eq!(env3.block.time, env.block.time.plus_seconds(10));
+    }
+
+    #[test]
+    fn envs_with_options_works() {
+        let envs = Envs::with_options(EnvsOptions {
+            bech32_prefix: "juno",
+            block_time_step: Duration::from_millis(500),
+            initial_height: 1,
+            initial_time: Timestamp::from_seconds(100),
+            chain_id: "juno-1".to_string(),
+        });
+
+        let blocks: Vec<_> = envs.take(3).map(|env| env.block).collect();
+        assert_eq!(
+            blocks,
+            [
+                BlockInfo {
+                    height: 1,
+                    time: Timestamp::from_nanos(100_000_000_000),
+                    chain_id: "juno-1".to_string(),
+                },
+                BlockInfo {
+                    height: 2,
+                    time: Timestamp::from_nanos(100_500_000_000),
+                    chain_id: "juno-1".to_string(),
+                },
+                BlockInfo {
+                    height: 3,
+                    time: Timestamp::from_nanos(101_000_000_000),
+                    chain_id: "juno-1".to_string(),
+                },
+            ]
+        );
+
+        let env = Envs::new("juno").make();
+        assert!(env.contract.address.as_str().starts_with("juno1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Block time overflow")]
+    fn envs_panics_on_time_overflow() {
+        let mut envs = Envs::with_options(EnvsOptions {
+            initial_time: Timestamp::from_nanos(u64::MAX),
+            ..Default::default()
+        });
+        envs.make();
+    }
+
     #[test]
     fn mock_info_works() {
         #[allow(deprecated)]
@@ -1557,7 +2090,10 @@ mod tests {
         let public_key = vec![];
 
         let res = api.secp256k1_verify(&hash, &signature, &public_key);
-        assert_eq!(res.unwrap_err(), VerificationError::InvalidPubkeyFormat);
+        assert_eq!(
+            res.unwrap_err(),
+            VerificationError::InvalidPubkeyFormat { length: None }
+        );
     }
 
     #[test]
@@ -1623,7 +2159,13 @@ mod tests {
         malformed_hash.push(0x8a);
         let result = api.secp256k1_recover_pubkey(&malformed_hash, &signature, recovery_param);
         match result.unwrap_err() {
-            RecoverPubkeyError::InvalidHashFormat => {}
+            RecoverPubkeyError::InvalidHashFormat {
+                length:
+                    Some(InputLength {
+                        expected: 32,
+                        actual: 33,
+                    }),
+            } => {}
             err => panic!("Unexpected error: {err:?}"),
         }
     }
@@ -1668,7 +2210,10 @@ mod tests {
         let public_key = vec![];
 
         let res = api.secp256r1_verify(&hash, &signature, &public_key);
-        assert_eq!(res.unwrap_err(), VerificationError::InvalidPubkeyFormat);
+        assert_eq!(
+            res.unwrap_err(),
+            VerificationError::InvalidPubkeyFormat { length: None }
+        );
     }
 
     #[test]
@@ -1730,7 +2275,13 @@ mod tests {
         malformed_hash.push(0x8a);
         let result = api.secp256r1_recover_pubkey(&malformed_hash, &signature, recovery_param);
         match result.unwrap_err() {
-            RecoverPubkeyError::InvalidHashFormat => {}
+            RecoverPubkeyError::InvalidHashFormat {
+                length:
+                    Some(InputLength {
+                        expected: 32,
+                        actual: 33,
+                    }),
+            } => {}
             err => panic!("Unexpected error: {err:?}"),
         }
     }
@@ -1771,7 +2322,36 @@ mod tests {
         let public_key = vec![];
 
         let res = api.ed25519_verify(&msg, &signature, &public_key);
-        assert_eq!(res.unwrap_err(), VerificationError::InvalidPubkeyFormat);
+        let err = res.unwrap_err();
+        assert_eq!(
+            err,
+            VerificationError::InvalidPubkeyFormat {
+                length: Some(InputLength {
+                    expected: 32,
+                    actual: 0
+                })
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Invalid public key format (expected 32 bytes, got 0 bytes)"
+        );
+    }
+
+    // Basic test. Exhaustive tests on crypto's side (packages/crypto/src/ed25519.rs)
+    #[test]
+    fn ed25519_validate_pubkey_works() {
+        let api = MockApi::default();
+
+        let public_key = hex::decode(ED25519_PUBKEY_HEX).unwrap();
+        assert!(api.ed25519_validate_pubkey(&public_key));
+
+        // the identity is a small-order point
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        assert!(!api.ed25519_validate_pubkey(&identity));
+        assert!(!api.ed25519_validate_pubkey(&public_key[..31]));
+        assert!(!api.ed25519_validate_pubkey(&[]));
     }
 
     // Basic "works" test.
@@ -1826,7 +2406,71 @@ mod tests {
         let public_keys: Vec<&[u8]> = vec![&public_key];
 
         let res = api.ed25519_batch_verify(&msgs, &signatures, &public_keys);
-        assert_eq!(res.unwrap_err(), VerificationError::InvalidPubkeyFormat);
+        let err = res.unwrap_err();
+        assert_eq!(
+            err,
+            VerificationError::InvalidPubkeyFormat {
+                length: Some(InputLength {
+                    expected: 32,
+                    actual: 0
+                })
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Invalid public key format (expected 32 bytes, got 0 bytes)"
+        );
+    }
+
+    #[test]
+    fn hmac_sha256_works() {
+        // Test vectors from https://datatracker.ietf.org/doc/html/rfc4231#section-4
+        let api = MockApi::default();
+
+        // Test Case 1
+        let mac = api.hmac_sha256(&[0x0b; 20], b"Hi There");
+        assert_eq!(
+            mac,
+            hex!("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7")
+        );
+
+        // Test Case 2
+        let mac = api.hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            mac,
+            hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+
+        // Test Case 6 (key larger than block size)
+        let mac = api.hmac_sha256(
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        assert_eq!(
+            mac,
+            hex!("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")
+        );
+    }
+
+    #[test]
+    fn hmac_sha256_verify_works() {
+        let api = MockApi::default();
+        let key = b"Jefe";
+        let data = b"what do ya want for nothing?";
+        let mac = hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+
+        assert!(api.hmac_sha256_verify(key, data, &mac));
+
+        // altered MAC
+        let mut altered = mac;
+        altered[0] ^= 0x01;
+        assert!(!api.hmac_sha256_verify(key, data, &altered));
+
+        // truncated MAC
+        assert!(!api.hmac_sha256_verify(key, data, &mac[..16]));
+
+        // wrong key
+        assert!(!api.hmac_sha256_verify(b"Jeff", data, &mac));
     }
 
     #[cfg(feature = "cosmwasm_1_1")]
@@ -1869,6 +2513,60 @@ mod tests {
         assert_eq!(res.amount, coin(0, "ATOM"));
     }
 
+    #[test]
+    fn bank_querier_burn_works() {
+        let addr1 = String::from("foo");
+        let balance1 = vec![coin(123, "ELF"), coin(777, "FLY")];
+
+        let addr2 = String::from("bar");
+        let balance2 = coins(321, "ELF");
+
+        let mut bank = BankQuerier::new(&[(&addr1, &balance1), (&addr2, &balance2)]);
+        assert_eq!(bank.burned("ELF"), Uint128::zero());
+
+        bank.burn(&addr1, &[coin(100, "ELF"), coin(777, "FLY")])
+            .unwrap();
+        bank.burn(&addr2, &coins(21, "ELF")).unwrap();
+        assert_eq!(bank.burned("ELF"), Uint128::new(121));
+        assert_eq!(bank.burned("FLY"), Uint128::new(777));
+
+        let query_balance = |bank: &BankQuerier, address: &str, denom: &str| {
+            let res = bank
+                .query(&BankQuery::Balance {
+                    address: address.to_string(),
+                    denom: denom.to_string(),
+                })
+                .unwrap()
+                .unwrap();
+            from_json::<BalanceResponse>(res).unwrap().amount
+        };
+        assert_eq!(query_balance(&bank, &addr1, "ELF"), coin(23, "ELF"));
+        assert_eq!(query_balance(&bank, &addr1, "FLY"), coin(0, "FLY"));
+        assert_eq!(query_balance(&bank, &addr2, "ELF"), coin(300, "ELF"));
+
+        #[cfg(feature = "cosmwasm_1_1")]
+        {
+            let res = bank
+                .query(&BankQuery::Supply {
+                    denom: "ELF".to_string(),
+                })
+                .unwrap()
+                .unwrap();
+            let res: SupplyResponse = from_json(res).unwrap();
+            assert_eq!(res.amount, coin(323, "ELF"));
+        }
+
+        // insufficient funds does not change the state
+        let err = bank
+            .burn(&addr1, &[coin(23, "ELF"), coin(1, "FLY")])
+            .unwrap_err();
+        assert!(err.to_string().contains("Insufficient funds to burn 1FLY"));
+        bank.burn("unknown", &coins(1, "ELF")).unwrap_err();
+        assert_eq!(query_balance(&bank, &addr1, "ELF"), coin(23, "ELF"));
+        assert_eq!(bank.burned("ELF"), Uint128::new(121));
+        assert_eq!(bank.burned("FLY"), Uint128::new(777));
+    }
+
     #[test]
     #[allow(deprecated)]
     fn bank_querier_all_balances() {
@@ -1884,6 +2582,184 @@ mod tests {
         assert_eq!(&res.amount, &balance);
     }
 
+    #[cfg(feature = "cosmwasm_2_3")]
+    fn query_balances_page(
+        bank: &BankQuerier,
+        address: &str,
+        pagination: Option<PageRequest>,
+    ) -> crate::PaginatedBalancesResponse {
+        let res = bank
+            .query(&BankQuery::AllBalancesPaginated {
+                address: address.to_string(),
+                pagination,
+            })
+            .unwrap()
+            .unwrap();
+        from_json(res).unwrap()
+    }
+
+    #[cfg(feature = "cosmwasm_2_3")]
+    #[test]
+    fn bank_querier_all_balances_paginated_works() {
+        let addr = String::from("foobar");
+        let balance = vec![coin(777, "FLY"), coin(123, "ELF"), coin(5, "ATOM")];
+        let bank = BankQuerier::new(&[(&addr, &balance)]);
+
+        // default pagination returns everything ordered by denom
+        let res = query_balances_page(&bank, &addr, None);
+        assert_eq!(
+            res.balances,
+            [coin(5, "ATOM"), coin(123, "ELF"), coin(777, "FLY")]
+        );
+        assert_eq!(res.next_key, None);
+
+        let res = query_balances_page(
+            &bank,
+            &addr,
+            Some(PageRequest {
+                key: None,
+                limit: 10,
+                reverse: true,
+            }),
+        );
+        assert_eq!(
+            res.balances,
+            [coin(777, "FLY"), coin(123, "ELF"), coin(5, "ATOM")]
+        );
+        assert_eq!(res.next_key, None);
+    }
+
+    #[cfg(feature = "cosmwasm_2_3")]
+    #[test]
+    fn bank_querier_all_balances_paginated_empty_account() {
+        let bank = BankQuerier::new(&[("foobar", &[coin(123, "ELF")])]);
+
+        let res = query_balances_page(&bank, "elsewhere", None);
+        assert!(res.balances.is_empty());
+        assert_eq!(res.next_key, None);
+
+        let res = query_balances_page(
+            &bank,
+            "elsewhere",
+            Some(PageRequest {
+                key: Some(Binary::from(b"ELF")),
+                limit: 1,
+                reverse: false,
+            }),
+        );
+        assert!(res.balances.is_empty());
+        assert_eq!(res.next_key, None);
+    }
+
+    #[cfg(feature = "cosmwasm_2_3")]
+    #[test]
+    fn bank_querier_all_balances_paginated_page_boundaries() {
+        let addr = String::from("foobar");
+        let balance = vec![coin(1, "a"), coin(2, "b"), coin(3, "c"), coin(4, "d")];
+        let bank = BankQuerier::new(&[(&addr, &balance)]);
+
+        for reverse in [false, true] {
+            let mut expected = balance.clone();
+            if reverse {
+                expected.reverse();
+            }
+
+            // page size divides the number of balances exactly
+            let first = query_balances_page(
+                &bank,
+                &addr,
+                Some(PageRequest {
+                    key: None,
+                    limit: 2,
+                    reverse,
+                }),
+            );
+            assert_eq!(first.balances, expected[..2]);
+            let next_key = first.next_key.expect("must have a next key");
+            assert_eq!(next_key.as_slice(), expected[2].denom.as_bytes());
+
+            // next_key round trip
+            let second = query_balances_page(
+                &bank,
+                &addr,
+                Some(PageRequest {
+                    key: Some(next_key),
+                    limit: 2,
+                    reverse,
+                }),
+            );
+            assert_eq!(second.balances, expected[2..]);
+            assert_eq!(second.next_key, None);
+
+            // page size equals the number of balances
+            let all = query_balances_page(
+                &bank,
+                &addr,
+                Some(PageRequest {
+                    key: None,
+                    limit: 4,
+                    reverse,
+                }),
+            );
+            assert_eq!(all.balances, expected);
+            assert_eq!(all.next_key, None);
+
+            // page size is one less than the number of balances
+            let almost = query_balances_page(
+                &bank,
+                &addr,
+                Some(PageRequest {
+                    key: None,
+                    limit: 3,
+                    reverse,
+                }),
+            );
+            assert_eq!(almost.balances, expected[..3]);
+            assert_eq!(
+                almost.next_key,
+                Some(Binary::from(expected[3].denom.as_bytes()))
+            );
+        }
+    }
+
+    #[cfg(feature = "cosmwasm_2_3")]
+    #[test]
+    fn mock_querier_honors_page_request_reverse() {
+        use crate::QuerierWrapper;
+
+        let querier: MockQuerier =
+            MockQuerier::new(&[("foobar", &[coin(1, "a"), coin(2, "b"), coin(3, "c")])]);
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+        let query_page = |pagination: PageRequest| -> crate::PaginatedBalancesResponse {
+            let request = BankQuery::AllBalancesPaginated {
+                address: "foobar".to_string(),
+                pagination: Some(pagination),
+            };
+            wrapper.query(&request.into()).unwrap()
+        };
+
+        let page = query_page(
+            PageRequest {
+                limit: 2,
+                ..Default::default()
+            }
+            .reverse(),
+        );
+        assert_eq!(page.balances, [coin(3, "c"), coin(2, "b")]);
+        let next_key = page.next_key.expect("must have a next key");
+
+        let page = query_page(
+            PageRequest {
+                limit: 2,
+                ..Default::default()
+            }
+            .reverse()
+            .with_key(next_key),
+        );
+        assert_eq!(page.balances, [coin(1, "a")]);
+        assert_eq!(page.next_key, None);
+    }
+
     #[test]
     fn bank_querier_one_balance() {
         let addr = String::from("foobar");
@@ -2153,6 +3029,29 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "cosmwasm_2_3")]
+    #[test]
+    fn distribution_querier_community_pool() {
+        use crate::{CommunityPoolResponse, Decimal256};
+
+        let query = DistributionQuery::CommunityPool {};
+
+        // empty by default
+        let distribution = DistributionQuerier::default();
+        let res = distribution.query(&query).unwrap().unwrap();
+        let res: CommunityPoolResponse = from_json(res).unwrap();
+        assert!(res.pool.is_empty());
+
+        let pool = vec![
+            DecCoin::new(Decimal256::from_atomics(123456u128, 3).unwrap(), "uatom"),
+            DecCoin::new(Decimal256::percent(50), "utest"),
+        ];
+        let distribution = DistributionQuerier::default().with_community_pool(pool.clone());
+        let res = distribution.query(&query).unwrap().unwrap();
+        let res: CommunityPoolResponse = from_json(res).unwrap();
+        assert_eq!(res.pool, pool);
+    }
+
     #[cfg(feature = "stargate")]
     #[test]
     fn ibc_querier_channel_existing() {
@@ -2462,6 +3361,56 @@ mod tests {
         assert_eq!(dels, Some(del2c));
     }
 
+    #[cfg(all(feature = "staking", feature = "cosmwasm_2_3"))]
+    #[test]
+    fn staking_querier_unbonding_delegations() {
+        let user_a = Addr::unchecked("investor");
+        let user_b = Addr::unchecked("speculator");
+
+        let unbonding1a = UnbondingDelegation {
+            delegator: user_a.clone(),
+            validator_address: String::from("validator-one"),
+            amount: coin(100, "ustake"),
+            completion_time: Timestamp::from_seconds(1_700_000_000),
+        };
+        let unbonding2a = UnbondingDelegation {
+            delegator: user_a.clone(),
+            validator_address: String::from("validator-two"),
+            amount: coin(50, "ustake"),
+            completion_time: Timestamp::from_seconds(1_700_100_000),
+        };
+        let unbonding1b = UnbondingDelegation {
+            delegator: user_b.clone(),
+            validator_address: String::from("validator-one"),
+            amount: coin(7, "ustake"),
+            completion_time: Timestamp::from_seconds(1_700_200_000),
+        };
+
+        let mut staking = StakingQuerier::new("ustake", &[], &[]);
+        let query = |staking: &StakingQuerier, delegator: &Addr| {
+            let raw = staking
+                .query(&StakingQuery::UnbondingDelegations {
+                    delegator: delegator.to_string(),
+                })
+                .unwrap()
+                .unwrap();
+            let res: UnbondingDelegationsResponse = from_json(raw).unwrap();
+            res.delegations
+        };
+
+        // empty by default
+        assert_eq!(query(&staking, &user_a), vec![]);
+
+        staking.set_unbonding_delegations(&[
+            unbonding1a.clone(),
+            unbonding1b.clone(),
+            unbonding2a.clone(),
+        ]);
+        assert_eq!(query(&staking, &user_a), vec![unbonding1a, unbonding2a]);
+        assert_eq!(query(&staking, &user_b), vec![unbonding1b]);
+        assert_eq!(query(&staking, &Addr::unchecked("no one")), vec![]);
+    }
+
     #[test]
     fn wasm_querier_works() {
         let mut querier = WasmQuerier::default();
@@ -2602,6 +3551,10 @@ mod tests {
                         SystemResult::Err(SystemError::NoSuchCode { code_id })
                     }
                 }
+                #[cfg(feature = "cosmwasm_2_3")]
+                WasmQuery::ContractsByCode { code_id, .. } => {
+                    SystemResult::Err(SystemError::NoSuchCode { code_id: *code_id })
+                }
             }
         });
 
@@ -2676,6 +3629,224 @@ mod tests {
         }
     }
 
+    #[test]
+    fn wasm_querier_smart_handler_works() {
+        use crate::QuerierWrapper;
+
+        #[cosmwasm_schema::cw_serde]
+        enum CounterQueryMsg {
+            Count {},
+            Plus { value: u32 },
+        }
+
+        let api = MockApi::default();
+        let counter = api.addr_make("counter");
+        let other = api.addr_make("other");
+
+        let mut querier: MockQuerier =
+            MockQuerier::new(&[]).with_smart_handler(counter.as_str(), |msg| {
+                match from_json(&msg) {
+                    Ok(CounterQueryMsg::Count {}) => to_json_binary(&7u32).into(),
+                    Ok(CounterQueryMsg::Plus { value }) => to_json_binary(&(7 + value)).into(),
+                    Err(err) => ContractResult::Err(err.to_string()),
+                }
+            });
+        querier.update_wasm(|request| match request {
+            WasmQuery::Smart { .. } => SystemResult::Ok(ContractResult::Ok(b"\"fallback\"".into())),
+            _ => SystemResult::Err(SystemError::Unknown {}),
+        });
+
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+        let count: u32 = wrapper
+            .query_wasm_smart(&counter, &CounterQueryMsg::Count {})
+            .unwrap();
+        assert_eq!(count, 7);
+        let sum: u32 = wrapper
+            .query_wasm_smart(&counter, &CounterQueryMsg::Plus { value: 35 })
+            .unwrap();
+        assert_eq!(sum, 42);
+
+        // the handler receives the raw message and can return errors
+        let err = wrapper
+            .query_wasm_smart::<u32>(&counter, &"unknown")
+            .unwrap_err();
+        assert!(err.to_string().contains("Querier contract error"));
+
+        // other contracts and other queries go to the fallback handler
+        let res: String = wrapper
+            .query_wasm_smart(&other, &CounterQueryMsg::Count {})
+            .unwrap();
+        assert_eq!(res, "fallback");
+        let err = querier
+            .handle_query(&QueryRequest::Wasm(WasmQuery::Raw {
+                contract_addr: counter.to_string(),
+                key: b"count".into(),
+            }))
+            .unwrap_err();
+        assert_eq!(err, SystemError::Unknown {});
+    }
+
+    #[test]
+    #[cfg(feature = "cosmwasm_2_3")]
+    fn wasm_querier_contracts_by_code_works() {
+        use crate::ContractsByCodeResponse;
+
+        fn query_contracts(
+            querier: &MockQuerier,
+            code_id: u64,
+            start_after: Option<&str>,
+            limit: Option<u32>,
+        ) -> Vec<String> {
+            let res = querier
+                .handle_query(&QueryRequest::Wasm(WasmQuery::ContractsByCode {
+                    code_id,
+                    start_after: start_after.map(ToString::to_string),
+                    limit,
+                }))
+                .unwrap()
+                .unwrap();
+            let res: ContractsByCodeResponse = from_json(res).unwrap();
+            res.contracts
+        }
+
+        let api = MockApi::default();
+        let contract1 = api.addr_make("contract1");
+        let contract2 = api.addr_make("contract2");
+        let contract3 = api.addr_make("contract3");
+        let querier = MockQuerier::default()
+            .with_contracts_by_code(
+                1,
+                vec![contract1.clone(), contract2.clone(), contract3.clone()],
+            )
+            .with_contracts_by_code(2, vec![]);
+
+        // all contracts in the given order
+        assert_eq!(
+            query_contracts(&querier, 1, None, None),
+            [contract1.as_str(), contract2.as_str(), contract3.as_str()]
+        );
+
+        // pagination
+        assert_eq!(
+            query_contracts(&querier, 1, None, Some(2)),
+            [contract1.as_str(), contract2.as_str()]
+        );
+        assert_eq!(
+            query_contracts(&querier, 1, Some(contract2.as_str()), Some(2)),
+            [contract3.as_str()]
+        );
+        assert!(query_contracts(&querier, 1, Some(contract3.as_str()), None).is_empty());
+        assert!(query_contracts(&querier, 1, Some("unknown"), None).is_empty());
+        assert!(query_contracts(&querier, 1, None, Some(0)).is_empty());
+
+        // known code without contracts
+        assert!(query_contracts(&querier, 2, None, None).is_empty());
+
+        // unknown code is forwarded to the handler
+        let err = querier
+            .handle_query(&QueryRequest::Wasm(WasmQuery::ContractsByCode {
+                code_id: 3,
+                start_after: None,
+                limit: None,
+            }))
+            .unwrap_err();
+        assert_eq!(err, SystemError::NoSuchCode { code_id: 3 });
+    }
+
+    #[test]
+    #[cfg(feature = "cosmwasm_1_2")]
+    fn wasm_querier_with_code_info_works() {
+        use crate::Checksum;
+
+        let creator = MockApi::default().addr_make("creator");
+        let info = CodeInfoResponse::new(5, creator, Checksum::generate(b"wasm"));
+        let querier: MockQuerier = MockQuerier::default().with_code_info(info.clone());
+
+        let res = querier
+            .handle_query(&QueryRequest::Wasm(WasmQuery::CodeInfo { code_id: 5 }))
+            .unwrap()
+            .unwrap();
+        assert_eq!(from_json::<CodeInfoResponse>(res).unwrap(), info);
+
+        // unknown code is forwarded to the handler
+        let err = querier
+            .handle_query(&QueryRequest::Wasm(WasmQuery::CodeInfo { code_id: 6 }))
+            .unwrap_err();
+        assert_eq!(err, SystemError::NoSuchCode { code_id: 6 });
+    }
+
+    #[test]
+    #[cfg(feature = "cosmwasm_2_0")]
+    fn grpc_querier_works() {
+        use crate::QuerierWrapper;
+
+        const BALANCE_PATH: &str = "/cosmos.bank.v1beta1.Query/Balance";
+
+        // Minimal protobuf encoding of length-delimited fields (strings and embedded messages)
+        fn encode_field(field_number: u8, value: &[u8]) -> Vec<u8> {
+            assert!(value.len() < 128, "Only single byte lengths supported");
+            let mut out = vec![(field_number << 3) | 2, value.len() as u8];
+            out.extend_from_slice(value);
+            out
+        }
+
+        fn decode_fields(mut data: &[u8]) -> BTreeMap<u8, Vec<u8>> {
+            let mut fields = BTreeMap::new();
+            while let [key, len, rest @ ..] = data {
+                assert_eq!(key & 0b111, 2, "Only length-delimited fields supported");
+                let (value, remainder) = rest.split_at(*len as usize);
+                fields.insert(key >> 3, value.to_vec());
+                data = remainder;
+            }
+            fields
+        }
+
+        let mut querier: MockQuerier = MockQuerier::new(&[]);
+        // QueryBalanceRequest { address = 1, denom = 2 } -> QueryBalanceResponse { balance = 1 }
+        // where balance is Coin { denom = 1, amount = 2 }
+        querier.update_grpc(BALANCE_PATH, |data| {
+            let request = decode_fields(data);
+            let denom = &request[&2];
+            let amount: &[u8] = if request[&1] == b"alice" {
+                b"123"
+            } else {
+                b"0"
+            };
+            let coin = [encode_field(1, denom), encode_field(2, amount)].concat();
+            Binary::new(encode_field(1, &coin))
+        });
+
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+        let request = [encode_field(1, b"alice"), encode_field(2, b"uatom")].concat();
+        let response = wrapper
+            .query_grpc(BALANCE_PATH.to_string(), Binary::new(request))
+            .unwrap();
+        let balance = decode_fields(&decode_fields(&response)[&1]);
+        assert_eq!(balance[&1], b"uatom");
+        assert_eq!(balance[&2], b"123");
+
+        let request = [encode_field(1, b"bob"), encode_field(2, b"uatom")].concat();
+        let response = wrapper
+            .query_grpc(BALANCE_PATH.to_string(), Binary::new(request))
+            .unwrap();
+        let balance = decode_fields(&decode_fields(&response)[&1]);
+        assert_eq!(balance[&2], b"0");
+
+        // unregistered path
+        let err = querier
+            .handle_query(&QueryRequest::Grpc(GrpcQuery {
+                path: "/cosmos.bank.v1beta1.Query/Supply".to_string(),
+                data: Binary::default(),
+            }))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SystemError::UnsupportedRequest {
+                kind: "GRPC /cosmos.bank.v1beta1.Query/Supply".to_string()
+            }
+        );
+    }
+
     #[test]
     fn making_an_address_works() {
         let mock_api = MockApi::default();
@@ -0,0 +1,189 @@
+//! Import and export of contract state in the format used by wasmd,
+//! e.g. in the output of `wasmd query wasm contract-state all <address>`.
+//!
+//! The format looks like this:
+//!
+//! ```json
+//! {
+//!   "models": [
+//!     { "key": "636F6E666967", "value": "eyJvd25lciI6ImNvc21vczEuLi4ifQ==" }
+//!   ]
+//! }
+//! ```
+//!
+//! Values are always base64 encoded. Keys are hex encoded by the query command, but
+//! wasmd has also exported them base64 encoded (e.g. in the `contract_state` of genesis files).
+//! Both are supported: a key that consists of an even number of hex digits only is decoded
+//! as hex, everything else as base64. Exports always use upper case hex keys.
+//! Other fields like `pagination` are ignored.
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::{from_json, to_json_string, Binary, HexBinary, StdError, StdResult};
+
+use super::MockStorage;
+
+#[derive(Serialize, Deserialize)]
+struct StateExport {
+    models: Vec<Model>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Model {
+    key: String,
+    value: Binary,
+}
+
+/// Parses a contract state export (see the [module documentation](self)) into key/value pairs.
+pub fn parse_state_export(json: &str) -> StdResult<Vec<(Vec<u8>, Vec<u8>)>> {
+    let export: StateExport = from_json(json)?;
+    export
+        .models
+        .into_iter()
+        .map(|model| Ok((decode_key(&model.key)?, model.value.into())))
+        .collect()
+}
+
+/// Creates a contract state export (see the [module documentation](self)) from key/value pairs.
+pub fn state_export<'a>(entries: impl IntoIterator<Item = (&'a [u8], &'a [u8])>) -> String {
+    let export = StateExport {
+        models: entries
+            .into_iter()
+            .map(|(key, value)| Model {
+                key: hex::encode_upper(key),
+                value: Binary::from(value),
+            })
+            .collect(),
+    };
+    to_json_string(&export).expect("Serializing a state export cannot fail")
+}
+
+fn decode_key(key: &str) -> StdResult<Vec<u8>> {
+    let is_hex = key.len() % 2 == 0 && key.bytes().all(|b| b.is_ascii_hexdigit());
+    let key: Vec<u8> = if is_hex {
+        HexBinary::from_hex(key)?.into()
+    } else {
+        Binary::from_base64(key)?.into()
+    };
+    if key.is_empty() {
+        return Err(StdError::generic_err("Empty key in state export"));
+    }
+    Ok(key)
+}
+
+impl MockStorage {
+    /// Creates a storage from a contract state export (see [`parse_state_export`]).
+    /// This allows running tests against state exported from a chain.
+    pub fn from_genesis_export(json: &str) -> StdResult<MockStorage> {
+        let mut storage = MockStorage::new();
+        for (key, value) in parse_state_export(json)? {
+            if value.is_empty() {
+                return Err(StdError::generic_err(format!(
+                    "Empty value for key {} in state export",
+                    hex::encode_upper(key)
+                )));
+            }
+            storage.data.insert(key, value);
+        }
+        Ok(storage)
+    }
+
+    /// Exports the storage content in the contract state export format (see [`state_export`]).
+    pub fn to_genesis_export(&self) -> String {
+        state_export(
+            self.data
+                .iter()
+                .map(|(key, value)| (key.as_slice(), value.as_slice())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Storage;
+
+    const STATE_EXPORT: &str = include_str!("../../testdata/contract_state_export.json");
+
+    #[test]
+    fn from_genesis_export_works() {
+        let storage = MockStorage::from_genesis_export(STATE_EXPORT).unwrap();
+
+        assert_eq!(
+            storage.get(b"token_info").unwrap(),
+            br#"{"name":"Test Token","symbol":"TEST","decimals":6,"total_supply":"1500000","mint":null}"#
+        );
+        // cw-storage-plus Map key with length prefixed namespace
+        let mut balance_key = b"\x00\x07balance".to_vec();
+        balance_key.extend_from_slice(
+            b"cosmwasm190vqdjtlpcq27xslcveglfmr4ynfwg7gmw86cnun4acakxrdd6gqvdcx9h",
+        );
+        assert_eq!(storage.get(&balance_key).unwrap(), br#""1000000""#);
+        assert_eq!(storage.data.len(), 3);
+    }
+
+    #[test]
+    fn from_genesis_export_supports_base64_keys() {
+        let json =
+            r#"{"models":[{"key":"Y29uZmln","value":"e30="},{"key":"AAM=","value":"AQ=="}]}"#;
+        let storage = MockStorage::from_genesis_export(json).unwrap();
+        assert_eq!(storage.get(b"config").unwrap(), b"{}");
+        assert_eq!(storage.get(&[0, 3]).unwrap(), [1]);
+
+        // mixed and lower case hex
+        let json =
+            r#"{"models":[{"key":"636f6e666967","value":"e30="},{"key":"AAM=","value":"AQ=="}]}"#;
+        let storage = MockStorage::from_genesis_export(json).unwrap();
+        assert_eq!(storage.get(b"config").unwrap(), b"{}");
+        assert_eq!(storage.get(&[0, 3]).unwrap(), [1]);
+    }
+
+    #[test]
+    fn from_genesis_export_fails_for_invalid_input() {
+        let err = MockStorage::from_genesis_export(r#"{"models":[{"key":"A","value":"e30="}]}"#)
+            .unwrap_err();
+        assert!(matches!(err, StdError::InvalidBase64 { .. }));
+
+        let err = MockStorage::from_genesis_export(r#"{"models":[{"key":"","value":"e30="}]}"#)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Generic error: Empty key in state export");
+
+        let err = MockStorage::from_genesis_export(r#"{"models":[{"key":"AB","value":""}]}"#)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Empty value for key AB in state export"
+        );
+
+        let err = MockStorage::from_genesis_export(r#"{"contracts":[]}"#).unwrap_err();
+        assert!(matches!(err, StdError::ParseErr { .. }));
+    }
+
+    #[test]
+    fn genesis_export_round_trip_works() {
+        let storage = MockStorage::from_genesis_export(STATE_EXPORT).unwrap();
+        let exported = storage.to_genesis_export();
+
+        // same models, without pagination
+        let expected: serde_json::Value = serde_json::from_str(STATE_EXPORT).unwrap();
+        let actual: serde_json::Value = serde_json::from_str(&exported).unwrap();
+        assert_eq!(actual["models"], expected["models"]);
+
+        let reimported = MockStorage::from_genesis_export(&exported).unwrap();
+        assert_eq!(reimported.data, storage.data);
+    }
+
+    #[test]
+    fn state_export_works() {
+        let json = state_export([(b"config".as_slice(), b"{}".as_slice())]);
+        assert_eq!(
+            json,
+            r#"{"models":[{"key":"636F6E666967","value":"e30="}]}"#
+        );
+        assert_eq!(
+            parse_state_export(&json).unwrap(),
+            vec![(b"config".to_vec(), b"{}".to_vec())]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use core::fmt;
+use core::time::Duration;
 use serde::{Deserialize, Serialize};
 
 use crate::Uint64;
@@ -148,6 +149,87 @@ impl Timestamp {
         Timestamp(self.0.strict_sub(Uint64::new(subtrahend)))
     }
 
+    /// Adds the given amount of nanoseconds to the timestamp and
+    /// returns the result. The original value remains unchanged.
+    ///
+    /// Returns `None` if the result exceeds the value range of [`Timestamp`].
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub const fn checked_add_nanos(&self, addition: u64) -> Option<Timestamp> {
+        match self.0.u64().checked_add(addition) {
+            Some(nanos) => Some(Timestamp::from_nanos(nanos)),
+            None => None,
+        }
+    }
+
+    /// Adds the given amount of seconds to the timestamp and
+    /// returns the result. The original value remains unchanged.
+    ///
+    /// Returns `None` if the result exceeds the value range of [`Timestamp`].
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub const fn checked_add_seconds(&self, addition: u64) -> Option<Timestamp> {
+        match addition.checked_mul(1_000_000_000) {
+            Some(nanos) => self.checked_add_nanos(nanos),
+            None => None,
+        }
+    }
+
+    /// Adds the given duration to the timestamp and
+    /// returns the result. The original value remains unchanged.
+    ///
+    /// Returns `None` if the result exceeds the value range of [`Timestamp`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use core::time::Duration;
+    /// # use cosmwasm_std::Timestamp;
+    /// let ts = Timestamp::from_seconds(1);
+    /// let later = ts.checked_add_duration(Duration::from_millis(1500)).unwrap();
+    /// assert_eq!(later.nanos(), 2_500_000_000);
+    ///
+    /// let max = Timestamp::from_nanos(u64::MAX);
+    /// assert_eq!(max.checked_add_duration(Duration::from_nanos(1)), None);
+    /// ```
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn checked_add_duration(&self, duration: Duration) -> Option<Timestamp> {
+        let nanos = u64::try_from(duration.as_nanos()).ok()?;
+        self.checked_add_nanos(nanos)
+    }
+
+    /// Subtracts the given amount of nanoseconds from the timestamp and
+    /// returns the result. The original value remains unchanged.
+    ///
+    /// Returns `None` if the result is not >= 0. I.e. times before epoch cannot be represented.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub const fn checked_sub_nanos(&self, subtrahend: u64) -> Option<Timestamp> {
+        match self.0.u64().checked_sub(subtrahend) {
+            Some(nanos) => Some(Timestamp::from_nanos(nanos)),
+            None => None,
+        }
+    }
+
+    /// Subtracts the given amount of seconds from the timestamp and
+    /// returns the result. The original value remains unchanged.
+    ///
+    /// Returns `None` if the result is not >= 0. I.e. times before epoch cannot be represented.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub const fn checked_sub_seconds(&self, subtrahend: u64) -> Option<Timestamp> {
+        match subtrahend.checked_mul(1_000_000_000) {
+            Some(nanos) => self.checked_sub_nanos(nanos),
+            None => None,
+        }
+    }
+
+    /// Subtracts the given duration from the timestamp and
+    /// returns the result. The original value remains unchanged.
+    ///
+    /// Returns `None` if the result is not >= 0. I.e. times before epoch cannot be represented.
+    #[must_use = "this returns the result of the operation, without modifying the original"]
+    pub fn checked_sub_duration(&self, duration: Duration) -> Option<Timestamp> {
+        let nanos = u64::try_from(duration.as_nanos()).ok()?;
+        self.checked_sub_nanos(nanos)
+    }
+
     /// Returns nanoseconds since epoch
     #[inline]
     pub fn nanos(&self) -> u64 {
@@ -251,6 +333,120 @@ mod tests {
         let _earlier = Timestamp::from_nanos(100).minus_nanos(101);
     }
 
+    #[test]
+    fn timestamp_checked_add_nanos() {
+        let sum = Timestamp::from_nanos(123).checked_add_nanos(3);
+        assert_eq!(sum, Some(Timestamp::from_nanos(126)));
+        let max = Timestamp::from_nanos(u64::MAX);
+        assert_eq!(max.checked_add_nanos(0), Some(max));
+        assert_eq!(max.checked_add_nanos(1), None);
+        assert_eq!(
+            Timestamp::from_nanos(u64::MAX - 5).checked_add_nanos(5),
+            Some(max)
+        );
+        assert_eq!(
+            Timestamp::from_nanos(u64::MAX - 5).checked_add_nanos(6),
+            None
+        );
+        assert_eq!(Timestamp::from_nanos(1).checked_add_nanos(u64::MAX), None);
+    }
+
+    #[test]
+    fn timestamp_checked_add_seconds() {
+        let sum = Timestamp::from_nanos(123).checked_add_seconds(42);
+        assert_eq!(sum, Some(Timestamp::from_nanos(42_000_000_123)));
+
+        // u64::MAX nanos is 18446744073.709551615 seconds
+        let ts = Timestamp::from_nanos(709_551_615);
+        assert_eq!(
+            ts.checked_add_seconds(18_446_744_073),
+            Some(Timestamp::from_nanos(u64::MAX))
+        );
+        let ts = Timestamp::from_nanos(709_551_616);
+        assert_eq!(ts.checked_add_seconds(18_446_744_073), None);
+        // the multiplication overflows
+        assert_eq!(
+            Timestamp::from_nanos(0).checked_add_seconds(18_446_744_074),
+            None
+        );
+        assert_eq!(Timestamp::from_nanos(0).checked_add_seconds(u64::MAX), None);
+    }
+
+    #[test]
+    fn timestamp_checked_add_duration() {
+        let ts = Timestamp::from_seconds(1);
+        assert_eq!(
+            ts.checked_add_duration(Duration::from_millis(1500)),
+            Some(Timestamp::from_nanos(2_500_000_000))
+        );
+        assert_eq!(ts.checked_add_duration(Duration::ZERO), Some(ts));
+
+        let max = Timestamp::from_nanos(u64::MAX);
+        assert_eq!(max.checked_add_duration(Duration::from_nanos(1)), None);
+        assert_eq!(
+            Timestamp::from_nanos(0).checked_add_duration(Duration::from_nanos(u64::MAX)),
+            Some(max)
+        );
+        // duration exceeds u64 nanos
+        assert_eq!(
+            Timestamp::from_nanos(0).checked_add_duration(Duration::from_secs(u64::MAX)),
+            None
+        );
+        assert_eq!(
+            Timestamp::from_nanos(0).checked_add_duration(Duration::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn timestamp_checked_sub_nanos() {
+        let earlier = Timestamp::from_nanos(123).checked_sub_nanos(3);
+        assert_eq!(earlier, Some(Timestamp::from_nanos(120)));
+        assert_eq!(
+            Timestamp::from_nanos(123).checked_sub_nanos(123),
+            Some(Timestamp::from_nanos(0))
+        );
+        assert_eq!(Timestamp::from_nanos(123).checked_sub_nanos(124), None);
+        let max = Timestamp::from_nanos(u64::MAX);
+        assert_eq!(
+            max.checked_sub_nanos(u64::MAX),
+            Some(Timestamp::from_nanos(0))
+        );
+        assert_eq!(Timestamp::from_nanos(0).checked_sub_nanos(u64::MAX), None);
+    }
+
+    #[test]
+    fn timestamp_checked_sub_seconds() {
+        let earlier = Timestamp::from_seconds(123).checked_sub_seconds(3);
+        assert_eq!(earlier, Some(Timestamp::from_seconds(120)));
+        assert_eq!(Timestamp::from_seconds(123).checked_sub_seconds(124), None);
+
+        let max = Timestamp::from_nanos(u64::MAX);
+        assert_eq!(
+            max.checked_sub_seconds(18_446_744_073),
+            Some(Timestamp::from_nanos(709_551_615))
+        );
+        // the multiplication overflows
+        assert_eq!(max.checked_sub_seconds(18_446_744_074), None);
+    }
+
+    #[test]
+    fn timestamp_checked_sub_duration() {
+        let ts = Timestamp::from_seconds(2);
+        assert_eq!(
+            ts.checked_sub_duration(Duration::from_millis(1500)),
+            Some(Timestamp::from_nanos(500_000_000))
+        );
+        assert_eq!(ts.checked_sub_duration(Duration::from_secs(3)), None);
+
+        let max = Timestamp::from_nanos(u64::MAX);
+        assert_eq!(
+            max.checked_sub_duration(Duration::from_nanos(u64::MAX)),
+            Some(Timestamp::from_nanos(0))
+        );
+        assert_eq!(max.checked_sub_duration(Duration::MAX), None);
+    }
+
     #[test]
     fn timestamp_plus_days() {
         let ts = Timestamp::from_seconds(123).plus_days(0);
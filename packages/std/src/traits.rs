@@ -1,6 +1,7 @@
 use core::marker::PhantomData;
 use core::ops::Deref;
 use serde::{de::DeserializeOwned, Serialize};
+use subtle::ConstantTimeEq;
 
 use crate::coin::Coin;
 #[cfg(feature = "iterator")]
@@ -23,7 +24,11 @@ use crate::query::{
     AllDenomMetadataResponse, DelegatorWithdrawAddressResponse, DenomMetadataResponse,
     DistributionQuery,
 };
+#[cfg(all(feature = "staking", feature = "cosmwasm_2_3"))]
+use crate::query::{UnbondingDelegation, UnbondingDelegationsResponse};
 use crate::results::{ContractResult, Empty, SystemResult};
+#[cfg(feature = "cosmwasm_1_2")]
+use crate::Checksum;
 use crate::ContractInfoResponse;
 use crate::{from_json, to_json_binary, to_json_vec, Binary};
 use crate::{Addr, CanonicalAddr};
@@ -54,6 +59,9 @@ pub trait Storage {
     ///
     /// Note: Support for differentiating between a non-existent key and a key with empty value
     /// is not great yet and might not be possible in all backends. But we're trying to get there.
+    /// In particular, raw queries (see [`QuerierWrapper::query_wasm_raw`]) cannot differentiate
+    /// between the two. This is why [`Storage::set`] does not allow empty values in contracts,
+    /// which guarantees that `Some(value)` is never empty for data written by a contract.
     fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
 
     /// Allows iteration over a set of key/value pairs, either forwards or backwards.
@@ -107,6 +115,11 @@ pub trait Storage {
         Box::new(self.range(start, end, order).map(|(_k, v)| v))
     }
 
+    /// Sets the value of the given key.
+    ///
+    /// The value must not be empty. The storage implementation used in contracts and
+    /// [`MemoryStorage`](crate::MemoryStorage) panic on empty values.
+    /// Use [`Storage::remove`] to delete a key instead.
     fn set(&mut self, key: &[u8], value: &[u8]);
 
     /// Removes a database entry at `key`.
@@ -313,6 +326,42 @@ pub trait Api {
         public_keys: &[&[u8]],
     ) -> Result<bool, VerificationError>;
 
+    /// Checks that `public_key` is a valid ed25519 public key.
+    ///
+    /// This is stricter than [`Api::ed25519_verify`], which accepts all keys allowed by ZIP-215.
+    /// Keys that are not 32 bytes long, non-canonical encodings and small-order points are
+    /// rejected. Use it to check keys before storing them.
+    #[allow(unused_variables)]
+    fn ed25519_validate_pubkey(&self, public_key: &[u8]) -> bool {
+        // Support for ed25519 public key validation is added in 2.3, i.e. we can't add a compile time requirement for new function.
+        // Any implementation of the Api trait which does not implement this function but tries to call it will
+        // panic at runtime. We don't assume such cases exist.
+        // See also https://doc.rust-lang.org/cargo/reference/semver.html#trait-new-default-item
+        unimplemented!()
+    }
+
+    /// Computes the HMAC-SHA256 ([RFC 2104](https://datatracker.ietf.org/doc/html/rfc2104))
+    /// of `data` using the given `key`.
+    ///
+    /// Keys of any length up to 1 KiB and data up to 128 KiB are supported by the host.
+    #[allow(unused_variables)]
+    fn hmac_sha256(&self, key: &[u8], data: &[u8]) -> [u8; 32] {
+        // Support for HMAC-SHA256 is added in 2.3, i.e. we can't add a compile time requirement for new function.
+        // Any implementation of the Api trait which does not implement this function but tries to call it will
+        // panic at runtime. We don't assume such cases exist.
+        // See also https://doc.rust-lang.org/cargo/reference/semver.html#trait-new-default-item
+        unimplemented!()
+    }
+
+    /// Verifies that `mac` is the HMAC-SHA256 of `data` using the given `key`.
+    ///
+    /// The comparison runs in constant time to avoid leaking information about the expected
+    /// MAC through timing side channels.
+    fn hmac_sha256_verify(&self, key: &[u8], data: &[u8], mac: &[u8]) -> bool {
+        let expected = self.hmac_sha256(key, data);
+        bool::from(expected.as_slice().ct_eq(mac))
+    }
+
     /// Emits a debugging message that is handled depending on the environment (typically printed to console or ignored).
     /// Those messages are not persisted to chain.
     fn debug(&self, message: &str);
@@ -378,10 +427,17 @@ impl<'a, C: CustomQuery> QuerierWrapper<'a, C> {
         self.query_raw(request).and_then(|raw| from_json(raw))
     }
 
+    /// Wraps the custom query in [`QueryRequest::Custom`], makes the query and parses the response.
+    ///
+    /// Errors are flattened in the same way as in [`QuerierWrapper::query`].
+    pub fn query_custom<U: DeserializeOwned>(&self, query: C) -> StdResult<U> {
+        self.query(&QueryRequest::Custom(query))
+    }
+
     /// Internal helper to avoid code duplication.
     /// Performs a query and returns the binary result without deserializing it,
     /// wrapping any errors that may occur into `StdError`.
-    fn query_raw(&self, request: &QueryRequest<C>) -> StdResult<Binary> {
+    pub(crate) fn query_raw(&self, request: &QueryRequest<C>) -> StdResult<Binary> {
         let raw = to_json_vec(request).map_err(|serialize_err| {
             StdError::generic_err(format!("Serializing QueryRequest: {serialize_err}"))
         })?;
@@ -431,6 +487,19 @@ impl<'a, C: CustomQuery> QuerierWrapper<'a, C> {
         Ok(res.amount)
     }
 
+    /// Like [`QuerierWrapper::query_all_balances`] but returns the balances as [`Coins`].
+    ///
+    /// [`Coins`]: crate::Coins
+    #[deprecated]
+    pub fn query_all_balances_as_coins(
+        &self,
+        address: impl Into<String>,
+    ) -> StdResult<crate::Coins> {
+        #[allow(deprecated)]
+        let balances = self.query_all_balances(address)?;
+        Ok(crate::Coins::try_from(balances)?)
+    }
+
     #[cfg(feature = "cosmwasm_1_3")]
     pub fn query_delegator_withdraw_address(
         &self,
@@ -466,6 +535,23 @@ impl<'a, C: CustomQuery> QuerierWrapper<'a, C> {
         self.query(&request)
     }
 
+    /// Queries one page of the balances of the given address.
+    ///
+    /// Pass the `next_key` of the response as `pagination.key` to get the next page.
+    #[cfg(feature = "cosmwasm_2_3")]
+    pub fn query_all_balances_paginated(
+        &self,
+        address: impl Into<String>,
+        pagination: PageRequest,
+    ) -> StdResult<crate::PaginatedBalancesResponse> {
+        let request = BankQuery::AllBalancesPaginated {
+            address: address.into(),
+            pagination: Some(pagination),
+        }
+        .into();
+        self.query(&request)
+    }
+
     #[cfg(feature = "cosmwasm_1_4")]
     pub fn query_delegation_rewards(
         &self,
@@ -511,6 +597,16 @@ impl<'a, C: CustomQuery> QuerierWrapper<'a, C> {
         Ok(res.validators)
     }
 
+    /// Queries the funds currently held by the community pool.
+    #[cfg(feature = "cosmwasm_2_3")]
+    pub fn query_community_pool(&self) -> StdResult<Vec<crate::DecCoin>> {
+        use crate::CommunityPoolResponse;
+
+        let request = DistributionQuery::CommunityPool {}.into();
+        let CommunityPoolResponse { pool } = self.query(&request)?;
+        Ok(pool)
+    }
+
     /// See [`GrpcQuery`](crate::GrpcQuery) for more information.
     #[cfg(feature = "cosmwasm_2_0")]
     pub fn query_grpc(&self, path: String, data: Binary) -> StdResult<Binary> {
@@ -518,6 +614,18 @@ impl<'a, C: CustomQuery> QuerierWrapper<'a, C> {
         self.query_raw(&QueryRequest::Grpc(GrpcQuery { path, data }))
     }
 
+    /// Sends multiple queries to the host in a single call.
+    ///
+    /// The returned results are parallel to `requests`, i.e. the n-th result belongs to the n-th request.
+    /// Errors of individual sub-queries are returned as part of their [`QuerierResult`]
+    /// and do not fail the whole batch.
+    ///
+    /// See [`QueryRequest::Multi`] for more information.
+    #[cfg(feature = "cosmwasm_2_3")]
+    pub fn query_multi(&self, requests: Vec<QueryRequest<C>>) -> StdResult<Vec<QuerierResult>> {
+        self.query(&QueryRequest::Multi(requests))
+    }
+
     /// Queries another wasm contract. You should know a priori the proper types for T and U
     /// (response and request) based on the contract API
     pub fn query_wasm_smart<T: DeserializeOwned>(
@@ -541,6 +649,11 @@ impl<'a, C: CustomQuery> QuerierWrapper<'a, C> {
     ///
     /// Similar return value to [`Storage::get`]. Returns `Some(val)` or `None` if the data is there.
     /// It only returns error on some runtime issue, not on any data cases.
+    ///
+    /// The response of a raw query is the value of the key or an empty response if the key
+    /// does not exist, i.e. it cannot differentiate between a missing key and an empty value.
+    /// An empty response is therefore always returned as `None`. Since contracts cannot store
+    /// empty values (see [`Storage::set`]), this is the correct result for all contract storage.
     pub fn query_wasm_raw(
         &self,
         contract_addr: impl Into<String>,
@@ -592,6 +705,42 @@ impl<'a, C: CustomQuery> QuerierWrapper<'a, C> {
         self.query(&request)
     }
 
+    /// Queries the code info of the given code ID and returns an error if its checksum
+    /// does not match `expected`.
+    ///
+    /// This is useful before migrating a contract to a code ID proposed by someone else,
+    /// e.g. in a governance proposal, to make sure the new code is the reviewed one.
+    /// See [`WasmMsg::migrate_checked`](crate::WasmMsg::migrate_checked) for a shortcut.
+    #[cfg(feature = "cosmwasm_1_2")]
+    pub fn assert_code_checksum(&self, code_id: u64, expected: &Checksum) -> StdResult<()> {
+        let info = self.query_wasm_code_info(code_id)?;
+        if info.checksum != *expected {
+            return Err(StdError::generic_err(format!(
+                "Checksum mismatch for code ID {code_id}: expected {expected}, got {}",
+                info.checksum
+            )));
+        }
+        Ok(())
+    }
+
+    /// Given a code ID, query the addresses of the contracts instantiated from that code.
+    ///
+    /// This sends a single query without pagination parameters, so the chain's default
+    /// limit applies. Use [`WasmQuery::ContractsByCode`] directly to page through the results.
+    #[cfg(feature = "cosmwasm_2_3")]
+    pub fn query_contracts_by_code(&self, code_id: u64) -> StdResult<Vec<String>> {
+        use crate::ContractsByCodeResponse;
+
+        let request = WasmQuery::ContractsByCode {
+            code_id,
+            start_after: None,
+            limit: None,
+        }
+        .into();
+        let ContractsByCodeResponse { contracts } = self.query(&request)?;
+        Ok(contracts)
+    }
+
     #[cfg(feature = "staking")]
     pub fn query_all_validators(&self) -> StdResult<Vec<Validator>> {
         let request = StakingQuery::AllValidators {}.into();
@@ -643,6 +792,19 @@ impl<'a, C: CustomQuery> QuerierWrapper<'a, C> {
         let res: DelegationResponse = self.query(&request)?;
         Ok(res.delegation)
     }
+
+    #[cfg(all(feature = "staking", feature = "cosmwasm_2_3"))]
+    pub fn query_unbonding_delegations(
+        &self,
+        delegator: impl Into<String>,
+    ) -> StdResult<Vec<UnbondingDelegation>> {
+        let request = StakingQuery::UnbondingDelegations {
+            delegator: delegator.into(),
+        }
+        .into();
+        let res: UnbondingDelegationsResponse = self.query(&request)?;
+        Ok(res.delegations)
+    }
 }
 
 #[cfg(test)]
@@ -694,7 +856,7 @@ mod tests {
     #[cfg(feature = "cosmwasm_1_1")]
     #[test]
     fn bank_query_helpers_work() {
-        use crate::coin;
+        use crate::{coin, Coins};
 
         let querier: MockQuerier<Empty> = MockQuerier::new(&[
             ("foo", &[coin(123, "ELF"), coin(777, "FLY")]),
@@ -711,6 +873,55 @@ mod tests {
         #[allow(deprecated)]
         let all_balances = wrapper.query_all_balances("foo").unwrap();
         assert_eq!(all_balances, vec![coin(123, "ELF"), coin(777, "FLY")]);
+
+        #[allow(deprecated)]
+        let all_balances = wrapper.query_all_balances_as_coins("foo").unwrap();
+        assert_eq!(
+            all_balances,
+            Coins::try_from([coin(123, "ELF"), coin(777, "FLY")]).unwrap()
+        );
+
+        #[allow(deprecated)]
+        let all_balances = wrapper.query_all_balances_as_coins("unknown").unwrap();
+        assert!(all_balances.is_empty());
+    }
+
+    #[cfg(feature = "cosmwasm_2_3")]
+    #[test]
+    fn query_multi_works() {
+        use crate::coin;
+
+        let querier: MockQuerier<Empty> = MockQuerier::new(&[("foo", &[coin(123, "ELF")])]);
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+
+        let results = wrapper
+            .query_multi(vec![
+                BankQuery::Balance {
+                    address: "foo".to_string(),
+                    denom: "ELF".to_string(),
+                }
+                .into(),
+                WasmQuery::Smart {
+                    contract_addr: "bar".to_string(),
+                    msg: Binary::default(),
+                }
+                .into(),
+            ])
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
+        let balance: BalanceResponse = from_json(results[0].clone().unwrap().unwrap()).unwrap();
+        assert_eq!(balance.amount, coin(123, "ELF"));
+        assert_eq!(
+            results[1],
+            SystemResult::Err(crate::SystemError::NoSuchContract {
+                addr: "bar".to_string()
+            })
+        );
+
+        // empty batch
+        let results = wrapper.query_multi(vec![]).unwrap();
+        assert_eq!(results, vec![]);
     }
 
     #[test]
@@ -781,6 +992,86 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn query_wasm_raw_treats_empty_response_as_none() {
+        const ACCT: &str = "foobar";
+
+        let mut querier: MockQuerier<Empty> = MockQuerier::new(&[]);
+        querier.update_wasm(|q| -> QuerierResult {
+            match q {
+                WasmQuery::Raw { key, .. } if key.as_slice() == b"foo" => {
+                    SystemResult::Ok(ContractResult::Ok(Binary::from(b"bar")))
+                }
+                WasmQuery::Raw { .. } => SystemResult::Ok(ContractResult::Ok(Binary::default())),
+                _ => SystemResult::Err(crate::SystemError::NoSuchContract {
+                    addr: ACCT.to_string(),
+                }),
+            }
+        });
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+
+        let value = wrapper.query_wasm_raw(ACCT, b"foo".as_slice()).unwrap();
+        assert_eq!(value, Some(b"bar".to_vec()));
+
+        let value = wrapper.query_wasm_raw(ACCT, b"missing".as_slice()).unwrap();
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    #[cfg(feature = "cosmwasm_2_3")]
+    fn query_contracts_by_code_works() {
+        let querier: MockQuerier<Empty> = MockQuerier::new(&[]).with_contracts_by_code(
+            7,
+            vec![Addr::unchecked("contract1"), Addr::unchecked("contract2")],
+        );
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+
+        let contracts = wrapper.query_contracts_by_code(7).unwrap();
+        assert_eq!(contracts, ["contract1", "contract2"]);
+
+        let err = wrapper.query_contracts_by_code(8).unwrap_err();
+        assert!(matches!(
+            err,
+            StdError::GenericErr {
+                msg,
+                ..
+            } if msg == "Querier system error: No such code: 8"
+        ));
+    }
+
+    #[cfg(feature = "cosmwasm_1_2")]
+    #[test]
+    fn assert_code_checksum_works() {
+        let checksum = Checksum::generate(b"reviewed code");
+        let querier: MockQuerier<Empty> = MockQuerier::new(&[]).with_code_info(
+            CodeInfoResponse::new(7, Addr::unchecked("creator"), checksum),
+        );
+        let wrapper = QuerierWrapper::<Empty>::new(&querier);
+
+        // match
+        wrapper.assert_code_checksum(7, &checksum).unwrap();
+
+        // mismatch
+        let other = Checksum::generate(b"other code");
+        let err = wrapper.assert_code_checksum(7, &other).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Generic error: Checksum mismatch for code ID 7: expected {other}, got {checksum}"
+            )
+        );
+
+        // missing code ID
+        let err = wrapper.assert_code_checksum(8, &checksum).unwrap_err();
+        assert!(matches!(
+            err,
+            StdError::GenericErr {
+                msg,
+                ..
+            } if msg == "Querier system error: No such code: 8"
+        ));
+    }
+
     #[test]
     fn querier_into_empty() {
         #[derive(Clone, Serialize, Deserialize)]
@@ -792,4 +1083,46 @@ mod tests {
 
         let _: QuerierWrapper<Empty> = wrapper.into_empty();
     }
+
+    #[test]
+    fn query_custom_works() {
+        #[derive(Clone, Serialize, Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        enum MyQuery {
+            Ping { id: u32 },
+        }
+        impl CustomQuery for MyQuery {}
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct PongResponse {
+            id: u32,
+        }
+
+        let querier: MockQuerier<MyQuery> =
+            MockQuerier::new(&[]).with_custom_handler(|MyQuery::Ping { id }| {
+                if *id == 0 {
+                    return SystemResult::Ok(ContractResult::Err(
+                        "zero is not allowed".to_string(),
+                    ));
+                }
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&PongResponse { id: *id }).unwrap(),
+                ))
+            });
+        let wrapper = QuerierWrapper::<MyQuery>::new(&querier);
+
+        let res: PongResponse = wrapper.query_custom(MyQuery::Ping { id: 7 }).unwrap();
+        assert_eq!(res, PongResponse { id: 7 });
+
+        let err = wrapper
+            .query_custom::<PongResponse>(MyQuery::Ping { id: 0 })
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            StdError::GenericErr {
+                msg,
+                ..
+            } if msg == "Querier contract error: zero is not allowed"
+        ));
+    }
 }
@@ -365,6 +365,57 @@ fn bench_instance_threads(c: &mut Criterion) {
             res
         });
     });
+
+    // Many threads instantiating the same pinned module. Only the module lookup happens
+    // under the cache lock, so the threads should barely wait for each other.
+    c.bench_function("multi-threaded get_instance pinned", |b| {
+        let options = CacheOptions::new(
+            TempDir::new().unwrap().into_path(),
+            capabilities_from_csv(DEFAULT_CAPABILITIES),
+            MEMORY_CACHE_SIZE,
+            DEFAULT_MEMORY_LIMIT,
+        );
+
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(options).unwrap() };
+        let cache = Arc::new(cache);
+        let checksum = cache.store_code(HACKATOM, true, true).unwrap();
+        cache.pin(&checksum).unwrap();
+
+        b.iter_custom(|iters| {
+            let mut res = Duration::from_secs(0);
+            for _ in 0..iters {
+                let mut durations: Vec<_> = (0..INSTANTIATION_THREADS)
+                    .map(|_id| {
+                        let cache = Arc::clone(&cache);
+
+                        thread::spawn(move || {
+                            // Perform measurement internally
+                            let t = SystemTime::now();
+                            black_box(
+                                cache
+                                    .get_instance(
+                                        &checksum,
+                                        mock_backend(&[]),
+                                        DEFAULT_INSTANCE_OPTIONS,
+                                    )
+                                    .unwrap(),
+                            );
+                            t.elapsed().unwrap()
+                        })
+                    })
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap())
+                    .collect(); // join threads, collect durations
+
+                // Calculate median thread duration
+                durations.sort_unstable();
+                res += durations[durations.len() / 2];
+            }
+            res
+        });
+    });
 }
 
 fn bench_combined(c: &mut Criterion) {
@@ -1,3 +1,8 @@
+#[cfg(feature = "iterator")]
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+#[cfg(feature = "iterator")]
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::AddAssign;
 use std::string::FromUtf8Error;
@@ -214,6 +219,178 @@ macro_rules! unwrap_or_return_with_gas {
 }
 pub(crate) use unwrap_or_return_with_gas;
 
+/// A copy-on-write overlay over another [`Storage`].
+///
+/// Reads are answered from the overlay's changes first and fall back to the inner storage.
+/// Writes and removals are only recorded in the overlay and never reach the inner storage.
+/// This allows running contract calls without committing their storage changes,
+/// e.g. for simulations (see [`call_execute_dry_run`](crate::call_execute_dry_run)).
+///
+/// Reads and iterations charge the gas reported by the inner storage. Iterators read from
+/// the inner storage lazily, so each step of an iteration is charged like a step over the
+/// inner storage. Since writes and removals are not forwarded, they are free, which makes
+/// the gas used on top of an overlay only an approximation of the gas of a real execution.
+pub struct OverlayStorage<S: Storage> {
+    inner: S,
+    /// Changes on top of the inner storage. `None` marks a removed key.
+    changes: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+    #[cfg(feature = "iterator")]
+    iterators: HashMap<u32, OverlayIterator>,
+}
+
+/// An iterator over an [`OverlayStorage`]. It merges the records of an iterator over the
+/// inner storage with the overlay's changes in the same range.
+#[cfg(feature = "iterator")]
+struct OverlayIterator {
+    inner_id: u32,
+    order: Order,
+    /// A record of the inner iterator that was read but not returned yet
+    peeked: Option<Record>,
+    inner_done: bool,
+    /// The changes in the iterator's range at the time of the scan, in iteration order
+    changes: std::iter::Peekable<std::vec::IntoIter<(Vec<u8>, Option<Vec<u8>>)>>,
+}
+
+impl<S: Storage> OverlayStorage<S> {
+    pub fn new(inner: S) -> Self {
+        OverlayStorage {
+            inner,
+            changes: BTreeMap::new(),
+            #[cfg(feature = "iterator")]
+            iterators: HashMap::new(),
+        }
+    }
+
+    /// The changes recorded so far. A value of `None` means the key was removed.
+    pub fn changes(&self) -> &BTreeMap<Vec<u8>, Option<Vec<u8>>> {
+        &self.changes
+    }
+
+    /// Returns the changes recorded so far and resets the overlay to the state
+    /// of the inner storage.
+    pub fn take_changes(&mut self) -> BTreeMap<Vec<u8>, Option<Vec<u8>>> {
+        std::mem::take(&mut self.changes)
+    }
+
+    /// Replaces the recorded changes, e.g. to restore a previous state of the overlay.
+    pub(crate) fn restore_changes(&mut self, changes: BTreeMap<Vec<u8>, Option<Vec<u8>>>) {
+        self.changes = changes;
+    }
+
+    /// Discards all changes and returns the inner storage.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Storage> Storage for OverlayStorage<S> {
+    fn get(&self, key: &[u8]) -> BackendResult<Option<Vec<u8>>> {
+        // We always query the inner storage to charge the same gas as without the overlay
+        let (result, gas_info) = self.inner.get(key);
+        match self.changes.get(key) {
+            Some(change) => (Ok(change.clone()), gas_info),
+            None => (result, gas_info),
+        }
+    }
+
+    #[cfg(feature = "iterator")]
+    fn scan(
+        &mut self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> BackendResult<u32> {
+        let (result, gas_info) = self.inner.scan(start, end, order);
+        let inner_id = unwrap_or_return_with_gas!(result, gas_info);
+        let in_range = |key: &[u8]| {
+            start.iter().all(|&start| key >= start) && end.iter().all(|&end| key < end)
+        };
+        let mut changes: Vec<_> = self
+            .changes
+            .iter()
+            .filter(|(key, _)| in_range(key))
+            .map(|(key, change)| (key.clone(), change.clone()))
+            .collect();
+        if order == Order::Descending {
+            changes.reverse();
+        }
+
+        let last_id: u32 = self
+            .iterators
+            .len()
+            .try_into()
+            .expect("Found more iterator IDs than supported");
+        let new_id = last_id + 1;
+        self.iterators.insert(
+            new_id,
+            OverlayIterator {
+                inner_id,
+                order,
+                peeked: None,
+                inner_done: false,
+                changes: changes.into_iter().peekable(),
+            },
+        );
+        (Ok(new_id), gas_info)
+    }
+
+    #[cfg(feature = "iterator")]
+    fn next(&mut self, iterator_id: u32) -> BackendResult<Option<Record>> {
+        let Some(iterator) = self.iterators.get_mut(&iterator_id) else {
+            return (
+                Err(BackendError::iterator_does_not_exist(iterator_id)),
+                GasInfo::free(),
+            );
+        };
+
+        // The inner iterator is advanced lazily, such that each record read from the inner
+        // storage is charged when it is needed. Only keys removed in the overlay cause
+        // additional inner reads, since they are skipped.
+        let mut gas_info = GasInfo::free();
+        loop {
+            if iterator.peeked.is_none() && !iterator.inner_done {
+                let (result, info) = self.inner.next(iterator.inner_id);
+                gas_info += info;
+                match unwrap_or_return_with_gas!(result, gas_info) {
+                    Some(record) => iterator.peeked = Some(record),
+                    None => iterator.inner_done = true,
+                }
+            }
+
+            // `Less` means the inner record comes first, `Greater` the change
+            let ordering = match (&iterator.peeked, iterator.changes.peek()) {
+                (None, None) => return (Ok(None), gas_info),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some((inner_key, _)), Some((change_key, _))) => match iterator.order {
+                    Order::Ascending => inner_key.cmp(change_key),
+                    Order::Descending => change_key.cmp(inner_key),
+                },
+            };
+            match ordering {
+                Ordering::Less => return (Ok(iterator.peeked.take()), gas_info),
+                // the change shadows the inner record
+                Ordering::Equal => iterator.peeked = None,
+                Ordering::Greater => {}
+            }
+            if let Some((key, Some(value))) = iterator.changes.next() {
+                return (Ok(Some((key, value))), gas_info);
+            }
+            // the key was removed in the overlay
+        }
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) -> BackendResult<()> {
+        self.changes.insert(key.to_vec(), Some(value.to_vec()));
+        (Ok(()), GasInfo::free())
+    }
+
+    fn remove(&mut self, key: &[u8]) -> BackendResult<()> {
+        self.changes.insert(key.to_vec(), None);
+        (Ok(()), GasInfo::free())
+    }
+}
+
 #[derive(Error, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum BackendError {
@@ -269,6 +446,7 @@ impl From<FromUtf8Error> for BackendError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::MockStorage;
 
     #[test]
     fn gas_info_with_cost_works() {
@@ -420,4 +598,151 @@ mod tests {
             e => panic!("Unexpected error: {e:?}"),
         }
     }
+
+    #[test]
+    fn overlay_storage_get_set_remove_works() {
+        let mut inner = MockStorage::new();
+        inner.set(b"foo", b"bar").0.unwrap();
+        inner.set(b"gone", b"soon").0.unwrap();
+
+        let mut overlay = OverlayStorage::new(inner);
+        assert_eq!(overlay.get(b"foo").0.unwrap(), Some(b"bar".to_vec()));
+
+        overlay.set(b"foo", b"baz").0.unwrap();
+        overlay.set(b"new", b"value").0.unwrap();
+        overlay.remove(b"gone").0.unwrap();
+        assert_eq!(overlay.get(b"foo").0.unwrap(), Some(b"baz".to_vec()));
+        assert_eq!(overlay.get(b"new").0.unwrap(), Some(b"value".to_vec()));
+        assert_eq!(overlay.get(b"gone").0.unwrap(), None);
+
+        // reads are charged like in the inner storage
+        assert_eq!(overlay.get(b"foo").1, MockStorage::new().get(b"foo").1);
+
+        assert_eq!(
+            overlay.changes(),
+            &BTreeMap::from([
+                (b"foo".to_vec(), Some(b"baz".to_vec())),
+                (b"gone".to_vec(), None),
+                (b"new".to_vec(), Some(b"value".to_vec())),
+            ])
+        );
+
+        // the inner storage is untouched
+        let inner = overlay.into_inner();
+        assert_eq!(inner.get(b"foo").0.unwrap(), Some(b"bar".to_vec()));
+        assert_eq!(inner.get(b"new").0.unwrap(), None);
+        assert_eq!(inner.get(b"gone").0.unwrap(), Some(b"soon".to_vec()));
+    }
+
+    #[test]
+    fn overlay_storage_take_changes_works() {
+        let mut overlay = OverlayStorage::new(MockStorage::new());
+        overlay.set(b"foo", b"bar").0.unwrap();
+
+        let changes = overlay.take_changes();
+        assert_eq!(
+            changes,
+            BTreeMap::from([(b"foo".to_vec(), Some(b"bar".to_vec()))])
+        );
+        assert!(overlay.changes().is_empty());
+        assert_eq!(overlay.get(b"foo").0.unwrap(), None);
+    }
+
+    #[cfg(feature = "iterator")]
+    fn record(key: &[u8], value: &[u8]) -> Record {
+        (key.to_vec(), value.to_vec())
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn overlay_storage_scan_merges_changes() {
+        let mut inner = MockStorage::new();
+        for key in [b"a", b"b", b"c", b"d"] {
+            inner.set(key, key).0.unwrap();
+        }
+
+        let mut overlay = OverlayStorage::new(inner);
+        overlay.set(b"b", b"B").0.unwrap();
+        overlay.set(b"bb", b"BB").0.unwrap();
+        overlay.set(b"e", b"E").0.unwrap();
+        overlay.remove(b"c").0.unwrap();
+
+        let mut collect = |start: Option<&[u8]>, end: Option<&[u8]>, order: Order| {
+            let id = overlay.scan(start, end, order).0.unwrap();
+            let mut out = vec![];
+            while let Some(record) = overlay.next(id).0.unwrap() {
+                out.push(record);
+            }
+            out
+        };
+
+        assert_eq!(
+            collect(None, None, Order::Ascending),
+            [
+                record(b"a", b"a"),
+                record(b"b", b"B"),
+                record(b"bb", b"BB"),
+                record(b"d", b"d"),
+                record(b"e", b"E"),
+            ]
+        );
+        assert_eq!(
+            collect(
+                Some(b"b".as_slice()),
+                Some(b"e".as_slice()),
+                Order::Descending
+            ),
+            [record(b"d", b"d"), record(b"bb", b"BB"), record(b"b", b"B")]
+        );
+        assert_eq!(
+            collect(
+                Some(b"bb".as_slice()),
+                Some(b"d".as_slice()),
+                Order::Ascending
+            ),
+            [record(b"bb", b"BB")]
+        );
+        assert!(collect(
+            Some(b"d".as_slice()),
+            Some(b"b".as_slice()),
+            Order::Ascending
+        )
+        .is_empty());
+
+        let err = overlay.next(42).0.unwrap_err();
+        assert_eq!(err, BackendError::iterator_does_not_exist(42));
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn overlay_storage_iteration_charges_same_gas_as_inner_storage() {
+        let mut plain = MockStorage::new();
+        for key in [b"a", b"b", b"c", b"d"] {
+            plain.set(key, key).0.unwrap();
+        }
+        let mut overlay = OverlayStorage::new(plain.clone());
+
+        for order in [Order::Ascending, Order::Descending] {
+            let (result, plain_gas) = plain.scan(None, None, order);
+            let plain_id = result.unwrap();
+            let (result, overlay_gas) = overlay.scan(None, None, order);
+            let overlay_id = result.unwrap();
+            assert_eq!(overlay_gas, plain_gas);
+
+            // every step charges the same gas, so a partial iteration is not charged
+            // for the rest of the range
+            for _ in 0..5 {
+                let (plain_record, plain_gas) = plain.next(plain_id);
+                let (overlay_record, overlay_gas) = overlay.next(overlay_id);
+                assert_eq!(overlay_record.unwrap(), plain_record.unwrap());
+                assert_eq!(overlay_gas, plain_gas);
+            }
+        }
+
+        // a partial iteration only reads the records it returns
+        let id = overlay.scan(None, None, Order::Ascending).0.unwrap();
+        let (result, gas_info) = overlay.next(id);
+        assert_eq!(result.unwrap(), Some(record(b"a", b"a")));
+        assert_eq!(gas_info, GasInfo::with_cost(2));
+    }
 }
@@ -1,22 +1,26 @@
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs::{self, File, OpenOptions};
 use std::io::{Read, Write};
 use std::marker::PhantomData;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::sync::Mutex;
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::time::SystemTime;
 use wasmer::{Module, Store};
 
 use cosmwasm_std::Checksum;
 
 use crate::backend::{Backend, BackendApi, Querier, Storage};
-use crate::capabilities::required_capabilities_from_module;
+use crate::capabilities::{
+    required_capabilities_for_entrypoint, required_capabilities_from_module,
+};
 use crate::compatibility::check_wasm;
-use crate::config::{CacheOptions, Config, WasmLimits};
+use crate::config::{CacheOptions, CodePolicy, Config, GasCosts, WasmLimits};
 use crate::errors::{VmError, VmResult};
 use crate::filesystem::mkdir_p;
 use crate::instance::{Instance, InstanceOptions};
-use crate::modules::{CachedModule, FileSystemCache, InMemoryCache, PinnedMemoryCache};
+use crate::modules::{CachedModule, FileSystemCache, InMemoryCache, PinPolicy, PinnedMemoryCache};
 use crate::parsed_wasm::ParsedWasm;
 use crate::size::Size;
 use crate::static_analysis::{Entrypoint, ExportInfo, REQUIRED_IBC_EXPORTS};
@@ -60,6 +64,12 @@ pub struct PerModuleMetrics {
     pub hits: u32,
     /// Size the module takes up in memory
     pub size: usize,
+    /// The time the module was pinned
+    pub pinned_at: SystemTime,
+    /// The time of the last load of the module. Equal to `pinned_at` if it was never loaded.
+    pub last_access: SystemTime,
+    /// The policy determining when the module is unpinned by [`Cache::prune_pinned`]
+    pub policy: PinPolicy,
 }
 
 #[derive(Debug, Clone)]
@@ -67,6 +77,8 @@ pub struct PinnedMetrics {
     // It is *intentional* that this is only a vector
     // We don't need a potentially expensive hashing algorithm here
     // The checksums are sourced from a hashmap already, ensuring uniqueness of the checksums
+    /// Metrics of all pinned modules, sorted by hits in descending order, i.e. the most
+    /// used module comes first. Modules with the same number of hits are sorted by checksum.
     pub per_module: Vec<(Checksum, PerModuleMetrics)>,
 }
 
@@ -77,6 +89,9 @@ pub struct CacheInner {
     memory_cache: InMemoryCache,
     fs_cache: FileSystemCache,
     stats: Stats,
+    /// The checksums of the modules [`Cache::get_module`] is currently compiling
+    /// without holding the lock.
+    compiling: HashSet<Checksum>,
 }
 
 pub struct Cache<A: BackendApi, S: Storage, Q: Querier> {
@@ -91,7 +106,18 @@ pub struct Cache<A: BackendApi, S: Storage, Q: Querier> {
     type_querier: PhantomData<Q>,
     /// To prevent concurrent access to `WasmerInstance::new`
     instantiation_lock: Mutex<()>,
+    /// Bounds the number of concurrent calls to `compile`
+    compilation_limiter: CompilationLimiter,
+    /// Notified whenever [`Cache::get_module`] finished compiling a module, such that
+    /// calls waiting for the same module look it up again.
+    compilation_finished: Condvar,
     wasm_limits: WasmLimits,
+    gas_costs: GasCosts,
+    /// Checked for every new instance. Can be replaced at runtime, so it is not part of `inner`
+    /// to avoid waiting for module lookups.
+    code_policy: RwLock<Option<CodePolicy>>,
+    /// Provides the current time for the pinned memory cache's access tracking
+    clock: Box<dyn Fn() -> SystemTime + Send + Sync>,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -104,6 +130,9 @@ pub struct AnalysisReport {
     pub entrypoints: BTreeSet<Entrypoint>,
     /// The set of capabilities the contract requires.
     pub required_capabilities: BTreeSet<String>,
+    /// The capabilities implied by each of the exported entrypoints, e.g. the IBC entrypoints
+    /// require `stargate`. Contains an entry for every element of `entrypoints`.
+    pub required_capabilities_for: BTreeMap<Entrypoint, BTreeSet<String>>,
     /// The contract migrate version exported set by the contract developer
     pub contract_migrate_version: Option<u64>,
 }
@@ -125,6 +154,8 @@ where
         Self::new_with_config(Config {
             wasm_limits: WasmLimits::default(),
             cache: options,
+            gas_costs: GasCosts::default(),
+            code_policy: None,
         })
     }
 
@@ -144,8 +175,11 @@ where
                     available_capabilities,
                     memory_cache_size_bytes,
                     instance_memory_limit_bytes,
+                    max_concurrent_compilations,
                 },
             wasm_limits,
+            gas_costs,
+            code_policy,
         } = config;
 
         let state_path = base_dir.join(STATE_DIR);
@@ -168,13 +202,19 @@ where
                 memory_cache: InMemoryCache::new(memory_cache_size_bytes),
                 fs_cache,
                 stats: Stats::default(),
+                compiling: HashSet::new(),
             }),
             instance_memory_limit: instance_memory_limit_bytes,
             type_storage: PhantomData::<S>,
             type_api: PhantomData::<A>,
             type_querier: PhantomData::<Q>,
             instantiation_lock: Mutex::new(()),
+            compilation_limiter: CompilationLimiter::new(max_concurrent_compilations),
+            compilation_finished: Condvar::new(),
             wasm_limits,
+            gas_costs,
+            code_policy: RwLock::new(code_policy),
+            clock: Box::new(SystemTime::now),
         })
     }
 
@@ -188,24 +228,49 @@ where
             .set_module_unchecked(unchecked);
     }
 
+    /// Replaces the clock used to record pin and access times of pinned modules.
+    /// By default, the system time is used. This is mainly useful for testing.
+    pub fn set_clock(&mut self, clock: impl Fn() -> SystemTime + Send + Sync + 'static) {
+        self.clock = Box::new(clock);
+    }
+
+    /// Replaces the [`CodePolicy`] of this cache. `None` allows all codes.
+    ///
+    /// This affects all subsequent calls to [`Cache::get_instance`]. Instances that were
+    /// created before are not affected, i.e. calls that are already running complete normally.
+    ///
+    /// See [`Config::code_policy`] for why this must not be used on consensus nodes.
+    pub fn set_code_policy(&self, policy: Option<CodePolicy>) {
+        *self.code_policy.write().unwrap() = policy;
+    }
+
     pub fn stats(&self) -> Stats {
         self.inner.lock().unwrap().stats
     }
 
     pub fn pinned_metrics(&self) -> PinnedMetrics {
         let cache = self.inner.lock().unwrap();
-        let per_module = cache
+        let mut per_module: Vec<_> = cache
             .pinned_memory_cache
             .iter()
             .map(|(checksum, module)| {
                 let metrics = PerModuleMetrics {
                     hits: module.hits,
                     size: module.module.size_estimate,
+                    pinned_at: module.pinned_at,
+                    last_access: module.last_access,
+                    policy: module.policy,
                 };
 
                 (*checksum, metrics)
             })
             .collect();
+        per_module.sort_by(|(checksum_a, metrics_a), (checksum_b, metrics_b)| {
+            metrics_b
+                .hits
+                .cmp(&metrics_a.hits)
+                .then_with(|| checksum_a.cmp(checksum_b))
+        });
 
         PinnedMetrics { per_module }
     }
@@ -251,7 +316,7 @@ where
             )?;
         }
 
-        let module = compile_module(wasm)?;
+        let module = self.compile_module(wasm)?;
 
         if persist {
             self.save_to_disk(wasm, &module)
@@ -299,6 +364,26 @@ where
         Ok(())
     }
 
+    /// Removes a contract stored via [`Cache::store_code`] entirely: the Wasm blob and
+    /// the compiled module on disk as well as the module in the memory caches, including
+    /// a pinned module.
+    ///
+    /// Use this when a chain prunes code. In contrast to [`Cache::remove_wasm`], this does not
+    /// leave modules in memory. Instances created before are not affected.
+    ///
+    /// Returns an error if no Wasm blob is stored for the given checksum. In this case
+    /// nothing is removed.
+    pub fn remove_code(&self, checksum: &Checksum) -> VmResult<()> {
+        let mut cache = self.inner.lock().unwrap();
+
+        // This errors if the code is unknown, so do this first
+        remove_wasm_from_disk(&cache.wasm_path, checksum)?;
+        cache.fs_cache.remove(checksum)?;
+        cache.memory_cache.remove(checksum);
+        cache.pinned_memory_cache.remove(checksum)?;
+        Ok(())
+    }
+
     /// Retrieves a Wasm blob that was previously stored via [`Cache::store_code`].
     /// When the cache is instantiated with the same base dir, this finds Wasm files on disc across multiple cache instances (i.e. node restarts).
     /// This function is public to allow a checksum to Wasm lookup in the blockchain.
@@ -328,10 +413,17 @@ where
         let module = ParsedWasm::parse(&wasm)?;
         let exports = module.exported_function_names(None);
 
-        let entrypoints = exports
+        let entrypoints: BTreeSet<Entrypoint> = exports
             .iter()
             .filter_map(|export| Entrypoint::from_str(export).ok())
             .collect();
+        let required_capabilities_for = entrypoints
+            .iter()
+            .map(|&entrypoint| {
+                let capabilities = required_capabilities_for_entrypoint(entrypoint);
+                (entrypoint, capabilities.into_iter().collect())
+            })
+            .collect();
 
         Ok(AnalysisReport {
             has_ibc_entry_points: REQUIRED_IBC_EXPORTS
@@ -341,6 +433,7 @@ where
             required_capabilities: required_capabilities_from_module(&module)
                 .into_iter()
                 .collect(),
+            required_capabilities_for,
             contract_migrate_version: module.contract_migrate_version,
         })
     }
@@ -353,9 +446,25 @@ where
     ///
     /// If the given contract for the given checksum is not found, or the content
     /// does not match the checksum, an error is returned.
+    ///
+    /// The module stays pinned until it is unpinned explicitly. Use [`Cache::pin_with_policy`]
+    /// to unpin it automatically.
     pub fn pin(&self, checksum: &Checksum) -> VmResult<()> {
+        self.pin_with_policy(checksum, PinPolicy::default())
+    }
+
+    /// Pins a Module just like [`Cache::pin`] but allows it to be unpinned automatically
+    /// by [`Cache::prune_pinned`] according to the given policy.
+    ///
+    /// If the module is pinned already, its policy is replaced and its pin time is reset,
+    /// i.e. the TTL starts again.
+    pub fn pin_with_policy(&self, checksum: &Checksum, policy: PinPolicy) -> VmResult<()> {
+        let now = (self.clock)();
         let mut cache = self.inner.lock().unwrap();
-        if cache.pinned_memory_cache.has(checksum) {
+        if cache
+            .pinned_memory_cache
+            .update_policy(checksum, policy, now)
+        {
             return Ok(());
         }
 
@@ -369,17 +478,17 @@ where
             .load(checksum, Some(self.instance_memory_limit))?
         {
             cache.stats.hits_fs_cache = cache.stats.hits_fs_cache.saturating_add(1);
-            return cache.pinned_memory_cache.store(checksum, cached_module);
+            return cache
+                .pinned_memory_cache
+                .store(checksum, cached_module, policy, now);
         }
 
         // Re-compile from original Wasm bytecode
         let wasm = self.load_wasm_with_path(&cache.wasm_path, checksum)?;
         cache.stats.misses = cache.stats.misses.saturating_add(1);
         {
-            // Module will run with a different engine, so we can set memory limit to None
-            let compiling_engine = make_compiling_engine(None);
             // This module cannot be executed directly as it was not created with the runtime engine
-            let module = compile(&compiling_engine, &wasm)?;
+            let module = self.compile_module(&wasm)?;
             cache.fs_cache.store(checksum, &module)?;
         }
 
@@ -393,7 +502,9 @@ where
             ));
         };
 
-        cache.pinned_memory_cache.store(checksum, cached_module)
+        cache
+            .pinned_memory_cache
+            .store(checksum, cached_module, policy, now)
     }
 
     /// Unpins a Module, i.e. removes it from the pinned memory cache.
@@ -408,73 +519,96 @@ where
             .remove(checksum)
     }
 
+    /// Unpins all modules whose [`PinPolicy`] is expired at time `now` and returns
+    /// their checksums.
+    ///
+    /// This is a maintenance operation that is not triggered automatically. Modules pinned
+    /// via [`Cache::pin`] are never pruned. Use [`Cache::pinned_metrics`] to inspect the
+    /// pin and last access times before pruning.
+    pub fn prune_pinned(&self, now: SystemTime) -> VmResult<Vec<Checksum>> {
+        Ok(self.inner.lock().unwrap().pinned_memory_cache.prune(now))
+    }
+
     /// Returns an Instance tied to a previously saved Wasm.
     ///
     /// It takes a module from cache or Wasm code and instantiates it.
+    /// The cache is only locked for the module lookup. Instantiation happens
+    /// without holding the cache lock.
+    ///
+    /// Fails with [`VmError::CodeBlocked`] if the [`CodePolicy`] does not allow the code.
     pub fn get_instance(
         &self,
         checksum: &Checksum,
         backend: Backend<A, S, Q>,
         options: InstanceOptions,
     ) -> VmResult<Instance<A, S, Q>> {
-        let (module, store) = self.get_module(checksum)?;
-        let instance = Instance::from_module(
+        if let Some(policy) = self.code_policy.read().unwrap().as_ref() {
+            if !policy.allows(checksum) {
+                return Err(VmError::code_blocked(*checksum));
+            }
+        }
+        let module = self.get_module(checksum)?;
+        let store = Store::new(module.engine.clone());
+        let mut instance = Instance::from_module(
             store,
-            &module,
+            &module.module,
             backend,
             options.gas_limit,
             None,
             Some(&self.instantiation_lock),
         )?;
+        instance.set_allocation_limit(self.wasm_limits.max_allocations_per_call());
+        instance.set_memory_grow_gas_per_page(self.wasm_limits.memory_grow_gas_per_page());
+        instance.set_max_call_depth(self.wasm_limits.max_call_depth());
+        instance.set_response_limits(self.wasm_limits.response_limits());
+        instance.set_result_limits(self.wasm_limits.result_limits);
+        instance.set_raw_result_limits(self.wasm_limits.raw_result_limits());
+        instance.set_gas_costs(self.gas_costs.clone());
         Ok(instance)
     }
 
     /// Returns a module tied to a previously saved Wasm.
     /// Depending on availability, this is either generated from a memory cache, file system cache or Wasm code.
-    /// This is part of `get_instance` but pulled out to reduce the locking time.
-    fn get_module(&self, checksum: &Checksum) -> VmResult<(Module, Store)> {
+    ///
+    /// This is what [`Cache::get_instance`] uses to look up the module. It can be used
+    /// to warm up the caches before instances are needed. The same stats are recorded.
+    ///
+    /// The cache is locked for the lookups but not while compiling a module from Wasm.
+    /// Concurrent calls for a module that is being compiled wait for that compilation
+    /// instead of compiling the module again.
+    pub fn get_module(&self, checksum: &Checksum) -> VmResult<Arc<CachedModule>> {
+        let now = (self.clock)();
         let mut cache = self.inner.lock().unwrap();
-        // Try to get module from the pinned memory cache
-        if let Some(element) = cache.pinned_memory_cache.load(checksum)? {
-            cache.stats.hits_pinned_memory_cache =
-                cache.stats.hits_pinned_memory_cache.saturating_add(1);
-            let CachedModule {
-                module,
-                engine,
-                size_estimate: _,
-            } = element;
-            let store = Store::new(engine);
-            return Ok((module, store));
-        }
+        loop {
+            // Try to get module from the pinned memory cache
+            if let Some(element) = cache.pinned_memory_cache.load(checksum, now)? {
+                cache.stats.hits_pinned_memory_cache =
+                    cache.stats.hits_pinned_memory_cache.saturating_add(1);
+                return Ok(element);
+            }
 
-        // Get module from memory cache
-        if let Some(element) = cache.memory_cache.load(checksum)? {
-            cache.stats.hits_memory_cache = cache.stats.hits_memory_cache.saturating_add(1);
-            let CachedModule {
-                module,
-                engine,
-                size_estimate: _,
-            } = element;
-            let store = Store::new(engine);
-            return Ok((module, store));
-        }
+            // Get module from memory cache
+            if let Some(element) = cache.memory_cache.load(checksum)? {
+                cache.stats.hits_memory_cache = cache.stats.hits_memory_cache.saturating_add(1);
+                return Ok(element);
+            }
 
-        // Get module from file system cache
-        if let Some(cached_module) = cache
-            .fs_cache
-            .load(checksum, Some(self.instance_memory_limit))?
-        {
-            cache.stats.hits_fs_cache = cache.stats.hits_fs_cache.saturating_add(1);
+            // Get module from file system cache
+            if let Some(cached_module) = cache
+                .fs_cache
+                .load(checksum, Some(self.instance_memory_limit))?
+            {
+                cache.stats.hits_fs_cache = cache.stats.hits_fs_cache.saturating_add(1);
 
-            cache.memory_cache.store(checksum, cached_module.clone())?;
+                cache.memory_cache.store(checksum, cached_module.clone())?;
+                return Ok(Arc::new(cached_module));
+            }
 
-            let CachedModule {
-                module,
-                engine,
-                size_estimate: _,
-            } = cached_module;
-            let store = Store::new(engine);
-            return Ok((module, store));
+            if !cache.compiling.contains(checksum) {
+                break;
+            }
+            // Another call is compiling this module. Wait for it and look it up again.
+            cache = self.compilation_finished.wait(cache).unwrap();
         }
 
         // Re-compile module from wasm
@@ -482,15 +616,26 @@ where
         // This is needed for chains that upgrade their node software in a way that changes the module
         // serialization format. If you do not replay all transactions, previous calls of `store_code`
         // stored the old module format.
-        let wasm = self.load_wasm_with_path(&cache.wasm_path, checksum)?;
         cache.stats.misses = cache.stats.misses.saturating_add(1);
-        {
-            // Module will run with a different engine, so we can set memory limit to None
-            let compiling_engine = make_compiling_engine(None);
-            // This module cannot be executed directly as it was not created with the runtime engine
-            let module = compile(&compiling_engine, &wasm)?;
-            cache.fs_cache.store(checksum, &module)?;
+        cache.compiling.insert(*checksum);
+        let wasm_path = cache.wasm_path.clone();
+        // Release the lock while compiling, such that other modules can be loaded in the meantime
+        drop(cache);
+        // This module cannot be executed directly as it was not created with the runtime engine
+        let compiled = self
+            .load_wasm_with_path(&wasm_path, checksum)
+            .and_then(|wasm| self.compile_module(&wasm));
+        let mut cache = self.inner.lock().unwrap();
+        cache.compiling.remove(checksum);
+        self.compilation_finished.notify_all();
+        let module = compiled?;
+
+        // The code might have been removed while compiling. Storing the module now would
+        // bring it back into the caches.
+        if !wasm_exists_on_disk(&cache.wasm_path, checksum) {
+            return Err(VmError::cache_err("Wasm file does not exist"));
         }
+        cache.fs_cache.store(checksum, &module)?;
 
         // This time we'll hit the file-system cache.
         let Some(cached_module) = cache
@@ -502,21 +647,60 @@ where
             ));
         };
         cache.memory_cache.store(checksum, cached_module.clone())?;
+        Ok(Arc::new(cached_module))
+    }
 
-        let CachedModule {
-            module,
-            engine,
-            size_estimate: _,
-        } = cached_module;
-        let store = Store::new(engine);
-        Ok((module, store))
+    /// Compiles the Wasm bytecode, waiting for a free slot if the number of
+    /// concurrent compilations is limited.
+    fn compile_module(&self, wasm: &[u8]) -> Result<Module, VmError> {
+        let _permit = self.compilation_limiter.acquire();
+        // Module will run with a different engine, so we can set memory limit to None
+        let compiling_engine = make_compiling_engine(None);
+        let module = compile(&compiling_engine, wasm)?;
+        Ok(module)
     }
 }
 
-fn compile_module(wasm: &[u8]) -> Result<Module, VmError> {
-    let compiling_engine = make_compiling_engine(None);
-    let module = compile(&compiling_engine, wasm)?;
-    Ok(module)
+/// A counting semaphore limiting the number of concurrent compilations.
+/// A limit of `None` never blocks.
+struct CompilationLimiter {
+    limit: Option<NonZeroUsize>,
+    running: Mutex<usize>,
+    finished: Condvar,
+}
+
+impl CompilationLimiter {
+    fn new(limit: Option<NonZeroUsize>) -> Self {
+        Self {
+            limit,
+            running: Mutex::new(0),
+            finished: Condvar::new(),
+        }
+    }
+
+    /// Blocks until a compilation slot is available. The slot is released
+    /// when the returned permit is dropped.
+    fn acquire(&self) -> Option<CompilationPermit<'_>> {
+        let limit = self.limit?.get();
+        let mut running = self
+            .finished
+            .wait_while(self.running.lock().unwrap(), |running| *running >= limit)
+            .unwrap();
+        *running += 1;
+        Some(CompilationPermit { limiter: self })
+    }
+}
+
+struct CompilationPermit<'a> {
+    limiter: &'a CompilationLimiter,
+}
+
+impl Drop for CompilationPermit<'_> {
+    fn drop(&mut self) {
+        let mut running = self.limiter.running.lock().unwrap();
+        *running -= 1;
+        self.limiter.finished.notify_one();
+    }
 }
 
 unsafe impl<A, S, Q> Sync for Cache<A, S, Q>
@@ -574,6 +758,13 @@ fn load_wasm_from_disk(dir: impl Into<PathBuf>, checksum: &Checksum) -> VmResult
     Ok(wasm)
 }
 
+/// Returns `true` if a Wasm blob for the given checksum is stored in the directory.
+fn wasm_exists_on_disk(dir: impl Into<PathBuf>, checksum: &Checksum) -> bool {
+    // the files previously had no extension, so we check both
+    let path = dir.into().join(checksum.to_hex());
+    path.with_extension("wasm").exists() || path.exists()
+}
+
 /// Removes the Wasm blob for the given checksum from disk.
 ///
 /// In contrast to the file system cache, the existence of the original
@@ -608,10 +799,13 @@ mod tests {
     use super::*;
     use crate::calls::{call_execute, call_instantiate};
     use crate::capabilities::capabilities_from_csv;
+    use crate::config::LinearGasCost;
     use crate::testing::{mock_backend, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
     use cosmwasm_std::{coins, Empty};
     use std::borrow::Cow;
     use std::fs::{create_dir_all, remove_dir_all};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
     use tempfile::TempDir;
     use wasm_encoder::ComponentSection;
 
@@ -644,6 +838,7 @@ mod tests {
             available_capabilities: default_capabilities(),
             memory_cache_size_bytes: TESTING_MEMORY_CACHE_SIZE,
             instance_memory_limit_bytes: TESTING_MEMORY_LIMIT,
+            max_concurrent_compilations: None,
         }
     }
 
@@ -655,6 +850,7 @@ mod tests {
             available_capabilities: capabilities,
             memory_cache_size_bytes: TESTING_MEMORY_CACHE_SIZE,
             instance_memory_limit_bytes: TESTING_MEMORY_LIMIT,
+            max_concurrent_compilations: None,
         }
     }
 
@@ -797,6 +993,7 @@ mod tests {
                 available_capabilities: default_capabilities(),
                 memory_cache_size_bytes: TESTING_MEMORY_CACHE_SIZE,
                 instance_memory_limit_bytes: TESTING_MEMORY_LIMIT,
+                max_concurrent_compilations: None,
             };
             let cache1: Cache<MockApi, MockStorage, MockQuerier> =
                 unsafe { Cache::new(options1).unwrap() };
@@ -809,6 +1006,7 @@ mod tests {
                 available_capabilities: default_capabilities(),
                 memory_cache_size_bytes: TESTING_MEMORY_CACHE_SIZE,
                 instance_memory_limit_bytes: TESTING_MEMORY_LIMIT,
+                max_concurrent_compilations: None,
             };
             let cache2: Cache<MockApi, MockStorage, MockQuerier> =
                 unsafe { Cache::new(options2).unwrap() };
@@ -842,6 +1040,7 @@ mod tests {
             available_capabilities: default_capabilities(),
             memory_cache_size_bytes: TESTING_MEMORY_CACHE_SIZE,
             instance_memory_limit_bytes: TESTING_MEMORY_LIMIT,
+            max_concurrent_compilations: None,
         };
         let cache: Cache<MockApi, MockStorage, MockQuerier> =
             unsafe { Cache::new(options).unwrap() };
@@ -896,6 +1095,219 @@ mod tests {
         }
     }
 
+    #[test]
+    fn remove_code_works() {
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(make_testing_options()).unwrap() };
+
+        // Store, load into memory cache and pin
+        let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+        let _instance = cache
+            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap();
+        cache.pin(&checksum).unwrap();
+        let metrics = cache.metrics();
+        assert_eq!(metrics.elements_memory_cache, 1);
+        assert_eq!(metrics.elements_pinned_memory_cache, 1);
+
+        // Remove
+        cache.remove_code(&checksum).unwrap();
+        let metrics = cache.metrics();
+        assert_eq!(metrics.elements_memory_cache, 0);
+        assert_eq!(metrics.elements_pinned_memory_cache, 0);
+        match cache.load_wasm(&checksum).unwrap_err() {
+            VmError::CacheErr { msg, .. } => {
+                assert_eq!(msg, "Error opening Wasm file for reading")
+            }
+            e => panic!("Unexpected error: {e:?}"),
+        }
+        // the module is not found in any cache and cannot be recompiled
+        let stats_before = cache.stats();
+        cache
+            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap_err();
+        assert_eq!(cache.stats().misses, stats_before.misses + 1);
+
+        // Removing again fails
+        match cache.remove_code(&checksum).unwrap_err() {
+            VmError::CacheErr { msg, .. } => {
+                assert_eq!(msg, "Wasm file does not exist")
+            }
+            e => panic!("Unexpected error: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn remove_code_errors_for_unknown_checksum() {
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(make_testing_options()).unwrap() };
+        let stored = cache.store_code(CONTRACT, true, true).unwrap();
+        cache.pin(&stored).unwrap();
+
+        let unknown = Checksum::generate(b"unknown");
+        match cache.remove_code(&unknown).unwrap_err() {
+            VmError::CacheErr { msg, .. } => {
+                assert_eq!(msg, "Wasm file does not exist")
+            }
+            e => panic!("Unexpected error: {e:?}"),
+        }
+
+        // other code is not affected
+        assert_eq!(cache.metrics().elements_pinned_memory_cache, 1);
+        cache.load_wasm(&stored).unwrap();
+    }
+
+    #[test]
+    fn remove_code_works_concurrently_with_get_instance() {
+        const THREADS: usize = 4;
+        const ROUNDS: usize = 10;
+
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(make_testing_options()).unwrap() };
+        let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+        let removed: Vec<_> = (0..ROUNDS)
+            .map(|i| {
+                // distinct modules that are not checked since they are never instantiated
+                let wasm = wat::parse_str(format!(
+                    r#"(module
+                        (memory 3)
+                        (export "memory" (memory 0))
+                        (func (export "foo") i32.const {i} drop)
+                    )"#
+                ))
+                .unwrap();
+                cache.store_code(&wasm, false, true).unwrap()
+            })
+            .collect();
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                for checksum in &removed {
+                    cache.remove_code(checksum).unwrap();
+                }
+            });
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    for _ in 0..ROUNDS {
+                        let mut instance = cache
+                            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+                            .unwrap();
+                        test_hackatom_instance_execution(&mut instance);
+                    }
+                });
+            }
+        });
+
+        for checksum in &removed {
+            cache.load_wasm(checksum).unwrap_err();
+        }
+        cache.load_wasm(&checksum).unwrap();
+    }
+
+    #[test]
+    fn remove_code_wins_against_concurrent_cold_get_instance() {
+        const ROUNDS: usize = 5;
+
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(make_testing_options()).unwrap() };
+
+        for _ in 0..ROUNDS {
+            let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+            // Make sure get_instance has to compile the module
+            cache
+                .inner
+                .lock()
+                .unwrap()
+                .fs_cache
+                .remove(&checksum)
+                .unwrap();
+
+            std::thread::scope(|scope| {
+                // Depending on the timing, this finds the code or not
+                scope.spawn(|| {
+                    let _ = cache.get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS);
+                });
+                scope.spawn(|| {
+                    cache.remove_code(&checksum).unwrap();
+                });
+            });
+
+            // The module does not reappear in any cache
+            let mut inner = cache.inner.lock().unwrap();
+            assert!(inner
+                .fs_cache
+                .load(&checksum, Some(TESTING_MEMORY_LIMIT))
+                .unwrap()
+                .is_none());
+            assert!(inner.memory_cache.load(&checksum).unwrap().is_none());
+            assert!(inner.compiling.is_empty());
+            drop(inner);
+            cache.load_wasm(&checksum).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn compilation_limiter_bounds_concurrency() {
+        const THREADS: usize = 8;
+
+        let limiter = CompilationLimiter::new(NonZeroUsize::new(2));
+        let running = AtomicU64::new(0);
+        let max_running = AtomicU64::new(0);
+
+        std::thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    let permit = limiter.acquire();
+                    assert!(permit.is_some());
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+        assert_eq!(*limiter.running.lock().unwrap(), 0);
+
+        // unbounded by default
+        let limiter = CompilationLimiter::new(None);
+        assert!(limiter.acquire().is_none());
+    }
+
+    #[test]
+    fn store_code_works_with_max_concurrent_compilations() {
+        let options =
+            make_testing_options().with_max_concurrent_compilations(NonZeroUsize::new(1).unwrap());
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(options).unwrap() };
+
+        let checksums: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let cache = &cache;
+                    scope.spawn(move || {
+                        let wasm = wat::parse_str(format!(
+                            r#"(module
+                                (memory 3)
+                                (export "memory" (memory 0))
+                                (func (export "foo") i32.const {i} drop)
+                            )"#
+                        ))
+                        .unwrap();
+                        cache.store_code(&wasm, false, true).unwrap()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        for checksum in &checksums {
+            cache.load_wasm(checksum).unwrap();
+        }
+        assert_eq!(*cache.compilation_limiter.running.lock().unwrap(), 0);
+    }
+
     #[test]
     fn get_instance_finds_cached_module() {
         let cache = unsafe { Cache::new(make_testing_options()).unwrap() };
@@ -1003,6 +1415,37 @@ mod tests {
         assert_eq!(cache.stats().misses, 1);
     }
 
+    #[test]
+    fn get_module_compiles_module_once_for_concurrent_calls() {
+        const THREADS: usize = 8;
+
+        let options = make_testing_options();
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(options.clone()).unwrap() };
+        let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+
+        // Remove compiled module from disk
+        remove_dir_all(options.base_dir.join(CACHE_DIR).join(MODULES_DIR)).unwrap();
+
+        std::thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    let mut instance = cache
+                        .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+                        .unwrap();
+                    test_hackatom_instance_execution(&mut instance);
+                });
+            }
+        });
+
+        // Only one call compiled the module, all others waited for it and found it in memory
+        let stats = cache.stats();
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hits_fs_cache, 0);
+        assert_eq!(stats.hits_memory_cache, THREADS as u32 - 1);
+        assert!(cache.inner.lock().unwrap().compiling.is_empty());
+    }
+
     #[test]
     fn call_instantiate_on_cached_contract() {
         let cache = unsafe { Cache::new(make_testing_options()).unwrap() };
@@ -1452,6 +1895,13 @@ mod tests {
                     E::Query
                 ]),
                 required_capabilities: BTreeSet::new(),
+                required_capabilities_for: BTreeMap::from([
+                    (E::Instantiate, BTreeSet::new()),
+                    (E::Migrate, BTreeSet::new()),
+                    (E::Sudo, BTreeSet::new()),
+                    (E::Execute, BTreeSet::new()),
+                    (E::Query, BTreeSet::new()),
+                ]),
                 contract_migrate_version: Some(42),
             }
         );
@@ -1461,6 +1911,17 @@ mod tests {
         let mut ibc_contract_entrypoints =
             BTreeSet::from([E::Instantiate, E::Migrate, E::Reply, E::Query]);
         ibc_contract_entrypoints.extend(REQUIRED_IBC_EXPORTS);
+        let mut ibc_contract_capabilities_for = BTreeMap::from([
+            (E::Instantiate, BTreeSet::new()),
+            (E::Migrate, BTreeSet::new()),
+            (E::Reply, BTreeSet::new()),
+            (E::Query, BTreeSet::new()),
+        ]);
+        ibc_contract_capabilities_for.extend(
+            REQUIRED_IBC_EXPORTS
+                .iter()
+                .map(|&entrypoint| (entrypoint, BTreeSet::from(["stargate".to_string()]))),
+        );
         assert_eq!(
             report2,
             AnalysisReport {
@@ -1470,6 +1931,7 @@ mod tests {
                     "iterator".to_string(),
                     "stargate".to_string()
                 ]),
+                required_capabilities_for: ibc_contract_capabilities_for,
                 contract_migrate_version: None,
             }
         );
@@ -1482,6 +1944,7 @@ mod tests {
                 has_ibc_entry_points: false,
                 entrypoints: BTreeSet::new(),
                 required_capabilities: BTreeSet::from(["iterator".to_string()]),
+                required_capabilities_for: BTreeMap::new(),
                 contract_migrate_version: None,
             }
         );
@@ -1501,6 +1964,7 @@ mod tests {
                 has_ibc_entry_points: false,
                 entrypoints: BTreeSet::new(),
                 required_capabilities: BTreeSet::from(["iterator".to_string()]),
+                required_capabilities_for: BTreeMap::new(),
                 contract_migrate_version: Some(21),
             }
         );
@@ -1548,6 +2012,46 @@ mod tests {
         assert_eq!(get_module_hits(empty_checksum).hits, 0);
     }
 
+    #[test]
+    fn pinned_metrics_are_sorted_by_hits() {
+        let cache = unsafe { Cache::new(make_stargate_testing_options()).unwrap() };
+        let checksum1 = cache.store_code(CONTRACT, true, true).unwrap();
+        let checksum2 = cache.store_code(IBC_CONTRACT, true, true).unwrap();
+        cache.pin(&checksum1).unwrap();
+        cache.pin(&checksum2).unwrap();
+
+        // no hits yet, so sorted by checksum
+        let per_module = cache.pinned_metrics().per_module;
+        let mut expected = [checksum1, checksum2];
+        expected.sort();
+        assert_eq!(
+            per_module
+                .iter()
+                .map(|(checksum, _)| *checksum)
+                .collect::<Vec<_>>(),
+            expected
+        );
+
+        for _ in 0..3 {
+            let backend = mock_backend(&[]);
+            let _ = cache
+                .get_instance(&checksum2, backend, TESTING_OPTIONS)
+                .unwrap();
+        }
+        let backend = mock_backend(&[]);
+        let _ = cache
+            .get_instance(&checksum1, backend, TESTING_OPTIONS)
+            .unwrap();
+
+        let per_module = cache.pinned_metrics().per_module;
+        assert_eq!(per_module.len(), 2);
+        assert_eq!(per_module[0].0, checksum2);
+        assert_eq!(per_module[0].1.hits, 3);
+        assert_eq!(per_module[1].0, checksum1);
+        assert_eq!(per_module[1].1.hits, 1);
+        assert_eq!(cache.stats().hits_pinned_memory_cache, 4);
+    }
+
     #[test]
     fn pin_unpin_works() {
         let cache = unsafe { Cache::new(make_testing_options()).unwrap() };
@@ -1611,6 +2115,110 @@ mod tests {
         cache.unpin(&non_id).unwrap();
     }
 
+    #[test]
+    fn pin_with_policy_and_prune_pinned_works() {
+        // A clock that only moves when the test advances it
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let elapsed_secs = Arc::new(AtomicU64::new(0));
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        let mut cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(make_stargate_testing_options()).unwrap() };
+        let clock_secs = Arc::clone(&elapsed_secs);
+        cache.set_clock(move || start + Duration::from_secs(clock_secs.load(Ordering::SeqCst)));
+        let advance_to = |secs: u64| elapsed_secs.store(secs, Ordering::SeqCst);
+
+        let forever = cache.store_code(CONTRACT, true, true).unwrap();
+        let idle = cache.store_code(EMPTY_CONTRACT, true, true).unwrap();
+        let ttl = cache.store_code(IBC_CONTRACT, true, true).unwrap();
+
+        cache.pin(&forever).unwrap();
+        cache
+            .pin_with_policy(
+                &idle,
+                PinPolicy {
+                    ttl: None,
+                    max_idle: Some(Duration::from_secs(60)),
+                },
+            )
+            .unwrap();
+        cache
+            .pin_with_policy(
+                &ttl,
+                PinPolicy {
+                    ttl: Some(Duration::from_secs(3600)),
+                    max_idle: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(cache.metrics().elements_pinned_memory_cache, 3);
+
+        // accessing the idle module keeps it pinned
+        advance_to(50);
+        cache.get_module(&idle).unwrap();
+        advance_to(100);
+        assert_eq!(cache.prune_pinned(at(100)).unwrap(), vec![]);
+
+        // metrics show the last access
+        let metrics = cache.pinned_metrics();
+        let (_, idle_metrics) = metrics
+            .per_module
+            .iter()
+            .find(|(checksum, _)| *checksum == idle)
+            .unwrap();
+        assert_eq!(idle_metrics.pinned_at, at(0));
+        assert_eq!(idle_metrics.last_access, at(50));
+        assert_eq!(idle_metrics.hits, 1);
+
+        // idle module is pruned after max idle time without access
+        assert_eq!(cache.prune_pinned(at(110)).unwrap(), vec![idle]);
+        assert_eq!(cache.metrics().elements_pinned_memory_cache, 2);
+
+        // ttl module is pruned even though it is used
+        advance_to(3599);
+        cache.get_module(&ttl).unwrap();
+        assert_eq!(cache.prune_pinned(at(3599)).unwrap(), vec![]);
+        assert_eq!(cache.prune_pinned(at(3600)).unwrap(), vec![ttl]);
+
+        // module pinned via pin() stays forever
+        assert_eq!(cache.prune_pinned(at(1_000_000_000)).unwrap(), vec![]);
+        assert_eq!(cache.metrics().elements_pinned_memory_cache, 1);
+        let hits_before = cache.stats().hits_pinned_memory_cache;
+        cache.get_module(&forever).unwrap();
+        assert_eq!(cache.stats().hits_pinned_memory_cache, hits_before + 1);
+
+        // pruned modules can still be loaded from the other caches
+        cache.get_module(&idle).unwrap();
+        assert_eq!(cache.stats().hits_pinned_memory_cache, hits_before + 1);
+    }
+
+    #[test]
+    fn pin_with_policy_replaces_policy_of_pinned_module() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(make_testing_options()).unwrap() };
+        cache.set_clock(move || start);
+        let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+
+        let policy = PinPolicy {
+            ttl: Some(Duration::from_secs(10)),
+            max_idle: None,
+        };
+        cache.pin_with_policy(&checksum, policy).unwrap();
+        // pinning again without policy makes the pin permanent
+        cache.pin(&checksum).unwrap();
+        assert_eq!(cache.prune_pinned(at(100)).unwrap(), vec![]);
+        assert_eq!(
+            cache.pinned_metrics().per_module[0].1.policy,
+            PinPolicy::default()
+        );
+
+        // and back
+        cache.pin_with_policy(&checksum, policy).unwrap();
+        assert_eq!(cache.prune_pinned(at(100)).unwrap(), vec![checksum]);
+    }
+
     #[test]
     fn pin_recompiles_module() {
         let options = make_testing_options();
@@ -1648,6 +2256,7 @@ mod tests {
             available_capabilities: default_capabilities(),
             memory_cache_size_bytes: TESTING_MEMORY_CACHE_SIZE,
             instance_memory_limit_bytes: TESTING_MEMORY_LIMIT,
+            max_concurrent_compilations: None,
         };
         let cache: Cache<MockApi, MockStorage, MockQuerier> =
             unsafe { Cache::new(options).unwrap() };
@@ -1681,7 +2290,10 @@ mod tests {
                 available_capabilities: default_capabilities(),
                 memory_cache_size_bytes: TESTING_MEMORY_CACHE_SIZE,
                 instance_memory_limit_bytes: TESTING_MEMORY_LIMIT,
+                max_concurrent_compilations: None,
             },
+            gas_costs: GasCosts::default(),
+            code_policy: None,
         };
 
         let cache: Cache<MockApi, MockStorage, MockQuerier> =
@@ -1689,4 +2301,183 @@ mod tests {
         let err = cache.store_code(CONTRACT, true, true).unwrap_err();
         assert!(matches!(err, VmError::StaticValidationErr { .. }));
     }
+
+    #[test]
+    fn get_instance_uses_configured_gas_costs() {
+        let mut gas_costs = GasCosts::default();
+        gas_costs.secp256k1_verify_cost = 42;
+        gas_costs.bls12_381_pairing_equality_cost = LinearGasCost {
+            base: 1000,
+            per_item: 100,
+        };
+        let mut config = Config::new(make_testing_options());
+        config.gas_costs = gas_costs.clone();
+
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new_with_config(config).unwrap() };
+        let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+        let instance = cache
+            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap();
+        assert_eq!(instance.gas_costs(), &gas_costs);
+
+        // the default cache uses the default costs
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(make_testing_options()).unwrap() };
+        let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+        let instance = cache
+            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap();
+        assert_eq!(instance.gas_costs(), &GasCosts::default());
+    }
+
+    #[test]
+    fn get_instance_rejects_blocked_code() {
+        let blocked = Checksum::generate(EMPTY_CONTRACT);
+        let mut config = Config::new(make_testing_options());
+        config.code_policy = Some(CodePolicy::Deny(HashSet::from([blocked])));
+
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new_with_config(config).unwrap() };
+        let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+        assert_eq!(
+            cache.store_code(EMPTY_CONTRACT, true, true).unwrap(),
+            blocked
+        );
+
+        let mut instance = cache
+            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap();
+        test_hackatom_instance_execution(&mut instance);
+
+        let err = cache
+            .get_instance(&blocked, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap_err();
+        match err {
+            VmError::CodeBlocked { checksum, .. } => assert_eq!(checksum, blocked),
+            e => panic!("Unexpected error: {e:?}"),
+        }
+        // storing and loading still works
+        assert_eq!(cache.load_wasm(&blocked).unwrap(), EMPTY_CONTRACT);
+    }
+
+    #[test]
+    fn get_instance_only_allows_allowlisted_code() {
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(make_testing_options()).unwrap() };
+        let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+        let other = cache.store_code(EMPTY_CONTRACT, true, true).unwrap();
+        cache.set_code_policy(Some(CodePolicy::Allow(HashSet::from([checksum]))));
+
+        let mut instance = cache
+            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap();
+        test_hackatom_instance_execution(&mut instance);
+
+        let err = cache
+            .get_instance(&other, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap_err();
+        assert!(matches!(err, VmError::CodeBlocked { checksum, .. } if checksum == other));
+
+        // an empty allowlist blocks everything
+        cache.set_code_policy(Some(CodePolicy::Allow(HashSet::new())));
+        let err = cache
+            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap_err();
+        assert!(matches!(err, VmError::CodeBlocked { .. }));
+    }
+
+    #[test]
+    fn set_code_policy_affects_subsequent_instances_only() {
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(make_testing_options()).unwrap() };
+        let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+
+        // created before the code is blocked
+        let mut running = cache
+            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap();
+
+        cache.set_code_policy(Some(CodePolicy::Deny(HashSet::from([checksum]))));
+        let err = cache
+            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap_err();
+        assert!(matches!(err, VmError::CodeBlocked { .. }));
+        // the existing instance keeps working
+        test_hackatom_instance_execution(&mut running);
+
+        // unblocking applies to the next instance
+        cache.set_code_policy(None);
+        let mut instance = cache
+            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap();
+        test_hackatom_instance_execution(&mut instance);
+    }
+
+    #[test]
+    fn get_module_works() {
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(make_testing_options()).unwrap() };
+        let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+
+        // from file system cache
+        let _module = cache.get_module(&checksum).unwrap();
+        assert_eq!(cache.stats().hits_memory_cache, 0);
+        assert_eq!(cache.stats().hits_fs_cache, 1);
+
+        // from memory cache
+        let _module = cache.get_module(&checksum).unwrap();
+        assert_eq!(cache.stats().hits_memory_cache, 1);
+        assert_eq!(cache.stats().hits_fs_cache, 1);
+
+        // pinned modules are shared
+        cache.pin(&checksum).unwrap();
+        let module1 = cache.get_module(&checksum).unwrap();
+        let module2 = cache.get_module(&checksum).unwrap();
+        assert!(Arc::ptr_eq(&module1, &module2));
+        assert_eq!(cache.stats().hits_pinned_memory_cache, 2);
+
+        // a prefetched module can be instantiated
+        let mut instance = cache
+            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+            .unwrap();
+        test_hackatom_instance_execution(&mut instance);
+        assert_eq!(cache.stats().hits_pinned_memory_cache, 3);
+    }
+
+    #[test]
+    fn get_instance_does_not_deadlock_with_concurrent_pin_unpin() {
+        const THREADS: usize = 8;
+        const ROUNDS: usize = 20;
+
+        let cache: Cache<MockApi, MockStorage, MockQuerier> =
+            unsafe { Cache::new(make_testing_options()).unwrap() };
+        let checksum = cache.store_code(CONTRACT, true, true).unwrap();
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                for _ in 0..ROUNDS {
+                    cache.pin(&checksum).unwrap();
+                    cache.unpin(&checksum).unwrap();
+                }
+            });
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    for _ in 0..ROUNDS {
+                        let mut instance = cache
+                            .get_instance(&checksum, mock_backend(&[]), TESTING_OPTIONS)
+                            .unwrap();
+                        test_hackatom_instance_execution(&mut instance);
+                    }
+                });
+            }
+        });
+
+        let stats = cache.stats();
+        let total_hits =
+            stats.hits_pinned_memory_cache + stats.hits_memory_cache + stats.hits_fs_cache;
+        // every get_instance call and the first pin load a module
+        assert!(total_hits as usize >= THREADS * ROUNDS);
+        assert_eq!(stats.misses, 0);
+    }
 }
@@ -1,8 +1,11 @@
-use serde::de::DeserializeOwned;
+use serde::de::{DeserializeOwned, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::fmt;
 use wasmer::Value;
 
 use cosmwasm_std::{
-    ContractResult, CustomMsg, Env, IbcBasicResponse, IbcDestinationCallbackMsg,
+    Binary, ContractResult, CustomMsg, Env, IbcBasicResponse, IbcDestinationCallbackMsg,
     IbcSourceCallbackMsg, MessageInfo, MigrateInfo, QueryResponse, Reply, Response,
 };
 #[cfg(feature = "stargate")]
@@ -11,7 +14,8 @@ use cosmwasm_std::{
     IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse,
 };
 
-use crate::backend::{BackendApi, Querier, Storage};
+use crate::backend::{BackendApi, OverlayStorage, Querier, Storage};
+use crate::config::{ResponseLimits, ResultLimits};
 use crate::conversion::ref_to_u32;
 use crate::errors::{VmError, VmResult};
 use crate::instance::Instance;
@@ -22,7 +26,7 @@ use crate::serde::{from_slice, to_vec};
 ///
 /// A JSON deserializer would want to set the limit to a much smaller value because
 /// deserializing JSON is more expensive. As a consequence, any sane contract should hit
-/// the deserializer limit before the read limit.
+/// the deserializer limit (see [`ResultLimits`](crate::ResultLimits)) before the read limit.
 mod read_limits {
     /// A mebi (mega binary)
     const MI: usize = 1024 * 1024;
@@ -62,49 +66,6 @@ mod read_limits {
     pub const RESULT_IBC_DESTINATION_CALLBACK: usize = 64 * MI;
 }
 
-/// The limits for the JSON deserialization.
-///
-/// Those limits are not used when the Rust JSON deserializer is bypassed by using the
-/// public `call_*_raw` functions directly.
-mod deserialization_limits {
-    /// A kibi (kilo binary)
-    const KI: usize = 1024;
-    /// Max length (in bytes) of the result data from an instantiate call.
-    pub const RESULT_INSTANTIATE: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from an execute call.
-    pub const RESULT_EXECUTE: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a migrate call.
-    pub const RESULT_MIGRATE: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a sudo call.
-    pub const RESULT_SUDO: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a reply call.
-    pub const RESULT_REPLY: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a query call.
-    pub const RESULT_QUERY: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a ibc_channel_open call.
-    #[cfg(feature = "stargate")]
-    pub const RESULT_IBC_CHANNEL_OPEN: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a ibc_channel_connect call.
-    #[cfg(feature = "stargate")]
-    pub const RESULT_IBC_CHANNEL_CONNECT: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a ibc_channel_close call.
-    #[cfg(feature = "stargate")]
-    pub const RESULT_IBC_CHANNEL_CLOSE: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a ibc_packet_receive call.
-    #[cfg(feature = "stargate")]
-    pub const RESULT_IBC_PACKET_RECEIVE: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a ibc_packet_ack call.
-    #[cfg(feature = "stargate")]
-    pub const RESULT_IBC_PACKET_ACK: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a ibc_packet_timeout call.
-    #[cfg(feature = "stargate")]
-    pub const RESULT_IBC_PACKET_TIMEOUT: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a ibc_source_callback call.
-    pub const RESULT_IBC_SOURCE_CALLBACK: usize = 256 * KI;
-    /// Max length (in bytes) of the result data from a ibc_destination_callback call.
-    pub const RESULT_IBC_DESTINATION_CALLBACK: usize = 256 * KI;
-}
-
 pub fn call_instantiate<A, S, Q, U>(
     instance: &mut Instance<A, S, Q>,
     env: &Env,
@@ -121,7 +82,7 @@ where
     let info = to_vec(info)?;
     let data = call_instantiate_raw(instance, &env, &info, msg)?;
     let result: ContractResult<Response<U>> =
-        from_slice(&data, deserialization_limits::RESULT_INSTANTIATE)?;
+        from_slice(&data, instance.result_limits().instantiate)?;
     Ok(result)
 }
 
@@ -140,11 +101,44 @@ where
     let env = to_vec(env)?;
     let info = to_vec(info)?;
     let data = call_execute_raw(instance, &env, &info, msg)?;
-    let result: ContractResult<Response<U>> =
-        from_slice(&data, deserialization_limits::RESULT_EXECUTE)?;
+    let result: ContractResult<Response<U>> = from_slice(&data, instance.result_limits().execute)?;
     Ok(result)
 }
 
+/// Calls Wasm export "execute" without committing the storage changes.
+///
+/// The instance runs on top of an [`OverlayStorage`], which is left in the state it had
+/// before the call. Alongside the result, this returns the storage changes made by the call,
+/// where a value of `None` means the key was removed.
+pub fn call_execute_dry_run<A, S, Q, U>(
+    instance: &mut Instance<A, OverlayStorage<S>, Q>,
+    env: &Env,
+    info: &MessageInfo,
+    msg: &[u8],
+) -> VmResult<(
+    ContractResult<Response<U>>,
+    BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+)>
+where
+    A: BackendApi + 'static,
+    S: Storage + 'static,
+    Q: Querier + 'static,
+    U: DeserializeOwned + CustomMsg,
+{
+    let before = instance.with_storage(|storage| Ok(storage.changes().clone()))?;
+    let result = call_execute(instance, env, info, msg);
+    let after = instance.with_storage(|storage| {
+        let after = storage.take_changes();
+        storage.restore_changes(before.clone());
+        Ok(after)
+    })?;
+    let changes = after
+        .into_iter()
+        .filter(|(key, value)| before.get(key) != Some(value))
+        .collect();
+    Ok((result?, changes))
+}
+
 pub fn call_migrate<A, S, Q, U>(
     instance: &mut Instance<A, S, Q>,
     env: &Env,
@@ -158,8 +152,7 @@ where
 {
     let env = to_vec(env)?;
     let data = call_migrate_raw(instance, &env, msg)?;
-    let result: ContractResult<Response<U>> =
-        from_slice(&data, deserialization_limits::RESULT_MIGRATE)?;
+    let result: ContractResult<Response<U>> = from_slice(&data, instance.result_limits().migrate)?;
     Ok(result)
 }
 
@@ -178,8 +171,7 @@ where
     let env = to_vec(env)?;
     let migrate_info = to_vec(migrate_info)?;
     let data = call_migrate_with_info_raw(instance, &env, msg, &migrate_info)?;
-    let result: ContractResult<Response<U>> =
-        from_slice(&data, deserialization_limits::RESULT_MIGRATE)?;
+    let result: ContractResult<Response<U>> = from_slice(&data, instance.result_limits().migrate)?;
     Ok(result)
 }
 
@@ -196,8 +188,7 @@ where
 {
     let env = to_vec(env)?;
     let data = call_sudo_raw(instance, &env, msg)?;
-    let result: ContractResult<Response<U>> =
-        from_slice(&data, deserialization_limits::RESULT_SUDO)?;
+    let result: ContractResult<Response<U>> = from_slice(&data, instance.result_limits().sudo)?;
     Ok(result)
 }
 
@@ -215,8 +206,7 @@ where
     let env = to_vec(env)?;
     let msg = to_vec(msg)?;
     let data = call_reply_raw(instance, &env, &msg)?;
-    let result: ContractResult<Response<U>> =
-        from_slice(&data, deserialization_limits::RESULT_REPLY)?;
+    let result: ContractResult<Response<U>> = from_slice(&data, instance.result_limits().reply)?;
     Ok(result)
 }
 
@@ -232,8 +222,7 @@ where
 {
     let env = to_vec(env)?;
     let data = call_query_raw(instance, &env, msg)?;
-    let result: ContractResult<QueryResponse> =
-        from_slice(&data, deserialization_limits::RESULT_QUERY)?;
+    let result: ContractResult<QueryResponse> = from_slice(&data, instance.result_limits().query)?;
     // Ensure query response is valid JSON
     if let ContractResult::Ok(binary_response) = &result {
         serde_json::from_slice::<serde_json::Value>(binary_response.as_slice())
@@ -258,7 +247,7 @@ where
     let msg = to_vec(msg)?;
     let data = call_ibc_channel_open_raw(instance, &env, &msg)?;
     let result: ContractResult<Option<Ibc3ChannelOpenResponse>> =
-        from_slice(&data, deserialization_limits::RESULT_IBC_CHANNEL_OPEN)?;
+        from_slice(&data, instance.result_limits().ibc_channel_open)?;
     Ok(result)
 }
 
@@ -277,7 +266,8 @@ where
     let env = to_vec(env)?;
     let msg = to_vec(msg)?;
     let data = call_ibc_channel_connect_raw(instance, &env, &msg)?;
-    let result = from_slice(&data, deserialization_limits::RESULT_IBC_CHANNEL_CONNECT)?;
+    let result: ContractResult<IbcBasicResponse<U>> =
+        from_slice(&data, instance.result_limits().ibc_channel_connect)?;
     Ok(result)
 }
 
@@ -296,7 +286,8 @@ where
     let env = to_vec(env)?;
     let msg = to_vec(msg)?;
     let data = call_ibc_channel_close_raw(instance, &env, &msg)?;
-    let result = from_slice(&data, deserialization_limits::RESULT_IBC_CHANNEL_CLOSE)?;
+    let result: ContractResult<IbcBasicResponse<U>> =
+        from_slice(&data, instance.result_limits().ibc_channel_close)?;
     Ok(result)
 }
 
@@ -315,7 +306,8 @@ where
     let env = to_vec(env)?;
     let msg = to_vec(msg)?;
     let data = call_ibc_packet_receive_raw(instance, &env, &msg)?;
-    let result = from_slice(&data, deserialization_limits::RESULT_IBC_PACKET_RECEIVE)?;
+    let result: ContractResult<IbcReceiveResponse<U>> =
+        from_slice(&data, instance.result_limits().ibc_packet_receive)?;
     Ok(result)
 }
 
@@ -334,7 +326,8 @@ where
     let env = to_vec(env)?;
     let msg = to_vec(msg)?;
     let data = call_ibc_packet_ack_raw(instance, &env, &msg)?;
-    let result = from_slice(&data, deserialization_limits::RESULT_IBC_PACKET_ACK)?;
+    let result: ContractResult<IbcBasicResponse<U>> =
+        from_slice(&data, instance.result_limits().ibc_packet_ack)?;
     Ok(result)
 }
 
@@ -353,7 +346,8 @@ where
     let env = to_vec(env)?;
     let msg = to_vec(msg)?;
     let data = call_ibc_packet_timeout_raw(instance, &env, &msg)?;
-    let result = from_slice(&data, deserialization_limits::RESULT_IBC_PACKET_TIMEOUT)?;
+    let result: ContractResult<IbcBasicResponse<U>> =
+        from_slice(&data, instance.result_limits().ibc_packet_timeout)?;
     Ok(result)
 }
 
@@ -371,7 +365,8 @@ where
     let env = to_vec(env)?;
     let msg = to_vec(msg)?;
     let data = call_ibc_source_callback_raw(instance, &env, &msg)?;
-    let result = from_slice(&data, deserialization_limits::RESULT_IBC_SOURCE_CALLBACK)?;
+    let result: ContractResult<IbcBasicResponse<U>> =
+        from_slice(&data, instance.result_limits().ibc_source_callback)?;
     Ok(result)
 }
 
@@ -389,15 +384,14 @@ where
     let env = to_vec(env)?;
     let msg = to_vec(msg)?;
     let data = call_ibc_destination_callback_raw(instance, &env, &msg)?;
-    let result = from_slice(
-        &data,
-        deserialization_limits::RESULT_IBC_DESTINATION_CALLBACK,
-    )?;
+    let result: ContractResult<IbcBasicResponse<U>> =
+        from_slice(&data, instance.result_limits().ibc_destination_callback)?;
     Ok(result)
 }
 
 /// Calls Wasm export "instantiate" and returns raw data from the contract.
-/// The result is length limited to prevent abuse but otherwise unchecked.
+/// The result is length limited and checked against the response limits to prevent abuse,
+/// but otherwise unchecked.
 pub fn call_instantiate_raw<A, S, Q>(
     instance: &mut Instance<A, S, Q>,
     env: &[u8],
@@ -415,11 +409,14 @@ where
         "instantiate",
         &[env, info, msg],
         read_limits::RESULT_INSTANTIATE,
+        |limits| limits.instantiate,
+        ResultKind::Response,
     )
 }
 
 /// Calls Wasm export "execute" and returns raw data from the contract.
-/// The result is length limited to prevent abuse but otherwise unchecked.
+/// The result is length limited and checked against the response limits to prevent abuse,
+/// but otherwise unchecked.
 pub fn call_execute_raw<A, S, Q>(
     instance: &mut Instance<A, S, Q>,
     env: &[u8],
@@ -437,11 +434,14 @@ where
         "execute",
         &[env, info, msg],
         read_limits::RESULT_EXECUTE,
+        |limits| limits.execute,
+        ResultKind::Response,
     )
 }
 
 /// Calls Wasm export "migrate" and returns raw data from the contract.
-/// The result is length limited to prevent abuse but otherwise unchecked.
+/// The result is length limited and checked against the response limits to prevent abuse,
+/// but otherwise unchecked.
 pub fn call_migrate_raw<A, S, Q>(
     instance: &mut Instance<A, S, Q>,
     env: &[u8],
@@ -458,11 +458,14 @@ where
         "migrate",
         &[env, msg],
         read_limits::RESULT_MIGRATE,
+        |limits| limits.migrate,
+        ResultKind::Response,
     )
 }
 
 /// Calls Wasm export "migrate" and returns raw data from the contract.
-/// The result is length limited to prevent abuse but otherwise unchecked.
+/// The result is length limited and checked against the response limits to prevent abuse,
+/// but otherwise unchecked.
 /// The difference between this function and [call_migrate_raw] is the
 /// additional argument - `migrate_info`. It contains additional data
 /// related to the contract's migration procedure.
@@ -487,6 +490,8 @@ where
         "migrate",
         &[env, msg, migrate_info],
         read_limits::RESULT_MIGRATE,
+        |limits| limits.migrate,
+        ResultKind::Response,
     )
     .or_else(|err| {
         if matches!(err, VmError::FunctionArityMismatch { .. }) {
@@ -495,6 +500,8 @@ where
                 "migrate",
                 &[env, msg],
                 read_limits::RESULT_MIGRATE,
+                |limits| limits.migrate,
+                ResultKind::Response,
             )
         } else {
             Err(err)
@@ -503,7 +510,8 @@ where
 }
 
 /// Calls Wasm export "sudo" and returns raw data from the contract.
-/// The result is length limited to prevent abuse but otherwise unchecked.
+/// The result is length limited and checked against the response limits to prevent abuse,
+/// but otherwise unchecked.
 pub fn call_sudo_raw<A, S, Q>(
     instance: &mut Instance<A, S, Q>,
     env: &[u8],
@@ -515,11 +523,19 @@ where
     Q: Querier + 'static,
 {
     instance.set_storage_readonly(false);
-    call_raw(instance, "sudo", &[env, msg], read_limits::RESULT_SUDO)
+    call_raw(
+        instance,
+        "sudo",
+        &[env, msg],
+        read_limits::RESULT_SUDO,
+        |limits| limits.sudo,
+        ResultKind::Response,
+    )
 }
 
 /// Calls Wasm export "reply" and returns raw data from the contract.
-/// The result is length limited to prevent abuse but otherwise unchecked.
+/// The result is length limited and checked against the response limits to prevent abuse,
+/// but otherwise unchecked.
 pub fn call_reply_raw<A, S, Q>(
     instance: &mut Instance<A, S, Q>,
     env: &[u8],
@@ -531,7 +547,14 @@ where
     Q: Querier + 'static,
 {
     instance.set_storage_readonly(false);
-    call_raw(instance, "reply", &[env, msg], read_limits::RESULT_REPLY)
+    call_raw(
+        instance,
+        "reply",
+        &[env, msg],
+        read_limits::RESULT_REPLY,
+        |limits| limits.reply,
+        ResultKind::Response,
+    )
 }
 
 /// Calls Wasm export "query" and returns raw data from the contract.
@@ -547,7 +570,14 @@ where
     Q: Querier + 'static,
 {
     instance.set_storage_readonly(true);
-    call_raw(instance, "query", &[env, msg], read_limits::RESULT_QUERY)
+    call_raw(
+        instance,
+        "query",
+        &[env, msg],
+        read_limits::RESULT_QUERY,
+        |limits| limits.query,
+        ResultKind::Other,
+    )
 }
 
 #[cfg(feature = "stargate")]
@@ -567,6 +597,8 @@ where
         "ibc_channel_open",
         &[env, msg],
         read_limits::RESULT_IBC_CHANNEL_OPEN,
+        |limits| limits.ibc_channel_open,
+        ResultKind::Other,
     )
 }
 
@@ -587,6 +619,8 @@ where
         "ibc_channel_connect",
         &[env, msg],
         read_limits::RESULT_IBC_CHANNEL_CONNECT,
+        |limits| limits.ibc_channel_connect,
+        ResultKind::Response,
     )
 }
 
@@ -607,6 +641,8 @@ where
         "ibc_channel_close",
         &[env, msg],
         read_limits::RESULT_IBC_CHANNEL_CLOSE,
+        |limits| limits.ibc_channel_close,
+        ResultKind::Response,
     )
 }
 
@@ -627,6 +663,8 @@ where
         "ibc_packet_receive",
         &[env, msg],
         read_limits::RESULT_IBC_PACKET_RECEIVE,
+        |limits| limits.ibc_packet_receive,
+        ResultKind::Response,
     )
 }
 
@@ -647,6 +685,8 @@ where
         "ibc_packet_ack",
         &[env, msg],
         read_limits::RESULT_IBC_PACKET_ACK,
+        |limits| limits.ibc_packet_ack,
+        ResultKind::Response,
     )
 }
 
@@ -667,6 +707,8 @@ where
         "ibc_packet_timeout",
         &[env, msg],
         read_limits::RESULT_IBC_PACKET_TIMEOUT,
+        |limits| limits.ibc_packet_timeout,
+        ResultKind::Response,
     )
 }
 
@@ -686,6 +728,8 @@ where
         "ibc_source_callback",
         &[env, msg],
         read_limits::RESULT_IBC_SOURCE_CALLBACK,
+        |limits| limits.ibc_source_callback,
+        ResultKind::Response,
     )
 }
 
@@ -705,22 +749,41 @@ where
         "ibc_destination_callback",
         &[env, msg],
         read_limits::RESULT_IBC_DESTINATION_CALLBACK,
+        |limits| limits.ibc_destination_callback,
+        ResultKind::Response,
     )
 }
 
+/// The kind of result an entry point returns. This determines whether the result
+/// is checked against the [`ResponseLimits`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ResultKind {
+    /// A `Response`, `IbcBasicResponse` or `IbcReceiveResponse`
+    Response,
+    /// Any other result, e.g. a query response
+    Other,
+}
+
 /// Calls a function with the given arguments.
 /// The exported function must return exactly one result (an offset to the result Region).
+///
+/// If the instance has raw result limits, the result is rejected if it is longer than
+/// the limit selected from them by `result_limit`. Otherwise it is only limited by
+/// `result_max_length`.
 pub(crate) fn call_raw<A, S, Q>(
     instance: &mut Instance<A, S, Q>,
     name: &str,
     args: &[&[u8]],
     result_max_length: usize,
+    result_limit: fn(&ResultLimits) -> usize,
+    result_kind: ResultKind,
 ) -> VmResult<Vec<u8>>
 where
     A: BackendApi + 'static,
     S: Storage + 'static,
     Q: Querier + 'static,
 {
+    instance.reset_allocations();
     let mut arg_region_ptrs = Vec::<Value>::with_capacity(args.len());
     for arg in args {
         let region_ptr = instance.allocate(arg.len())?;
@@ -732,15 +795,251 @@ where
     let data = instance.read_memory(res_region_ptr, result_max_length)?;
     // free return value in wasm (arguments were freed in wasm code)
     instance.deallocate(res_region_ptr)?;
+    if let Some(limits) = instance.raw_result_limits() {
+        let result_limit = result_limit(&limits);
+        if data.len() > result_limit {
+            return Err(VmError::deserialization_limit_exceeded(
+                data.len(),
+                result_limit,
+            ));
+        }
+    }
+    if result_kind == ResultKind::Response {
+        check_response_limits(&data, instance.response_limits())?;
+    }
     Ok(data)
 }
 
+/// Checks a serialized contract result against the response limits.
+///
+/// The messages, attributes and events are only counted while parsing, so a response
+/// exceeding the limits is rejected without deserializing it into memory.
+/// Errors returned by the contract are passed through unchecked. Results that cannot be
+/// parsed are left to the deserializer of the caller.
+fn check_response_limits(data: &[u8], limits: ResponseLimits) -> VmResult<()> {
+    let Ok(ResultSummary(Some(response))) = serde_json::from_slice::<ResultSummary>(data) else {
+        return Ok(());
+    };
+
+    if response.submessages > limits.max_submessages {
+        return Err(VmError::result_limit_exceeded(
+            "max_submessages_per_response",
+            response.submessages,
+            limits.max_submessages,
+        ));
+    }
+    if response.events > limits.max_events {
+        return Err(VmError::result_limit_exceeded(
+            "max_events_per_response",
+            response.events,
+            limits.max_events,
+        ));
+    }
+    if response.max_attributes > limits.max_attributes_per_event {
+        return Err(VmError::result_limit_exceeded(
+            "max_attributes_per_event",
+            response.max_attributes,
+            limits.max_attributes_per_event,
+        ));
+    }
+    if response.data_bytes > limits.max_data_bytes {
+        return Err(VmError::result_limit_exceeded(
+            "max_response_data_bytes",
+            response.data_bytes,
+            limits.max_data_bytes,
+        ));
+    }
+    Ok(())
+}
+
+/// A contract result of which only the successful response is inspected
+struct ResultSummary(Option<ResponseSummary>);
+
+/// The parts of a `Response`, `IbcBasicResponse` or `IbcReceiveResponse` that are
+/// checked against the [`ResponseLimits`]
+#[derive(Default)]
+struct ResponseSummary {
+    submessages: usize,
+    events: usize,
+    /// The highest number of attributes of the response itself or any of its events
+    max_attributes: usize,
+    /// The length of the data, or the acknowledgement of an `IbcReceiveResponse`
+    data_bytes: usize,
+}
+
+/// The number of elements of a JSON array, which are skipped without being stored
+struct Count(usize);
+
+/// The number of events and the highest number of attributes per event
+struct EventsSummary {
+    events: usize,
+    max_attributes: usize,
+}
+
+/// The number of attributes of a single event
+struct EventSummary(usize);
+
+impl<'de> Deserialize<'de> for ResultSummary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ResultVisitor;
+
+        impl<'de> Visitor<'de> for ResultVisitor {
+            type Value = ResultSummary;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a contract result")
+            }
+
+            fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+                let mut response = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "ok" {
+                        response = Some(map.next_value()?);
+                    } else {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+                Ok(ResultSummary(response))
+            }
+        }
+
+        deserializer.deserialize_map(ResultVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for ResponseSummary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ResponseVisitor;
+
+        impl<'de> Visitor<'de> for ResponseVisitor {
+            type Value = ResponseSummary;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a contract response")
+            }
+
+            fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+                let mut summary = ResponseSummary::default();
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "messages" => summary.submessages = map.next_value::<Count>()?.0,
+                        "attributes" => {
+                            let attributes = map.next_value::<Count>()?.0;
+                            summary.max_attributes = summary.max_attributes.max(attributes);
+                        }
+                        "events" => {
+                            let events = map.next_value::<EventsSummary>()?;
+                            summary.events = events.events;
+                            summary.max_attributes =
+                                summary.max_attributes.max(events.max_attributes);
+                        }
+                        "data" | "acknowledgement" => {
+                            let data = map.next_value::<Option<Binary>>()?;
+                            summary.data_bytes = data.map_or(0, |data| data.len());
+                        }
+                        _ => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(summary)
+            }
+        }
+
+        deserializer.deserialize_map(ResponseVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Count {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CountVisitor;
+
+        impl<'de> Visitor<'de> for CountVisitor {
+            type Value = Count;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an array")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut count = 0;
+                while seq.next_element::<IgnoredAny>()?.is_some() {
+                    count += 1;
+                }
+                Ok(Count(count))
+            }
+        }
+
+        deserializer.deserialize_seq(CountVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for EventsSummary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EventsVisitor;
+
+        impl<'de> Visitor<'de> for EventsVisitor {
+            type Value = EventsSummary;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an array of events")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut summary = EventsSummary {
+                    events: 0,
+                    max_attributes: 0,
+                };
+                while let Some(EventSummary(attributes)) = seq.next_element()? {
+                    summary.events += 1;
+                    summary.max_attributes = summary.max_attributes.max(attributes);
+                }
+                Ok(summary)
+            }
+        }
+
+        deserializer.deserialize_seq(EventsVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for EventSummary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EventVisitor;
+
+        impl<'de> Visitor<'de> for EventVisitor {
+            type Value = EventSummary;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an event")
+            }
+
+            fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+                let mut attributes = 0;
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "attributes" {
+                        attributes = map.next_value::<Count>()?.0;
+                    } else {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+                Ok(EventSummary(attributes))
+            }
+        }
+
+        deserializer.deserialize_map(EventVisitor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::Backend;
+    use crate::instance::InstanceOptions;
     use crate::testing::{
         mock_env, mock_info, mock_instance, mock_instance_with_options, MockInstanceOptions,
     };
+    use crate::testing::{MockApi, MockQuerier, MockStorage};
+    use crate::{ResponseLimits, ResultLimits};
     use cosmwasm_std::{coins, from_json, to_json_string, Addr, Empty};
     use sha2::{Digest, Sha256};
 
@@ -806,6 +1105,252 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn call_execute_dry_run_works() {
+        let api = MockApi::default();
+        let backend = Backend {
+            api: api.clone(),
+            storage: OverlayStorage::new(MockStorage::new()),
+            querier: MockQuerier::new(&[]),
+        };
+        let options = InstanceOptions {
+            gas_limit: 2_000_000_000,
+        };
+        let mut instance = Instance::from_code(CONTRACT, backend, options, None).unwrap();
+
+        // init writes the config into the overlay
+        let info = mock_info(&api.addr_make("creator"), &coins(1000, "earth"));
+        let verifier = api.addr_make("verifies");
+        let beneficiary = api.addr_make("benefits");
+        let msg = format!(r#"{{"verifier": "{verifier}", "beneficiary": "{beneficiary}"}}"#);
+        call_instantiate::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg.as_bytes())
+            .unwrap()
+            .unwrap();
+        let changes_after_init = instance
+            .with_storage(|storage| Ok(storage.changes().clone()))
+            .unwrap();
+        assert_eq!(
+            changes_after_init.keys().collect::<Vec<_>>(),
+            [&b"config".to_vec()]
+        );
+
+        // execute reads the config from the overlay and does not write
+        let info = mock_info(&verifier, &coins(15, "earth"));
+        let msg = br#"{"release":{}}"#;
+        let (result, changes) =
+            call_execute_dry_run::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg).unwrap();
+        assert_eq!(result.unwrap().messages.len(), 1);
+        assert!(changes.is_empty());
+
+        // errors of the contract are returned as well
+        let info = mock_info(&beneficiary, &[]);
+        let (result, changes) =
+            call_execute_dry_run::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg).unwrap();
+        assert_eq!(result.unwrap_err(), "Unauthorized");
+        assert!(changes.is_empty());
+
+        // the overlay is unchanged and nothing reached the inner storage
+        let changes_after_dry_run = instance
+            .with_storage(|storage| Ok(storage.changes().clone()))
+            .unwrap();
+        assert_eq!(changes_after_dry_run, changes_after_init);
+        let inner = instance.recycle().unwrap().storage.into_inner();
+        assert_eq!(inner.get(b"config").0.unwrap(), None);
+    }
+
+    #[test]
+    fn call_execute_enforces_allocation_limit() {
+        let mut instance = mock_instance(CONTRACT, &[]);
+
+        // init (writes env, info and msg into the contract)
+        instance.set_allocation_limit(3);
+        let info = mock_info(&instance.api().addr_make("creator"), &coins(1000, "earth"));
+        let verifier = instance.api().addr_make("verifies");
+        let beneficiary = instance.api().addr_make("benefits");
+        let msg = format!(r#"{{"verifier": "{verifier}", "beneficiary": "{beneficiary}"}}"#);
+        call_instantiate::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg.as_bytes())
+            .unwrap()
+            .unwrap();
+        assert_eq!(instance.create_gas_report().allocations, 3);
+
+        // execute needs one more allocation for reading the config from storage
+        let info = mock_info(&verifier, &coins(15, "earth"));
+        let msg = br#"{"release":{}}"#;
+        let err =
+            call_execute::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg).unwrap_err();
+        match err {
+            VmError::RuntimeErr { msg, .. } => {
+                assert!(msg.contains("Maximum number of allocations per call exceeded. Limit: 3"))
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn call_execute_enforces_response_limits() {
+        let mut instance = mock_instance(CONTRACT, &[]);
+
+        // init
+        let info = mock_info(&instance.api().addr_make("creator"), &coins(1000, "earth"));
+        let verifier = instance.api().addr_make("verifies");
+        let beneficiary = instance.api().addr_make("benefits");
+        let msg = format!(r#"{{"verifier": "{verifier}", "beneficiary": "{beneficiary}"}}"#);
+        call_instantiate::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg.as_bytes())
+            .unwrap()
+            .unwrap();
+
+        // release returns 1 message, 2 attributes, 1 event with 1 attribute and 3 bytes of data
+        let info = mock_info(&verifier, &[]);
+        let msg = br#"{"release":{}}"#;
+        let exact = ResponseLimits {
+            max_events: 1,
+            max_attributes_per_event: 2,
+            max_submessages: 1,
+            max_data_bytes: 3,
+        };
+        instance.set_response_limits(exact);
+        call_execute::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg)
+            .unwrap()
+            .unwrap();
+
+        let cases = [
+            (
+                ResponseLimits {
+                    max_submessages: 0,
+                    ..exact
+                },
+                "max_submessages_per_response",
+                1,
+            ),
+            (
+                ResponseLimits {
+                    max_events: 0,
+                    ..exact
+                },
+                "max_events_per_response",
+                1,
+            ),
+            (
+                ResponseLimits {
+                    max_attributes_per_event: 1,
+                    ..exact
+                },
+                "max_attributes_per_event",
+                2,
+            ),
+            (
+                ResponseLimits {
+                    max_data_bytes: 2,
+                    ..exact
+                },
+                "max_response_data_bytes",
+                3,
+            ),
+        ];
+        for (limits, expected_limit, expected_actual) in cases {
+            instance.set_response_limits(limits);
+            let err =
+                call_execute::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg).unwrap_err();
+            match err {
+                VmError::ResultLimitExceeded { limit, actual, .. } => {
+                    assert_eq!(limit, expected_limit);
+                    assert_eq!(actual, expected_actual);
+                }
+                err => panic!("Unexpected error: {err:?}"),
+            }
+        }
+
+        // errors returned by the contract are not affected
+        instance.set_response_limits(ResponseLimits {
+            max_events: 0,
+            max_attributes_per_event: 0,
+            max_submessages: 0,
+            max_data_bytes: 0,
+        });
+        let info = mock_info(&beneficiary, &[]);
+        let result =
+            call_execute::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg).unwrap();
+        assert!(result.into_result().is_err());
+    }
+
+    #[test]
+    fn call_execute_raw_enforces_response_limits() {
+        let mut instance = mock_instance(CONTRACT, &[]);
+
+        // init
+        let info = mock_info(&instance.api().addr_make("creator"), &coins(1000, "earth"));
+        let verifier = instance.api().addr_make("verifies");
+        let beneficiary = instance.api().addr_make("benefits");
+        let msg = format!(r#"{{"verifier": "{verifier}", "beneficiary": "{beneficiary}"}}"#);
+        call_instantiate::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg.as_bytes())
+            .unwrap()
+            .unwrap();
+
+        let env = to_vec(&mock_env()).unwrap();
+        let info = to_vec(&mock_info(&verifier, &[])).unwrap();
+        let msg = br#"{"release":{}}"#;
+        instance.set_response_limits(ResponseLimits {
+            max_events: 0,
+            ..ResponseLimits::default()
+        });
+        match call_execute_raw(&mut instance, &env, &info, msg).unwrap_err() {
+            VmError::ResultLimitExceeded {
+                limit, actual, max, ..
+            } => {
+                assert_eq!(limit, "max_events_per_response");
+                assert_eq!(actual, 1);
+                assert_eq!(max, 0);
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        instance.set_response_limits(ResponseLimits::default());
+        let data = call_execute_raw(&mut instance, &env, &info, msg).unwrap();
+        let result: ContractResult<Response> = from_json(data).unwrap();
+        assert_eq!(result.unwrap().events.len(), 1);
+    }
+
+    #[test]
+    fn check_response_limits_works() {
+        let limits = ResponseLimits {
+            max_events: 2,
+            max_attributes_per_event: 2,
+            max_submessages: 1,
+            max_data_bytes: 3,
+        };
+
+        // within limits
+        let response = br#"{"ok":{"messages":[{"id":1}],"attributes":[{"key":"a","value":"b"}],"events":[{"type":"a","attributes":[]},{"type":"b","attributes":[{"key":"a","value":"b"},{"key":"c","value":"d"}]}],"data":"AQID"}}"#;
+        check_response_limits(response, limits).unwrap();
+
+        // the acknowledgement of an IbcReceiveResponse counts as data
+        let response =
+            br#"{"ok":{"acknowledgement":"AQIDBA==","messages":[],"attributes":[],"events":[]}}"#;
+        match check_response_limits(response, limits).unwrap_err() {
+            VmError::ResultLimitExceeded { limit, actual, .. } => {
+                assert_eq!(limit, "max_response_data_bytes");
+                assert_eq!(actual, 4);
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        // a huge number of events is counted without being stored
+        let events = vec![r#"{"type":"a","attributes":[]}"#; 100_000].join(",");
+        let response = format!(r#"{{"ok":{{"messages":[],"attributes":[],"events":[{events}]}}}}"#);
+        match check_response_limits(response.as_bytes(), limits).unwrap_err() {
+            VmError::ResultLimitExceeded { limit, actual, .. } => {
+                assert_eq!(limit, "max_events_per_response");
+                assert_eq!(actual, 100_000);
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        // errors and results that cannot be parsed are not checked
+        check_response_limits(br#"{"error":"kaputt"}"#, limits).unwrap();
+        check_response_limits(br#"{"ok":{"messages":1}}"#, limits).unwrap();
+        check_response_limits(b"not json", limits).unwrap();
+    }
+
     #[test]
     fn call_execute_runs_out_of_gas() {
         let mut instance = mock_instance(CYBERPUNK, &[]);
@@ -963,6 +1508,167 @@ mod tests {
         );
     }
 
+    type TestingInstance = Instance<MockApi, MockStorage, MockQuerier>;
+
+    /// Sets the result limit of one entry point to 1 byte using `set_limit` and checks
+    /// that `call` is rejected with the limits of the typed calls as well as with the
+    /// limits of the raw calls, while it succeeds with the default limits.
+    /// `new_instance` creates a fresh instance for each of those runs.
+    fn assert_result_limit_enforced(
+        new_instance: fn() -> TestingInstance,
+        set_limit: fn(&mut ResultLimits, usize),
+        call: fn(&mut TestingInstance) -> VmResult<()>,
+    ) {
+        let mut limits = ResultLimits::default();
+        set_limit(&mut limits, 1);
+
+        let assert_limit_exceeded = |result: VmResult<()>| match result.unwrap_err() {
+            VmError::DeserializationLimitExceeded {
+                length, max_length, ..
+            } => {
+                assert!(length > 1);
+                assert_eq!(max_length, 1);
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        };
+
+        let mut instance = new_instance();
+        instance.set_result_limits(limits);
+        assert_limit_exceeded(call(&mut instance));
+
+        let mut instance = new_instance();
+        instance.set_raw_result_limits(Some(limits));
+        assert_limit_exceeded(call(&mut instance));
+
+        let mut instance = new_instance();
+        call(&mut instance).unwrap();
+    }
+
+    #[test]
+    fn calls_enforce_result_limits() {
+        fn instantiate(instance: &mut TestingInstance) -> VmResult<()> {
+            let info = mock_info(&instance.api().addr_make("creator"), &coins(1000, "earth"));
+            let verifier = instance.api().addr_make("verifies");
+            let beneficiary = instance.api().addr_make("benefits");
+            let msg = format!(r#"{{"verifier": "{verifier}", "beneficiary": "{beneficiary}"}}"#);
+            call_instantiate::<_, _, _, Empty>(instance, &mock_env(), &info, msg.as_bytes())?
+                .unwrap();
+            Ok(())
+        }
+
+        fn execute(instance: &mut TestingInstance) -> VmResult<()> {
+            let info = mock_info(&instance.api().addr_make("verifies"), &[]);
+            call_execute::<_, _, _, Empty>(instance, &mock_env(), &info, br#"{"release":{}}"#)?
+                .unwrap();
+            Ok(())
+        }
+
+        fn migrate(instance: &mut TestingInstance) -> VmResult<()> {
+            let verifier = instance.api().addr_make("someone else");
+            let msg = format!(r#"{{"verifier": "{verifier}"}}"#);
+            call_migrate::<_, _, _, Empty>(instance, &mock_env(), msg.as_bytes())?.unwrap();
+            Ok(())
+        }
+
+        fn migrate_with_info(instance: &mut TestingInstance) -> VmResult<()> {
+            let verifier = instance.api().addr_make("someone else");
+            let msg = format!(r#"{{"verifier": "{verifier}"}}"#);
+            let migrate_info = MigrateInfo {
+                sender: Addr::unchecked(verifier),
+                old_migrate_version: Some(33),
+            };
+            call_migrate_with_info::<_, _, _, Empty>(
+                instance,
+                &mock_env(),
+                msg.as_bytes(),
+                &migrate_info,
+            )?
+            .unwrap();
+            Ok(())
+        }
+
+        fn sudo(instance: &mut TestingInstance) -> VmResult<()> {
+            let recipient = instance.api().addr_make("community-pool");
+            let msg = format!(
+                r#"{{"steal_funds":{{"recipient":"{recipient}","amount":[{{"amount":"10","denom":"earth"}}]}}}}"#
+            );
+            call_sudo::<_, _, _, Empty>(instance, &mock_env(), msg.as_bytes())?.unwrap();
+            Ok(())
+        }
+
+        fn query(instance: &mut TestingInstance) -> VmResult<()> {
+            call_query(instance, &mock_env(), br#"{"verifier":{}}"#)?.unwrap();
+            Ok(())
+        }
+
+        fn new_instance() -> TestingInstance {
+            mock_instance(CONTRACT, &[])
+        }
+
+        fn instantiated() -> TestingInstance {
+            let mut instance = new_instance();
+            instantiate(&mut instance).unwrap();
+            instance
+        }
+
+        type Case = (
+            fn() -> TestingInstance,
+            fn(&mut ResultLimits, usize),
+            fn(&mut TestingInstance) -> VmResult<()>,
+        );
+        let cases: [Case; 6] = [
+            (new_instance, |l, v| l.instantiate = v, instantiate),
+            (instantiated, |l, v| l.execute = v, execute),
+            (instantiated, |l, v| l.migrate = v, migrate),
+            (instantiated, |l, v| l.migrate = v, migrate_with_info),
+            (instantiated, |l, v| l.sudo = v, sudo),
+            (instantiated, |l, v| l.query = v, query),
+        ];
+        for (new_instance, set_limit, call) in cases {
+            assert_result_limit_enforced(new_instance, set_limit, call);
+        }
+    }
+
+    #[test]
+    fn call_raw_only_uses_raw_result_limits() {
+        let mut instance = mock_instance(CONTRACT, &[]);
+
+        // init
+        let info = mock_info(&instance.api().addr_make("creator"), &coins(1000, "earth"));
+        let verifier = instance.api().addr_make("verifies");
+        let beneficiary = instance.api().addr_make("benefits");
+        let msg = format!(r#"{{"verifier": "{verifier}", "beneficiary": "{beneficiary}"}}"#);
+        call_instantiate::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg.as_bytes())
+            .unwrap()
+            .unwrap();
+
+        let env = to_vec(&mock_env()).unwrap();
+        let msg = br#"{"verifier":{}}"#;
+        let limits = ResultLimits {
+            query: 1,
+            ..ResultLimits::default()
+        };
+
+        // the limits of the typed calls do not apply to the raw calls
+        instance.set_result_limits(limits);
+        call_query_raw(&mut instance, &env, msg).unwrap();
+
+        instance.set_result_limits(ResultLimits::default());
+        instance.set_raw_result_limits(Some(limits));
+        match call_query_raw(&mut instance, &env, msg).unwrap_err() {
+            VmError::DeserializationLimitExceeded {
+                length, max_length, ..
+            } => {
+                assert!(length > 1);
+                assert_eq!(max_length, 1);
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        instance.set_raw_result_limits(None);
+        call_query_raw(&mut instance, &env, msg).unwrap();
+    }
+
     #[test]
     fn float_instrs_are_deterministic() {
         #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -1167,6 +1873,140 @@ mod tests {
                 .unwrap();
         }
 
+        #[test]
+        fn ibc_calls_enforce_result_limits() {
+            fn instantiated() -> TestingInstance {
+                let mut instance = mock_instance(CONTRACT, &[]);
+                let info = mock_info("creator", &[]);
+                let msg = br#"{"reflect_code_id":77}"#;
+                call_instantiate::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg)
+                    .unwrap()
+                    .unwrap();
+                instance
+            }
+
+            fn opened() -> TestingInstance {
+                let mut instance = instantiated();
+                channel_open(&mut instance).unwrap();
+                instance
+            }
+
+            fn connected() -> TestingInstance {
+                let mut instance = mock_instance(CONTRACT, &[]);
+                let account = instance.api().addr_make(ACCOUNT);
+                setup(&mut instance, CHANNEL_ID, &account);
+                instance
+            }
+
+            fn callbacks_instantiated() -> TestingInstance {
+                let mut instance = mock_instance(IBC_CALLBACKS, &[]);
+                let info = mock_info(&instance.api().addr_make("creator"), &[]);
+                call_instantiate::<_, _, _, Empty>(&mut instance, &mock_env(), &info, br#"{}"#)
+                    .unwrap()
+                    .unwrap();
+                instance
+            }
+
+            fn channel_open(instance: &mut TestingInstance) -> VmResult<()> {
+                let msg = mock_ibc_channel_open_init(CHANNEL_ID, IbcOrder::Ordered, IBC_VERSION);
+                call_ibc_channel_open(instance, &mock_env(), &msg)?.unwrap();
+                Ok(())
+            }
+
+            fn channel_connect(instance: &mut TestingInstance) -> VmResult<()> {
+                let msg = mock_ibc_channel_connect_ack(CHANNEL_ID, IbcOrder::Ordered, IBC_VERSION);
+                call_ibc_channel_connect::<_, _, _, Empty>(instance, &mock_env(), &msg)?.unwrap();
+                Ok(())
+            }
+
+            fn channel_close(instance: &mut TestingInstance) -> VmResult<()> {
+                let msg = mock_ibc_channel_close_init(CHANNEL_ID, IbcOrder::Ordered, IBC_VERSION);
+                call_ibc_channel_close::<_, _, _, Empty>(instance, &mock_env(), &msg)?.unwrap();
+                Ok(())
+            }
+
+            fn packet_receive(instance: &mut TestingInstance) -> VmResult<()> {
+                let msg = mock_ibc_packet_recv(CHANNEL_ID, br#"{"who_am_i":{}}"#).unwrap();
+                call_ibc_packet_receive::<_, _, _, Empty>(instance, &mock_env(), &msg)?.unwrap();
+                Ok(())
+            }
+
+            fn packet_ack(instance: &mut TestingInstance) -> VmResult<()> {
+                let ack = IbcAcknowledgement::new(br#"{}"#);
+                let msg = mock_ibc_packet_ack(CHANNEL_ID, br#"{}"#, ack).unwrap();
+                call_ibc_packet_ack::<_, _, _, Empty>(instance, &mock_env(), &msg)?.unwrap();
+                Ok(())
+            }
+
+            fn packet_timeout(instance: &mut TestingInstance) -> VmResult<()> {
+                let msg = mock_ibc_packet_timeout(CHANNEL_ID, br#"{}"#).unwrap();
+                call_ibc_packet_timeout::<_, _, _, Empty>(instance, &mock_env(), &msg)?.unwrap();
+                Ok(())
+            }
+
+            fn reply(instance: &mut TestingInstance) -> VmResult<()> {
+                // a failed dispatch is turned into an error acknowledgement
+                #[allow(deprecated)]
+                let msg = Reply {
+                    id: 1234,
+                    payload: Binary::default(),
+                    gas_used: 0,
+                    result: SubMsgResult::Err("dispatch failed".to_string()),
+                };
+                call_reply::<_, _, _, Empty>(instance, &mock_env(), &msg)?.unwrap();
+                Ok(())
+            }
+
+            fn source_callback(instance: &mut TestingInstance) -> VmResult<()> {
+                let timeout = mock_ibc_packet_timeout(CHANNEL_ID, br#"{}"#).unwrap();
+                let msg = IbcSourceCallbackMsg::Timeout(IbcTimeoutCallbackMsg::new(
+                    timeout.packet,
+                    timeout.relayer,
+                ));
+                call_ibc_source_callback::<_, _, _, Empty>(instance, &mock_env(), &msg)?.unwrap();
+                Ok(())
+            }
+
+            fn destination_callback(instance: &mut TestingInstance) -> VmResult<()> {
+                let receive = mock_ibc_packet_recv(CHANNEL_ID, br#"{}"#).unwrap();
+                let msg = IbcDestinationCallbackMsg {
+                    packet: receive.packet,
+                    ack: IbcAcknowledgement::new(br#"{}"#),
+                };
+                call_ibc_destination_callback::<_, _, _, Empty>(instance, &mock_env(), &msg)?
+                    .unwrap();
+                Ok(())
+            }
+
+            type Case = (
+                fn() -> TestingInstance,
+                fn(&mut ResultLimits, usize),
+                fn(&mut TestingInstance) -> VmResult<()>,
+            );
+            let cases: [Case; 9] = [
+                (instantiated, |l, v| l.reply = v, reply),
+                (instantiated, |l, v| l.ibc_channel_open = v, channel_open),
+                (opened, |l, v| l.ibc_channel_connect = v, channel_connect),
+                (connected, |l, v| l.ibc_channel_close = v, channel_close),
+                (connected, |l, v| l.ibc_packet_receive = v, packet_receive),
+                (connected, |l, v| l.ibc_packet_ack = v, packet_ack),
+                (connected, |l, v| l.ibc_packet_timeout = v, packet_timeout),
+                (
+                    callbacks_instantiated,
+                    |l, v| l.ibc_source_callback = v,
+                    source_callback,
+                ),
+                (
+                    callbacks_instantiated,
+                    |l, v| l.ibc_destination_callback = v,
+                    destination_callback,
+                ),
+            ];
+            for (new_instance, set_limit, call) in cases {
+                assert_result_limit_enforced(new_instance, set_limit, call);
+            }
+        }
+
         #[test]
         fn call_ibc_source_callback_works() {
             let mut instance = mock_instance(IBC_CALLBACKS, &[]);
@@ -1,8 +1,11 @@
 use std::collections::HashSet;
+use std::str::FromStr;
 
-use crate::static_analysis::ExportInfo;
+use crate::errors::VmResult;
+use crate::parsed_wasm::ParsedWasm;
+use crate::static_analysis::{Entrypoint, ExportInfo};
 
-const REQUIRES_PREFIX: &str = "requires_";
+pub(crate) const REQUIRES_PREFIX: &str = "requires_";
 
 /// Takes a comma-separated string, splits it by commas, removes empty elements and returns a set of capabilities.
 /// This can be used e.g. to initialize the cache.
@@ -30,10 +33,60 @@ pub fn required_capabilities_from_module(module: impl ExportInfo) -> HashSet<Str
         .collect()
 }
 
+/// Returns the capabilities a chain must support in order to call the given entrypoint.
+/// This is independent of the capabilities the contract requires explicitly.
+pub fn required_capabilities_for_entrypoint(entrypoint: Entrypoint) -> HashSet<String> {
+    match entrypoint {
+        Entrypoint::Instantiate
+        | Entrypoint::Execute
+        | Entrypoint::Migrate
+        | Entrypoint::Sudo
+        | Entrypoint::Reply
+        | Entrypoint::Query => HashSet::new(),
+        Entrypoint::IbcChannelOpen
+        | Entrypoint::IbcChannelConnect
+        | Entrypoint::IbcChannelClose
+        | Entrypoint::IbcPacketReceive
+        | Entrypoint::IbcPacketAck
+        | Entrypoint::IbcPacketTimeout => HashSet::from(["stargate".to_string()]),
+    }
+}
+
+/// Returns the capabilities a chain must support in order to provide the given import,
+/// which is identified by module and name.
+fn required_capabilities_for_import(module: &str, name: &str) -> HashSet<String> {
+    match (module, name) {
+        ("env", "db_scan" | "db_next" | "db_next_key" | "db_next_value") => {
+            HashSet::from(["iterator".to_string()])
+        }
+        _ => HashSet::new(),
+    }
+}
+
+/// Returns the minimal set of capabilities a chain must support in order to store and use
+/// the given Wasm.
+///
+/// This combines the capabilities the contract requires explicitly (see
+/// [`required_capabilities_from_module`]) with the ones implied by its entrypoints
+/// (e.g. the IBC entrypoints require `stargate`) and its imports (e.g. the iterator imports
+/// require `iterator`).
+pub fn required_capabilities(wasm: &[u8]) -> VmResult<HashSet<String>> {
+    let module = ParsedWasm::parse(wasm)?;
+
+    let mut capabilities = required_capabilities_from_module(&module);
+    for export in module.exported_function_names(None) {
+        if let Ok(entrypoint) = Entrypoint::from_str(&export) {
+            capabilities.extend(required_capabilities_for_entrypoint(entrypoint));
+        }
+    }
+    for import in &module.imports {
+        capabilities.extend(required_capabilities_for_import(import.module, import.name));
+    }
+    Ok(capabilities)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::parsed_wasm::ParsedWasm;
-
     use super::*;
 
     #[test]
@@ -90,4 +143,63 @@ mod tests {
         let required_capabilities = required_capabilities_from_module(&module);
         assert_eq!(required_capabilities.len(), 0);
     }
+
+    #[test]
+    fn required_capabilities_for_entrypoint_works() {
+        assert!(required_capabilities_for_entrypoint(Entrypoint::Execute).is_empty());
+        assert!(required_capabilities_for_entrypoint(Entrypoint::Query).is_empty());
+        assert_eq!(
+            required_capabilities_for_entrypoint(Entrypoint::IbcPacketReceive),
+            HashSet::from(["stargate".to_string()])
+        );
+        assert_eq!(
+            required_capabilities_for_entrypoint(Entrypoint::IbcChannelOpen),
+            HashSet::from(["stargate".to_string()])
+        );
+    }
+
+    #[test]
+    fn required_capabilities_works() {
+        let wasm = wat::parse_str(
+            r#"(module
+            (type (func))
+            (type (func (param i32 i32 i32) (result i32)))
+            (import "env" "db_scan" (func (type 1)))
+            (func (type 0) nop)
+            (export "requires_cosmwasm_2_1" (func 1))
+            (export "execute" (func 1))
+            (export "ibc_channel_open" (func 1))
+            (export "ibc_packet_receive" (func 1))
+            )"#,
+        )
+        .unwrap();
+        let required = required_capabilities(&wasm).unwrap();
+        assert_eq!(
+            required,
+            HashSet::from([
+                "cosmwasm_2_1".to_string(),
+                "stargate".to_string(),
+                "iterator".to_string(),
+            ])
+        );
+
+        // no entrypoints or imports requiring capabilities
+        let wasm = wat::parse_str(
+            r#"(module
+            (type (func))
+            (import "env" "db_read" (func (type 0)))
+            (func (type 0) nop)
+            (export "instantiate" (func 1))
+            (export "query" (func 1))
+            )"#,
+        )
+        .unwrap();
+        assert!(required_capabilities(&wasm).unwrap().is_empty());
+
+        let wasm = wat::parse_str(r#"(module)"#).unwrap();
+        assert!(required_capabilities(&wasm).unwrap().is_empty());
+
+        // invalid Wasm
+        required_capabilities(b"not a wasm").unwrap_err();
+    }
 }
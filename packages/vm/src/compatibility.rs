@@ -4,12 +4,12 @@ use std::collections::HashSet;
 use wasmer::wasmparser::Import;
 use wasmer::wasmparser::TypeRef;
 
-use crate::capabilities::required_capabilities_from_module;
+use crate::capabilities::{required_capabilities_from_module, REQUIRES_PREFIX};
 use crate::config::WasmLimits;
 use crate::errors::{VmError, VmResult};
 use crate::limited::LimitedDisplay;
 use crate::parsed_wasm::ParsedWasm;
-use crate::static_analysis::ExportInfo;
+use crate::static_analysis::{float_function_indices, max_direct_call_depth, ExportInfo};
 
 /// Lists all imports we provide upon instantiating the instance in Instance::from_module()
 /// This should be updated when new imports are added
@@ -32,6 +32,8 @@ const SUPPORTED_IMPORTS: &[&str] = &[
     "env.secp256r1_recover_pubkey",
     "env.ed25519_verify",
     "env.ed25519_batch_verify",
+    "env.ed25519_validate_pubkey",
+    "env.hmac_sha256",
     "env.debug",
     "env.query_chain",
     #[cfg(feature = "iterator")]
@@ -54,6 +56,28 @@ const REQUIRED_EXPORTS: &[&str] = &[
     "deallocate",
 ];
 
+/// Entry points the VM calls by name. Exports that are a near-miss of one of these names are
+/// likely misspelled and would never be called.
+const KNOWN_ENTRY_POINTS: &[&str] = &[
+    "instantiate",
+    "execute",
+    "query",
+    "migrate",
+    "sudo",
+    "reply",
+    "ibc_channel_open",
+    "ibc_channel_connect",
+    "ibc_channel_close",
+    "ibc_packet_receive",
+    "ibc_packet_ack",
+    "ibc_packet_timeout",
+    "ibc_source_callback",
+    "ibc_destination_callback",
+];
+
+/// The maximum edit distance for which an export is considered a misspelled entry point
+const MAX_ENTRY_POINT_DISTANCE: usize = 2;
+
 const INTERFACE_VERSION_PREFIX: &str = "interface_version_";
 const SUPPORTED_INTERFACE_VERSIONS: &[&str] = &["interface_version_8"];
 
@@ -109,9 +133,12 @@ pub fn check_wasm(
     check_wasm_memories(&module, limits)?;
     check_interface_version(&module)?;
     check_wasm_exports(&module, logs)?;
+    check_wasm_entry_points(&module, logs);
     check_wasm_imports(&module, SUPPORTED_IMPORTS, limits, logs)?;
     check_wasm_capabilities(&module, available_capabilities, logs)?;
     check_wasm_functions(&module, limits, logs)?;
+    check_wasm_floats(&module, limits, logs)?;
+    check_wasm_call_depth(&module, limits, logs)?;
 
     module.validate_funcs()
 }
@@ -208,6 +235,63 @@ fn check_wasm_exports(module: &ParsedWasm, logs: Logger) -> VmResult<()> {
     Ok(())
 }
 
+/// Returns a warning for every export of the given Wasm blob that looks like a misspelled
+/// entry point. These are also logged by [`check_wasm`].
+pub fn entry_point_warnings(wasm_code: &[u8]) -> VmResult<Vec<String>> {
+    let module = ParsedWasm::parse(wasm_code)?;
+    Ok(misspelled_entry_points(&module))
+}
+
+/// Logs a warning for every export that looks like a misspelled entry point, e.g. `excute`.
+/// Those are never called by the VM, which is easy to miss.
+fn check_wasm_entry_points(module: &ParsedWasm, logs: Logger) {
+    for warning in misspelled_entry_points(module) {
+        logs.add(|| format!("Warning: {warning}"));
+    }
+}
+
+/// Returns a warning for every exported function whose name is within an edit distance of
+/// [`MAX_ENTRY_POINT_DISTANCE`] of a known entry point, but not a known export itself.
+fn misspelled_entry_points(module: &ParsedWasm) -> Vec<String> {
+    let mut exports: Vec<String> = module.exported_function_names(None).into_iter().collect();
+    exports.sort();
+
+    exports
+        .iter()
+        .filter(|export| {
+            !KNOWN_ENTRY_POINTS.contains(&export.as_str())
+                && !REQUIRED_EXPORTS.contains(&export.as_str())
+                && !export.starts_with(INTERFACE_VERSION_PREFIX)
+                && !export.starts_with(REQUIRES_PREFIX)
+        })
+        .filter_map(|export| {
+            KNOWN_ENTRY_POINTS
+                .iter()
+                .find(|entry_point| edit_distance(export, entry_point) <= MAX_ENTRY_POINT_DISTANCE)
+                .map(|entry_point| {
+                    format!(
+                        "Export \"{export}\" looks like a misspelling of the entry point \"{entry_point}\" and will never be called"
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Returns the Levenshtein distance between the two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
 /// Checks if the import requirements of the contract are satisfied.
 /// When this is not the case, we either have an incompatibility between contract and VM
 /// or a error in the contract.
@@ -328,6 +412,53 @@ fn check_wasm_functions(module: &ParsedWasm, limits: &WasmLimits, logs: Logger)
     Ok(())
 }
 
+/// Checks for floating-point operations, which must be called before the function bodies are
+/// validated. Those are only rejected if `forbid_floats` is set.
+fn check_wasm_floats(module: &ParsedWasm, limits: &WasmLimits, logs: Logger) -> VmResult<()> {
+    let indices = float_function_indices(module)?;
+    if indices.is_empty() {
+        return Ok(());
+    }
+
+    if limits.forbid_floats() {
+        // Keep the error short for contracts with many such functions
+        let shown = &indices[..indices.len().min(10)];
+        return Err(VmError::static_validation_err(format!(
+            "Wasm contract uses floating-point operations in {} functions. First function indices: {shown:?}",
+            indices.len()
+        )));
+    }
+    logs.add(|| format!("Warning: Floating-point operations in functions {indices:?}"));
+    Ok(())
+}
+
+/// Reports the maximum depth of direct function calls and warns if it exceeds the
+/// configured maximum call depth.
+///
+/// This does not reject the contract, since the static depth is only an estimate: it
+/// ignores indirect calls and cannot bound recursion, while not every call path
+/// is actually executed. The limit is enforced at runtime.
+fn check_wasm_call_depth(module: &ParsedWasm, limits: &WasmLimits, logs: Logger) -> VmResult<()> {
+    let depth = max_direct_call_depth(module)?;
+    logs.add(|| match depth {
+        Some(depth) => format!("Max direct call depth: {depth}"),
+        None => "Max direct call depth: unbounded (recursive calls)".to_string(),
+    });
+
+    if let Some(limit) = limits.max_call_depth() {
+        match depth {
+            Some(depth) if depth <= limit => {}
+            Some(depth) => logs.add(|| {
+                format!("Warning: Direct call depth of {depth} exceeds the maximum call depth of {limit}")
+            }),
+            None => logs.add(|| {
+                format!("Warning: Recursive calls may exceed the maximum call depth of {limit}")
+            }),
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -340,6 +471,7 @@ mod tests {
     static CONTRACT: &[u8] = include_bytes!("../testdata/hackatom.wasm");
     static CYBERPUNK: &[u8] = include_bytes!("../testdata/cyberpunk.wasm");
     static CONTRACT_RUST_170: &[u8] = include_bytes!("../testdata/cyberpunk_rust170.wasm");
+    static CONTRACT_FLOATY: &[u8] = include_bytes!("../testdata/floaty_2.0.wasm");
 
     fn default_capabilities() -> HashSet<String> {
         capabilities_from_csv("cosmwasm_1_1,cosmwasm_1_2,cosmwasm_1_3,iterator,staking,stargate")
@@ -1051,6 +1183,101 @@ mod tests {
         }
     }
 
+    #[test]
+    fn edit_distance_works() {
+        assert_eq!(edit_distance("execute", "execute"), 0);
+        assert_eq!(edit_distance("excute", "execute"), 1);
+        assert_eq!(edit_distance("exceute", "execute"), 2);
+        assert_eq!(edit_distance("", "sudo"), 4);
+        assert_eq!(edit_distance("query", "reply"), 4);
+    }
+
+    #[test]
+    fn check_wasm_entry_points_works() {
+        let wasm = wat::parse_str(
+            r#"(module
+                (type (func))
+                (func (type 0) nop)
+                (export "allocate" (func 0))
+                (export "deallocate" (func 0))
+                (export "interface_version_8" (func 0))
+                (export "requires_staking" (func 0))
+                (export "instantiate" (func 0))
+                (export "excute" (func 0))
+                (export "qurey" (func 0))
+                (export "ibc_packet_recieve" (func 0))
+                (export "add_one" (func 0))
+            )"#,
+        )
+        .unwrap();
+        let module = ParsedWasm::parse(&wasm).unwrap();
+        assert_eq!(
+            misspelled_entry_points(&module),
+            [
+                "Export \"excute\" looks like a misspelling of the entry point \"execute\" and will never be called",
+                "Export \"ibc_packet_recieve\" looks like a misspelling of the entry point \"ibc_packet_receive\" and will never be called",
+                "Export \"qurey\" looks like a misspelling of the entry point \"query\" and will never be called",
+            ]
+        );
+
+        // real contracts have no misspelled entry points
+        let module = ParsedWasm::parse(CONTRACT).unwrap();
+        assert!(misspelled_entry_points(&module).is_empty());
+    }
+
+    #[test]
+    fn check_wasm_floats_works() {
+        let wasm = wat::parse_str(
+            r#"(module
+                (func (result i32) i32.const 1)
+                (func (result f64) f64.const 1.5)
+            )"#,
+        )
+        .unwrap();
+
+        // allowed by default
+        let module = ParsedWasm::parse(&wasm).unwrap();
+        check_wasm_floats(&module, &WasmLimits::default(), Off).unwrap();
+
+        let limits = WasmLimits {
+            forbid_floats: Some(true),
+            ..Default::default()
+        };
+        match check_wasm_floats(&module, &limits, Off).unwrap_err() {
+            VmError::StaticValidationErr { msg, .. } => assert_eq!(
+                msg,
+                "Wasm contract uses floating-point operations in 1 functions. First function indices: [1]"
+            ),
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        // contracts without floats pass
+        let wasm = wat::parse_str(r#"(module (func (result i32) i32.const 1))"#).unwrap();
+        let module = ParsedWasm::parse(&wasm).unwrap();
+        check_wasm_floats(&module, &limits, Off).unwrap();
+    }
+
+    #[test]
+    fn check_wasm_forbid_floats() {
+        let limits = WasmLimits {
+            forbid_floats: Some(true),
+            ..Default::default()
+        };
+        check_wasm(
+            CONTRACT_FLOATY,
+            &default_capabilities(),
+            &WasmLimits::default(),
+            Off,
+        )
+        .unwrap();
+        match check_wasm(CONTRACT_FLOATY, &default_capabilities(), &limits, Off).unwrap_err() {
+            VmError::StaticValidationErr { msg, .. } => {
+                assert!(msg.starts_with("Wasm contract uses floating-point operations in"))
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+    }
+
     #[test]
     fn check_wasm_fails_for_big_functions() {
         let limits = WasmLimits::default();
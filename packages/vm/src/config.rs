@@ -1,5 +1,6 @@
-use std::{collections::HashSet, path::PathBuf};
+use std::{collections::HashSet, num::NonZeroUsize, path::PathBuf};
 
+use cosmwasm_std::Checksum;
 use serde::{Deserialize, Serialize};
 
 use crate::Size;
@@ -21,6 +22,28 @@ const DEFAULT_MAX_TOTAL_FUNCTION_PARAMS: usize = 10_000;
 
 const DEFAULT_MAX_FUNCTION_RESULTS: usize = 1;
 
+/// Every `db_read`, `db_next`, `query_chain` etc. requires one allocation. Gas runs out long before
+/// a contract gets close to this number under normal circumstances.
+const DEFAULT_MAX_ALLOCATIONS_PER_CALL: u64 = 1_000_000;
+
+/// The response limits are far above anything a normal contract returns. They only exist
+/// to stop contracts from returning a huge number of tiny events, attributes or messages,
+/// which are much larger in memory than in their JSON representation.
+const DEFAULT_MAX_EVENTS_PER_RESPONSE: usize = 10_000;
+
+const DEFAULT_MAX_ATTRIBUTES_PER_EVENT: usize = 10_000;
+
+const DEFAULT_MAX_SUBMESSAGES_PER_RESPONSE: usize = 10_000;
+
+/// This matches the 64 MiB the VM reads from the contract's memory at most, so it never
+/// triggers by default, including for the `call_*_raw` functions.
+const DEFAULT_MAX_RESPONSE_DATA_BYTES: usize = 64 * 1024 * 1024;
+
+/// The default deserialization limit for the JSON result of every entry point.
+/// This is plenty of room for a normal contract response and small enough to keep
+/// the cost of deserializing it low.
+const DEFAULT_RESULT_LIMIT: usize = 256 * 1024;
+
 /// Various configurations for the VM.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -32,6 +55,23 @@ pub struct Config {
 
     /// Configuration for the cache.
     pub cache: CacheOptions,
+
+    /// The gas costs of the host functions provided by the VM.
+    /// They are applied to all instances created by the cache.
+    ///
+    /// Changing any of these changes the gas consumption of contracts,
+    /// so it must only be done as part of a consensus breaking upgrade.
+    #[serde(default)]
+    pub gas_costs: GasCosts,
+
+    /// Restricts which codes can be instantiated by the cache. `None` allows all codes.
+    /// The policy can be changed at runtime using [`Cache::set_code_policy`](crate::Cache::set_code_policy).
+    ///
+    /// This is a local guard for nodes that do not take part in consensus, e.g. public
+    /// query nodes. It must not be enabled on consensus nodes, since blocking a code there
+    /// makes the node produce different results than the rest of the network.
+    #[serde(default)]
+    pub code_policy: Option<CodePolicy>,
 }
 
 impl Config {
@@ -39,6 +79,8 @@ impl Config {
         Self {
             wasm_limits: WasmLimits::default(),
             cache,
+            gas_costs: GasCosts::default(),
+            code_policy: None,
         }
     }
 }
@@ -85,6 +127,77 @@ pub struct WasmLimits {
 
     /// The maximum number of results a Wasm function type can have.
     pub max_function_results: Option<usize>,
+
+    /// The maximum number of times the VM may call the contract's `allocate` export
+    /// during a single call into the contract (e.g. to write env, info, msg, query responses
+    /// or storage values into the contract's memory).
+    ///
+    /// In contrast to the other limits, this one is not checked during static validation
+    /// but enforced at runtime for all instances created by the cache.
+    pub max_allocations_per_call: Option<u64>,
+
+    /// The gas charged per Wasm memory page (64 KiB) a contract grows its memory by
+    /// during a call. Unset by default, which means memory growth is only limited by the
+    /// memory limit but does not cost gas.
+    ///
+    /// Setting this changes the gas consumption of contracts, so it must only be enabled
+    /// as part of a consensus breaking upgrade. Like `max_allocations_per_call`, this
+    /// is applied at runtime to all instances created by the cache.
+    pub memory_grow_gas_per_page: Option<u64>,
+
+    /// The maximum nesting depth of Wasm function calls within a single call into the
+    /// contract, e.g. for recursion. Exceeding it results in a [`VmError::RuntimeErr`](crate::VmError::RuntimeErr)
+    /// with the message "Maximum Wasm call depth exceeded".
+    ///
+    /// Unset by default, which means the call depth is only limited by the native stack size,
+    /// which can differ between hosts. Like `max_allocations_per_call`, this is applied at
+    /// runtime to all instances created by the cache. Setting it changes which executions fail,
+    /// so it must only be enabled as part of a consensus breaking upgrade.
+    pub max_call_depth: Option<u32>,
+
+    /// The maximum number of events in a contract response.
+    ///
+    /// This and the following response limits are enforced at runtime for all instances
+    /// created by the cache. They are checked while parsing the result of every call returning
+    /// a response, including the `call_*_raw` functions, before the response is deserialized.
+    /// A response exceeding any of them results in
+    /// [`VmError::ResultLimitExceeded`](crate::VmError::ResultLimitExceeded).
+    pub max_events_per_response: Option<usize>,
+    /// The maximum number of attributes per event in a contract response.
+    /// This also applies to the attributes of the response itself.
+    pub max_attributes_per_event: Option<usize>,
+    /// The maximum number of (sub)messages in a contract response.
+    pub max_submessages_per_response: Option<usize>,
+    /// The maximum length in bytes of the data set in a contract response
+    /// (or the acknowledgement of an `ibc_packet_receive` call).
+    pub max_response_data_bytes: Option<usize>,
+
+    /// The maximum length in bytes of the JSON result of each entry point.
+    /// Unset fields use the default of 256 KiB.
+    ///
+    /// Like the response limits, those are enforced at runtime for all instances
+    /// created by the cache. They apply to the `call_*` functions, which deserialize
+    /// the result. A longer result is rejected with
+    /// [`VmError::DeserializationLimitExceeded`](crate::VmError::DeserializationLimitExceeded)
+    /// before it is deserialized.
+    #[serde(default)]
+    pub result_limits: ResultLimits,
+
+    /// The maximum length in bytes of the JSON result of each entry point for the
+    /// `call_*_raw` functions, which return the result without deserializing it.
+    ///
+    /// Unset by default, which means those results are only limited by the 64 MiB the VM
+    /// reads from the contract's memory at most. The raw functions are the ones used by
+    /// the chain, so setting this changes which executions fail and must only be done
+    /// as part of a consensus breaking upgrade.
+    pub raw_result_limits: Option<ResultLimits>,
+
+    /// If set to `true`, Wasm files using floating-point operations are rejected during
+    /// static validation. Otherwise, the functions using them are only logged as a warning.
+    ///
+    /// Floating-point operations are executed deterministically by the VM,
+    /// so this is not needed for consensus safety.
+    pub forbid_floats: Option<bool>,
 }
 
 impl WasmLimits {
@@ -120,6 +233,253 @@ impl WasmLimits {
         self.max_function_results
             .unwrap_or(DEFAULT_MAX_FUNCTION_RESULTS)
     }
+
+    pub fn max_allocations_per_call(&self) -> u64 {
+        self.max_allocations_per_call
+            .unwrap_or(DEFAULT_MAX_ALLOCATIONS_PER_CALL)
+    }
+
+    pub fn memory_grow_gas_per_page(&self) -> Option<u64> {
+        self.memory_grow_gas_per_page
+    }
+
+    pub fn max_call_depth(&self) -> Option<u32> {
+        self.max_call_depth
+    }
+
+    pub fn raw_result_limits(&self) -> Option<ResultLimits> {
+        self.raw_result_limits
+    }
+
+    pub fn max_events_per_response(&self) -> usize {
+        self.max_events_per_response
+            .unwrap_or(DEFAULT_MAX_EVENTS_PER_RESPONSE)
+    }
+
+    pub fn max_attributes_per_event(&self) -> usize {
+        self.max_attributes_per_event
+            .unwrap_or(DEFAULT_MAX_ATTRIBUTES_PER_EVENT)
+    }
+
+    pub fn max_submessages_per_response(&self) -> usize {
+        self.max_submessages_per_response
+            .unwrap_or(DEFAULT_MAX_SUBMESSAGES_PER_RESPONSE)
+    }
+
+    pub fn max_response_data_bytes(&self) -> usize {
+        self.max_response_data_bytes
+            .unwrap_or(DEFAULT_MAX_RESPONSE_DATA_BYTES)
+    }
+
+    pub fn forbid_floats(&self) -> bool {
+        self.forbid_floats.unwrap_or(false)
+    }
+
+    /// Returns the response limits with all defaults applied
+    pub fn response_limits(&self) -> ResponseLimits {
+        ResponseLimits {
+            max_events: self.max_events_per_response(),
+            max_attributes_per_event: self.max_attributes_per_event(),
+            max_submessages: self.max_submessages_per_response(),
+            max_data_bytes: self.max_response_data_bytes(),
+        }
+    }
+}
+
+/// Limits for the contents of a successful contract response.
+/// See the corresponding fields of [`WasmLimits`] for details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResponseLimits {
+    pub max_events: usize,
+    pub max_attributes_per_event: usize,
+    pub max_submessages: usize,
+    pub max_data_bytes: usize,
+}
+
+impl Default for ResponseLimits {
+    fn default() -> Self {
+        WasmLimits::default().response_limits()
+    }
+}
+
+/// The maximum length in bytes of the JSON result of each entry point.
+///
+/// Those limits apply to the `call_*` functions, which deserialize the result. The
+/// `call_*_raw` functions only check them if they are set via
+/// [`WasmLimits::raw_result_limits`]. Independent of them, the VM never reads more
+/// than 64 MiB of result data from the contract's memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResultLimits {
+    pub instantiate: usize,
+    pub execute: usize,
+    pub migrate: usize,
+    pub sudo: usize,
+    pub reply: usize,
+    pub query: usize,
+    pub ibc_channel_open: usize,
+    pub ibc_channel_connect: usize,
+    pub ibc_channel_close: usize,
+    /// This limit includes the acknowledgement returned by the contract
+    pub ibc_packet_receive: usize,
+    pub ibc_packet_ack: usize,
+    pub ibc_packet_timeout: usize,
+    pub ibc_source_callback: usize,
+    pub ibc_destination_callback: usize,
+}
+
+impl Default for ResultLimits {
+    fn default() -> Self {
+        Self {
+            instantiate: DEFAULT_RESULT_LIMIT,
+            execute: DEFAULT_RESULT_LIMIT,
+            migrate: DEFAULT_RESULT_LIMIT,
+            sudo: DEFAULT_RESULT_LIMIT,
+            reply: DEFAULT_RESULT_LIMIT,
+            query: DEFAULT_RESULT_LIMIT,
+            ibc_channel_open: DEFAULT_RESULT_LIMIT,
+            ibc_channel_connect: DEFAULT_RESULT_LIMIT,
+            ibc_channel_close: DEFAULT_RESULT_LIMIT,
+            ibc_packet_receive: DEFAULT_RESULT_LIMIT,
+            ibc_packet_ack: DEFAULT_RESULT_LIMIT,
+            ibc_packet_timeout: DEFAULT_RESULT_LIMIT,
+            ibc_source_callback: DEFAULT_RESULT_LIMIT,
+            ibc_destination_callback: DEFAULT_RESULT_LIMIT,
+        }
+    }
+}
+
+/// The gas costs of the host functions provided by the VM (not the backend),
+/// measured in [CosmWasm gas](https://github.com/CosmWasm/cosmwasm/blob/main/docs/GAS.md).
+///
+/// The defaults are derived from benchmarks. Chains can override individual costs
+/// via [`Config::gas_costs`], but this is consensus breaking: all nodes of a chain must
+/// use the same values, and changing them must be coordinated as part of an upgrade.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct GasCosts {
+    /// secp256k1 signature verification cost
+    pub secp256k1_verify_cost: u64,
+    /// secp256k1 public key recovery cost
+    pub secp256k1_recover_pubkey_cost: u64,
+    /// secp256r1 signature verification cost
+    pub secp256r1_verify_cost: u64,
+    /// secp256r1 public key recovery cost
+    pub secp256r1_recover_pubkey_cost: u64,
+    /// ed25519 signature verification cost
+    pub ed25519_verify_cost: u64,
+    /// ed25519 batch signature verification cost
+    pub ed25519_batch_verify_cost: LinearGasCost,
+    /// ed25519 batch signature verification cost (single public key)
+    pub ed25519_batch_verify_one_pubkey_cost: LinearGasCost,
+    /// ed25519 public key validation cost
+    pub ed25519_validate_pubkey_cost: u64,
+    /// bls12-381 aggregate cost (g1)
+    pub bls12_381_aggregate_g1_cost: LinearGasCost,
+    /// bls12-381 aggregate cost (g2)
+    pub bls12_381_aggregate_g2_cost: LinearGasCost,
+    /// bls12-381 hash to g1 cost
+    pub bls12_381_hash_to_g1_cost: u64,
+    /// bls12-381 hash to g2 cost
+    pub bls12_381_hash_to_g2_cost: u64,
+    /// bls12-381 pairing equality check cost
+    pub bls12_381_pairing_equality_cost: LinearGasCost,
+    /// HMAC-SHA256 cost, linear in the number of key and data bytes
+    pub hmac_sha256_cost: LinearGasCost,
+}
+
+impl Default for GasCosts {
+    fn default() -> Self {
+        // Target is 10^12 per second (see GAS.md), i.e. 10^6 gas per µ second.
+        const GAS_PER_US: u64 = 1_000_000;
+        Self {
+            // ~96 us in crypto benchmarks
+            secp256k1_verify_cost: 96 * GAS_PER_US,
+            // ~194 us in crypto benchmarks
+            secp256k1_recover_pubkey_cost: 194 * GAS_PER_US,
+            // ~279 us in crypto benchmarks
+            secp256r1_verify_cost: 279 * GAS_PER_US,
+            // ~592 us in crypto benchmarks
+            secp256r1_recover_pubkey_cost: 592 * GAS_PER_US,
+            // ~35 us in crypto benchmarks
+            ed25519_verify_cost: 35 * GAS_PER_US,
+            // Calculated based on the benchmark results for `ed25519_batch_verify_{x}`.
+            ed25519_batch_verify_cost: LinearGasCost {
+                base: 24 * GAS_PER_US,
+                per_item: 21 * GAS_PER_US,
+            },
+            // Calculated based on the benchmark results for `ed25519_batch_verify_one_pubkey_{x}`.
+            ed25519_batch_verify_one_pubkey_cost: LinearGasCost {
+                base: 36 * GAS_PER_US,
+                per_item: 10 * GAS_PER_US,
+            },
+            // Estimated as a quarter of `ed25519_verify`, which decompresses the same point
+            ed25519_validate_pubkey_cost: 9 * GAS_PER_US,
+            // just assume the production machines have more than 4 cores, so we can half that
+            bls12_381_aggregate_g1_cost: LinearGasCost {
+                base: 136 * GAS_PER_US / 2,
+                per_item: 24 * GAS_PER_US / 2,
+            },
+            bls12_381_aggregate_g2_cost: LinearGasCost {
+                base: 207 * GAS_PER_US / 2,
+                per_item: 49 * GAS_PER_US / 2,
+            },
+            bls12_381_hash_to_g1_cost: 563 * GAS_PER_US,
+            bls12_381_hash_to_g2_cost: 871 * GAS_PER_US,
+            bls12_381_pairing_equality_cost: LinearGasCost {
+                base: 2112 * GAS_PER_US,
+                per_item: 163 * GAS_PER_US,
+            },
+            // Calculated based on the benchmark results for `hmac_sha256_{n}_bytes`
+            // (~1 us plus ~2 ns per byte).
+            hmac_sha256_cost: LinearGasCost {
+                base: GAS_PER_US,
+                per_item: 2 * GAS_PER_US / 1000,
+            },
+        }
+    }
+}
+
+/// Linear gas cost model where the cost is linear in the number of items.
+///
+/// To calculate it, you sample the cost for a few different amounts of items and fit a line to it.
+/// Let `b` be that line of best fit. Then `base = b(0)` is the y-intercept and
+/// `per_item = b(1) - b(0)` the slope.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct LinearGasCost {
+    /// This is a flat part of the cost, charged once per batch.
+    pub base: u64,
+    /// This is the cost per item in the batch.
+    pub per_item: u64,
+}
+
+impl LinearGasCost {
+    pub fn total_cost(&self, items: u64) -> u64 {
+        self.base + self.per_item * items
+    }
+}
+
+/// Decides which codes the cache is allowed to instantiate, identified by their checksum.
+///
+/// See [`Config::code_policy`] for when this may be used.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodePolicy {
+    /// Only the listed codes are allowed
+    Allow(HashSet<Checksum>),
+    /// All codes except the listed ones are allowed
+    Deny(HashSet<Checksum>),
+}
+
+impl CodePolicy {
+    /// Returns `true` if the code with the given checksum may be instantiated
+    pub fn allows(&self, checksum: &Checksum) -> bool {
+        match self {
+            CodePolicy::Allow(allowed) => allowed.contains(checksum),
+            CodePolicy::Deny(denied) => !denied.contains(checksum),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -136,6 +496,14 @@ pub struct CacheOptions {
     /// Memory limit for instances, in bytes. Use a value that is divisible by the Wasm page size 65536,
     /// e.g. full MiBs.
     pub instance_memory_limit_bytes: Size,
+    /// The maximum number of Wasm modules compiled at the same time by this cache.
+    /// Further compilations block until a running one finished.
+    ///
+    /// Compiling a module is CPU and memory intensive, so nodes that receive many
+    /// `store_code` calls in parallel can use this to bound their resource usage.
+    /// Defaults to `None`, which means unbounded.
+    #[serde(default)]
+    pub max_concurrent_compilations: Option<NonZeroUsize>,
 }
 
 impl CacheOptions {
@@ -150,6 +518,14 @@ impl CacheOptions {
             available_capabilities: available_capabilities.into(),
             memory_cache_size_bytes,
             instance_memory_limit_bytes,
+            max_concurrent_compilations: None,
         }
     }
+
+    /// Limits the number of modules that are compiled concurrently.
+    /// See [`CacheOptions::max_concurrent_compilations`].
+    pub fn with_max_concurrent_compilations(mut self, limit: NonZeroUsize) -> Self {
+        self.max_concurrent_compilations = Some(limit);
+        self
+    }
 }
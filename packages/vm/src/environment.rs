@@ -1,6 +1,7 @@
 //! Internal details to be used by instance.rs only
 use std::borrow::BorrowMut;
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
 use std::ptr::NonNull;
 use std::rc::Rc;
@@ -11,7 +12,9 @@ use wasmer::{AsStoreMut, Instance as WasmerInstance, Memory, MemoryView, Value};
 use wasmer_middlewares::metering::{get_remaining_points, set_remaining_points, MeteringPoints};
 
 use crate::backend::{BackendApi, GasInfo, Querier, Storage};
+use crate::config::{GasCosts, ResponseLimits, ResultLimits, WasmLimits};
 use crate::errors::{VmError, VmResult};
+use crate::wasm_backend::{call_depth_exceeded, reset_call_depth, set_max_call_depth};
 
 /// Keep this as low as necessary to avoid deepy nested errors like this:
 ///
@@ -25,103 +28,17 @@ const MAX_CALL_DEPTH: usize = 2;
 #[derive(Debug)]
 pub enum Never {}
 
-/** gas config data */
-
-#[derive(Clone, PartialEq, Eq, Debug)]
-#[non_exhaustive]
-pub struct GasConfig {
-    /// Gas costs of VM (not Backend) provided functionality
-    /// secp256k1 signature verification cost
-    pub secp256k1_verify_cost: u64,
-    /// secp256k1 public key recovery cost
-    pub secp256k1_recover_pubkey_cost: u64,
-    /// secp256r1 signature verification cost
-    pub secp256r1_verify_cost: u64,
-    /// secp256r1 public key recovery cost
-    pub secp256r1_recover_pubkey_cost: u64,
-    /// ed25519 signature verification cost
-    pub ed25519_verify_cost: u64,
-    /// ed25519 batch signature verification cost
-    pub ed25519_batch_verify_cost: LinearGasCost,
-    /// ed25519 batch signature verification cost (single public key)
-    pub ed25519_batch_verify_one_pubkey_cost: LinearGasCost,
-    /// bls12-381 aggregate cost (g1)
-    pub bls12_381_aggregate_g1_cost: LinearGasCost,
-    /// bls12-381 aggregate cost (g2)
-    pub bls12_381_aggregate_g2_cost: LinearGasCost,
-    /// bls12-381 hash to g1 cost
-    pub bls12_381_hash_to_g1_cost: u64,
-    /// bls12-381 hash to g2 cost
-    pub bls12_381_hash_to_g2_cost: u64,
-    /// bls12-381 pairing equality check cost
-    pub bls12_381_pairing_equality_cost: LinearGasCost,
-}
-
-impl Default for GasConfig {
-    fn default() -> Self {
-        // Target is 10^12 per second (see GAS.md), i.e. 10^6 gas per µ second.
-        const GAS_PER_US: u64 = 1_000_000;
-        Self {
-            // ~96 us in crypto benchmarks
-            secp256k1_verify_cost: 96 * GAS_PER_US,
-            // ~194 us in crypto benchmarks
-            secp256k1_recover_pubkey_cost: 194 * GAS_PER_US,
-            // ~279 us in crypto benchmarks
-            secp256r1_verify_cost: 279 * GAS_PER_US,
-            // ~592 us in crypto benchmarks
-            secp256r1_recover_pubkey_cost: 592 * GAS_PER_US,
-            // ~35 us in crypto benchmarks
-            ed25519_verify_cost: 35 * GAS_PER_US,
-            // Calculated based on the benchmark results for `ed25519_batch_verify_{x}`.
-            ed25519_batch_verify_cost: LinearGasCost {
-                base: 24 * GAS_PER_US,
-                per_item: 21 * GAS_PER_US,
-            },
-            // Calculated based on the benchmark results for `ed25519_batch_verify_one_pubkey_{x}`.
-            ed25519_batch_verify_one_pubkey_cost: LinearGasCost {
-                base: 36 * GAS_PER_US,
-                per_item: 10 * GAS_PER_US,
-            },
-            // just assume the production machines have more than 4 cores, so we can half that
-            bls12_381_aggregate_g1_cost: LinearGasCost {
-                base: 136 * GAS_PER_US / 2,
-                per_item: 24 * GAS_PER_US / 2,
-            },
-            bls12_381_aggregate_g2_cost: LinearGasCost {
-                base: 207 * GAS_PER_US / 2,
-                per_item: 49 * GAS_PER_US / 2,
-            },
-            bls12_381_hash_to_g1_cost: 563 * GAS_PER_US,
-            bls12_381_hash_to_g2_cost: 871 * GAS_PER_US,
-            bls12_381_pairing_equality_cost: LinearGasCost {
-                base: 2112 * GAS_PER_US,
-                per_item: 163 * GAS_PER_US,
-            },
-        }
-    }
-}
-
-/// Linear gas cost model where the cost is linear in the number of items.
-///
-/// To calculate it, you sample the cost for a few different amounts of items and fit a line to it.
-/// Let `b` be that line of best fit. Then `base = b(0)` is the y-intercept and
-/// `per_item = b(1) - b(0)` the slope.
-#[derive(Clone, PartialEq, Eq, Debug)]
-pub struct LinearGasCost {
-    /// This is a flat part of the cost, charged once per batch.
-    base: u64,
-    /// This is the cost per item in the batch.
-    per_item: u64,
-}
+/** context data **/
 
-impl LinearGasCost {
-    pub fn total_cost(&self, items: u64) -> u64 {
-        self.base + self.per_item * items
-    }
+/// The kind of backend a host function (import) calls into. Used to break down
+/// the externally used gas in the gas report.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GasCategory {
+    Storage,
+    Querier,
+    Api,
 }
 
-/** context data **/
-
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
 pub struct GasState {
     /// Gas limit for the computation, including internally and externally used gas.
@@ -130,7 +47,21 @@ pub struct GasState {
     /// Measured in [CosmWasm gas](https://github.com/CosmWasm/cosmwasm/blob/main/docs/GAS.md).
     pub gas_limit: u64,
     /// Tracking the gas used in the Cosmos SDK, in CosmWasm gas units.
+    /// This is the sum of the externally used gas of all categories.
     pub externally_used_gas: u64,
+    /// The part of `externally_used_gas` used by storage operations.
+    pub externally_used_storage_gas: u64,
+    /// The part of `externally_used_gas` used by the querier.
+    pub externally_used_querier_gas: u64,
+    /// The part of `externally_used_gas` used by API calls.
+    pub externally_used_api_gas: u64,
+    /// Gas charged by the host functions (imports) called by the contract, keyed by import name.
+    /// This includes both the cost of the host function and externally used gas.
+    pub per_import: BTreeMap<&'static str, u64>,
+    /// The number of Wasm memory pages the contract grew its memory by.
+    pub pages_grown: u64,
+    /// The gas charged for growing the memory. This is only non-zero if a memory growth cost is set.
+    pub memory_gas: u64,
 }
 
 impl GasState {
@@ -138,6 +69,12 @@ impl GasState {
         Self {
             gas_limit,
             externally_used_gas: 0,
+            externally_used_storage_gas: 0,
+            externally_used_querier_gas: 0,
+            externally_used_api_gas: 0,
+            per_import: BTreeMap::new(),
+            pages_grown: 0,
+            memory_gas: 0,
         }
     }
 }
@@ -165,12 +102,26 @@ pub struct DebugInfo<'a> {
 //                            v                                                 v
 pub type DebugHandlerFn = dyn for<'a, 'b> FnMut(/* msg */ &'a str, DebugInfo<'b>);
 
+/// The growth of the contract's memory during a call into the contract, as reported
+/// to the memory growth per call handler.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MemoryGrowth {
+    /// The size of the memory in pages before the call
+    pub pages_before: u32,
+    /// The size of the memory in pages after the call
+    pub pages_after: u32,
+    /// The maximum number of pages the memory can grow to, or `None` if there is no maximum
+    pub limit_pages: Option<u32>,
+}
+
+pub type MemoryGrowthPerCallHandlerFn = dyn FnMut(MemoryGrowth);
+
 /// A environment that provides access to the ContextData.
 /// The environment is cloneable but clones access the same underlying data.
 pub struct Environment<A, S, Q> {
     pub memory: Option<Memory>,
     pub api: A,
-    pub gas_config: GasConfig,
+    pub gas_costs: GasCosts,
     data: Arc<RwLock<ContextData<S, Q>>>,
 }
 
@@ -183,7 +134,7 @@ impl<A: BackendApi, S: Storage, Q: Querier> Clone for Environment<A, S, Q> {
         Environment {
             memory: None,
             api: self.api.clone(),
-            gas_config: self.gas_config.clone(),
+            gas_costs: self.gas_costs.clone(),
             data: self.data.clone(),
         }
     }
@@ -194,7 +145,7 @@ impl<A: BackendApi, S: Storage, Q: Querier> Environment<A, S, Q> {
         Environment {
             memory: None,
             api,
-            gas_config: GasConfig::default(),
+            gas_costs: GasCosts::default(),
             data: Arc::new(RwLock::new(ContextData::new(gas_limit))),
         }
     }
@@ -212,6 +163,21 @@ impl<A: BackendApi, S: Storage, Q: Querier> Environment<A, S, Q> {
         })
     }
 
+    pub fn set_memory_growth_per_call_handler(
+        &self,
+        handler: Option<Rc<RefCell<MemoryGrowthPerCallHandlerFn>>>,
+    ) {
+        self.with_context_data_mut(|context_data| {
+            context_data.memory_growth_per_call_handler = handler;
+        })
+    }
+
+    pub fn memory_growth_per_call_handler(
+        &self,
+    ) -> Option<Rc<RefCell<MemoryGrowthPerCallHandlerFn>>> {
+        self.with_context_data(|context_data| context_data.memory_growth_per_call_handler.clone())
+    }
+
     fn with_context_data_mut<C, R>(&self, callback: C) -> R
     where
         C: FnOnce(&mut ContextData<S, Q>) -> R,
@@ -275,10 +241,24 @@ impl<A: BackendApi, S: Storage, Q: Querier> Environment<A, S, Q> {
         if args.len() != function_arity {
             return Err(VmError::function_arity_mismatch(function_arity));
         };
-        self.increment_call_depth()?;
+        let call_depth = self.increment_call_depth()?;
+        // Nested calls (e.g. `allocate` called from an import) are covered by the outermost call
+        let pages_before = if call_depth == 1 {
+            // a previous call that trapped may have left the Wasm call depth counter behind
+            self.with_wasmer_instance(|instance| {
+                reset_call_depth(store, instance);
+                Ok(())
+            })?;
+            self.memory_pages(&*store)
+        } else {
+            None
+        };
         let res = func.call(store, args).map_err(|runtime_err| -> VmError {
             self.with_wasmer_instance::<_, Never>(|instance| {
                 let err: VmError = match get_remaining_points(store, instance) {
+                    MeteringPoints::Remaining(_) if call_depth_exceeded(store, instance) => {
+                        VmError::wasm_call_depth_exceeded()
+                    }
                     MeteringPoints::Remaining(_) => VmError::from(runtime_err),
                     MeteringPoints::Exhausted => VmError::gas_depletion(),
                 };
@@ -287,7 +267,25 @@ impl<A: BackendApi, S: Storage, Q: Querier> Environment<A, S, Q> {
             .unwrap_err() // with_wasmer_instance can only succeed if the callback succeeds
         });
         self.decrement_call_depth();
-        res
+        let memory_growth = match pages_before {
+            Some(pages_before) => {
+                let pages_after = self.memory_pages(&*store).unwrap_or(pages_before);
+                if pages_after > pages_before {
+                    if let Some(handler) = self.memory_growth_per_call_handler() {
+                        handler.borrow_mut()(MemoryGrowth {
+                            pages_before,
+                            pages_after,
+                            limit_pages: self.memory_limit_pages(&*store),
+                        });
+                    }
+                }
+                let pages_grown = pages_after.saturating_sub(pages_before);
+                process_memory_growth(self, store, pages_grown)
+            }
+            None => Ok(()),
+        };
+        // errors from the call take precedence
+        res.and_then(|values| memory_growth.map(|_| values))
     }
 
     pub fn call_function0(
@@ -383,6 +381,113 @@ impl<A: BackendApi, S: Storage, Q: Querier> Environment<A, S, Q> {
         })
     }
 
+    /// Sets the maximum number of allocations the VM may request from the contract
+    /// during a single call
+    pub fn set_allocation_limit(&self, new_value: u64) {
+        self.with_context_data_mut(|context_data| {
+            context_data.allocation_limit = new_value;
+        })
+    }
+
+    /// Sets the gas charged per Wasm memory page the contract grows its memory by.
+    /// `None` disables charging memory growth, which is the default.
+    pub fn set_memory_grow_gas_per_page(&self, new_value: Option<u64>) {
+        self.with_context_data_mut(|context_data| {
+            context_data.memory_grow_gas_per_page = new_value;
+        })
+    }
+
+    /// Returns the gas charged per Wasm memory page the contract grows its memory by,
+    /// if memory growth is charged at all.
+    pub fn memory_grow_gas_per_page(&self) -> Option<u64> {
+        self.with_context_data(|context_data| context_data.memory_grow_gas_per_page)
+    }
+
+    /// Sets the maximum nesting depth of Wasm function calls within a call into the contract.
+    /// `None` means unlimited.
+    pub fn set_max_call_depth(&self, store: &mut impl AsStoreMut, max_call_depth: Option<u32>) {
+        self.with_wasmer_instance(|instance| {
+            set_max_call_depth(store, instance, max_call_depth);
+            Ok(())
+        })
+        .expect("Wasmer instance is not set. This is a bug in the lifecycle.")
+    }
+
+    /// Sets the address of the contract running in this environment.
+    /// See [`Instance::set_contract_address`](crate::Instance::set_contract_address).
+    pub fn set_contract_address(&self, new_value: Option<String>) {
+        self.with_context_data_mut(|context_data| {
+            context_data.contract_address = new_value;
+        })
+    }
+
+    /// Returns the address of the contract running in this environment, if known
+    pub fn contract_address(&self) -> Option<String> {
+        self.with_context_data(|context_data| context_data.contract_address.clone())
+    }
+
+    /// Sets the limits the contents of a successful contract response must stay within
+    pub fn set_response_limits(&self, new_value: ResponseLimits) {
+        self.with_context_data_mut(|context_data| {
+            context_data.response_limits = new_value;
+        })
+    }
+
+    pub fn response_limits(&self) -> ResponseLimits {
+        self.with_context_data(|context_data| context_data.response_limits)
+    }
+
+    /// Sets the maximum lengths of the JSON results of the entry points
+    pub fn set_result_limits(&self, new_value: ResultLimits) {
+        self.with_context_data_mut(|context_data| {
+            context_data.result_limits = new_value;
+        })
+    }
+
+    pub fn result_limits(&self) -> ResultLimits {
+        self.with_context_data(|context_data| context_data.result_limits)
+    }
+
+    /// Sets the maximum lengths of the results returned by the `call_*_raw` functions.
+    /// `None` means those results are only limited by the read limits.
+    pub fn set_raw_result_limits(&self, new_value: Option<ResultLimits>) {
+        self.with_context_data_mut(|context_data| {
+            context_data.raw_result_limits = new_value;
+        })
+    }
+
+    pub fn raw_result_limits(&self) -> Option<ResultLimits> {
+        self.with_context_data(|context_data| context_data.raw_result_limits)
+    }
+
+    /// Returns the number of allocations the VM requested from the contract
+    /// since the last reset
+    pub fn allocations(&self) -> u64 {
+        self.with_context_data(|context_data| context_data.allocations)
+    }
+
+    /// Sets the allocation counter to 0. This should be done right before every call
+    /// into the contract.
+    pub fn reset_allocations(&self) {
+        self.with_context_data_mut(|context_data| {
+            context_data.allocations = 0;
+        })
+    }
+
+    /// Increments the allocation counter by 1 and returns the new value.
+    /// Errors if this exceeds the allocation limit.
+    pub fn increment_allocations(&self) -> VmResult<u64> {
+        let (new, limit) = self.with_context_data_mut(|context_data| {
+            let new = context_data.allocations.saturating_add(1);
+            context_data.allocations = new;
+            (new, context_data.allocation_limit)
+        });
+        if new > limit {
+            return Err(VmError::max_allocations_exceeded(limit));
+        }
+        Ok(new)
+    }
+
     /// Returns the remaining gas measured in [CosmWasm gas].
     ///
     /// [CosmWasm gas]: https://github.com/CosmWasm/cosmwasm/blob/main/docs/GAS.md
@@ -436,6 +541,22 @@ impl<A: BackendApi, S: Storage, Q: Querier> Environment<A, S, Q> {
             .view(store)
     }
 
+    /// Returns the current size of the memory in Wasm pages or `None` if the memory is not set.
+    fn memory_pages(&self, store: &impl AsStoreMut) -> Option<u32> {
+        self.memory
+            .as_ref()
+            .map(|memory| memory.view(store).size().0)
+    }
+
+    /// Returns the maximum size of the memory in Wasm pages as seen by Wasmer, or `None`
+    /// if the memory is not set or has no maximum.
+    pub fn memory_limit_pages(&self, store: &impl AsStoreMut) -> Option<u32> {
+        self.memory
+            .as_ref()
+            .and_then(|memory| memory.ty(store).maximum)
+            .map(|pages| pages.0)
+    }
+
     /// Moves owned instances of storage and querier into the env.
     /// Should be followed by exactly one call to move_out when the instance is finished.
     pub fn move_in(&self, storage: S, querier: Q) {
@@ -459,8 +580,21 @@ pub struct ContextData<S, Q> {
     storage: Option<S>,
     storage_readonly: bool,
     call_depth: usize,
+    /// The number of allocations the VM requested from the contract in the current call
+    allocations: u64,
+    allocation_limit: u64,
+    /// Gas charged per memory page grown. `None` if memory growth is free.
+    memory_grow_gas_per_page: Option<u64>,
+    response_limits: ResponseLimits,
+    result_limits: ResultLimits,
+    /// The result limits of the `call_*_raw` functions. `None` if they are not limited.
+    raw_result_limits: Option<ResultLimits>,
+    /// The address of the contract. If set, raw queries of the contract's own storage
+    /// are served from the storage directly.
+    contract_address: Option<String>,
     querier: Option<Q>,
     debug_handler: Option<Rc<RefCell<DebugHandlerFn>>>,
+    memory_growth_per_call_handler: Option<Rc<RefCell<MemoryGrowthPerCallHandlerFn>>>,
     /// A non-owning link to the wasmer instance
     wasmer_instance: Option<NonNull<WasmerInstance>>,
 }
@@ -472,22 +606,77 @@ impl<S: Storage, Q: Querier> ContextData<S, Q> {
             storage: None,
             storage_readonly: true,
             call_depth: 0,
+            allocations: 0,
+            allocation_limit: WasmLimits::default().max_allocations_per_call(),
+            memory_grow_gas_per_page: None,
+            response_limits: ResponseLimits::default(),
+            result_limits: ResultLimits::default(),
+            raw_result_limits: None,
+            contract_address: None,
             querier: None,
             debug_handler: None,
+            memory_growth_per_call_handler: None,
             wasmer_instance: None,
         }
     }
 }
 
+/// Records that the contract grew its memory by `pages_grown` pages and charges gas for it
+/// if a memory growth cost is set.
+pub fn process_memory_growth<A: BackendApi, S: Storage, Q: Querier>(
+    env: &Environment<A, S, Q>,
+    store: &mut impl AsStoreMut,
+    pages_grown: u32,
+) -> VmResult<()> {
+    if pages_grown == 0 {
+        return Ok(());
+    }
+    let pages_grown = u64::from(pages_grown);
+    let cost = env
+        .memory_grow_gas_per_page()
+        .map(|gas_per_page| pages_grown.saturating_mul(gas_per_page))
+        .unwrap_or_default();
+    env.with_gas_state_mut(|gas_state| {
+        gas_state.pages_grown = gas_state.pages_grown.saturating_add(pages_grown);
+        gas_state.memory_gas = gas_state.memory_gas.saturating_add(cost);
+    });
+    if cost == 0 {
+        return Ok(());
+    }
+
+    let gas_left = env.get_gas_left(store);
+    env.set_gas_left(store, gas_left.saturating_sub(cost));
+    if cost > gas_left {
+        Err(VmError::gas_depletion())
+    } else {
+        Ok(())
+    }
+}
+
+/// Charges the gas of a host function call. `import` is the name of the host function
+/// the gas is attributed to in the gas report and `category` the kind of backend its
+/// externally used gas is attributed to.
 pub fn process_gas_info<A: BackendApi, S: Storage, Q: Querier>(
     env: &Environment<A, S, Q>,
     store: &mut impl AsStoreMut,
+    import: &'static str,
+    category: GasCategory,
     info: GasInfo,
 ) -> VmResult<()> {
     let gas_left = env.get_gas_left(store);
 
     let new_limit = env.with_gas_state_mut(|gas_state| {
         gas_state.externally_used_gas += info.externally_used;
+        let category_gas = match category {
+            GasCategory::Storage => &mut gas_state.externally_used_storage_gas,
+            GasCategory::Querier => &mut gas_state.externally_used_querier_gas,
+            GasCategory::Api => &mut gas_state.externally_used_api_gas,
+        };
+        *category_gas += info.externally_used;
+        let import_gas = gas_state.per_import.entry(import).or_default();
+        *import_gas = import_gas
+            .saturating_add(info.externally_used)
+            .saturating_add(info.cost);
         // These lines reduce the amount of gas available to wasmer
         // so it can not consume gas that was consumed externally.
         gas_left
@@ -620,17 +809,53 @@ mod tests {
         assert_eq!(env.get_gas_left(&mut store), 100);
 
         // Consume all the Gas that we allocated
-        process_gas_info(&env, &mut store, GasInfo::with_cost(70)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_cost(70),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 30);
-        process_gas_info(&env, &mut store, GasInfo::with_cost(4)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_cost(4),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 26);
-        process_gas_info(&env, &mut store, GasInfo::with_cost(6)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_cost(6),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 20);
-        process_gas_info(&env, &mut store, GasInfo::with_cost(20)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_cost(20),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 0);
 
         // Using one more unit of gas triggers a failure
-        match process_gas_info(&env, &mut store, GasInfo::with_cost(1)).unwrap_err() {
+        match process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_cost(1),
+        )
+        .unwrap_err()
+        {
             VmError::GasDepletion { .. } => {}
             err => panic!("unexpected error: {err:?}"),
         }
@@ -642,17 +867,53 @@ mod tests {
         assert_eq!(env.get_gas_left(&mut store), 100);
 
         // Consume all the Gas that we allocated
-        process_gas_info(&env, &mut store, GasInfo::with_externally_used(70)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_externally_used(70),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 30);
-        process_gas_info(&env, &mut store, GasInfo::with_externally_used(4)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_externally_used(4),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 26);
-        process_gas_info(&env, &mut store, GasInfo::with_externally_used(6)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_externally_used(6),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 20);
-        process_gas_info(&env, &mut store, GasInfo::with_externally_used(20)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_externally_used(20),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 0);
 
         // Using one more unit of gas triggers a failure
-        match process_gas_info(&env, &mut store, GasInfo::with_externally_used(1)).unwrap_err() {
+        match process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_externally_used(1),
+        )
+        .unwrap_err()
+        {
             VmError::GasDepletion { .. } => {}
             err => panic!("unexpected error: {err:?}"),
         }
@@ -666,26 +927,55 @@ mod tests {
         assert_eq!(gas_state.gas_limit, 100);
         assert_eq!(gas_state.externally_used_gas, 0);
 
-        process_gas_info(&env, &mut store, GasInfo::new(17, 4)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::new(17, 4),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 79);
         let gas_state = env.with_gas_state(|gas_state| gas_state.clone());
         assert_eq!(gas_state.gas_limit, 100);
         assert_eq!(gas_state.externally_used_gas, 4);
 
-        process_gas_info(&env, &mut store, GasInfo::new(9, 0)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::new(9, 0),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 70);
         let gas_state = env.with_gas_state(|gas_state| gas_state.clone());
         assert_eq!(gas_state.gas_limit, 100);
         assert_eq!(gas_state.externally_used_gas, 4);
 
-        process_gas_info(&env, &mut store, GasInfo::new(0, 70)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::new(0, 70),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 0);
         let gas_state = env.with_gas_state(|gas_state| gas_state.clone());
         assert_eq!(gas_state.gas_limit, 100);
         assert_eq!(gas_state.externally_used_gas, 74);
 
         // More cost fail but do not change stats
-        match process_gas_info(&env, &mut store, GasInfo::new(1, 0)).unwrap_err() {
+        match process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::new(1, 0),
+        )
+        .unwrap_err()
+        {
             VmError::GasDepletion { .. } => {}
             err => panic!("unexpected error: {err:?}"),
         }
@@ -695,7 +985,15 @@ mod tests {
         assert_eq!(gas_state.externally_used_gas, 74);
 
         // More externally used fails and changes stats
-        match process_gas_info(&env, &mut store, GasInfo::new(0, 1)).unwrap_err() {
+        match process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::new(0, 1),
+        )
+        .unwrap_err()
+        {
             VmError::GasDepletion { .. } => {}
             err => panic!("unexpected error: {err:?}"),
         }
@@ -705,12 +1003,196 @@ mod tests {
         assert_eq!(gas_state.externally_used_gas, 75);
     }
 
+    #[test]
+    fn process_gas_info_tracks_gas_per_import() {
+        let (env, mut store, _instance) = make_instance(100);
+        let gas_state = env.with_gas_state(|gas_state| gas_state.clone());
+        assert!(gas_state.per_import.is_empty());
+
+        process_gas_info(
+            &env,
+            &mut store,
+            "db_read",
+            GasCategory::Storage,
+            GasInfo::new(17, 4),
+        )
+        .unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "secp256k1_verify",
+            GasCategory::Api,
+            GasInfo::with_cost(30),
+        )
+        .unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "db_read",
+            GasCategory::Storage,
+            GasInfo::with_externally_used(2),
+        )
+        .unwrap();
+        let gas_state = env.with_gas_state(|gas_state| gas_state.clone());
+        assert_eq!(
+            gas_state.per_import,
+            BTreeMap::from([("db_read", 23), ("secp256k1_verify", 30)])
+        );
+        assert_eq!(env.get_gas_left(&mut store), 100 - 23 - 30);
+    }
+
+    #[test]
+    fn process_gas_info_tracks_externally_used_gas_per_category() {
+        let (env, mut store, _instance) = make_instance(1000);
+
+        process_gas_info(
+            &env,
+            &mut store,
+            "db_read",
+            GasCategory::Storage,
+            GasInfo::new(3, 10),
+        )
+        .unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "db_write",
+            GasCategory::Storage,
+            GasInfo::with_externally_used(5),
+        )
+        .unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "query_chain",
+            GasCategory::Querier,
+            GasInfo::with_externally_used(70),
+        )
+        .unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "addr_validate",
+            GasCategory::Api,
+            GasInfo::with_externally_used(8),
+        )
+        .unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "secp256k1_verify",
+            GasCategory::Api,
+            GasInfo::with_cost(30),
+        )
+        .unwrap();
+
+        let gas_state = env.with_gas_state(|gas_state| gas_state.clone());
+        assert_eq!(gas_state.externally_used_storage_gas, 15);
+        assert_eq!(gas_state.externally_used_querier_gas, 70);
+        assert_eq!(gas_state.externally_used_api_gas, 8);
+        assert_eq!(gas_state.externally_used_gas, 15 + 70 + 8);
+        assert_eq!(env.get_gas_left(&mut store), 1000 - 3 - 15 - 70 - 8 - 30);
+    }
+
+    #[test]
+    fn response_limits_works() {
+        let (env, _store, _instance) = make_instance(100);
+        assert_eq!(env.response_limits(), ResponseLimits::default());
+
+        let limits = ResponseLimits {
+            max_events: 1,
+            max_attributes_per_event: 2,
+            max_submessages: 3,
+            max_data_bytes: 4,
+        };
+        env.set_response_limits(limits);
+        assert_eq!(env.response_limits(), limits);
+    }
+
+    #[test]
+    fn result_limits_works() {
+        let (env, _store, _instance) = make_instance(100);
+        assert_eq!(env.result_limits(), ResultLimits::default());
+
+        let limits = ResultLimits {
+            query: 1,
+            ibc_packet_receive: 2,
+            ..ResultLimits::default()
+        };
+        env.set_result_limits(limits);
+        assert_eq!(env.result_limits(), limits);
+    }
+
+    #[test]
+    fn raw_result_limits_works() {
+        let (env, _store, _instance) = make_instance(100);
+        assert_eq!(env.raw_result_limits(), None);
+
+        let limits = ResultLimits {
+            execute: 1,
+            ..ResultLimits::default()
+        };
+        env.set_raw_result_limits(Some(limits));
+        assert_eq!(env.raw_result_limits(), Some(limits));
+        // the limits of the typed calls are not affected
+        assert_eq!(env.result_limits(), ResultLimits::default());
+
+        env.set_raw_result_limits(None);
+        assert_eq!(env.raw_result_limits(), None);
+    }
+
+    #[test]
+    fn contract_address_works() {
+        let (env, _store, _instance) = make_instance(100);
+        assert_eq!(env.contract_address(), None);
+
+        env.set_contract_address(Some("contract".to_string()));
+        assert_eq!(env.contract_address().as_deref(), Some("contract"));
+
+        env.set_contract_address(None);
+        assert_eq!(env.contract_address(), None);
+    }
+
+    #[test]
+    fn process_memory_growth_works() {
+        let (env, mut store, _instance) = make_instance(100);
+
+        // disabled by default, only pages are counted
+        assert_eq!(env.memory_grow_gas_per_page(), None);
+        process_memory_growth(&env, &mut store, 3).unwrap();
+        let gas_state = env.with_gas_state(|gas_state| gas_state.clone());
+        assert_eq!(gas_state.pages_grown, 3);
+        assert_eq!(gas_state.memory_gas, 0);
+        assert_eq!(env.get_gas_left(&mut store), 100);
+
+        env.set_memory_grow_gas_per_page(Some(20));
+        process_memory_growth(&env, &mut store, 2).unwrap();
+        process_memory_growth(&env, &mut store, 0).unwrap();
+        let gas_state = env.with_gas_state(|gas_state| gas_state.clone());
+        assert_eq!(gas_state.pages_grown, 5);
+        assert_eq!(gas_state.memory_gas, 40);
+        assert_eq!(env.get_gas_left(&mut store), 60);
+
+        // exceeding the gas limit
+        match process_memory_growth(&env, &mut store, 4).unwrap_err() {
+            VmError::GasDepletion { .. } => {}
+            err => panic!("unexpected error: {err:?}"),
+        }
+        assert_eq!(env.get_gas_left(&mut store), 0);
+    }
+
     #[test]
     fn process_gas_info_zeros_gas_left_when_exceeded() {
         // with_externally_used
         {
             let (env, mut store, _instance) = make_instance(100);
-            let result = process_gas_info(&env, &mut store, GasInfo::with_externally_used(120));
+            let result = process_gas_info(
+                &env,
+                &mut store,
+                "test",
+                GasCategory::Api,
+                GasInfo::with_externally_used(120),
+            );
             match result.unwrap_err() {
                 VmError::GasDepletion { .. } => {}
                 err => panic!("unexpected error: {err:?}"),
@@ -724,7 +1206,13 @@ mod tests {
         // with_cost
         {
             let (env, mut store, _instance) = make_instance(100);
-            let result = process_gas_info(&env, &mut store, GasInfo::with_cost(120));
+            let result = process_gas_info(
+                &env,
+                &mut store,
+                "test",
+                GasCategory::Api,
+                GasInfo::with_cost(120),
+            );
             match result.unwrap_err() {
                 VmError::GasDepletion { .. } => {}
                 err => panic!("unexpected error: {err:?}"),
@@ -742,22 +1230,58 @@ mod tests {
         assert_eq!(env.get_gas_left(&mut store), 100);
 
         // Some gas was consumed externally
-        process_gas_info(&env, &mut store, GasInfo::with_externally_used(50)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_externally_used(50),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 50);
-        process_gas_info(&env, &mut store, GasInfo::with_externally_used(4)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_externally_used(4),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 46);
 
         // Consume 20 gas directly in wasmer
         env.decrease_gas_left(&mut store, 20).unwrap();
         assert_eq!(env.get_gas_left(&mut store), 26);
 
-        process_gas_info(&env, &mut store, GasInfo::with_externally_used(6)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_externally_used(6),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 20);
-        process_gas_info(&env, &mut store, GasInfo::with_externally_used(20)).unwrap();
+        process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_externally_used(20),
+        )
+        .unwrap();
         assert_eq!(env.get_gas_left(&mut store), 0);
 
         // Using one more unit of gas triggers a failure
-        match process_gas_info(&env, &mut store, GasInfo::with_externally_used(1)).unwrap_err() {
+        match process_gas_info(
+            &env,
+            &mut store,
+            "test",
+            GasCategory::Api,
+            GasInfo::with_externally_used(1),
+        )
+        .unwrap_err()
+        {
             VmError::GasDepletion { .. } => {}
             err => panic!("unexpected error: {err:?}"),
         }
@@ -789,6 +1313,38 @@ mod tests {
         assert!(env.is_storage_readonly());
     }
 
+    #[test]
+    fn increment_allocations_works() {
+        let (env, _store, _instance) = make_instance(TESTING_GAS_LIMIT);
+        leave_default_data(&env);
+
+        assert_eq!(env.allocations(), 0);
+        assert_eq!(env.increment_allocations().unwrap(), 1);
+        assert_eq!(env.increment_allocations().unwrap(), 2);
+        assert_eq!(env.allocations(), 2);
+
+        env.reset_allocations();
+        assert_eq!(env.allocations(), 0);
+    }
+
+    #[test]
+    fn increment_allocations_errors_when_limit_exceeded() {
+        let (env, _store, _instance) = make_instance(TESTING_GAS_LIMIT);
+        leave_default_data(&env);
+
+        env.set_allocation_limit(2);
+        env.increment_allocations().unwrap();
+        env.increment_allocations().unwrap();
+        match env.increment_allocations().unwrap_err() {
+            VmError::MaxAllocationsExceeded { limit, .. } => assert_eq!(limit, 2),
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        // a new call starts from 0 again
+        env.reset_allocations();
+        env.increment_allocations().unwrap();
+    }
+
     #[test]
     fn call_function_works() {
         let (env, mut store, _instance) = make_instance(TESTING_GAS_LIMIT);
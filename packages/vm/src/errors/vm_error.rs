@@ -3,6 +3,7 @@ use std::fmt::{Debug, Display};
 use thiserror::Error;
 
 use cosmwasm_crypto::CryptoError;
+use cosmwasm_std::Checksum;
 
 use super::communication_error::CommunicationError;
 use crate::backend::BackendError;
@@ -21,6 +22,9 @@ pub enum VmError {
         source: CommunicationError,
         backtrace: BT,
     },
+    /// The code is not allowed to run by the [`CodePolicy`](crate::CodePolicy) of the cache
+    #[error("Code {checksum} is blocked by the code policy of this node")]
+    CodeBlocked { checksum: Checksum, backtrace: BT },
     #[error("Error compiling Wasm: {msg}")]
     CompileErr { msg: String, backtrace: BT },
     #[error("Couldn't convert from {} to {}. Input: {}", from_type, to_type, input)]
@@ -94,6 +98,16 @@ pub enum VmError {
         contract_method_arity: usize,
         backtrace: BT,
     },
+    #[error("Maximum number of allocations per call exceeded. Limit: {}", limit)]
+    MaxAllocationsExceeded { limit: u64, backtrace: BT },
+    #[error("Contract result exceeds the limit for {limit}. Got: {actual}; limit: {max}")]
+    ResultLimitExceeded {
+        /// the name of the limit that was hit, e.g. `max_events_per_response`
+        limit: String,
+        actual: usize,
+        max: usize,
+        backtrace: BT,
+    },
 }
 
 impl VmError {
@@ -118,6 +132,13 @@ impl VmError {
         }
     }
 
+    pub(crate) fn code_blocked(checksum: Checksum) -> Self {
+        VmError::CodeBlocked {
+            checksum,
+            backtrace: BT::capture(),
+        }
+    }
+
     pub(crate) fn compile_err(msg: impl Into<String>) -> Self {
         VmError::CompileErr {
             msg: msg.into(),
@@ -225,6 +246,12 @@ impl VmError {
         }
     }
 
+    /// The contract exceeded the maximum nesting depth of Wasm function calls
+    /// (see [`WasmLimits::max_call_depth`](crate::WasmLimits::max_call_depth)).
+    pub(crate) fn wasm_call_depth_exceeded() -> Self {
+        VmError::runtime_err("Maximum Wasm call depth exceeded")
+    }
+
     pub(crate) fn static_validation_err(msg: impl Into<String>) -> Self {
         VmError::StaticValidationErr {
             msg: msg.into(),
@@ -257,6 +284,26 @@ impl VmError {
             backtrace: BT::capture(),
         }
     }
+
+    pub(crate) fn max_allocations_exceeded(limit: u64) -> Self {
+        VmError::MaxAllocationsExceeded {
+            limit,
+            backtrace: BT::capture(),
+        }
+    }
+
+    pub(crate) fn result_limit_exceeded(
+        limit: impl Into<String>,
+        actual: usize,
+        max: usize,
+    ) -> Self {
+        VmError::ResultLimitExceeded {
+            limit: limit.into(),
+            actual,
+            max,
+            backtrace: BT::capture(),
+        }
+    }
 }
 
 impl_from_err!(CommunicationError, VmError, VmError::CommunicationErr);
@@ -366,6 +413,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn code_blocked_works() {
+        let checksum = Checksum::generate(b"wasm");
+        let error = VmError::code_blocked(checksum);
+        assert_eq!(
+            error.to_string(),
+            format!("Code {checksum} is blocked by the code policy of this node")
+        );
+        match error {
+            VmError::CodeBlocked { checksum: c, .. } => assert_eq!(c, checksum),
+            e => panic!("Unexpected error: {e:?}"),
+        }
+    }
+
     #[test]
     fn compile_err_works() {
         let error = VmError::compile_err("something went wrong");
@@ -508,6 +569,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn wasm_call_depth_exceeded_works() {
+        let error = VmError::wasm_call_depth_exceeded();
+        match error {
+            VmError::RuntimeErr { msg, .. } => assert_eq!(msg, "Maximum Wasm call depth exceeded"),
+            e => panic!("Unexpected error: {e:?}"),
+        }
+    }
+
     #[test]
     fn static_validation_err_works() {
         let error = VmError::static_validation_err("export xy missing");
@@ -534,4 +604,32 @@ mod tests {
             e => panic!("Unexpected error: {e:?}"),
         }
     }
+
+    #[test]
+    fn max_allocations_exceeded_works() {
+        let error = VmError::max_allocations_exceeded(42);
+        match error {
+            VmError::MaxAllocationsExceeded { limit, .. } => assert_eq!(limit, 42),
+            e => panic!("Unexpected error: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn result_limit_exceeded_works() {
+        let error = VmError::result_limit_exceeded("max_events_per_response", 12, 10);
+        assert_eq!(
+            error.to_string(),
+            "Contract result exceeds the limit for max_events_per_response. Got: 12; limit: 10"
+        );
+        match error {
+            VmError::ResultLimitExceeded {
+                limit, actual, max, ..
+            } => {
+                assert_eq!(limit, "max_events_per_response");
+                assert_eq!(actual, 12);
+                assert_eq!(max, 10);
+            }
+            e => panic!("Unexpected error: {e:?}"),
+        }
+    }
 }
@@ -5,8 +5,9 @@ use std::marker::PhantomData;
 use cosmwasm_core::{BLS12_381_G1_POINT_LEN, BLS12_381_G2_POINT_LEN};
 use cosmwasm_crypto::{
     bls12_381_aggregate_g1, bls12_381_aggregate_g2, bls12_381_hash_to_g1, bls12_381_hash_to_g2,
-    bls12_381_pairing_equality, ed25519_batch_verify, ed25519_verify, secp256k1_recover_pubkey,
-    secp256k1_verify, secp256r1_recover_pubkey, secp256r1_verify, CryptoError, HashFunction,
+    bls12_381_pairing_equality, ed25519_batch_verify, ed25519_validate_pubkey, ed25519_verify,
+    hmac_sha256, secp256k1_recover_pubkey, secp256k1_verify, secp256r1_recover_pubkey,
+    secp256r1_verify, CryptoError, HashFunction,
 };
 use cosmwasm_crypto::{
     ECDSA_PUBKEY_MAX_LEN, ECDSA_SIGNATURE_LEN, EDDSA_PUBKEY_LEN, MESSAGE_HASH_MAX_LEN,
@@ -15,11 +16,13 @@ use rand_core::OsRng;
 
 #[cfg(feature = "iterator")]
 use cosmwasm_std::Order;
+use cosmwasm_std::{Binary, ContractResult, SystemError, SystemResult};
+use serde::Deserialize;
 use wasmer::{AsStoreMut, FunctionEnvMut};
 
-use crate::backend::{BackendApi, BackendError, Querier, Storage};
+use crate::backend::{BackendApi, BackendError, BackendResult, Querier, Storage};
 use crate::conversion::{ref_to_u32, to_u32};
-use crate::environment::{process_gas_info, DebugInfo, Environment};
+use crate::environment::{process_gas_info, DebugInfo, Environment, GasCategory};
 use crate::errors::{CommunicationError, VmError, VmResult};
 #[cfg(feature = "iterator")]
 use crate::memory::maybe_read_region;
@@ -45,6 +48,9 @@ const MAX_LENGTH_CANONICAL_ADDRESS: usize = 64;
 /// is 90 characters and we're adding some safety margin around that for other formats.
 const MAX_LENGTH_HUMAN_ADDRESS: usize = 256;
 const MAX_LENGTH_QUERY_CHAIN_REQUEST: usize = 64 * KI;
+/// Max number of sub-queries in a `QueryRequest::Multi`.
+/// This limits the number of querier calls a contract can trigger with a single import call.
+const MAX_COUNT_QUERY_MULTI: usize = 64;
 /// Length of a serialized Ed25519  signature
 const MAX_LENGTH_ED25519_SIGNATURE: usize = 64;
 /// Max length of a Ed25519 message in bytes.
@@ -55,6 +61,11 @@ const MAX_LENGTH_ED25519_MESSAGE: usize = 128 * 1024;
 /// This is an arbitrary value, for performance / memory constraints. If you need to batch-verify a
 /// larger number of signatures, let us know.
 const MAX_COUNT_ED25519_BATCH: usize = 256;
+/// Max length of an HMAC-SHA256 key in bytes.
+/// Keys longer than the block size are hashed first, so there is no benefit in allowing huge keys.
+const MAX_LENGTH_HMAC_SHA256_KEY: usize = KI;
+/// Max length of the data authenticated by HMAC-SHA256 in bytes.
+const MAX_LENGTH_HMAC_SHA256_DATA: usize = 128 * KI;
 
 /// Max length for a debug message
 const MAX_LENGTH_DEBUG: usize = 2 * MI;
@@ -70,6 +81,10 @@ const MAX_LENGTH_ABORT: usize = 2 * MI;
 // through the env.
 
 /// Reads a storage entry from the VM's storage into Wasm memory
+///
+/// Returns 0 (a null pointer) if the key does not exist. An existing key always results in a
+/// pointer to a region, even if the value is empty. This way the contract can
+/// differentiate between the two cases.
 pub fn do_db_read<A: BackendApi + 'static, S: Storage + 'static, Q: Querier + 'static>(
     mut env: FunctionEnvMut<Environment<A, S, Q>>,
     key_ptr: u32,
@@ -79,7 +94,7 @@ pub fn do_db_read<A: BackendApi + 'static, S: Storage + 'static, Q: Querier + 's
     let key = read_region(&data.memory(&store), key_ptr, MAX_LENGTH_DB_KEY)?;
 
     let (result, gas_info) = data.with_storage_from_context::<_, _>(|store| Ok(store.get(&key)))?;
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(data, &mut store, "db_read", GasCategory::Storage, gas_info)?;
     let value = result?;
 
     let out_data = match value {
@@ -123,7 +138,7 @@ pub fn do_db_write<A: BackendApi + 'static, S: Storage + 'static, Q: Querier + '
 
     let (result, gas_info) =
         data.with_storage_from_context::<_, _>(|store| Ok(store.set(&key, &value)))?;
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(data, &mut store, "db_write", GasCategory::Storage, gas_info)?;
     result?;
 
     Ok(())
@@ -143,7 +158,13 @@ pub fn do_db_remove<A: BackendApi + 'static, S: Storage + 'static, Q: Querier +
 
     let (result, gas_info) =
         data.with_storage_from_context::<_, _>(|store| Ok(store.remove(&key)))?;
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(
+        data,
+        &mut store,
+        "db_remove",
+        GasCategory::Storage,
+        gas_info,
+    )?;
     result?;
 
     Ok(())
@@ -166,7 +187,13 @@ pub fn do_addr_validate<A: BackendApi + 'static, S: Storage + 'static, Q: Querie
     };
 
     let (result, gas_info) = data.api.addr_validate(&source_string);
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(
+        data,
+        &mut store,
+        "addr_validate",
+        GasCategory::Api,
+        gas_info,
+    )?;
     match result {
         Ok(()) => Ok(0),
         Err(BackendError::UserErr { msg, .. }) => {
@@ -194,7 +221,13 @@ pub fn do_addr_canonicalize<A: BackendApi + 'static, S: Storage + 'static, Q: Qu
     };
 
     let (result, gas_info) = data.api.addr_canonicalize(&source_string);
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(
+        data,
+        &mut store,
+        "addr_canonicalize",
+        GasCategory::Api,
+        gas_info,
+    )?;
     match result {
         Ok(canonical) => {
             write_region(&data.memory(&store), destination_ptr, canonical.as_slice())?;
@@ -221,7 +254,13 @@ pub fn do_addr_humanize<A: BackendApi + 'static, S: Storage + 'static, Q: Querie
     )?;
 
     let (result, gas_info) = data.api.addr_humanize(&canonical);
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(
+        data,
+        &mut store,
+        "addr_humanize",
+        GasCategory::Api,
+        gas_info,
+    )?;
     match result {
         Ok(human) => {
             write_region(&data.memory(&store), destination_ptr, human.as_bytes())?;
@@ -277,11 +316,17 @@ pub fn do_bls12_381_aggregate_g1<
 
     let estimated_point_count = (g1s.len() / BLS12_381_G1_POINT_LEN) as u64;
     let gas_info = GasInfo::with_cost(
-        data.gas_config
+        data.gas_costs
             .bls12_381_aggregate_g1_cost
             .total_cost(estimated_point_count),
     );
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(
+        data,
+        &mut store,
+        "bls12_381_aggregate_g1",
+        GasCategory::Api,
+        gas_info,
+    )?;
 
     let code = match bls12_381_aggregate_g1(&g1s) {
         Ok(point) => {
@@ -323,11 +368,17 @@ pub fn do_bls12_381_aggregate_g2<
 
     let estimated_point_count = (g2s.len() / BLS12_381_G2_POINT_LEN) as u64;
     let gas_info = GasInfo::with_cost(
-        data.gas_config
+        data.gas_costs
             .bls12_381_aggregate_g2_cost
             .total_cost(estimated_point_count),
     );
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(
+        data,
+        &mut store,
+        "bls12_381_aggregate_g2",
+        GasCategory::Api,
+        gas_info,
+    )?;
 
     let code = match bls12_381_aggregate_g2(&g2s) {
         Ok(point) => {
@@ -380,11 +431,17 @@ pub fn do_bls12_381_pairing_equality<
     let estimated_k = estimated_n + 1;
 
     let gas_info = GasInfo::with_cost(
-        data.gas_config
+        data.gas_costs
             .bls12_381_pairing_equality_cost
             .total_cost(estimated_k),
     );
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(
+        data,
+        &mut store,
+        "bls12_381_pairing_equality",
+        GasCategory::Api,
+        gas_info,
+    )?;
 
     let code = match bls12_381_pairing_equality(&ps, &qs, &r, &s) {
         Ok(true) => BLS12_381_VALID_PAIRING,
@@ -424,8 +481,14 @@ pub fn do_bls12_381_hash_to_g1<
     let msg = read_region(&memory, msg_ptr, BLS12_381_MAX_MESSAGE_SIZE)?;
     let dst = read_region(&memory, dst_ptr, BLS12_381_MAX_DST_SIZE)?;
 
-    let gas_info = GasInfo::with_cost(data.gas_config.bls12_381_hash_to_g1_cost);
-    process_gas_info(data, &mut store, gas_info)?;
+    let gas_info = GasInfo::with_cost(data.gas_costs.bls12_381_hash_to_g1_cost);
+    process_gas_info(
+        data,
+        &mut store,
+        "bls12_381_hash_to_g1",
+        GasCategory::Api,
+        gas_info,
+    )?;
 
     let hash_function = match HashFunction::from_u32(hash_function) {
         Ok(func) => func,
@@ -456,8 +519,14 @@ pub fn do_bls12_381_hash_to_g2<
     let msg = read_region(&memory, msg_ptr, BLS12_381_MAX_MESSAGE_SIZE)?;
     let dst = read_region(&memory, dst_ptr, BLS12_381_MAX_DST_SIZE)?;
 
-    let gas_info = GasInfo::with_cost(data.gas_config.bls12_381_hash_to_g2_cost);
-    process_gas_info(data, &mut store, gas_info)?;
+    let gas_info = GasInfo::with_cost(data.gas_costs.bls12_381_hash_to_g2_cost);
+    process_gas_info(
+        data,
+        &mut store,
+        "bls12_381_hash_to_g2",
+        GasCategory::Api,
+        gas_info,
+    )?;
 
     let hash_function = match HashFunction::from_u32(hash_function) {
         Ok(func) => func,
@@ -483,8 +552,14 @@ pub fn do_secp256k1_verify<A: BackendApi + 'static, S: Storage + 'static, Q: Que
     let signature = read_region(&data.memory(&store), signature_ptr, ECDSA_SIGNATURE_LEN)?;
     let pubkey = read_region(&data.memory(&store), pubkey_ptr, ECDSA_PUBKEY_MAX_LEN)?;
 
-    let gas_info = GasInfo::with_cost(data.gas_config.secp256k1_verify_cost);
-    process_gas_info(data, &mut store, gas_info)?;
+    let gas_info = GasInfo::with_cost(data.gas_costs.secp256k1_verify_cost);
+    process_gas_info(
+        data,
+        &mut store,
+        "secp256k1_verify",
+        GasCategory::Api,
+        gas_info,
+    )?;
     let result = secp256k1_verify(&hash, &signature, &pubkey);
     let code = match result {
         Ok(valid) => {
@@ -531,8 +606,14 @@ pub fn do_secp256k1_recover_pubkey<
         Err(_) => return Ok((CryptoError::invalid_recovery_param().code() as u64) << 32),
     };
 
-    let gas_info = GasInfo::with_cost(data.gas_config.secp256k1_recover_pubkey_cost);
-    process_gas_info(data, &mut store, gas_info)?;
+    let gas_info = GasInfo::with_cost(data.gas_costs.secp256k1_recover_pubkey_cost);
+    process_gas_info(
+        data,
+        &mut store,
+        "secp256k1_recover_pubkey",
+        GasCategory::Api,
+        gas_info,
+    )?;
     let result = secp256k1_recover_pubkey(&hash, &signature, recover_param);
     match result {
         Ok(pubkey) => {
@@ -574,8 +655,14 @@ pub fn do_secp256r1_verify<A: BackendApi + 'static, S: Storage + 'static, Q: Que
     let signature = read_region(&data.memory(&store), signature_ptr, ECDSA_SIGNATURE_LEN)?;
     let pubkey = read_region(&data.memory(&store), pubkey_ptr, ECDSA_PUBKEY_MAX_LEN)?;
 
-    let gas_info = GasInfo::with_cost(data.gas_config.secp256r1_verify_cost);
-    process_gas_info(data, &mut store, gas_info)?;
+    let gas_info = GasInfo::with_cost(data.gas_costs.secp256r1_verify_cost);
+    process_gas_info(
+        data,
+        &mut store,
+        "secp256r1_verify",
+        GasCategory::Api,
+        gas_info,
+    )?;
     let result = secp256r1_verify(&hash, &signature, &pubkey);
     let code = match result {
         Ok(valid) => {
@@ -622,8 +709,14 @@ pub fn do_secp256r1_recover_pubkey<
         Err(_) => return Ok((CryptoError::invalid_recovery_param().code() as u64) << 32),
     };
 
-    let gas_info = GasInfo::with_cost(data.gas_config.secp256r1_recover_pubkey_cost);
-    process_gas_info(data, &mut store, gas_info)?;
+    let gas_info = GasInfo::with_cost(data.gas_costs.secp256r1_recover_pubkey_cost);
+    process_gas_info(
+        data,
+        &mut store,
+        "secp256r1_recover_pubkey",
+        GasCategory::Api,
+        gas_info,
+    )?;
     let result = secp256r1_recover_pubkey(&hash, &signature, recover_param);
     match result {
         Ok(pubkey) => {
@@ -673,8 +766,14 @@ pub fn do_ed25519_verify<A: BackendApi + 'static, S: Storage + 'static, Q: Queri
     )?;
     let pubkey = read_region(&data.memory(&store), pubkey_ptr, EDDSA_PUBKEY_LEN)?;
 
-    let gas_info = GasInfo::with_cost(data.gas_config.ed25519_verify_cost);
-    process_gas_info(data, &mut store, gas_info)?;
+    let gas_info = GasInfo::with_cost(data.gas_costs.ed25519_verify_cost);
+    process_gas_info(
+        data,
+        &mut store,
+        "ed25519_verify",
+        GasCategory::Api,
+        gas_info,
+    )?;
     let result = ed25519_verify(&message, &signature, &pubkey);
     let code = match result {
         Ok(valid) => {
@@ -735,12 +834,18 @@ pub fn do_ed25519_batch_verify<
     let public_keys = decode_sections(&public_keys)?;
 
     let gas_cost = if public_keys.len() == 1 {
-        &data.gas_config.ed25519_batch_verify_one_pubkey_cost
+        &data.gas_costs.ed25519_batch_verify_one_pubkey_cost
     } else {
-        &data.gas_config.ed25519_batch_verify_cost
+        &data.gas_costs.ed25519_batch_verify_cost
     };
     let gas_info = GasInfo::with_cost(gas_cost.total_cost(signatures.len() as u64));
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(
+        data,
+        &mut store,
+        "ed25519_batch_verify",
+        GasCategory::Api,
+        gas_info,
+    )?;
     let result = ed25519_batch_verify(&mut OsRng, &messages, &signatures, &public_keys);
     let code = match result {
         Ok(valid) => {
@@ -768,6 +873,70 @@ pub fn do_ed25519_batch_verify<
     Ok(code)
 }
 
+/// Return code (error code) for a valid ed25519 public key
+const ED25519_VALIDATE_PUBKEY_CODE_VALID: u32 = 0;
+
+/// Return code (error code) for an invalid ed25519 public key
+const ED25519_VALIDATE_PUBKEY_CODE_INVALID: u32 = 1;
+
+pub fn do_ed25519_validate_pubkey<
+    A: BackendApi + 'static,
+    S: Storage + 'static,
+    Q: Querier + 'static,
+>(
+    mut env: FunctionEnvMut<Environment<A, S, Q>>,
+    pubkey_ptr: u32,
+) -> VmResult<u32> {
+    let (data, mut store) = env.data_and_store_mut();
+
+    let pubkey = read_region(&data.memory(&store), pubkey_ptr, EDDSA_PUBKEY_LEN)?;
+
+    let gas_info = GasInfo::with_cost(data.gas_costs.ed25519_validate_pubkey_cost);
+    process_gas_info(
+        data,
+        &mut store,
+        "ed25519_validate_pubkey",
+        GasCategory::Api,
+        gas_info,
+    )?;
+    // All errors mean the key is invalid, so there are no error codes here
+    let code = match ed25519_validate_pubkey(&pubkey) {
+        Ok(()) => ED25519_VALIDATE_PUBKEY_CODE_VALID,
+        Err(_) => ED25519_VALIDATE_PUBKEY_CODE_INVALID,
+    };
+    Ok(code)
+}
+
+/// Return code (error code) for a successful HMAC-SHA256 computation
+const HMAC_SHA256_SUCCESS: u32 = 0;
+
+pub fn do_hmac_sha256<A: BackendApi + 'static, S: Storage + 'static, Q: Querier + 'static>(
+    mut env: FunctionEnvMut<Environment<A, S, Q>>,
+    key_ptr: u32,
+    data_ptr: u32,
+    out_ptr: u32,
+) -> VmResult<u32> {
+    let (data, mut store) = env.data_and_store_mut();
+    let memory = data.memory(&store);
+
+    let key = read_region(&memory, key_ptr, MAX_LENGTH_HMAC_SHA256_KEY)?;
+    let message = read_region(&memory, data_ptr, MAX_LENGTH_HMAC_SHA256_DATA)?;
+
+    let gas_info = GasInfo::with_cost(
+        data.gas_costs
+            .hmac_sha256_cost
+            .total_cost((key.len() + message.len()) as u64),
+    );
+    process_gas_info(data, &mut store, "hmac_sha256", GasCategory::Api, gas_info)?;
+
+    let mac = hmac_sha256(&key, &message);
+
+    let memory = data.memory(&store);
+    write_region(&memory, out_ptr, &mac)?;
+
+    Ok(HMAC_SHA256_SUCCESS)
+}
+
 /// Prints a debug message to console.
 /// This does not charge gas, so debug printing should be disabled when used in a blockchain module.
 pub fn do_debug<A: BackendApi + 'static, S: Storage + 'static, Q: Querier + 'static>(
@@ -815,15 +984,159 @@ pub fn do_query_chain<A: BackendApi + 'static, S: Storage + 'static, Q: Querier
         MAX_LENGTH_QUERY_CHAIN_REQUEST,
     )?;
 
+    if let Some(key) = own_raw_query_key(data, &request) {
+        // The contract queries its own storage, which we can serve without a querier roundtrip.
+        // This is a storage read, so the key is limited and the gas is accounted like in `db_read`.
+        if key.len() > MAX_LENGTH_DB_KEY {
+            return Err(
+                CommunicationError::region_length_too_big(key.len(), MAX_LENGTH_DB_KEY).into(),
+            );
+        }
+        let (result, gas_info) =
+            data.with_storage_from_context::<_, _>(|storage| Ok(storage.get(&key)))?;
+        process_gas_info(
+            data,
+            &mut store,
+            "query_chain",
+            GasCategory::Storage,
+            gas_info,
+        )?;
+        let value = result?.unwrap_or_default();
+        let result: SystemResult<ContractResult<Binary>> =
+            SystemResult::Ok(ContractResult::Ok(Binary::new(value)));
+        let serialized = to_vec(&result)?;
+        return write_to_contract(data, &mut store, &serialized);
+    }
+
     let gas_remaining = data.get_gas_left(&mut store);
     let (result, gas_info) = data.with_querier_from_context::<_, _>(|querier| {
-        Ok(querier.query_raw(&request, gas_remaining))
+        let result = match serde_json::from_slice(&request) {
+            Ok(MultiQueryRequest::Multi(requests)) => {
+                query_multi(querier, &request, &requests, gas_remaining)
+            }
+            Err(_) => querier.query_raw(&request, gas_remaining),
+        };
+        Ok(result)
     })?;
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(
+        data,
+        &mut store,
+        "query_chain",
+        GasCategory::Querier,
+        gas_info,
+    )?;
     let serialized = to_vec(&result?)?;
     write_to_contract(data, &mut store, &serialized)
 }
 
+/// The start of a `QueryRequest::Wasm(WasmQuery::Raw { .. })` as serialized by cosmwasm-std.
+const RAW_QUERY_PREFIX: &[u8] = br#"{"wasm":{"raw":{"#;
+
+/// The VM's view on `QueryRequest::Wasm(WasmQuery::Raw { .. })`.
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RawQueryRequest {
+    Wasm(RawWasmQuery),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RawWasmQuery {
+    Raw { contract_addr: String, key: Binary },
+}
+
+/// Returns the storage key if the request is a raw query of the contract's own storage.
+/// This is only the case if the contract address is set in the environment.
+///
+/// Only requests starting like a serialized raw query are parsed. All other requests,
+/// including raw queries encoded differently, take the regular path through the querier.
+fn own_raw_query_key<A: BackendApi, S: Storage, Q: Querier>(
+    env: &Environment<A, S, Q>,
+    request: &[u8],
+) -> Option<Vec<u8>> {
+    if !request.starts_with(RAW_QUERY_PREFIX) {
+        return None;
+    }
+    let contract_address = env.contract_address()?;
+    match serde_json::from_slice(request).ok()? {
+        RawQueryRequest::Wasm(RawWasmQuery::Raw { contract_addr, key })
+            if contract_addr == contract_address =>
+        {
+            Some(key.into())
+        }
+        _ => None,
+    }
+}
+
+/// The VM's view on `QueryRequest::Multi`.
+///
+/// The sub-queries are opaque to the VM and forwarded to the querier unchanged.
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MultiQueryRequest {
+    Multi(Vec<serde_json::Value>),
+}
+
+/// Executes the sub-queries of a `QueryRequest::Multi` one after another within a single
+/// querier access and returns a JSON encoded `Vec<QuerierResult>` parallel to the requests.
+///
+/// All sub-queries share the given gas limit. A backend error in any of the sub-queries
+/// (e.g. running out of gas) aborts the whole batch.
+///
+/// Batches with more than [`MAX_COUNT_QUERY_MULTI`] sub-queries and nested batches are
+/// rejected with a `SystemError::InvalidRequest` before any sub-query is executed.
+fn query_multi<Q: Querier>(
+    querier: &Q,
+    bin_request: &[u8],
+    requests: &[serde_json::Value],
+    gas_limit: u64,
+) -> BackendResult<SystemResult<ContractResult<Binary>>> {
+    let invalid_request = |error: String| {
+        (
+            Ok(SystemResult::Err(SystemError::InvalidRequest {
+                error,
+                request: Binary::from(bin_request),
+            })),
+            GasInfo::free(),
+        )
+    };
+    if requests.len() > MAX_COUNT_QUERY_MULTI {
+        return invalid_request(format!(
+            "Too many sub-queries in multi query: got {}, max {MAX_COUNT_QUERY_MULTI}",
+            requests.len()
+        ));
+    }
+    if requests
+        .iter()
+        .any(|request| request.get("multi").is_some())
+    {
+        return invalid_request("Nested multi queries are not supported".to_string());
+    }
+
+    let mut gas_info = GasInfo::free();
+    let mut results = Vec::with_capacity(requests.len());
+    for request in requests {
+        let request = match serde_json::to_vec(request) {
+            Ok(request) => request,
+            Err(e) => return (Err(BackendError::unknown(e.to_string())), gas_info),
+        };
+        let gas_left = gas_limit.saturating_sub(gas_info.cost + gas_info.externally_used);
+        let (result, sub_gas_info) = querier.query_raw(&request, gas_left);
+        gas_info += sub_gas_info;
+        match result {
+            Ok(result) => results.push(result),
+            Err(e) => return (Err(e), gas_info),
+        }
+    }
+    match serde_json::to_vec(&results) {
+        Ok(response) => (
+            Ok(SystemResult::Ok(ContractResult::Ok(Binary::from(response)))),
+            gas_info,
+        ),
+        Err(e) => (Err(BackendError::unknown(e.to_string())), gas_info),
+    }
+}
+
 #[cfg(feature = "iterator")]
 pub fn do_db_scan<A: BackendApi + 'static, S: Storage + 'static, Q: Querier + 'static>(
     mut env: FunctionEnvMut<Environment<A, S, Q>>,
@@ -842,7 +1155,7 @@ pub fn do_db_scan<A: BackendApi + 'static, S: Storage + 'static, Q: Querier + 's
     let (result, gas_info) = data.with_storage_from_context::<_, _>(|store| {
         Ok(store.scan(start.as_deref(), end.as_deref(), order))
     })?;
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(data, &mut store, "db_scan", GasCategory::Storage, gas_info)?;
     let iterator_id = result?;
     Ok(iterator_id)
 }
@@ -857,7 +1170,7 @@ pub fn do_db_next<A: BackendApi + 'static, S: Storage + 'static, Q: Querier + 's
     let (result, gas_info) =
         data.with_storage_from_context::<_, _>(|store| Ok(store.next(iterator_id)))?;
 
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(data, &mut store, "db_next", GasCategory::Storage, gas_info)?;
 
     // Empty key will later be treated as _no more element_.
     let (key, value) = result?.unwrap_or_else(|| (Vec::<u8>::new(), Vec::<u8>::new()));
@@ -876,7 +1189,13 @@ pub fn do_db_next_key<A: BackendApi + 'static, S: Storage + 'static, Q: Querier
     let (result, gas_info) =
         data.with_storage_from_context::<_, _>(|store| Ok(store.next_key(iterator_id)))?;
 
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(
+        data,
+        &mut store,
+        "db_next_key",
+        GasCategory::Storage,
+        gas_info,
+    )?;
 
     let key = match result? {
         Some(key) => key,
@@ -896,7 +1215,13 @@ pub fn do_db_next_value<A: BackendApi + 'static, S: Storage + 'static, Q: Querie
     let (result, gas_info) =
         data.with_storage_from_context::<_, _>(|store| Ok(store.next_value(iterator_id)))?;
 
-    process_gas_info(data, &mut store, gas_info)?;
+    process_gas_info(
+        data,
+        &mut store,
+        "db_next_value",
+        GasCategory::Storage,
+        gas_info,
+    )?;
 
     let value = match result? {
         Some(value) => value,
@@ -913,6 +1238,7 @@ fn write_to_contract<A: BackendApi + 'static, S: Storage + 'static, Q: Querier +
     input: &[u8],
 ) -> VmResult<u32> {
     let out_size = to_u32(input.len())?;
+    data.increment_allocations()?;
     let result = data.call_function1(store, "allocate", &[out_size.into()])?;
     let target_ptr = ref_to_u32(&result)?;
     if target_ptr == 0 {
@@ -946,8 +1272,8 @@ fn to_low_half(data: u32) -> u64 {
 mod tests {
     use super::*;
     use cosmwasm_std::{
-        coins, from_json, AllBalanceResponse, BankQuery, Binary, Empty, QueryRequest, SystemError,
-        SystemResult, WasmQuery,
+        coins, from_json, AllBalanceResponse, BankQuery, Empty, QuerierResult, QueryRequest,
+        WasmQuery,
     };
     use hex_literal::hex;
     use std::ptr::NonNull;
@@ -1120,6 +1446,29 @@ mod tests {
         assert_eq!(force_read(&mut fe_mut, value_ptr), VALUE1);
     }
 
+    #[test]
+    fn do_db_read_distinguishes_empty_value_from_non_existent_key() {
+        let api = MockApi::default();
+        let (fe, mut store, _instance) = make_instance(api);
+        let mut fe_mut = fe.into_mut(&mut store);
+        leave_default_data(&mut fe_mut);
+        fe_mut
+            .data()
+            .with_storage_from_context(|storage| Ok(storage.set(b"empty", b"").0))
+            .unwrap()
+            .unwrap();
+
+        // an empty value is returned as an empty region
+        let key_ptr = write_data(&mut fe_mut, b"empty");
+        let value_ptr = do_db_read(fe_mut.as_mut(), key_ptr).unwrap();
+        assert!(value_ptr > 0);
+        assert_eq!(force_read(&mut fe_mut, value_ptr), b"");
+
+        // a non-existent key is returned as a null pointer
+        let key_ptr = write_data(&mut fe_mut, b"missing");
+        assert_eq!(do_db_read(fe_mut.as_mut(), key_ptr).unwrap(), 0);
+    }
+
     #[test]
     fn do_db_read_works_for_non_existent_key() {
         let api = MockApi::default();
@@ -1711,6 +2060,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn do_secp256k1_verify_charges_configured_cost() {
+        let api = MockApi::default();
+        let (fe, mut store, _instance) = make_instance(api);
+        fe.as_mut(&mut store).gas_costs.secp256k1_verify_cost = 1234;
+        let mut fe_mut = fe.into_mut(&mut store);
+
+        let hash = hex::decode(ECDSA_P256K1_HASH_HEX).unwrap();
+        let hash_ptr = write_data(&mut fe_mut, &hash);
+        let sig = hex::decode(ECDSA_P256K1_SIG_HEX).unwrap();
+        let sig_ptr = write_data(&mut fe_mut, &sig);
+        let pubkey = hex::decode(ECDSA_P256K1_PUBKEY_HEX).unwrap();
+        let pubkey_ptr = write_data(&mut fe_mut, &pubkey);
+
+        let gas_before = {
+            let (env, mut store) = fe_mut.data_and_store_mut();
+            env.get_gas_left(&mut store)
+        };
+        assert_eq!(
+            do_secp256k1_verify(fe_mut.as_mut(), hash_ptr, sig_ptr, pubkey_ptr).unwrap(),
+            0
+        );
+
+        let (env, mut store) = fe_mut.data_and_store_mut();
+        let gas_used = gas_before - env.get_gas_left(&mut store);
+        assert_eq!(gas_used, 1234);
+    }
+
     #[test]
     fn do_secp256k1_verify_wrong_hash_verify_fails() {
         let api = MockApi::default();
@@ -2532,6 +2909,113 @@ mod tests {
         )
     }
 
+    #[test]
+    fn do_ed25519_validate_pubkey_works() {
+        let api = MockApi::default();
+        let (fe, mut store, _instance) = make_instance(api);
+        let mut fe_mut = fe.into_mut(&mut store);
+
+        let pubkey = hex::decode(EDDSA_PUBKEY_HEX).unwrap();
+        let pubkey_ptr = write_data(&mut fe_mut, &pubkey);
+
+        let gas_before = {
+            let (env, mut store) = fe_mut.data_and_store_mut();
+            env.get_gas_left(&mut store)
+        };
+        assert_eq!(
+            do_ed25519_validate_pubkey(fe_mut.as_mut(), pubkey_ptr).unwrap(),
+            ED25519_VALIDATE_PUBKEY_CODE_VALID
+        );
+        let (env, mut store) = fe_mut.data_and_store_mut();
+        let gas_used = gas_before - env.get_gas_left(&mut store);
+        assert_eq!(gas_used, env.gas_costs.ed25519_validate_pubkey_cost);
+    }
+
+    #[test]
+    fn do_ed25519_validate_pubkey_rejects_invalid_keys() {
+        let api = MockApi::default();
+        let (fe, mut store, _instance) = make_instance(api);
+        let mut fe_mut = fe.into_mut(&mut store);
+
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let pubkey = hex::decode(EDDSA_PUBKEY_HEX).unwrap();
+        for key in [&identity[..], &pubkey[..31], &[]] {
+            let pubkey_ptr = write_data(&mut fe_mut, key);
+            assert_eq!(
+                do_ed25519_validate_pubkey(fe_mut.as_mut(), pubkey_ptr).unwrap(),
+                ED25519_VALIDATE_PUBKEY_CODE_INVALID
+            );
+        }
+    }
+
+    #[test]
+    fn do_ed25519_validate_pubkey_larger_pubkey_fails() {
+        let api = MockApi::default();
+        let (fe, mut store, _instance) = make_instance(api);
+        let mut fe_mut = fe.into_mut(&mut store);
+
+        let mut pubkey = hex::decode(EDDSA_PUBKEY_HEX).unwrap();
+        pubkey.push(0x00);
+        let pubkey_ptr = write_data(&mut fe_mut, &pubkey);
+
+        match do_ed25519_validate_pubkey(fe_mut, pubkey_ptr).unwrap_err() {
+            VmError::CommunicationErr {
+                source: CommunicationError::RegionLengthTooBig { length, .. },
+                ..
+            } => assert_eq!(length, EDDSA_PUBKEY_LEN + 1),
+            e => panic!("Unexpected error: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn do_hmac_sha256_works() {
+        let api = MockApi::default();
+        let (fe, mut store, instance) = make_instance(api);
+        let mut fe_mut = fe.into_mut(&mut store);
+
+        // Test Case 2 from https://datatracker.ietf.org/doc/html/rfc4231#section-4.3
+        let key_ptr = write_data(&mut fe_mut, b"Jefe");
+        let data_ptr = write_data(&mut fe_mut, b"what do ya want for nothing?");
+        let out_ptr = create_empty(&instance, &mut fe_mut, 32);
+
+        let gas_before = {
+            let (env, mut store) = fe_mut.data_and_store_mut();
+            env.get_gas_left(&mut store)
+        };
+        let result = do_hmac_sha256(fe_mut.as_mut(), key_ptr, data_ptr, out_ptr).unwrap();
+        assert_eq!(result, HMAC_SHA256_SUCCESS);
+        assert_eq!(
+            force_read(&mut fe_mut, out_ptr),
+            hex!("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+
+        let (env, mut store) = fe_mut.data_and_store_mut();
+        let gas_used = gas_before - env.get_gas_left(&mut store);
+        assert_eq!(gas_used, env.gas_costs.hmac_sha256_cost.total_cost(4 + 28));
+    }
+
+    #[test]
+    fn do_hmac_sha256_larger_key_fails() {
+        let api = MockApi::default();
+        let (fe, mut store, instance) = make_instance(api);
+        let mut fe_mut = fe.into_mut(&mut store);
+
+        let key = vec![0xaa; MAX_LENGTH_HMAC_SHA256_KEY + 1];
+        let key_ptr = write_data(&mut fe_mut, &key);
+        let data_ptr = write_data(&mut fe_mut, b"Hi There");
+        let out_ptr = create_empty(&instance, &mut fe_mut, 32);
+
+        let result = do_hmac_sha256(fe_mut, key_ptr, data_ptr, out_ptr);
+        match result.unwrap_err() {
+            VmError::CommunicationErr {
+                source: CommunicationError::RegionLengthTooBig { length, .. },
+                ..
+            } => assert_eq!(length, key.len()),
+            e => panic!("Unexpected error: {e:?}"),
+        }
+    }
+
     #[test]
     #[allow(deprecated)]
     fn do_query_chain_works() {
@@ -2557,6 +3041,77 @@ mod tests {
         assert_eq!(parsed_again.amount, coins(INIT_AMOUNT, INIT_DENOM));
     }
 
+    #[test]
+    fn do_query_chain_serves_own_raw_queries_from_storage() {
+        let api = MockApi::default();
+        let (fe, mut store, _instance) = make_instance(api);
+        let mut fe_mut = fe.into_mut(&mut store);
+
+        leave_default_data(&mut fe_mut);
+        fe_mut
+            .data()
+            .set_contract_address(Some("contract".to_string()));
+
+        fn raw_query(
+            fe_mut: &mut FunctionEnvMut<Environment<MockApi, MockStorage, MockQuerier>>,
+            contract: &str,
+            key: &[u8],
+        ) -> QuerierResult {
+            let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Raw {
+                contract_addr: contract.to_string(),
+                key: key.into(),
+            });
+            let request_data = cosmwasm_std::to_json_vec(&request).unwrap();
+            let request_ptr = write_data(fe_mut, &request_data);
+            let response_ptr = do_query_chain(fe_mut.as_mut(), request_ptr).unwrap();
+            let response = force_read(fe_mut, response_ptr);
+            from_json(response).unwrap()
+        }
+        fn query_count(
+            fe_mut: &FunctionEnvMut<Environment<MockApi, MockStorage, MockQuerier>>,
+        ) -> u64 {
+            fe_mut
+                .data()
+                .with_querier_from_context::<_, _>(|querier| Ok(querier.query_count()))
+                .unwrap()
+        }
+
+        let result = raw_query(&mut fe_mut, "contract", KEY1);
+        assert_eq!(result.unwrap().unwrap(), VALUE1);
+        let result = raw_query(&mut fe_mut, "contract", b"unknown");
+        assert_eq!(result.unwrap().unwrap(), b"");
+        assert_eq!(query_count(&fe_mut), 0);
+        // the reads are accounted as storage gas
+        let gas_state = fe_mut.data().with_gas_state(|gas_state| gas_state.clone());
+        assert_eq!(
+            gas_state.externally_used_storage_gas,
+            (KEY1.len() + b"unknown".len()) as u64
+        );
+        assert_eq!(gas_state.externally_used_querier_gas, 0);
+
+        // other contracts are queried via the querier
+        let result = raw_query(&mut fe_mut, "other", KEY1);
+        assert!(matches!(
+            result,
+            SystemResult::Err(SystemError::NoSuchContract { .. })
+        ));
+        assert_eq!(query_count(&fe_mut), 1);
+
+        // smart queries of the own contract are queried via the querier
+        let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: "contract".to_string(),
+            msg: Binary::from(b"{}"),
+        });
+        let request_ptr = write_data(&mut fe_mut, &cosmwasm_std::to_json_vec(&request).unwrap());
+        do_query_chain(fe_mut.as_mut(), request_ptr).unwrap();
+        assert_eq!(query_count(&fe_mut), 2);
+
+        // without a contract address, all queries go to the querier
+        fe_mut.data().set_contract_address(None);
+        let _ = raw_query(&mut fe_mut, "contract", KEY1);
+        assert_eq!(query_count(&fe_mut), 3);
+    }
+
     #[test]
     fn do_query_chain_fails_for_broken_request() {
         let api = MockApi::default();
@@ -2609,6 +3164,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn do_query_chain_multi_works() {
+        let api = MockApi::default();
+        let (fe, mut store, _instance) = make_instance(api);
+        let mut fe_mut = fe.into_mut(&mut store);
+
+        let balance_request: QueryRequest<Empty> = QueryRequest::Bank(BankQuery::AllBalances {
+            address: INIT_ADDR.to_string(),
+        });
+        let contract_request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: String::from("non-existent"),
+            msg: Binary::from(b"{}" as &[u8]),
+        });
+        let request: QueryRequest<Empty> =
+            QueryRequest::Multi(vec![balance_request, contract_request]);
+        let request_data = cosmwasm_std::to_json_vec(&request).unwrap();
+        let request_ptr = write_data(&mut fe_mut, &request_data);
+
+        leave_default_data(&mut fe_mut);
+
+        let response_ptr = do_query_chain(fe_mut.as_mut(), request_ptr).unwrap();
+        let response = force_read(&mut fe_mut, response_ptr);
+
+        let query_result: QuerierResult = from_json(response).unwrap();
+        let results: Vec<QuerierResult> = from_json(query_result.unwrap().unwrap()).unwrap();
+        assert_eq!(results.len(), 2);
+
+        let parsed: AllBalanceResponse = from_json(results[0].clone().unwrap().unwrap()).unwrap();
+        assert_eq!(parsed.amount, coins(INIT_AMOUNT, INIT_DENOM));
+        match &results[1] {
+            SystemResult::Err(SystemError::NoSuchContract { addr }) => {
+                assert_eq!(addr, "non-existent")
+            }
+            res => panic!("Unexpected result: {res:?}"),
+        }
+    }
+
+    #[test]
+    fn do_query_chain_multi_rejects_invalid_batches() {
+        let api = MockApi::default();
+        let (fe, mut store, _instance) = make_instance(api);
+        let mut fe_mut = fe.into_mut(&mut store);
+        leave_default_data(&mut fe_mut);
+
+        let balance_request: QueryRequest<Empty> = QueryRequest::Bank(BankQuery::AllBalances {
+            address: INIT_ADDR.to_string(),
+        });
+        let too_many = vec![balance_request.clone(); MAX_COUNT_QUERY_MULTI + 1];
+        let nested = vec![QueryRequest::Multi(vec![balance_request.clone()])];
+
+        for (requests, expected_error) in [
+            (
+                too_many,
+                "Too many sub-queries in multi query: got 65, max 64",
+            ),
+            (nested, "Nested multi queries are not supported"),
+        ] {
+            let request_data = cosmwasm_std::to_json_vec(&QueryRequest::Multi(requests)).unwrap();
+            let request_ptr = write_data(&mut fe_mut, &request_data);
+
+            let response_ptr = do_query_chain(fe_mut.as_mut(), request_ptr).unwrap();
+            let response = force_read(&mut fe_mut, response_ptr);
+
+            let query_result: QuerierResult = from_json(response).unwrap();
+            match query_result {
+                SystemResult::Err(SystemError::InvalidRequest { error, request }) => {
+                    assert_eq!(error, expected_error);
+                    assert_eq!(request.as_slice(), request_data);
+                }
+                res => panic!("Unexpected result: {res:?}"),
+            }
+        }
+
+        // a batch of the maximum size is fine
+        let request = QueryRequest::Multi(vec![balance_request; MAX_COUNT_QUERY_MULTI]);
+        let request_data = cosmwasm_std::to_json_vec(&request).unwrap();
+        let request_ptr = write_data(&mut fe_mut, &request_data);
+        let response_ptr = do_query_chain(fe_mut.as_mut(), request_ptr).unwrap();
+        let response = force_read(&mut fe_mut, response_ptr);
+        let query_result: QuerierResult = from_json(response).unwrap();
+        let results: Vec<QuerierResult> = from_json(query_result.unwrap().unwrap()).unwrap();
+        assert_eq!(results.len(), MAX_COUNT_QUERY_MULTI);
+    }
+
     #[test]
     #[cfg(feature = "iterator")]
     fn do_db_scan_unbound_works() {
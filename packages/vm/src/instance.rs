@@ -1,5 +1,5 @@
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ptr::NonNull;
 use std::rc::Rc;
 use std::sync::Mutex;
@@ -8,8 +8,13 @@ use wasmer::{
     Exports, Function, FunctionEnv, Imports, Instance as WasmerInstance, Module, Store, Value,
 };
 
+use cosmwasm_std::ContractResult;
+use serde::de::IgnoredAny;
+
 use crate::backend::{Backend, BackendApi, Querier, Storage};
+use crate::calls::call_instantiate_raw;
 use crate::capabilities::required_capabilities_from_module;
+use crate::config::{GasCosts, ResponseLimits, ResultLimits};
 use crate::conversion::{ref_to_u32, to_u32};
 use crate::environment::Environment;
 use crate::errors::{CommunicationError, VmError, VmResult};
@@ -17,28 +22,52 @@ use crate::imports::{
     do_abort, do_addr_canonicalize, do_addr_humanize, do_addr_validate, do_bls12_381_aggregate_g1,
     do_bls12_381_aggregate_g2, do_bls12_381_hash_to_g1, do_bls12_381_hash_to_g2,
     do_bls12_381_pairing_equality, do_db_read, do_db_remove, do_db_write, do_debug,
-    do_ed25519_batch_verify, do_ed25519_verify, do_query_chain, do_secp256k1_recover_pubkey,
-    do_secp256k1_verify, do_secp256r1_recover_pubkey, do_secp256r1_verify,
+    do_ed25519_batch_verify, do_ed25519_validate_pubkey, do_ed25519_verify, do_hmac_sha256,
+    do_query_chain, do_secp256k1_recover_pubkey, do_secp256k1_verify, do_secp256r1_recover_pubkey,
+    do_secp256r1_verify,
 };
 #[cfg(feature = "iterator")]
 use crate::imports::{do_db_next, do_db_next_key, do_db_next_value, do_db_scan};
 use crate::memory::{read_region, write_region};
+use crate::serde::from_slice;
 use crate::size::Size;
 use crate::wasm_backend::{compile, make_compiling_engine};
 
 pub use crate::environment::DebugInfo; // Re-exported as public via to be usable for set_debug_handler
+pub use crate::environment::MemoryGrowth; // Re-exported as public via to be usable for set_memory_growth_per_call_handler
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub struct GasReport {
     /// The original limit the instance was created with
     pub limit: u64,
     /// The remaining gas that can be spend
     pub remaining: u64,
-    /// The amount of gas that was spend and metered externally in operations triggered by this instance
+    /// The amount of gas that was spend and metered externally in operations triggered by this instance.
+    /// This is the sum of `used_externally_storage`, `used_externally_querier` and `used_externally_api`.
     pub used_externally: u64,
+    /// The part of `used_externally` that was spend in storage operations
+    pub used_externally_storage: u64,
+    /// The part of `used_externally` that was spend in the querier, which includes
+    /// the execution of other contracts queried by this instance
+    pub used_externally_querier: u64,
+    /// The part of `used_externally` that was spend in API calls such as address validation
+    pub used_externally_api: u64,
     /// The amount of gas that was spend and metered internally (i.e. by executing Wasm and calling
     /// API methods which are not metered externally)
     pub used_internally: u64,
+    /// The number of times the VM called the contract's `allocate` export during the
+    /// last call into the contract
+    pub allocations: u64,
+    /// The gas charged by each host function (import) called by this instance, keyed by
+    /// import name, e.g. `bls12_381_pairing_equality`. Each value contains the cost of the
+    /// host function as well as the gas it used externally.
+    /// Host functions that were never called are not included.
+    pub per_import: BTreeMap<String, u64>,
+    /// The number of Wasm memory pages (64 KiB each) the contract grew its memory by
+    pub pages_grown: u64,
+    /// The gas charged for growing the memory. This is part of `used_internally` and
+    /// only non-zero if a memory growth cost is set (see [`Instance::set_memory_grow_gas_per_page`]).
+    pub gas_for_memory: u64,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -230,6 +259,23 @@ where
             Function::new_typed_with_env(&mut store, &fe, do_ed25519_batch_verify),
         );
 
+        // Checks that the given bytes are a valid ed25519 public key. In contrast to ed25519_verify,
+        // non-canonical encodings and small-order points are rejected.
+        // Returns 0 if the key is valid and 1 if it is not.
+        // Ownership of the input pointer is not transferred to the host.
+        env_imports.insert(
+            "ed25519_validate_pubkey",
+            Function::new_typed_with_env(&mut store, &fe, do_ed25519_validate_pubkey),
+        );
+
+        // Computes the HMAC-SHA256 of the data using the given key.
+        // Writes the 32 byte MAC into the output region and returns 0 on success.
+        // Ownership of input and output pointers is not transferred to the host.
+        env_imports.insert(
+            "hmac_sha256",
+            Function::new_typed_with_env(&mut store, &fe, do_hmac_sha256),
+        );
+
         // Allows the contract to emit debug logs that the host can either process or ignore.
         // This is never written to chain.
         // Takes a pointer argument of a memory region that must contain an UTF-8 encoded string.
@@ -376,6 +422,27 @@ where
         self.fe.as_ref(&self.store).set_debug_handler(None);
     }
 
+    /// Sets a handler that is called after every call into the contract that grew
+    /// the contract's memory. This can be used to find out how much memory a contract
+    /// needs in order to tune the memory limit.
+    ///
+    /// The handler is called once per call with the total growth, not for every
+    /// `memory.grow` instruction executed during the call.
+    pub fn set_memory_growth_per_call_handler<H>(&mut self, handler: H)
+    where
+        H: FnMut(MemoryGrowth) + 'static,
+    {
+        self.fe
+            .as_ref(&self.store)
+            .set_memory_growth_per_call_handler(Some(Rc::new(RefCell::new(handler))));
+    }
+
+    pub fn unset_memory_growth_per_call_handler(&mut self) {
+        self.fe
+            .as_ref(&self.store)
+            .set_memory_growth_per_call_handler(None);
+    }
+
     /// Returns the features required by this contract.
     ///
     /// This is not needed for production because we can do static analysis
@@ -396,6 +463,16 @@ where
         env.memory(&store).size().0 as _
     }
 
+    /// Returns the maximum size of the default memory in pages, i.e. the number of pages
+    /// the memory can grow to. This is the maximum declared by the contract or, if unset,
+    /// the instance's memory limit. `None` means the memory can grow without limit.
+    pub fn memory_limit_pages(&mut self) -> Option<usize> {
+        let mut fe_mut = self.fe.clone().into_mut(&mut self.store);
+        let (env, store) = fe_mut.data_and_store_mut();
+
+        env.memory_limit_pages(&store).map(|pages| pages as _)
+    }
+
     /// Returns the currently remaining gas.
     pub fn get_gas_left(&mut self) -> u64 {
         let mut fe_mut = self.fe.clone().into_mut(&mut self.store);
@@ -404,6 +481,14 @@ where
         env.get_gas_left(&mut store)
     }
 
+    /// Sets the remaining gas.
+    pub(crate) fn set_gas_left(&mut self, new_value: u64) {
+        let mut fe_mut = self.fe.clone().into_mut(&mut self.store);
+        let (env, mut store) = fe_mut.data_and_store_mut();
+
+        env.set_gas_left(&mut store, new_value);
+    }
+
     /// Creates and returns a gas report.
     /// This is a snapshot and multiple reports can be created during the lifetime of
     /// an instance.
@@ -417,6 +502,9 @@ where
             limit: state.gas_limit,
             remaining: gas_left,
             used_externally: state.externally_used_gas,
+            used_externally_storage: state.externally_used_storage_gas,
+            used_externally_querier: state.externally_used_querier_gas,
+            used_externally_api: state.externally_used_api_gas,
             // If externally_used_gas exceeds the gas limit, this will return 0.
             // no matter how much gas was used internally. But then we error with out of gas
             // anyways, and it does not matter much anymore where gas was spend.
@@ -424,6 +512,14 @@ where
                 .gas_limit
                 .saturating_sub(state.externally_used_gas)
                 .saturating_sub(gas_left),
+            allocations: env.allocations(),
+            per_import: state
+                .per_import
+                .iter()
+                .map(|(import, gas)| (import.to_string(), *gas))
+                .collect(),
+            pages_grown: state.pages_grown,
+            gas_for_memory: state.memory_gas,
         }
     }
 
@@ -444,6 +540,92 @@ where
         env.set_storage_readonly(new_value);
     }
 
+    /// Sets the maximum number of times the VM may call the contract's `allocate` export
+    /// during a single call into the contract. Exceeding this results in
+    /// [`VmError::MaxAllocationsExceeded`].
+    pub fn set_allocation_limit(&mut self, new_value: u64) {
+        self.fe.as_ref(&self.store).set_allocation_limit(new_value);
+    }
+
+    /// Sets the maximum nesting depth of Wasm function calls within a call into the contract.
+    /// `None` (the default) means the depth is only limited by the native stack size.
+    ///
+    /// Exceeding the limit results in a [`VmError::RuntimeErr`] with the message
+    /// "Maximum Wasm call depth exceeded".
+    pub fn set_max_call_depth(&mut self, max_call_depth: Option<u32>) {
+        let mut fe_mut = self.fe.clone().into_mut(&mut self.store);
+        let (env, mut store) = fe_mut.data_and_store_mut();
+
+        env.set_max_call_depth(&mut store, max_call_depth);
+    }
+
+    /// Sets the gas charged per Wasm memory page the contract grows its memory by.
+    /// `None` (the default) means growing the memory does not cost gas.
+    ///
+    /// Growth is measured at the end of every call into the contract and reported
+    /// in [`GasReport::pages_grown`] and [`GasReport::gas_for_memory`].
+    pub fn set_memory_grow_gas_per_page(&mut self, gas_per_page: Option<u64>) {
+        self.fe
+            .as_ref(&self.store)
+            .set_memory_grow_gas_per_page(gas_per_page);
+    }
+
+    /// Sets the limits for the contents of a successful contract response. Exceeding them
+    /// results in [`VmError::ResultLimitExceeded`].
+    pub fn set_response_limits(&mut self, limits: ResponseLimits) {
+        self.fe.as_ref(&self.store).set_response_limits(limits);
+    }
+
+    pub(crate) fn response_limits(&self) -> ResponseLimits {
+        self.fe.as_ref(&self.store).response_limits()
+    }
+
+    /// Sets the maximum lengths of the JSON results of the entry points. A longer result
+    /// results in [`VmError::DeserializationLimitExceeded`].
+    pub fn set_result_limits(&mut self, limits: ResultLimits) {
+        self.fe.as_ref(&self.store).set_result_limits(limits);
+    }
+
+    pub(crate) fn result_limits(&self) -> ResultLimits {
+        self.fe.as_ref(&self.store).result_limits()
+    }
+
+    /// Sets the maximum lengths of the results returned by the `call_*_raw` functions.
+    /// Unset by default, which means those results are only limited to 64 MiB.
+    /// A longer result results in [`VmError::DeserializationLimitExceeded`].
+    pub fn set_raw_result_limits(&mut self, limits: Option<ResultLimits>) {
+        self.fe.as_ref(&self.store).set_raw_result_limits(limits);
+    }
+
+    pub(crate) fn raw_result_limits(&self) -> Option<ResultLimits> {
+        self.fe.as_ref(&self.store).raw_result_limits()
+    }
+
+    /// Sets the gas costs of the host functions provided by the VM.
+    /// Instances created by the cache use the costs configured in [`crate::Config::gas_costs`].
+    pub fn set_gas_costs(&mut self, gas_costs: GasCosts) {
+        self.fe.as_mut(&mut self.store).gas_costs = gas_costs;
+    }
+
+    pub(crate) fn gas_costs(&self) -> &GasCosts {
+        &self.fe.as_ref(&self.store).gas_costs
+    }
+
+    /// Sets the address of the contract this instance runs as. Unset by default.
+    ///
+    /// When set, `WasmQuery::Raw` queries of this address issued by the contract are served
+    /// from the instance's storage directly instead of calling the querier. Such a query is
+    /// charged like a storage read (`db_read`) instead of a query, so enabling this changes
+    /// gas consumption.
+    pub fn set_contract_address(&mut self, address: Option<String>) {
+        self.fe.as_ref(&self.store).set_contract_address(address);
+    }
+
+    /// Resets the allocation counter to 0. This is done at the beginning of every call.
+    pub(crate) fn reset_allocations(&mut self) {
+        self.fe.as_ref(&self.store).reset_allocations();
+    }
+
     pub fn with_storage<F: FnOnce(&mut S) -> VmResult<T>, T>(&mut self, func: F) -> VmResult<T> {
         self.fe
             .as_ref(&self.store)
@@ -459,6 +641,7 @@ where
     /// Requests memory allocation by the instance and returns a pointer
     /// in the Wasm address space to the created Region object.
     pub(crate) fn allocate(&mut self, size: usize) -> VmResult<u32> {
+        self.fe.as_ref(&self.store).increment_allocations()?;
         let ret = self.call_function1("allocate", &[to_u32(size)?.into()])?;
         let ptr = ref_to_u32(&ret)?;
         if ptr == 0 {
@@ -528,6 +711,52 @@ where
     Instance::from_module(store, module, backend, gas_limit, extra_imports, None)
 }
 
+/// Checks if the contract can be instantiated with the given message within `gas_budget`
+/// [CosmWasm gas](https://github.com/CosmWasm/cosmwasm/blob/main/docs/GAS.md).
+///
+/// The instantiation runs against the given backend, with the serialized `env` and `info` and
+/// the same memory limit as the chain's instances. The backend's storage is written to, so
+/// callers should pass a throwaway storage.
+///
+/// Returns `Ok(true)` if the instantiation succeeds and `Ok(false)` if it runs out of gas.
+/// Errors returned by the contract and all other errors are passed through.
+///
+/// This exists only to be exported through `internals` for use by crates that are
+/// part of Cosmwasm.
+pub fn probe_instantiate<A, S, Q>(
+    wasm: &[u8],
+    backend: Backend<A, S, Q>,
+    env: &[u8],
+    info: &[u8],
+    msg: &[u8],
+    gas_budget: u64,
+    memory_limit: Option<Size>,
+) -> VmResult<bool>
+where
+    A: BackendApi + 'static,
+    S: Storage + 'static,
+    Q: Querier + 'static,
+{
+    let options = InstanceOptions {
+        gas_limit: gas_budget,
+    };
+    let mut instance = Instance::from_code(wasm, backend, options, memory_limit)?;
+
+    let data = match call_instantiate_raw(&mut instance, env, info, msg) {
+        Ok(data) => data,
+        Err(VmError::GasDepletion { .. }) => return Ok(false),
+        Err(err) => return Err(err),
+    };
+    let result: ContractResult<IgnoredAny> =
+        from_slice(&data, instance.result_limits().instantiate)?;
+    match result {
+        ContractResult::Ok(_) => Ok(true),
+        ContractResult::Err(err) => {
+            Err(VmError::generic_err(format!("Instantiation failed: {err}")))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::atomic::{AtomicBool, Ordering};
@@ -536,10 +765,11 @@ mod tests {
 
     use super::*;
     use crate::calls::{call_execute, call_instantiate, call_query};
+    use crate::serde::to_vec;
     use crate::testing::{
         mock_backend, mock_env, mock_info, mock_instance, mock_instance_options,
         mock_instance_with_balances, mock_instance_with_failing_api, mock_instance_with_gas_limit,
-        mock_instance_with_options, MockInstanceOptions,
+        mock_instance_with_options, MockApi, MockInstanceOptions,
     };
     use cosmwasm_std::{
         coin, coins, from_json, AllBalanceResponse, BalanceResponse, BankQuery, Empty, QueryRequest,
@@ -885,6 +1115,159 @@ mod tests {
         assert_eq!(instance.memory_pages(), 19);
     }
 
+    #[test]
+    fn memory_limit_pages_works() {
+        // max: 5 pages
+        let wasm = wat::parse_str(
+            r#"(module
+                (memory 1 5)
+                (export "memory" (memory 0))
+
+                (type (func))
+                (func (type 0) nop)
+                (export "interface_version_8" (func 0))
+                (export "instantiate" (func 0))
+                (export "allocate" (func 0))
+                (export "deallocate" (func 0))
+            )"#,
+        )
+        .unwrap();
+        let mut instance = mock_instance(&wasm, &[]);
+        assert_eq!(instance.memory_limit_pages(), Some(5));
+
+        // max: none, so the instance's memory limit (16 MiB) is used
+        let mut instance = mock_instance(CONTRACT, &[]);
+        assert_eq!(instance.memory_limit_pages(), Some(256));
+    }
+
+    #[test]
+    fn set_max_call_depth_works() {
+        let wasm = wat::parse_str(
+            r#"(module
+                (memory 1)
+                (export "memory" (memory 0))
+
+                (type (func))
+                (func (type 0) nop)
+                (export "interface_version_8" (func 0))
+                (export "instantiate" (func 0))
+                (export "allocate" (func 0))
+                (export "deallocate" (func 0))
+
+                ;; calls itself n times
+                (func $recurse (export "recurse") (param i32) (result i32)
+                    (if (result i32) (i32.eqz (local.get 0))
+                        (then (i32.const 0))
+                        (else (i32.add
+                            (call $recurse (i32.sub (local.get 0) (i32.const 1)))
+                            (i32.const 1)))
+                    )
+                )
+            )"#,
+        )
+        .unwrap();
+        let mut instance = mock_instance(&wasm, &[]);
+
+        // unlimited by default
+        let result = instance
+            .call_function1("recurse", &[500u32.into()])
+            .unwrap();
+        assert_eq!(result, Value::I32(500));
+
+        instance.set_max_call_depth(Some(200));
+        for depth in [199u32, 200] {
+            let result = instance.call_function1("recurse", &[depth.into()]).unwrap();
+            assert_eq!(result, Value::I32(depth as i32));
+        }
+        match instance
+            .call_function1("recurse", &[201u32.into()])
+            .unwrap_err()
+        {
+            VmError::RuntimeErr { msg, .. } => assert_eq!(msg, "Maximum Wasm call depth exceeded"),
+            err => panic!("Unexpected error: {err:?}"),
+        }
+
+        // the trap does not affect subsequent calls
+        let result = instance
+            .call_function1("recurse", &[200u32.into()])
+            .unwrap();
+        assert_eq!(result, Value::I32(200));
+
+        // real contracts work with a reasonable limit
+        let mut instance = mock_instance(CONTRACT, &[]);
+        instance.set_max_call_depth(Some(1));
+        let verifier = instance.api().addr_make("verifies");
+        let beneficiary = instance.api().addr_make("benefits");
+        let msg = format!(r#"{{"verifier": "{verifier}", "beneficiary": "{beneficiary}"}}"#);
+        let info = mock_info(&instance.api().addr_make("creator"), &[]);
+        let err =
+            call_instantiate::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg.as_bytes())
+                .unwrap_err();
+        assert!(err.to_string().contains("Maximum Wasm call depth exceeded"));
+        instance.set_max_call_depth(Some(10_000));
+        call_instantiate::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg.as_bytes())
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    fn memory_growth_per_call_handler_works() {
+        let wasm = wat::parse_str(
+            r#"(module
+                (memory 1 7)
+                (export "memory" (memory 0))
+
+                (type (func))
+                (func (type 0) nop)
+                (export "interface_version_8" (func 0))
+                (export "instantiate" (func 0))
+                (export "allocate" (func 0))
+                (export "deallocate" (func 0))
+
+                (func (export "grow") (param i32)
+                    (drop (memory.grow (local.get 0)))
+                )
+            )"#,
+        )
+        .unwrap();
+        let mut instance = mock_instance(&wasm, &[]);
+
+        let events = Rc::new(RefCell::new(Vec::<MemoryGrowth>::new()));
+        let events_clone = events.clone();
+        instance.set_memory_growth_per_call_handler(move |growth| {
+            events_clone.borrow_mut().push(growth)
+        });
+
+        instance.call_function0("grow", &[2u32.into()]).unwrap();
+        // no growth
+        instance.call_function0("grow", &[0u32.into()]).unwrap();
+        // exceeds the maximum, so memory.grow fails
+        instance.call_function0("grow", &[5u32.into()]).unwrap();
+        instance.call_function0("grow", &[2u32.into()]).unwrap();
+        assert_eq!(instance.memory_pages(), 5);
+
+        assert_eq!(
+            *events.borrow(),
+            [
+                MemoryGrowth {
+                    pages_before: 1,
+                    pages_after: 3,
+                    limit_pages: Some(7),
+                },
+                MemoryGrowth {
+                    pages_before: 3,
+                    pages_after: 5,
+                    limit_pages: Some(7),
+                },
+            ]
+        );
+
+        instance.unset_memory_growth_per_call_handler();
+        instance.call_function0("grow", &[2u32.into()]).unwrap();
+        assert_eq!(instance.memory_pages(), 7);
+        assert_eq!(events.borrow().len(), 2);
+    }
+
     #[test]
     fn get_gas_left_works() {
         let mut instance = mock_instance_with_gas_limit(CONTRACT, 123321);
@@ -899,9 +1282,14 @@ mod tests {
 
         let report1 = instance.create_gas_report();
         assert_eq!(report1.used_externally, 0);
+        assert_eq!(report1.used_externally_storage, 0);
+        assert_eq!(report1.used_externally_querier, 0);
+        assert_eq!(report1.used_externally_api, 0);
         assert_eq!(report1.used_internally, 0);
         assert_eq!(report1.limit, LIMIT);
         assert_eq!(report1.remaining, LIMIT);
+        assert_eq!(report1.allocations, 0);
+        assert!(report1.per_import.is_empty());
 
         // init contract
         let info = mock_info(&instance.api().addr_make("creator"), &coins(1000, "earth"));
@@ -914,14 +1302,60 @@ mod tests {
 
         let report2 = instance.create_gas_report();
         assert_eq!(report2.used_externally, 251);
+        assert_eq!(
+            report2.used_externally,
+            report2.used_externally_storage
+                + report2.used_externally_querier
+                + report2.used_externally_api
+        );
         assert_eq!(report2.used_internally, 21589990);
         assert_eq!(report2.limit, LIMIT);
+        // env, info and msg
+        assert_eq!(report2.allocations, 3);
+        // storing the config is attributed to db_write
+        assert!(report2.per_import["db_write"] > 0);
+        let per_import_total: u64 = report2.per_import.values().sum();
+        assert!(per_import_total >= report2.used_externally);
+        assert!(per_import_total <= report2.used_externally + report2.used_internally);
         assert_eq!(
             report2.remaining,
             LIMIT - report2.used_externally - report2.used_internally
         );
     }
 
+    #[test]
+    fn create_gas_report_includes_memory_growth() {
+        const LIMIT: u64 = 700_000_000_000;
+        const GAS_PER_PAGE: u64 = 1_000_000;
+        let grow_msg = br#"{"allocate_large_memory":{"pages":48}}"#;
+
+        for gas_per_page in [None, Some(GAS_PER_PAGE)] {
+            let mut instance = mock_instance_with_gas_limit(CYBERPUNK, LIMIT);
+            instance.set_memory_grow_gas_per_page(gas_per_page);
+
+            let info = mock_info("creator", &[]);
+            call_instantiate::<_, _, _, Empty>(&mut instance, &mock_env(), &info, br#"{}"#)
+                .unwrap()
+                .unwrap();
+            let report1 = instance.create_gas_report();
+
+            let info = mock_info("caller", &[]);
+            call_execute::<_, _, _, Empty>(&mut instance, &mock_env(), &info, grow_msg)
+                .unwrap()
+                .unwrap();
+            let report2 = instance.create_gas_report();
+
+            let pages_grown = report2.pages_grown - report1.pages_grown;
+            assert!(pages_grown >= 48, "Pages grown: {pages_grown}");
+            let gas_for_memory = report2.gas_for_memory - report1.gas_for_memory;
+            match gas_per_page {
+                None => assert_eq!(gas_for_memory, 0),
+                Some(gas_per_page) => assert_eq!(gas_for_memory, pages_grown * gas_per_page),
+            }
+            assert!(report2.used_internally - report1.used_internally > gas_for_memory);
+        }
+    }
+
     #[test]
     fn set_storage_readonly_works() {
         let mut instance = mock_instance(CONTRACT, &[]);
@@ -977,6 +1411,45 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn storage_accesses_can_be_recorded() {
+        let mut instance = mock_instance(CONTRACT, &[]);
+        instance
+            .with_storage(|store| {
+                store.record_accesses();
+                Ok(())
+            })
+            .unwrap();
+
+        // instantiate writes the config
+        let info = mock_info(&instance.api().addr_make("creator"), &coins(1000, "earth"));
+        let verifier = instance.api().addr_make("verifies");
+        let beneficiary = instance.api().addr_make("benefits");
+        let msg = format!(r#"{{"verifier": "{verifier}", "beneficiary": "{beneficiary}"}}"#);
+        call_instantiate::<_, _, _, Empty>(&mut instance, &mock_env(), &info, msg.as_bytes())
+            .unwrap()
+            .unwrap();
+        let accesses = instance
+            .with_storage(|store| {
+                let accesses = store.accesses().unwrap();
+                store.record_accesses();
+                Ok(accesses)
+            })
+            .unwrap();
+        assert!(accesses.writes.contains(b"config".as_slice()));
+
+        // release only reads the config
+        let info = mock_info(&verifier, &coins(15, "earth"));
+        call_execute::<_, _, _, Empty>(&mut instance, &mock_env(), &info, br#"{"release":{}}"#)
+            .unwrap()
+            .unwrap();
+        let accesses = instance
+            .with_storage(|store| Ok(store.accesses().unwrap()))
+            .unwrap();
+        assert!(accesses.reads.contains(b"config".as_slice()));
+        assert!(accesses.writes.is_empty());
+    }
+
     #[test]
     #[allow(deprecated)]
     fn with_querier_works_readonly() {
@@ -1091,6 +1564,43 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn probe_instantiate_works() {
+        let api = MockApi::default();
+        let verifier = api.addr_make("verifies");
+        let beneficiary = api.addr_make("benefits");
+        let msg = format!(r#"{{"verifier": "{verifier}", "beneficiary": "{beneficiary}"}}"#);
+        let env = to_vec(&mock_env()).unwrap();
+        let info = to_vec(&mock_info(&api.addr_make("creator"), &[])).unwrap();
+        let (_, memory_limit) = mock_instance_options();
+        let probe = |msg: &[u8], gas_budget: u64| {
+            probe_instantiate(
+                CONTRACT,
+                mock_backend(&[]),
+                &env,
+                &info,
+                msg,
+                gas_budget,
+                memory_limit,
+            )
+        };
+
+        // generous budget
+        let ok = probe(msg.as_bytes(), 500_000_000_000).unwrap();
+        assert!(ok);
+
+        // tiny budget
+        let ok = probe(msg.as_bytes(), 1_000).unwrap();
+        assert!(!ok);
+
+        // invalid message
+        let err = probe(b"{}", 500_000_000_000).unwrap_err();
+        assert!(
+            err.to_string().contains("Instantiation failed"),
+            "Unexpected error: {err}"
+        );
+    }
+
     #[test]
     fn contract_deducts_gas_init() {
         let mut instance = mock_instance(CONTRACT, &[]);
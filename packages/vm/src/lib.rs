@@ -22,11 +22,11 @@ pub mod testing;
 mod wasm_backend;
 
 pub use crate::backend::{
-    Backend, BackendApi, BackendError, BackendResult, GasInfo, Querier, Storage,
+    Backend, BackendApi, BackendError, BackendResult, GasInfo, OverlayStorage, Querier, Storage,
 };
 pub use crate::cache::{AnalysisReport, Cache, Metrics, PerModuleMetrics, PinnedMetrics, Stats};
 pub use crate::calls::{
-    call_execute, call_execute_raw, call_ibc_destination_callback,
+    call_execute, call_execute_dry_run, call_execute_raw, call_ibc_destination_callback,
     call_ibc_destination_callback_raw, call_ibc_source_callback, call_ibc_source_callback_raw,
     call_instantiate, call_instantiate_raw, call_migrate, call_migrate_raw, call_migrate_with_info,
     call_migrate_with_info_raw, call_query, call_query_raw, call_reply, call_reply_raw, call_sudo,
@@ -39,13 +39,17 @@ pub use crate::calls::{
     call_ibc_packet_ack, call_ibc_packet_ack_raw, call_ibc_packet_receive,
     call_ibc_packet_receive_raw, call_ibc_packet_timeout, call_ibc_packet_timeout_raw,
 };
-pub use crate::capabilities::capabilities_from_csv;
-pub use crate::config::{CacheOptions, Config, WasmLimits};
+pub use crate::capabilities::{capabilities_from_csv, required_capabilities};
+pub use crate::config::{
+    CacheOptions, CodePolicy, Config, GasCosts, LinearGasCost, ResponseLimits, ResultLimits,
+    WasmLimits,
+};
 pub use crate::errors::{
     CommunicationError, CommunicationResult, RegionValidationError, RegionValidationResult,
     VmError, VmResult,
 };
-pub use crate::instance::{DebugInfo, GasReport, Instance, InstanceOptions};
+pub use crate::instance::{DebugInfo, GasReport, Instance, InstanceOptions, MemoryGrowth};
+pub use crate::modules::{CachedModule, PinPolicy};
 pub use crate::serde::{from_slice, to_vec};
 pub use crate::size::Size;
 
@@ -56,7 +60,7 @@ pub mod internals {
     //! Please don't use any of these types directly, as
     //! they might change frequently or be removed in the future.
 
-    pub use crate::compatibility::{check_wasm, LogOutput, Logger};
-    pub use crate::instance::instance_from_module;
+    pub use crate::compatibility::{check_wasm, entry_point_warnings, LogOutput, Logger};
+    pub use crate::instance::{instance_from_module, probe_instantiate};
     pub use crate::wasm_backend::{compile, make_compiling_engine, make_runtime_engine};
 }
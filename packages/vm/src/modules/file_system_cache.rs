@@ -67,7 +67,9 @@ use super::CachedModule;
 /// - **v20**:<br>
 ///   New version because of Wasmer 4.3.3 -> 4.3.7 upgrade.
 ///   Module compatibility between Wasmer versions is not guaranteed.
-const MODULE_SERIALIZATION_VERSION: &str = "v20";
+/// - **v21**:<br>
+///   New version because of the call depth limiting middleware.
+const MODULE_SERIALIZATION_VERSION: &str = "v21";
 
 /// Function that actually does the heavy lifting of creating the module version discriminator.
 ///
@@ -480,6 +482,6 @@ mod tests {
     #[test]
     fn module_version_static() {
         let version = raw_module_version_discriminator();
-        assert_eq!(version, "5b35f8ce52");
+        assert_eq!(version, "cf5cdf0dce");
     }
 }
@@ -1,6 +1,7 @@
 use clru::{CLruCache, CLruCacheConfig, WeightScale};
 use std::collections::hash_map::RandomState;
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 
 use cosmwasm_std::Checksum;
 
@@ -19,16 +20,16 @@ const MINIMUM_MODULE_SIZE: Size = Size::kibi(250);
 #[derive(Debug)]
 struct SizeScale;
 
-impl WeightScale<Checksum, CachedModule> for SizeScale {
+impl WeightScale<Checksum, Arc<CachedModule>> for SizeScale {
     #[inline]
-    fn weight(&self, key: &Checksum, value: &CachedModule) -> usize {
+    fn weight(&self, key: &Checksum, value: &Arc<CachedModule>) -> usize {
         std::mem::size_of_val(key) + value.size_estimate
     }
 }
 
 /// An in-memory module cache
 pub struct InMemoryCache {
-    modules: Option<CLruCache<Checksum, CachedModule, RandomState, SizeScale>>,
+    modules: Option<CLruCache<Checksum, Arc<CachedModule>, RandomState, SizeScale>>,
 }
 
 impl InMemoryCache {
@@ -53,17 +54,17 @@ impl InMemoryCache {
     pub fn store(&mut self, checksum: &Checksum, cached_module: CachedModule) -> VmResult<()> {
         if let Some(modules) = &mut self.modules {
             modules
-                .put_with_weight(*checksum, cached_module)
+                .put_with_weight(*checksum, Arc::new(cached_module))
                 .map_err(|e| VmError::cache_err(format!("{e:?}")))?;
         }
         Ok(())
     }
 
-    /// Looks up a module in the cache and creates a new module
-    pub fn load(&mut self, checksum: &Checksum) -> VmResult<Option<CachedModule>> {
+    /// Looks up a module in the cache and returns a shared reference to it
+    pub fn load(&mut self, checksum: &Checksum) -> VmResult<Option<Arc<CachedModule>>> {
         if let Some(modules) = &mut self.modules {
             match modules.get(checksum) {
-                Some(cached) => Ok(Some(cached.clone())),
+                Some(cached) => Ok(Some(Arc::clone(cached))),
                 None => Ok(None),
             }
         } else {
@@ -71,6 +72,14 @@ impl InMemoryCache {
         }
     }
 
+    /// Removes a module from the cache and returns true if it was present.
+    pub fn remove(&mut self, checksum: &Checksum) -> bool {
+        self.modules
+            .as_mut()
+            .and_then(|modules| modules.pop(checksum))
+            .is_some()
+    }
+
     /// Returns the number of elements in the cache.
     pub fn len(&self) -> usize {
         self.modules
@@ -277,6 +286,32 @@ mod tests {
         assert_eq!(cache.size(), 1_500_032);
     }
 
+    #[test]
+    fn remove_works() {
+        let mut cache = InMemoryCache::new(Size::mebi(2));
+
+        let wasm = wat::parse_str(WAT1).unwrap();
+        let checksum = Checksum::generate(&wasm);
+
+        // Not present
+        assert!(!cache.remove(&checksum));
+
+        let engine = make_compiling_engine(TESTING_MEMORY_LIMIT);
+        let module = CachedModule {
+            module: compile(&engine, &wasm).unwrap(),
+            engine: make_runtime_engine(TESTING_MEMORY_LIMIT),
+            size_estimate: 900_000,
+        };
+        cache.store(&checksum, module).unwrap();
+        assert_eq!(cache.len(), 1);
+
+        assert!(cache.remove(&checksum));
+        assert_eq!(cache.len(), 0);
+        assert_eq!(cache.size(), 0);
+        assert!(cache.load(&checksum).unwrap().is_none());
+        assert!(!cache.remove(&checksum));
+    }
+
     #[test]
     fn in_memory_cache_works_for_zero_size() {
         // A cache size of 0 practically disabled the cache. It must work
@@ -7,5 +7,5 @@ mod versioning;
 pub use cached_module::CachedModule;
 pub use file_system_cache::FileSystemCache;
 pub use in_memory_cache::InMemoryCache;
-pub use pinned_memory_cache::PinnedMemoryCache;
+pub use pinned_memory_cache::{PinPolicy, PinnedMemoryCache};
 pub use versioning::current_wasmer_module_version;
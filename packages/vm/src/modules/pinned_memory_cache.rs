@@ -1,16 +1,54 @@
 use cosmwasm_std::Checksum;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use super::cached_module::CachedModule;
 use crate::VmResult;
 
+/// Describes when a pinned module is automatically unpinned by [`crate::Cache::prune_pinned`].
+///
+/// The default policy has neither a TTL nor a max idle time, i.e. the module stays pinned
+/// until it is explicitly unpinned.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PinPolicy {
+    /// Maximum time the module stays pinned after it was pinned, regardless of its usage
+    pub ttl: Option<Duration>,
+    /// Maximum time the module stays pinned without being loaded
+    pub max_idle: Option<Duration>,
+}
+
+impl PinPolicy {
+    /// Returns true if and only if a module pinned at `pinned_at` and last loaded at `last_access`
+    /// should be unpinned at time `now`.
+    pub fn is_expired(
+        &self,
+        pinned_at: SystemTime,
+        last_access: SystemTime,
+        now: SystemTime,
+    ) -> bool {
+        // If the clock went backwards, the elapsed time is zero
+        let elapsed = |since: SystemTime| now.duration_since(since).unwrap_or_default();
+        self.ttl.is_some_and(|ttl| elapsed(pinned_at) >= ttl)
+            || self
+                .max_idle
+                .is_some_and(|max_idle| elapsed(last_access) >= max_idle)
+    }
+}
+
 /// Struct storing some additional metadata, which is only of interest for the pinned cache,
 /// alongside the cached module.
 pub struct InstrumentedModule {
     /// Number of loads from memory this module received
     pub hits: u32,
+    /// The policy determining when the module is unpinned automatically
+    pub policy: PinPolicy,
+    /// The time the module was pinned
+    pub pinned_at: SystemTime,
+    /// The time of the last load of this module. Equal to `pinned_at` if it was never loaded.
+    pub last_access: SystemTime,
     /// The actual cached module
-    pub module: CachedModule,
+    pub module: Arc<CachedModule>,
 }
 
 /// An pinned in memory module cache
@@ -30,18 +68,47 @@ impl PinnedMemoryCache {
         self.modules.iter()
     }
 
-    pub fn store(&mut self, checksum: &Checksum, cached_module: CachedModule) -> VmResult<()> {
+    /// Stores a module in the cache. `now` is used as the pin time and initial access time.
+    pub fn store(
+        &mut self,
+        checksum: &Checksum,
+        cached_module: CachedModule,
+        policy: PinPolicy,
+        now: SystemTime,
+    ) -> VmResult<()> {
         self.modules.insert(
             *checksum,
             InstrumentedModule {
                 hits: 0,
-                module: cached_module,
+                policy,
+                pinned_at: now,
+                last_access: now,
+                module: Arc::new(cached_module),
             },
         );
 
         Ok(())
     }
 
+    /// Replaces the policy of a module in the cache and resets its pin time to `now`.
+    ///
+    /// Returns false if the module is not in the cache.
+    pub fn update_policy(
+        &mut self,
+        checksum: &Checksum,
+        policy: PinPolicy,
+        now: SystemTime,
+    ) -> bool {
+        match self.modules.get_mut(checksum) {
+            Some(cached) => {
+                cached.policy = policy;
+                cached.pinned_at = now;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Removes a module from the cache
     /// Not found modules are silently ignored. Potential integrity errors (wrong checksum) are not checked / enforced
     pub fn remove(&mut self, checksum: &Checksum) -> VmResult<()> {
@@ -49,12 +116,36 @@ impl PinnedMemoryCache {
         Ok(())
     }
 
-    /// Looks up a module in the cache and creates a new module
-    pub fn load(&mut self, checksum: &Checksum) -> VmResult<Option<CachedModule>> {
+    /// Removes all modules whose policy is expired at time `now` and returns their checksums
+    pub fn prune(&mut self, now: SystemTime) -> Vec<Checksum> {
+        let expired: Vec<Checksum> = self
+            .modules
+            .iter()
+            .filter(|(_, cached)| {
+                cached
+                    .policy
+                    .is_expired(cached.pinned_at, cached.last_access, now)
+            })
+            .map(|(checksum, _)| *checksum)
+            .collect();
+        for checksum in &expired {
+            self.modules.remove(checksum);
+        }
+        expired
+    }
+
+    /// Looks up a module in the cache and returns a shared reference to it.
+    /// `now` is recorded as the last access time of the module.
+    pub fn load(
+        &mut self,
+        checksum: &Checksum,
+        now: SystemTime,
+    ) -> VmResult<Option<Arc<CachedModule>>> {
         match self.modules.get_mut(checksum) {
             Some(cached) => {
                 cached.hits = cached.hits.saturating_add(1);
-                Ok(Some(cached.module.clone()))
+                cached.last_access = now;
+                Ok(Some(Arc::clone(&cached.module)))
             }
             None => Ok(None),
         }
@@ -113,7 +204,7 @@ mod tests {
         let checksum = Checksum::generate(&wasm);
 
         // Module does not exist
-        let cache_entry = cache.load(&checksum).unwrap();
+        let cache_entry = cache.load(&checksum, SystemTime::UNIX_EPOCH).unwrap();
         assert!(cache_entry.is_none());
 
         // Compile module
@@ -136,10 +227,20 @@ mod tests {
             engine: make_runtime_engine(TESTING_MEMORY_LIMIT),
             size_estimate: 0,
         };
-        cache.store(&checksum, module).unwrap();
+        cache
+            .store(
+                &checksum,
+                module,
+                PinPolicy::default(),
+                SystemTime::UNIX_EPOCH,
+            )
+            .unwrap();
 
         // Load module
-        let cached = cache.load(&checksum).unwrap().unwrap();
+        let cached = cache
+            .load(&checksum, SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .unwrap();
 
         // Ensure cached module can be executed
         {
@@ -179,7 +280,14 @@ mod tests {
             engine: make_runtime_engine(TESTING_MEMORY_LIMIT),
             size_estimate: 0,
         };
-        cache.store(&checksum, module).unwrap();
+        cache
+            .store(
+                &checksum,
+                module,
+                PinPolicy::default(),
+                SystemTime::UNIX_EPOCH,
+            )
+            .unwrap();
 
         assert!(cache.has(&checksum));
 
@@ -216,7 +324,14 @@ mod tests {
             engine: make_runtime_engine(TESTING_MEMORY_LIMIT),
             size_estimate: 0,
         };
-        cache.store(&checksum, module).unwrap();
+        cache
+            .store(
+                &checksum,
+                module,
+                PinPolicy::default(),
+                SystemTime::UNIX_EPOCH,
+            )
+            .unwrap();
 
         let (_checksum, module) = cache
             .iter()
@@ -225,7 +340,7 @@ mod tests {
 
         assert_eq!(module.hits, 0);
 
-        let _ = cache.load(&checksum).unwrap();
+        let _ = cache.load(&checksum, SystemTime::UNIX_EPOCH).unwrap();
         let (_checksum, module) = cache
             .iter()
             .find(|(iter_checksum, _module)| **iter_checksum == checksum)
@@ -261,7 +376,14 @@ mod tests {
             engine: make_runtime_engine(TESTING_MEMORY_LIMIT),
             size_estimate: 0,
         };
-        cache.store(&checksum, module).unwrap();
+        cache
+            .store(
+                &checksum,
+                module,
+                PinPolicy::default(),
+                SystemTime::UNIX_EPOCH,
+            )
+            .unwrap();
 
         assert_eq!(cache.len(), 1);
 
@@ -308,7 +430,14 @@ mod tests {
             engine: make_runtime_engine(TESTING_MEMORY_LIMIT),
             size_estimate: 500,
         };
-        cache.store(&checksum1, module).unwrap();
+        cache
+            .store(
+                &checksum1,
+                module,
+                PinPolicy::default(),
+                SystemTime::UNIX_EPOCH,
+            )
+            .unwrap();
         assert_eq!(cache.size(), 532);
 
         // Add 2
@@ -318,7 +447,14 @@ mod tests {
             engine: make_runtime_engine(TESTING_MEMORY_LIMIT),
             size_estimate: 300,
         };
-        cache.store(&checksum2, module).unwrap();
+        cache
+            .store(
+                &checksum2,
+                module,
+                PinPolicy::default(),
+                SystemTime::UNIX_EPOCH,
+            )
+            .unwrap();
         assert_eq!(cache.size(), 532 + 332);
 
         // Remove 1
@@ -329,4 +465,129 @@ mod tests {
         cache.remove(&checksum2).unwrap();
         assert_eq!(cache.size(), 0);
     }
+
+    #[test]
+    fn pin_policy_is_expired_works() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let secs = Duration::from_secs;
+
+        // default policy never expires
+        let policy = PinPolicy::default();
+        assert!(!policy.is_expired(t0, t0, t0 + secs(1_000_000_000)));
+
+        // ttl
+        let policy = PinPolicy {
+            ttl: Some(secs(100)),
+            max_idle: None,
+        };
+        assert!(!policy.is_expired(t0, t0, t0 + secs(99)));
+        assert!(policy.is_expired(t0, t0, t0 + secs(100)));
+        // access does not extend the ttl
+        assert!(policy.is_expired(t0, t0 + secs(100), t0 + secs(100)));
+
+        // max idle
+        let policy = PinPolicy {
+            ttl: None,
+            max_idle: Some(secs(10)),
+        };
+        assert!(!policy.is_expired(t0, t0, t0 + secs(9)));
+        assert!(policy.is_expired(t0, t0, t0 + secs(10)));
+        assert!(!policy.is_expired(t0, t0 + secs(95), t0 + secs(100)));
+
+        // both
+        let policy = PinPolicy {
+            ttl: Some(secs(100)),
+            max_idle: Some(secs(10)),
+        };
+        assert!(!policy.is_expired(t0, t0 + secs(95), t0 + secs(99)));
+        assert!(policy.is_expired(t0, t0 + secs(95), t0 + secs(100)));
+        assert!(policy.is_expired(t0, t0 + secs(50), t0 + secs(60)));
+
+        // clock going backwards does not expire
+        assert!(!policy.is_expired(t0, t0, t0 - secs(1_000)));
+    }
+
+    #[test]
+    fn prune_works() {
+        let mut cache = PinnedMemoryCache::new();
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let secs = Duration::from_secs;
+
+        let wasm1 = wat::parse_str(r#"(module (func (export "one")))"#).unwrap();
+        let checksum1 = Checksum::generate(&wasm1);
+        let wasm2 = wat::parse_str(r#"(module (func (export "two")))"#).unwrap();
+        let checksum2 = Checksum::generate(&wasm2);
+        let wasm3 = wat::parse_str(r#"(module (func (export "three")))"#).unwrap();
+        let checksum3 = Checksum::generate(&wasm3);
+
+        let engine = make_compiling_engine(TESTING_MEMORY_LIMIT);
+        let make_module = |wasm: &[u8]| CachedModule {
+            module: compile(&engine, wasm).unwrap(),
+            engine: make_runtime_engine(TESTING_MEMORY_LIMIT),
+            size_estimate: 0,
+        };
+
+        // pinned forever
+        cache
+            .store(&checksum1, make_module(&wasm1), PinPolicy::default(), t0)
+            .unwrap();
+        // ttl
+        let policy = PinPolicy {
+            ttl: Some(secs(100)),
+            max_idle: None,
+        };
+        cache
+            .store(&checksum2, make_module(&wasm2), policy, t0)
+            .unwrap();
+        // max idle
+        let policy = PinPolicy {
+            ttl: None,
+            max_idle: Some(secs(10)),
+        };
+        cache
+            .store(&checksum3, make_module(&wasm3), policy, t0)
+            .unwrap();
+
+        assert_eq!(cache.prune(t0 + secs(5)), vec![]);
+        cache.load(&checksum3, t0 + secs(9)).unwrap().unwrap();
+        assert_eq!(cache.prune(t0 + secs(15)), vec![]);
+        assert_eq!(cache.prune(t0 + secs(19)), vec![checksum3]);
+        assert_eq!(cache.len(), 2);
+
+        assert_eq!(cache.prune(t0 + secs(99)), vec![]);
+        assert_eq!(cache.prune(t0 + secs(100)), vec![checksum2]);
+        assert_eq!(cache.len(), 1);
+
+        assert_eq!(cache.prune(t0 + secs(1_000_000)), vec![]);
+        assert!(cache.has(&checksum1));
+    }
+
+    #[test]
+    fn update_policy_works() {
+        let mut cache = PinnedMemoryCache::new();
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let secs = Duration::from_secs;
+
+        let wasm = wat::parse_str(r#"(module (func (export "one")))"#).unwrap();
+        let checksum = Checksum::generate(&wasm);
+        let policy = PinPolicy {
+            ttl: Some(secs(100)),
+            max_idle: None,
+        };
+
+        assert!(!cache.update_policy(&checksum, policy, t0));
+
+        let engine = make_compiling_engine(TESTING_MEMORY_LIMIT);
+        let module = CachedModule {
+            module: compile(&engine, &wasm).unwrap(),
+            engine: make_runtime_engine(TESTING_MEMORY_LIMIT),
+            size_estimate: 0,
+        };
+        cache.store(&checksum, module, policy, t0).unwrap();
+
+        // re-pinning with the same policy restarts the ttl
+        assert!(cache.update_policy(&checksum, policy, t0 + secs(50)));
+        assert_eq!(cache.prune(t0 + secs(100)), vec![]);
+        assert_eq!(cache.prune(t0 + secs(150)), vec![checksum]);
+    }
 }
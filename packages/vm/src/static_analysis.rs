@@ -1,9 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use strum::{AsRefStr, Display, EnumString};
-use wasmer::wasmparser::ExternalKind;
+use wasmer::wasmparser::{ExternalKind, Operator};
 
-use crate::parsed_wasm::ParsedWasm;
+use crate::errors::VmResult;
+use crate::parsed_wasm::{FunctionValidator, OpaqueDebug, ParsedWasm};
 
 /// An enum containing all available contract entrypoints.
 /// This also provides conversions to and from strings.
@@ -103,6 +104,184 @@ impl ExportInfo for &wasmer::Module {
     }
 }
 
+/// Returns the indices of all functions that contain floating-point operations,
+/// in ascending order.
+///
+/// This scans the function bodies pending validation, so it must be called before
+/// [`ParsedWasm::validate_funcs`]. Afterwards, no function bodies are available anymore
+/// and the result is empty.
+pub fn float_function_indices(module: &ParsedWasm) -> VmResult<Vec<u32>> {
+    let FunctionValidator::Pending(OpaqueDebug(funcs)) = &module.func_validator else {
+        return Ok(vec![]);
+    };
+
+    let mut indices = vec![];
+    for (func, body) in funcs {
+        let mut reader = body.get_operators_reader()?;
+        while !reader.eof() {
+            if is_float_operator(&reader.read()?) {
+                indices.push(func.index);
+                break;
+            }
+        }
+    }
+    indices.sort_unstable();
+    Ok(indices)
+}
+
+/// Returns the maximum nesting depth of direct function calls (`call`) in the module,
+/// or `None` if the direct calls are recursive.
+///
+/// Indirect calls (`call_indirect`) are not included since their targets are only
+/// known at runtime. A function without calls has a depth of 0 and a call to an import
+/// counts as one level. Like [`float_function_indices`], this returns a depth of 0 if the
+/// function bodies are not available anymore.
+pub fn max_direct_call_depth(module: &ParsedWasm) -> VmResult<Option<u32>> {
+    let FunctionValidator::Pending(OpaqueDebug(funcs)) = &module.func_validator else {
+        return Ok(Some(0));
+    };
+
+    let positions: HashMap<u32, usize> = funcs
+        .iter()
+        .enumerate()
+        .map(|(position, (func, _))| (func.index, position))
+        .collect();
+
+    // The call graph of the functions defined in the module
+    let mut callers = vec![vec![]; funcs.len()];
+    let mut pending_callees = vec![0usize; funcs.len()];
+    let mut depths = vec![0u32; funcs.len()];
+    for (position, (_, body)) in funcs.iter().enumerate() {
+        let mut callees = HashSet::new();
+        let mut reader = body.get_operators_reader()?;
+        while !reader.eof() {
+            if let Operator::Call { function_index } = reader.read()? {
+                match positions.get(&function_index) {
+                    Some(&callee) => {
+                        callees.insert(callee);
+                    }
+                    // an imported function
+                    None => depths[position] = 1,
+                }
+            }
+        }
+        pending_callees[position] = callees.len();
+        for callee in callees {
+            callers[callee].push(position);
+        }
+    }
+
+    // Process the functions in reverse topological order, starting with the ones that
+    // do not call other functions of the module. This is iterative on purpose to avoid
+    // a stack overflow for long call chains.
+    let mut ready: Vec<usize> = (0..funcs.len())
+        .filter(|&position| pending_callees[position] == 0)
+        .collect();
+    let mut processed = 0;
+    while let Some(callee) = ready.pop() {
+        processed += 1;
+        for &caller in &callers[callee] {
+            depths[caller] = depths[caller].max(depths[callee] + 1);
+            pending_callees[caller] -= 1;
+            if pending_callees[caller] == 0 {
+                ready.push(caller);
+            }
+        }
+    }
+
+    if processed < funcs.len() {
+        // the remaining functions are part of or call into a cycle
+        return Ok(None);
+    }
+    Ok(Some(depths.into_iter().max().unwrap_or_default()))
+}
+
+/// Returns true if the operator takes or produces a floating-point value
+/// (including loads, stores and reinterpretations)
+fn is_float_operator(op: &Operator) -> bool {
+    use Operator::*;
+
+    matches!(
+        op,
+        F32Load { .. }
+            | F64Load { .. }
+            | F32Store { .. }
+            | F64Store { .. }
+            | F32Const { .. }
+            | F64Const { .. }
+            | F32Eq
+            | F32Ne
+            | F32Lt
+            | F32Gt
+            | F32Le
+            | F32Ge
+            | F64Eq
+            | F64Ne
+            | F64Lt
+            | F64Gt
+            | F64Le
+            | F64Ge
+            | F32Abs
+            | F32Neg
+            | F32Ceil
+            | F32Floor
+            | F32Trunc
+            | F32Nearest
+            | F32Sqrt
+            | F32Add
+            | F32Sub
+            | F32Mul
+            | F32Div
+            | F32Min
+            | F32Max
+            | F32Copysign
+            | F64Abs
+            | F64Neg
+            | F64Ceil
+            | F64Floor
+            | F64Trunc
+            | F64Nearest
+            | F64Sqrt
+            | F64Add
+            | F64Sub
+            | F64Mul
+            | F64Div
+            | F64Min
+            | F64Max
+            | F64Copysign
+            | I32TruncF32S
+            | I32TruncF32U
+            | I32TruncF64S
+            | I32TruncF64U
+            | I64TruncF32S
+            | I64TruncF32U
+            | I64TruncF64S
+            | I64TruncF64U
+            | F32ConvertI32S
+            | F32ConvertI32U
+            | F32ConvertI64S
+            | F32ConvertI64U
+            | F32DemoteF64
+            | F64ConvertI32S
+            | F64ConvertI32U
+            | F64ConvertI64S
+            | F64ConvertI64U
+            | F64PromoteF32
+            | I32ReinterpretF32
+            | I64ReinterpretF64
+            | F32ReinterpretI32
+            | F64ReinterpretI64
+            | I32TruncSatF32S
+            | I32TruncSatF32U
+            | I32TruncSatF64S
+            | I32TruncSatF64U
+            | I64TruncSatF32S
+            | I64TruncSatF32U
+            | I64TruncSatF64S
+            | I64TruncSatF64U
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -115,6 +294,7 @@ mod tests {
 
     static CONTRACT: &[u8] = include_bytes!("../testdata/hackatom.wasm");
     static CORRUPTED: &[u8] = include_bytes!("../testdata/corrupted.wasm");
+    static FLOATY: &[u8] = include_bytes!("../testdata/floaty_2.0.wasm");
 
     #[test]
     fn deserialize_exports_works() {
@@ -265,6 +445,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn float_function_indices_works() {
+        let wasm = wat::parse_str(
+            r#"(module
+                (import "env" "abort" (func (param i32)))
+                (func (param f32) (result f32) local.get 0)
+                (func (result f64) f64.const 1.5)
+                (func (result i32) i32.const 1 f32.convert_i32_s i32.trunc_sat_f32_s)
+                (func (result i32) i32.const 1)
+                (func (param i32) (result i64) local.get 0 i64.load)
+                (func (param i32) local.get 0 f32.const 0 f32.store)
+            )"#,
+        )
+        .unwrap();
+        let module = ParsedWasm::parse(&wasm).unwrap();
+        // function indices include imported functions.
+        // A float parameter alone does not count as a float operation.
+        assert_eq!(float_function_indices(&module).unwrap(), [2, 3, 6]);
+
+        let module = ParsedWasm::parse(FLOATY).unwrap();
+        assert!(!float_function_indices(&module).unwrap().is_empty());
+
+        // no function bodies are available after validation
+        let mut module = ParsedWasm::parse(FLOATY).unwrap();
+        module.validate_funcs().unwrap();
+        assert_eq!(float_function_indices(&module).unwrap(), []);
+    }
+
+    #[test]
+    fn max_direct_call_depth_works() {
+        let wasm = wat::parse_str(
+            r#"(module
+                (import "env" "abort" (func (param i32)))
+                (func $leaf nop)
+                (func $middle call $leaf)
+                (func $top call $middle call $leaf)
+                (func $aborts i32.const 0 call 0)
+                (func $calls_aborts call $aborts)
+            )"#,
+        )
+        .unwrap();
+        let module = ParsedWasm::parse(&wasm).unwrap();
+        assert_eq!(max_direct_call_depth(&module).unwrap(), Some(2));
+
+        // no calls
+        let wasm = wat::parse_str(r#"(module (func nop))"#).unwrap();
+        let module = ParsedWasm::parse(&wasm).unwrap();
+        assert_eq!(max_direct_call_depth(&module).unwrap(), Some(0));
+
+        // recursion
+        let wasm = wat::parse_str(
+            r#"(module
+                (func $leaf nop)
+                (func $recursive call $leaf call $recursive)
+            )"#,
+        )
+        .unwrap();
+        let module = ParsedWasm::parse(&wasm).unwrap();
+        assert_eq!(max_direct_call_depth(&module).unwrap(), None);
+
+        // mutual recursion
+        let wasm = wat::parse_str(
+            r#"(module
+                (func $a call $b)
+                (func $b call $a)
+                (func $c call $a)
+            )"#,
+        )
+        .unwrap();
+        let module = ParsedWasm::parse(&wasm).unwrap();
+        assert_eq!(max_direct_call_depth(&module).unwrap(), None);
+
+        // real contracts work
+        let module = ParsedWasm::parse(CONTRACT).unwrap();
+        max_direct_call_depth(&module).unwrap();
+    }
+
     #[test]
     fn entrypoint_from_string_works() {
         assert_eq!(
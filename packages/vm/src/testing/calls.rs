@@ -5,7 +5,8 @@ use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Serialize};
 
 use cosmwasm_std::{
-    ContractResult, CustomMsg, Env, MessageInfo, MigrateInfo, QueryResponse, Reply, Response,
+    from_json, ContractResult, CustomMsg, Env, MessageInfo, MigrateInfo, QueryResponse, Reply,
+    Response,
 };
 #[cfg(feature = "stargate")]
 use cosmwasm_std::{
@@ -160,6 +161,54 @@ where
     call_query(instance, &env, &serialized_msg).expect("VM error")
 }
 
+/// Runs a paginated query page by page and collects the items of all pages.
+///
+/// `msg` creates the query message for a page from the cursor returned by the previous page
+/// (`None` for the first page). `page` splits the deserialized response into the items of the page
+/// and the cursor of the next page. The iteration ends as soon as `page` returns no cursor.
+///
+/// Returns the first error returned by the contract.
+///
+/// ```ignore
+/// let keys = query_all_pages(
+///     &mut deps,
+///     mock_env(),
+///     |start_after| QueryMsg::Page { start_after, limit: 100 },
+///     |res: PageResponse| (res.items, res.next_start_after),
+/// )
+/// .unwrap();
+/// ```
+pub fn query_all_pages<A, S, Q, M, R, C, T>(
+    instance: &mut Instance<A, S, Q>,
+    env: Env,
+    mut msg: impl FnMut(Option<C>) -> M,
+    mut page: impl FnMut(R) -> (Vec<T>, Option<C>),
+) -> ContractResult<Vec<T>>
+where
+    A: BackendApi + 'static,
+    S: Storage + 'static,
+    Q: Querier + 'static,
+    M: Serialize + JsonSchema,
+    R: DeserializeOwned,
+{
+    let mut items = Vec::new();
+    let mut cursor = None;
+    loop {
+        let response = match query(instance, env.clone(), msg(cursor)) {
+            ContractResult::Ok(response) => response,
+            ContractResult::Err(err) => return ContractResult::Err(err),
+        };
+        let response: R =
+            from_json(response).expect("Testing error: Could not deserialize query response");
+        let (page_items, next) = page(response);
+        items.extend(page_items);
+        match next {
+            Some(next) => cursor = Some(next),
+            None => return ContractResult::Ok(items),
+        }
+    }
+}
+
 // ibc_channel_open mimics the call signature of the smart contracts.
 // thus it moves env and channel rather than take them as reference.
 // this is inefficient here, but only used in test code
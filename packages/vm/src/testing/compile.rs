@@ -0,0 +1,55 @@
+//! Helpers to check properties of the compilation output in tests.
+//! They should be imported via full path to ensure there is no confusion
+//! use cosmwasm_vm::testing::X
+use crate::wasm_backend::{compile, make_compiling_engine};
+
+/// Compiles the given Wasm twice, each time using a fresh engine, and asserts
+/// that the serialized modules are byte-for-byte identical.
+///
+/// Nodes must produce the same artifacts for the same Wasm. This helps catching
+/// nondeterminism introduced by compiler flags or middlewares.
+///
+/// Panics if compilation or serialization fails or if the outputs differ.
+pub fn assert_deterministic_compile(wasm: &[u8]) {
+    let compile_and_serialize = || {
+        let engine = make_compiling_engine(None);
+        let module = compile(&engine, wasm).expect("Error compiling Wasm");
+        module.serialize().expect("Error serializing module")
+    };
+
+    let first = compile_and_serialize();
+    let second = compile_and_serialize();
+
+    if first != second {
+        let first_difference = first
+            .iter()
+            .zip(second.iter())
+            .position(|(a, b)| a != b)
+            .unwrap_or(first.len().min(second.len()));
+        panic!(
+            "Compilation is not deterministic: serialized modules differ at byte {first_difference} (lengths: {} and {})",
+            first.len(),
+            second.len()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static HACKATOM: &[u8] = include_bytes!("../../testdata/hackatom.wasm");
+    static CYBERPUNK: &[u8] = include_bytes!("../../testdata/cyberpunk.wasm");
+
+    #[test]
+    fn assert_deterministic_compile_works() {
+        assert_deterministic_compile(HACKATOM);
+        assert_deterministic_compile(CYBERPUNK);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error compiling Wasm")]
+    fn assert_deterministic_compile_panics_for_invalid_wasm() {
+        assert_deterministic_compile(b"not a wasm");
+    }
+}
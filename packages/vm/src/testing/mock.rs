@@ -1,8 +1,10 @@
 use bech32::primitives::decode::CheckedHrpstring;
 use bech32::{encode, Bech32, Hrp};
 use cosmwasm_std::{
-    Addr, BlockInfo, Coin, ContractInfo, Env, MessageInfo, Timestamp, TransactionInfo,
+    from_json, to_json_vec, Addr, AuthzMsg, BlockInfo, Coin, ContractInfo, Env, MessageInfo,
+    Response, Timestamp, TransactionInfo,
 };
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 use super::querier::MockQuerier;
@@ -296,10 +298,33 @@ pub fn mock_info(sender: &str, funds: &[Coin]) -> MessageInfo {
     }
 }
 
+/// Returns all `CosmosMsg::Authz` messages emitted in the response, in order.
+///
+/// This allows asserting that a contract dispatched authz messages. It works independently
+/// of the `cosmwasm_2_0` feature of cosmwasm-std, which is not enabled by the VM itself.
+pub fn authz_msgs<T: Serialize>(response: &Response<T>) -> Vec<AuthzMsg> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum AuthzOnly {
+        Authz(AuthzMsg),
+    }
+
+    response
+        .messages
+        .iter()
+        .filter_map(|sub_msg| {
+            let json = to_json_vec(&sub_msg.msg).ok()?;
+            from_json::<AuthzOnly>(json)
+                .ok()
+                .map(|AuthzOnly::Authz(msg)| msg)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::coins;
+    use cosmwasm_std::{coins, BankMsg};
 
     #[test]
     fn mock_env_matches_mock_contract_addr() {
@@ -307,6 +332,15 @@ mod tests {
         assert_eq!(contract_address, Addr::unchecked(MOCK_CONTRACT_ADDR));
     }
 
+    #[test]
+    fn authz_msgs_ignores_other_messages() {
+        let response: Response = Response::new().add_message(BankMsg::Send {
+            to_address: "friend".to_string(),
+            amount: coins(1, "token"),
+        });
+        assert!(authz_msgs(&response).is_empty());
+    }
+
     #[test]
     fn mock_info_works() {
         let info = mock_info("my name", &coins(100, "atom"));
@@ -1,12 +1,20 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
 use serde::de::DeserializeOwned;
 
 use cosmwasm_std::testing::{MockQuerier as StdMockQuerier, MockQuerierCustomHandlerResult};
 use cosmwasm_std::{
-    to_json_binary, to_json_vec, Binary, Coin, ContractResult, CustomQuery, Empty, Querier as _,
-    QueryRequest, SystemError, SystemResult,
+    from_json, to_json_binary, to_json_vec, Addr, Binary, Coin, CommunityPoolResponse,
+    ContractResult, ContractsByCodeResponse, CustomQuery, DecCoin, DistributionQuery, Empty, Env,
+    Querier as _, QuerierResult, QueryRequest, StdResult, SystemError, SystemResult, Uint128,
+    WasmQuery,
 };
 
-use crate::{BackendError, BackendResult, GasInfo, Querier};
+use crate::calls::call_query;
+use crate::instance::Instance;
+use crate::{BackendApi, BackendError, BackendResult, GasInfo, Querier, Storage, VmError};
 
 const GAS_COST_QUERY_FLAT: u64 = 100_000;
 /// Gas per request byte
@@ -17,15 +25,30 @@ const GAS_COST_QUERY_RESPONSE_MULTIPLIER: u64 = 100;
 /// MockQuerier holds an immutable table of bank balances
 pub struct MockQuerier<C: CustomQuery + DeserializeOwned = Empty> {
     querier: StdMockQuerier<C>,
+    /// The funds returned for `DistributionQuery::CommunityPool`
+    community_pool: Vec<DecCoin>,
+    /// The contracts returned for `WasmQuery::ContractsByCode`, by code ID
+    contracts_by_code: HashMap<u64, Vec<Addr>>,
+    /// The number of queries processed so far
+    query_count: Cell<u64>,
 }
 
 impl<C: CustomQuery + DeserializeOwned> MockQuerier<C> {
     pub fn new(balances: &[(&str, &[Coin])]) -> Self {
         MockQuerier {
             querier: StdMockQuerier::new(balances),
+            community_pool: Vec::new(),
+            contracts_by_code: HashMap::new(),
+            query_count: Cell::new(0),
         }
     }
 
+    /// Returns the number of queries this querier processed. Each sub-query of a
+    /// `QueryRequest::Multi` is counted individually.
+    pub fn query_count(&self) -> u64 {
+        self.query_count.get()
+    }
+
     /// Set a new balance for the given address and return the old balance
     pub fn update_balance(
         &mut self,
@@ -35,6 +58,19 @@ impl<C: CustomQuery + DeserializeOwned> MockQuerier<C> {
         self.querier.bank.update_balance(addr, balance)
     }
 
+    /// Removes the given coins from the balance of the given address and the total supply,
+    /// like the chain does when processing a `BankMsg::Burn` sent by that address.
+    ///
+    /// Errors if the address does not have enough funds.
+    pub fn burn(&mut self, addr: impl Into<String>, amount: &[Coin]) -> StdResult<()> {
+        self.querier.bank.burn(addr, amount)
+    }
+
+    /// Returns the total amount of the given denom burned via [`MockQuerier::burn`].
+    pub fn burned(&self, denom: &str) -> Uint128 {
+        self.querier.bank.burned(denom)
+    }
+
     #[cfg(feature = "staking")]
     pub fn update_staking(
         &mut self,
@@ -45,6 +81,28 @@ impl<C: CustomQuery + DeserializeOwned> MockQuerier<C> {
         self.querier.staking.update(denom, validators, delegations);
     }
 
+    /// Set the unbonding delegations returned for `StakingQuery::UnbondingDelegations`
+    #[cfg(feature = "staking")]
+    pub fn update_unbonding_delegations(
+        &mut self,
+        unbonding_delegations: &[cosmwasm_std::UnbondingDelegation],
+    ) {
+        self.querier
+            .staking
+            .set_unbonding_delegations(unbonding_delegations);
+    }
+
+    /// Set the funds returned for `DistributionQuery::CommunityPool`
+    pub fn update_community_pool(&mut self, pool: Vec<DecCoin>) {
+        self.community_pool = pool;
+    }
+
+    /// Set the contracts returned for `WasmQuery::ContractsByCode` with the given code ID.
+    /// Queries for unknown code IDs fail with `SystemError::NoSuchCode`.
+    pub fn update_contracts_by_code(&mut self, code_id: u64, contracts: Vec<Addr>) {
+        self.contracts_by_code.insert(code_id, contracts);
+    }
+
     pub fn update_wasm<WH>(&mut self, handler: WH)
     where
         WH: Fn(&cosmwasm_std::WasmQuery) -> cosmwasm_std::QuerierResult + 'static,
@@ -59,6 +117,51 @@ impl<C: CustomQuery + DeserializeOwned> MockQuerier<C> {
         self.querier = self.querier.with_custom_handler(handler);
         self
     }
+
+    /// Answers the queries for which this querier holds the state and forwards
+    /// all other queries to the cosmwasm-std mock querier.
+    fn handle_query(&self, request: &QueryRequest<C>) -> QuerierResult {
+        match request {
+            QueryRequest::Distribution(DistributionQuery::CommunityPool {}) => {
+                let response = CommunityPoolResponse::new(self.community_pool.clone());
+                SystemResult::Ok(to_json_binary(&response).into())
+            }
+            QueryRequest::Wasm(WasmQuery::ContractsByCode {
+                code_id,
+                start_after,
+                limit,
+            }) => self.query_contracts_by_code(*code_id, start_after.as_deref(), *limit),
+            _ => self.querier.handle_query(request),
+        }
+    }
+
+    /// Answers `WasmQuery::ContractsByCode` requests.
+    fn query_contracts_by_code(
+        &self,
+        code_id: u64,
+        start_after: Option<&str>,
+        limit: Option<u32>,
+    ) -> QuerierResult {
+        let Some(contracts) = self.contracts_by_code.get(&code_id) else {
+            return SystemResult::Err(SystemError::NoSuchCode { code_id });
+        };
+        let start = match start_after {
+            Some(start_after) => contracts
+                .iter()
+                .position(|addr| addr.as_str() == start_after)
+                .map_or(contracts.len(), |pos| pos + 1),
+            None => 0,
+        };
+        let limit = limit.map_or(usize::MAX, |limit| limit as usize);
+        let response = ContractsByCodeResponse::new(
+            contracts[start..]
+                .iter()
+                .take(limit)
+                .map(|addr| addr.to_string())
+                .collect(),
+        );
+        SystemResult::Ok(to_json_binary(&response).into())
+    }
 }
 
 impl<C: CustomQuery + DeserializeOwned> Querier for MockQuerier<C> {
@@ -67,7 +170,12 @@ impl<C: CustomQuery + DeserializeOwned> Querier for MockQuerier<C> {
         bin_request: &[u8],
         gas_limit: u64,
     ) -> BackendResult<SystemResult<ContractResult<Binary>>> {
-        let response = self.querier.raw_query(bin_request);
+        self.query_count.set(self.query_count.get() + 1);
+        let response = match from_json::<QueryRequest<C>>(bin_request) {
+            Ok(request) => self.handle_query(&request),
+            // let the std querier create the error response
+            Err(_) => self.querier.raw_query(bin_request),
+        };
         let gas_info = GasInfo::with_externally_used(
             GAS_COST_QUERY_FLAT
                 + (GAS_COST_QUERY_REQUEST_MULTIPLIER * (bin_request.len() as u64))
@@ -110,12 +218,315 @@ impl MockQuerier {
     }
 }
 
+/// The default for [`QueryRouter::max_depth`].
+const DEFAULT_MAX_QUERY_DEPTH: usize = 10;
+
+/// A contract that Wasm queries can be routed to.
+trait RoutedContract {
+    fn query_smart(
+        &self,
+        msg: &[u8],
+        gas_limit: u64,
+    ) -> BackendResult<SystemResult<ContractResult<Binary>>>;
+
+    fn query_raw(&self, key: &[u8]) -> BackendResult<SystemResult<ContractResult<Binary>>>;
+}
+
+struct InstanceRoute<A: BackendApi, S: Storage, Q: Querier> {
+    /// The instance is held weakly, since its querier holds the router which holds this route.
+    /// A strong reference would create a reference cycle and leak all registered instances.
+    instance: Weak<RefCell<Instance<A, S, Q>>>,
+    env: Env,
+}
+
+impl<A, S, Q> InstanceRoute<A, S, Q>
+where
+    A: BackendApi + 'static,
+    S: Storage + 'static,
+    Q: Querier + 'static,
+{
+    fn with_instance<F>(&self, callback: F) -> BackendResult<SystemResult<ContractResult<Binary>>>
+    where
+        F: FnOnce(&mut Instance<A, S, Q>) -> BackendResult<SystemResult<ContractResult<Binary>>>,
+    {
+        let Some(instance) = self.instance.upgrade() else {
+            // the instance was dropped, so the contract is gone
+            return (
+                Ok(SystemResult::Err(SystemError::NoSuchContract {
+                    addr: self.env.contract.address.to_string(),
+                })),
+                GasInfo::free(),
+            );
+        };
+        let Ok(mut instance) = instance.try_borrow_mut() else {
+            // Querying a contract instance that is currently executing is not supported
+            return (
+                Ok(SystemResult::Err(SystemError::UnsupportedRequest {
+                    kind: format!(
+                        "re-entrant query into contract {}",
+                        self.env.contract.address
+                    ),
+                })),
+                GasInfo::free(),
+            );
+        };
+        callback(&mut instance)
+    }
+}
+
+impl<A, S, Q> RoutedContract for InstanceRoute<A, S, Q>
+where
+    A: BackendApi + 'static,
+    S: Storage + 'static,
+    Q: Querier + 'static,
+{
+    fn query_smart(
+        &self,
+        msg: &[u8],
+        gas_limit: u64,
+    ) -> BackendResult<SystemResult<ContractResult<Binary>>> {
+        self.with_instance(|instance| {
+            // Only make the gas limit of the query available to the call, such that
+            // the contract stops executing as soon as the limit is reached.
+            let gas_before = instance.get_gas_left();
+            let gas_available = gas_before.min(gas_limit);
+            instance.set_gas_left(gas_available);
+            let result = call_query(instance, &self.env, msg);
+            let gas_used = gas_available.saturating_sub(instance.get_gas_left());
+            instance.set_gas_left(gas_before - gas_used);
+            let gas_info = GasInfo::with_externally_used(gas_used);
+
+            match result {
+                Ok(response) => (Ok(SystemResult::Ok(response)), gas_info),
+                Err(VmError::GasDepletion { .. }) => (Err(BackendError::out_of_gas()), gas_info),
+                Err(err) => (Err(BackendError::unknown(err.to_string())), gas_info),
+            }
+        })
+    }
+
+    fn query_raw(&self, key: &[u8]) -> BackendResult<SystemResult<ContractResult<Binary>>> {
+        self.with_instance(|instance| {
+            let mut gas_info = GasInfo::free();
+            let result = instance.with_storage(|storage| {
+                let (result, storage_gas_info) = storage.get(key);
+                gas_info = storage_gas_info;
+                Ok(result)
+            });
+            match result {
+                Ok(Ok(value)) => (
+                    // An empty response is used for missing keys, see `QuerierWrapper::query_wasm_raw`
+                    Ok(SystemResult::Ok(ContractResult::Ok(
+                        value.unwrap_or_default().into(),
+                    ))),
+                    gas_info,
+                ),
+                Ok(Err(err)) => (Err(err), gas_info),
+                Err(err) => (Err(BackendError::unknown(err.to_string())), gas_info),
+            }
+        })
+    }
+}
+
+/// A registry of contract instances shared between multiple [`RoutingQuerier`]s.
+///
+/// Cloning the router is cheap and returns a handle to the same registry. This allows
+/// every instance in an integration test to use its own querier while all of them
+/// can query each other.
+///
+/// This is designed for single-threaded integration tests only.
+#[derive(Clone)]
+pub struct QueryRouter {
+    contracts: Rc<RefCell<HashMap<String, Rc<dyn RoutedContract>>>>,
+    depth: Rc<Cell<usize>>,
+    max_depth: usize,
+}
+
+impl QueryRouter {
+    pub fn new() -> Self {
+        QueryRouter {
+            contracts: Rc::default(),
+            depth: Rc::default(),
+            max_depth: DEFAULT_MAX_QUERY_DEPTH,
+        }
+    }
+
+    /// Sets the maximum number of nested contract queries. Queries exceeding
+    /// this depth fail with a backend error.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// The maximum number of nested contract queries.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Registers a contract instance under the given address. Smart and raw queries
+    /// to this address are then executed in this instance.
+    ///
+    /// The `env` is passed to the contract's `query` entry point, so its contract address
+    /// should match `address`.
+    ///
+    /// The router only holds a weak reference to the instance, so the caller has to keep it
+    /// alive. Queries to contracts whose instance was dropped fail with
+    /// `SystemError::NoSuchContract`.
+    pub fn register<A, S, Q>(
+        &self,
+        address: impl Into<String>,
+        instance: &Rc<RefCell<Instance<A, S, Q>>>,
+        env: Env,
+    ) where
+        A: BackendApi + 'static,
+        S: Storage + 'static,
+        Q: Querier + 'static,
+    {
+        self.contracts.borrow_mut().insert(
+            address.into(),
+            Rc::new(InstanceRoute {
+                instance: Rc::downgrade(instance),
+                env,
+            }),
+        );
+    }
+
+    fn route(
+        &self,
+        request: &WasmQuery,
+        gas_limit: u64,
+    ) -> Option<BackendResult<SystemResult<ContractResult<Binary>>>> {
+        let (contract_addr, is_smart) = match request {
+            WasmQuery::Smart { contract_addr, .. } => (contract_addr, true),
+            WasmQuery::Raw { contract_addr, .. } => (contract_addr, false),
+            _ => return None,
+        };
+        // clone the route out of the registry to not hold the borrow during the call
+        let contract = self.contracts.borrow().get(contract_addr).cloned()?;
+
+        let depth = self.depth.get();
+        if is_smart && depth >= self.max_depth {
+            return Some((
+                Err(BackendError::user_err(format!(
+                    "Maximum query depth of {} exceeded",
+                    self.max_depth
+                ))),
+                GasInfo::free(),
+            ));
+        }
+
+        Some(match request {
+            WasmQuery::Smart { msg, .. } => {
+                self.depth.set(depth + 1);
+                let result = contract.query_smart(msg, gas_limit);
+                self.depth.set(depth);
+                result
+            }
+            WasmQuery::Raw { key, .. } => contract.query_raw(key),
+            _ => unreachable!(),
+        })
+    }
+}
+
+impl Default for QueryRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A querier that routes Wasm smart and raw queries to contract instances registered in
+/// a [`QueryRouter`] and handles all other queries using a [`MockQuerier`].
+///
+/// Queries to unregistered contracts are handled by the [`MockQuerier`] as well.
+/// Querying a contract instance that is currently executing, e.g. a contract querying
+/// itself, is not supported and fails with `SystemError::UnsupportedRequest`.
+pub struct RoutingQuerier<C: CustomQuery + DeserializeOwned = Empty> {
+    router: QueryRouter,
+    fallback: MockQuerier<C>,
+}
+
+impl<C: CustomQuery + DeserializeOwned> RoutingQuerier<C> {
+    pub fn new(router: &QueryRouter, fallback: MockQuerier<C>) -> Self {
+        RoutingQuerier {
+            router: router.clone(),
+            fallback,
+        }
+    }
+
+    /// Access to the underlying [`MockQuerier`], e.g. to update balances.
+    pub fn fallback_mut(&mut self) -> &mut MockQuerier<C> {
+        &mut self.fallback
+    }
+}
+
+impl<C: CustomQuery + DeserializeOwned> Querier for RoutingQuerier<C> {
+    fn query_raw(
+        &self,
+        bin_request: &[u8],
+        gas_limit: u64,
+    ) -> BackendResult<SystemResult<ContractResult<Binary>>> {
+        if let Ok(QueryRequest::Wasm(request)) = from_json::<QueryRequest<C>>(bin_request) {
+            if let Some(result) = self.router.route(&request, gas_limit) {
+                return result;
+            }
+        }
+        self.fallback.query_raw(bin_request, gas_limit)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::{coin, from_json, AllBalanceResponse, BalanceResponse, BankQuery};
+    use crate::calls::call_instantiate;
+    use crate::testing::{mock_env, mock_info, MockApi, MockStorage};
+    use crate::{Backend, InstanceOptions};
+    use cosmwasm_std::{
+        coin, coins, Addr, AllBalanceResponse, BalanceResponse, BankQuery, ContractInfo, Decimal256,
+    };
 
     const DEFAULT_QUERY_GAS_LIMIT: u64 = 300_000;
+    const ROUTED_GAS_LIMIT: u64 = 500_000_000_000;
+
+    static HACKATOM: &[u8] = include_bytes!("../../testdata/hackatom.wasm");
+
+    type RoutedInstance = Instance<MockApi, MockStorage, RoutingQuerier>;
+
+    /// Creates an instantiated hackatom instance and registers it in the router
+    fn routed_hackatom(
+        router: &QueryRouter,
+        address: &str,
+        balances: &[(&str, &[Coin])],
+    ) -> Rc<RefCell<RoutedInstance>> {
+        let backend = Backend {
+            api: MockApi::default(),
+            storage: MockStorage::default(),
+            querier: RoutingQuerier::new(router, MockQuerier::new(balances)),
+        };
+        let options = InstanceOptions {
+            gas_limit: ROUTED_GAS_LIMIT,
+        };
+        let mut instance = Instance::from_code(HACKATOM, backend, options, None).unwrap();
+
+        let env = routed_env(address);
+        let info = mock_info(&instance.api().addr_make("creator"), &[]);
+        let verifier = instance.api().addr_make("verifies");
+        let beneficiary = instance.api().addr_make("benefits");
+        let msg = format!(r#"{{"verifier": "{verifier}", "beneficiary": "{beneficiary}"}}"#);
+        call_instantiate::<_, _, _, Empty>(&mut instance, &env, &info, msg.as_bytes())
+            .unwrap()
+            .unwrap();
+
+        let instance = Rc::new(RefCell::new(instance));
+        router.register(address, &instance, env);
+        instance
+    }
+
+    fn routed_env(address: &str) -> Env {
+        let mut env = mock_env();
+        env.contract = ContractInfo {
+            address: Addr::unchecked(address),
+        };
+        env
+    }
 
     #[test]
     fn query_raw_fails_when_out_of_gas() {
@@ -131,6 +542,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn query_count_works() {
+        let querier: MockQuerier<Empty> = MockQuerier::new(&[("foobar", &coins(5, "ELF"))]);
+        assert_eq!(querier.query_count(), 0);
+
+        let request: QueryRequest<Empty> = QueryRequest::Bank(BankQuery::Balance {
+            address: "foobar".to_string(),
+            denom: "ELF".to_string(),
+        });
+        querier.query(&request, DEFAULT_QUERY_GAS_LIMIT).0.unwrap();
+        querier.query(&request, DEFAULT_QUERY_GAS_LIMIT).0.unwrap();
+        assert_eq!(querier.query_count(), 2);
+
+        // failing queries are counted as well
+        let _ = querier.query_raw(b"broken request", DEFAULT_QUERY_GAS_LIMIT);
+        assert_eq!(querier.query_count(), 3);
+    }
+
     #[test]
     #[allow(deprecated)]
     fn bank_querier_all_balances() {
@@ -152,6 +581,31 @@ mod tests {
         assert_eq!(&res.amount, &balance);
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn bank_querier_burn_works() {
+        let addr = String::from("foobar");
+        let balance = vec![coin(123, "ELF"), coin(777, "FLY")];
+        let mut querier = MockQuerier::new(&[(&addr, &balance)]);
+
+        querier.burn(&addr, &[coin(100, "ELF")]).unwrap();
+        assert_eq!(querier.burned("ELF"), Uint128::new(100));
+        assert_eq!(querier.burned("FLY"), Uint128::zero());
+        querier.burn(&addr, &[coin(24, "ELF")]).unwrap_err();
+
+        let all = querier
+            .query::<Empty>(
+                &BankQuery::AllBalances { address: addr }.into(),
+                DEFAULT_QUERY_GAS_LIMIT,
+            )
+            .0
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let res: AllBalanceResponse = from_json(all).unwrap();
+        assert_eq!(res.amount, [coin(23, "ELF"), coin(777, "FLY")]);
+    }
+
     #[test]
     fn bank_querier_one_balance() {
         let addr = String::from("foobar");
@@ -233,4 +687,244 @@ mod tests {
         let res: BalanceResponse = from_json(miss).unwrap();
         assert_eq!(res.amount, coin(0, "ELF"));
     }
+
+    #[test]
+    fn community_pool_query_works() {
+        let mut querier: MockQuerier<Empty> = MockQuerier::new(&[]);
+        let request = to_json_vec(&QueryRequest::<Empty>::Distribution(
+            DistributionQuery::CommunityPool {},
+        ))
+        .unwrap();
+
+        // empty by default
+        let res = querier
+            .query_raw(&request, DEFAULT_QUERY_GAS_LIMIT)
+            .0
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let res: CommunityPoolResponse = from_json(res).unwrap();
+        assert_eq!(res.pool, vec![]);
+
+        let pool = vec![DecCoin::new(Decimal256::percent(150), "uatom")];
+        querier.update_community_pool(pool.clone());
+        let res = querier
+            .query_raw(&request, DEFAULT_QUERY_GAS_LIMIT)
+            .0
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let res: CommunityPoolResponse = from_json(res).unwrap();
+        assert_eq!(res.pool, pool);
+    }
+
+    #[test]
+    fn contracts_by_code_query_works() {
+        let mut querier: MockQuerier<Empty> = MockQuerier::new(&[]);
+        let query = |querier: &MockQuerier, request: WasmQuery| {
+            querier
+                .query::<Empty>(&request.into(), DEFAULT_QUERY_GAS_LIMIT)
+                .0
+                .unwrap()
+        };
+
+        // unknown code
+        let request = WasmQuery::ContractsByCode {
+            code_id: 5,
+            start_after: None,
+            limit: None,
+        };
+        let err = query(&querier, request.clone()).unwrap_err();
+        assert_eq!(err, SystemError::NoSuchCode { code_id: 5 });
+
+        querier.update_contracts_by_code(
+            5,
+            vec![
+                Addr::unchecked("contract1"),
+                Addr::unchecked("contract2"),
+                Addr::unchecked("contract3"),
+            ],
+        );
+        let res = query(&querier, request).unwrap().unwrap();
+        let res: ContractsByCodeResponse = from_json(res).unwrap();
+        assert_eq!(res.contracts, ["contract1", "contract2", "contract3"]);
+
+        // pagination
+        let request = WasmQuery::ContractsByCode {
+            code_id: 5,
+            start_after: Some("contract1".to_string()),
+            limit: Some(1),
+        };
+        let res = query(&querier, request).unwrap().unwrap();
+        let res: ContractsByCodeResponse = from_json(res).unwrap();
+        assert_eq!(res.contracts, ["contract2"]);
+
+        // other Wasm queries are not affected
+        let request = WasmQuery::ContractInfo {
+            contract_addr: "contract1".to_string(),
+        };
+        let err = query(&querier, request).unwrap_err();
+        assert_eq!(
+            err,
+            SystemError::NoSuchContract {
+                addr: "contract1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn routing_querier_routes_smart_queries() {
+        let router = QueryRouter::new();
+        let _contract = routed_hackatom(&router, "contract", &[("rich", &coins(123, "ELF"))]);
+        let querier = RoutingQuerier::new(&router, MockQuerier::new(&[]));
+
+        // the contract performs a bank query using its own querier
+        let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: "contract".to_string(),
+            msg: br#"{"other_balance":{"address":"rich"}}"#.into(),
+        });
+        let (result, gas_info) =
+            querier.query_raw(&to_json_vec(&request).unwrap(), ROUTED_GAS_LIMIT);
+        let response = result.unwrap().unwrap().unwrap();
+        let balance: AllBalanceResponse = from_json(response).unwrap();
+        assert_eq!(balance.amount, coins(123, "ELF"));
+        assert!(gas_info.externally_used > 0);
+    }
+
+    #[test]
+    fn routing_querier_routes_raw_queries() {
+        let router = QueryRouter::new();
+        let _contract = routed_hackatom(&router, "contract", &[]);
+        let querier = RoutingQuerier::new(&router, MockQuerier::new(&[]));
+
+        let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Raw {
+            contract_addr: "contract".to_string(),
+            key: b"config".into(),
+        });
+        let (result, _gas_info) =
+            querier.query_raw(&to_json_vec(&request).unwrap(), DEFAULT_QUERY_GAS_LIMIT);
+        let response = result.unwrap().unwrap().unwrap();
+        assert!(!response.is_empty());
+
+        // missing keys return an empty response
+        let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Raw {
+            contract_addr: "contract".to_string(),
+            key: b"foo".into(),
+        });
+        let (result, _gas_info) =
+            querier.query_raw(&to_json_vec(&request).unwrap(), DEFAULT_QUERY_GAS_LIMIT);
+        let response = result.unwrap().unwrap().unwrap();
+        assert!(response.is_empty());
+    }
+
+    #[test]
+    fn routing_querier_delegates_other_queries() {
+        let router = QueryRouter::new();
+        let querier = RoutingQuerier::new(&router, MockQuerier::new(&[("rich", &coins(5, "ELF"))]));
+
+        let request: QueryRequest<Empty> = QueryRequest::Bank(BankQuery::Balance {
+            address: "rich".to_string(),
+            denom: "ELF".to_string(),
+        });
+        let (result, _gas_info) =
+            querier.query_raw(&to_json_vec(&request).unwrap(), DEFAULT_QUERY_GAS_LIMIT);
+        let balance: BalanceResponse = from_json(result.unwrap().unwrap().unwrap()).unwrap();
+        assert_eq!(balance.amount, coin(5, "ELF"));
+
+        // unknown contracts
+        let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: "unknown".to_string(),
+            msg: b"{}".into(),
+        });
+        let (result, _gas_info) =
+            querier.query_raw(&to_json_vec(&request).unwrap(), DEFAULT_QUERY_GAS_LIMIT);
+        match result.unwrap() {
+            SystemResult::Err(SystemError::NoSuchContract { addr }) => assert_eq!(addr, "unknown"),
+            res => panic!("Unexpected result: {res:?}"),
+        }
+    }
+
+    #[test]
+    fn routing_querier_enforces_max_depth() {
+        let router = QueryRouter::new().with_max_depth(0);
+        let _contract = routed_hackatom(&router, "contract", &[]);
+        let querier = RoutingQuerier::new(&router, MockQuerier::new(&[]));
+
+        let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: "contract".to_string(),
+            msg: br#"{"verifier":{}}"#.into(),
+        });
+        let (result, _gas_info) =
+            querier.query_raw(&to_json_vec(&request).unwrap(), DEFAULT_QUERY_GAS_LIMIT);
+        match result.unwrap_err() {
+            BackendError::UserErr { msg } => {
+                assert_eq!(msg, "Maximum query depth of 0 exceeded")
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn routing_querier_enforces_gas_limit_during_call() {
+        let router = QueryRouter::new();
+        let contract = routed_hackatom(&router, "contract", &[]);
+        let querier = RoutingQuerier::new(&router, MockQuerier::new(&[]));
+        let gas_before = contract.borrow_mut().get_gas_left();
+
+        // far more work than the gas limit allows
+        let gas_limit = 1_000_000;
+        let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: "contract".to_string(),
+            msg: br#"{"recurse":{"depth":0,"work":1000000}}"#.into(),
+        });
+        let (result, gas_info) = querier.query_raw(&to_json_vec(&request).unwrap(), gas_limit);
+        match result.unwrap_err() {
+            BackendError::OutOfGas {} => {}
+            err => panic!("Unexpected error: {err:?}"),
+        }
+        // the call was stopped when reaching the limit
+        assert_eq!(gas_info.externally_used, gas_limit);
+        let gas_after = contract.borrow_mut().get_gas_left();
+        assert_eq!(gas_before - gas_after, gas_limit);
+    }
+
+    #[test]
+    fn routing_querier_does_not_keep_instances_alive() {
+        let router = QueryRouter::new();
+        let contract = routed_hackatom(&router, "contract", &[]);
+        let querier = RoutingQuerier::new(&router, MockQuerier::new(&[]));
+
+        // the instance's querier holds the router, which must not hold the instance
+        let weak = Rc::downgrade(&contract);
+        drop(contract);
+        assert!(weak.upgrade().is_none());
+
+        let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: "contract".to_string(),
+            msg: br#"{"verifier":{}}"#.into(),
+        });
+        let (result, _gas_info) =
+            querier.query_raw(&to_json_vec(&request).unwrap(), DEFAULT_QUERY_GAS_LIMIT);
+        match result.unwrap() {
+            SystemResult::Err(SystemError::NoSuchContract { addr }) => assert_eq!(addr, "contract"),
+            res => panic!("Unexpected result: {res:?}"),
+        }
+    }
+
+    #[test]
+    fn routing_querier_rejects_reentrancy() {
+        let router = QueryRouter::new();
+        let contract = routed_hackatom(&router, "contract", &[]);
+
+        // hackatom's recurse query queries the contract itself
+        let mut instance = contract.borrow_mut();
+        let msg = br#"{"recurse":{"depth":1,"work":0}}"#;
+        let err = call_query(&mut instance, &routed_env("contract"), msg)
+            .unwrap()
+            .unwrap_err();
+        assert!(
+            err.contains("Unsupported query type: re-entrant query into contract contract"),
+            "Unexpected error: {err}"
+        );
+    }
 }
@@ -1,9 +1,12 @@
-use std::collections::BTreeMap;
 #[cfg(feature = "iterator")]
 use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
 #[cfg(feature = "iterator")]
 use std::ops::{Bound, RangeBounds};
+use std::sync::Mutex;
 
+use cosmwasm_std::testing::{parse_state_export, state_export};
+use cosmwasm_std::StdResult;
 #[cfg(feature = "iterator")]
 use cosmwasm_std::{Order, Record};
 
@@ -24,11 +27,23 @@ struct Iter {
     position: usize,
 }
 
+/// The storage keys read and written while recording accesses
+/// (see [`MockStorage::record_accesses`]).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct StorageAccesses {
+    /// Keys read via `get` or returned by an iterator
+    pub reads: BTreeSet<Vec<u8>>,
+    /// Keys set or removed
+    pub writes: BTreeSet<Vec<u8>>,
+}
+
 #[derive(Default, Debug)]
 pub struct MockStorage {
     data: BTreeMap<Vec<u8>, Vec<u8>>,
     #[cfg(feature = "iterator")]
     iterators: HashMap<u32, Iter>,
+    /// `Some` if accesses are recorded. This uses a mutex since `get` only gets a shared reference.
+    accesses: Option<Mutex<StorageAccesses>>,
 }
 
 impl MockStorage {
@@ -36,6 +51,51 @@ impl MockStorage {
         MockStorage::default()
     }
 
+    /// Starts recording all storage keys that are read or written from now on.
+    /// Previously recorded accesses are discarded.
+    pub fn record_accesses(&mut self) {
+        self.accesses = Some(Mutex::new(StorageAccesses::default()));
+    }
+
+    /// Returns the accesses recorded since the last call of [`MockStorage::record_accesses`]
+    /// or `None` if accesses are not recorded.
+    pub fn accesses(&self) -> Option<StorageAccesses> {
+        self.accesses
+            .as_ref()
+            .map(|accesses| accesses.lock().unwrap().clone())
+    }
+
+    fn record_read(&self, key: &[u8]) {
+        if let Some(accesses) = &self.accesses {
+            accesses.lock().unwrap().reads.insert(key.to_vec());
+        }
+    }
+
+    fn record_write(&self, key: &[u8]) {
+        if let Some(accesses) = &self.accesses {
+            accesses.lock().unwrap().writes.insert(key.to_vec());
+        }
+    }
+
+    /// Creates a storage from a contract state export as produced by
+    /// `wasmd query wasm contract-state all <address>` or found in genesis files.
+    /// See [`cosmwasm_std::testing::parse_state_export`] for details on the format.
+    ///
+    /// This allows running contracts against state exported from a chain.
+    pub fn from_genesis_export(json: &str) -> StdResult<Self> {
+        Ok(parse_state_export(json)?.into_iter().collect())
+    }
+
+    /// Exports the storage content in the contract state export format
+    /// (see [`MockStorage::from_genesis_export`]).
+    pub fn to_genesis_export(&self) -> String {
+        state_export(
+            self.data
+                .iter()
+                .map(|(key, value)| (key.as_slice(), value.as_slice())),
+        )
+    }
+
     #[cfg(feature = "iterator")]
     pub fn all(&mut self, iterator_id: u32) -> BackendResult<Vec<Record>> {
         let mut out: Vec<Record> = Vec::new();
@@ -58,8 +118,30 @@ impl MockStorage {
     }
 }
 
+/// Bulk-loads entries into the storage without charging gas.
+///
+/// This is useful to prepare large data sets for tests, e.g. for paginated queries.
+/// Existing values are overwritten.
+impl<K: Into<Vec<u8>>, V: Into<Vec<u8>>> Extend<(K, V)> for MockStorage {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.data.extend(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+    }
+}
+
+impl<K: Into<Vec<u8>>, V: Into<Vec<u8>>> FromIterator<(K, V)> for MockStorage {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut storage = MockStorage::new();
+        storage.extend(iter);
+        storage
+    }
+}
+
 impl Storage for MockStorage {
     fn get(&self, key: &[u8]) -> BackendResult<Option<Vec<u8>>> {
+        self.record_read(key);
         let gas_info = GasInfo::with_externally_used(key.len() as u64);
         (Ok(self.data.get(key).cloned()), gas_info)
     }
@@ -120,17 +202,22 @@ impl Storage for MockStorage {
             } else {
                 (None, GasInfo::with_externally_used(GAS_COST_LAST_ITERATION))
             };
+        if let Some((key, _)) = &value {
+            self.record_read(key);
+        }
 
         (Ok(value), gas_info)
     }
 
     fn set(&mut self, key: &[u8], value: &[u8]) -> BackendResult<()> {
+        self.record_write(key);
         self.data.insert(key.to_vec(), value.to_vec());
         let gas_info = GasInfo::with_externally_used((key.len() + value.len()) as u64);
         (Ok(()), gas_info)
     }
 
     fn remove(&mut self, key: &[u8]) -> BackendResult<()> {
+        self.record_write(key);
         self.data.remove(key);
         let gas_info = GasInfo::with_externally_used(key.len() as u64);
         (Ok(()), gas_info)
@@ -169,6 +256,148 @@ mod tests {
         assert_eq!(None, store.get(b"food").0.unwrap());
     }
 
+    #[test]
+    fn extend_works() {
+        let mut store: MockStorage = [(b"foo".to_vec(), b"bar".to_vec())].into_iter().collect();
+        assert_eq!(Some(b"bar".to_vec()), store.get(b"foo").0.unwrap());
+
+        store.extend([("foo", "baz"), ("food", "bank")]);
+        assert_eq!(Some(b"baz".to_vec()), store.get(b"foo").0.unwrap());
+        assert_eq!(Some(b"bank".to_vec()), store.get(b"food").0.unwrap());
+    }
+
+    #[test]
+    fn genesis_export_works() {
+        let json = include_str!("../../testdata/hackatom_state_export.json");
+        let store = MockStorage::from_genesis_export(json).unwrap();
+        let config = store.get(b"config").0.unwrap().unwrap();
+        assert!(config.starts_with(br#"{"verifier":"cosmwasm1"#));
+
+        // round trip
+        let exported = store.to_genesis_export();
+        let reimported = MockStorage::from_genesis_export(&exported).unwrap();
+        assert_eq!(reimported.data, store.data);
+
+        // base64 keys
+        let json = r#"{"models":[{"key":"Y29uZmln","value":"e30="}]}"#;
+        let store = MockStorage::from_genesis_export(json).unwrap();
+        assert_eq!(store.get(b"config").0.unwrap(), Some(b"{}".to_vec()));
+        assert_eq!(
+            store.to_genesis_export(),
+            r#"{"models":[{"key":"636F6E666967","value":"e30="}]}"#
+        );
+
+        MockStorage::from_genesis_export(r#"{"models":[{"key":"A","value":"e30="}]}"#).unwrap_err();
+    }
+
+    #[test]
+    fn genesis_export_can_be_used_by_contract() {
+        use crate::testing::{mock_env, mock_instance_options, MockApi, MockQuerier};
+        use crate::{call_query, Backend, Instance};
+
+        static HACKATOM: &[u8] = include_bytes!("../../testdata/hackatom.wasm");
+
+        let json = include_str!("../../testdata/hackatom_state_export.json");
+        let backend = Backend {
+            api: MockApi::default(),
+            storage: MockStorage::from_genesis_export(json).unwrap(),
+            querier: MockQuerier::new(&[]),
+        };
+        let (options, memory_limit) = mock_instance_options();
+        let mut instance = Instance::from_code(HACKATOM, backend, options, memory_limit).unwrap();
+
+        let res = call_query(&mut instance, &mock_env(), br#"{"verifier":{}}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            res.as_slice(),
+            br#"{"verifier":"cosmwasm13ry74e5wkvqt99c690kfuk3xlaqhnltxr44hmps7f3j40wd2ac2q92x34s"}"#
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn extend_with_many_entries_can_be_paginated() {
+        const ENTRIES: u32 = 1000;
+        const PAGE_SIZE: usize = 100;
+
+        let mut store: MockStorage = (0..ENTRIES)
+            .map(|i| (i.to_be_bytes(), i.to_string()))
+            .collect();
+
+        let mut collected = Vec::<Record>::new();
+        let mut pages = 0;
+        loop {
+            // start after the last key of the previous page
+            let start = collected.last().map(|(key, _)| {
+                let mut start = key.clone();
+                start.push(0);
+                start
+            });
+            let id = store
+                .scan(start.as_deref(), None, Order::Ascending)
+                .0
+                .unwrap();
+            let page: Vec<Record> = (0..PAGE_SIZE)
+                .map_while(|_| store.next(id).0.unwrap())
+                .collect();
+            if page.is_empty() {
+                break;
+            }
+            pages += 1;
+            collected.extend(page);
+        }
+
+        assert_eq!(pages, ENTRIES as usize / PAGE_SIZE);
+        assert_eq!(collected.len(), ENTRIES as usize);
+        // complete, in order and without duplicates
+        for (i, (key, value)) in (0..ENTRIES).zip(collected) {
+            assert_eq!(key, i.to_be_bytes());
+            assert_eq!(value, i.to_string().into_bytes());
+        }
+    }
+
+    #[test]
+    fn record_accesses_works() {
+        let mut store = MockStorage::new();
+        store.set(b"foo", b"bar").0.unwrap();
+        assert_eq!(store.accesses(), None);
+
+        store.record_accesses();
+        assert_eq!(store.accesses(), Some(StorageAccesses::default()));
+
+        store.get(b"foo").0.unwrap();
+        store.get(b"missing").0.unwrap();
+        store.set(b"new", b"value").0.unwrap();
+        store.remove(b"foo").0.unwrap();
+        assert_eq!(
+            store.accesses().unwrap(),
+            StorageAccesses {
+                reads: BTreeSet::from([b"foo".to_vec(), b"missing".to_vec()]),
+                writes: BTreeSet::from([b"foo".to_vec(), b"new".to_vec()]),
+            }
+        );
+
+        // restarting discards previous accesses
+        store.record_accesses();
+        assert_eq!(store.accesses(), Some(StorageAccesses::default()));
+    }
+
+    #[test]
+    #[cfg(feature = "iterator")]
+    fn record_accesses_works_for_iterators() {
+        let mut store: MockStorage = [("a", "1"), ("b", "2"), ("c", "3")].into_iter().collect();
+        store.record_accesses();
+
+        let id = store.scan(None, None, Order::Ascending).0.unwrap();
+        store.next(id).0.unwrap();
+        store.next(id).0.unwrap();
+        assert_eq!(
+            store.accesses().unwrap().reads,
+            BTreeSet::from([b"a".to_vec(), b"b".to_vec()])
+        );
+    }
+
     #[test]
     fn delete() {
         let mut store = MockStorage::new();
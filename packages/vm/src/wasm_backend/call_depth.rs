@@ -0,0 +1,303 @@
+use std::fmt;
+use std::sync::Mutex;
+use wasmer::wasmparser::{BlockType as WpTypeOrFuncType, Operator};
+use wasmer::{
+    AsStoreMut, ExportIndex, FunctionMiddleware, GlobalInit, GlobalType, Instance,
+    LocalFunctionIndex, MiddlewareError, MiddlewareReaderState, ModuleMiddleware, Mutability, Type,
+    Value,
+};
+use wasmer_types::{GlobalIndex, ModuleInfo};
+
+const CALL_DEPTH_GLOBAL: &str = "cosmwasm_call_depth";
+const MAX_CALL_DEPTH_GLOBAL: &str = "cosmwasm_max_call_depth";
+const CALL_DEPTH_EXCEEDED_GLOBAL: &str = "cosmwasm_call_depth_exceeded";
+
+#[derive(Clone)]
+struct CallDepthGlobalIndexes {
+    /// The number of nested Wasm function calls currently running
+    call_depth: GlobalIndex,
+    /// The maximum allowed value of `call_depth`, compared as an unsigned integer
+    max_call_depth: GlobalIndex,
+    /// A boolean indicating whether the limit was exceeded, represented as an i32 global:
+    ///   * 0: the limit was not exceeded
+    ///   * 1: the limit was exceeded
+    call_depth_exceeded: GlobalIndex,
+}
+
+impl fmt::Debug for CallDepthGlobalIndexes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallDepthGlobalIndexes")
+            .field("call_depth", &self.call_depth)
+            .field("max_call_depth", &self.max_call_depth)
+            .field("call_depth_exceeded", &self.call_depth_exceeded)
+            .finish()
+    }
+}
+
+/// The module-level middleware limiting the nesting depth of Wasm function calls.
+///
+/// Every `call` and `call_indirect` increments a counter before and decrements it after
+/// the call. If the counter exceeds the maximum, the execution traps and the
+/// `cosmwasm_call_depth_exceeded` global is set, such that the trap can be told apart from
+/// others. This makes deep recursion fail deterministically instead of depending on the
+/// native stack size of the host.
+///
+/// The maximum is stored in a global as well, which allows changing it for each instance
+/// without recompiling the module (see [`set_max_call_depth`]). It is unlimited by default.
+///
+/// # Panic
+///
+/// An instance of `CallDepthLimit` should _not_ be shared among different
+/// modules, since it tracks module-specific information like the
+/// global indexes. Attempts to use a `CallDepthLimit` instance from
+/// multiple modules will result in a panic.
+#[derive(Debug, Default)]
+pub struct CallDepthLimit {
+    /// The global indexes for the call depth state.
+    global_indexes: Mutex<Option<CallDepthGlobalIndexes>>,
+}
+
+/// The function-level call depth middleware.
+#[derive(Debug)]
+pub struct FunctionCallDepthLimit {
+    /// The global indexes for the call depth state.
+    global_indexes: CallDepthGlobalIndexes,
+}
+
+impl ModuleMiddleware for CallDepthLimit {
+    /// Generates a `FunctionMiddleware` for a given function.
+    fn generate_function_middleware(&self, _: LocalFunctionIndex) -> Box<dyn FunctionMiddleware> {
+        Box::new(FunctionCallDepthLimit {
+            global_indexes: self.global_indexes.lock().unwrap().clone().unwrap(),
+        })
+    }
+
+    /// Transforms a `ModuleInfo` struct in-place. This is called before application on functions begins.
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) -> Result<(), MiddlewareError> {
+        let mut global_indexes = self.global_indexes.lock().unwrap();
+
+        if global_indexes.is_some() {
+            panic!("CallDepthLimit::transform_module_info: Attempting to use a `CallDepthLimit` middleware from multiple modules.");
+        }
+
+        let mut add_global = |name: &str, initial_value: i32| {
+            let index = module_info
+                .globals
+                .push(GlobalType::new(Type::I32, Mutability::Var));
+            module_info
+                .global_initializers
+                .push(GlobalInit::I32Const(initial_value));
+            module_info
+                .exports
+                .insert(name.to_string(), ExportIndex::Global(index));
+            index
+        };
+
+        *global_indexes = Some(CallDepthGlobalIndexes {
+            call_depth: add_global(CALL_DEPTH_GLOBAL, 0),
+            // u32::MAX, i.e. unlimited
+            max_call_depth: add_global(MAX_CALL_DEPTH_GLOBAL, -1),
+            call_depth_exceeded: add_global(CALL_DEPTH_EXCEEDED_GLOBAL, 0),
+        });
+
+        Ok(())
+    }
+}
+
+impl FunctionMiddleware for FunctionCallDepthLimit {
+    fn feed<'a>(
+        &mut self,
+        operator: Operator<'a>,
+        state: &mut MiddlewareReaderState<'a>,
+    ) -> Result<(), MiddlewareError> {
+        // Tail calls and function references are rejected by the gatekeeper,
+        // so those are all the operators calling a function.
+        let is_call = matches!(
+            operator,
+            Operator::Call { .. } | Operator::CallIndirect { .. }
+        );
+        if !is_call {
+            state.push_operator(operator);
+            return Ok(());
+        }
+
+        let call_depth = self.global_indexes.call_depth.as_u32();
+        state.extend(&[
+            // globals[call_depth] += 1;
+            Operator::GlobalGet {
+                global_index: call_depth,
+            },
+            Operator::I32Const { value: 1 },
+            Operator::I32Add,
+            Operator::GlobalSet {
+                global_index: call_depth,
+            },
+            // if unsigned(globals[call_depth]) > unsigned(globals[max_call_depth]) { throw(); }
+            Operator::GlobalGet {
+                global_index: call_depth,
+            },
+            Operator::GlobalGet {
+                global_index: self.global_indexes.max_call_depth.as_u32(),
+            },
+            Operator::I32GtU,
+            Operator::If {
+                blockty: WpTypeOrFuncType::Empty,
+            },
+            Operator::I32Const { value: 1 },
+            Operator::GlobalSet {
+                global_index: self.global_indexes.call_depth_exceeded.as_u32(),
+            },
+            Operator::Unreachable,
+            Operator::End,
+        ]);
+        state.push_operator(operator);
+        // The call returned normally (traps abort the whole execution)
+        state.extend(&[
+            // globals[call_depth] -= 1;
+            Operator::GlobalGet {
+                global_index: call_depth,
+            },
+            Operator::I32Const { value: 1 },
+            Operator::I32Sub,
+            Operator::GlobalSet {
+                global_index: call_depth,
+            },
+        ]);
+
+        Ok(())
+    }
+}
+
+fn set_global(store: &mut impl AsStoreMut, instance: &Instance, name: &str, value: u32) {
+    // Modules compiled without the middleware do not have the globals
+    if let Ok(global) = instance.exports.get_global(name) {
+        global
+            .set(store, Value::I32(value as i32))
+            .expect("Can't set call depth global");
+    }
+}
+
+/// Sets the maximum nesting depth of Wasm function calls for the given instance.
+/// `None` means unlimited, which leaves the limit to the native stack size of the host.
+pub fn set_max_call_depth(
+    store: &mut impl AsStoreMut,
+    instance: &Instance,
+    max_call_depth: Option<u32>,
+) {
+    set_global(
+        store,
+        instance,
+        MAX_CALL_DEPTH_GLOBAL,
+        max_call_depth.unwrap_or(u32::MAX),
+    );
+}
+
+/// Resets the call depth state of the given instance.
+/// This needs to be done before calling into the instance, since a trap leaves the
+/// call depth counter at the depth at which it occurred.
+pub fn reset_call_depth(store: &mut impl AsStoreMut, instance: &Instance) {
+    set_global(store, instance, CALL_DEPTH_GLOBAL, 0);
+    set_global(store, instance, CALL_DEPTH_EXCEEDED_GLOBAL, 0);
+}
+
+/// Returns `true` if the last call into the given instance trapped because the maximum
+/// call depth was exceeded.
+pub fn call_depth_exceeded(store: &mut impl AsStoreMut, instance: &Instance) -> bool {
+    instance
+        .exports
+        .get_global(CALL_DEPTH_EXCEEDED_GLOBAL)
+        .map(|global| matches!(global.get(store), Value::I32(1)))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use wasmer::{imports, CompilerConfig, Engine, Module, Store, TypedFunction};
+
+    use crate::wasm_backend::make_compiler_config;
+
+    /// `recurse(n)` calls itself `n` times, so the maximum call depth is `n + 1`
+    /// when called from the host.
+    const RECURSION: &str = r#"(module
+        (type $t (func (param i32) (result i32)))
+        (table 1 funcref)
+        (elem (i32.const 0) $recurse_indirect)
+        (func $recurse (export "recurse") (param i32) (result i32)
+            local.get 0
+            i32.eqz
+            if (result i32)
+                i32.const 0
+            else
+                local.get 0
+                i32.const 1
+                i32.sub
+                call $recurse
+                i32.const 1
+                i32.add
+            end)
+        (func $recurse_indirect (export "recurse_indirect") (param i32) (result i32)
+            local.get 0
+            i32.eqz
+            if (result i32)
+                i32.const 0
+            else
+                local.get 0
+                i32.const 1
+                i32.sub
+                i32.const 0
+                call_indirect (type $t)
+                i32.const 1
+                i32.add
+            end)
+    )"#;
+
+    fn instantiate() -> (Store, Instance) {
+        let wasm = wat::parse_str(RECURSION).unwrap();
+
+        let mut compiler = make_compiler_config();
+        compiler.push_middleware(Arc::new(CallDepthLimit::default()));
+        let engine: Engine = compiler.into();
+        let mut store = Store::new(engine);
+        let module = Module::new(&store, wasm).unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        (store, instance)
+    }
+
+    #[test]
+    fn call_depth_limit_works() {
+        let (mut store, instance) = instantiate();
+
+        for name in ["recurse", "recurse_indirect"] {
+            let recurse: TypedFunction<i32, i32> = instance
+                .exports
+                .get_function(name)
+                .unwrap()
+                .typed(&store)
+                .unwrap();
+
+            // unlimited by default
+            assert_eq!(recurse.call(&mut store, 1000).unwrap(), 1000);
+            assert!(!call_depth_exceeded(&mut store, &instance));
+
+            set_max_call_depth(&mut store, &instance, Some(100));
+
+            // N - 1 and N nested calls are fine
+            assert_eq!(recurse.call(&mut store, 99).unwrap(), 99);
+            assert_eq!(recurse.call(&mut store, 100).unwrap(), 100);
+            assert!(!call_depth_exceeded(&mut store, &instance));
+
+            // N + 1 nested calls trap
+            recurse.call(&mut store, 101).unwrap_err();
+            assert!(call_depth_exceeded(&mut store, &instance));
+
+            // the counter is not reset by the trap
+            reset_call_depth(&mut store, &instance);
+            assert!(!call_depth_exceeded(&mut store, &instance));
+            assert_eq!(recurse.call(&mut store, 100).unwrap(), 100);
+
+            set_max_call_depth(&mut store, &instance, None);
+        }
+    }
+}
@@ -7,6 +7,7 @@ use wasmer::{
 
 use crate::size::Size;
 
+use super::call_depth::CallDepthLimit;
 use super::gatekeeper::Gatekeeper;
 use super::limiting_tunables::LimitingTunables;
 use super::metering::{is_accounting, Metering};
@@ -60,11 +61,19 @@ pub fn make_compiling_engine(memory_limit: Option<Size>) -> Engine {
     let gas_limit = 0;
     let deterministic = Arc::new(Gatekeeper::default());
     let metering = Arc::new(Metering::new(gas_limit, cost));
+    let call_depth_limit = Arc::new(CallDepthLimit::default());
 
     let mut compiler = make_compiler_config();
+    // Float operations are deterministic except for the bit pattern of NaN results,
+    // which we canonicalize. Converting floats to integers does not need any configuration:
+    // the `trunc` instructions trap for NaN and out of range values and the `trunc_sat`
+    // instructions (non-trapping float-to-int conversions) saturate and map NaN to 0.
+    // Which one is used is decided by the compiler of the contract.
     compiler.canonicalize_nans(true);
     compiler.push_middleware(deterministic);
     compiler.push_middleware(metering);
+    // After metering, such that the injected code does not cost gas
+    compiler.push_middleware(call_depth_limit);
     let mut engine: Engine = compiler.into();
     if let Some(limit) = memory_limit {
         let base = BaseTunables::for_target(&Target::default());
@@ -91,6 +100,26 @@ fn limit_to_pages(limit: Size) -> Pages {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use wasmer::{imports, Instance as WasmerInstance, RuntimeError, Store, TrapCode, Value};
+    use wasmer_middlewares::metering::set_remaining_points;
+
+    use crate::wasm_backend::compile;
+
+    /// Converts the given float to an i32 using all four float-to-int instructions
+    const FLOAT_TO_INT: &str = r#"(module
+        (func (export "trunc_f32") (param f32) (result i32)
+            local.get 0
+            i32.trunc_f32_s)
+        (func (export "trunc_sat_f32") (param f32) (result i32)
+            local.get 0
+            i32.trunc_sat_f32_s)
+        (func (export "trunc_f64") (param f64) (result i32)
+            local.get 0
+            i32.trunc_f64_s)
+        (func (export "trunc_sat_f64") (param f64) (result i32)
+            local.get 0
+            i32.trunc_sat_f64_s)
+    )"#;
 
     #[test]
     fn cost_works() {
@@ -118,4 +147,51 @@ mod tests {
         assert_eq!(limit_to_pages(Size::gibi(5)), Pages(65536));
         assert_eq!(limit_to_pages(Size::new(usize::MAX)), Pages(65536));
     }
+
+    #[test]
+    fn float_to_int_conversions_trap_or_saturate() {
+        let wasm = wat::parse_str(FLOAT_TO_INT).unwrap();
+        let engine = make_compiling_engine(None);
+        let module = compile(&engine, &wasm).unwrap();
+        let mut store = Store::new(engine);
+        let instance = WasmerInstance::new(&mut store, &module, &imports! {}).unwrap();
+        set_remaining_points(&mut store, &instance, 1_000_000);
+
+        let mut call = |name: &str, arg: Value| -> Result<i32, RuntimeError> {
+            let function = instance.exports.get_function(name).unwrap();
+            function
+                .call(&mut store, &[arg])
+                .map(|result| result[0].unwrap_i32())
+        };
+
+        for (arg32, arg64) in [
+            (Value::F32(f32::NAN), Value::F64(f64::NAN)),
+            (Value::F32(-f32::NAN), Value::F64(-f64::NAN)),
+        ] {
+            // trapping conversions trap
+            let err = call("trunc_f32", arg32.clone()).unwrap_err();
+            assert_eq!(err.to_trap(), Some(TrapCode::BadConversionToInteger));
+            let err = call("trunc_f64", arg64.clone()).unwrap_err();
+            assert_eq!(err.to_trap(), Some(TrapCode::BadConversionToInteger));
+            // saturating conversions map NaN to 0
+            assert_eq!(call("trunc_sat_f32", arg32).unwrap(), 0);
+            assert_eq!(call("trunc_sat_f64", arg64).unwrap(), 0);
+        }
+
+        // out of range
+        let err = call("trunc_f32", Value::F32(f32::INFINITY)).unwrap_err();
+        assert_eq!(err.to_trap(), Some(TrapCode::IntegerOverflow));
+        let err = call("trunc_f64", Value::F64(-1e10)).unwrap_err();
+        assert_eq!(err.to_trap(), Some(TrapCode::IntegerOverflow));
+        let result = call("trunc_sat_f32", Value::F32(f32::INFINITY)).unwrap();
+        assert_eq!(result, i32::MAX);
+        let result = call("trunc_sat_f64", Value::F64(-1e10)).unwrap();
+        assert_eq!(result, i32::MIN);
+
+        // in range
+        assert_eq!(call("trunc_f32", Value::F32(-3.9)).unwrap(), -3);
+        assert_eq!(call("trunc_sat_f32", Value::F32(-3.9)).unwrap(), -3);
+        assert_eq!(call("trunc_f64", Value::F64(7.5)).unwrap(), 7);
+        assert_eq!(call("trunc_sat_f64", Value::F64(7.5)).unwrap(), 7);
+    }
 }
@@ -1,3 +1,4 @@
+mod call_depth;
 mod compile;
 mod engine;
 mod gatekeeper;
@@ -7,5 +8,6 @@ mod metering;
 #[cfg(test)]
 pub use engine::make_compiler_config;
 
+pub use call_depth::{call_depth_exceeded, reset_call_depth, set_max_call_depth};
 pub use compile::compile;
 pub use engine::{make_compiling_engine, make_runtime_engine, COST_FUNCTION_HASH};